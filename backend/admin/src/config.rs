@@ -0,0 +1,77 @@
+use config::{Config, Environment};
+use serde::Deserialize;
+
+/// Connection settings, read from the same environment variables the services
+/// use so the CLI can run next to them with the deployment's `.env`.
+#[derive(Debug, Deserialize)]
+pub struct AdminConfig {
+    pub auth_database_url: Option<String>,
+    pub routes_database_url: Option<String>,
+    pub jwt_secret: Option<String>,
+    #[serde(default = "default_routes_url")]
+    pub routes_url: String,
+    #[serde(default = "default_nats_url")]
+    pub nats_url: String,
+    #[serde(default = "default_minio_endpoint")]
+    pub minio_endpoint: String,
+    #[serde(default = "default_minio_credential")]
+    pub minio_access_key: String,
+    #[serde(default = "default_minio_credential")]
+    pub minio_secret_key: String,
+    /// Bucket of processed route photos, `MINIO_BUCKET` of the photo worker.
+    #[serde(default = "default_minio_bucket")]
+    pub minio_bucket: String,
+    #[serde(default = "default_export_bucket")]
+    pub export_bucket: String,
+}
+
+fn default_routes_url() -> String {
+    "http://localhost:8088".to_string()
+}
+
+fn default_nats_url() -> String {
+    "nats://localhost:4222".to_string()
+}
+
+fn default_minio_endpoint() -> String {
+    "http://localhost:9000".to_string()
+}
+
+fn default_minio_credential() -> String {
+    "minioadmin".to_string()
+}
+
+fn default_minio_bucket() -> String {
+    "photos".to_string()
+}
+
+fn default_export_bucket() -> String {
+    "exports".to_string()
+}
+
+impl AdminConfig {
+    pub fn from_env() -> anyhow::Result<Self> {
+        Ok(Config::builder()
+            .add_source(Environment::default())
+            .build()?
+            .try_deserialize()?)
+    }
+
+    pub fn auth_database_url(&self) -> anyhow::Result<&str> {
+        self.auth_database_url
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("AUTH_DATABASE_URL is not set"))
+    }
+
+    pub fn routes_database_url(&self) -> anyhow::Result<&str> {
+        self.routes_database_url
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("ROUTES_DATABASE_URL is not set"))
+    }
+
+    pub fn jwt_secret(&self) -> anyhow::Result<&str> {
+        self.jwt_secret
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("JWT_SECRET is not set"))
+    }
+}
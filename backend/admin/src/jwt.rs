@@ -0,0 +1,43 @@
+use guide_helper_auth_core::JwtService;
+use rand::{distributions::Alphanumeric, Rng};
+use uuid::Uuid;
+
+/// Signs a short-lived admin access token so the CLI can call admin endpoints
+/// of the routes service without a stored account.
+pub fn admin_token(secret: &str) -> anyhow::Result<String> {
+    Ok(JwtService::new(secret.to_string())
+        .with_access_minutes(5)
+        .generate_access_token(Uuid::nil(), "guide-helper-admin@localhost".to_string(), "admin")?)
+}
+
+/// Generates a random alphanumeric secret suitable for `JWT_SECRET`.
+pub fn generate_secret(length: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_secret_length_and_charset() {
+        let secret = generate_secret(64);
+        assert_eq!(secret.len(), 64);
+        assert!(secret.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_ne!(secret, generate_secret(64));
+    }
+
+    #[test]
+    fn test_admin_token_has_admin_access_claims() {
+        let token = admin_token("secret").unwrap();
+
+        let user = JwtService::new("secret".to_string()).authenticate(&token).unwrap();
+
+        assert_eq!(user.user_id, Uuid::nil());
+        assert_eq!(user.role, "admin");
+    }
+}
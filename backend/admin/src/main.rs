@@ -0,0 +1,242 @@
+mod config;
+mod jwt;
+mod photo_queue;
+mod photos;
+mod purge;
+mod seed;
+mod storage;
+mod users;
+
+use anyhow::Context;
+use chrono::{Duration, Utc};
+use clap::{Parser, Subcommand};
+use guide_helper_api::client::ApiClient;
+use guide_helper_api::photo::PHOTO_MAX_DELIVER;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use tracing_subscriber::EnvFilter;
+
+use crate::config::AdminConfig;
+use crate::purge::UserBuckets;
+use crate::storage::S3Bucket;
+use crate::users::CreateAdminOutcome;
+
+/// Operator tasks for a guide_helper deployment.
+///
+/// Database URLs, `JWT_SECRET`, `ROUTES_URL`, `NATS_URL` and the MinIO
+/// settings are read from the environment.
+#[derive(Parser)]
+#[command(name = "guide-helper-admin", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create an admin account, or promote an existing account to admin.
+    CreateAdmin {
+        #[arg(long)]
+        email: String,
+        /// Password for a new account (ignored when promoting).
+        #[arg(long, env = "ADMIN_PASSWORD", hide_env_values = true)]
+        password: String,
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Generate a new JWT secret to roll out to the auth and routes services.
+    RotateJwtSecret {
+        #[arg(long, default_value_t = 64)]
+        length: usize,
+    },
+    /// Rebuild the search index through the routes service.
+    ReindexSearch,
+    /// Re-enqueue photo processing for points whose photos failed.
+    RequeuePhotos {
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Inspect or recover the photo processing queue.
+    PhotoQueue {
+        #[command(subcommand)]
+        action: PhotoQueueAction,
+    },
+    /// Permanently remove soft-deleted users and everything they own,
+    /// including their photos and data exports in MinIO.
+    PurgeDeleted {
+        /// Only purge users deleted at least this many days ago.
+        #[arg(long, default_value_t = 30)]
+        older_than_days: i64,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Fill the databases with reproducible demo data for local dev and staging.
+    Seed {
+        /// Random seed; the same seed always produces the same data.
+        #[arg(long, default_value_t = 42)]
+        seed: u64,
+        /// City to place routes in: moscow, spb or kazan.
+        #[arg(long, default_value = "moscow")]
+        city: String,
+        #[arg(long, default_value_t = 10)]
+        users: usize,
+        #[arg(long, default_value_t = 30)]
+        routes: usize,
+        /// Share of routes published on explore, from 0 to 1.
+        #[arg(long, default_value_t = 0.0)]
+        public_ratio: f64,
+        /// Chat conversations per user, ten messages each.
+        #[arg(long, default_value_t = 0)]
+        conversations: usize,
+        /// Password shared by all demo accounts.
+        #[arg(long, default_value = "demo-password")]
+        password: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum PhotoQueueAction {
+    /// Show queued, in-flight and dead photo tasks.
+    Status,
+    /// Re-enqueue tasks the workers gave up on, e.g. after an outage.
+    Redrive {
+        /// Also run while tasks are in flight; those may be processed twice.
+        #[arg(long)]
+        force: bool,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Drop every queued task, processed or not.
+    Purge {
+        /// Required, as photos of dropped tasks stay pending until re-enqueued.
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")))
+        .init();
+
+    let cli = Cli::parse();
+    let config = AdminConfig::from_env().context("failed to load configuration from environment")?;
+
+    match cli.command {
+        Command::CreateAdmin { email, password, name } => {
+            let pool = connect(config.auth_database_url()?).await?;
+            match users::create_admin(&pool, &email, &password, name.as_deref()).await? {
+                CreateAdminOutcome::Created(id) => println!("created admin {} ({})", email, id),
+                CreateAdminOutcome::Promoted(id) => println!("promoted {} ({}) to admin", email, id),
+            }
+        }
+        Command::RotateJwtSecret { length } => {
+            if length < 32 {
+                anyhow::bail!("JWT secret must be at least 32 characters");
+            }
+            println!("{}", jwt::generate_secret(length));
+            eprintln!("Set this value as JWT_SECRET for both the auth and routes services and restart them.");
+            eprintln!("Existing tokens stop validating, so every user has to sign in again.");
+            eprintln!("Share links stop opening too, unless the routes service has its own SHARE_LINK_SECRET.");
+        }
+        Command::ReindexSearch => {
+            let token = jwt::admin_token(config.jwt_secret()?)?;
+            let response = ApiClient::new(&config.routes_url)
+                .with_token(token)
+                .reindex_search()
+                .await
+                .context("reindex request failed")?;
+            println!("indexed {} routes", response.indexed);
+        }
+        Command::RequeuePhotos { dry_run } => {
+            let pool = connect(config.routes_database_url()?).await?;
+            let nats_client = if dry_run {
+                None
+            } else {
+                Some(
+                    async_nats::connect(&config.nats_url)
+                        .await
+                        .context("failed to connect to NATS")?,
+                )
+            };
+            let tasks = photos::requeue_failed_photos(&pool, nats_client.as_ref(), dry_run).await?;
+            let points: usize = tasks.iter().map(|t| t.point_indices.len()).sum();
+            let verb = if dry_run { "would re-enqueue" } else { "re-enqueued" };
+            println!("{} {} photos across {} routes", verb, points, tasks.len());
+            if let Some(client) = nats_client {
+                client.flush().await?;
+            }
+        }
+        Command::PhotoQueue { action } => {
+            let nats_client = async_nats::connect(&config.nats_url)
+                .await
+                .context("failed to connect to NATS")?;
+            match action {
+                PhotoQueueAction::Status => {
+                    let status = photo_queue::status(&nats_client).await?;
+                    println!("messages:    {} ({} bytes)", status.messages, status.bytes);
+                    println!("pending:     {}", status.pending);
+                    println!("in flight:   {}", status.in_flight);
+                    println!("redelivered: {}", status.redelivered);
+                    println!("dead:        {} (gave up after {} deliveries)", status.dead(), PHOTO_MAX_DELIVER);
+                }
+                PhotoQueueAction::Redrive { force, dry_run } => {
+                    let report = photo_queue::redrive(&nats_client, force, dry_run).await?;
+                    let verb = if dry_run { "would re-drive" } else { "re-drove" };
+                    println!("{} {} photo tasks", verb, report.redriven);
+                    if report.skipped > 0 {
+                        println!("skipped {} tasks that could not be read", report.skipped);
+                    }
+                }
+                PhotoQueueAction::Purge { yes } => {
+                    if !yes {
+                        anyhow::bail!("purging drops unprocessed photo tasks; pass --yes to confirm");
+                    }
+                    let purged = photo_queue::purge(&nats_client).await?;
+                    println!("purged {} photo tasks", purged);
+                }
+            }
+        }
+        Command::PurgeDeleted { older_than_days, dry_run } => {
+            let auth_pool = connect(config.auth_database_url()?).await?;
+            let cutoff = Utc::now() - Duration::days(older_than_days.max(0));
+            let user_ids = purge::deleted_users(&auth_pool, cutoff).await?;
+            if dry_run {
+                println!("would purge {} users deleted before {}", user_ids.len(), cutoff);
+                return Ok(());
+            }
+            let routes_pool = connect(config.routes_database_url()?).await?;
+            let bucket = |name: &str| {
+                S3Bucket::new(&config.minio_endpoint, &config.minio_access_key, &config.minio_secret_key, name.to_string())
+            };
+            let buckets = UserBuckets {
+                photos: bucket(&config.minio_bucket).await,
+                exports: bucket(&config.export_bucket).await,
+            };
+            let purged = purge::purge_users(&auth_pool, &routes_pool, &buckets, &user_ids).await?;
+            println!("purged {} users deleted before {}", purged, cutoff);
+        }
+        Command::Seed { seed, city, users, routes, public_ratio, conversations, password } => {
+            let data = seed::generate(&seed::SeedOptions { seed, city, users, routes, public_ratio, conversations })?;
+            let auth_pool = connect(config.auth_database_url()?).await?;
+            let routes_pool = connect(config.routes_database_url()?).await?;
+            let password_hash = users::hash_password(&password)?;
+            let report = seed::insert(&auth_pool, &routes_pool, &data, &password_hash).await?;
+            println!(
+                "seeded {} users, {} routes, {} comments, {} likes, {} ratings, {} chat messages (seed {})",
+                report.users, report.routes, report.comments, report.likes, report.ratings, report.chat_messages, seed
+            );
+            println!("demo accounts: demo1@guide-helper.local .. demo{}@guide-helper.local", data.users.len());
+        }
+    }
+
+    Ok(())
+}
+
+async fn connect(url: &str) -> anyhow::Result<PgPool> {
+    PgPoolOptions::new()
+        .max_connections(2)
+        .connect(url)
+        .await
+        .context("failed to connect to database")
+}
@@ -0,0 +1,147 @@
+use anyhow::Context;
+use async_nats::jetstream::{self, stream::RawMessageErrorKind};
+use guide_helper_api::photo::{PHOTO_CONSUMER_NAME, PHOTO_PROCESS_SUBJECT, PHOTO_STREAM_NAME};
+
+/// Snapshot of the photo processing queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueStatus {
+    /// Messages held by the stream, whatever their state.
+    pub messages: u64,
+    pub bytes: u64,
+    /// Not yet delivered to a worker.
+    pub pending: u64,
+    /// Delivered and awaiting an ack.
+    pub in_flight: u64,
+    pub redelivered: u64,
+    /// Stream sequence of the last message handed to a worker.
+    pub last_delivered_sequence: u64,
+    pub first_sequence: u64,
+}
+
+impl QueueStatus {
+    /// Messages the consumer gave up on after exhausting their deliveries.
+    /// Acked messages leave the work queue, so whatever is neither pending nor
+    /// in flight is dead.
+    pub fn dead(&self) -> u64 {
+        self.messages.saturating_sub(self.pending + self.in_flight)
+    }
+}
+
+/// Result of a re-drive: dead messages published again, and the sequences
+/// that were left alone because they could not be read.
+#[derive(Debug, Default)]
+pub struct RedriveReport {
+    pub redriven: u64,
+    pub skipped: u64,
+}
+
+async fn stream(client: &async_nats::Client) -> anyhow::Result<jetstream::stream::Stream> {
+    jetstream::new(client.clone())
+        .get_stream(PHOTO_STREAM_NAME)
+        .await
+        .with_context(|| format!("failed to open the {} stream", PHOTO_STREAM_NAME))
+}
+
+pub async fn status(client: &async_nats::Client) -> anyhow::Result<QueueStatus> {
+    let mut stream = stream(client).await?;
+    let state = stream.info().await.context("failed to read stream info")?.state.clone();
+    let consumer = stream
+        .consumer_info(PHOTO_CONSUMER_NAME)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to read consumer info: {}", e))?;
+
+    Ok(QueueStatus {
+        messages: state.messages,
+        bytes: state.bytes,
+        pending: consumer.num_pending,
+        in_flight: consumer.num_ack_pending as u64,
+        redelivered: consumer.num_redelivered as u64,
+        last_delivered_sequence: consumer.delivered.stream_sequence,
+        first_sequence: state.first_sequence,
+    })
+}
+
+/// Drops every message from the stream, including ones not yet processed.
+/// Returns how many were removed.
+pub async fn purge(client: &async_nats::Client) -> anyhow::Result<u64> {
+    let stream = stream(client).await?;
+    let response = stream.purge().await.context("failed to purge stream")?;
+    tracing::info!(purged = response.purged, "photo stream purged");
+    Ok(response.purged)
+}
+
+/// Publishes dead messages again as fresh tasks and deletes the originals,
+/// so workers get a full set of delivery attempts at them again.
+///
+/// Delivered messages that are still awaiting an ack look the same as dead
+/// ones, so this refuses to run while any are in flight unless `force` is set.
+pub async fn redrive(client: &async_nats::Client, force: bool, dry_run: bool) -> anyhow::Result<RedriveReport> {
+    let queue = status(client).await?;
+    if queue.in_flight > 0 && !force {
+        anyhow::bail!(
+            "{} photo tasks are being processed right now; wait for them to finish or pass --force",
+            queue.in_flight
+        );
+    }
+
+    let mut report = RedriveReport::default();
+    if queue.dead() == 0 {
+        return Ok(report);
+    }
+
+    let stream = stream(client).await?;
+    let jetstream = jetstream::new(client.clone());
+
+    for sequence in queue.first_sequence..=queue.last_delivered_sequence {
+        let message = match stream.get_raw_message(sequence).await {
+            Ok(message) => message,
+            Err(e) if matches!(e.kind(), RawMessageErrorKind::NoMessageFound) => continue,
+            Err(e) => {
+                tracing::warn!(sequence, error = %e, "failed to read photo task");
+                report.skipped += 1;
+                continue;
+            }
+        };
+        if dry_run {
+            report.redriven += 1;
+            continue;
+        }
+
+        jetstream
+            .publish(PHOTO_PROCESS_SUBJECT, message.payload)
+            .await
+            .context("failed to publish photo task")?
+            .await
+            .context("failed to get NATS publish ack")?;
+        stream
+            .delete_message(sequence)
+            .await
+            .with_context(|| format!("failed to delete re-driven message {}", sequence))?;
+        tracing::info!(sequence, "photo task re-driven");
+        report.redriven += 1;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dead_counts_messages_neither_pending_nor_in_flight() {
+        let status = QueueStatus {
+            messages: 10,
+            bytes: 4096,
+            pending: 4,
+            in_flight: 1,
+            redelivered: 7,
+            last_delivered_sequence: 20,
+            first_sequence: 3,
+        };
+        assert_eq!(status.dead(), 5);
+
+        let drained = QueueStatus { messages: 0, pending: 0, in_flight: 1, ..status };
+        assert_eq!(drained.dead(), 0);
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::Context;
+use guide_helper_api::events;
+use guide_helper_api::photo::{PhotoProcessTask, PHOTO_PROCESS_SUBJECT};
+use guide_helper_domain::route::{PhotoStatus, Route, RoutePoint};
+use sqlx::PgPool;
+
+/// Indices of points whose photo failed processing but still carries the
+/// inline base64 original, i.e. can be processed again.
+pub fn retryable_point_indices(points: &[RoutePoint]) -> Vec<usize> {
+    points
+        .iter()
+        .enumerate()
+        .filter_map(|(i, point)| match &point.photo {
+            Some(photo) if photo.status == PhotoStatus::Failed && photo.original.starts_with("data:") => Some(i),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Re-publishes processing tasks for every route with failed photos, resetting
+/// those photos to `pending`. Returns the tasks that were (or, with `dry_run`,
+/// would have been) enqueued.
+pub async fn requeue_failed_photos(
+    pool: &PgPool,
+    nats_client: Option<&async_nats::Client>,
+    dry_run: bool,
+) -> anyhow::Result<Vec<PhotoProcessTask>> {
+    let routes: Vec<Route> = sqlx::query_as(
+        r#"
+        SELECT r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token,
+               COALESCE(ARRAY(SELECT category_id FROM route_categories WHERE route_id = r.id), ARRAY[]::uuid[]) AS category_ids,
+               r.start_location, r.end_location, r.seasons, r.description
+        FROM routes r
+        WHERE r.points @> '[{"photo": {"status": "failed"}}]'::jsonb
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("failed to fetch routes with failed photos")?;
+
+    let mut tasks = Vec::new();
+    for route in routes {
+        let indices = retryable_point_indices(&route.points);
+        if indices.is_empty() {
+            continue;
+        }
+        let task = PhotoProcessTask {
+            route_id: route.id,
+            user_id: route.user_id,
+            point_indices: indices,
+        };
+
+        if !dry_run {
+            let Some(client) = nats_client else {
+                anyhow::bail!("NATS client is required to enqueue photo tasks");
+            };
+            let mut points = route.points;
+            for &idx in &task.point_indices {
+                if let Some(photo) = points[idx].photo.as_mut() {
+                    photo.status = PhotoStatus::Pending;
+                }
+            }
+            sqlx::query("UPDATE routes SET points = $2, updated_at = NOW() WHERE id = $1")
+                .bind(route.id)
+                .bind(serde_json::to_value(&points)?)
+                .execute(pool)
+                .await
+                .context("failed to reset photo status")?;
+
+            let jetstream = async_nats::jetstream::new(client.clone());
+            jetstream
+                .publish(PHOTO_PROCESS_SUBJECT, events::encode(&task)?.into())
+                .await
+                .context("failed to publish photo task")?
+                .await
+                .context("failed to get NATS publish ack")?;
+            tracing::info!(route_id = %task.route_id, point_count = task.point_indices.len(), "photo task re-enqueued");
+        }
+        tasks.push(task);
+    }
+
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use guide_helper_domain::route::PhotoData;
+
+    fn point(photo: Option<PhotoData>) -> RoutePoint {
+        RoutePoint {
+            lat: 55.0,
+            lng: 37.0,
+            name: None,
+            segment_mode: None,
+            photo,
+            address: None,
+        }
+    }
+
+    fn photo(original: &str, status: PhotoStatus) -> Option<PhotoData> {
+        Some(PhotoData {
+            original: original.to_string(),
+            thumbnail_url: None,
+            status,
+        })
+    }
+
+    #[test]
+    fn test_retryable_point_indices_only_failed_inline_photos() {
+        let points = vec![
+            point(photo("data:image/png;base64,abc", PhotoStatus::Failed)),
+            point(None),
+            point(photo("data:image/png;base64,abc", PhotoStatus::Pending)),
+            point(photo("/photos/u/r/photo_3.jpg", PhotoStatus::Failed)),
+            point(photo("data:image/jpeg;base64,xyz", PhotoStatus::Failed)),
+        ];
+
+        assert_eq!(retryable_point_indices(&points), vec![0, 4]);
+    }
+}
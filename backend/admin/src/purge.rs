@@ -0,0 +1,290 @@
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use guide_helper_domain::user_data::USER_KEYED_TABLES;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::storage::Bucket;
+
+/// Tables of the routes database keyed by the owning user, besides the
+/// [`USER_KEYED_TABLES`] shared with the data takeout. Route children (likes,
+/// ratings, comments, ... on the user's routes) go with the routes through
+/// `ON DELETE CASCADE`.
+const ROUTES_USER_TABLES: &[(&str, &str)] = &[
+    ("comments", "user_id"),
+    ("route_likes", "user_id"),
+    ("route_ratings", "user_id"),
+    ("route_bookmarks", "user_id"),
+    ("route_reports", "reporter_id"),
+    ("notifications", "user_id"),
+    ("notification_preferences", "user_id"),
+    ("chat_messages", "user_id"),
+    ("routes", "user_id"),
+];
+
+/// Ids of users soft-deleted before `cutoff`.
+pub async fn deleted_users(auth_pool: &PgPool, cutoff: DateTime<Utc>) -> anyhow::Result<Vec<Uuid>> {
+    sqlx::query_scalar("SELECT id FROM users WHERE deleted_at IS NOT NULL AND deleted_at < $1")
+        .bind(cutoff)
+        .fetch_all(auth_pool)
+        .await
+        .context("failed to list soft-deleted users")
+}
+
+/// Buckets holding files of users, under keys starting with `{user_id}/`.
+pub struct UserBuckets<B> {
+    /// Processed photos, also recorded in `photo_storage_objects`.
+    pub photos: B,
+    /// Data takeout archives.
+    pub exports: B,
+}
+
+/// Deletes the users' photos and data takeouts. Photos other users copied
+/// into their routes (templates) go as well, so those points lose their photo.
+async fn erase_objects<B: Bucket>(routes_pool: &PgPool, buckets: &UserBuckets<B>, user_ids: &[Uuid]) -> anyhow::Result<()> {
+    let mut photos: Vec<String> =
+        sqlx::query_scalar("SELECT object_key FROM photo_storage_objects WHERE user_id = ANY($1)")
+            .bind(user_ids)
+            .fetch_all(routes_pool)
+            .await
+            .context("failed to list stored photos")?;
+    let mut exports = Vec::new();
+    for user_id in user_ids {
+        let prefix = format!("{}/", user_id);
+        photos.extend(buckets.photos.list(&prefix).await?);
+        exports.extend(buckets.exports.list(&prefix).await?);
+    }
+    photos.sort();
+    photos.dedup();
+
+    for key in &photos {
+        buckets.photos.delete(key).await?;
+    }
+    for key in &exports {
+        buckets.exports.delete(key).await?;
+    }
+    tracing::info!(photos = photos.len(), exports = exports.len(), "purged user files");
+    Ok(())
+}
+
+/// Removes the given users' files, then everything they own in the routes
+/// database, then the user rows themselves, so a failure leaves the users
+/// around to retry.
+pub async fn purge_users<B: Bucket>(
+    auth_pool: &PgPool,
+    routes_pool: &PgPool,
+    buckets: &UserBuckets<B>,
+    user_ids: &[Uuid],
+) -> anyhow::Result<u64> {
+    if user_ids.is_empty() {
+        return Ok(0);
+    }
+
+    erase_objects(routes_pool, buckets, user_ids).await?;
+
+    let mut tx = routes_pool.begin().await?;
+    let tables = USER_KEYED_TABLES
+        .iter()
+        .map(|t| (t.table, t.user_column))
+        .chain(ROUTES_USER_TABLES.iter().copied());
+    for (table, column) in tables {
+        let result = sqlx::query(&format!("DELETE FROM {} WHERE {} = ANY($1)", table, column))
+            .bind(user_ids)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("failed to purge {}", table))?;
+        tracing::info!(table, rows = result.rows_affected(), "purged user data");
+    }
+    tx.commit().await?;
+
+    let result = sqlx::query("DELETE FROM users WHERE id = ANY($1) AND deleted_at IS NOT NULL")
+        .bind(user_ids)
+        .execute(auth_pool)
+        .await
+        .context("failed to purge users")?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::sync::Mutex;
+
+    /// One row owned by `$1` in every table the purge covers.
+    const SEED: &[&str] = &[
+        "INSERT INTO routes (id, user_id, name, points, created_at, updated_at) VALUES ($2, $1, 'Route', '[]', NOW(), NOW())",
+        "INSERT INTO comments (id, route_id, user_id, author_name, text, created_at) VALUES (gen_random_uuid(), $2, $1, 'me', 'Nice', NOW())",
+        "INSERT INTO route_likes (id, route_id, user_id, created_at) VALUES (gen_random_uuid(), $2, $1, NOW())",
+        "INSERT INTO route_ratings (id, route_id, user_id, rating, created_at) VALUES (gen_random_uuid(), $2, $1, 5, NOW())",
+        "INSERT INTO route_bookmarks (id, route_id, user_id, created_at) VALUES (gen_random_uuid(), $2, $1, NOW())",
+        "INSERT INTO route_reports (id, route_id, reporter_id, category) VALUES (gen_random_uuid(), $2, $1, 'spam')",
+        "INSERT INTO notifications (id, user_id, notification_type, route_id, actor_name, message) \
+            VALUES (gen_random_uuid(), $1, 'like', $2, 'someone', 'liked')",
+        "INSERT INTO notification_preferences (user_id, notification_type, enabled) VALUES ($1, 'like', FALSE)",
+        "INSERT INTO chat_messages (id, user_id, conversation_id, role, content) VALUES (gen_random_uuid(), $1, $2, 'user', 'hi')",
+        "INSERT INTO chat_conversations (user_id, conversation_id) VALUES ($1, $2)",
+        "INSERT INTO chat_preferences (user_id, language) VALUES ($1, 'en')",
+        "INSERT INTO chat_pending_actions (id, user_id, conversation_id, tool, arguments, summary) \
+            VALUES (gen_random_uuid(), $1, $2, 'create_route', '{}', 'Create a route')",
+        "INSERT INTO chat_turns (id, user_id, conversation_id, latency_ms) VALUES (gen_random_uuid(), $1, $2, 10)",
+        "INSERT INTO saved_searches (id, user_id, name, last_checked_at) VALUES (gen_random_uuid(), $1, 'Hikes', NOW())",
+        "INSERT INTO route_collections (id, user_id, name) VALUES (gen_random_uuid(), $1, 'Favourites')",
+        "INSERT INTO usage_events (day, user_id) VALUES (CURRENT_DATE, $1)",
+        "INSERT INTO route_quota_overrides (user_id, max_routes) VALUES ($1, 10)",
+        "INSERT INTO user_blocks (blocker_id, blocked_id) VALUES ($1, gen_random_uuid())",
+        "INSERT INTO user_blocks (blocker_id, blocked_id) VALUES (gen_random_uuid(), $1)",
+        "INSERT INTO spam_flags (id, content_type, content_id, user_id, reasons) \
+            VALUES (gen_random_uuid(), 'comment', gen_random_uuid(), $1, 'links')",
+        "INSERT INTO route_activity (id, route_id, actor_id, kind) VALUES (gen_random_uuid(), $2, $1, 'created')",
+        "INSERT INTO photo_storage_objects (object_key, user_id, route_id, size_bytes) \
+            VALUES ($1::text || '/' || $2::text || '/photo_a.jpg', $1, $2, 100)",
+    ];
+
+    #[derive(Default)]
+    struct MemoryBucket(Mutex<BTreeSet<String>>);
+
+    impl MemoryBucket {
+        fn with(keys: &[String]) -> Self {
+            Self(Mutex::new(keys.iter().cloned().collect()))
+        }
+
+        fn keys(&self) -> BTreeSet<String> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl Bucket for MemoryBucket {
+        async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>> {
+            Ok(self.0.lock().unwrap().iter().filter(|k| k.starts_with(prefix)).cloned().collect())
+        }
+
+        async fn delete(&self, key: &str) -> anyhow::Result<()> {
+            self.0.lock().unwrap().remove(key);
+            Ok(())
+        }
+    }
+
+    async fn seed(pool: &PgPool, user_id: Uuid) -> Uuid {
+        let route_id = Uuid::new_v4();
+        for statement in SEED {
+            sqlx::query(statement)
+                .bind(user_id)
+                .bind(route_id)
+                .execute(pool)
+                .await
+                .unwrap_or_else(|e| panic!("{statement}: {e}"));
+        }
+        route_id
+    }
+
+    /// Tables with any row mentioning `user_id`, in a column or inside JSON.
+    async fn tables_mentioning(pool: &PgPool, user_id: Uuid) -> Vec<String> {
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT table_name::text FROM information_schema.tables WHERE table_schema = 'public' AND table_type = 'BASE TABLE'",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        let mut mentioning = Vec::new();
+        for table in tables {
+            let count: i64 = sqlx::query_scalar(&format!(
+                "SELECT COUNT(*) FROM {} t WHERE to_jsonb(t)::text LIKE '%' || $1 || '%'",
+                table
+            ))
+            .bind(user_id.to_string())
+            .fetch_one(pool)
+            .await
+            .unwrap();
+            if count > 0 {
+                mentioning.push(table);
+            }
+        }
+        mentioning
+    }
+
+    async fn rows_of(pool: &PgPool, user_id: Uuid) -> Vec<(&'static str, &'static str, i64)> {
+        let tables = USER_KEYED_TABLES
+            .iter()
+            .map(|t| (t.table, t.user_column))
+            .chain(ROUTES_USER_TABLES.iter().copied());
+        let mut counts = Vec::new();
+        for (table, column) in tables {
+            let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {} WHERE {} = $1", table, column))
+                .bind(user_id)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+            counts.push((table, column, count));
+        }
+        counts
+    }
+
+    #[sqlx::test(migrations = "../routes/migrations")]
+    #[ignore = "needs Postgres, set DATABASE_URL"]
+    async fn test_purge_users_erases_every_user_keyed_table(pool: PgPool) {
+        sqlx::query("CREATE TABLE users (id UUID PRIMARY KEY, deleted_at TIMESTAMPTZ)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let (purged, kept) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut route_ids = Vec::new();
+        for user_id in [purged, kept] {
+            sqlx::query("INSERT INTO users (id, deleted_at) VALUES ($1, NOW())")
+                .bind(user_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+            route_ids.push(seed(&pool, user_id).await);
+        }
+        // Activity on someone else's route, and a photo handed over from a
+        // route of someone else that the purged user's copy still shows.
+        sqlx::query(
+            "INSERT INTO route_activity (id, route_id, actor_id, kind, details) \
+             VALUES (gen_random_uuid(), $2, $1, 'commented', jsonb_build_object('author_name', 'me'))",
+        )
+        .bind(purged)
+        .bind(route_ids[1])
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO photo_storage_objects (object_key, user_id, route_id, size_bytes) VALUES ('gone/r/photo_b.jpg', $1, $2, 10)")
+            .bind(purged)
+            .bind(route_ids[0])
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (table, column, count) in rows_of(&pool, purged).await {
+            assert!(count > 0, "nothing seeded in {table}.{column}");
+        }
+
+        let object = |user_id: Uuid, route_id: Uuid| format!("{user_id}/{route_id}/photo_a.jpg");
+        let buckets = UserBuckets {
+            photos: MemoryBucket::with(&[
+                object(purged, route_ids[0]),
+                format!("{purged}/{}/photo_unrecorded.jpg", route_ids[0]),
+                "gone/r/photo_b.jpg".to_string(),
+                object(kept, route_ids[1]),
+            ]),
+            exports: MemoryBucket::with(&[
+                format!("{purged}/takeout-20260101T000000Z.zip"),
+                format!("{kept}/takeout-20260101T000000Z.zip"),
+            ]),
+        };
+
+        assert_eq!(purge_users(&pool, &pool, &buckets, &[purged]).await.unwrap(), 1);
+
+        for (table, column, count) in rows_of(&pool, purged).await {
+            assert_eq!(count, 0, "{table}.{column} kept rows of the purged user");
+        }
+        assert!(tables_mentioning(&pool, purged).await.is_empty(), "rows still mention the purged user");
+        assert_eq!(buckets.photos.keys(), BTreeSet::from([object(kept, route_ids[1])]));
+        assert_eq!(buckets.exports.keys(), BTreeSet::from([format!("{kept}/takeout-20260101T000000Z.zip")]));
+
+        for (table, column, count) in rows_of(&pool, kept).await {
+            assert!(count > 0, "{table}.{column} lost rows of another user");
+        }
+        assert!(tables_mentioning(&pool, kept).await.contains(&"route_activity".to_string()));
+    }
+}
@@ -0,0 +1,573 @@
+use anyhow::Context;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use guide_helper_domain::route::RoutePoint;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Demo cities: name, center and landmarks used for point and route names.
+const CITIES: &[(&str, f64, f64, &[&str])] = &[
+    (
+        "moscow",
+        55.7558,
+        37.6173,
+        &[
+            "Красная площадь",
+            "Парк Горького",
+            "Воробьёвы горы",
+            "Патриаршие пруды",
+            "Чистые пруды",
+            "ВДНХ",
+            "Царицыно",
+            "Коломенское",
+            "Арбат",
+            "Зарядье",
+        ],
+    ),
+    (
+        "spb",
+        59.9343,
+        30.3351,
+        &[
+            "Дворцовая площадь",
+            "Петропавловская крепость",
+            "Летний сад",
+            "Исаакиевский собор",
+            "Новая Голландия",
+            "Елагин остров",
+            "Стрелка Васильевского острова",
+            "Смольный собор",
+            "Михайловский сад",
+            "Таврический сад",
+        ],
+    ),
+    (
+        "kazan",
+        55.7961,
+        49.1064,
+        &[
+            "Казанский кремль",
+            "Улица Баумана",
+            "Озеро Кабан",
+            "Парк Горького",
+            "Старо-Татарская слобода",
+            "Чаша",
+            "Дворец земледельцев",
+            "Парк Тысячелетия",
+        ],
+    ),
+];
+
+const ROUTE_PREFIXES: &[&str] = &["Прогулка", "Маршрут", "Вечерняя прогулка", "Велопрогулка", "Экскурсия"];
+
+const SEGMENT_MODES: &[&str] = &["walk", "walk", "walk", "bike", "auto"];
+
+const SEASONS: &[&str] = &["winter", "spring", "summer", "autumn"];
+
+const FIRST_NAMES: &[&str] = &["Анна", "Иван", "Мария", "Дмитрий", "Елена", "Сергей", "Ольга", "Алексей", "Наталья", "Павел"];
+
+const COMMENTS: &[&str] = &[
+    "Отличный маршрут, прошли за пару часов.",
+    "Очень красиво на закате!",
+    "Добавьте, пожалуйста, кафе по пути.",
+    "С детьми идти тяжеловато, но виды того стоят.",
+    "Прошли в выходные, всё как в описании.",
+    "Местами много людей, лучше идти утром.",
+    "Спасибо, сохранил в закладки.",
+    "На велосипеде тоже отлично проезжается.",
+];
+
+const CHAT_QUESTIONS: &[&str] = &[
+    "Куда сходить вечером в центре?",
+    "Составь маршрут на два часа с кофейней по пути.",
+    "Какие парки рядом с метро?",
+    "Что посмотреть с детьми в выходные?",
+    "Где лучше кататься на велосипеде?",
+];
+
+const CHAT_ANSWERS: &[&str] = &[
+    "Предлагаю начать с набережной и закончить в парке — около 5 км пешком.",
+    "Вот маршрут из четырёх точек, по пути есть пара кофеен.",
+    "Ближе всего три парка, добавил их на карту.",
+    "Подойдёт прогулка по центру с остановкой на детской площадке.",
+];
+
+/// Messages in each seeded chat conversation, alternating user and assistant.
+const CHAT_MESSAGES_PER_CONVERSATION: usize = 10;
+
+/// Parameters of a demo dataset. The same options always produce the same data.
+pub struct SeedOptions {
+    pub seed: u64,
+    pub city: String,
+    pub users: usize,
+    pub routes: usize,
+    /// Share of routes published on explore, from 0 to 1.
+    pub public_ratio: f64,
+    /// Chat conversations per user.
+    pub conversations: usize,
+}
+
+pub struct DemoUser {
+    pub id: Uuid,
+    pub email: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct DemoRoute {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub points: Vec<RoutePoint>,
+    pub category_names: Vec<&'static str>,
+    pub seasons: Vec<String>,
+    pub start_location: Option<String>,
+    pub end_location: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Set for routes published on explore.
+    pub share_token: Option<Uuid>,
+}
+
+pub struct DemoComment {
+    pub id: Uuid,
+    pub route_id: Uuid,
+    pub user_id: Uuid,
+    pub author_name: String,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct DemoReaction {
+    pub id: Uuid,
+    pub route_id: Uuid,
+    pub user_id: Uuid,
+    pub rating: Option<i16>,
+    pub liked: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct DemoChatMessage {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub conversation_id: Uuid,
+    pub role: &'static str,
+    pub content: &'static str,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct DemoData {
+    pub users: Vec<DemoUser>,
+    pub routes: Vec<DemoRoute>,
+    pub comments: Vec<DemoComment>,
+    pub reactions: Vec<DemoReaction>,
+    pub chat_messages: Vec<DemoChatMessage>,
+}
+
+#[derive(Debug, Default)]
+pub struct SeedReport {
+    pub users: u64,
+    pub routes: u64,
+    pub comments: u64,
+    pub likes: u64,
+    pub ratings: u64,
+    pub chat_messages: u64,
+}
+
+/// Rows per multi-row insert of the high-volume tables.
+const INSERT_BATCH: usize = 5000;
+
+/// Names of the categories created by the categories migration.
+const CATEGORIES: &[&str] = &["hiking", "cycling", "historical", "nature", "urban"];
+
+fn random_uuid(rng: &mut StdRng) -> Uuid {
+    uuid::Builder::from_random_bytes(rng.r#gen()).into_uuid()
+}
+
+/// Generates the demo dataset without touching the database.
+pub fn generate(options: &SeedOptions) -> anyhow::Result<DemoData> {
+    let (_, center_lat, center_lng, landmarks) = CITIES
+        .iter()
+        .find(|(name, ..)| *name == options.city)
+        .copied()
+        .ok_or_else(|| {
+            let known: Vec<&str> = CITIES.iter().map(|(name, ..)| *name).collect();
+            anyhow::anyhow!("unknown city {}, expected one of: {}", options.city, known.join(", "))
+        })?;
+    if options.users == 0 {
+        anyhow::bail!("at least one user is required");
+    }
+    if !(0.0..=1.0).contains(&options.public_ratio) {
+        anyhow::bail!("public ratio must be between 0 and 1");
+    }
+
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    // A fixed epoch keeps timestamps reproducible between runs.
+    let epoch = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+
+    let users: Vec<DemoUser> = (0..options.users)
+        .map(|i| {
+            let first_name = FIRST_NAMES[i % FIRST_NAMES.len()];
+            DemoUser {
+                id: random_uuid(&mut rng),
+                email: format!("demo{}@guide-helper.local", i + 1),
+                name: format!("{} {}", first_name, i + 1),
+                created_at: epoch - Duration::days(rng.gen_range(60..365)),
+            }
+        })
+        .collect();
+
+    let mut routes = Vec::with_capacity(options.routes);
+    for _ in 0..options.routes {
+        let owner = users.choose(&mut rng).expect("users is not empty");
+        let point_count = rng.gen_range(3..=8);
+        // Start within ~5 km of the center and walk 200-800 m per step.
+        let mut lat = center_lat + rng.gen_range(-0.045..0.045);
+        let mut lng = center_lng + rng.gen_range(-0.07..0.07);
+        let mut points = Vec::with_capacity(point_count);
+        for i in 0..point_count {
+            if i > 0 {
+                let bearing: f64 = rng.gen_range(0.0..std::f64::consts::TAU);
+                let step_km: f64 = rng.gen_range(0.2..0.8);
+                lat += step_km / 111.0 * bearing.cos();
+                lng += step_km / (111.0 * lat.to_radians().cos()) * bearing.sin();
+            }
+            let name = rng.gen_bool(0.5).then(|| landmarks.choose(&mut rng).unwrap().to_string());
+            let segment_mode = (i > 0).then(|| SEGMENT_MODES.choose(&mut rng).unwrap().to_string());
+            points.push(RoutePoint {
+                lat: (lat * 1e6).round() / 1e6,
+                lng: (lng * 1e6).round() / 1e6,
+                name,
+                segment_mode,
+                photo: None,
+                address: None,
+            });
+        }
+
+        let start = *landmarks.choose(&mut rng).unwrap();
+        let end = *landmarks.choose(&mut rng).unwrap();
+        let name = format!("{}: {} — {}", ROUTE_PREFIXES.choose(&mut rng).unwrap(), start, end);
+        let category_count = rng.gen_range(1..=2);
+        let category_names = CATEGORIES.choose_multiple(&mut rng, category_count).copied().collect();
+        let season_count = rng.gen_range(1..=SEASONS.len());
+        let seasons = SEASONS
+            .choose_multiple(&mut rng, season_count)
+            .map(|s| s.to_string())
+            .collect();
+        let created_at = owner.created_at + Duration::hours(rng.gen_range(1..24 * 60));
+
+        routes.push(DemoRoute {
+            id: random_uuid(&mut rng),
+            user_id: owner.id,
+            name,
+            points,
+            category_names,
+            seasons,
+            start_location: Some(start.to_string()),
+            end_location: Some(end.to_string()),
+            created_at,
+            share_token: None,
+        });
+    }
+
+    let mut comments = Vec::new();
+    let mut reactions = Vec::new();
+    for route in &routes {
+        for user in &users {
+            if user.id == route.user_id {
+                continue;
+            }
+            let created_at = route.created_at + Duration::hours(rng.gen_range(1..24 * 30));
+            if rng.gen_bool(0.2) {
+                comments.push(DemoComment {
+                    id: random_uuid(&mut rng),
+                    route_id: route.id,
+                    user_id: user.id,
+                    author_name: user.name.clone(),
+                    text: COMMENTS.choose(&mut rng).unwrap().to_string(),
+                    created_at,
+                });
+            }
+            let liked = rng.gen_bool(0.35);
+            let rating = rng.gen_bool(0.3).then(|| rng.gen_range(3..=5));
+            if liked || rating.is_some() {
+                reactions.push(DemoReaction {
+                    id: random_uuid(&mut rng),
+                    route_id: route.id,
+                    user_id: user.id,
+                    rating,
+                    liked,
+                    created_at,
+                });
+            }
+        }
+    }
+
+    // Drawn after everything else so existing seeds keep producing the same
+    // routes, comments and reactions.
+    if options.public_ratio > 0.0 {
+        for route in &mut routes {
+            if rng.gen_bool(options.public_ratio) {
+                route.share_token = Some(random_uuid(&mut rng));
+            }
+        }
+    }
+
+    let mut chat_messages = Vec::with_capacity(users.len() * options.conversations * CHAT_MESSAGES_PER_CONVERSATION);
+    for user in &users {
+        for _ in 0..options.conversations {
+            let conversation_id = random_uuid(&mut rng);
+            let started_at = user.created_at + Duration::hours(rng.gen_range(1..24 * 60));
+            for i in 0..CHAT_MESSAGES_PER_CONVERSATION {
+                let (role, content) = if i % 2 == 0 {
+                    ("user", *CHAT_QUESTIONS.choose(&mut rng).unwrap())
+                } else {
+                    ("assistant", *CHAT_ANSWERS.choose(&mut rng).unwrap())
+                };
+                chat_messages.push(DemoChatMessage {
+                    id: random_uuid(&mut rng),
+                    user_id: user.id,
+                    conversation_id,
+                    role,
+                    content,
+                    created_at: started_at + Duration::seconds(30 * i as i64),
+                });
+            }
+        }
+    }
+
+    Ok(DemoData {
+        users,
+        routes,
+        comments,
+        reactions,
+        chat_messages,
+    })
+}
+
+/// Writes the dataset. Ids are deterministic, so re-running the same seed
+/// skips rows that already exist instead of duplicating them.
+pub async fn insert(
+    auth_pool: &PgPool,
+    routes_pool: &PgPool,
+    data: &DemoData,
+    password_hash: &str,
+) -> anyhow::Result<SeedReport> {
+    let mut report = SeedReport::default();
+
+    for user in &data.users {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO users (id, email, password_hash, name, avatar_url, role, created_at, updated_at, deleted_at)
+            VALUES ($1, $2, $3, $4, NULL, 'user', $5, $5, NULL)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(user.id)
+        .bind(&user.email)
+        .bind(password_hash)
+        .bind(&user.name)
+        .bind(user.created_at)
+        .execute(auth_pool)
+        .await
+        .context("failed to insert demo user")?;
+        report.users += result.rows_affected();
+    }
+
+    let mut tx = routes_pool.begin().await?;
+    for route in &data.routes {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO routes (id, user_id, name, points, created_at, updated_at, start_location, end_location, seasons,
+                                share_token, visibility, shared_at)
+            VALUES ($1, $2, $3, $4, $5, $5, $6, $7, $8,
+                    $9, CASE WHEN $9::uuid IS NULL THEN 'private' ELSE 'public' END, CASE WHEN $9::uuid IS NULL THEN NULL ELSE $5 END)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(route.id)
+        .bind(route.user_id)
+        .bind(&route.name)
+        .bind(serde_json::to_value(&route.points)?)
+        .bind(route.created_at)
+        .bind(&route.start_location)
+        .bind(&route.end_location)
+        .bind(&route.seasons)
+        .bind(route.share_token)
+        .execute(&mut *tx)
+        .await
+        .context("failed to insert demo route")?;
+        report.routes += result.rows_affected();
+
+        sqlx::query(
+            r#"
+            INSERT INTO route_categories (route_id, category_id)
+            SELECT $1, id FROM categories WHERE name = ANY($2)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(route.id)
+        .bind(&route.category_names)
+        .execute(&mut *tx)
+        .await
+        .context("failed to link demo route categories")?;
+    }
+
+    for chunk in data.comments.chunks(INSERT_BATCH) {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO comments (id, route_id, user_id, author_name, text, created_at)
+            SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::text[], $5::text[], $6::timestamptz[])
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(chunk.iter().map(|c| c.id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|c| c.route_id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|c| c.user_id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|c| c.author_name.as_str()).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|c| c.text.as_str()).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|c| c.created_at).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await
+        .context("failed to insert demo comments")?;
+        report.comments += result.rows_affected();
+    }
+
+    let likes: Vec<&DemoReaction> = data.reactions.iter().filter(|r| r.liked).collect();
+    for chunk in likes.chunks(INSERT_BATCH) {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO route_likes (id, route_id, user_id, created_at)
+            SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::timestamptz[])
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(chunk.iter().map(|r| r.id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|r| r.route_id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|r| r.user_id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|r| r.created_at).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await
+        .context("failed to insert demo likes")?;
+        report.likes += result.rows_affected();
+    }
+
+    let ratings: Vec<(&DemoReaction, i16)> = data.reactions.iter().filter_map(|r| Some((r, r.rating?))).collect();
+    for chunk in ratings.chunks(INSERT_BATCH) {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO route_ratings (id, route_id, user_id, rating, created_at)
+            SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::smallint[], $5::timestamptz[])
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(chunk.iter().map(|(r, _)| r.id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|(r, _)| r.route_id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|(r, _)| r.user_id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|(_, rating)| *rating).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|(r, _)| r.created_at).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await
+        .context("failed to insert demo ratings")?;
+        report.ratings += result.rows_affected();
+    }
+
+    for chunk in data.chat_messages.chunks(INSERT_BATCH) {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO chat_messages (id, user_id, conversation_id, role, content, created_at)
+            SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::text[], $5::text[], $6::timestamptz[])
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(chunk.iter().map(|m| m.id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|m| m.user_id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|m| m.conversation_id).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|m| m.role).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|m| m.content).collect::<Vec<_>>())
+        .bind(chunk.iter().map(|m| m.created_at).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await
+        .context("failed to insert demo chat messages")?;
+        report.chat_messages += result.rows_affected();
+    }
+    tx.commit().await?;
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(seed: u64) -> SeedOptions {
+        SeedOptions {
+            seed,
+            city: "moscow".to_string(),
+            users: 5,
+            routes: 12,
+            public_ratio: 0.0,
+            conversations: 0,
+        }
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        let a = generate(&options(7)).unwrap();
+        let b = generate(&options(7)).unwrap();
+
+        let ids = |d: &DemoData| d.routes.iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(ids(&a), ids(&b));
+        assert_eq!(a.routes[0].points, b.routes[0].points);
+        assert_eq!(a.comments.len(), b.comments.len());
+        assert_ne!(ids(&a), ids(&generate(&options(8)).unwrap()));
+    }
+
+    #[test]
+    fn test_generate_points_stay_near_city() {
+        let data = generate(&options(1)).unwrap();
+
+        assert_eq!(data.users.len(), 5);
+        assert_eq!(data.routes.len(), 12);
+        for route in &data.routes {
+            assert!((3..=8).contains(&route.points.len()));
+            for point in &route.points {
+                assert!((point.lat - 55.7558).abs() < 0.1, "lat {} too far", point.lat);
+                assert!((point.lng - 37.6173).abs() < 0.2, "lng {} too far", point.lng);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_never_reacts_to_own_routes() {
+        let data = generate(&options(3)).unwrap();
+        let owner = |route_id: Uuid| data.routes.iter().find(|r| r.id == route_id).unwrap().user_id;
+
+        assert!(data.comments.iter().all(|c| c.user_id != owner(c.route_id)));
+        assert!(data.reactions.iter().all(|r| r.user_id != owner(r.route_id)));
+    }
+
+    #[test]
+    fn test_public_routes_and_chats_leave_the_rest_unchanged() {
+        let plain = generate(&options(5)).unwrap();
+        let mut opts = options(5);
+        opts.public_ratio = 0.5;
+        opts.conversations = 2;
+        let data = generate(&opts).unwrap();
+
+        let ids = |d: &DemoData| d.routes.iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(ids(&data), ids(&plain));
+        assert_eq!(data.comments.len(), plain.comments.len());
+        assert!(plain.routes.iter().all(|r| r.share_token.is_none()));
+        assert!(data.routes.iter().any(|r| r.share_token.is_some()));
+        assert_eq!(data.chat_messages.len(), 5 * 2 * CHAT_MESSAGES_PER_CONVERSATION);
+        assert_eq!(data.chat_messages[0].role, "user");
+        assert_eq!(data.chat_messages[1].role, "assistant");
+    }
+
+    #[test]
+    fn test_generate_rejects_unknown_city() {
+        let mut opts = options(1);
+        opts.city = "atlantis".to_string();
+        assert!(generate(&opts).is_err());
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::Context;
+use aws_sdk_s3::Client as S3Client;
+
+/// A bucket of the deployment's object storage, as far as erasing files goes.
+pub trait Bucket {
+    /// Keys of every object whose key starts with `prefix`.
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>>;
+    /// Deletes an object; deleting a missing key succeeds.
+    async fn delete(&self, key: &str) -> anyhow::Result<()>;
+}
+
+/// A MinIO/S3 bucket.
+pub struct S3Bucket {
+    client: S3Client,
+    bucket: String,
+}
+
+impl S3Bucket {
+    pub async fn new(endpoint: &str, access_key: &str, secret_key: &str, bucket: String) -> Self {
+        let s3_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .endpoint_url(endpoint)
+            .region(aws_config::Region::new("us-east-1"))
+            .credentials_provider(aws_sdk_s3::config::Credentials::new(access_key, secret_key, None, None, "env"))
+            .load()
+            .await;
+        let client = S3Client::from_conf(
+            aws_sdk_s3::Config::from(&s3_config)
+                .to_builder()
+                .force_path_style(true)
+                .build(),
+        );
+        Self { client, bucket }
+    }
+}
+
+impl Bucket for S3Bucket {
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .with_context(|| format!("failed to list {}/{}", self.bucket, prefix))?;
+            keys.extend(page.contents().iter().filter_map(|object| object.key().map(String::from)));
+            continuation_token = page.next_continuation_token().map(String::from);
+            if continuation_token.is_none() {
+                return Ok(keys);
+            }
+        }
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| format!("failed to delete {}/{}", self.bucket, key))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::Context;
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHasher, SaltString},
+    Argon2,
+};
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+pub enum CreateAdminOutcome {
+    Created(Uuid),
+    Promoted(Uuid),
+}
+
+/// Creates an admin account, or promotes the existing account with that email.
+/// The password is only used when a new account is created.
+pub async fn create_admin(
+    pool: &PgPool,
+    email: &str,
+    password: &str,
+    name: Option<&str>,
+) -> anyhow::Result<CreateAdminOutcome> {
+    let email = email.trim().to_lowercase();
+    if !email.contains('@') {
+        anyhow::bail!("invalid email: {}", email);
+    }
+
+    let existing: Option<Uuid> =
+        sqlx::query_scalar("SELECT id FROM users WHERE email = $1 AND deleted_at IS NULL")
+            .bind(&email)
+            .fetch_optional(pool)
+            .await
+            .context("failed to look up user")?;
+
+    if let Some(id) = existing {
+        sqlx::query("UPDATE users SET role = 'admin', updated_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await
+            .context("failed to promote user")?;
+        tracing::info!(user_id = %id, "existing user promoted to admin");
+        return Ok(CreateAdminOutcome::Promoted(id));
+    }
+
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        anyhow::bail!("password must be at least {} characters", MIN_PASSWORD_LENGTH);
+    }
+    let password_hash = hash_password(password)?;
+
+    let id = Uuid::new_v4();
+    let now = Utc::now();
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, avatar_url, role, created_at, updated_at, deleted_at)
+        VALUES ($1, $2, $3, $4, NULL, 'admin', $5, $5, NULL)
+        "#,
+    )
+    .bind(id)
+    .bind(&email)
+    .bind(&password_hash)
+    .bind(name)
+    .bind(now)
+    .execute(pool)
+    .await
+    .context("failed to insert admin user")?;
+
+    tracing::info!(user_id = %id, "admin user created");
+    Ok(CreateAdminOutcome::Created(id))
+}
+
+pub fn hash_password(password: &str) -> anyhow::Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| anyhow::anyhow!("failed to hash password: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_password_is_argon2() {
+        let hash = hash_password("admin_password").unwrap();
+        assert!(hash.starts_with("$argon2"));
+    }
+}
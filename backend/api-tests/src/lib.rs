@@ -0,0 +1,158 @@
+//! Black-box tests of the public API. A [`Stack`] runs the auth and routes
+//! service images next to throwaway Postgres and NATS containers, and the
+//! tests talk to it only through [`ApiClient`], so they catch regressions
+//! between the services that mocked unit tests cannot.
+//!
+//! The images are built from the service Dockerfiles beforehand:
+//!
+//! ```sh
+//! cd backend
+//! docker build -f auth/Dockerfile -t guide-helper-auth:e2e .
+//! docker build -f routes/Dockerfile -t guide-helper-routes:e2e .
+//! cd api-tests && cargo test -- --ignored
+//! ```
+//!
+//! `AUTH_IMAGE` and `ROUTES_IMAGE` point the suite at other images.
+
+use std::time::Duration;
+
+use anyhow::Context;
+use guide_helper_api::auth::RegisterRequest;
+use guide_helper_api::client::ApiClient;
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+use testcontainers_modules::nats::{Nats, NatsServerCmd};
+use testcontainers_modules::postgres::Postgres;
+use uuid::Uuid;
+
+const DB_USER: &str = "authuser";
+const DB_PASSWORD: &str = "authpass123";
+const JWT_SECRET: &str = "api-tests-secret";
+const SERVICE_PORT: u16 = 8080;
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Creates `routes_db` next to the auth database, as in docker-compose.
+const INIT_SQL: &str = include_str!("../../../docker/postgres/init.sql");
+
+/// Both services and their dependencies on a private Docker network. The
+/// containers are removed when the stack is dropped.
+pub struct Stack {
+    auth_url: String,
+    routes_url: String,
+    _auth: ContainerAsync<GenericImage>,
+    _routes: ContainerAsync<GenericImage>,
+    _postgres: ContainerAsync<Postgres>,
+    _nats: ContainerAsync<Nats>,
+}
+
+/// A registered user with authenticated clients for both services.
+pub struct Session {
+    pub user_id: Uuid,
+    pub email: String,
+    pub auth: ApiClient,
+    pub routes: ApiClient,
+}
+
+fn service_image(var: &str, default: &str, ready_message: &str) -> GenericImage {
+    let image = std::env::var(var).unwrap_or_else(|_| default.to_string());
+    let (name, tag) = image.rsplit_once(':').unwrap_or((image.as_str(), "latest"));
+    GenericImage::new(name, tag)
+        .with_exposed_port(SERVICE_PORT.tcp())
+        .with_wait_for(WaitFor::message_on_stdout(ready_message))
+}
+
+async fn base_url(container: &ContainerAsync<GenericImage>) -> anyhow::Result<String> {
+    let host = container.get_host().await?;
+    let port = container.get_host_port_ipv4(SERVICE_PORT).await?;
+    Ok(format!("http://{}:{}", host, port))
+}
+
+impl Stack {
+    pub async fn start() -> anyhow::Result<Self> {
+        // Unique names let several stacks run side by side.
+        let suffix = Uuid::new_v4().simple().to_string()[..8].to_string();
+        let network = format!("guide-helper-e2e-{}", suffix);
+        let postgres_host = format!("postgres-{}", suffix);
+        let nats_host = format!("nats-{}", suffix);
+        let database_url =
+            |db: &str| format!("postgres://{}:{}@{}:5432/{}", DB_USER, DB_PASSWORD, postgres_host, db);
+
+        let postgres = Postgres::default()
+            .with_db_name("auth_db")
+            .with_user(DB_USER)
+            .with_password(DB_PASSWORD)
+            .with_init_sql(INIT_SQL.as_bytes().to_vec())
+            .with_network(&network)
+            .with_container_name(&postgres_host)
+            .start()
+            .await
+            .context("failed to start postgres")?;
+        let nats = Nats::default()
+            .with_cmd(&NatsServerCmd::default().with_jetstream())
+            .with_network(&network)
+            .with_container_name(&nats_host)
+            .start()
+            .await
+            .context("failed to start nats")?;
+
+        let auth = service_image("AUTH_IMAGE", "guide-helper-auth:e2e", "server running on")
+            .with_network(&network)
+            .with_container_name(format!("auth-{}", suffix))
+            .with_env_var("DATABASE_URL", database_url("auth_db"))
+            .with_env_var("DATABASE_MAX_CONNECTIONS", "5")
+            .with_env_var("JWT_SECRET", JWT_SECRET)
+            .with_env_var("JWT_ACCESS_TOKEN_MINUTES", "15")
+            .with_env_var("JWT_REFRESH_TOKEN_DAYS", "7")
+            .with_startup_timeout(STARTUP_TIMEOUT)
+            .start()
+            .await
+            .context("failed to start the auth service")?;
+        let routes = service_image("ROUTES_IMAGE", "guide-helper-routes:e2e", "routes service running on")
+            .with_network(&network)
+            .with_container_name(format!("routes-{}", suffix))
+            .with_env_var("DATABASE_URL", database_url("routes_db"))
+            .with_env_var("JWT_SECRET", JWT_SECRET)
+            .with_env_var("NATS_URL", format!("nats://{}:4222", nats_host))
+            .with_startup_timeout(STARTUP_TIMEOUT)
+            .start()
+            .await
+            .context("failed to start the routes service")?;
+
+        Ok(Self {
+            auth_url: base_url(&auth).await?,
+            routes_url: base_url(&routes).await?,
+            _auth: auth,
+            _routes: routes,
+            _postgres: postgres,
+            _nats: nats,
+        })
+    }
+
+    /// Routes service client without a token, as used by link visitors.
+    pub fn anonymous(&self) -> ApiClient {
+        ApiClient::new(&self.routes_url)
+    }
+
+    /// Registers a user with a fresh email address.
+    pub async fn register(&self) -> anyhow::Result<Session> {
+        let email = format!("user-{}@example.com", Uuid::new_v4().simple());
+        let request = RegisterRequest {
+            email: email.clone(),
+            password: "correct-horse-battery".to_string(),
+        };
+        let tokens = ApiClient::new(&self.auth_url)
+            .register(&request)
+            .await
+            .context("failed to register")?;
+
+        let auth = ApiClient::new(&self.auth_url).with_token(&tokens.access_token);
+        let profile = auth.profile().await.context("failed to load the profile")?;
+        Ok(Session {
+            user_id: profile.id,
+            email,
+            auth,
+            routes: ApiClient::new(&self.routes_url).with_token(tokens.access_token),
+        })
+    }
+}
@@ -0,0 +1,110 @@
+use guide_helper_api::client::ClientError;
+use guide_helper_api::comments::CreateCommentRequest;
+use guide_helper_api::notifications::NotificationListParams;
+use guide_helper_api::routes::{CreateRouteRequest, RoutePoint};
+use guide_helper_api_tests::Stack;
+
+fn point(lat: f64, lng: f64) -> RoutePoint {
+    RoutePoint {
+        lat,
+        lng,
+        name: None,
+        segment_mode: None,
+        photo: None,
+        address: None,
+    }
+}
+
+fn is_not_found(result: Result<impl std::fmt::Debug, ClientError>) -> bool {
+    matches!(result, Err(ClientError::Status { status: 404, .. }))
+}
+
+/// Register → create route → share → comment from a second user →
+/// notification appears → the owner removes the route.
+///
+/// The API has no account deletion yet, so the journey ends with the owner
+/// deleting what they created.
+#[tokio::test]
+#[ignore = "needs Docker and the service images, see the crate docs"]
+async fn test_shared_route_comment_notifies_owner() {
+    let stack = Stack::start().await.unwrap();
+    let owner = stack.register().await.unwrap();
+    let visitor = stack.register().await.unwrap();
+
+    let route = owner
+        .routes
+        .create_route(&CreateRouteRequest {
+            name: "Lake loop".to_string(),
+            points: vec![point(55.75, 37.61), point(55.76, 37.62)],
+            category_ids: vec![],
+            seasons: vec!["summer".to_string()],
+            form_elapsed_ms: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(route.user_id, owner.user_id);
+
+    let share = owner.routes.enable_share(route.id).await.unwrap();
+    let shared = stack.anonymous().get_shared_route(&share.share_token).await.unwrap();
+    assert_eq!(shared.id, route.id);
+
+    let comment = visitor
+        .routes
+        .create_comment(
+            route.id,
+            &CreateCommentRequest {
+                text: "Great views from the pier".to_string(),
+                author_name: "Visitor".to_string(),
+                form_elapsed_ms: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(comment.user_id, visitor.user_id);
+
+    let notifications = owner.routes.list_notifications(&NotificationListParams::default()).await.unwrap();
+    assert_eq!(notifications.data.len(), 1);
+    assert_eq!(notifications.data[0].notification_type, "comment");
+    assert_eq!(notifications.data[0].route_id, route.id);
+    assert_eq!(notifications.data[0].actor_name, "Visitor");
+    assert_eq!(notifications.meta.unwrap().unread_count, 1);
+    let visitor_notifications = visitor.routes.list_notifications(&NotificationListParams::default()).await.unwrap();
+    assert!(visitor_notifications.data.is_empty());
+
+    owner.routes.delete_route(route.id).await.unwrap();
+    assert!(is_not_found(stack.anonymous().get_shared_route(&share.share_token).await));
+    assert!(is_not_found(owner.routes.get_route(route.id).await));
+    assert_eq!(owner.routes.list_routes().await.unwrap().pagination.total, 0);
+}
+
+/// Tokens issued by the auth service are accepted by the routes service, and
+/// one user's routes stay invisible to another.
+#[tokio::test]
+#[ignore = "needs Docker and the service images, see the crate docs"]
+async fn test_routes_are_private_to_their_owner() {
+    let stack = Stack::start().await.unwrap();
+    let owner = stack.register().await.unwrap();
+    let stranger = stack.register().await.unwrap();
+
+    let profile = owner.auth.profile().await.unwrap();
+    assert_eq!(profile.email, owner.email);
+
+    let route = owner
+        .routes
+        .create_route(&CreateRouteRequest {
+            name: "Old town".to_string(),
+            points: vec![point(59.93, 30.31), point(59.94, 30.32)],
+            category_ids: vec![],
+            seasons: vec![],
+            form_elapsed_ms: None,
+        })
+        .await
+        .unwrap();
+
+    assert!(is_not_found(stranger.routes.get_route(route.id).await));
+    assert_eq!(stranger.routes.list_routes().await.unwrap().pagination.total, 0);
+    assert!(matches!(
+        stack.anonymous().list_routes().await,
+        Err(ClientError::Status { status: 401, .. })
+    ));
+}
@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockedUserResponse {
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
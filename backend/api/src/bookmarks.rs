@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToggleBookmarkResponse {
+    pub bookmarked: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserBookmarkStatusResponse {
+    pub bookmarked: bool,
+}
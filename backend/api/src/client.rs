@@ -0,0 +1,289 @@
+use std::time::Duration;
+
+use reqwest::{Client, Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use crate::auth::{AuthResponse, LoginRequest, RegisterRequest};
+use crate::comments::{CommentResponse, CreateCommentRequest};
+use crate::list::ListResponse;
+use crate::notifications::{NotificationListParams, NotificationResponse, NotificationsListMeta};
+use crate::profile::ProfileResponse;
+use crate::routes::{
+    CreateRouteRequest, ExploreMeta, ExploreQuery, ExploreRouteResponse, MergeRoutesRequest, PatchPointsRequest,
+    RouteResponse, ShareResponse, SplitRouteRequest, SplitRouteResponse, UpdateRouteRequest,
+};
+use crate::search::ReindexResponse;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("server responded with {status}: {body}")]
+    Status { status: u16, body: String },
+}
+
+/// Typed client for the public API. `base_url` is the gateway address that
+/// serves both the auth and routes services under `/api/v1`.
+#[derive(Clone)]
+pub struct ApiClient {
+    client: Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl ApiClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("failed to build api http client");
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: None,
+        }
+    }
+
+    /// Sends `token` as a bearer token on every request.
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let builder = self.client.request(method, format!("{}/api/v1{}", self.base_url, path));
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    async fn send(builder: RequestBuilder) -> Result<Response, ClientError> {
+        let response = builder.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(ClientError::Status {
+            status: status.as_u16(),
+            body,
+        })
+    }
+
+    async fn json<T: DeserializeOwned>(builder: RequestBuilder) -> Result<T, ClientError> {
+        Ok(Self::send(builder).await?.json().await?)
+    }
+
+    pub async fn register(&self, request: &RegisterRequest) -> Result<AuthResponse, ClientError> {
+        Self::json(self.request(Method::POST, "/auth/register").json(request)).await
+    }
+
+    pub async fn login(&self, request: &LoginRequest) -> Result<AuthResponse, ClientError> {
+        Self::json(self.request(Method::POST, "/auth/login").json(request)).await
+    }
+
+    pub async fn profile(&self) -> Result<ProfileResponse, ClientError> {
+        Self::json(self.request(Method::GET, "/auth/me")).await
+    }
+
+    pub async fn list_routes(&self) -> Result<ListResponse<RouteResponse>, ClientError> {
+        Self::json(self.request(Method::GET, "/routes")).await
+    }
+
+    pub async fn get_route(&self, id: Uuid) -> Result<RouteResponse, ClientError> {
+        Self::json(self.request(Method::GET, &format!("/routes/{}", id))).await
+    }
+
+    pub async fn create_route(&self, request: &CreateRouteRequest) -> Result<RouteResponse, ClientError> {
+        Self::json(self.request(Method::POST, "/routes").json(request)).await
+    }
+
+    pub async fn update_route(&self, id: Uuid, request: &UpdateRouteRequest) -> Result<RouteResponse, ClientError> {
+        Self::json(self.request(Method::PUT, &format!("/routes/{}", id)).json(request)).await
+    }
+
+    pub async fn patch_route_points(&self, id: Uuid, request: &PatchPointsRequest) -> Result<RouteResponse, ClientError> {
+        Self::json(self.request(Method::PATCH, &format!("/routes/{}/points", id)).json(request)).await
+    }
+
+    pub async fn merge_routes(&self, request: &MergeRoutesRequest) -> Result<RouteResponse, ClientError> {
+        Self::json(self.request(Method::POST, "/routes/merge").json(request)).await
+    }
+
+    pub async fn split_route(&self, id: Uuid, request: &SplitRouteRequest) -> Result<SplitRouteResponse, ClientError> {
+        Self::json(self.request(Method::POST, &format!("/routes/{}/split", id)).json(request)).await
+    }
+
+    pub async fn reverse_route(&self, id: Uuid) -> Result<RouteResponse, ClientError> {
+        Self::json(self.request(Method::POST, &format!("/routes/{}/reverse", id))).await
+    }
+
+    pub async fn delete_route(&self, id: Uuid) -> Result<(), ClientError> {
+        Self::send(self.request(Method::DELETE, &format!("/routes/{}", id))).await?;
+        Ok(())
+    }
+
+    pub async fn explore_routes(
+        &self,
+        query: &ExploreQuery,
+    ) -> Result<ListResponse<ExploreRouteResponse, ExploreMeta>, ClientError> {
+        Self::json(self.request(Method::GET, "/routes/explore").query(query)).await
+    }
+
+    /// Makes the route viewable by link and returns its share token.
+    pub async fn enable_share(&self, id: Uuid) -> Result<ShareResponse, ClientError> {
+        Self::json(self.request(Method::POST, &format!("/routes/{}/share", id))).await
+    }
+
+    pub async fn get_shared_route(&self, token: &str) -> Result<RouteResponse, ClientError> {
+        Self::json(self.request(Method::GET, &format!("/shared/{}", token))).await
+    }
+
+    pub async fn list_comments(&self, route_id: Uuid) -> Result<ListResponse<CommentResponse>, ClientError> {
+        Self::json(self.request(Method::GET, &format!("/routes/{}/comments", route_id))).await
+    }
+
+    pub async fn create_comment(
+        &self,
+        route_id: Uuid,
+        request: &CreateCommentRequest,
+    ) -> Result<CommentResponse, ClientError> {
+        Self::json(self.request(Method::POST, &format!("/routes/{}/comments", route_id)).json(request)).await
+    }
+
+    pub async fn list_notifications(
+        &self,
+        params: &NotificationListParams,
+    ) -> Result<ListResponse<NotificationResponse, NotificationsListMeta>, ClientError> {
+        Self::json(self.request(Method::GET, "/notifications").query(params)).await
+    }
+
+    /// Rebuilds the search index from the routes table. Requires an admin token.
+    pub async fn reindex_search(&self) -> Result<ReindexResponse, ClientError> {
+        Self::json(self.request(Method::POST, "/admin/search/reindex")).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_list_routes_sends_token_and_parses_envelope() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/routes"))
+            .and(header("authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": Uuid::nil(),
+                    "user_id": Uuid::nil(),
+                    "name": "Lake loop",
+                    "points": [{ "lat": 55.0, "lng": 37.0, "name": null, "segment_mode": null }],
+                    "created_at": "2026-01-01T00:00:00Z",
+                    "updated_at": "2026-01-01T00:00:00Z",
+                    "category_ids": [],
+                    "seasons": ["summer"],
+                }],
+                "pagination": { "limit": 1, "offset": 0, "total": 1 },
+                "request_id": "req-1",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ApiClient::new(format!("{}/", server.uri())).with_token("secret");
+        let routes = client.list_routes().await.unwrap();
+
+        assert_eq!(routes.data[0].name, "Lake loop");
+        assert_eq!(routes.pagination.total, 1);
+        assert_eq!(routes.request_id, "req-1");
+    }
+
+    #[tokio::test]
+    async fn test_explore_passes_query_parameters() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/routes/explore"))
+            .and(query_param("season", "winter"))
+            .and(query_param("limit", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [],
+                "pagination": { "limit": 5, "offset": 0, "total": 0 },
+                "request_id": "req-2",
+                "meta": { "facets": { "category_ids": {}, "difficulty": { "easy": 2 } } },
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let query = ExploreQuery {
+            season: Some("winter".to_string()),
+            limit: Some(5),
+            ..Default::default()
+        };
+        let result = ApiClient::new(server.uri()).explore_routes(&query).await.unwrap();
+
+        assert!(result.data.is_empty());
+        assert_eq!(result.meta.unwrap().facets.difficulty["easy"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_notifications_carry_unread_count() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/notifications"))
+            .and(query_param("limit", "10"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": Uuid::nil(),
+                    "user_id": Uuid::nil(),
+                    "notification_type": "comment",
+                    "route_id": Uuid::nil(),
+                    "actor_name": "Ann",
+                    "message": "Ann commented on Lake loop",
+                    "is_read": false,
+                    "created_at": "2026-01-01T00:00:00Z",
+                }],
+                "pagination": { "limit": 10, "offset": 0, "total": 1 },
+                "request_id": "req-3",
+                "meta": { "unread_count": 1 },
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = NotificationListParams {
+            limit: Some(10),
+            ..Default::default()
+        };
+        let result = ApiClient::new(server.uri()).with_token("secret").list_notifications(&params).await.unwrap();
+
+        assert_eq!(result.data[0].notification_type, "comment");
+        assert_eq!(result.meta.unwrap().unread_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_error_status_carries_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/api/v1/routes/{}", Uuid::nil())))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Route not found"))
+            .mount(&server)
+            .await;
+
+        let err = ApiClient::new(server.uri()).get_route(Uuid::nil()).await.unwrap_err();
+
+        match err {
+            ClientError::Status { status, body } => {
+                assert_eq!(status, 404);
+                assert_eq!(body, "Route not found");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentResponse {
+    pub id: Uuid,
+    pub route_id: Uuid,
+    pub user_id: Uuid,
+    pub author_name: String,
+    pub text: String,
+    /// False while the comment awaits the route owner's approval.
+    pub approved: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateCommentRequest {
+    #[validate(length(min = 1, max = 2000))]
+    pub text: String,
+    #[validate(length(min = 1, max = 100))]
+    pub author_name: String,
+    /// Milliseconds between opening the form and submitting it, sent by the
+    /// web client for spam screening.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_elapsed_ms: Option<u64>,
+}
+
+/// Who can comment on a route: `open`, `pre_moderated` or `locked`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentPolicyResponse {
+    pub policy: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SetCommentPolicyRequest {
+    #[validate(length(min = 1, max = 20))]
+    pub policy: String,
+}
@@ -0,0 +1,306 @@
+//! Versioned payloads of the NATS events exchanged by the routes service,
+//! the photo worker and the admin CLI.
+//!
+//! Every payload carries a `schema_version`. Consumers accept versions up to
+//! the one they were built with and reject newer ones instead of misreading
+//! them; payloads without the field predate versioning and count as version 1.
+//! Adding an optional field keeps the version, anything else bumps it.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::photo::{PhotoCompletedEvent, PhotoPipelineSettings, PhotoProcessTask};
+
+/// Subject on which route creations, edits and deletions are announced.
+pub const ROUTE_CHANGED_SUBJECT: &str = "routes.changed";
+
+/// A route was created, changed or deleted; consumers look up its current
+/// state themselves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteChangedEvent {
+    pub route_id: Uuid,
+}
+
+/// Subject on which significant edits of shared routes are published.
+pub const ROUTE_UPDATED_SUBJECT: &str = "routes.updated";
+
+/// A shared route was edited enough for the users who saved it to hear about.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteUpdatedEvent {
+    pub route_id: Uuid,
+    pub owner_id: Uuid,
+    pub route_name: String,
+    pub actor_name: String,
+}
+
+/// A payload published on NATS.
+pub trait Event: Serialize + DeserializeOwned {
+    /// Version written by this build.
+    const SCHEMA_VERSION: u16;
+}
+
+impl Event for PhotoProcessTask {
+    const SCHEMA_VERSION: u16 = 1;
+}
+
+impl Event for PhotoCompletedEvent {
+    const SCHEMA_VERSION: u16 = 1;
+}
+
+impl Event for PhotoPipelineSettings {
+    const SCHEMA_VERSION: u16 = 1;
+}
+
+impl Event for RouteChangedEvent {
+    const SCHEMA_VERSION: u16 = 1;
+}
+
+impl Event for RouteUpdatedEvent {
+    const SCHEMA_VERSION: u16 = 1;
+}
+
+#[derive(Debug)]
+pub enum EventError {
+    Json(serde_json::Error),
+    /// Published by a newer build than this consumer understands.
+    UnsupportedVersion { found: u16, supported: u16 },
+}
+
+impl fmt::Display for EventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventError::Json(e) => write!(f, "malformed event: {}", e),
+            EventError::UnsupportedVersion { found, supported } => {
+                write!(f, "event schema version {} is newer than supported {}", found, supported)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EventError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EventError::Json(e) => Some(e),
+            EventError::UnsupportedVersion { .. } => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for EventError {
+    fn from(e: serde_json::Error) -> Self {
+        EventError::Json(e)
+    }
+}
+
+#[derive(Serialize)]
+struct Versioned<'a, E> {
+    schema_version: u16,
+    #[serde(flatten)]
+    event: &'a E,
+}
+
+#[derive(Deserialize)]
+struct VersionProbe {
+    #[serde(default = "legacy_version")]
+    schema_version: u16,
+}
+
+fn legacy_version() -> u16 {
+    1
+}
+
+/// Serializes the event with its current schema version.
+pub fn encode<E: Event>(event: &E) -> Result<Vec<u8>, EventError> {
+    Ok(serde_json::to_vec(&Versioned {
+        schema_version: E::SCHEMA_VERSION,
+        event,
+    })?)
+}
+
+/// Parses an event, refusing versions newer than this build's.
+pub fn decode<E: Event>(payload: &[u8]) -> Result<E, EventError> {
+    let VersionProbe { schema_version } = serde_json::from_slice(payload)?;
+    if schema_version > E::SCHEMA_VERSION {
+        return Err(EventError::UnsupportedVersion {
+            found: schema_version,
+            supported: E::SCHEMA_VERSION,
+        });
+    }
+    Ok(serde_json::from_slice(payload)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::photo::{PhotoFormat, PhotoUpdate};
+    use guide_helper_domain::route::{PhotoData, PhotoStatus, RoutePoint};
+    use serde_json::json;
+
+    const ROUTE_ID: &str = "6f1c2a4e-8a8f-4a43-9d1e-3f2b8c7d9a10";
+    const USER_ID: &str = "0b9e6c1d-2f3a-4b5c-8d7e-9f0a1b2c3d4e";
+
+    fn route_id() -> Uuid {
+        ROUTE_ID.parse().unwrap()
+    }
+
+    fn user_id() -> Uuid {
+        USER_ID.parse().unwrap()
+    }
+
+    fn encoded<E: Event>(event: &E) -> serde_json::Value {
+        serde_json::from_slice(&encode(event).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_photo_task_wire_format() {
+        let task = PhotoProcessTask { route_id: route_id(), user_id: user_id(), point_indices: vec![0, 2] };
+
+        assert_eq!(
+            encoded(&task),
+            json!({ "schema_version": 1, "route_id": ROUTE_ID, "user_id": USER_ID, "point_indices": [0, 2] })
+        );
+    }
+
+    #[test]
+    fn test_photo_completed_wire_format() {
+        let event = PhotoCompletedEvent::PhotoUpdate(PhotoUpdate {
+            route_id: route_id(),
+            user_id: user_id(),
+            processed: 1,
+            failed: 0,
+            points: vec![RoutePoint {
+                lat: 55.75,
+                lng: 37.61,
+                name: None,
+                segment_mode: None,
+                photo: Some(PhotoData {
+                    original: "https://cdn.example/photo_0.jpg".to_string(),
+                    thumbnail_url: Some("https://cdn.example/thumb_0.jpg".to_string()),
+                    status: PhotoStatus::Done,
+                }),
+                address: None,
+            }],
+        });
+
+        assert_eq!(
+            encoded(&event),
+            json!({
+                "schema_version": 1,
+                "type": "photo_update",
+                "route_id": ROUTE_ID,
+                "user_id": USER_ID,
+                "processed": 1,
+                "failed": 0,
+                "points": [{
+                    "lat": 55.75,
+                    "lng": 37.61,
+                    "name": null,
+                    "segment_mode": null,
+                    "photo": {
+                        "original": "https://cdn.example/photo_0.jpg",
+                        "thumbnail_url": "https://cdn.example/thumb_0.jpg",
+                        "status": "done"
+                    }
+                }]
+            })
+        );
+        assert_eq!(decode::<PhotoCompletedEvent>(&encode(&event).unwrap()).unwrap(), event);
+    }
+
+    #[test]
+    fn test_settings_and_route_changed_wire_format() {
+        assert_eq!(
+            encoded(&PhotoPipelineSettings { format: PhotoFormat::Webp, ..Default::default() }),
+            json!({
+                "schema_version": 1,
+                "max_width": 1920,
+                "quality": 85,
+                "thumbnail_width": 300,
+                "thumbnail_quality": 75,
+                "format": "webp"
+            })
+        );
+        assert_eq!(
+            encoded(&RouteChangedEvent { route_id: route_id() }),
+            json!({ "schema_version": 1, "route_id": ROUTE_ID })
+        );
+    }
+
+    #[test]
+    fn test_route_updated_wire_format() {
+        let event = RouteUpdatedEvent {
+            route_id: route_id(),
+            owner_id: user_id(),
+            route_name: "Lake loop".to_string(),
+            actor_name: "anna".to_string(),
+        };
+
+        assert_eq!(
+            encoded(&event),
+            json!({
+                "schema_version": 1,
+                "route_id": ROUTE_ID,
+                "owner_id": USER_ID,
+                "route_name": "Lake loop",
+                "actor_name": "anna"
+            })
+        );
+        assert_eq!(decode::<RouteUpdatedEvent>(&encode(&event).unwrap()).unwrap(), event);
+    }
+
+    #[test]
+    fn test_decode_accepts_payloads_from_before_versioning() {
+        let task = json!({ "route_id": ROUTE_ID, "user_id": USER_ID, "point_indices": [1] });
+        let task: PhotoProcessTask = decode(task.to_string().as_bytes()).unwrap();
+        assert_eq!(task.point_indices, vec![1]);
+
+        let completed = json!({
+            "type": "photo_update",
+            "route_id": ROUTE_ID,
+            "user_id": USER_ID,
+            "processed": 2,
+            "failed": 1,
+            "points": []
+        });
+        let PhotoCompletedEvent::PhotoUpdate(update) = decode(completed.to_string().as_bytes()).unwrap() else {
+            panic!("expected a photo update");
+        };
+        assert_eq!(update.summary().failed, 1);
+
+        let changed: RouteChangedEvent = decode(json!({ "route_id": ROUTE_ID }).to_string().as_bytes()).unwrap();
+        assert_eq!(changed.route_id, route_id());
+
+        let updated = json!({ "route_id": ROUTE_ID, "owner_id": USER_ID, "route_name": "Loop", "actor_name": "anna" });
+        let updated: RouteUpdatedEvent = decode(updated.to_string().as_bytes()).unwrap();
+        assert_eq!(updated.owner_id, user_id());
+    }
+
+    #[test]
+    fn test_decode_ignores_added_fields_and_unknown_kinds() {
+        let task = json!({
+            "schema_version": 1,
+            "route_id": ROUTE_ID,
+            "user_id": USER_ID,
+            "point_indices": [],
+            "priority": "high"
+        });
+        assert!(decode::<PhotoProcessTask>(task.to_string().as_bytes()).is_ok());
+
+        let progress = json!({ "schema_version": 1, "type": "photo_progress", "route_id": ROUTE_ID, "done": 3 });
+        assert_eq!(
+            decode::<PhotoCompletedEvent>(progress.to_string().as_bytes()).unwrap(),
+            PhotoCompletedEvent::Unknown
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_newer_versions() {
+        let task = json!({ "schema_version": 2, "route_id": ROUTE_ID, "user_id": USER_ID, "point_indices": [] });
+
+        let result = decode::<PhotoProcessTask>(task.to_string().as_bytes());
+        assert!(matches!(result, Err(EventError::UnsupportedVersion { found: 2, supported: 1 })));
+    }
+}
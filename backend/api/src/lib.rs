@@ -0,0 +1,27 @@
+//! Request and response types of the guide_helper HTTP API and the NATS
+//! events between services, shared by the services, the photo worker and
+//! API consumers.
+//!
+//! Features:
+//! - `axum`: server-side helpers (`IntoResponse` for list envelopes, request-id extractor)
+//! - `client`: a typed reqwest client for the public endpoints
+
+pub mod auth;
+pub mod blocks;
+pub mod bookmarks;
+pub mod categories;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod comments;
+pub mod events;
+pub mod likes;
+pub mod list;
+pub mod notifications;
+pub mod photo;
+pub mod profile;
+pub mod ratings;
+pub mod routes;
+pub mod search;
+pub mod translations;
+pub mod users;
+pub mod v2;
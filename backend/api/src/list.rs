@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pagination {
+    pub limit: i64,
+    pub offset: i64,
+    pub total: i64,
+}
+
+/// Envelope returned by every v1 list endpoint:
+/// `{"data": [...], "pagination": {...}, "request_id": "..."}`, plus an
+/// optional endpoint-specific `meta` object.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>, M: Deserialize<'de>"))]
+pub struct ListResponse<T, M = ()> {
+    pub data: Vec<T>,
+    pub pagination: Pagination,
+    pub request_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<M>,
+}
+
+/// ID of the current request, taken from the `X-Request-Id` header set by
+/// the request-id layer. Falls back to a fresh UUID when the header is absent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl<T> ListResponse<T> {
+    /// A list that is returned in full; the page covers every item.
+    pub fn all(data: Vec<T>, request_id: RequestId) -> Self {
+        let total = data.len() as i64;
+        Self {
+            data,
+            pagination: Pagination { limit: total, offset: 0, total },
+            request_id: request_id.0,
+            meta: None,
+        }
+    }
+
+    /// One page of a larger list.
+    pub fn page(data: Vec<T>, limit: i64, offset: i64, total: i64, request_id: RequestId) -> Self {
+        Self {
+            data,
+            pagination: Pagination { limit, offset, total },
+            request_id: request_id.0,
+            meta: None,
+        }
+    }
+
+    pub fn with_meta<M>(self, meta: M) -> ListResponse<T, M> {
+        ListResponse {
+            data: self.data,
+            pagination: self.pagination,
+            request_id: self.request_id,
+            meta: Some(meta),
+        }
+    }
+}
+
+#[cfg(feature = "axum")]
+mod server {
+    use std::convert::Infallible;
+
+    use axum::{
+        extract::FromRequestParts,
+        http::{request::Parts, StatusCode},
+        response::{IntoResponse, Response},
+        Json,
+    };
+    use serde::Serialize;
+    use uuid::Uuid;
+
+    use super::{ListResponse, RequestId, REQUEST_ID_HEADER};
+
+    impl<S: Send + Sync> FromRequestParts<S> for RequestId {
+        type Rejection = Infallible;
+
+        async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+            let id = parts
+                .headers
+                .get(REQUEST_ID_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| Uuid::new_v4().to_string());
+            Ok(Self(id))
+        }
+    }
+
+    impl<T: Serialize, M: Serialize> IntoResponse for ListResponse<T, M> {
+        fn into_response(self) -> Response {
+            (StatusCode::OK, Json(self)).into_response()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use axum::http::Request;
+
+        #[tokio::test]
+        async fn test_request_id_from_header_or_generated() {
+            let (mut parts, _) = Request::builder()
+                .header(REQUEST_ID_HEADER, "abc")
+                .body(())
+                .unwrap()
+                .into_parts();
+            let id = RequestId::from_request_parts(&mut parts, &()).await.unwrap();
+            assert_eq!(id.0, "abc");
+
+            let (mut parts, _) = Request::builder().body(()).unwrap().into_parts();
+            let id = RequestId::from_request_parts(&mut parts, &()).await.unwrap();
+            assert!(Uuid::parse_str(&id.0).is_ok());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Meta {
+        unread_count: i64,
+    }
+
+    #[test]
+    fn test_all_covers_whole_list() {
+        let response = ListResponse::all(vec![1, 2, 3], RequestId("req-1".to_string()));
+        let json = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "data": [1, 2, 3],
+                "pagination": { "limit": 3, "offset": 0, "total": 3 },
+                "request_id": "req-1",
+            })
+        );
+    }
+
+    #[test]
+    fn test_page_with_meta_round_trip() {
+        let response = ListResponse::page(vec!["a".to_string()], 20, 40, 41, RequestId("req-2".to_string()))
+            .with_meta(Meta { unread_count: 5 });
+        let json = serde_json::to_string(&response).unwrap();
+
+        let parsed: ListResponse<String, Meta> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.pagination, Pagination { limit: 20, offset: 40, total: 41 });
+        assert_eq!(parsed.meta, Some(Meta { unread_count: 5 }));
+    }
+
+    #[test]
+    fn test_meta_is_optional_when_parsing() {
+        let parsed: ListResponse<i32> = serde_json::from_str(
+            r#"{"data":[],"pagination":{"limit":0,"offset":0,"total":0},"request_id":"r"}"#,
+        )
+        .unwrap();
+        assert!(parsed.meta.is_none());
+    }
+}
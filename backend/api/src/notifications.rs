@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationListParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub notification_type: String,
+    pub route_id: Uuid,
+    pub actor_name: String,
+    pub message: String,
+    pub is_read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// `meta` of the notifications list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsListMeta {
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnreadCountResponse {
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationPreferencesBody {
+    pub preferences: HashMap<String, bool>,
+}
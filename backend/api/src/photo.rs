@@ -0,0 +1,187 @@
+use guide_helper_domain::route::RoutePoint;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// NATS subject on which routes with new inline photos are announced.
+pub const PHOTO_PROCESS_SUBJECT: &str = "photos.process";
+
+/// JetStream work queue holding [`PHOTO_PROCESS_SUBJECT`] tasks.
+pub const PHOTO_STREAM_NAME: &str = "PHOTOS";
+
+/// Durable consumer the photo workers pull tasks through.
+pub const PHOTO_CONSUMER_NAME: &str = "photo-worker";
+
+/// Deliveries of a task before the worker gives up on it. Tasks that hit
+/// the limit stay in the stream until re-driven or purged.
+pub const PHOTO_MAX_DELIVER: i64 = 3;
+
+/// Asks the photo worker to upload and thumbnail the inline photos of a route.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhotoProcessTask {
+    pub route_id: Uuid,
+    pub user_id: Uuid,
+    pub point_indices: Vec<usize>,
+}
+
+/// Prefix of the per-route subjects on which the worker reports finished photos.
+pub const PHOTO_COMPLETED_SUBJECT_PREFIX: &str = "photos.completed.";
+
+pub fn photo_completed_subject(route_id: Uuid) -> String {
+    format!("{}{}", PHOTO_COMPLETED_SUBJECT_PREFIX, route_id)
+}
+
+/// JetStream stream holding completions until the routes service acks them,
+/// so events published while it restarts are not lost.
+pub const PHOTO_COMPLETED_STREAM_NAME: &str = "PHOTOS_COMPLETED";
+
+/// Durable consumer shared by the routes replicas; each completion is
+/// handled by one of them.
+pub const PHOTO_COMPLETED_CONSUMER_NAME: &str = "routes-photo-completed";
+
+/// Published by the worker on [`photo_completed_subject`]. The payload is
+/// also forwarded to WS clients as is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PhotoCompletedEvent {
+    PhotoUpdate(PhotoUpdate),
+    /// A kind added by a newer worker; consumers skip it.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A processing task finished.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhotoUpdate {
+    pub route_id: Uuid,
+    pub user_id: Uuid,
+    /// Photos uploaded with a thumbnail.
+    pub processed: usize,
+    pub failed: usize,
+    /// The route's points after the task, photo URLs included.
+    pub points: Vec<RoutePoint>,
+}
+
+impl PhotoUpdate {
+    pub fn summary(&self) -> PhotoCompletedSummary {
+        PhotoCompletedSummary {
+            route_id: self.route_id,
+            user_id: self.user_id,
+            processed: self.processed,
+            failed: self.failed,
+        }
+    }
+}
+
+/// Outcome of a processing task, without the points.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhotoCompletedSummary {
+    pub route_id: Uuid,
+    pub user_id: Uuid,
+    /// Photos uploaded with a thumbnail.
+    pub processed: usize,
+    pub failed: usize,
+}
+
+/// Settings key under which the photo pipeline profile is stored, and the
+/// core NATS subject announcing changes to it.
+pub const PHOTO_PIPELINE_SETTINGS_KEY: &str = "photo_pipeline";
+pub const PHOTO_SETTINGS_UPDATED_SUBJECT: &str = "photos.settings.updated";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PhotoFormat {
+    #[default]
+    Jpeg,
+    /// Lossless; the quality settings do not apply.
+    Webp,
+}
+
+impl PhotoFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PhotoFormat::Jpeg => "jpg",
+            PhotoFormat::Webp => "webp",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            PhotoFormat::Jpeg => "image/jpeg",
+            PhotoFormat::Webp => "image/webp",
+        }
+    }
+}
+
+/// How the photo worker resizes and encodes photos. Edited by admins and
+/// applied by running workers without a restart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhotoPipelineSettings {
+    /// Wider photos are scaled down to this width.
+    pub max_width: u32,
+    /// JPEG quality of full-size photos, 1-100.
+    pub quality: u8,
+    pub thumbnail_width: u32,
+    pub thumbnail_quality: u8,
+    pub format: PhotoFormat,
+}
+
+impl Default for PhotoPipelineSettings {
+    fn default() -> Self {
+        Self {
+            max_width: 1920,
+            quality: 85,
+            thumbnail_width: 300,
+            thumbnail_quality: 75,
+            format: PhotoFormat::Jpeg,
+        }
+    }
+}
+
+impl PhotoPipelineSettings {
+    pub const MIN_WIDTH: u32 = 16;
+    pub const MAX_WIDTH: u32 = 8192;
+
+    pub fn validate(&self) -> Result<(), String> {
+        let widths = Self::MIN_WIDTH..=Self::MAX_WIDTH;
+        if !widths.contains(&self.max_width) || !widths.contains(&self.thumbnail_width) {
+            return Err(format!(
+                "Widths must be between {} and {} pixels",
+                Self::MIN_WIDTH,
+                Self::MAX_WIDTH
+            ));
+        }
+        if self.thumbnail_width > self.max_width {
+            return Err("Thumbnail width must not exceed the photo width".to_string());
+        }
+        if !(1..=100).contains(&self.quality) || !(1..=100).contains(&self.thumbnail_quality) {
+            return Err("Quality must be between 1 and 100".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_settings_validation() {
+        assert!(PhotoPipelineSettings::default().validate().is_ok());
+
+        let tiny = PhotoPipelineSettings { max_width: 8, ..Default::default() };
+        assert!(tiny.validate().is_err());
+
+        let big_thumb = PhotoPipelineSettings { thumbnail_width: 4000, ..Default::default() };
+        assert!(big_thumb.validate().is_err());
+
+        let zero_quality = PhotoPipelineSettings { quality: 0, ..Default::default() };
+        assert!(zero_quality.validate().is_err());
+    }
+
+    #[test]
+    fn test_pipeline_settings_serialize_format_lowercase() {
+        let settings = PhotoPipelineSettings { format: PhotoFormat::Webp, ..Default::default() };
+        let json = serde_json::to_value(&settings).unwrap();
+        assert_eq!(json["format"], "webp");
+    }
+}
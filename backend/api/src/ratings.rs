@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SetRatingRequest {
+    #[validate(range(min = 1, max = 5))]
+    pub rating: i16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingAggregateResponse {
+    pub average: f64,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserRatingResponse {
+    pub rating: Option<i16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetRatingResponse {
+    pub average: f64,
+    pub count: i64,
+    pub user_rating: i16,
+}
@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+pub use guide_helper_domain::route::{PhotoData, PhotoStatus, RoutePoint, RouteVisibility};
+pub use guide_helper_domain::route_stats::{ModeStats, RouteStats};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub points: Vec<RoutePoint>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Signed token opening the route at `/shared/{token}`; only sent to the owner.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub share_token: Option<String>,
+    #[serde(default)]
+    pub visibility: RouteVisibility,
+    pub category_ids: Vec<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_location: Option<String>,
+    pub seasons: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Straight-line distance and estimated duration.
+    #[serde(default)]
+    pub stats: RouteStats,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListRoutesQuery {
+    /// Also return archived routes.
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Part of the route name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<Uuid>,
+    /// Only routes created at or after this time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only routes created before this time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only routes in this collection of the caller.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_id: Option<Uuid>,
+    /// `newest` (default), `oldest`, `name` or `updated`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateRouteRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+    #[validate(length(min = 1), nested)]
+    pub points: Vec<RoutePoint>,
+    #[serde(default)]
+    pub category_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub seasons: Vec<String>,
+    /// Milliseconds between opening the form and submitting it, sent by the
+    /// web client for spam screening.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_elapsed_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct UpdateRouteRequest {
+    #[validate(length(min = 1, max = 200))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[validate(nested)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points: Option<Vec<RoutePoint>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_ids: Option<Vec<Uuid>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seasons: Option<Vec<String>>,
+}
+
+/// One change of a point edit. Operations apply in order, each to the points
+/// as the previous ones left them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PointOperation {
+    /// Puts `point` before the point at `index`; the point count appends it.
+    Insert { index: usize, point: RoutePoint },
+    /// Takes the point at `from` out and puts it back at `to`.
+    Move { from: usize, to: usize },
+    Delete { index: usize },
+    /// Replaces the point at `index`.
+    Update { index: usize, point: RoutePoint },
+}
+
+/// Edits some points of a route; the other points are left as stored.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct PatchPointsRequest {
+    /// `updated_at` of the route the operations were written against; the
+    /// indexes only hold for that version.
+    pub expected_updated_at: DateTime<Utc>,
+    #[validate(length(min = 1, max = 1000))]
+    pub operations: Vec<PointOperation>,
+}
+
+/// Joins routes of the caller, in the given order, into a new one; the
+/// source routes are kept.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct MergeRoutesRequest {
+    /// Routes in walking order, each at most once.
+    pub route_ids: Vec<Uuid>,
+    /// Routes of `route_ids` to walk from their end to their start; an id
+    /// missing from `route_ids` fails the request.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reversed_route_ids: Vec<Uuid>,
+    /// Name of the new route; defaults to "<first> + <second> + ...".
+    #[validate(length(min = 1, max = 200))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Splits a route at a point, which ends the first part and starts the second.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitRouteRequest {
+    pub point_index: usize,
+    /// Keep the first part in the original route instead of creating two
+    /// new routes.
+    #[serde(default)]
+    pub truncate_original: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitRouteResponse {
+    pub first: RouteResponse,
+    pub second: RouteResponse,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportRouteParams {
+    /// Import even when a nearly identical route already exists.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Body of `POST /api/v1/routes/import/url`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportUrlRequest {
+    /// Link to a GeoJSON or GPX file.
+    pub url: String,
+}
+
+/// Body of the 409 returned when an imported route duplicates an existing one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateRouteResponse {
+    pub error: String,
+    pub message: String,
+    pub existing_route_id: Uuid,
+}
+
+/// Outcome of one file of a multi-file import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportFileResult {
+    pub filename: Option<String>,
+    /// `created`, `duplicate` or `failed`.
+    pub status: String,
+    pub route: Option<RouteResponse>,
+    /// Set for `duplicate`: the route the file matched.
+    pub existing_route_id: Option<Uuid>,
+    pub error: Option<String>,
+}
+
+/// Report returned when several files are imported in one request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportBatchResponse {
+    pub created: usize,
+    pub failed: usize,
+    pub files: Vec<ImportFileResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShareResponse {
+    /// Signed token opening the route at `/shared/{token}`.
+    pub share_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetVisibilityRequest {
+    pub visibility: RouteVisibility,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExploreQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub season: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExploreRouteResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub points_count: i64,
+    pub created_at: DateTime<Utc>,
+    /// Signed token opening the route at `/shared/{token}`.
+    pub link_token: String,
+    pub likes_count: i64,
+    pub avg_rating: f64,
+    pub ratings_count: i64,
+    #[serde(default)]
+    pub comments_count: i64,
+    #[serde(default)]
+    pub bookmarks_count: i64,
+    pub category_ids: Vec<Uuid>,
+    pub seasons: Vec<String>,
+    /// Geocoded names of the first and last points.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_thumbnail_url: Option<String>,
+    /// Straight-line distance and estimated duration.
+    #[serde(default)]
+    pub stats: RouteStats,
+}
+
+/// How many explore results each filter value would yield.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExploreFacets {
+    /// Counted with every current filter except the category one.
+    pub category_ids: BTreeMap<Uuid, i64>,
+    /// "easy", "moderate" or "hard". Routes whose distance has not been
+    /// computed yet are left out.
+    pub difficulty: BTreeMap<String, i64>,
+}
+
+/// `meta` of the explore list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExploreMeta {
+    pub facets: ExploreFacets,
+}
+
+/// Picks the photo of the point at `point_index` as the route cover;
+/// `None` clears the cover.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SetCoverRequest {
+    #[serde(default)]
+    pub point_index: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_thumbnail_url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_request_omits_unset_fields() {
+        let request = UpdateRouteRequest {
+            name: Some("Loop".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_value(&request).unwrap(), serde_json::json!({ "name": "Loop" }));
+    }
+}
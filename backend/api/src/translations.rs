@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationResponse {
+    pub key: String,
+    pub locale: String,
+    pub value: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Creates or replaces the text of `key` in `locale`. Category names use
+/// `category.<id>`, notification templates `notification.<type>` with
+/// `{placeholder}` parameters, difficulty labels `difficulty.<level>`.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SetTranslationRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub key: String,
+    #[validate(length(min = 2, max = 10))]
+    pub locale: String,
+    #[validate(length(min = 1, max = 1000))]
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationListParams {
+    pub locale: String,
+}
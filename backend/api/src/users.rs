@@ -0,0 +1,51 @@
+//! Admin user management DTOs of the auth service.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsersQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserListItem {
+    pub id: Uuid,
+    pub email: String,
+    pub name: Option<String>,
+    pub role: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateRoleRequest {
+    pub role: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsResponse {
+    pub total_users: i64,
+    pub by_role: Vec<RoleStatItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleStatItem {
+    pub role: String,
+    pub count: i64,
+}
+
+/// Access token letting an admin act as another user. It carries an `act`
+/// claim naming the admin and cannot be refreshed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpersonationResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub user_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
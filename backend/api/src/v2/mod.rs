@@ -0,0 +1,4 @@
+//! Types of the `/api/v2` endpoints. Unlike v1, route metadata and point
+//! geometry are separate resources and photos are referenced by URL only.
+
+pub mod routes;
@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use guide_helper_domain::route::{PhotoData, PhotoStatus, RoutePoint, RouteVisibility};
+
+/// Route metadata without its points, returned by `GET /api/v2/routes/{id}`
+/// and by every v2 list endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteSummary {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub points_count: usize,
+    pub photos_count: usize,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Signed token opening the route at `/shared/{token}`; only sent to the owner.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub share_token: Option<String>,
+    #[serde(default)]
+    pub visibility: RouteVisibility,
+    pub category_ids: Vec<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_location: Option<String>,
+    pub seasons: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+/// A photo attached to a point. Inline `data:` originals that the photo
+/// worker has not uploaded yet are never sent; `url` stays empty until then.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhotoRef {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+    pub status: PhotoStatus,
+}
+
+impl From<&PhotoData> for PhotoRef {
+    fn from(photo: &PhotoData) -> Self {
+        Self {
+            url: Some(photo.original.clone()).filter(|url| !url.starts_with("data:")),
+            thumbnail_url: photo.thumbnail_url.clone(),
+            status: photo.status.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutePointResponse {
+    pub lat: f64,
+    pub lng: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segment_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub photo: Option<PhotoRef>,
+}
+
+impl From<&RoutePoint> for RoutePointResponse {
+    fn from(point: &RoutePoint) -> Self {
+        Self {
+            lat: point.lat,
+            lng: point.lng,
+            name: point.name.clone(),
+            segment_mode: point.segment_mode.clone(),
+            photo: point.photo.as_ref().map(PhotoRef::from),
+        }
+    }
+}
+
+/// Point geometry of a route, returned by `GET /api/v2/routes/{id}/points`.
+/// `updated_at` matches the summary so clients can tell whether a cached
+/// copy of the points is still current.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutePointsResponse {
+    pub route_id: Uuid,
+    pub updated_at: DateTime<Utc>,
+    pub points: Vec<RoutePointResponse>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point_with_photo(original: &str, status: PhotoStatus) -> RoutePoint {
+        RoutePoint {
+            lat: 55.75,
+            lng: 37.61,
+            name: None,
+            segment_mode: None,
+            photo: Some(PhotoData {
+                original: original.to_string(),
+                thumbnail_url: None,
+                status,
+            }),
+            address: None,
+        }
+    }
+
+    #[test]
+    fn test_inline_photo_data_is_not_sent() {
+        let point = point_with_photo("data:image/jpeg;base64,AAAA", PhotoStatus::Pending);
+
+        let json = serde_json::to_value(RoutePointResponse::from(&point)).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({ "lat": 55.75, "lng": 37.61, "photo": { "status": "pending" } })
+        );
+    }
+
+    #[test]
+    fn test_uploaded_photo_is_referenced_by_url() {
+        let point = point_with_photo("https://cdn.example.com/photos/1.jpg", PhotoStatus::Done);
+
+        let response = RoutePointResponse::from(&point);
+
+        assert_eq!(
+            response.photo.unwrap().url.as_deref(),
+            Some("https://cdn.example.com/photos/1.jpg")
+        );
+    }
+}
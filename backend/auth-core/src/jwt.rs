@@ -12,7 +12,6 @@ pub enum JwtError {
     #[error("Token expired")]
     TokenExpired,
     #[error("Invalid token")]
-    #[allow(dead_code)]
     InvalidToken,
 }
 
@@ -20,10 +19,23 @@ pub enum JwtError {
 pub struct Claims {
     pub sub: String,      // Subject (user id)
     pub email: String,    // User email
-    pub role: String,     // User role
+    #[serde(default = "default_role")]
+    pub role: String,     // User role (backward compat: defaults to "user")
     pub exp: i64,         // Expiration time
     pub iat: i64,         // Issued at
     pub token_type: TokenType,
+    /// Set on impersonation tokens: the admin acting as `sub` (RFC 8693).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Actor {
+    pub sub: String,
+}
+
+fn default_role() -> String {
+    "user".to_string()
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -37,17 +49,41 @@ pub struct JwtService {
     secret: String,
     access_token_duration: Duration,
     refresh_token_duration: Duration,
+    impersonation_token_duration: Duration,
 }
 
 impl JwtService {
-    pub fn new(secret: String, access_minutes: i64, refresh_days: i64) -> Self {
+    /// Service signing and validating with `secret`. Services that only
+    /// validate tokens need nothing else; the issuer sets the lifetimes with
+    /// the `with_*` builders.
+    pub fn new(secret: String) -> Self {
         Self {
             secret,
-            access_token_duration: Duration::minutes(access_minutes),
-            refresh_token_duration: Duration::days(refresh_days),
+            access_token_duration: Duration::minutes(15),
+            refresh_token_duration: Duration::days(7),
+            impersonation_token_duration: Duration::minutes(15),
         }
     }
 
+    pub fn with_access_minutes(mut self, minutes: i64) -> Self {
+        self.access_token_duration = Duration::minutes(minutes);
+        self
+    }
+
+    pub fn with_refresh_days(mut self, days: i64) -> Self {
+        self.refresh_token_duration = Duration::days(days);
+        self
+    }
+
+    pub fn with_impersonation_minutes(mut self, minutes: i64) -> Self {
+        self.impersonation_token_duration = Duration::minutes(minutes);
+        self
+    }
+
+    pub fn impersonation_token_duration(&self) -> Duration {
+        self.impersonation_token_duration
+    }
+
     #[tracing::instrument(skip(self, email, role), fields(user_id = %user_id))]
     pub fn generate_access_token(&self, user_id: Uuid, email: String, role: &str) -> Result<String, JwtError> {
         self.generate_token(user_id, email, role, TokenType::Access, self.access_token_duration)
@@ -58,6 +94,29 @@ impl JwtService {
         self.generate_token(user_id, email, role, TokenType::Refresh, self.refresh_token_duration)
     }
 
+    /// Short-lived access token for `user_id` marked with the admin acting
+    /// as them. There is no refresh token to go with it.
+    #[tracing::instrument(skip(self, email, role), fields(user_id = %user_id, admin_id = %admin_id))]
+    pub fn generate_impersonation_token(
+        &self,
+        user_id: Uuid,
+        email: String,
+        role: &str,
+        admin_id: Uuid,
+    ) -> Result<String, JwtError> {
+        let now = Utc::now();
+        let claims = Claims {
+            sub: user_id.to_string(),
+            email,
+            role: role.to_string(),
+            exp: (now + self.impersonation_token_duration).timestamp(),
+            iat: now.timestamp(),
+            token_type: TokenType::Access,
+            act: Some(Actor { sub: admin_id.to_string() }),
+        };
+        self.encode(&claims)
+    }
+
     fn generate_token(
         &self,
         user_id: Uuid,
@@ -77,11 +136,15 @@ impl JwtService {
             exp,
             iat,
             token_type,
+            act: None,
         };
+        self.encode(&claims)
+    }
 
+    fn encode(&self, claims: &Claims) -> Result<String, JwtError> {
         jsonwebtoken::encode(
             &jsonwebtoken::Header::default(),
-            &claims,
+            claims,
             &jsonwebtoken::EncodingKey::from_secret(self.secret.as_bytes()),
         )
         .map_err(|e| JwtError::TokenGenerationError(e.to_string()))
@@ -108,7 +171,6 @@ impl JwtService {
         Ok(token_data.claims)
     }
 
-    #[allow(dead_code)]
     #[tracing::instrument(skip(self, token))]
     pub fn decode_token_without_validation(&self, token: &str) -> Result<Claims, JwtError> {
         let mut validation = jsonwebtoken::Validation::default();
@@ -131,12 +193,14 @@ mod tests {
     use super::*;
 
     fn create_test_jwt_service() -> JwtService {
-        JwtService::new("test_secret_key_123".to_string(), 15, 7)
+        JwtService::new("test_secret_key_123".to_string())
     }
 
     #[test]
     fn test_jwt_service_creation() {
-        let service = JwtService::new("secret".to_string(), 30, 14);
+        let service = JwtService::new("secret".to_string())
+            .with_access_minutes(30)
+            .with_refresh_days(14);
         assert_eq!(service.secret, "secret");
         assert_eq!(service.access_token_duration, Duration::minutes(30));
         assert_eq!(service.refresh_token_duration, Duration::days(14));
@@ -228,8 +292,8 @@ mod tests {
 
     #[test]
     fn test_validate_token_with_wrong_secret() {
-        let service1 = JwtService::new("secret1".to_string(), 15, 7);
-        let service2 = JwtService::new("secret2".to_string(), 15, 7);
+        let service1 = JwtService::new("secret1".to_string());
+        let service2 = JwtService::new("secret2".to_string());
 
         let user_id = Uuid::new_v4();
         let email = "test@example.com".to_string();
@@ -283,6 +347,25 @@ mod tests {
         assert!(exp_diff < 5);
     }
 
+    #[test]
+    fn test_impersonation_token_names_the_admin() {
+        let service = create_test_jwt_service().with_impersonation_minutes(5);
+        let (user_id, admin_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let token = service
+            .generate_impersonation_token(user_id, "user@example.com".to_string(), "user", admin_id)
+            .unwrap();
+        let claims = service.validate_token(&token).unwrap();
+
+        assert_eq!(claims.sub, user_id.to_string());
+        assert_eq!(claims.token_type, TokenType::Access);
+        assert_eq!(claims.act, Some(Actor { sub: admin_id.to_string() }));
+        assert!((claims.exp - (Utc::now() + Duration::minutes(5)).timestamp()).abs() < 5);
+
+        let regular = service.generate_access_token(user_id, "user@example.com".to_string(), "user").unwrap();
+        assert_eq!(service.validate_token(&regular).unwrap().act, None);
+    }
+
     #[test]
     fn test_token_with_empty_email() {
         let service = create_test_jwt_service();
@@ -306,4 +389,25 @@ mod tests {
 
         assert_ne!(token1, token2);
     }
+
+    #[test]
+    fn test_claims_without_role_default_to_user() {
+        let service = create_test_jwt_service();
+        let now = Utc::now().timestamp();
+        let claims = serde_json::json!({
+            "sub": Uuid::new_v4().to_string(),
+            "email": "user@example.com",
+            "exp": now + 60,
+            "iat": now,
+            "token_type": "Access",
+        });
+        let token = jsonwebtoken::encode(
+            &jsonwebtoken::Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret(b"test_secret_key_123"),
+        )
+        .unwrap();
+
+        assert_eq!(service.validate_token(&token).unwrap().role, "user");
+    }
 }
@@ -0,0 +1,18 @@
+//! Access token handling shared by every service that authenticates users:
+//! the token claims, issuing and validating tokens, and turning a bearer token
+//! into the [`AuthenticatedUser`] handlers work with, and the role guards
+//! staff-only endpoints sit behind.
+//!
+//! Features:
+//! - `axum`: `IntoResponse` for [`AuthError`], an `AuthenticatedUser` extractor
+//!   and the `require_role` middleware
+
+pub mod jwt;
+pub mod role;
+pub mod user;
+
+pub use jwt::{Actor, Claims, JwtError, JwtService, TokenType};
+#[cfg(feature = "axum")]
+pub use role::require_role;
+pub use role::{RequireRole, ROLE_ADMIN, ROLE_MODERATOR, ROLE_USER};
+pub use user::{bearer_token, AuthError, AuthenticatedUser};
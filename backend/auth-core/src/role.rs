@@ -0,0 +1,148 @@
+use crate::user::AuthenticatedUser;
+
+pub const ROLE_ADMIN: &str = "admin";
+pub const ROLE_MODERATOR: &str = "moderator";
+pub const ROLE_USER: &str = "user";
+
+/// Roles a subtree of the API is restricted to. Applied at the routing layer
+/// with [`require_role`], so handlers behind it never see other callers.
+///
+/// Impersonation tokens never pass: an admin acting as a user has that user's
+/// access, not staff access.
+#[derive(Clone, Copy, Debug)]
+pub struct RequireRole {
+    roles: &'static [&'static str],
+    denied: &'static str,
+}
+
+impl RequireRole {
+    pub const ADMIN: Self = Self {
+        roles: &[ROLE_ADMIN],
+        denied: "Admin access required",
+    };
+    pub const MODERATOR: Self = Self {
+        roles: &[ROLE_ADMIN, ROLE_MODERATOR],
+        denied: "Moderator access required",
+    };
+
+    pub fn allows(&self, user: &AuthenticatedUser) -> bool {
+        self.roles.contains(&user.effective_role())
+    }
+
+    /// Message returned to callers the guard turns away.
+    pub fn denied(&self) -> &'static str {
+        self.denied
+    }
+}
+
+#[cfg(feature = "axum")]
+mod server {
+    use axum::{
+        extract::{Request, State},
+        middleware::Next,
+        response::Response,
+    };
+
+    use super::RequireRole;
+    use crate::user::{AuthError, AuthenticatedUser};
+
+    /// Rejects callers without one of the guard's roles with 403 before any
+    /// handler runs. Must run inside the service's auth middleware; add it with
+    /// `route_layer` so unknown paths still answer 404.
+    pub async fn require_role(
+        State(guard): State<RequireRole>,
+        request: Request,
+        next: Next,
+    ) -> Result<Response, AuthError> {
+        let Some(user) = request.extensions().get::<AuthenticatedUser>() else {
+            return Err(AuthError::MissingToken);
+        };
+        if !guard.allows(user) {
+            tracing::warn!(
+                user_id = %user.user_id,
+                role = %user.role,
+                uri = %request.uri(),
+                "role guard rejected request"
+            );
+            return Err(AuthError::Forbidden(guard.denied()));
+        }
+        Ok(next.run(request).await)
+    }
+}
+
+#[cfg(feature = "axum")]
+pub use server::require_role;
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn user(role: &str, impersonator_id: Option<Uuid>) -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: Uuid::new_v4(),
+            email: "user@example.com".to_string(),
+            role: role.to_string(),
+            impersonator_id,
+        }
+    }
+
+    #[test]
+    fn test_admin_guard_allows_only_admins() {
+        assert!(RequireRole::ADMIN.allows(&user("admin", None)));
+        assert!(!RequireRole::ADMIN.allows(&user("moderator", None)));
+        assert!(!RequireRole::ADMIN.allows(&user("user", None)));
+    }
+
+    #[test]
+    fn test_moderator_guard_allows_admins_and_moderators() {
+        assert!(RequireRole::MODERATOR.allows(&user("admin", None)));
+        assert!(RequireRole::MODERATOR.allows(&user("moderator", None)));
+        assert!(!RequireRole::MODERATOR.allows(&user("user", None)));
+    }
+
+    #[test]
+    fn test_guards_reject_impersonation_tokens() {
+        assert!(!RequireRole::ADMIN.allows(&user("admin", Some(Uuid::new_v4()))));
+        assert!(!RequireRole::MODERATOR.allows(&user("moderator", Some(Uuid::new_v4()))));
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn test_require_role_rejects_before_handler_runs() {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode},
+            middleware,
+            routing::get,
+            Router,
+        };
+        use tower::ServiceExt;
+
+        let app = |caller: AuthenticatedUser| {
+            Router::new()
+                .route("/admin", get(|| async { "ok" }))
+                .route_layer(middleware::from_fn_with_state(RequireRole::ADMIN, require_role))
+                .layer(axum::Extension(caller))
+        };
+
+        let response = app(user("user", None))
+            .oneshot(Request::get("/admin").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app(user("admin", None))
+            .oneshot(Request::get("/admin").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app(user("user", None))
+            .oneshot(Request::get("/missing").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
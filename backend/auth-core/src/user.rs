@@ -0,0 +1,260 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::jwt::{JwtError, JwtService, TokenType};
+use crate::role::ROLE_USER;
+
+/// The caller of an authenticated request, as read from their access token.
+#[derive(Clone, Debug)]
+pub struct AuthenticatedUser {
+    pub user_id: Uuid,
+    pub email: String,
+    pub role: String,
+    /// Admin acting as this user through an impersonation token.
+    pub impersonator_id: Option<Uuid>,
+}
+
+impl AuthenticatedUser {
+    /// Role to authorize the request with. An admin acting as someone through
+    /// an impersonation token gets plain user access, whatever the role of
+    /// the impersonated account.
+    pub fn effective_role(&self) -> &str {
+        if self.impersonator_id.is_some() {
+            ROLE_USER
+        } else {
+            &self.role
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AuthError {
+    #[error("Missing or invalid Authorization header")]
+    MissingToken,
+    #[error("Invalid token: {0}")]
+    InvalidToken(JwtError),
+    #[error("Invalid token type")]
+    InvalidTokenType,
+    #[error("Invalid user ID in token")]
+    InvalidUserId,
+    #[error("Invalid token actor")]
+    InvalidActor,
+    #[error("{0}")]
+    Forbidden(&'static str),
+}
+
+/// The token of an `Authorization: Bearer <token>` header value.
+pub fn bearer_token(authorization: &str) -> Option<&str> {
+    authorization.strip_prefix("Bearer ")
+}
+
+impl JwtService {
+    /// Validates an access token and reads the user it was issued to.
+    /// Refresh tokens are rejected.
+    pub fn authenticate(&self, token: &str) -> Result<AuthenticatedUser, AuthError> {
+        let claims = self.validate_token(token).map_err(|e| {
+            tracing::warn!(?e, "invalid token");
+            AuthError::InvalidToken(e)
+        })?;
+
+        if claims.token_type != TokenType::Access {
+            tracing::warn!("attempted to use non-access token for authentication");
+            return Err(AuthError::InvalidTokenType);
+        }
+
+        let user_id = Uuid::parse_str(&claims.sub).map_err(|e| {
+            tracing::error!(?e, "failed to parse user_id from token");
+            AuthError::InvalidUserId
+        })?;
+
+        let impersonator_id = claims
+            .act
+            .map(|act| Uuid::parse_str(&act.sub))
+            .transpose()
+            .map_err(|e| {
+                tracing::warn!(?e, "invalid actor in token");
+                AuthError::InvalidActor
+            })?;
+
+        Ok(AuthenticatedUser {
+            user_id,
+            email: claims.email,
+            role: claims.role,
+            impersonator_id,
+        })
+    }
+
+    /// [`authenticate`](Self::authenticate) for the value of an
+    /// `Authorization` header, if the request had one.
+    pub fn authenticate_header(&self, authorization: Option<&str>) -> Result<AuthenticatedUser, AuthError> {
+        let Some(token) = authorization.and_then(bearer_token) else {
+            tracing::warn!("missing or invalid authorization header");
+            return Err(AuthError::MissingToken);
+        };
+        self.authenticate(token)
+    }
+}
+
+#[cfg(feature = "axum")]
+mod server {
+    use axum::{
+        extract::FromRequestParts,
+        http::{request::Parts, StatusCode},
+        response::{IntoResponse, Response},
+    };
+
+    use super::{AuthError, AuthenticatedUser};
+
+    impl AuthError {
+        pub fn status(&self) -> StatusCode {
+            match self {
+                AuthError::InvalidUserId => StatusCode::INTERNAL_SERVER_ERROR,
+                AuthError::Forbidden(_) => StatusCode::FORBIDDEN,
+                _ => StatusCode::UNAUTHORIZED,
+            }
+        }
+    }
+
+    impl IntoResponse for AuthError {
+        fn into_response(self) -> Response {
+            (self.status(), self.to_string()).into_response()
+        }
+    }
+
+    /// Reads the user the service's auth middleware stored in the request
+    /// extensions. Requests that did not pass through it are rejected as
+    /// unauthenticated.
+    impl<S: Send + Sync> FromRequestParts<S> for AuthenticatedUser {
+        type Rejection = AuthError;
+
+        async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+            parts
+                .extensions
+                .get::<AuthenticatedUser>()
+                .cloned()
+                .ok_or(AuthError::MissingToken)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jwt::{Actor, Claims};
+
+    fn service() -> JwtService {
+        JwtService::new("secret".to_string())
+    }
+
+    fn token_with(claims: &Claims) -> String {
+        jsonwebtoken::encode(
+            &jsonwebtoken::Header::default(),
+            claims,
+            &jsonwebtoken::EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap()
+    }
+
+    fn claims(sub: String, act: Option<Actor>) -> Claims {
+        let now = chrono::Utc::now().timestamp();
+        Claims {
+            sub,
+            email: "user@example.com".to_string(),
+            role: "user".to_string(),
+            exp: now + 60,
+            iat: now,
+            token_type: TokenType::Access,
+            act,
+        }
+    }
+
+    #[test]
+    fn test_authenticate_reads_user_and_impersonator() {
+        let (user_id, admin_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let token = service()
+            .generate_impersonation_token(user_id, "user@example.com".to_string(), "user", admin_id)
+            .unwrap();
+
+        let user = service().authenticate(&token).unwrap();
+
+        assert_eq!(user.user_id, user_id);
+        assert_eq!(user.email, "user@example.com");
+        assert_eq!(user.role, "user");
+        assert_eq!(user.impersonator_id, Some(admin_id));
+    }
+
+    #[test]
+    fn test_effective_role_drops_staff_roles_when_impersonating() {
+        let moderator = |impersonator_id| AuthenticatedUser {
+            user_id: Uuid::new_v4(),
+            email: "mod@example.com".to_string(),
+            role: "moderator".to_string(),
+            impersonator_id,
+        };
+
+        assert_eq!(moderator(None).effective_role(), "moderator");
+        assert_eq!(moderator(Some(Uuid::new_v4())).effective_role(), "user");
+    }
+
+    #[test]
+    fn test_authenticate_rejects_refresh_token() {
+        let token = service()
+            .generate_refresh_token(Uuid::new_v4(), "user@example.com".to_string(), "user")
+            .unwrap();
+
+        assert!(matches!(service().authenticate(&token), Err(AuthError::InvalidTokenType)));
+    }
+
+    #[test]
+    fn test_authenticate_rejects_malformed_subject_and_actor() {
+        let bad_sub = token_with(&claims("not-a-uuid".to_string(), None));
+        assert!(matches!(service().authenticate(&bad_sub), Err(AuthError::InvalidUserId)));
+
+        let bad_actor = token_with(&claims(
+            Uuid::new_v4().to_string(),
+            Some(Actor { sub: "not-a-uuid".to_string() }),
+        ));
+        assert!(matches!(service().authenticate(&bad_actor), Err(AuthError::InvalidActor)));
+    }
+
+    #[test]
+    fn test_authenticate_header_requires_bearer_scheme() {
+        let token = service()
+            .generate_access_token(Uuid::new_v4(), "user@example.com".to_string(), "user")
+            .unwrap();
+
+        assert!(matches!(service().authenticate_header(None), Err(AuthError::MissingToken)));
+        assert!(matches!(
+            service().authenticate_header(Some(&format!("Basic {token}"))),
+            Err(AuthError::MissingToken)
+        ));
+        assert!(service().authenticate_header(Some(&format!("Bearer {token}"))).is_ok());
+    }
+
+    #[test]
+    fn test_authenticate_reports_invalid_token() {
+        let err = service().authenticate("invalid.token.here").unwrap_err();
+        assert!(matches!(err, AuthError::InvalidToken(_)));
+        assert!(err.to_string().starts_with("Invalid token: "));
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn test_extractor_reads_user_stored_by_middleware() {
+        use axum::extract::FromRequestParts;
+
+        let (mut parts, _) = axum::http::Request::new(()).into_parts();
+        let rejection = AuthenticatedUser::from_request_parts(&mut parts, &()).await.unwrap_err();
+        assert_eq!(rejection.status(), axum::http::StatusCode::UNAUTHORIZED);
+
+        let user_id = Uuid::new_v4();
+        parts.extensions.insert(AuthenticatedUser {
+            user_id,
+            email: "user@example.com".to_string(),
+            role: "user".to_string(),
+            impersonator_id: None,
+        });
+        let user = AuthenticatedUser::from_request_parts(&mut parts, &()).await.unwrap();
+        assert_eq!(user.user_id, user_id);
+    }
+}
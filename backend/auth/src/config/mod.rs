@@ -1,13 +1,31 @@
+use std::time::Duration;
+
 use config::{Config, Environment};
 use serde::Deserialize;
 
+use crate::repository::pool::PoolSettings;
+use crate::telemetry::LogFormat;
+
 #[derive(Deserialize, Debug)]
 pub struct AppConfig {
     pub database_url: String,
     pub database_max_connections: u32,
+    #[serde(default)]
+    pub database_min_connections: u32,
+    #[serde(default = "default_database_acquire_timeout_secs")]
+    pub database_acquire_timeout_secs: u64,
+    /// 0 keeps idle connections open indefinitely.
+    #[serde(default = "default_database_idle_timeout_secs")]
+    pub database_idle_timeout_secs: u64,
+    /// 0 never recycles connections.
+    #[serde(default = "default_database_max_lifetime_secs")]
+    pub database_max_lifetime_secs: u64,
     pub jwt_secret: String,
     pub jwt_access_token_minutes: i64,
     pub jwt_refresh_token_days: i64,
+    /// Lifetime of the access tokens admins get from impersonating a user.
+    #[serde(default = "default_jwt_impersonation_minutes")]
+    pub jwt_impersonation_minutes: i64,
     #[serde(default)]
     pub telemetry_enabled: bool,
     #[serde(default = "default_telemetry_service_name")]
@@ -18,6 +36,25 @@ pub struct AppConfig {
     pub telemetry_environment: String,
     #[serde(default = "default_telemetry_otlp_endpoint")]
     pub telemetry_otlp_endpoint: String,
+    /// "text" (default) or "json" for log shippers.
+    #[serde(default)]
+    pub log_format: LogFormat,
+}
+
+fn default_database_acquire_timeout_secs() -> u64 {
+    30
+}
+
+fn default_database_idle_timeout_secs() -> u64 {
+    600
+}
+
+fn default_database_max_lifetime_secs() -> u64 {
+    1800
+}
+
+fn default_jwt_impersonation_minutes() -> i64 {
+    15
 }
 
 fn default_telemetry_service_name() -> String {
@@ -41,14 +78,20 @@ impl Default for AppConfig {
         Self {
             database_url: String::new(),
             database_max_connections: 5,
+            database_min_connections: 0,
+            database_acquire_timeout_secs: default_database_acquire_timeout_secs(),
+            database_idle_timeout_secs: default_database_idle_timeout_secs(),
+            database_max_lifetime_secs: default_database_max_lifetime_secs(),
             jwt_secret: "change_this_secret_key_in_production".to_string(),
             jwt_access_token_minutes: 15,
             jwt_refresh_token_days: 7,
+            jwt_impersonation_minutes: default_jwt_impersonation_minutes(),
             telemetry_enabled: false,
             telemetry_service_name: default_telemetry_service_name(),
             telemetry_service_version: default_telemetry_service_version(),
             telemetry_environment: default_telemetry_environment(),
             telemetry_otlp_endpoint: default_telemetry_otlp_endpoint(),
+            log_format: LogFormat::default(),
         }
     }
 }
@@ -62,4 +105,15 @@ impl AppConfig {
 
         settings.try_deserialize().unwrap_or_else(|_| Self::default())
     }
+
+    pub fn pool_settings(&self) -> PoolSettings {
+        let non_zero = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+        PoolSettings {
+            max_connections: self.database_max_connections,
+            min_connections: self.database_min_connections,
+            acquire_timeout: Duration::from_secs(self.database_acquire_timeout_secs),
+            idle_timeout: non_zero(self.database_idle_timeout_secs),
+            max_lifetime: non_zero(self.database_max_lifetime_secs),
+        }
+    }
 }
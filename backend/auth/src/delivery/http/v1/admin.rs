@@ -6,69 +6,26 @@ use axum::{
     response::IntoResponse,
     Extension, Json,
 };
-use serde::{Deserialize, Serialize};
+use guide_helper_api::list::{ListResponse, RequestId};
 use uuid::Uuid;
 
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::user::Role;
 use crate::usecase::contracts::UserRepository;
 use crate::AppState;
 
-#[derive(Debug, Deserialize)]
-pub struct UsersQuery {
-    pub limit: Option<i64>,
-    pub offset: Option<i64>,
-    pub search: Option<String>,
-}
-
-#[derive(Serialize)]
-pub struct UserListItem {
-    pub id: Uuid,
-    pub email: String,
-    pub name: Option<String>,
-    pub role: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-}
-
-#[derive(Serialize)]
-pub struct UsersListResponse {
-    pub users: Vec<UserListItem>,
-    pub total: i64,
-}
-
-#[derive(Deserialize)]
-pub struct UpdateRoleRequest {
-    pub role: String,
-}
-
-#[derive(Serialize)]
-pub struct StatsResponse {
-    pub total_users: i64,
-    pub by_role: Vec<RoleStatItem>,
-}
-
-#[derive(Serialize)]
-pub struct RoleStatItem {
-    pub role: String,
-    pub count: i64,
-}
-
-pub fn require_admin(user: &AuthenticatedUser) -> Result<(), (StatusCode, String)> {
-    if user.role != "admin" {
-        tracing::warn!(user_id = %user.user_id, role = %user.role, "non-admin access attempt to admin endpoint");
-        return Err((StatusCode::FORBIDDEN, "Admin access required".to_string()));
-    }
-    Ok(())
-}
+pub use guide_helper_api::users::{
+    ImpersonationResponse, RoleStatItem, StatsResponse, UpdateRoleRequest, UserListItem, UsersQuery,
+};
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
 pub async fn list_users(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
     Query(params): Query<UsersQuery>,
+    request_id: RequestId,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    require_admin(&user)?;
-
-    let limit = params.limit.unwrap_or(20).min(100).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
     let offset = params.offset.unwrap_or(0).max(0);
     let search = params.search.filter(|s| !s.is_empty());
 
@@ -98,7 +55,7 @@ pub async fn list_users(
         .collect();
 
     tracing::debug!(count = items.len(), total, "users listed");
-    Ok((StatusCode::OK, Json(UsersListResponse { users: items, total })))
+    Ok(ListResponse::page(items, limit, offset, total, request_id))
 }
 
 #[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, target_user_id = %target_user_id))]
@@ -108,8 +65,6 @@ pub async fn update_user_role(
     Path(target_user_id): Path<Uuid>,
     Json(payload): Json<UpdateRoleRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    require_admin(&user)?;
-
     // Validate role value
     let valid_roles = ["user", "moderator", "admin"];
     if !valid_roles.contains(&payload.role.as_str()) {
@@ -140,8 +95,6 @@ pub async fn get_stats(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    require_admin(&user)?;
-
     tracing::debug!("getting admin stats");
 
     let total_users = state.auth_usecase.user_repository().count_users(None).await
@@ -167,3 +120,56 @@ pub async fn get_stats(
     tracing::debug!(total_users, role_count = by_role.len(), "admin stats retrieved");
     Ok((StatusCode::OK, Json(StatsResponse { total_users, by_role })))
 }
+
+/// Issues a short-lived access token for `target_user_id` so support staff can
+/// reproduce what the user sees. Admins cannot be impersonated, and every
+/// request made with the token lands in the `audit` log.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, target_user_id = %target_user_id))]
+pub async fn impersonate_user(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(target_user_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    if target_user_id == user.user_id {
+        return Err((StatusCode::BAD_REQUEST, "Cannot impersonate yourself".to_string()));
+    }
+
+    let target = state.auth_usecase.user_repository().find_by_id(target_user_id).await
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to load user to impersonate");
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to impersonate user: {}", e))
+        })?
+        .filter(|u| u.deleted_at.is_none())
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "User not found".to_string()))?;
+
+    if target.role == Role::Admin {
+        tracing::warn!("attempt to impersonate an admin");
+        return Err((StatusCode::FORBIDDEN, "Admins cannot be impersonated".to_string()));
+    }
+
+    let access_token = state
+        .jwt_service
+        .generate_impersonation_token(target.id, target.email, &target.role.to_string(), user.user_id)
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to generate impersonation token");
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to impersonate user: {}", e))
+        })?;
+    let expires_at = chrono::Utc::now() + state.jwt_service.impersonation_token_duration();
+
+    tracing::warn!(
+        target: "audit",
+        impersonator_id = %user.user_id,
+        user_id = %target_user_id,
+        %expires_at,
+        "impersonation token issued"
+    );
+    Ok((
+        StatusCode::OK,
+        Json(ImpersonationResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            user_id: target_user_id,
+            expires_at,
+        }),
+    ))
+}
@@ -1,40 +1,12 @@
 use std::sync::Arc;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
-use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::delivery::{contracts::AuthUseCase};
 use crate::AppState;
 
-#[derive(Deserialize, Validate)]
-pub struct RegisterRequest {
-    #[validate(email)]
-    email: String,
-    #[validate(length(min = 8))]
-    password: String,
-}
-
-#[derive(Deserialize, Validate)]
-pub struct LoginRequest {
-    #[validate(email)]
-    email: String,
-    #[validate(length(min = 1))]
-    password: String,
-}
-
-#[derive(Deserialize, Validate)]
-pub struct RefreshTokenRequest {
-    #[validate(length(min = 1))]
-    refresh_token: String,
-}
-
-#[derive(Serialize)]
-struct AuthResponse {
-    access_token: String,
-    refresh_token: String,
-    token_type: String,
-}
+pub use guide_helper_api::auth::{AuthResponse, LoginRequest, RefreshTokenRequest, RegisterRequest};
 
 #[tracing::instrument(skip(state, payload), fields(email = %payload.email))]
 pub async fn register(State(state): State<Arc<AppState>>, Json(payload): Json<RegisterRequest>) -> Result<impl IntoResponse, (StatusCode, String)>
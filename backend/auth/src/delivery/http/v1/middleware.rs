@@ -1,20 +1,121 @@
 use std::sync::Arc;
+use std::time::Instant;
 
 use axum::{
-    extract::{Request, State},
-    http::StatusCode,
+    extract::{MatchedPath, Request, State},
     middleware::Next,
     response::Response,
 };
-use uuid::Uuid;
+use metrics::{describe_histogram, Unit};
+use tracing::Span;
+use guide_helper_auth_core::AuthError;
 
-use crate::{usecase::jwt::TokenType, AppState};
+use crate::AppState;
 
+pub use guide_helper_auth_core::AuthenticatedUser;
+
+/// The `TraceLayer` span of the request, kept in the request extensions so
+/// middleware running inside other spans can still record on it.
 #[derive(Clone, Debug)]
-pub struct AuthenticatedUser {
-    pub user_id: Uuid,
-    pub email: String,
-    pub role: String,
+pub struct RequestSpan(pub Span);
+
+/// Root span for every HTTP request. `user_id`, `role` and `impersonator_id`
+/// are declared empty and filled in by [`record_user_middleware`] on
+/// authenticated routes.
+pub fn make_request_span<B>(request: &Request<B>) -> Span {
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        version = ?request.version(),
+        request_id,
+        user_id = tracing::field::Empty,
+        role = tracing::field::Empty,
+        impersonator_id = tracing::field::Empty,
+    )
+}
+
+/// Stores the current request span in the extensions. Must run inside `TraceLayer`.
+pub async fn capture_request_span(mut request: Request, next: Next) -> Response {
+    request.extensions_mut().insert(RequestSpan(Span::current()));
+    next.run(request).await
+}
+
+/// Histogram buckets for `http_request_duration_seconds`, in seconds. Dense
+/// around the usual latency targets so SLO burn can be read off the buckets.
+pub const REQUEST_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Registers help text for the HTTP metrics. Call once after the Prometheus
+/// recorder is installed.
+pub fn describe_request_metrics() {
+    describe_histogram!(
+        "http_request_duration_seconds",
+        Unit::Seconds,
+        "Time to produce a response, by `method`, matched `route` and `status`"
+    );
+}
+
+/// Records the request duration under its route template (`/api/v1/routes/{id}`)
+/// rather than the raw path, so IDs do not create new series. Must be added
+/// with `route_layer`, where the matched path is known.
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().to_string();
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    metrics::histogram!(
+        "http_request_duration_seconds",
+        "method" => method,
+        "route" => route,
+        "status" => response.status().as_u16().to_string(),
+    )
+    .record(start.elapsed().as_secs_f64());
+    response
+}
+
+/// Records the authenticated user on the request span, so every log line and
+/// exported span of the request is attributable to them. Requests made with
+/// an impersonation token are also written to the `audit` log target. Must
+/// run inside `auth_middleware`.
+pub async fn record_user_middleware(request: Request, next: Next) -> Response {
+    let user = request.extensions().get::<AuthenticatedUser>().cloned();
+    if let (Some(user), Some(RequestSpan(span))) = (&user, request.extensions().get::<RequestSpan>()) {
+        span.record("user_id", tracing::field::display(user.user_id));
+        span.record("role", user.role.as_str());
+        if let Some(impersonator_id) = user.impersonator_id {
+            span.record("impersonator_id", tracing::field::display(impersonator_id));
+        }
+    }
+
+    let Some((user_id, impersonator_id)) = user.and_then(|u| Some((u.user_id, u.impersonator_id?))) else {
+        return next.run(request).await;
+    };
+    let (method, uri) = (request.method().clone(), request.uri().clone());
+    let response = next.run(request).await;
+    tracing::info!(
+        target: "audit",
+        %impersonator_id,
+        %user_id,
+        %method,
+        %uri,
+        status = response.status().as_u16(),
+        "impersonated request"
+    );
+    response
 }
 
 #[tracing::instrument(skip_all)]
@@ -22,55 +123,12 @@ pub async fn auth_middleware(
     State(state): State<Arc<AppState>>,
     mut request: Request,
     next: Next,
-) -> Result<Response, (StatusCode, String)> {
+) -> Result<Response, AuthError> {
     let auth_header = request
         .headers()
         .get("Authorization")
         .and_then(|h| h.to_str().ok());
-
-    let token = match auth_header {
-        Some(header) if header.starts_with("Bearer ") => {
-            header.strip_prefix("Bearer ").unwrap()
-        }
-        _ => {
-            tracing::warn!("missing or invalid authorization header");
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                "Missing or invalid Authorization header".to_string(),
-            ));
-        }
-    };
-
-    let claims = match state.jwt_service.validate_token(token) {
-        Ok(claims) => claims,
-        Err(e) => {
-            tracing::warn!(?e, "invalid token");
-            return Err((StatusCode::UNAUTHORIZED, format!("Invalid token: {}", e)));
-        }
-    };
-
-    // Ensure it's an access token, not a refresh token
-    if claims.token_type != TokenType::Access {
-        tracing::warn!("attempted to use non-access token for authentication");
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            "Invalid token type".to_string(),
-        ));
-    }
-
-    let user_id = Uuid::parse_str(&claims.sub).map_err(|e| {
-        tracing::error!(?e, "failed to parse user_id from token");
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Invalid user ID in token".to_string(),
-        )
-    })?;
-
-    let authenticated_user = AuthenticatedUser {
-        user_id,
-        email: claims.email,
-        role: claims.role,
-    };
+    let authenticated_user = state.jwt_service.authenticate_header(auth_header)?;
 
     tracing::debug!(?authenticated_user, "user authenticated successfully");
     request.extensions_mut().insert(authenticated_user);
@@ -80,6 +138,8 @@ pub async fn auth_middleware(
 
 #[cfg(test)]
 mod tests {
+    use uuid::Uuid;
+
     use super::*;
 
     #[test]
@@ -88,6 +148,7 @@ mod tests {
             user_id: Uuid::new_v4(),
             email: "test@example.com".to_string(),
             role: "user".to_string(),
+            impersonator_id: None,
         };
 
         let cloned = user.clone();
@@ -102,6 +163,7 @@ mod tests {
             user_id: Uuid::new_v4(),
             email: "test@example.com".to_string(),
             role: "admin".to_string(),
+            impersonator_id: None,
         };
 
         let debug_str = format!("{:?}", user);
@@ -1,40 +1,13 @@
 use std::sync::Arc;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 use validator::Validate;
 
 use crate::delivery::contracts::AuthUseCase;
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
 use crate::AppState;
 
-#[derive(Serialize)]
-pub struct ProfileResponse {
-    pub id: Uuid,
-    pub email: String,
-    pub name: Option<String>,
-    pub avatar_url: Option<String>,
-    pub role: String,
-    pub created_at: DateTime<Utc>,
-}
-
-#[derive(Deserialize, Validate)]
-pub struct UpdateProfileRequest {
-    #[validate(length(max = 100))]
-    pub name: Option<String>,
-    #[validate(url)]
-    pub avatar_url: Option<String>,
-}
-
-#[derive(Deserialize, Validate)]
-pub struct ChangePasswordRequest {
-    #[validate(length(min = 1))]
-    pub old_password: String,
-    #[validate(length(min = 8))]
-    pub new_password: String,
-}
+pub use guide_helper_api::profile::{ChangePasswordRequest, ProfileResponse, UpdateProfileRequest};
 
 pub async fn get_profile(
     State(state): State<Arc<AppState>>,
@@ -115,6 +88,11 @@ pub async fn change_password(
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     tracing::debug!(user_id = %user.user_id, "handling change password request");
 
+    if user.impersonator_id.is_some() {
+        tracing::warn!(user_id = %user.user_id, impersonator_id = ?user.impersonator_id, "password change attempted while impersonating");
+        return Err((StatusCode::FORBIDDEN, "Password cannot be changed while impersonating".to_string()));
+    }
+
     if let Err(validation_errors) = payload.validate() {
         tracing::warn!(user_id = %user.user_id, ?validation_errors, "validation failed");
         return Err((
@@ -151,6 +129,8 @@ pub async fn change_password(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
 
     #[test]
     fn test_update_profile_request_validation_valid() {
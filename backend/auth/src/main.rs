@@ -10,15 +10,20 @@ mod usecase;
 use std::sync::Arc;
 
 use axum::{extract::State, middleware, routing::{get, post, put}, Router};
-use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
 use tower_http::trace::TraceLayer;
+use guide_helper_auth_core::{require_role, JwtService, RequireRole};
 use tracing_subscriber::EnvFilter;
-use crate::delivery::http::v1::admin::{list_users, update_user_role, get_stats};
+use crate::delivery::http::v1::admin::{impersonate_user, list_users, update_user_role, get_stats};
 use crate::delivery::http::v1::auth::{register, login, refresh_token};
-use crate::delivery::http::v1::middleware::auth_middleware;
+use crate::delivery::http::v1::middleware::{
+    auth_middleware, capture_request_span, describe_request_metrics, make_request_span, record_user_middleware,
+    track_metrics, REQUEST_DURATION_BUCKETS,
+};
 use crate::delivery::http::v1::profile::{get_profile, update_profile, change_password};
 
-use crate::{repository::postgres::{create_pool, PostgresUserRepository}, usecase::auth::AuthUseCase, usecase::jwt::JwtService};
+use crate::{repository::postgres::{create_pool, PostgresUserRepository}, usecase::auth::AuthUseCase};
 
 pub struct AppState {
     pub auth_usecase: AuthUseCase<PostgresUserRepository>,
@@ -42,47 +47,61 @@ async fn main() -> anyhow::Result<()> {
             otlp_endpoint: config.telemetry_otlp_endpoint.clone(),
         };
 
-        telemetry::init_telemetry_with_subscriber(&telemetry_config, env_filter)
+        telemetry::init_telemetry_with_subscriber(&telemetry_config, env_filter, config.log_format)
             .expect("failed to initialize telemetry");
     } else {
-        telemetry::init_subscriber_without_telemetry(env_filter);
+        telemetry::init_subscriber_without_telemetry(env_filter, config.log_format);
     }
 
     tracing::info!("starting the auth service");
 
     let metrics_handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full("http_request_duration_seconds".to_string()),
+            REQUEST_DURATION_BUCKETS,
+        )
+        .expect("invalid request duration buckets")
         .install_recorder()
         .expect("failed to install Prometheus recorder");
     metrics_process::Collector::default().describe();
+    describe_request_metrics();
+    repository::pool::describe();
     tracing::info!("prometheus metrics initialized");
 
     tracing::info!("config loaded, telemetry_enabled={}", config.telemetry_enabled);
 
-    let pool = create_pool(&config.database_url, config.database_max_connections)
+    let pool = create_pool(&config.database_url, &config.pool_settings())
         .await
         .expect("failed to create database pool");
+    repository::pool::spawn_pool_metrics("primary", pool.clone());
 
     sqlx::migrate!().run(&pool).await?;
 
     let user_repository = PostgresUserRepository::new(pool);
 
     // Create JWT service with configuration
-    let jwt_service = JwtService::new(
-        config.jwt_secret.clone(),
-        config.jwt_access_token_minutes,
-        config.jwt_refresh_token_days,
-    );
+    let jwt_service = JwtService::new(config.jwt_secret.clone())
+        .with_access_minutes(config.jwt_access_token_minutes)
+        .with_refresh_days(config.jwt_refresh_token_days)
+        .with_impersonation_minutes(config.jwt_impersonation_minutes);
 
     let auth_usecase = AuthUseCase::with_jwt_service(user_repository, jwt_service.clone());
 
     let shared_state = Arc::new(AppState{auth_usecase, jwt_service, metrics_handle});
+    // Admin endpoints: the role is checked here, before any handler runs
+    let admin_routes = Router::new()
+        .route("/api/v1/admin/users", get(list_users))
+        .route("/api/v1/admin/users/{id}/role", put(update_user_role))
+        .route("/api/v1/admin/stats", get(get_stats))
+        .route("/api/v1/admin/impersonate/{user_id}", post(impersonate_user))
+        .route_layer(middleware::from_fn_with_state(RequireRole::ADMIN, require_role));
+
     // Protected routes that require authentication
     let protected_routes = Router::new()
         .route("/api/v1/auth/me", get(get_profile).put(update_profile))
         .route("/api/v1/auth/password", put(change_password))
-        .route("/api/v1/admin/users", get(list_users))
-        .route("/api/v1/admin/users/{id}/role", put(update_user_role))
-        .route("/api/v1/admin/stats", get(get_stats))
+        .merge(admin_routes)
+        .layer(middleware::from_fn(record_user_middleware))
         .layer(middleware::from_fn_with_state(shared_state.clone(), auth_middleware));
 
     let router = Router::new()
@@ -93,7 +112,11 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/v1/auth/login", post(login))
         .route("/api/v1/auth/refresh", post(refresh_token))
         .merge(protected_routes)
-        .layer(TraceLayer::new_for_http())
+        .route_layer(middleware::from_fn(track_metrics))
+        .layer(middleware::from_fn(capture_request_span))
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(TraceLayer::new_for_http().make_span_with(make_request_span))
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .with_state(shared_state);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
@@ -1,2 +1,3 @@
+pub mod pool;
 pub mod postgres;
 pub mod errors;
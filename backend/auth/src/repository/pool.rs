@@ -0,0 +1,86 @@
+//! Connection pool settings shared by every backend, and the gauges that
+//! make pool saturation visible on `/metrics` before requests time out.
+
+use std::time::{Duration, Instant};
+
+use metrics::{describe_counter, describe_gauge, describe_histogram, Unit};
+use sqlx::pool::PoolOptions;
+use sqlx::{Database, Pool};
+
+/// How often pool gauges are sampled.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    /// Connections kept open even when idle.
+    pub min_connections: u32,
+    /// How long a query waits for a free connection before failing.
+    pub acquire_timeout: Duration,
+    /// Idle connections above `min_connections` are closed after this long.
+    pub idle_timeout: Option<Duration>,
+    /// Connections are recycled after this long, so failovers and credential
+    /// rotations are picked up.
+    pub max_lifetime: Option<Duration>,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        Self {
+            max_connections: 5,
+            min_connections: 0,
+            acquire_timeout: Duration::from_secs(30),
+            idle_timeout: Some(Duration::from_secs(600)),
+            max_lifetime: Some(Duration::from_secs(1800)),
+        }
+    }
+}
+
+impl PoolSettings {
+    pub fn options<DB: Database>(&self) -> PoolOptions<DB> {
+        PoolOptions::new()
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .idle_timeout(self.idle_timeout)
+            .max_lifetime(self.max_lifetime)
+    }
+}
+
+pub fn describe() {
+    describe_gauge!("db_pool_connections", "Open connections in the pool, by `pool`");
+    describe_gauge!("db_pool_idle_connections", "Open connections not in use, by `pool`");
+    describe_gauge!("db_pool_max_connections", "Configured connection limit, by `pool`");
+    describe_histogram!("db_pool_acquire_seconds", Unit::Seconds, "Time to check out a connection, sampled periodically");
+    describe_counter!("db_pool_acquire_errors_total", "Sampled checkouts that failed or timed out, by `pool`");
+}
+
+/// Samples the pool every [`SAMPLE_INTERVAL`] for as long as it is open. The
+/// wait time comes from a checkout made by the sampler itself, so it tracks
+/// what a request arriving at that moment would have waited.
+pub fn spawn_pool_metrics<DB: Database>(name: &'static str, pool: Pool<DB>) {
+    metrics::gauge!("db_pool_max_connections", "pool" => name).set(pool.options().get_max_connections() as f64);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+        while !pool.is_closed() {
+            interval.tick().await;
+
+            metrics::gauge!("db_pool_connections", "pool" => name).set(pool.size() as f64);
+            metrics::gauge!("db_pool_idle_connections", "pool" => name).set(pool.num_idle() as f64);
+
+            let started = Instant::now();
+            match pool.acquire().await {
+                Ok(conn) => {
+                    metrics::histogram!("db_pool_acquire_seconds", "pool" => name)
+                        .record(started.elapsed().as_secs_f64());
+                    drop(conn);
+                }
+                Err(e) => {
+                    tracing::warn!(pool = name, error = %e, "failed to acquire a pooled connection");
+                    metrics::counter!("db_pool_acquire_errors_total", "pool" => name).increment(1);
+                }
+            }
+        }
+    });
+}
@@ -1,6 +1,6 @@
-use sqlx::{postgres::PgPoolOptions, PgPool, Row};
+use sqlx::{PgPool, Postgres, Row};
 
-use crate::{domain::user::User, repository::errors::RepositoryError, usecase::contracts::{RoleCount, UserRepository, UserRow}};
+use crate::{domain::user::User, repository::errors::RepositoryError, repository::pool::PoolSettings, usecase::contracts::{RoleCount, UserRepository, UserRow}};
 
 pub struct PostgresUserRepository {
     pool: PgPool,
@@ -205,9 +205,6 @@ impl UserRepository for PostgresUserRepository {
     }
 }
 
-pub async fn create_pool(database_url: &str, max_connections: u32) -> Result<PgPool, sqlx::Error> {
-    PgPoolOptions::new()
-    .max_connections(max_connections)
-    .connect(database_url)
-    .await
+pub async fn create_pool(database_url: &str, settings: &PoolSettings) -> Result<PgPool, sqlx::Error> {
+    settings.options::<Postgres>().connect(database_url).await
 }
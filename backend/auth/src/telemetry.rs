@@ -1,12 +1,31 @@
-use opentelemetry::trace::TracerProvider;
+use chrono::{SecondsFormat, Utc};
+use opentelemetry::trace::{SpanId, TraceContextExt, TraceId, TracerProvider};
 use opentelemetry::KeyValue;
 use opentelemetry_otlp::{SpanExporter, WithExportConfig};
 use opentelemetry_sdk::{trace as sdktrace, Resource};
 use opentelemetry_semantic_conventions::resource::{SERVICE_NAME, SERVICE_VERSION};
-use tracing_opentelemetry::OpenTelemetryLayer;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_opentelemetry::{OpenTelemetryLayer, OtelData};
+use tracing_subscriber::fmt::format::{JsonFields, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
 use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::util::SubscriberInitExt;
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+
+/// Log line format, selected with `LOG_FORMAT`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+    /// One JSON object per line, see [`JsonLogFormat`].
+    Json,
+}
 
 pub struct TelemetryConfig {
     pub service_name: String,
@@ -29,6 +48,7 @@ impl Default for TelemetryConfig {
 pub fn init_telemetry_with_subscriber(
     config: &TelemetryConfig,
     env_filter: EnvFilter,
+    log_format: LogFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let resource = Resource::builder_empty()
         .with_attribute(KeyValue::new(SERVICE_NAME, config.service_name.clone()))
@@ -57,7 +77,7 @@ pub fn init_telemetry_with_subscriber(
 
     tracing_subscriber::registry()
         .with(env_filter)
-        .with(fmt::layer().json())
+        .with(fmt_layer(log_format))
         .with(otel_layer)
         .init();
 
@@ -70,10 +90,10 @@ pub fn init_telemetry_with_subscriber(
     Ok(())
 }
 
-pub fn init_subscriber_without_telemetry(env_filter: EnvFilter) {
+pub fn init_subscriber_without_telemetry(env_filter: EnvFilter, log_format: LogFormat) {
     tracing_subscriber::registry()
         .with(env_filter)
-        .with(fmt::layer().json())
+        .with(fmt_layer(log_format))
         .init();
 }
 
@@ -82,3 +102,175 @@ pub fn shutdown_telemetry() {
     // The tracer provider is dropped when it goes out of scope
     tracing::info!("OpenTelemetry tracer provider shutdown");
 }
+
+fn fmt_layer<S>(log_format: LogFormat) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    match log_format {
+        LogFormat::Text => fmt::layer().boxed(),
+        LogFormat::Json => fmt::layer()
+            .fmt_fields(JsonFields::new())
+            .event_format(JsonLogFormat)
+            .boxed(),
+    }
+}
+
+/// Flat JSON log lines for Loki/ELK: `timestamp`, `level`, `target`, the
+/// innermost `span` name, the fields of every enclosing span (inner spans
+/// win), the event's own fields and, when the OpenTelemetry layer is
+/// installed, the `trace_id`/`span_id` that link the line to its trace.
+pub struct JsonLogFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonLogFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> std::fmt::Result {
+        let metadata = event.metadata();
+        let mut line = Map::new();
+        line.insert("timestamp".to_string(), Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true).into());
+        line.insert("level".to_string(), metadata.level().as_str().into());
+        line.insert("target".to_string(), metadata.target().into());
+
+        if let Some(scope) = ctx.event_scope() {
+            let mut otel_ids = None;
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                if let Some(Ok(Value::Object(fields))) = extensions
+                    .get::<FormattedFields<N>>()
+                    .map(|f| serde_json::from_str::<Value>(&f.fields))
+                {
+                    line.extend(fields);
+                }
+                otel_ids = extensions.get::<OtelData>().and_then(trace_and_span_id).or(otel_ids);
+                line.insert("span".to_string(), span.name().into());
+            }
+            if let Some((trace_id, span_id)) = otel_ids {
+                line.insert("trace_id".to_string(), trace_id.to_string().into());
+                line.insert("span_id".to_string(), span_id.to_string().into());
+            }
+        }
+
+        event.record(&mut JsonVisitor(&mut line));
+
+        let json = serde_json::to_string(&line).map_err(|_| std::fmt::Error)?;
+        writeln!(writer, "{}", json)
+    }
+}
+
+/// Ids of the OpenTelemetry span backing a tracing span. Child spans only
+/// carry the trace id in their parent context.
+fn trace_and_span_id(otel: &OtelData) -> Option<(TraceId, SpanId)> {
+    let trace_id = otel.builder.trace_id.or_else(|| {
+        let parent = otel.parent_cx.span();
+        let context = parent.span_context();
+        context.is_valid().then(|| context.trace_id())
+    })?;
+    Some((trace_id, otel.builder.span_id?))
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_log_format_flattens_span_and_event_fields() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            fmt::layer()
+                .fmt_fields(JsonFields::new())
+                .event_format(JsonLogFormat)
+                .with_writer(move || writer.clone()),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _outer = tracing::info_span!("request", user_id = "u1", route_id = 1).entered();
+            let _inner = tracing::info_span!("create_route", route_id = 2).entered();
+            tracing::info!(points = 3, "route created");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line: Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["message"], "route created");
+        assert_eq!(line["span"], "create_route");
+        assert_eq!(line["user_id"], "u1");
+        assert_eq!(line["route_id"], 2);
+        assert_eq!(line["points"], 3);
+        assert!(line.get("trace_id").is_none());
+    }
+
+    #[test]
+    fn test_json_log_format_includes_otel_ids() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let tracer = sdktrace::SdkTracerProvider::builder().build().tracer("test");
+        let subscriber = tracing_subscriber::registry()
+            .with(
+                fmt::layer()
+                    .fmt_fields(JsonFields::new())
+                    .event_format(JsonLogFormat)
+                    .with_writer(move || writer.clone()),
+            )
+            .with(OpenTelemetryLayer::new(tracer));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _outer = tracing::info_span!("request").entered();
+            let _inner = tracing::info_span!("handler").entered();
+            tracing::info!("handled");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line: Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["trace_id"].as_str().unwrap().len(), 32);
+        assert_eq!(line["span_id"].as_str().unwrap().len(), 16);
+    }
+}
@@ -6,7 +6,7 @@ use crate::domain::user::{Role, User};
 use crate::{domain, usecase::contracts::UserRepository};
 use crate::delivery::contracts;
 use crate::usecase::password::{hash_password, verify_password};
-use crate::usecase::jwt::{JwtService, TokenType};
+use guide_helper_auth_core::{JwtService, TokenType};
 
 
 pub struct AuthUseCase<R>
@@ -24,7 +24,7 @@ where
     pub fn new(user_repository: R) -> Self {
         // For now, use default JWT settings
         // TODO: Make this configurable
-        let jwt_service = JwtService::new("default_secret_key".to_string(), 15, 7);
+        let jwt_service = JwtService::new("default_secret_key".to_string());
 
         Self {
             user_repository,
@@ -64,7 +64,7 @@ where
 
         let user = User {
             id: uuid,
-            email: email,
+            email,
             password_hash,
             name: None,
             avatar_url: None,
@@ -546,7 +546,7 @@ mod tests {
     #[tokio::test]
     async fn test_refresh_token_with_valid_token() {
         use crate::usecase::password::hash_password;
-        use crate::usecase::jwt::JwtService;
+        use guide_helper_auth_core::JwtService;
 
         let email = "test@example.com".to_string();
         let password = "password";
@@ -567,7 +567,7 @@ mod tests {
         let user_clone = user.clone();
 
         // Create a JWT service to generate a valid token
-        let jwt_service = JwtService::new("test_secret".to_string(), 15, 7);
+        let jwt_service = JwtService::new("test_secret".to_string());
         let valid_refresh_token = jwt_service
             .generate_refresh_token(user_id, email.clone(), "user")
             .unwrap();
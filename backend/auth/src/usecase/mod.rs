@@ -1,4 +1,3 @@
 pub mod auth;
 pub mod contracts;
 pub mod password;
-pub mod jwt;
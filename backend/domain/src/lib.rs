@@ -0,0 +1,10 @@
+//! Domain types stored in the routes database and shared by every binary that
+//! reads or writes them (the routes service, the photo worker and the admin
+//! CLI), plus the values computed from them that appear in API responses.
+//!
+//! Features:
+//! - `sqlx`: `FromRow` for `Route`
+
+pub mod route;
+pub mod route_stats;
+pub mod user_data;
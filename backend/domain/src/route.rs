@@ -0,0 +1,379 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhotoStatus {
+    Pending,
+    Processing,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhotoData {
+    pub original: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+    pub status: PhotoStatus,
+}
+
+/// Deserializes photo field with backward compatibility.
+/// Accepts either a plain string (old format) or a PhotoData struct (new format).
+fn deserialize_photo_compat<'de, D>(deserializer: D) -> Result<Option<PhotoData>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    match value {
+        None => Ok(None),
+        Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(s)) => Ok(Some(PhotoData {
+            original: s,
+            thumbnail_url: None,
+            status: PhotoStatus::Pending,
+        })),
+        Some(obj @ serde_json::Value::Object(_)) => {
+            let photo_data: PhotoData =
+                serde_json::from_value(obj).map_err(serde::de::Error::custom)?;
+            Ok(Some(photo_data))
+        }
+        Some(other) => Err(serde::de::Error::custom(format!(
+            "expected string or object for photo, got: {}",
+            other
+        ))),
+    }
+}
+
+/// Postal address of a route point, filled in by reverse geocoding.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PointAddress {
+    /// Street with the house number when the provider knows it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub street: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+}
+
+impl PointAddress {
+    pub fn is_empty(&self) -> bool {
+        self.street.is_none() && self.city.is_none() && self.country.is_none()
+    }
+}
+
+impl fmt::Display for PointAddress {
+    /// Formats the known parts as "street, city, country".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<&str> = [&self.street, &self.city, &self.country]
+            .into_iter()
+            .filter_map(|part| part.as_deref())
+            .collect();
+        f.write_str(&parts.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+pub struct RoutePoint {
+    #[validate(range(min = -90.0, max = 90.0))]
+    pub lat: f64,
+    #[validate(range(min = -180.0, max = 180.0))]
+    pub lng: f64,
+    pub name: Option<String>,
+    pub segment_mode: Option<String>,
+    #[serde(deserialize_with = "deserialize_photo_compat", default)]
+    pub photo: Option<PhotoData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<PointAddress>,
+}
+
+/// Who besides the owner can see a route.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteVisibility {
+    /// Only the owner.
+    #[default]
+    Private,
+    /// Anyone with the share link; not listed in explore or search.
+    Unlisted,
+    /// Listed in explore and search, and reachable through the share link.
+    Public,
+}
+
+impl RouteVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteVisibility::Private => "private",
+            RouteVisibility::Unlisted => "unlisted",
+            RouteVisibility::Public => "public",
+        }
+    }
+
+    /// Whether the route can be opened through its share link.
+    pub fn has_link(&self) -> bool {
+        !matches!(self, RouteVisibility::Private)
+    }
+}
+
+impl FromStr for RouteVisibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "private" => Ok(RouteVisibility::Private),
+            "unlisted" => Ok(RouteVisibility::Unlisted),
+            "public" => Ok(RouteVisibility::Public),
+            other => Err(format!("unknown route visibility \"{}\"", other)),
+        }
+    }
+}
+
+impl TryFrom<String> for RouteVisibility {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// A row of the `routes` table together with its category ids.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct Route {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    #[cfg_attr(feature = "sqlx", sqlx(json))]
+    pub points: Vec<RoutePoint>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub share_token: Option<Uuid>,
+    /// Stored as text; binaries whose queries predate the column read
+    /// routes as private.
+    #[cfg_attr(feature = "sqlx", sqlx(try_from = "String", default))]
+    pub visibility: RouteVisibility,
+    pub category_ids: Vec<Uuid>,
+    pub start_location: Option<String>,
+    pub end_location: Option<String>,
+    pub seasons: Vec<String>,
+    pub description: Option<String>,
+    /// Set while the route is archived: hidden from the owner's default list
+    /// and from explore, but kept along with its share link.
+    #[cfg_attr(feature = "sqlx", sqlx(default))]
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+impl Route {
+    pub fn new(user_id: Uuid, name: String, points: Vec<RoutePoint>, category_ids: Vec<Uuid>, seasons: Vec<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            name,
+            points,
+            created_at: now,
+            updated_at: now,
+            share_token: None,
+            visibility: RouteVisibility::Private,
+            category_ids,
+            start_location: None,
+            end_location: None,
+            seasons,
+            description: None,
+            archived_at: None,
+        }
+    }
+
+    pub fn update(&mut self, name: Option<String>, points: Option<Vec<RoutePoint>>, category_ids: Option<Vec<Uuid>>, seasons: Option<Vec<String>>, description: Option<String>) {
+        if let Some(n) = name {
+            self.name = n;
+        }
+        if let Some(p) = points {
+            self.points = p;
+        }
+        if let Some(c) = category_ids {
+            self.category_ids = c;
+        }
+        if let Some(s) = seasons {
+            self.seasons = s;
+        }
+        if description.is_some() {
+            self.description = description;
+        }
+        self.updated_at = Utc::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_creation() {
+        let user_id = Uuid::new_v4();
+        let points = vec![
+            RoutePoint {
+                lat: 55.7558,
+                lng: 37.6173,
+                name: Some("Moscow".to_string()),
+                segment_mode: None,
+                photo: None,
+                address: None,
+            },
+            RoutePoint {
+                lat: 59.9343,
+                lng: 30.3351,
+                name: Some("Saint Petersburg".to_string()),
+                segment_mode: Some("auto".to_string()),
+                photo: Some(PhotoData {
+                    original: "data:image/png;base64,test".to_string(),
+                    thumbnail_url: None,
+                    status: PhotoStatus::Pending,
+                }),
+                address: None,
+            },
+        ];
+
+        let route = Route::new(user_id, "Test Route".to_string(), points.clone(), vec![], vec![]);
+
+        assert_eq!(route.user_id, user_id);
+        assert_eq!(route.name, "Test Route");
+        assert_eq!(route.points.len(), 2);
+        assert_eq!(route.created_at, route.updated_at);
+        assert!(route.category_ids.is_empty());
+    }
+
+    #[test]
+    fn test_route_update() {
+        let user_id = Uuid::new_v4();
+        let points = vec![RoutePoint {
+            lat: 55.7558,
+            lng: 37.6173,
+            name: None,
+            segment_mode: None,
+            photo: None,
+            address: None,
+        }];
+        let mut route = Route::new(user_id, "Original".to_string(), points, vec![], vec![]);
+        let original_updated_at = route.updated_at;
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+
+        let new_points = vec![
+            RoutePoint {
+                lat: 55.7558,
+                lng: 37.6173,
+                name: None,
+                segment_mode: None,
+                photo: None,
+                address: None,
+            },
+            RoutePoint {
+                lat: 59.9343,
+                lng: 30.3351,
+                name: None,
+                segment_mode: Some("auto".to_string()),
+                photo: None,
+                address: None,
+            },
+        ];
+        route.update(Some("Updated".to_string()), Some(new_points), None, None, None);
+
+        assert_eq!(route.name, "Updated");
+        assert_eq!(route.points.len(), 2);
+        assert!(route.updated_at > original_updated_at);
+    }
+
+    #[test]
+    fn test_route_point_serialization() {
+        let point = RoutePoint {
+            lat: 55.7558,
+            lng: 37.6173,
+            name: Some("Moscow".to_string()),
+            segment_mode: Some("auto".to_string()),
+            photo: Some(PhotoData {
+                original: "data:image/png;base64,test".to_string(),
+                thumbnail_url: None,
+                status: PhotoStatus::Pending,
+            }),
+            address: None,
+        };
+
+        let json = serde_json::to_string(&point).unwrap();
+        let deserialized: RoutePoint = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(point, deserialized);
+    }
+
+    #[test]
+    fn test_backward_compat_plain_string_photo() {
+        let json = r#"{"lat":55.0,"lng":37.0,"name":null,"segment_mode":null,"photo":"data:image/png;base64,abc"}"#;
+        let point: RoutePoint = serde_json::from_str(json).unwrap();
+
+        let photo = point.photo.unwrap();
+        assert_eq!(photo.original, "data:image/png;base64,abc");
+        assert_eq!(photo.status, PhotoStatus::Pending);
+        assert!(photo.thumbnail_url.is_none());
+    }
+
+    #[test]
+    fn test_backward_compat_null_photo() {
+        let json = r#"{"lat":55.0,"lng":37.0,"name":null,"segment_mode":null,"photo":null}"#;
+        let point: RoutePoint = serde_json::from_str(json).unwrap();
+        assert!(point.photo.is_none());
+    }
+
+    #[test]
+    fn test_photo_data_struct_deserialization() {
+        let json = r#"{"lat":55.0,"lng":37.0,"name":null,"segment_mode":null,"photo":{"original":"data:image/png;base64,abc","thumbnail_url":"/photos/thumb.jpg","status":"done"}}"#;
+        let point: RoutePoint = serde_json::from_str(json).unwrap();
+
+        let photo = point.photo.unwrap();
+        assert_eq!(photo.original, "data:image/png;base64,abc");
+        assert_eq!(photo.thumbnail_url, Some("/photos/thumb.jpg".to_string()));
+        assert_eq!(photo.status, PhotoStatus::Done);
+    }
+
+    #[test]
+    fn test_photo_status_serialization() {
+        let photo = PhotoData {
+            original: "test".to_string(),
+            thumbnail_url: Some("/thumb.jpg".to_string()),
+            status: PhotoStatus::Done,
+        };
+        let json = serde_json::to_string(&photo).unwrap();
+        assert!(json.contains("\"status\":\"done\""));
+        assert!(json.contains("\"thumbnail_url\":\"/thumb.jpg\""));
+    }
+
+    #[test]
+    fn test_point_address_is_optional_and_displayed() {
+        let json = r#"{"lat":55.0,"lng":37.0,"name":null,"segment_mode":null,"photo":null}"#;
+        let point: RoutePoint = serde_json::from_str(json).unwrap();
+        assert!(point.address.is_none());
+        assert!(!serde_json::to_string(&point).unwrap().contains("address"));
+
+        let json = r#"{"lat":55.0,"lng":37.0,"address":{"street":"Kremlyovskaya, 2","city":"Kazan"}}"#;
+        let point: RoutePoint = serde_json::from_str(json).unwrap();
+        let address = point.address.unwrap();
+        assert_eq!(address.country, None);
+        assert_eq!(address.to_string(), "Kremlyovskaya, 2, Kazan");
+    }
+
+    #[test]
+    fn test_route_visibility_parses_and_defaults_to_private() {
+        assert_eq!("unlisted".parse::<RouteVisibility>(), Ok(RouteVisibility::Unlisted));
+        assert!("friends".parse::<RouteVisibility>().is_err());
+        assert_eq!(serde_json::to_string(&RouteVisibility::Public).unwrap(), "\"public\"");
+
+        let route = Route::new(Uuid::new_v4(), "Walk".to_string(), vec![], vec![], vec![]);
+        assert_eq!(route.visibility, RouteVisibility::Private);
+        assert!(!route.visibility.has_link());
+    }
+}
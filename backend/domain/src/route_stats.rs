@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+pub const TRAVEL_MODE_WALKING: &str = "walking";
+pub const TRAVEL_MODE_CYCLING: &str = "cycling";
+pub const TRAVEL_MODE_DRIVING: &str = "driving";
+/// Public transport. Timed with a flat average speed until schedules are used.
+pub const TRAVEL_MODE_TRANSIT: &str = "transit";
+
+/// Maps a point's `segment_mode` to the way the segment is travelled. Hand
+/// drawn and unset segments are walked.
+pub fn travel_mode(segment_mode: Option<&str>) -> &'static str {
+    match segment_mode {
+        Some("bike") => TRAVEL_MODE_CYCLING,
+        Some("car") => TRAVEL_MODE_DRIVING,
+        Some("transit") => TRAVEL_MODE_TRANSIT,
+        _ => TRAVEL_MODE_WALKING,
+    }
+}
+
+/// Average speed of a travel mode, in km/h.
+pub fn average_speed_kmh(mode: &str) -> f64 {
+    match mode {
+        TRAVEL_MODE_CYCLING => 15.0,
+        TRAVEL_MODE_DRIVING => 50.0,
+        TRAVEL_MODE_TRANSIT => 25.0,
+        _ => 5.0,
+    }
+}
+
+/// Distance and travel time of the segments covered in one mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModeStats {
+    pub mode: String,
+    pub segments: usize,
+    pub distance_km: f64,
+    pub duration_min: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct RouteStats {
+    pub distance_km: f64,
+    pub duration_min: f64,
+    /// In order of first appearance along the route.
+    pub by_mode: Vec<ModeStats>,
+    /// Length of each segment, the first one ending at the second point.
+    #[serde(default)]
+    pub segment_distances_km: Vec<f64>,
+}
+
+impl RouteStats {
+    /// Sums `(travel mode, distance in km)` segments.
+    pub fn from_segments<'a>(segments: impl IntoIterator<Item = (&'a str, f64)>) -> Self {
+        let mut stats = Self::default();
+        for (mode, distance_km) in segments {
+            let duration_min = distance_km / average_speed_kmh(mode) * 60.0;
+            stats.distance_km += distance_km;
+            stats.duration_min += duration_min;
+            stats.segment_distances_km.push(distance_km);
+
+            let index = match stats.by_mode.iter().position(|m| m.mode == mode) {
+                Some(index) => index,
+                None => {
+                    stats.by_mode.push(ModeStats {
+                        mode: mode.to_string(),
+                        segments: 0,
+                        distance_km: 0.0,
+                        duration_min: 0.0,
+                    });
+                    stats.by_mode.len() - 1
+                }
+            };
+            let entry = &mut stats.by_mode[index];
+            entry.segments += 1;
+            entry.distance_km += distance_km;
+            entry.duration_min += duration_min;
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_travel_mode_defaults_to_walking() {
+        assert_eq!(travel_mode(None), TRAVEL_MODE_WALKING);
+        assert_eq!(travel_mode(Some("manual")), TRAVEL_MODE_WALKING);
+        assert_eq!(travel_mode(Some("auto")), TRAVEL_MODE_WALKING);
+        assert_eq!(travel_mode(Some("bike")), TRAVEL_MODE_CYCLING);
+    }
+
+    #[test]
+    fn test_stats_group_segments_by_mode() {
+        let stats = RouteStats::from_segments([
+            (TRAVEL_MODE_WALKING, 1.0),
+            (TRAVEL_MODE_DRIVING, 50.0),
+            (TRAVEL_MODE_WALKING, 1.5),
+        ]);
+
+        assert_eq!(stats.distance_km, 52.5);
+        assert_eq!(stats.segment_distances_km, vec![1.0, 50.0, 1.5]);
+        assert!((stats.duration_min - 90.0).abs() < 1e-9);
+        assert_eq!(stats.by_mode.len(), 2);
+        assert_eq!(stats.by_mode[0].mode, TRAVEL_MODE_WALKING);
+        assert_eq!(stats.by_mode[0].segments, 2);
+        assert!((stats.by_mode[0].duration_min - 30.0).abs() < 1e-9);
+    }
+}
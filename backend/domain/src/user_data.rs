@@ -0,0 +1,42 @@
+/// A table of the routes database holding a user's own rows under a plain
+/// user id column, with no foreign key to `routes` that would remove them
+/// together with the user's routes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserKeyedTable {
+    pub table: &'static str,
+    pub user_column: &'static str,
+    /// Whether the rows go into the user's data takeout. Rows that name the
+    /// user but belong to someone else (who blocked them, whose route they
+    /// acted on) and storage bookkeeping are only erased.
+    pub exported: bool,
+}
+
+const fn owned(table: &'static str) -> UserKeyedTable {
+    UserKeyedTable { table, user_column: "user_id", exported: true }
+}
+
+const fn erased(table: &'static str, user_column: &'static str) -> UserKeyedTable {
+    UserKeyedTable { table, user_column, exported: false }
+}
+
+/// Every [`UserKeyedTable`]. The admin CLI erases these rows when it purges
+/// a user and the data takeout exports them, so a table added here is
+/// covered by both.
+pub const USER_KEYED_TABLES: &[UserKeyedTable] = &[
+    owned("chat_conversations"),
+    owned("chat_preferences"),
+    owned("chat_pending_actions"),
+    owned("chat_turns"),
+    owned("saved_searches"),
+    owned("route_collections"),
+    owned("usage_events"),
+    owned("route_quota_overrides"),
+    owned("spam_flags"),
+    UserKeyedTable { table: "user_blocks", user_column: "blocker_id", exported: true },
+    erased("user_blocks", "blocked_id"),
+    // Activity of the user on other people's routes; on their own routes it
+    // goes with the route.
+    erased("route_activity", "actor_id"),
+    // The objects themselves are deleted from storage by the purge.
+    erased("photo_storage_objects", "user_id"),
+];
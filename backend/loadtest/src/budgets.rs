@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use goose::metrics::GooseRequestMetricAggregate;
+
+/// Largest share of failed requests tolerated for any request.
+pub const MAX_ERROR_RATE: f64 = 0.01;
+
+/// 95th percentile response time allowed for a request, keyed like goose
+/// reports requests: `<METHOD> <name>`.
+pub struct Budget {
+    pub request: &'static str,
+    pub p95_ms: usize,
+}
+
+/// Budgets against the nightly dataset (100k routes, 1M comments) behind the
+/// gateway. Keep in sync with the table in the README.
+pub const BUDGETS: &[Budget] = &[
+    Budget { request: "GET explore", p95_ms: 300 },
+    Budget { request: "GET explore search", p95_ms: 400 },
+    Budget { request: "GET route comments", p95_ms: 200 },
+    Budget { request: "POST login", p95_ms: 500 },
+    Budget { request: "GET my routes", p95_ms: 250 },
+    Budget { request: "POST route", p95_ms: 300 },
+    Budget { request: "GET route", p95_ms: 150 },
+    Budget { request: "PUT route", p95_ms: 300 },
+    Budget { request: "DELETE route", p95_ms: 200 },
+    Budget { request: "GET chat conversations", p95_ms: 200 },
+    Budget { request: "GET chat history", p95_ms: 150 },
+];
+
+/// Response times and outcomes of one request.
+#[derive(Debug, Default)]
+pub struct RequestStats {
+    /// Count of responses per response time in milliseconds.
+    pub times: BTreeMap<usize, usize>,
+    pub success_count: usize,
+    pub fail_count: usize,
+}
+
+impl From<&GooseRequestMetricAggregate> for RequestStats {
+    fn from(aggregate: &GooseRequestMetricAggregate) -> Self {
+        Self {
+            times: aggregate.raw_data.times.clone(),
+            success_count: aggregate.success_count,
+            fail_count: aggregate.fail_count,
+        }
+    }
+}
+
+/// Smallest recorded time covering `quantile` of the responses.
+pub fn percentile(times: &BTreeMap<usize, usize>, quantile: f64) -> usize {
+    let total: usize = times.values().sum();
+    let target = (total as f64 * quantile).ceil() as usize;
+    let mut seen = 0;
+    for (&time, &count) in times {
+        seen += count;
+        if seen >= target {
+            return time;
+        }
+    }
+    0
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Violation {
+    Missing { request: &'static str },
+    Slow { request: &'static str, p95_ms: usize, budget_ms: usize },
+    Failing { request: &'static str, error_rate: f64 },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Missing { request } => write!(f, "{}: no requests recorded", request),
+            Violation::Slow { request, p95_ms, budget_ms } => {
+                write!(f, "{}: p95 {} ms over the {} ms budget", request, p95_ms, budget_ms)
+            }
+            Violation::Failing { request, error_rate } => write!(
+                f,
+                "{}: {:.2}% of requests failed, at most {:.2}% allowed",
+                request,
+                error_rate * 100.0,
+                MAX_ERROR_RATE * 100.0
+            ),
+        }
+    }
+}
+
+/// Budgets the run broke. Every budgeted request has to show up in the run.
+pub fn check(requests: &HashMap<String, RequestStats>) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for budget in BUDGETS {
+        let Some(stats) = requests.get(budget.request) else {
+            violations.push(Violation::Missing { request: budget.request });
+            continue;
+        };
+
+        let p95_ms = percentile(&stats.times, 0.95);
+        if p95_ms > budget.p95_ms {
+            violations.push(Violation::Slow {
+                request: budget.request,
+                p95_ms,
+                budget_ms: budget.p95_ms,
+            });
+        }
+        let total = stats.success_count + stats.fail_count;
+        let error_rate = if total == 0 { 0.0 } else { stats.fail_count as f64 / total as f64 };
+        if error_rate > MAX_ERROR_RATE {
+            violations.push(Violation::Failing {
+                request: budget.request,
+                error_rate,
+            });
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(times: &[(usize, usize)], fail_count: usize) -> RequestStats {
+        let times: BTreeMap<usize, usize> = times.iter().copied().collect();
+        RequestStats {
+            success_count: times.values().sum::<usize>() - fail_count,
+            fail_count,
+            times,
+        }
+    }
+
+    fn passing_run() -> HashMap<String, RequestStats> {
+        BUDGETS
+            .iter()
+            .map(|b| (b.request.to_string(), stats(&[(b.p95_ms / 2, 99), (b.p95_ms * 10, 1)], 0)))
+            .collect()
+    }
+
+    #[test]
+    fn test_percentile_picks_the_covering_bucket() {
+        let times = stats(&[(10, 90), (50, 5), (400, 5)], 0).times;
+
+        assert_eq!(percentile(&times, 0.5), 10);
+        assert_eq!(percentile(&times, 0.95), 50);
+        assert_eq!(percentile(&times, 0.99), 400);
+        assert_eq!(percentile(&BTreeMap::new(), 0.95), 0);
+    }
+
+    #[test]
+    fn test_check_reports_slow_failing_and_missing_requests() {
+        assert!(check(&passing_run()).is_empty());
+
+        let mut run = passing_run();
+        run.insert("GET explore".to_string(), stats(&[(100, 90), (900, 10)], 0));
+        run.insert("GET route".to_string(), stats(&[(20, 100)], 5));
+        run.remove("GET chat history");
+
+        assert_eq!(
+            check(&run),
+            vec![
+                Violation::Slow { request: "GET explore", p95_ms: 900, budget_ms: 300 },
+                Violation::Failing { request: "GET route", error_rate: 0.05 },
+                Violation::Missing { request: "GET chat history" },
+            ]
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! Load test of the explore, route CRUD and chat history endpoints against a
+//! seeded deployment, checked against the performance budgets in
+//! [`budgets::BUDGETS`]. Exits with status 1 when a budget is broken, so the
+//! nightly job fails.
+//!
+//! Goose options (`--users`, `--run-time`, `--report-file`, ...) are taken
+//! from the command line; see the README for the dataset and defaults.
+
+mod budgets;
+mod scenarios;
+
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use goose::prelude::*;
+
+use crate::budgets::RequestStats;
+use crate::scenarios::{chat_history, explore, explore_search, log_in, my_routes, route_comments, route_crud};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<ExitCode> {
+    let think_time = (Duration::from_millis(200), Duration::from_secs(1));
+    let metrics = GooseAttack::initialize()?
+        .register_scenario(
+            scenario!("Explore")
+                .set_weight(6)?
+                .set_wait_time(think_time.0, think_time.1)?
+                .register_transaction(transaction!(explore).set_weight(3)?)
+                .register_transaction(transaction!(explore_search))
+                .register_transaction(transaction!(route_comments).set_weight(2)?),
+        )
+        .register_scenario(
+            scenario!("RouteCrud")
+                .set_weight(3)?
+                .set_wait_time(think_time.0, think_time.1)?
+                .register_transaction(transaction!(log_in).set_on_start())
+                .register_transaction(transaction!(my_routes).set_weight(2)?)
+                .register_transaction(transaction!(route_crud)),
+        )
+        .register_scenario(
+            scenario!("ChatHistory")
+                .set_wait_time(think_time.0, think_time.1)?
+                .register_transaction(transaction!(log_in).set_on_start())
+                .register_transaction(transaction!(chat_history)),
+        )
+        // The frontend gateway of docker-compose.
+        .set_default(GooseDefault::Host, "http://localhost:3005")?
+        .set_default(GooseDefault::Users, 50)?
+        .set_default(GooseDefault::HatchRate, "5")?
+        .set_default(GooseDefault::RunTime, 300)?
+        .execute()
+        .await?;
+
+    let requests: HashMap<String, RequestStats> =
+        metrics.requests.iter().map(|(name, aggregate)| (name.clone(), aggregate.into())).collect();
+    let violations = budgets::check(&requests);
+    if violations.is_empty() {
+        println!("all {} performance budgets met", budgets::BUDGETS.len());
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    eprintln!("{} performance budget(s) broken:", violations.len());
+    for violation in &violations {
+        eprintln!("  {}", violation);
+    }
+    Ok(ExitCode::FAILURE)
+}
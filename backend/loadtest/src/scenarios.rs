@@ -0,0 +1,176 @@
+use goose::prelude::*;
+use guide_helper_api::auth::{AuthResponse, LoginRequest};
+use guide_helper_api::routes::{CreateRouteRequest, RoutePoint, UpdateRouteRequest};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde_json::Value;
+
+const EXPLORE_SORTS: &[&str] = &["newest", "popular", "top_rated"];
+/// Words that occur in seeded route names.
+const SEARCH_TERMS: &[&str] = &["Прогулка", "Маршрут", "Экскурсия", "парк", "пруды"];
+const PAGE_SIZE: usize = 20;
+/// Explore pages visitors browse through.
+const EXPLORE_PAGES: usize = 10;
+
+/// Per-user state: the access token of logged-in users and the routes seen
+/// on explore, whose comments are read next.
+#[derive(Default)]
+struct Session {
+    token: Option<String>,
+    route_ids: Vec<String>,
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+fn session(user: &mut GooseUser) -> &mut Session {
+    if user.get_session_data::<Session>().is_none() {
+        user.set_session_data(Session::default());
+    }
+    user.get_session_data_unchecked_mut::<Session>()
+}
+
+/// Sends a request, with the session token if there is one, and returns the
+/// JSON body of a successful response. Goose records failures by status.
+async fn send(
+    user: &mut GooseUser,
+    method: GooseMethod,
+    path: &str,
+    name: &str,
+    body: Option<Value>,
+) -> Result<Option<Value>, Box<TransactionError>> {
+    let mut builder = user.get_request_builder(&method, path)?;
+    if let Some(token) = user.get_session_data::<Session>().and_then(|s| s.token.as_deref()) {
+        builder = builder.bearer_auth(token);
+    }
+    if let Some(body) = body {
+        builder = builder.json(&body);
+    }
+
+    let request = GooseRequest::builder()
+        .method(method)
+        .path(path)
+        .name(name)
+        .set_request_builder(builder)
+        .build();
+    let goose = user.request(request).await?;
+    match goose.response {
+        Ok(response) if response.status().is_success() => Ok(response.json::<Value>().await.ok()),
+        _ => Ok(None),
+    }
+}
+
+fn ids(list: &Option<Value>, field: &str) -> Vec<String> {
+    list.as_ref()
+        .and_then(|v| v["data"].as_array())
+        .map(|items| items.iter().filter_map(|i| i[field].as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+fn json(value: impl serde::Serialize) -> Option<Value> {
+    Some(serde_json::to_value(value).expect("request bodies serialize"))
+}
+
+/// Logs in as one of the seeded demo accounts, spreading users over
+/// `LOADTEST_DEMO_USERS` accounts.
+pub async fn log_in(user: &mut GooseUser) -> TransactionResult {
+    let accounts = env_or("LOADTEST_DEMO_USERS", 51usize).max(1);
+    let request = LoginRequest {
+        email: format!("demo{}@guide-helper.local", user.weighted_users_index % accounts + 1),
+        password: env_or("LOADTEST_PASSWORD", "demo-password".to_string()),
+    };
+
+    let response = send(user, GooseMethod::Post, "/api/v1/auth/login", "login", json(request)).await?;
+    session(user).token = response
+        .and_then(|v| serde_json::from_value::<AuthResponse>(v).ok())
+        .map(|auth| auth.access_token);
+    Ok(())
+}
+
+pub async fn explore(user: &mut GooseUser) -> TransactionResult {
+    let (sort, page) = {
+        let mut rng = rand::thread_rng();
+        (*EXPLORE_SORTS.choose(&mut rng).unwrap(), rng.gen_range(0..EXPLORE_PAGES))
+    };
+    let path = format!("/api/v1/routes/explore?sort={}&limit={}&offset={}", sort, PAGE_SIZE, page * PAGE_SIZE);
+
+    let list = send(user, GooseMethod::Get, &path, "explore", None).await?;
+    let route_ids = ids(&list, "id");
+    if !route_ids.is_empty() {
+        session(user).route_ids = route_ids;
+    }
+    Ok(())
+}
+
+pub async fn explore_search(user: &mut GooseUser) -> TransactionResult {
+    let term = *SEARCH_TERMS.choose(&mut rand::thread_rng()).unwrap();
+    let path = format!("/api/v1/routes/explore?search={}&limit={}", term, PAGE_SIZE);
+    send(user, GooseMethod::Get, &path, "explore search", None).await?;
+    Ok(())
+}
+
+/// Comments of a route seen on explore; public routes carry the most of them.
+pub async fn route_comments(user: &mut GooseUser) -> TransactionResult {
+    let Some(route_id) = session(user).route_ids.choose(&mut rand::thread_rng()).cloned() else {
+        return Ok(());
+    };
+    let path = format!("/api/v1/routes/{}/comments", route_id);
+    send(user, GooseMethod::Get, &path, "route comments", None).await?;
+    Ok(())
+}
+
+pub async fn my_routes(user: &mut GooseUser) -> TransactionResult {
+    let path = format!("/api/v1/routes?limit={}", PAGE_SIZE);
+    send(user, GooseMethod::Get, &path, "my routes", None).await?;
+    Ok(())
+}
+
+/// Creates, reads, renames and deletes a route, so the dataset keeps its size.
+pub async fn route_crud(user: &mut GooseUser) -> TransactionResult {
+    let point = |lat: f64, lng: f64| RoutePoint {
+        lat,
+        lng,
+        name: None,
+        segment_mode: None,
+        photo: None,
+        address: None,
+    };
+    let (lat, lng) = {
+        let mut rng = rand::thread_rng();
+        (55.75 + rng.gen_range(-0.04..0.04), 37.62 + rng.gen_range(-0.06..0.06))
+    };
+    let request = CreateRouteRequest {
+        name: "Нагрузочный маршрут".to_string(),
+        points: vec![point(lat, lng), point(lat + 0.004, lng + 0.006), point(lat + 0.008, lng + 0.003)],
+        category_ids: vec![],
+        seasons: vec!["summer".to_string()],
+        form_elapsed_ms: None,
+    };
+
+    let created = send(user, GooseMethod::Post, "/api/v1/routes", "route", json(request)).await?;
+    let Some(id) = created.as_ref().and_then(|v| v["id"].as_str()).map(str::to_string) else {
+        return Ok(());
+    };
+    let path = format!("/api/v1/routes/{}", id);
+    send(user, GooseMethod::Get, &path, "route", None).await?;
+    let update = UpdateRouteRequest {
+        name: Some("Нагрузочный маршрут (изменён)".to_string()),
+        ..Default::default()
+    };
+    send(user, GooseMethod::Put, &path, "route", json(update)).await?;
+    send(user, GooseMethod::Delete, &path, "route", None).await?;
+    Ok(())
+}
+
+/// Lists the conversations and opens one of them.
+pub async fn chat_history(user: &mut GooseUser) -> TransactionResult {
+    let path = format!("/api/v1/chat?limit={}", PAGE_SIZE);
+    let list = send(user, GooseMethod::Get, &path, "chat conversations", None).await?;
+    let Some(conversation_id) = ids(&list, "conversation_id").choose(&mut rand::thread_rng()).cloned() else {
+        return Ok(());
+    };
+    let path = format!("/api/v1/chat/{}", conversation_id);
+    send(user, GooseMethod::Get, &path, "chat history", None).await?;
+    Ok(())
+}
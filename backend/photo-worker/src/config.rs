@@ -1,4 +1,5 @@
 use config::{Config, Environment};
+use guide_helper_api::photo::PhotoPipelineSettings;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -16,12 +17,15 @@ pub struct AppConfig {
     pub minio_secret_key: String,
     #[serde(default = "default_minio_bucket")]
     pub minio_bucket: String,
+    // Pipeline settings used until an admin saves a profile in the API.
     #[serde(default = "default_photo_max_width")]
     pub photo_max_width: u32,
     #[serde(default = "default_photo_quality")]
     pub photo_quality: u8,
     #[serde(default = "default_thumbnail_width")]
     pub thumbnail_width: u32,
+    #[serde(default = "default_thumbnail_quality")]
+    pub thumbnail_quality: u8,
     #[serde(default = "default_photo_base_url")]
     pub photo_base_url: String,
 }
@@ -62,6 +66,10 @@ fn default_thumbnail_width() -> u32 {
     300
 }
 
+fn default_thumbnail_quality() -> u8 {
+    75
+}
+
 fn default_photo_base_url() -> String {
     "/photos".to_string()
 }
@@ -75,4 +83,14 @@ impl AppConfig {
             .try_deserialize()
             .unwrap()
     }
+
+    pub fn default_pipeline(&self) -> PhotoPipelineSettings {
+        PhotoPipelineSettings {
+            max_width: self.photo_max_width,
+            quality: self.photo_quality,
+            thumbnail_width: self.thumbnail_width,
+            thumbnail_quality: self.thumbnail_quality,
+            ..Default::default()
+        }
+    }
 }
@@ -1,17 +1,25 @@
 mod config;
-mod domain;
 mod processing;
+mod storage;
 mod telemetry;
 
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use anyhow::Context;
 use aws_sdk_s3::Client as S3Client;
+use guide_helper_api::events;
+use guide_helper_api::photo::{
+    photo_completed_subject, PhotoCompletedEvent, PhotoPipelineSettings, PhotoProcessTask, PhotoUpdate, PHOTO_COMPLETED_STREAM_NAME,
+    PHOTO_COMPLETED_SUBJECT_PREFIX, PHOTO_CONSUMER_NAME, PHOTO_MAX_DELIVER, PHOTO_PIPELINE_SETTINGS_KEY, PHOTO_PROCESS_SUBJECT,
+    PHOTO_SETTINGS_UPDATED_SUBJECT, PHOTO_STREAM_NAME,
+};
+use guide_helper_domain::route::{PhotoData, PhotoStatus, Route};
 use sqlx::PgPool;
 
 use crate::config::AppConfig;
-use crate::domain::{PhotoData, PhotoProcessTask, PhotoStatus, Route};
 use crate::processing::{compress_image, create_thumbnail, decode_data_url, upload_to_s3};
+use crate::storage::record_object;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -63,14 +71,34 @@ async fn main() -> anyhow::Result<()> {
         .context("failed to connect to NATS")?;
     tracing::info!(nats_url = %config.nats_url, "connected to NATS");
 
-    let nats_publisher = nats_client.clone();
+    let pipeline = Arc::new(RwLock::new(load_pipeline_settings(&pool, &config).await));
+    spawn_settings_listener(nats_client.clone(), pipeline.clone());
+    storage::spawn_cleanup_listener(
+        nats_client.clone(),
+        pool.clone(),
+        s3_client.clone(),
+        config.minio_bucket.clone(),
+    );
+
     let jetstream = async_nats::jetstream::new(nats_client);
 
+    // Completions wait here until the routes service consumes them
+    jetstream
+        .get_or_create_stream(async_nats::jetstream::stream::Config {
+            name: PHOTO_COMPLETED_STREAM_NAME.to_string(),
+            subjects: vec![format!("{}*", PHOTO_COMPLETED_SUBJECT_PREFIX)],
+            retention: async_nats::jetstream::stream::RetentionPolicy::WorkQueue,
+            max_age: Duration::from_secs(24 * 60 * 60),
+            ..Default::default()
+        })
+        .await
+        .context("failed to get or create PHOTOS_COMPLETED stream")?;
+
     // Create consumer for photo processing
     let stream = jetstream
         .get_or_create_stream(async_nats::jetstream::stream::Config {
-            name: "PHOTOS".to_string(),
-            subjects: vec!["photos.process".to_string()],
+            name: PHOTO_STREAM_NAME.to_string(),
+            subjects: vec![PHOTO_PROCESS_SUBJECT.to_string()],
             retention: async_nats::jetstream::stream::RetentionPolicy::WorkQueue,
             ..Default::default()
         })
@@ -80,11 +108,11 @@ async fn main() -> anyhow::Result<()> {
 
     let consumer = stream
         .get_or_create_consumer(
-            "photo-worker",
+            PHOTO_CONSUMER_NAME,
             async_nats::jetstream::consumer::pull::Config {
-                durable_name: Some("photo-worker".to_string()),
+                durable_name: Some(PHOTO_CONSUMER_NAME.to_string()),
                 ack_wait: Duration::from_secs(120),
-                max_deliver: 3,
+                max_deliver: PHOTO_MAX_DELIVER,
                 ..Default::default()
             },
         )
@@ -107,8 +135,10 @@ async fn main() -> anyhow::Result<()> {
         while let Some(msg_result) = messages.next().await {
             match msg_result {
                 Ok(msg) => {
+                    // Settings stay fixed for the whole task.
+                    let settings = pipeline.read().unwrap().clone();
                     if let Err(e) =
-                        process_message(&msg, &pool, &s3_client, &config, &nats_publisher).await
+                        process_message(&msg, &pool, &s3_client, &config, &settings, &jetstream).await
                     {
                         tracing::error!(error = %e, "failed to process message");
                         // Message will be redelivered by NATS
@@ -132,10 +162,10 @@ async fn process_message(
     pool: &PgPool,
     s3_client: &S3Client,
     config: &AppConfig,
-    nats_publisher: &async_nats::Client,
+    settings: &PhotoPipelineSettings,
+    jetstream: &async_nats::jetstream::Context,
 ) -> anyhow::Result<()> {
-    let task: PhotoProcessTask =
-        serde_json::from_slice(&msg.payload).context("failed to deserialize task")?;
+    let task: PhotoProcessTask = events::decode(&msg.payload).context("failed to deserialize task")?;
 
     tracing::info!(
         route_id = %task.route_id,
@@ -146,7 +176,13 @@ async fn process_message(
 
     // Fetch route from database
     let route: Route = sqlx::query_as(
-        "SELECT id, user_id, name, points, created_at, updated_at FROM routes WHERE id = $1",
+        r#"
+        SELECT r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token,
+               COALESCE(ARRAY(SELECT category_id FROM route_categories WHERE route_id = r.id), ARRAY[]::uuid[]) AS category_ids,
+               r.start_location, r.end_location, r.seasons, r.description
+        FROM routes r
+        WHERE r.id = $1
+        "#,
     )
     .bind(task.route_id)
     .fetch_one(pool)
@@ -205,7 +241,7 @@ async fn process_message(
         };
 
         // Compress full image
-        let compressed = match compress_image(&raw_data, config.photo_max_width, config.photo_quality)
+        let compressed = match compress_image(&raw_data, settings.max_width, settings.quality, settings.format)
         {
             Ok(data) => data,
             Err(e) => {
@@ -225,7 +261,12 @@ async fn process_message(
         };
 
         // Create thumbnail
-        let thumbnail = match create_thumbnail(&raw_data, config.thumbnail_width) {
+        let thumbnail = match create_thumbnail(
+            &raw_data,
+            settings.thumbnail_width,
+            settings.thumbnail_quality,
+            settings.format,
+        ) {
             Ok(data) => data,
             Err(e) => {
                 tracing::error!(
@@ -244,15 +285,17 @@ async fn process_message(
         };
 
         // Upload to MinIO
-        let photo_key = format!("{}/{}/photo_{}.jpg", task.user_id, task.route_id, idx);
-        let thumb_key = format!("{}/{}/thumb_{}.jpg", task.user_id, task.route_id, idx);
+        let extension = settings.format.extension();
+        let photo_key = format!("{}/{}/photo_{}.{}", task.user_id, task.route_id, idx, extension);
+        let thumb_key = format!("{}/{}/thumb_{}.{}", task.user_id, task.route_id, idx, extension);
+        let (photo_size, thumb_size) = (compressed.len(), thumbnail.len());
 
         if let Err(e) = upload_to_s3(
             s3_client,
             &config.minio_bucket,
             &photo_key,
             compressed,
-            "image/jpeg",
+            settings.format.content_type(),
         )
         .await
         {
@@ -269,13 +312,14 @@ async fn process_message(
             });
             continue;
         }
+        record_upload(pool, &task, &photo_key, photo_size).await;
 
         if let Err(e) = upload_to_s3(
             s3_client,
             &config.minio_bucket,
             &thumb_key,
             thumbnail,
-            "image/jpeg",
+            settings.format.content_type(),
         )
         .await
         {
@@ -293,6 +337,8 @@ async fn process_message(
             continue;
         }
 
+        record_upload(pool, &task, &thumb_key, thumb_size).await;
+
         // Update point with URLs
         let photo_url = format!("{}/{}", config.photo_base_url, photo_key);
         let thumb_url = format!("{}/{}", config.photo_base_url, thumb_key);
@@ -313,6 +359,13 @@ async fn process_message(
         processed_count += 1;
     }
 
+    let failed_count = task
+        .point_indices
+        .iter()
+        .filter_map(|&idx| points.get(idx).and_then(|p| p.photo.as_ref()))
+        .filter(|photo| matches!(photo.status, PhotoStatus::Failed))
+        .count();
+
     // Update route in database
     let points_json =
         serde_json::to_value(&points).context("failed to serialize updated points")?;
@@ -324,16 +377,25 @@ async fn process_message(
         .await
         .context("failed to update route in database")?;
 
-    // Publish completion event via core NATS for real-time WS notifications
-    let subject = format!("photos.completed.{}", task.route_id);
-    let payload = serde_json::json!({
-        "type": "photo_update",
-        "route_id": task.route_id.to_string(),
-        "points": points_json,
+    // Publish the completion durably; the routes service turns it into WS
+    // updates and a notification. The points are saved by now and a retried
+    // task would find nothing left to process, so a failed publish is only
+    // logged.
+    let subject = photo_completed_subject(task.route_id);
+    let event = PhotoCompletedEvent::PhotoUpdate(PhotoUpdate {
+        route_id: task.route_id,
+        user_id: task.user_id,
+        processed: processed_count,
+        failed: failed_count,
+        points,
     });
-    match serde_json::to_vec(&payload) {
+    match events::encode(&event) {
         Ok(bytes) => {
-            if let Err(e) = nats_publisher.publish(subject.clone(), bytes.into()).await {
+            let published = match jetstream.publish(subject.clone(), bytes.into()).await {
+                Ok(ack) => ack.await.map(|_| ()).map_err(anyhow::Error::from),
+                Err(e) => Err(anyhow::Error::from(e)),
+            };
+            if let Err(e) = published {
                 tracing::warn!(
                     route_id = %task.route_id,
                     error = %e,
@@ -359,6 +421,7 @@ async fn process_message(
     tracing::info!(
         route_id = %task.route_id,
         processed = processed_count,
+        failed = failed_count,
         total = task.point_indices.len(),
         "photo task completed"
     );
@@ -366,6 +429,78 @@ async fn process_message(
     Ok(())
 }
 
+/// Adds an uploaded object to the per-user storage ledger. Failures only
+/// skew usage reporting, so they don't fail the task.
+async fn record_upload(pool: &PgPool, task: &PhotoProcessTask, key: &str, size: usize) {
+    if let Err(e) = record_object(pool, key, task.user_id, task.route_id, size).await {
+        tracing::warn!(
+            route_id = %task.route_id,
+            key = %key,
+            error = %e,
+            "failed to record stored object"
+        );
+    }
+}
+
+/// Reads the admin-saved pipeline profile, falling back to the env config.
+async fn load_pipeline_settings(pool: &PgPool, config: &AppConfig) -> PhotoPipelineSettings {
+    let row: Result<Option<(serde_json::Value,)>, _> = sqlx::query_as("SELECT value FROM settings WHERE key = $1")
+        .bind(PHOTO_PIPELINE_SETTINGS_KEY)
+        .fetch_optional(pool)
+        .await;
+
+    match row.map(|row| row.map(|(value,)| serde_json::from_value::<PhotoPipelineSettings>(value))) {
+        Ok(Some(Ok(settings))) => {
+            tracing::info!(?settings, "loaded photo pipeline settings");
+            settings
+        }
+        Ok(None) => {
+            tracing::info!("no saved photo pipeline settings, using config");
+            config.default_pipeline()
+        }
+        Ok(Some(Err(e))) => {
+            tracing::warn!(error = %e, "invalid saved photo pipeline settings, using config");
+            config.default_pipeline()
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to load photo pipeline settings, using config");
+            config.default_pipeline()
+        }
+    }
+}
+
+/// Applies settings changes published by the API without a restart. Core
+/// NATS, so every worker replica receives them.
+fn spawn_settings_listener(client: async_nats::Client, pipeline: Arc<RwLock<PhotoPipelineSettings>>) {
+    tokio::spawn(async move {
+        let mut subscriber = match client.subscribe(PHOTO_SETTINGS_UPDATED_SUBJECT).await {
+            Ok(subscriber) => subscriber,
+            Err(e) => {
+                tracing::error!(error = %e, "failed to subscribe to photo settings updates");
+                return;
+            }
+        };
+
+        use futures::StreamExt;
+        while let Some(msg) = subscriber.next().await {
+            let settings: PhotoPipelineSettings = match events::decode(&msg.payload) {
+                Ok(settings) => settings,
+                Err(e) => {
+                    tracing::warn!(error = %e, "invalid photo settings update");
+                    continue;
+                }
+            };
+            if let Err(e) = settings.validate() {
+                tracing::warn!(error = %e, "rejected photo settings update");
+                continue;
+            }
+            tracing::info!(?settings, "photo pipeline settings updated");
+            *pipeline.write().unwrap() = settings;
+        }
+        tracing::warn!("photo settings subscriber ended");
+    });
+}
+
 async fn ensure_bucket(s3_client: &S3Client, bucket: &str) -> anyhow::Result<()> {
     match s3_client.head_bucket().bucket(bucket).send().await {
         Ok(_) => {
@@ -1,9 +1,11 @@
 use anyhow::{anyhow, Context, Result};
 use aws_sdk_s3::Client as S3Client;
 use aws_sdk_s3::primitives::ByteStream;
+use guide_helper_api::photo::PhotoFormat;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
 use image::imageops::FilterType;
-use image::ImageFormat;
-use std::io::Cursor;
+use image::DynamicImage;
 
 /// Decodes a data URL (e.g. "data:image/jpeg;base64,/9j/4AAQ...") into raw bytes.
 pub fn decode_data_url(data_url: &str) -> Result<Vec<u8>> {
@@ -28,8 +30,9 @@ pub fn decode_data_url(data_url: &str) -> Result<Vec<u8>> {
     Ok(decoded)
 }
 
-/// Compresses an image: resizes if wider than max_width, encodes as JPEG with given quality.
-pub fn compress_image(data: &[u8], max_width: u32, quality: u8) -> Result<Vec<u8>> {
+/// Compresses an image: resizes if wider than max_width and encodes it in
+/// the given format (`quality` applies to JPEG only).
+pub fn compress_image(data: &[u8], max_width: u32, quality: u8, format: PhotoFormat) -> Result<Vec<u8>> {
     let img = image::load_from_memory(data).context("failed to load image from memory")?;
 
     let img = if img.width() > max_width {
@@ -47,34 +50,26 @@ pub fn compress_image(data: &[u8], max_width: u32, quality: u8) -> Result<Vec<u8
         img
     };
 
-    let mut buf = Cursor::new(Vec::new());
-    img.write_to(&mut buf, ImageFormat::Jpeg)
-        .context("failed to encode image as JPEG")?;
-
-    let result = buf.into_inner();
+    let result = encode(&img, quality, format).context("failed to encode image")?;
     tracing::debug!(
         output_size = result.len(),
         quality = quality,
-        "compressed image to JPEG"
+        ?format,
+        "compressed image"
     );
 
     Ok(result)
 }
 
 /// Creates a small thumbnail from raw image data.
-pub fn create_thumbnail(data: &[u8], width: u32) -> Result<Vec<u8>> {
+pub fn create_thumbnail(data: &[u8], width: u32, quality: u8, format: PhotoFormat) -> Result<Vec<u8>> {
     let img = image::load_from_memory(data).context("failed to load image for thumbnail")?;
 
     let ratio = width as f64 / img.width() as f64;
     let height = (img.height() as f64 * ratio) as u32;
     let thumb = img.resize(width, height, FilterType::Lanczos3);
 
-    let mut buf = Cursor::new(Vec::new());
-    thumb
-        .write_to(&mut buf, ImageFormat::Jpeg)
-        .context("failed to encode thumbnail as JPEG")?;
-
-    let result = buf.into_inner();
+    let result = encode(&thumb, quality, format).context("failed to encode thumbnail")?;
     tracing::debug!(
         thumb_width = width,
         thumb_height = height,
@@ -85,6 +80,18 @@ pub fn create_thumbnail(data: &[u8], width: u32) -> Result<Vec<u8>> {
     Ok(result)
 }
 
+fn encode(img: &DynamicImage, quality: u8, format: PhotoFormat) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    match format {
+        // JPEG has no alpha channel.
+        PhotoFormat::Jpeg => DynamicImage::ImageRgb8(img.to_rgb8())
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut buf, quality))?,
+        PhotoFormat::Webp => DynamicImage::ImageRgba8(img.to_rgba8())
+            .write_with_encoder(WebPEncoder::new_lossless(&mut buf))?,
+    }
+    Ok(buf)
+}
+
 /// Uploads data to S3/MinIO.
 pub async fn upload_to_s3(
     client: &S3Client,
@@ -127,6 +134,24 @@ mod tests {
         assert!(decode_data_url(data_url).is_err());
     }
 
+    #[test]
+    fn test_compress_image_honours_format_and_width() {
+        let png = {
+            let mut buf = Vec::new();
+            DynamicImage::new_rgba8(64, 32)
+                .write_to(&mut std::io::Cursor::new(&mut buf), image::ImageFormat::Png)
+                .unwrap();
+            buf
+        };
+
+        let jpeg = compress_image(&png, 32, 80, PhotoFormat::Jpeg).unwrap();
+        assert_eq!(image::guess_format(&jpeg).unwrap(), image::ImageFormat::Jpeg);
+        assert_eq!(image::load_from_memory(&jpeg).unwrap().width(), 32);
+
+        let webp = create_thumbnail(&png, 16, 80, PhotoFormat::Webp).unwrap();
+        assert_eq!(image::guess_format(&webp).unwrap(), image::ImageFormat::WebP);
+    }
+
     #[test]
     fn test_decode_data_url_not_base64() {
         let data_url = "data:image/png,aGVsbG8=";
@@ -0,0 +1,181 @@
+use anyhow::{Context, Result};
+use aws_sdk_s3::Client as S3Client;
+use guide_helper_api::events::{self, RouteChangedEvent, ROUTE_CHANGED_SUBJECT};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Records an uploaded object in the storage ledger. Keys are reused when a
+/// photo is reprocessed, so the size is overwritten rather than added.
+pub async fn record_object(pool: &PgPool, key: &str, user_id: Uuid, route_id: Uuid, size_bytes: usize) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO photo_storage_objects (object_key, user_id, route_id, size_bytes, updated_at)
+        VALUES ($1, $2, $3, $4, NOW())
+        ON CONFLICT (object_key) DO UPDATE SET size_bytes = EXCLUDED.size_bytes, updated_at = NOW()
+        "#,
+    )
+    .bind(key)
+    .bind(user_id)
+    .bind(route_id)
+    .bind(size_bytes as i64)
+    .execute(pool)
+    .await
+    .context("failed to record stored object")?;
+
+    tracing::debug!(key, size_bytes, "stored object recorded");
+    Ok(())
+}
+
+/// Moves the ledger rows of a deleted route to another route of the same
+/// owner that still shows the object, as the halves of a split or a merged
+/// route do, so the object is kept until that route goes too.
+async fn reassign_shown_objects(pool: &PgPool, route_id: Uuid) -> Result<u64> {
+    // Photo URLs end with the object key
+    let result = sqlx::query(
+        r#"
+        UPDATE photo_storage_objects o
+        SET route_id = r.id, updated_at = NOW()
+        FROM routes r
+        WHERE o.route_id = $1
+          AND r.user_id = o.user_id
+          AND strpos(r.points::text, o.object_key || '"') > 0
+        "#,
+    )
+    .bind(route_id)
+    .execute(pool)
+    .await
+    .context("failed to reassign shown objects")?;
+
+    Ok(result.rows_affected())
+}
+
+/// Deletes the objects of a route that no longer exists, and their ledger
+/// rows. Objects another route still shows are kept, and rows of objects
+/// that could not be deleted are kept for a later try.
+async fn remove_route_objects(pool: &PgPool, s3_client: &S3Client, bucket: &str, route_id: Uuid) -> Result<()> {
+    let (exists,): (bool,) = sqlx::query_as("SELECT EXISTS(SELECT 1 FROM routes WHERE id = $1)")
+        .bind(route_id)
+        .fetch_one(pool)
+        .await
+        .context("failed to check route existence")?;
+    if exists {
+        return Ok(());
+    }
+
+    let reassigned = reassign_shown_objects(pool, route_id).await?;
+    if reassigned > 0 {
+        tracing::info!(%route_id, reassigned, "kept objects other routes still show");
+    }
+
+    let keys: Vec<(String,)> = sqlx::query_as("SELECT object_key FROM photo_storage_objects WHERE route_id = $1")
+        .bind(route_id)
+        .fetch_all(pool)
+        .await
+        .context("failed to list stored objects")?;
+    if keys.is_empty() {
+        return Ok(());
+    }
+
+    let mut removed = Vec::with_capacity(keys.len());
+    for (key,) in keys {
+        match s3_client.delete_object().bucket(bucket).key(&key).send().await {
+            Ok(_) => removed.push(key),
+            Err(e) => tracing::warn!(%route_id, key = %key, error = %e, "failed to delete stored object"),
+        }
+    }
+
+    sqlx::query("DELETE FROM photo_storage_objects WHERE object_key = ANY($1)")
+        .bind(&removed)
+        .execute(pool)
+        .await
+        .context("failed to delete stored object records")?;
+
+    tracing::info!(%route_id, removed = removed.len(), "removed objects of deleted route");
+    Ok(())
+}
+
+/// Frees the storage of deleted routes. Uses a queue group so only one
+/// worker replica handles each event.
+pub fn spawn_cleanup_listener(client: async_nats::Client, pool: PgPool, s3_client: S3Client, bucket: String) {
+    tokio::spawn(async move {
+        let mut subscriber = match client
+            .queue_subscribe(ROUTE_CHANGED_SUBJECT, "photo-worker-storage".to_string())
+            .await
+        {
+            Ok(subscriber) => subscriber,
+            Err(e) => {
+                tracing::error!(error = %e, "failed to subscribe to route changes");
+                return;
+            }
+        };
+
+        use futures::StreamExt;
+        while let Some(msg) = subscriber.next().await {
+            let event: RouteChangedEvent = match events::decode(&msg.payload) {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::warn!(error = %e, "invalid route changed event");
+                    continue;
+                }
+            };
+            if let Err(e) = remove_route_objects(&pool, &s3_client, &bucket, event.route_id).await {
+                tracing::error!(route_id = %event.route_id, error = %e, "failed to clean up route storage");
+            }
+        }
+        tracing::warn!("route changes subscriber ended");
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_route(pool: &PgPool, user_id: Uuid, photo_keys: &[&str]) -> Uuid {
+        let points: Vec<serde_json::Value> = photo_keys
+            .iter()
+            .map(|key| {
+                serde_json::json!({
+                    "lat": 55.0,
+                    "lng": 37.0,
+                    "photo": { "original": format!("/photos/{key}"), "status": "done" },
+                })
+            })
+            .collect();
+        let route_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO routes (id, user_id, name, points, created_at, updated_at) VALUES ($1, $2, 'Route', $3, NOW(), NOW())")
+            .bind(route_id)
+            .bind(user_id)
+            .bind(serde_json::Value::Array(points))
+            .execute(pool)
+            .await
+            .unwrap();
+        route_id
+    }
+
+    async fn owner_of(pool: &PgPool, key: &str) -> Uuid {
+        sqlx::query_scalar("SELECT route_id FROM photo_storage_objects WHERE object_key = $1")
+            .bind(key)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "../routes/migrations")]
+    #[ignore = "needs Postgres, set DATABASE_URL"]
+    async fn test_reassign_keeps_objects_a_split_half_shows(pool: PgPool) {
+        let (user_id, other_user) = (Uuid::new_v4(), Uuid::new_v4());
+        let original = create_route(&pool, user_id, &["u/r/photo_0.jpg", "u/r/photo_1.jpg"]).await;
+        for key in ["u/r/photo_0.jpg", "u/r/photo_1.jpg", "u/r/photo_10.jpg"] {
+            record_object(&pool, key, user_id, original, 100).await.unwrap();
+        }
+        let half = create_route(&pool, user_id, &["u/r/photo_1.jpg"]).await;
+        // Someone else's route showing the object does not keep it
+        create_route(&pool, other_user, &["u/r/photo_0.jpg"]).await;
+        sqlx::query("DELETE FROM routes WHERE id = $1").bind(original).execute(&pool).await.unwrap();
+
+        assert_eq!(reassign_shown_objects(&pool, original).await.unwrap(), 1);
+        assert_eq!(owner_of(&pool, "u/r/photo_1.jpg").await, half);
+        assert_eq!(owner_of(&pool, "u/r/photo_0.jpg").await, original);
+        assert_eq!(owner_of(&pool, "u/r/photo_10.jpg").await, original);
+    }
+}
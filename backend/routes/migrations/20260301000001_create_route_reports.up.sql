@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS route_reports (
+    id UUID PRIMARY KEY,
+    route_id UUID NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    reporter_id UUID NOT NULL,
+    category TEXT NOT NULL CHECK (category IN ('dangerous', 'wrong_location', 'spam', 'copyright')),
+    details TEXT,
+    status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'resolved', 'dismissed')),
+    resolution_note TEXT,
+    resolved_by UUID,
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_route_reports_open_route_reporter ON route_reports(route_id, reporter_id) WHERE status = 'open';
+CREATE INDEX idx_route_reports_status_created ON route_reports(status, created_at DESC);
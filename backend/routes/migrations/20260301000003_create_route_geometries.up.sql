@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS route_geometries (
+    route_id UUID PRIMARY KEY REFERENCES routes(id) ON DELETE CASCADE,
+    segments JSONB NOT NULL,
+    distance_km DOUBLE PRECISION NOT NULL,
+    route_updated_at TIMESTAMPTZ NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS route_elevations (
+    route_id UUID PRIMARY KEY REFERENCES routes(id) ON DELETE CASCADE,
+    samples JSONB NOT NULL,
+    gain_m DOUBLE PRECISION NOT NULL,
+    loss_m DOUBLE PRECISION NOT NULL,
+    min_m DOUBLE PRECISION NOT NULL,
+    max_m DOUBLE PRECISION NOT NULL,
+    route_updated_at TIMESTAMPTZ NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
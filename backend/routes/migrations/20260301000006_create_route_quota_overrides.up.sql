@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS route_quota_overrides (
+    user_id UUID PRIMARY KEY,
+    max_routes BIGINT,
+    max_photo_bytes BIGINT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1 @@
+ALTER TABLE routes ADD COLUMN IF NOT EXISTS archived_at TIMESTAMPTZ;
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_chat_messages_created_at;
+DROP INDEX IF EXISTS idx_routes_created_at;
+DROP TABLE IF EXISTS usage_events;
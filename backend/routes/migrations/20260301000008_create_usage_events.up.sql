@@ -0,0 +1,10 @@
+-- One row per user and UTC day with at least one authenticated request.
+CREATE TABLE IF NOT EXISTS usage_events (
+    day DATE NOT NULL,
+    user_id UUID NOT NULL,
+    first_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (day, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_routes_created_at ON routes(created_at);
+CREATE INDEX IF NOT EXISTS idx_chat_messages_created_at ON chat_messages(created_at);
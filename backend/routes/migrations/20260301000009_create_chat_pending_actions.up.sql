@@ -0,0 +1,12 @@
+-- Tool calls proposed by the chat assistant that wait for the user's confirmation.
+CREATE TABLE IF NOT EXISTS chat_pending_actions (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    conversation_id UUID NOT NULL,
+    tool TEXT NOT NULL,
+    arguments JSONB NOT NULL,
+    summary TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_chat_pending_actions_created_at ON chat_pending_actions(created_at);
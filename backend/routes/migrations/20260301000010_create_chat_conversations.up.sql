@@ -0,0 +1,10 @@
+-- Per-conversation settings. Conversations are defined by their messages;
+-- a row only exists once a setting differs from the defaults.
+CREATE TABLE IF NOT EXISTS chat_conversations (
+    user_id UUID NOT NULL,
+    conversation_id UUID NOT NULL,
+    archived BOOLEAN NOT NULL DEFAULT FALSE,
+    pinned BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, conversation_id)
+);
@@ -0,0 +1 @@
+ALTER TABLE chat_conversations DROP COLUMN IF EXISTS share_token;
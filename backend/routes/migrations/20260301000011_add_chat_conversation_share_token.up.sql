@@ -0,0 +1 @@
+ALTER TABLE chat_conversations ADD COLUMN IF NOT EXISTS share_token UUID UNIQUE;
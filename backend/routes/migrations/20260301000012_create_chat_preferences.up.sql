@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS chat_preferences (
+    user_id UUID PRIMARY KEY,
+    language VARCHAR(8) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,2 @@
+ALTER TABLE routes DROP COLUMN IF EXISTS cover_thumbnail_url;
+ALTER TABLE routes DROP COLUMN IF EXISTS cover_url;
@@ -0,0 +1,2 @@
+ALTER TABLE routes ADD COLUMN IF NOT EXISTS cover_url TEXT;
+ALTER TABLE routes ADD COLUMN IF NOT EXISTS cover_thumbnail_url TEXT;
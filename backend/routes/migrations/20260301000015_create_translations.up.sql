@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS translations (
+    key VARCHAR(200) NOT NULL,
+    locale VARCHAR(10) NOT NULL,
+    value TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (key, locale)
+);
+
+-- Template parameters, so notifications can be rendered in the reader's locale
+ALTER TABLE notifications ADD COLUMN IF NOT EXISTS params JSONB;
+
+INSERT INTO translations (key, locale, value) VALUES
+    ('notification.like', 'ru', '{actor} отметил(а) ваш маршрут «{route}»'),
+    ('notification.comment', 'ru', '{actor} прокомментировал(а) ваш маршрут «{route}»'),
+    ('notification.rating', 'ru', '{actor} оценил(а) ваш маршрут «{route}» на {rating}/5'),
+    ('notification.route_report', 'ru', '{actor} пожаловался(-ась) на маршрут «{route}»: {category}'),
+    ('notification.report_resolved', 'ru', 'Модератор рассмотрел вашу жалобу, статус: {status}'),
+    ('notification.route_updated', 'ru', 'Сохранённый вами маршрут «{route}» обновлён'),
+    ('notification.photos_processed', 'ru', 'Фото маршрута «{route}» обработаны: готово {processed}, с ошибкой {failed}'),
+    ('difficulty.easy', 'ru', 'лёгкий'),
+    ('difficulty.moderate', 'ru', 'средний'),
+    ('difficulty.hard', 'ru', 'сложный')
+ON CONFLICT (key, locale) DO NOTHING;
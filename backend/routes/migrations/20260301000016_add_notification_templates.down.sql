@@ -0,0 +1,14 @@
+DELETE FROM translations
+WHERE locale = 'ru' AND key IN (
+    'notification.photo_processed',
+    'notification.photo_processed_failed',
+    'notification.photos_processed_failed',
+    'notification.data_export_ready',
+    'notification.data_export_failed'
+);
+UPDATE translations
+SET value = 'Фото маршрута «{route}» обработаны: готово {processed}, с ошибкой {failed}'
+WHERE key = 'notification.photos_processed' AND locale = 'ru'
+  AND value = 'Обработано фото маршрута «{route}»: {processed}';
+
+ALTER TABLE notifications DROP COLUMN IF EXISTS template_key;
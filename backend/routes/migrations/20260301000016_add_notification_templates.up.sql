@@ -0,0 +1,19 @@
+ALTER TABLE notifications ADD COLUMN IF NOT EXISTS template_key VARCHAR(200);
+
+-- Notifications created with parameters so far used the type as the key
+UPDATE notifications SET template_key = 'notification.' || notification_type
+WHERE params IS NOT NULL AND template_key IS NULL;
+
+-- Superseded by the per-count keys below
+DELETE FROM translations
+WHERE key = 'notification.photos_processed' AND locale = 'ru'
+  AND value = 'Фото маршрута «{route}» обработаны: готово {processed}, с ошибкой {failed}';
+
+INSERT INTO translations (key, locale, value) VALUES
+    ('notification.photo_processed', 'ru', 'Обработано фото маршрута «{route}»: {processed}'),
+    ('notification.photos_processed', 'ru', 'Обработано фото маршрута «{route}»: {processed}'),
+    ('notification.photo_processed_failed', 'ru', 'Обработано фото маршрута «{route}»: {processed}, с ошибкой: {failed}'),
+    ('notification.photos_processed_failed', 'ru', 'Обработано фото маршрута «{route}»: {processed}, с ошибкой: {failed}'),
+    ('notification.data_export_ready', 'ru', 'Архив с вашими данными готов. Ссылка действует {hours} ч: {url}'),
+    ('notification.data_export_failed', 'ru', 'Не удалось подготовить архив с вашими данными. Попробуйте позже.')
+ON CONFLICT (key, locale) DO NOTHING;
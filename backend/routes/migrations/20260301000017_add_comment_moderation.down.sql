@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_comments_route_id_pending;
+ALTER TABLE comments DROP COLUMN IF EXISTS approved;
+ALTER TABLE routes DROP COLUMN IF EXISTS comment_policy;
@@ -0,0 +1,4 @@
+ALTER TABLE routes ADD COLUMN IF NOT EXISTS comment_policy VARCHAR(20) NOT NULL DEFAULT 'open';
+ALTER TABLE comments ADD COLUMN IF NOT EXISTS approved BOOLEAN NOT NULL DEFAULT TRUE;
+
+CREATE INDEX IF NOT EXISTS idx_comments_route_id_pending ON comments(route_id) WHERE NOT approved;
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS spam_flags (
+    id UUID PRIMARY KEY,
+    content_type VARCHAR(20) NOT NULL,
+    content_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    reasons TEXT NOT NULL,
+    reviewed_by UUID,
+    reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_spam_flags_unreviewed ON spam_flags(created_at DESC) WHERE reviewed_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_comments_text_created_at ON comments(md5(text), created_at);
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS route_activity (
+    id UUID PRIMARY KEY,
+    route_id UUID NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    actor_id UUID,
+    kind VARCHAR(30) NOT NULL,
+    details JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_route_activity_route_id ON route_activity(route_id, created_at);
+
+-- Start the log of existing routes with what is already known
+INSERT INTO route_activity (id, route_id, actor_id, kind, created_at)
+SELECT gen_random_uuid(), id, user_id, 'created', created_at FROM routes;
+
+INSERT INTO route_activity (id, route_id, actor_id, kind, details, created_at)
+SELECT gen_random_uuid(), route_id, user_id, 'commented', jsonb_build_object('comment_id', id, 'author_name', author_name), created_at
+FROM comments;
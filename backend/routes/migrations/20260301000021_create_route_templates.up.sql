@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS route_templates (
+    route_id UUID PRIMARY KEY REFERENCES routes(id) ON DELETE CASCADE,
+    owner_id UUID NOT NULL,
+    public BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_route_templates_owner_id ON route_templates(owner_id);
+CREATE INDEX IF NOT EXISTS idx_route_templates_public ON route_templates(public) WHERE public;
@@ -0,0 +1,5 @@
+DELETE FROM translations WHERE locale = 'ru' AND key = 'notification.saved_search_match';
+
+DROP TABLE IF EXISTS saved_searches;
+DROP INDEX IF EXISTS idx_routes_shared_at;
+ALTER TABLE routes DROP COLUMN IF EXISTS shared_at;
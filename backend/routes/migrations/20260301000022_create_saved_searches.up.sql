@@ -0,0 +1,23 @@
+-- When the route was last shared; saved search alerts only look at routes shared since their last check
+ALTER TABLE routes ADD COLUMN IF NOT EXISTS shared_at TIMESTAMPTZ;
+
+CREATE TABLE IF NOT EXISTS saved_searches (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    search TEXT,
+    category_id UUID REFERENCES categories(id) ON DELETE CASCADE,
+    min_lat DOUBLE PRECISION,
+    min_lng DOUBLE PRECISION,
+    max_lat DOUBLE PRECISION,
+    max_lng DOUBLE PRECISION,
+    last_checked_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_saved_searches_user_id ON saved_searches(user_id);
+CREATE INDEX IF NOT EXISTS idx_routes_shared_at ON routes(shared_at) WHERE shared_at IS NOT NULL;
+
+INSERT INTO translations (key, locale, value) VALUES
+    ('notification.saved_search_match', 'ru', 'Новый маршрут «{route}» подходит под ваш поиск «{search}»')
+ON CONFLICT (key, locale) DO NOTHING;
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_chat_conversations_flagged_at;
+ALTER TABLE chat_conversations
+    DROP COLUMN IF EXISTS review_consent,
+    DROP COLUMN IF EXISTS flag_reason,
+    DROP COLUMN IF EXISTS flagged_at;
+
+DROP TABLE IF EXISTS chat_turns;
@@ -0,0 +1,22 @@
+-- One row per assistant turn, for the admin chat operations dashboard
+CREATE TABLE IF NOT EXISTS chat_turns (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    conversation_id UUID NOT NULL,
+    tool_calls JSONB NOT NULL DEFAULT '[]',
+    latency_ms BIGINT NOT NULL,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_chat_turns_created_at ON chat_turns(created_at);
+
+-- Conversations users flagged for abuse review; admins may only read the
+-- messages when the user consented to it
+ALTER TABLE chat_conversations
+    ADD COLUMN IF NOT EXISTS flagged_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS flag_reason TEXT,
+    ADD COLUMN IF NOT EXISTS review_consent BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS idx_chat_conversations_flagged_at
+    ON chat_conversations(flagged_at) WHERE flagged_at IS NOT NULL;
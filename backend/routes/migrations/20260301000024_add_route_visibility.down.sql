@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_routes_public;
+ALTER TABLE routes DROP COLUMN IF EXISTS visibility;
@@ -0,0 +1,10 @@
+-- Who can see a route: only the owner, anyone with the share link, or
+-- everyone through explore and search. Routes shared before visibility
+-- existed keep their links but leave explore until published again.
+ALTER TABLE routes
+    ADD COLUMN IF NOT EXISTS visibility TEXT NOT NULL DEFAULT 'private'
+    CHECK (visibility IN ('private', 'unlisted', 'public'));
+
+UPDATE routes SET visibility = 'unlisted', shared_at = NULL WHERE share_token IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_routes_public ON routes(created_at) WHERE visibility = 'public';
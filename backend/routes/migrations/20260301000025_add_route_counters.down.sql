@@ -0,0 +1,6 @@
+DROP TRIGGER IF EXISTS trg_route_bookmarks_count ON route_bookmarks;
+DROP TRIGGER IF EXISTS trg_route_likes_count ON route_likes;
+DROP FUNCTION IF EXISTS route_bookmarks_count();
+DROP FUNCTION IF EXISTS route_likes_count();
+ALTER TABLE routes DROP COLUMN IF EXISTS bookmarks_count;
+ALTER TABLE routes DROP COLUMN IF EXISTS likes_count;
@@ -0,0 +1,42 @@
+-- Like and bookmark counts kept on the route row so listings do not
+-- aggregate the child tables on every request. Triggers maintain them;
+-- a periodic job repairs any drift.
+ALTER TABLE routes
+    ADD COLUMN IF NOT EXISTS likes_count BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS bookmarks_count BIGINT NOT NULL DEFAULT 0;
+
+UPDATE routes r SET
+    likes_count = (SELECT COUNT(*) FROM route_likes WHERE route_id = r.id),
+    bookmarks_count = (SELECT COUNT(*) FROM route_bookmarks WHERE route_id = r.id);
+
+CREATE OR REPLACE FUNCTION route_likes_count() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE routes SET likes_count = likes_count + 1 WHERE id = NEW.route_id;
+    ELSE
+        UPDATE routes SET likes_count = likes_count - 1 WHERE id = OLD.route_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION route_bookmarks_count() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE routes SET bookmarks_count = bookmarks_count + 1 WHERE id = NEW.route_id;
+    ELSE
+        UPDATE routes SET bookmarks_count = bookmarks_count - 1 WHERE id = OLD.route_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_route_likes_count ON route_likes;
+CREATE TRIGGER trg_route_likes_count
+    AFTER INSERT OR DELETE ON route_likes
+    FOR EACH ROW EXECUTE FUNCTION route_likes_count();
+
+DROP TRIGGER IF EXISTS trg_route_bookmarks_count ON route_bookmarks;
+CREATE TRIGGER trg_route_bookmarks_count
+    AFTER INSERT OR DELETE ON route_bookmarks
+    FOR EACH ROW EXECUTE FUNCTION route_bookmarks_count();
@@ -0,0 +1,11 @@
+-- Extra share links per route, each labelled and revocable on its own. The
+-- URL token is an HMAC over the id, so only the id is stored.
+CREATE TABLE IF NOT EXISTS route_share_links (
+    id UUID PRIMARY KEY,
+    route_id UUID NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    label VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_route_share_links_route_id ON route_share_links(route_id, created_at);
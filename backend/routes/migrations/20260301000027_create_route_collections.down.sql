@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS route_collection_routes;
+DROP TABLE IF EXISTS route_collections;
@@ -0,0 +1,20 @@
+-- Named folders the owner groups their routes in. A route can be in several
+-- collections; deleting a collection keeps its routes.
+CREATE TABLE IF NOT EXISTS route_collections (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_route_collections_user_name ON route_collections(user_id, LOWER(name));
+
+CREATE TABLE IF NOT EXISTS route_collection_routes (
+    collection_id UUID NOT NULL REFERENCES route_collections(id) ON DELETE CASCADE,
+    route_id UUID NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (collection_id, route_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_route_collection_routes_route_id ON route_collection_routes(route_id);
@@ -0,0 +1,158 @@
+-- SQLite schema equivalent to the Postgres migrations up to 20260301000005.
+-- UUIDs are 16-byte BLOBs, timestamps RFC 3339 TEXT, JSONB/arrays JSON TEXT.
+
+CREATE TABLE IF NOT EXISTS routes (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL,
+    name TEXT NOT NULL,
+    points TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    share_token BLOB DEFAULT NULL,
+    start_location TEXT,
+    end_location TEXT,
+    seasons TEXT NOT NULL DEFAULT '[]',
+    description TEXT
+);
+
+CREATE INDEX idx_routes_user_id ON routes(user_id);
+CREATE UNIQUE INDEX idx_routes_share_token ON routes(share_token) WHERE share_token IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS comments (
+    id BLOB PRIMARY KEY,
+    route_id BLOB NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    user_id BLOB NOT NULL,
+    author_name TEXT NOT NULL,
+    text TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_comments_route_id_created_at ON comments(route_id, created_at);
+
+CREATE TABLE IF NOT EXISTS route_likes (
+    id BLOB PRIMARY KEY,
+    route_id BLOB NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    user_id BLOB NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_route_likes_route_user ON route_likes(route_id, user_id);
+
+CREATE TABLE IF NOT EXISTS route_ratings (
+    id BLOB PRIMARY KEY,
+    route_id BLOB NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    user_id BLOB NOT NULL,
+    rating INTEGER NOT NULL CHECK (rating >= 1 AND rating <= 5),
+    created_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_route_ratings_route_user ON route_ratings(route_id, user_id);
+
+CREATE TABLE IF NOT EXISTS settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+INSERT INTO settings (key, value) VALUES (
+    'difficulty_thresholds',
+    '{"distance_easy_max_km": 5.0, "distance_moderate_max_km": 15.0, "elevation_easy_max_m": 300.0, "elevation_moderate_max_m": 800.0, "score_easy_max": 3, "score_moderate_max": 4}'
+) ON CONFLICT (key) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS categories (
+    id BLOB PRIMARY KEY,
+    name TEXT UNIQUE NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+INSERT INTO categories (id, name) VALUES
+    (randomblob(16), 'hiking'),
+    (randomblob(16), 'cycling'),
+    (randomblob(16), 'historical'),
+    (randomblob(16), 'nature'),
+    (randomblob(16), 'urban')
+ON CONFLICT (name) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS route_categories (
+    route_id BLOB NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    category_id BLOB NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    PRIMARY KEY (route_id, category_id)
+);
+
+CREATE TABLE IF NOT EXISTS notifications (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL,
+    notification_type TEXT NOT NULL,
+    route_id BLOB NOT NULL,
+    actor_name TEXT NOT NULL,
+    message TEXT NOT NULL,
+    is_read BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_notifications_user_created ON notifications(user_id, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS chat_messages (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL,
+    conversation_id BLOB NOT NULL,
+    role TEXT NOT NULL,
+    content TEXT NOT NULL,
+    actions TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_chat_messages_user_conv ON chat_messages(user_id, conversation_id, created_at ASC);
+
+CREATE TABLE IF NOT EXISTS route_bookmarks (
+    id BLOB PRIMARY KEY,
+    route_id BLOB NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    user_id BLOB NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_route_bookmarks_route_user ON route_bookmarks(route_id, user_id);
+CREATE INDEX idx_route_bookmarks_user_id ON route_bookmarks(user_id);
+
+CREATE TABLE IF NOT EXISTS route_reports (
+    id BLOB PRIMARY KEY,
+    route_id BLOB NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    reporter_id BLOB NOT NULL,
+    category TEXT NOT NULL CHECK (category IN ('dangerous', 'wrong_location', 'spam', 'copyright')),
+    details TEXT,
+    status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'resolved', 'dismissed')),
+    resolution_note TEXT,
+    resolved_by BLOB,
+    resolved_at TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_route_reports_open_route_reporter ON route_reports(route_id, reporter_id) WHERE status = 'open';
+CREATE INDEX idx_route_reports_status_created ON route_reports(status, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS route_geometries (
+    route_id BLOB PRIMARY KEY REFERENCES routes(id) ON DELETE CASCADE,
+    segments TEXT NOT NULL,
+    distance_km REAL NOT NULL,
+    route_updated_at TEXT NOT NULL,
+    computed_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS route_elevations (
+    route_id BLOB PRIMARY KEY REFERENCES routes(id) ON DELETE CASCADE,
+    samples TEXT NOT NULL,
+    gain_m REAL NOT NULL,
+    loss_m REAL NOT NULL,
+    min_m REAL NOT NULL,
+    max_m REAL NOT NULL,
+    route_updated_at TEXT NOT NULL,
+    computed_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id BLOB NOT NULL,
+    notification_type TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, notification_type)
+);
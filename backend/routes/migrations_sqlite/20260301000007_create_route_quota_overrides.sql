@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS route_quota_overrides (
+    user_id BLOB PRIMARY KEY,
+    max_routes INTEGER,
+    max_photo_bytes INTEGER,
+    updated_at TEXT NOT NULL
+);
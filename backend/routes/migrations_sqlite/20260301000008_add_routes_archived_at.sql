@@ -0,0 +1 @@
+ALTER TABLE routes ADD COLUMN archived_at TEXT;
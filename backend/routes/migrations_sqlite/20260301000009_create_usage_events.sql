@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS usage_events (
+    day TEXT NOT NULL,
+    user_id BLOB NOT NULL,
+    first_seen_at TEXT NOT NULL,
+    PRIMARY KEY (day, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_routes_created_at ON routes(created_at);
+CREATE INDEX IF NOT EXISTS idx_chat_messages_created_at ON chat_messages(created_at);
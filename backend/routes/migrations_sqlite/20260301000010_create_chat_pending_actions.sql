@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS chat_pending_actions (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL,
+    conversation_id BLOB NOT NULL,
+    tool TEXT NOT NULL,
+    arguments TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chat_pending_actions_created_at ON chat_pending_actions(created_at);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS chat_conversations (
+    user_id BLOB NOT NULL,
+    conversation_id BLOB NOT NULL,
+    archived INTEGER NOT NULL DEFAULT 0,
+    pinned INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, conversation_id)
+);
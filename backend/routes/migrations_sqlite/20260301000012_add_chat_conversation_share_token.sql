@@ -0,0 +1,2 @@
+ALTER TABLE chat_conversations ADD COLUMN share_token BLOB;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_chat_conversations_share_token ON chat_conversations(share_token);
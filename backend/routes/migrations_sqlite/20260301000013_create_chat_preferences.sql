@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS chat_preferences (
+    user_id BLOB PRIMARY KEY,
+    language TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE routes ADD COLUMN cover_url TEXT;
+ALTER TABLE routes ADD COLUMN cover_thumbnail_url TEXT;
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS photo_storage_objects (
+    object_key TEXT PRIMARY KEY,
+    user_id BLOB NOT NULL,
+    route_id BLOB NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_photo_storage_objects_user_id ON photo_storage_objects(user_id);
+CREATE INDEX IF NOT EXISTS idx_photo_storage_objects_route_id ON photo_storage_objects(route_id);
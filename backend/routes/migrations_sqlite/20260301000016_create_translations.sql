@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS translations (
+    key TEXT NOT NULL,
+    locale TEXT NOT NULL,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    PRIMARY KEY (key, locale)
+);
+
+ALTER TABLE notifications ADD COLUMN params TEXT;
+
+INSERT OR IGNORE INTO translations (key, locale, value) VALUES
+    ('notification.like', 'ru', '{actor} отметил(а) ваш маршрут «{route}»'),
+    ('notification.comment', 'ru', '{actor} прокомментировал(а) ваш маршрут «{route}»'),
+    ('notification.rating', 'ru', '{actor} оценил(а) ваш маршрут «{route}» на {rating}/5'),
+    ('notification.route_report', 'ru', '{actor} пожаловался(-ась) на маршрут «{route}»: {category}'),
+    ('notification.report_resolved', 'ru', 'Модератор рассмотрел вашу жалобу, статус: {status}'),
+    ('notification.route_updated', 'ru', 'Сохранённый вами маршрут «{route}» обновлён'),
+    ('notification.photos_processed', 'ru', 'Фото маршрута «{route}» обработаны: готово {processed}, с ошибкой {failed}'),
+    ('difficulty.easy', 'ru', 'лёгкий'),
+    ('difficulty.moderate', 'ru', 'средний'),
+    ('difficulty.hard', 'ru', 'сложный');
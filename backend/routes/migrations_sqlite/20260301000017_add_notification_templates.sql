@@ -0,0 +1,16 @@
+ALTER TABLE notifications ADD COLUMN template_key TEXT;
+
+UPDATE notifications SET template_key = 'notification.' || notification_type
+WHERE params IS NOT NULL AND template_key IS NULL;
+
+DELETE FROM translations
+WHERE key = 'notification.photos_processed' AND locale = 'ru'
+  AND value = 'Фото маршрута «{route}» обработаны: готово {processed}, с ошибкой {failed}';
+
+INSERT OR IGNORE INTO translations (key, locale, value) VALUES
+    ('notification.photo_processed', 'ru', 'Обработано фото маршрута «{route}»: {processed}'),
+    ('notification.photos_processed', 'ru', 'Обработано фото маршрута «{route}»: {processed}'),
+    ('notification.photo_processed_failed', 'ru', 'Обработано фото маршрута «{route}»: {processed}, с ошибкой: {failed}'),
+    ('notification.photos_processed_failed', 'ru', 'Обработано фото маршрута «{route}»: {processed}, с ошибкой: {failed}'),
+    ('notification.data_export_ready', 'ru', 'Архив с вашими данными готов. Ссылка действует {hours} ч: {url}'),
+    ('notification.data_export_failed', 'ru', 'Не удалось подготовить архив с вашими данными. Попробуйте позже.');
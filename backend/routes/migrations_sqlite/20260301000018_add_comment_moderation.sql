@@ -0,0 +1,4 @@
+ALTER TABLE routes ADD COLUMN comment_policy TEXT NOT NULL DEFAULT 'open';
+ALTER TABLE comments ADD COLUMN approved BOOLEAN NOT NULL DEFAULT 1;
+
+CREATE INDEX idx_comments_route_id_pending ON comments(route_id) WHERE NOT approved;
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS spam_flags (
+    id TEXT PRIMARY KEY,
+    content_type TEXT NOT NULL,
+    content_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    reasons TEXT NOT NULL,
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_spam_flags_created_at ON spam_flags(created_at);
@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS route_activity (
+    id BLOB PRIMARY KEY,
+    route_id BLOB NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    actor_id BLOB,
+    kind TEXT NOT NULL,
+    details TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_route_activity_route_id ON route_activity(route_id, created_at);
+
+-- Start the log of existing routes with what is already known
+INSERT INTO route_activity (id, route_id, actor_id, kind, created_at)
+SELECT randomblob(16), id, user_id, 'created', created_at FROM routes;
+
+INSERT INTO route_activity (id, route_id, actor_id, kind, details, created_at)
+SELECT
+    randomblob(16), route_id, user_id, 'commented',
+    json_object(
+        'comment_id',
+        lower(substr(hex(id), 1, 8) || '-' || substr(hex(id), 9, 4) || '-' || substr(hex(id), 13, 4) || '-'
+            || substr(hex(id), 17, 4) || '-' || substr(hex(id), 21)),
+        'author_name', author_name
+    ),
+    created_at
+FROM comments;
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS route_templates (
+    route_id BLOB PRIMARY KEY REFERENCES routes(id) ON DELETE CASCADE,
+    owner_id BLOB NOT NULL,
+    public INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_route_templates_owner_id ON route_templates(owner_id);
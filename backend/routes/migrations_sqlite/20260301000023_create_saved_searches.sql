@@ -0,0 +1,20 @@
+ALTER TABLE routes ADD COLUMN shared_at TEXT;
+
+CREATE TABLE IF NOT EXISTS saved_searches (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL,
+    name TEXT NOT NULL,
+    search TEXT,
+    category_id BLOB REFERENCES categories(id) ON DELETE CASCADE,
+    min_lat REAL,
+    min_lng REAL,
+    max_lat REAL,
+    max_lng REAL,
+    last_checked_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_saved_searches_user_id ON saved_searches(user_id);
+
+INSERT OR IGNORE INTO translations (key, locale, value) VALUES
+    ('notification.saved_search_match', 'ru', 'Новый маршрут «{route}» подходит под ваш поиск «{search}»');
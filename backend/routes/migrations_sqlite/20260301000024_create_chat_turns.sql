@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS chat_turns (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL,
+    conversation_id BLOB NOT NULL,
+    tool_calls TEXT NOT NULL DEFAULT '[]',
+    latency_ms INTEGER NOT NULL,
+    error TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_chat_turns_created_at ON chat_turns(created_at);
+
+ALTER TABLE chat_conversations ADD COLUMN flagged_at TEXT;
+ALTER TABLE chat_conversations ADD COLUMN flag_reason TEXT;
+ALTER TABLE chat_conversations ADD COLUMN review_consent INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,8 @@
+-- Routes shared before visibility existed keep their links but leave
+-- explore until published again
+ALTER TABLE routes ADD COLUMN visibility TEXT NOT NULL DEFAULT 'private'
+    CHECK (visibility IN ('private', 'unlisted', 'public'));
+
+UPDATE routes SET visibility = 'unlisted', shared_at = NULL WHERE share_token IS NOT NULL;
+
+CREATE INDEX idx_routes_public ON routes(created_at) WHERE visibility = 'public';
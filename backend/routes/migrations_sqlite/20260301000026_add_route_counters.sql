@@ -0,0 +1,27 @@
+-- Like and bookmark counts kept on the route row, maintained by triggers
+ALTER TABLE routes ADD COLUMN likes_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE routes ADD COLUMN bookmarks_count INTEGER NOT NULL DEFAULT 0;
+
+UPDATE routes SET
+    likes_count = (SELECT COUNT(*) FROM route_likes WHERE route_id = routes.id),
+    bookmarks_count = (SELECT COUNT(*) FROM route_bookmarks WHERE route_id = routes.id);
+
+CREATE TRIGGER trg_route_likes_insert AFTER INSERT ON route_likes
+BEGIN
+    UPDATE routes SET likes_count = likes_count + 1 WHERE id = NEW.route_id;
+END;
+
+CREATE TRIGGER trg_route_likes_delete AFTER DELETE ON route_likes
+BEGIN
+    UPDATE routes SET likes_count = likes_count - 1 WHERE id = OLD.route_id;
+END;
+
+CREATE TRIGGER trg_route_bookmarks_insert AFTER INSERT ON route_bookmarks
+BEGIN
+    UPDATE routes SET bookmarks_count = bookmarks_count + 1 WHERE id = NEW.route_id;
+END;
+
+CREATE TRIGGER trg_route_bookmarks_delete AFTER DELETE ON route_bookmarks
+BEGIN
+    UPDATE routes SET bookmarks_count = bookmarks_count - 1 WHERE id = OLD.route_id;
+END;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS route_share_links (
+    id BLOB PRIMARY KEY,
+    route_id BLOB NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    label TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    revoked_at TEXT
+);
+
+CREATE INDEX idx_route_share_links_route_id ON route_share_links(route_id, created_at);
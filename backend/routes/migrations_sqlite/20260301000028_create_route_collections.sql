@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS route_collections (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL,
+    name TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_route_collections_user_name ON route_collections(user_id, LOWER(name));
+
+CREATE TABLE IF NOT EXISTS route_collection_routes (
+    collection_id BLOB NOT NULL REFERENCES route_collections(id) ON DELETE CASCADE,
+    route_id BLOB NOT NULL REFERENCES routes(id) ON DELETE CASCADE,
+    added_at TEXT NOT NULL,
+    PRIMARY KEY (collection_id, route_id)
+);
+
+CREATE INDEX idx_route_collection_routes_route_id ON route_collection_routes(route_id);
@@ -1,11 +1,55 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
 use config::{Config, Environment};
 use serde::Deserialize;
 
+use crate::repository::pool::PoolSettings;
+use crate::repository::retry::RetryPolicy;
+use crate::telemetry::LogFormat;
+use crate::usecase::circuit_breaker::BreakerSettings;
+use crate::usecase::spam::SpamSettings;
+
 #[derive(Debug, Deserialize)]
 pub struct AppConfig {
     pub database_url: String,
     pub database_max_connections: u32,
+    /// Optional read replica for explore, counts, admin listings and chat
+    /// history. Writes always go to `database_url`.
+    #[serde(default)]
+    pub database_read_url: Option<String>,
+    #[serde(default)]
+    pub database_min_connections: u32,
+    #[serde(default = "default_database_acquire_timeout_secs")]
+    pub database_acquire_timeout_secs: u64,
+    /// 0 keeps idle connections open indefinitely.
+    #[serde(default = "default_database_idle_timeout_secs")]
+    pub database_idle_timeout_secs: u64,
+    /// 0 never recycles connections.
+    #[serde(default = "default_database_max_lifetime_secs")]
+    pub database_max_lifetime_secs: u64,
+    /// Attempts per repository call on transient errors; 1 disables retries.
+    #[serde(default = "default_database_retry_max_attempts")]
+    pub database_retry_max_attempts: u32,
+    #[serde(default = "default_database_retry_base_delay_ms")]
+    pub database_retry_base_delay_ms: u64,
+    #[serde(default = "default_database_retry_max_delay_ms")]
+    pub database_retry_max_delay_ms: u64,
+    /// Apply pending migrations at startup. Disable when migrations run as a
+    /// separate deploy step (`routes --migrate-only`).
+    #[serde(default = "default_auto_migrate")]
+    pub auto_migrate: bool,
     pub jwt_secret: String,
+    /// Key signing share link tokens. When unset, a key derived from the JWT
+    /// secret is used, so rotating `JWT_SECRET` invalidates every share link
+    /// handed out; set this to keep links across rotations.
+    #[serde(default)]
+    pub share_link_secret: Option<String>,
+    /// Until when `/shared/{token}` still opens routes by their bare share
+    /// token, as links were handed out before they were signed (RFC 3339).
+    /// Bare tokens are rejected when unset.
+    #[serde(default)]
+    pub legacy_share_tokens_until: Option<DateTime<Utc>>,
     #[serde(default)]
     pub telemetry_enabled: bool,
     #[serde(default = "default_telemetry_service_name")]
@@ -16,6 +60,9 @@ pub struct AppConfig {
     pub telemetry_environment: String,
     #[serde(default = "default_telemetry_otlp_endpoint")]
     pub telemetry_otlp_endpoint: String,
+    /// "text" (default) or "json" for log shippers.
+    #[serde(default)]
+    pub log_format: LogFormat,
     #[serde(default = "default_nats_url")]
     pub nats_url: String,
     #[serde(default)]
@@ -24,20 +71,216 @@ pub struct AppConfig {
     pub openai_base_url: String,
     #[serde(default = "default_openai_model")]
     pub openai_model: String,
+    /// Per-attempt timeout for assistant model calls.
+    #[serde(default = "default_openai_timeout_secs")]
+    pub openai_timeout_secs: u64,
+    /// Retries after a timeout, 5xx or 429 before giving up on a model.
+    #[serde(default = "default_openai_max_retries")]
+    pub openai_max_retries: u32,
+    /// Model used when `openai_model` keeps failing; answers from it carry a
+    /// degraded-mode notice.
+    #[serde(default)]
+    pub openai_fallback_model: Option<String>,
+    /// Whether the assistant models accept image attachments.
+    #[serde(default)]
+    pub openai_vision_enabled: bool,
     #[serde(default = "default_chat_rate_limit_max")]
     pub chat_rate_limit_max: u32,
     #[serde(default = "default_chat_rate_limit_window_secs")]
     pub chat_rate_limit_window_secs: u64,
+    /// Where rate limit counters live: "memory" (per replica) or "nats"
+    /// (shared by all replicas).
+    #[serde(default = "default_rate_limit_backend")]
+    pub rate_limit_backend: String,
     #[serde(default = "default_chat_max_tool_iterations")]
     pub chat_max_tool_iterations: usize,
     #[serde(default = "default_nominatim_url")]
     pub nominatim_url: String,
+    /// Reverse geocoder for route locations: "nominatim", "photon" or "mapbox".
+    #[serde(default = "default_geocoder_provider")]
+    pub geocoder_provider: String,
+    /// Provider base URL; defaults to NOMINATIM_URL or the provider's public API.
+    #[serde(default)]
+    pub geocoder_url: Option<String>,
+    #[serde(default)]
+    pub geocoder_api_key: Option<String>,
+    /// Overrides the provider's default delay between requests.
+    #[serde(default)]
+    pub geocoder_request_interval_ms: Option<u64>,
+    /// Consecutive failures before calls to an external service (geocoder,
+    /// LLM, OSRM, elevation) fail fast.
+    #[serde(default = "default_circuit_breaker_failure_threshold")]
+    pub circuit_breaker_failure_threshold: u32,
+    /// How long an open breaker fails fast before letting a trial call through.
+    #[serde(default = "default_circuit_breaker_open_secs")]
+    pub circuit_breaker_open_secs: u64,
     #[serde(default = "default_chat_max_message_length")]
     pub chat_max_message_length: usize,
+    /// Substituted for `{app_name}` in the chat system prompt.
+    #[serde(default = "default_app_name")]
+    pub app_name: String,
+    /// How long a replica keeps the admin-edited chat prompt before
+    /// re-reading it from settings.
+    #[serde(default = "default_chat_prompt_cache_secs")]
+    pub chat_prompt_cache_secs: u64,
+    /// How long a replica keeps the admin-edited blocked-term filter before
+    /// re-reading it from settings.
+    #[serde(default = "default_content_filter_cache_secs")]
+    pub content_filter_cache_secs: u64,
+    #[serde(default = "default_route_max_points")]
+    pub route_max_points: usize,
+    /// Maximum size of a point's inline photo payload, in bytes.
+    #[serde(default = "default_route_max_photo_bytes")]
+    pub route_max_photo_bytes: usize,
+    /// Comma-separated MIME types accepted for inline photos.
+    #[serde(default = "default_route_photo_types")]
+    pub route_photo_types: String,
+    /// Minimum time between retries of a route's failed photos.
+    #[serde(default = "default_photo_retry_cooldown_secs")]
+    pub photo_retry_cooldown_secs: u64,
+    /// Default per-user route count limit; unlimited when unset.
+    #[serde(default)]
+    pub route_quota_max_routes: Option<i64>,
+    /// Default per-user limit on inline photo payloads, in bytes; unlimited when unset.
+    #[serde(default)]
+    pub route_quota_max_photo_bytes: Option<i64>,
+    /// HTML-escape user-entered names and text before storing them.
+    #[serde(default)]
+    pub html_escape_user_text: bool,
     #[serde(default)]
     pub ollama_base_url: Option<String>,
     #[serde(default = "default_ollama_vision_model")]
     pub ollama_vision_model: String,
+    #[serde(default)]
+    pub photo_fetch_base_url: Option<String>,
+    #[serde(default = "default_export_font_path")]
+    pub export_font_path: String,
+    #[serde(default = "default_minio_endpoint")]
+    pub minio_endpoint: String,
+    #[serde(default)]
+    pub minio_public_endpoint: Option<String>,
+    #[serde(default = "default_minio_credential")]
+    pub minio_access_key: String,
+    #[serde(default = "default_minio_credential")]
+    pub minio_secret_key: String,
+    #[serde(default = "default_export_bucket")]
+    pub export_bucket: String,
+    /// Lifetime of data export download links, capped at S3's seven days.
+    /// Archives are deleted from the bucket shortly after their link expires.
+    #[serde(default = "default_export_link_ttl_secs")]
+    pub export_link_ttl_secs: u64,
+    #[serde(default = "default_chat_attachments_bucket")]
+    pub chat_attachments_bucket: String,
+    /// Lifetime of chat attachment upload links and of the download links
+    /// handed to the assistant.
+    #[serde(default = "default_chat_attachment_link_ttl_secs")]
+    pub chat_attachment_link_ttl_secs: u64,
+    /// Comma-separated user IDs notified about new route reports.
+    #[serde(default)]
+    pub moderator_user_ids: String,
+    #[serde(default)]
+    pub meilisearch_url: Option<String>,
+    #[serde(default)]
+    pub meilisearch_api_key: Option<String>,
+    #[serde(default = "default_meilisearch_index")]
+    pub meilisearch_index: String,
+    /// OSRM base URL for snapping route segments; straight lines are used when unset.
+    #[serde(default)]
+    pub osrm_url: Option<String>,
+    /// Open-Elevation base URL for elevation profiles; profiles are unavailable when unset.
+    #[serde(default)]
+    pub elevation_url: Option<String>,
+    /// Shared-cache lifetime (`s-maxage`) of explore pages; 0 sends `no-cache`.
+    #[serde(default = "default_cache_explore_secs")]
+    pub cache_explore_secs: u64,
+    /// Shared-cache lifetime of `/shared/{token}` routes and like counts.
+    #[serde(default = "default_cache_shared_secs")]
+    pub cache_shared_secs: u64,
+    /// How long shared route payloads are kept in memory; 0 disables the cache.
+    #[serde(default = "default_shared_route_cache_secs")]
+    pub shared_route_cache_secs: u64,
+    /// Requests one client address may make to the `/shared/{token}`
+    /// endpoints per window.
+    #[serde(default = "default_shared_rate_limit_max")]
+    pub shared_rate_limit_max: u32,
+    #[serde(default = "default_shared_rate_limit_window_secs")]
+    pub shared_rate_limit_window_secs: u64,
+    /// Comma-separated addresses and CIDR blocks of the reverse proxies whose
+    /// `X-Forwarded-For` is believed when limiting clients by address.
+    #[serde(default)]
+    pub trusted_proxies: String,
+    /// Client and shared-cache lifetime (`max-age`) of the category list.
+    #[serde(default = "default_cache_categories_secs")]
+    pub cache_categories_secs: u64,
+    /// Links allowed in a comment or route before it is flagged as spam.
+    #[serde(default = "default_spam_max_links")]
+    pub spam_max_links: usize,
+    /// Comment and route forms submitted sooner than this after opening are
+    /// flagged as spam.
+    #[serde(default = "default_spam_min_form_ms")]
+    pub spam_min_form_ms: u64,
+    /// How far back an identical comment flags a new one as a duplicate.
+    #[serde(default = "default_spam_duplicate_window_secs")]
+    pub spam_duplicate_window_secs: u64,
+    /// How often saved searches are checked against newly shared routes.
+    #[serde(default = "default_saved_search_alert_interval_secs")]
+    pub saved_search_alert_interval_secs: u64,
+    /// How often stored like/bookmark counters are checked against their tables.
+    #[serde(default = "default_counter_reconcile_interval_secs")]
+    pub counter_reconcile_interval_secs: u64,
+    /// Chat turns processed at once; requests beyond it get 503 right away.
+    #[serde(default = "default_chat_concurrency_limit")]
+    pub chat_concurrency_limit: usize,
+    /// Largest GeoJSON file accepted by the import endpoint.
+    #[serde(default = "default_import_max_bytes")]
+    pub import_max_bytes: usize,
+    /// Files accepted in one import request.
+    #[serde(default = "default_import_max_files")]
+    pub import_max_files: usize,
+    /// Comma-separated hosts routes can be imported from by link, subdomains
+    /// included. Empty allows any public host.
+    #[serde(default)]
+    pub import_url_allowed_hosts: String,
+    /// Also allow plain `http` import links.
+    #[serde(default)]
+    pub import_url_allow_http: bool,
+    /// Time allowed to download an import link.
+    #[serde(default = "default_import_url_timeout_secs")]
+    pub import_url_timeout_secs: u64,
+    /// Route imports processed at once; requests beyond it get 503 right away.
+    #[serde(default = "default_import_concurrency_limit")]
+    pub import_concurrency_limit: usize,
+    /// `Retry-After` sent with responses shed by a concurrency limit.
+    #[serde(default = "default_overload_retry_after_secs")]
+    pub overload_retry_after_secs: u64,
+}
+
+fn default_auto_migrate() -> bool {
+    true
+}
+
+fn default_database_acquire_timeout_secs() -> u64 {
+    30
+}
+
+fn default_database_idle_timeout_secs() -> u64 {
+    600
+}
+
+fn default_database_max_lifetime_secs() -> u64 {
+    1800
+}
+
+fn default_database_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_database_retry_base_delay_ms() -> u64 {
+    50
+}
+
+fn default_database_retry_max_delay_ms() -> u64 {
+    1000
 }
 
 fn default_nats_url() -> String {
@@ -52,6 +295,14 @@ fn default_openai_model() -> String {
     "gpt-4o-mini".to_string()
 }
 
+fn default_openai_timeout_secs() -> u64 {
+    60
+}
+
+fn default_openai_max_retries() -> u32 {
+    1
+}
+
 fn default_chat_rate_limit_max() -> u32 {
     10
 }
@@ -60,6 +311,10 @@ fn default_chat_rate_limit_window_secs() -> u64 {
     60
 }
 
+fn default_rate_limit_backend() -> String {
+    "memory".to_string()
+}
+
 fn default_chat_max_tool_iterations() -> usize {
     5
 }
@@ -68,6 +323,46 @@ fn default_nominatim_url() -> String {
     "https://nominatim.openstreetmap.org".to_string()
 }
 
+fn default_geocoder_provider() -> String {
+    "nominatim".to_string()
+}
+
+fn default_route_max_points() -> usize {
+    1000
+}
+
+fn default_route_max_photo_bytes() -> usize {
+    2 * 1024 * 1024
+}
+
+fn default_route_photo_types() -> String {
+    "image/jpeg,image/png,image/webp".to_string()
+}
+
+fn default_photo_retry_cooldown_secs() -> u64 {
+    60
+}
+
+fn default_circuit_breaker_failure_threshold() -> u32 {
+    5
+}
+
+fn default_circuit_breaker_open_secs() -> u64 {
+    30
+}
+
+fn default_app_name() -> String {
+    crate::usecase::chat_prompt::DEFAULT_APP_NAME.to_string()
+}
+
+fn default_chat_prompt_cache_secs() -> u64 {
+    60
+}
+
+fn default_content_filter_cache_secs() -> u64 {
+    60
+}
+
 fn default_chat_max_message_length() -> usize {
     2000
 }
@@ -76,6 +371,106 @@ fn default_ollama_vision_model() -> String {
     "llama3.2-vision".to_string()
 }
 
+fn default_export_font_path() -> String {
+    "/usr/share/fonts/dejavu/DejaVuSans.ttf".to_string()
+}
+
+fn default_minio_endpoint() -> String {
+    "http://minio:9000".to_string()
+}
+
+fn default_minio_credential() -> String {
+    "minioadmin".to_string()
+}
+
+fn default_export_bucket() -> String {
+    "exports".to_string()
+}
+
+fn default_chat_attachments_bucket() -> String {
+    "chat-attachments".to_string()
+}
+
+fn default_chat_attachment_link_ttl_secs() -> u64 {
+    600
+}
+
+fn default_export_link_ttl_secs() -> u64 {
+    86400
+}
+
+fn default_meilisearch_index() -> String {
+    "routes".to_string()
+}
+
+fn default_cache_explore_secs() -> u64 {
+    30
+}
+
+fn default_cache_shared_secs() -> u64 {
+    60
+}
+
+fn default_shared_route_cache_secs() -> u64 {
+    30
+}
+
+fn default_shared_rate_limit_max() -> u32 {
+    120
+}
+
+fn default_shared_rate_limit_window_secs() -> u64 {
+    60
+}
+
+fn default_cache_categories_secs() -> u64 {
+    3600
+}
+
+fn default_spam_max_links() -> usize {
+    2
+}
+
+fn default_spam_min_form_ms() -> u64 {
+    3000
+}
+
+fn default_spam_duplicate_window_secs() -> u64 {
+    3600
+}
+
+fn default_saved_search_alert_interval_secs() -> u64 {
+    600
+}
+
+fn default_counter_reconcile_interval_secs() -> u64 {
+    3600
+}
+
+fn default_chat_concurrency_limit() -> usize {
+    32
+}
+
+fn default_import_max_bytes() -> usize {
+    20 * 1024 * 1024
+}
+
+fn default_import_max_files() -> usize {
+    10
+}
+
+fn default_import_url_timeout_secs() -> u64 {
+    15
+}
+
+fn default_import_concurrency_limit() -> usize {
+    4
+}
+
+fn default_overload_retry_after_secs() -> u64 {
+    5
+}
+
 fn default_telemetry_service_name() -> String {
     "guide-helper-routes".to_string()
 }
@@ -100,4 +495,38 @@ impl AppConfig {
             .build()?
             .try_deserialize()
     }
+
+    pub fn pool_settings(&self) -> PoolSettings {
+        let non_zero = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+        PoolSettings {
+            max_connections: self.database_max_connections,
+            min_connections: self.database_min_connections,
+            acquire_timeout: Duration::from_secs(self.database_acquire_timeout_secs),
+            idle_timeout: non_zero(self.database_idle_timeout_secs),
+            max_lifetime: non_zero(self.database_max_lifetime_secs),
+        }
+    }
+
+    pub fn breaker_settings(&self) -> BreakerSettings {
+        BreakerSettings {
+            failure_threshold: self.circuit_breaker_failure_threshold.max(1),
+            open_duration: Duration::from_secs(self.circuit_breaker_open_secs),
+        }
+    }
+
+    pub fn spam_settings(&self) -> SpamSettings {
+        SpamSettings {
+            max_links: self.spam_max_links,
+            min_form_time: Duration::from_millis(self.spam_min_form_ms),
+            duplicate_window: Duration::from_secs(self.spam_duplicate_window_secs),
+        }
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.database_retry_max_attempts.max(1),
+            base_delay: Duration::from_millis(self.database_retry_base_delay_ms),
+            max_delay: Duration::from_millis(self.database_retry_max_delay_ms),
+        }
+    }
 }
@@ -0,0 +1,65 @@
+use axum::{
+    extract::State,
+    http::{header, HeaderValue, StatusCode},
+    response::Response,
+};
+
+/// `Cache-Control` for public data that may be a little stale: browsers
+/// revalidate every time (cheap with ETags), shared caches such as a CDN or
+/// nginx keep the response for `s_maxage_secs`. Zero disables caching.
+pub fn shared_cache_control(s_maxage_secs: u64) -> HeaderValue {
+    if s_maxage_secs == 0 {
+        return HeaderValue::from_static("no-cache");
+    }
+    HeaderValue::from_str(&format!("public, max-age=0, s-maxage={}", s_maxage_secs))
+        .expect("cache-control value is ascii")
+}
+
+/// `Cache-Control` for rarely changing data that clients may keep as well.
+/// Zero disables caching.
+pub fn public_cache_control(max_age_secs: u64) -> HeaderValue {
+    if max_age_secs == 0 {
+        return HeaderValue::from_static("no-cache");
+    }
+    HeaderValue::from_str(&format!("public, max-age={}", max_age_secs)).expect("cache-control value is ascii")
+}
+
+/// Response middleware (see `axum::middleware::map_response_with_state`)
+/// that sets `Cache-Control` on successful and 304 responses. Errors are left
+/// uncached so a proxy never keeps serving a transient 404 or 500.
+pub async fn set_cache_control(State(value): State<HeaderValue>, mut response: Response) -> Response {
+    let status = response.status();
+    if (status.is_success() || status == StatusCode::NOT_MODIFIED)
+        && !response.headers().contains_key(header::CACHE_CONTROL)
+    {
+        response.headers_mut().insert(header::CACHE_CONTROL, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+
+    #[test]
+    fn test_cache_control_values() {
+        assert_eq!(shared_cache_control(30), "public, max-age=0, s-maxage=30");
+        assert_eq!(public_cache_control(3600), "public, max-age=3600");
+        assert_eq!(shared_cache_control(0), "no-cache");
+        assert_eq!(public_cache_control(0), "no-cache");
+    }
+
+    #[tokio::test]
+    async fn test_only_successful_responses_are_cacheable() {
+        let value = shared_cache_control(30);
+
+        let ok = set_cache_control(State(value.clone()), StatusCode::OK.into_response()).await;
+        let not_modified = set_cache_control(State(value.clone()), StatusCode::NOT_MODIFIED.into_response()).await;
+        let not_found = set_cache_control(State(value), StatusCode::NOT_FOUND.into_response()).await;
+
+        assert_eq!(ok.headers()[header::CACHE_CONTROL], "public, max-age=0, s-maxage=30");
+        assert!(not_modified.headers().contains_key(header::CACHE_CONTROL));
+        assert!(!not_found.headers().contains_key(header::CACHE_CONTROL));
+    }
+}
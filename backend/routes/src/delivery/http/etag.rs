@@ -0,0 +1,98 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+/// Weak ETag over the serialized response body. Hashing the body rather than
+/// `updated_at` alone also catches changes that don't bump the timestamp,
+/// such as sharing a route or filling in geocoded start/end names.
+pub fn etag_for<T: Serialize>(body: &T) -> String {
+    let bytes = serde_json::to_vec(body).unwrap_or_default();
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("W/\"{:016x}\"", hasher.finish())
+}
+
+/// Whether `If-None-Match` already names `etag` (weak comparison, RFC 9110 §13.1.2).
+pub fn is_not_modified(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|tag| tag.trim() == "*" || opaque(tag) == etag)
+}
+
+/// Responds with `body` as JSON and its ETag, or with a bodiless 304 when the
+/// client's cached copy is still current.
+pub fn conditional_json<T: Serialize>(headers: &HeaderMap, body: T) -> Response {
+    let etag = etag_for(&body);
+    conditional(headers, &etag, (StatusCode::OK, Json(body)))
+}
+
+/// Like [`conditional_json`] for responses whose body carries per-request
+/// data (e.g. the `request_id` of list envelopes) that must not feed the tag.
+pub fn conditional(headers: &HeaderMap, etag: &str, response: impl IntoResponse) -> Response {
+    let mut response = if is_not_modified(headers, etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        response.into_response()
+    };
+
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers_with(if_none_match: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(if_none_match).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_etag_changes_with_body() {
+        let a = etag_for(&serde_json::json!({ "name": "Loop" }));
+        let b = etag_for(&serde_json::json!({ "name": "Loop 2" }));
+
+        assert_eq!(a, etag_for(&serde_json::json!({ "name": "Loop" })));
+        assert_ne!(a, b);
+        assert!(a.starts_with("W/\""));
+    }
+
+    #[test]
+    fn test_if_none_match_uses_weak_comparison_and_lists() {
+        let etag = "W/\"00000000000000ff\"";
+
+        assert!(is_not_modified(&headers_with("\"00000000000000ff\""), etag));
+        assert!(is_not_modified(&headers_with("W/\"1\", W/\"00000000000000ff\""), etag));
+        assert!(is_not_modified(&headers_with("*"), etag));
+        assert!(!is_not_modified(&headers_with("W/\"1\""), etag));
+        assert!(!is_not_modified(&HeaderMap::new(), etag));
+    }
+
+    #[test]
+    fn test_conditional_json_returns_304_for_matching_tag() {
+        let body = serde_json::json!({ "id": 1 });
+        let etag = etag_for(&body);
+
+        let fresh = conditional_json(&HeaderMap::new(), body.clone());
+        let cached = conditional_json(&headers_with(&etag), body);
+
+        assert_eq!(fresh.status(), StatusCode::OK);
+        assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(cached.headers()[header::ETAG], etag.as_str());
+    }
+}
@@ -0,0 +1,88 @@
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::{
+    error_handling::HandleErrorLayer,
+    extract::Request,
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+    routing::Route,
+    BoxError,
+};
+use tower::{layer::Layer, limit::GlobalConcurrencyLimitLayer, load_shed::error::Overloaded, Service, ServiceBuilder};
+
+use crate::usecase::error::UsecaseError;
+
+/// Caps the requests a group of endpoints handles at once. Everything the
+/// layer is applied to shares the same `max_concurrent` slots; a request
+/// arriving while they are all taken is answered with 503 and `Retry-After`
+/// at once instead of queueing behind the others.
+///
+/// A slot is released when the handler returns its response. For streamed
+/// responses that is before the body is sent.
+pub fn shed_load(
+    group: &'static str,
+    max_concurrent: usize,
+    retry_after: Duration,
+) -> impl Layer<
+    Route,
+    Service: Service<Request, Response = Response, Error = Infallible, Future: Send + 'static> + Clone + Send + Sync + 'static,
+> + Clone + Send + Sync + 'static {
+    ServiceBuilder::new()
+        .layer(HandleErrorLayer::new(move |err: BoxError| async move {
+            overloaded_response(group, retry_after, err)
+        }))
+        .load_shed()
+        .layer(GlobalConcurrencyLimitLayer::new(max_concurrent.max(1)))
+}
+
+fn overloaded_response(group: &'static str, retry_after: Duration, err: BoxError) -> Response {
+    if !err.is::<Overloaded>() {
+        return UsecaseError::Internal(err.to_string()).into_response();
+    }
+
+    tracing::warn!(group, "concurrency limit reached, shedding request");
+    metrics::counter!("http_requests_shed_total", "group" => group).increment(1);
+
+    let mut response = UsecaseError::Unavailable("Server is busy, please retry shortly".to_string()).into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after.as_secs().max(1)));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+    use tokio::sync::Notify;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_requests_beyond_the_limit_are_shed() {
+        let started = Arc::new(Notify::new());
+        let release = Arc::new(Notify::new());
+        let handler = {
+            let (started, release) = (started.clone(), release.clone());
+            move || async move {
+                started.notify_one();
+                release.notified().await;
+                "done"
+            }
+        };
+        let app = Router::new().route("/slow", get(handler).layer(shed_load("test", 1, Duration::from_secs(7))));
+        let request = || Request::get("/slow").body(Body::empty()).unwrap();
+
+        let first = tokio::spawn(app.clone().oneshot(request()));
+        started.notified().await;
+
+        let shed = app.oneshot(request()).await.unwrap();
+        assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(shed.headers()[header::RETRY_AFTER], "7");
+
+        release.notify_one();
+        assert_eq!(first.await.unwrap().unwrap().status(), StatusCode::OK);
+    }
+}
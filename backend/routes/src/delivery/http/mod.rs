@@ -1 +1,6 @@
+pub mod cache;
+pub mod etag;
+pub mod load_shed;
+pub mod throttle;
 pub mod v1;
+pub mod v2;
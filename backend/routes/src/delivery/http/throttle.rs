@@ -0,0 +1,271 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::usecase::error::UsecaseError;
+use crate::usecase::rate_limit::RateLimitStore;
+
+/// Per-client request budget for a group of unauthenticated endpoints.
+#[derive(Clone)]
+pub struct IpThrottle {
+    pub group: &'static str,
+    pub store: Arc<dyn RateLimitStore>,
+    pub max: u32,
+    pub window: Duration,
+    pub trusted_proxies: Arc<TrustedProxies>,
+}
+
+/// Reverse proxies in front of the service, such as the frontend nginx.
+/// `X-Forwarded-For` is only believed when the connection comes from one.
+#[derive(Clone, Debug, Default)]
+pub struct TrustedProxies {
+    networks: Vec<(IpAddr, u8)>,
+}
+
+impl TrustedProxies {
+    /// Parses a comma-separated list of addresses and CIDR blocks
+    /// (`10.0.0.0/8`). Invalid entries are skipped with a warning.
+    pub fn from_list(list: &str) -> Self {
+        let networks = list
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| match parse_network(s) {
+                Some(network) => Some(network),
+                None => {
+                    tracing::warn!(value = %s, "ignoring invalid trusted proxy");
+                    None
+                }
+            })
+            .collect();
+        Self { networks }
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.networks.iter().any(|&(network, prefix)| match (network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        })
+    }
+}
+
+fn parse_network(value: &str) -> Option<(IpAddr, u8)> {
+    let (address, prefix) = match value.split_once('/') {
+        Some((address, prefix)) => (address, Some(prefix.parse::<u8>().ok()?)),
+        None => (value, None),
+    };
+    let address = address.parse::<IpAddr>().ok()?.to_canonical();
+    let max = if address.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(max);
+    (prefix <= max).then_some((address, prefix))
+}
+
+/// Client address of the request: the peer of the connection, unless it is a
+/// trusted proxy. Then the right-most `X-Forwarded-For` hop that is not a
+/// trusted proxy, since each proxy appends the address it was reached from
+/// and anything left of that was sent by the client.
+pub fn client_ip(request: &Request, trusted: &TrustedProxies) -> Option<IpAddr> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip().to_canonical())?;
+    if !trusted.contains(peer) {
+        return Some(peer);
+    }
+
+    let forwarded = request
+        .headers()
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .collect::<Vec<_>>();
+    for hop in forwarded.into_iter().rev() {
+        let Ok(ip) = hop.parse::<IpAddr>() else {
+            // A malformed hop could be anything the client sent; stop at it
+            // rather than look past it.
+            break;
+        };
+        if !trusted.contains(ip) {
+            return Some(ip.to_canonical());
+        }
+    }
+    Some(peer)
+}
+
+/// Store key of a client's counter. NATS key-value keys only allow
+/// `[-/_=.a-zA-Z0-9]`, so the colons of IPv6 addresses become dashes.
+fn throttle_key(group: &str, ip: IpAddr) -> String {
+    format!("{}.{}", group, ip.to_string().replace(':', "-"))
+}
+
+/// Middleware (see `axum::middleware::from_fn_with_state`) answering 429 with
+/// `Retry-After` once a client exceeds the group's budget. Requests without a
+/// known address and failures of the store are let through.
+pub async fn throttle_by_ip(State(throttle): State<IpThrottle>, request: Request, next: Next) -> Response {
+    let Some(ip) = client_ip(&request, &throttle.trusted_proxies) else {
+        return next.run(request).await;
+    };
+
+    let key = throttle_key(throttle.group, ip);
+    match throttle.store.hit(&key, throttle.max, throttle.window).await {
+        Ok(true) => next.run(request).await,
+        Ok(false) => {
+            tracing::warn!(group = throttle.group, %ip, "client rate limit exceeded");
+            metrics::counter!("http_requests_throttled_total", "group" => throttle.group).increment(1);
+            let mut response = UsecaseError::RateLimited("Too many requests, please retry later".to_string()).into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(throttle.window.as_secs().max(1)));
+            response
+        }
+        Err(e) => {
+            tracing::warn!(group = throttle.group, error = %e, "rate limit store unavailable, allowing request");
+            next.run(request).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecase::rate_limit::{InMemoryRateLimitStore, NatsRateLimitStore};
+
+    use axum::{body::Body, http::StatusCode, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn request(peer: &str, forwarded: Option<&str>) -> Request {
+        let mut builder = Request::get("/shared");
+        if let Some(forwarded) = forwarded {
+            builder = builder.header("x-forwarded-for", forwarded);
+        }
+        let mut request = builder.body(Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 40000)));
+        request
+    }
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_each_client_gets_its_own_budget() {
+        let throttle = IpThrottle {
+            group: "shared",
+            store: Arc::new(InMemoryRateLimitStore::default()),
+            max: 2,
+            window: Duration::from_secs(30),
+            trusted_proxies: Arc::new(TrustedProxies::from_list("10.0.0.0/8")),
+        };
+        let app = Router::new()
+            .route("/shared", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(throttle, throttle_by_ip));
+
+        for _ in 0..2 {
+            let response = app.clone().oneshot(request("10.0.0.1", Some("203.0.113.7"))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        let limited = app.clone().oneshot(request("10.0.0.2", Some("198.51.100.9, 203.0.113.7"))).await.unwrap();
+        let other = app.clone().oneshot(request("10.0.0.1", Some("198.51.100.2"))).await.unwrap();
+        let anonymous = app.oneshot(Request::get("/shared").body(Body::empty()).unwrap()).await.unwrap();
+
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(limited.headers()[header::RETRY_AFTER], "30");
+        assert_eq!(other.status(), StatusCode::OK);
+        assert_eq!(anonymous.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_throttle_keys_are_valid_nats_keys() {
+        let is_nats_key = |key: &str| {
+            !key.starts_with('.')
+                && !key.ends_with('.')
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || "-/_=.".contains(c))
+        };
+
+        for address in ["203.0.113.7", "2001:db8::1", "::1", "fe80::1:2:3:4"] {
+            let key = throttle_key("shared", address.parse().unwrap());
+            assert!(is_nats_key(&key), "{key}");
+        }
+        assert_eq!(throttle_key("shared", "2001:db8::1".parse().unwrap()), "shared.2001-db8--1");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs NATS with JetStream, set NATS_URL"]
+    async fn test_ipv6_clients_are_throttled_by_the_nats_store() {
+        let url = std::env::var("NATS_URL").expect("NATS_URL");
+        let client = async_nats::connect(url).await.unwrap();
+        let throttle = IpThrottle {
+            group: "shared",
+            store: Arc::new(NatsRateLimitStore::new(client, Duration::from_secs(60)).await.unwrap()),
+            max: 1,
+            window: Duration::from_secs(30),
+            trusted_proxies: Arc::new(TrustedProxies::default()),
+        };
+        let app = Router::new()
+            .route("/shared", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(throttle, throttle_by_ip));
+        // A fresh address per run, so a previous run's window does not count
+        let peer = format!("2001:db8::{:x}", rand::random::<u16>());
+
+        let first = app.clone().oneshot(request(&peer, None)).await.unwrap();
+        let second = app.oneshot(request(&peer, None)).await.unwrap();
+
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[test]
+    fn test_client_ip_ignores_forwarded_headers_from_untrusted_peers() {
+        let trusted = TrustedProxies::from_list("10.0.0.0/8");
+
+        let direct = request("198.51.100.2", Some("203.0.113.7"));
+        assert_eq!(client_ip(&direct, &trusted), ip("198.51.100.2"));
+        assert_eq!(client_ip(&direct, &TrustedProxies::default()), ip("198.51.100.2"));
+    }
+
+    #[test]
+    fn test_client_ip_takes_right_most_untrusted_hop() {
+        let trusted = TrustedProxies::from_list("10.0.0.0/8, 192.0.2.10");
+
+        let spoofed = request("10.0.0.1", Some("1.2.3.4, 203.0.113.7, 192.0.2.10"));
+        assert_eq!(client_ip(&spoofed, &trusted), ip("203.0.113.7"));
+
+        let only_proxies = request("10.0.0.1", Some("10.0.0.5"));
+        assert_eq!(client_ip(&only_proxies, &trusted), ip("10.0.0.1"));
+
+        let garbage = request("10.0.0.1", Some("203.0.113.7, not-an-ip"));
+        assert_eq!(client_ip(&garbage, &trusted), ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_trusted_proxies_match_networks() {
+        let trusted = TrustedProxies::from_list("10.0.0.0/8, 192.0.2.10, fd00::/8, bogus, 10.0.0.0/33");
+
+        assert!(trusted.contains("10.200.1.1".parse().unwrap()));
+        assert!(trusted.contains("::ffff:10.0.0.1".parse().unwrap()));
+        assert!(trusted.contains("192.0.2.10".parse().unwrap()));
+        assert!(!trusted.contains("192.0.2.11".parse().unwrap()));
+        assert!(trusted.contains("fd12::1".parse().unwrap()));
+        assert!(!trusted.contains("2001:db8::1".parse().unwrap()));
+        assert!(TrustedProxies::from_list("0.0.0.0/0").contains("203.0.113.7".parse().unwrap()));
+    }
+}
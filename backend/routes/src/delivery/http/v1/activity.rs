@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    Extension,
+};
+use chrono::{DateTime, Utc};
+use guide_helper_api::list::{ListResponse, RequestId};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::activity::RouteActivity;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct RouteActivityResponse {
+    pub id: Uuid,
+    pub kind: String,
+    pub actor_id: Option<Uuid>,
+    pub details: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RouteActivityParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+fn activity_to_response(a: RouteActivity) -> RouteActivityResponse {
+    RouteActivityResponse {
+        id: a.id,
+        kind: a.kind,
+        actor_id: a.actor_id,
+        details: a.details,
+        created_at: a.created_at,
+    }
+}
+
+/// Adds an entry to the route's activity log (best-effort).
+pub async fn record_activity(
+    state: &AppState,
+    route_id: Uuid,
+    actor_id: Option<Uuid>,
+    kind: &str,
+    details: Option<serde_json::Value>,
+) {
+    let result = state.activity_usecase.record(route_id, actor_id, kind, details).await;
+    if let Err(e) = result {
+        tracing::error!(%route_id, %kind, error = %e, "failed to record route activity");
+    }
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, route_id = %route_id))]
+pub async fn list_route_activity(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    Query(params): Query<RouteActivityParams>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let limit = params.limit.unwrap_or(50).min(200);
+    let offset = params.offset.unwrap_or(0);
+    tracing::debug!(limit, offset, "listing route activity");
+
+    let (activity, total) = state
+        .activity_usecase
+        .list_activity(route_id, user.user_id, limit, offset)
+        .await?;
+
+    let activity: Vec<RouteActivityResponse> = activity.into_iter().map(activity_to_response).collect();
+
+    tracing::debug!(count = activity.len(), total, "route activity listed");
+    Ok(ListResponse::page(activity, limit, offset, total, request_id))
+}
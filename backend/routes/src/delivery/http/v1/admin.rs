@@ -1,16 +1,19 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Extension, Json,
 };
 use chrono::{DateTime, Utc};
+use guide_helper_api::list::{ListResponse, RequestId};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::quota::RouteQuota;
+use crate::domain::stats::UsageStats;
 use crate::usecase::contracts::{CommentRepository, RouteRepository};
 use crate::usecase::error::UsecaseError;
 use crate::AppState;
@@ -21,12 +24,39 @@ pub struct RoutesStatsResponse {
     pub total_comments: i64,
 }
 
+#[derive(Serialize)]
+pub struct AdminStatsResponse {
+    pub total_routes: i64,
+    pub total_comments: i64,
+    #[serde(flatten)]
+    pub usage: UsageStats,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminStatsParams {
+    /// Length of the reporting window, today included.
+    pub days: Option<i64>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AdminListParams {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AdminStorageParams {
+    /// How many of the largest consumers to list.
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminCommentSearchParams {
+    pub q: String,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
 #[derive(Serialize)]
 pub struct AdminRouteResponse {
     pub id: Uuid,
@@ -38,12 +68,6 @@ pub struct AdminRouteResponse {
     pub category_ids: Vec<Uuid>,
 }
 
-#[derive(Serialize)]
-pub struct AdminRoutesListResponse {
-    pub routes: Vec<AdminRouteResponse>,
-    pub total: i64,
-}
-
 #[derive(Serialize)]
 pub struct AdminCommentResponse {
     pub id: Uuid,
@@ -51,30 +75,15 @@ pub struct AdminCommentResponse {
     pub user_id: Uuid,
     pub author_name: String,
     pub text: String,
+    pub approved: bool,
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Serialize)]
-pub struct AdminCommentsListResponse {
-    pub comments: Vec<AdminCommentResponse>,
-    pub total: i64,
-}
-
-pub(crate) fn require_admin(user: &AuthenticatedUser) -> Result<(), UsecaseError> {
-    if user.role != "admin" {
-        tracing::warn!(user_id = %user.user_id, role = %user.role, "non-admin access attempt to admin endpoint");
-        return Err(UsecaseError::Forbidden("Admin access required".to_string()));
-    }
-    Ok(())
-}
-
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
 pub async fn get_routes_stats(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
 ) -> Result<impl IntoResponse, UsecaseError> {
-    require_admin(&user)?;
-
     tracing::debug!("getting routes admin stats");
 
     let total_routes = state.routes_usecase.route_repository().count_all().await?;
@@ -84,14 +93,47 @@ pub async fn get_routes_stats(
     Ok((StatusCode::OK, Json(RoutesStatsResponse { total_routes, total_comments })))
 }
 
+/// Route totals plus active users, new routes, chat usage and photo
+/// processing volume over the last `days` days (30 by default).
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_admin_stats(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<AdminStatsParams>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let days = params.days.unwrap_or(30);
+    tracing::debug!(days, "getting admin stats");
+
+    let total_routes = state.routes_usecase.route_repository().count_all().await?;
+    let total_comments = state.comments_usecase.comment_repository().count_all().await?;
+    let usage = state.stats_usecase.get_usage_stats(days).await?;
+
+    Ok((StatusCode::OK, Json(AdminStatsResponse { total_routes, total_comments, usage })))
+}
+
+/// Assistant messages per day, tool usage, turn latency, error rate and the
+/// heaviest users over the last `days` days (30 by default).
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_admin_chat_stats(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<AdminStatsParams>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let days = params.days.unwrap_or(30);
+    tracing::debug!(days, "getting admin chat stats");
+
+    let stats = state.stats_usecase.get_chat_stats(days).await?;
+
+    Ok((StatusCode::OK, Json(stats)))
+}
+
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
 pub async fn list_admin_routes(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
     Query(params): Query<AdminListParams>,
+    request_id: RequestId,
 ) -> Result<impl IntoResponse, UsecaseError> {
-    require_admin(&user)?;
-
     let limit = params.limit.unwrap_or(20).min(100);
     let offset = params.offset.unwrap_or(0);
     tracing::debug!(limit, offset, "listing admin routes");
@@ -113,7 +155,7 @@ pub async fn list_admin_routes(
         .collect();
 
     tracing::debug!(count = routes.len(), total, "admin routes listed");
-    Ok((StatusCode::OK, Json(AdminRoutesListResponse { routes, total })))
+    Ok(ListResponse::page(routes, limit, offset, total, request_id))
 }
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
@@ -121,9 +163,8 @@ pub async fn list_admin_comments(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
     Query(params): Query<AdminListParams>,
+    request_id: RequestId,
 ) -> Result<impl IntoResponse, UsecaseError> {
-    require_admin(&user)?;
-
     let limit = params.limit.unwrap_or(20).min(100);
     let offset = params.offset.unwrap_or(0);
     tracing::debug!(limit, offset, "listing admin comments");
@@ -139,10 +180,94 @@ pub async fn list_admin_comments(
             user_id: c.user_id,
             author_name: c.author_name,
             text: c.text,
+            approved: c.approved,
             created_at: c.created_at,
         })
         .collect();
 
     tracing::debug!(count = comments.len(), total, "admin comments listed");
-    Ok((StatusCode::OK, Json(AdminCommentsListResponse { comments, total })))
+    Ok(ListResponse::page(comments, limit, offset, total, request_id))
+}
+
+#[tracing::instrument(skip(state, params), fields(user_id = %user.user_id))]
+pub async fn search_admin_comments(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<AdminCommentSearchParams>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let limit = params.limit.unwrap_or(20).min(100);
+    let offset = params.offset.unwrap_or(0);
+    tracing::debug!(limit, offset, "searching admin comments");
+
+    let (rows, total) = state
+        .comments_usecase
+        .search_comments(None, &params.q, limit, offset)
+        .await?;
+
+    let comments: Vec<AdminCommentResponse> = rows
+        .into_iter()
+        .map(|c| AdminCommentResponse {
+            id: c.id,
+            route_id: c.route_id,
+            user_id: c.user_id,
+            author_name: c.author_name,
+            text: c.text,
+            approved: c.approved,
+            created_at: c.created_at,
+        })
+        .collect();
+
+    tracing::debug!(count = comments.len(), total, "admin comments search completed");
+    Ok(ListResponse::page(comments, limit, offset, total, request_id))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %target_user_id))]
+pub async fn get_route_quota(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(target_user_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let status = state.routes_usecase.get_quota_status(target_user_id).await?;
+
+    tracing::debug!(?status, "route quota retrieved");
+    Ok((StatusCode::OK, Json(status)))
+}
+
+/// Object storage totals and the users holding the most (20 by default).
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_storage_overview(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<AdminStorageParams>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let overview = state.routes_usecase.storage_overview(limit).await?;
+
+    Ok((StatusCode::OK, Json(overview)))
+}
+
+/// Replaces the user's quota with the given limits; omitted limits are unlimited.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %target_user_id))]
+pub async fn set_route_quota(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(target_user_id): Path<Uuid>,
+    Json(quota): Json<RouteQuota>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    state.routes_usecase.set_quota_override(target_user_id, Some(quota)).await?;
+    let status = state.routes_usecase.get_quota_status(target_user_id).await?;
+
+    Ok((StatusCode::OK, Json(status)))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %target_user_id))]
+pub async fn delete_route_quota(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(target_user_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    state.routes_usecase.set_quota_override(target_user_id, None).await?;
+
+    Ok(StatusCode::NO_CONTENT)
 }
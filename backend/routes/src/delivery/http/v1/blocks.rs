@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use guide_helper_api::list::{ListResponse, RequestId};
+use uuid::Uuid;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::block::UserBlock;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+pub use guide_helper_api::blocks::BlockedUserResponse;
+
+fn block_to_response(b: UserBlock) -> BlockedUserResponse {
+    BlockedUserResponse {
+        user_id: b.blocked_id,
+        created_at: b.created_at,
+    }
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, blocked_id = %blocked_id))]
+pub async fn block_user(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(blocked_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling block user request");
+
+    let block = state.blocks_usecase.block_user(user.user_id, blocked_id).await?;
+
+    tracing::debug!(blocked_id = %blocked_id, "user blocked successfully");
+    Ok((StatusCode::OK, Json(block_to_response(block))))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, blocked_id = %blocked_id))]
+pub async fn unblock_user(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(blocked_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling unblock user request");
+
+    state.blocks_usecase.unblock_user(user.user_id, blocked_id).await?;
+
+    tracing::debug!(blocked_id = %blocked_id, "user unblocked successfully");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn list_blocked_users(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling list blocked users request");
+
+    let blocks = state.blocks_usecase.list_blocked(user.user_id).await?;
+    let response: Vec<BlockedUserResponse> = blocks.into_iter().map(block_to_response).collect();
+
+    tracing::debug!(count = response.len(), "blocked users listed successfully");
+    Ok(ListResponse::all(response, request_id))
+}
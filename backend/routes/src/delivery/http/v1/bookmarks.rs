@@ -6,23 +6,16 @@ use axum::{
     response::IntoResponse,
     Extension, Json,
 };
-use serde::Serialize;
+use guide_helper_api::list::{ListResponse, RequestId};
 use uuid::Uuid;
 
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
 use crate::delivery::http::v1::routes::ExploreRouteResponse;
 use crate::usecase::error::UsecaseError;
+use crate::usecase::routes::route_stats;
 use crate::AppState;
 
-#[derive(Serialize)]
-pub struct ToggleBookmarkResponse {
-    pub bookmarked: bool,
-}
-
-#[derive(Serialize)]
-pub struct UserBookmarkStatusResponse {
-    pub bookmarked: bool,
-}
+pub use guide_helper_api::bookmarks::{ToggleBookmarkResponse, UserBookmarkStatusResponse};
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id, route_id = %route_id))]
 pub async fn toggle_bookmark(
@@ -62,6 +55,7 @@ pub async fn get_user_bookmark_status(
 pub async fn list_bookmarks(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
+    request_id: RequestId,
 ) -> Result<impl IntoResponse, UsecaseError> {
     tracing::debug!("handling list bookmarks request");
 
@@ -70,22 +64,30 @@ pub async fn list_bookmarks(
         .list_bookmarks(user.user_id)
         .await?;
 
+    // Routes made private since they were bookmarked have no link to open
     let response: Vec<ExploreRouteResponse> = rows
         .into_iter()
-        .map(|r| ExploreRouteResponse {
+        .filter_map(|r| Some(ExploreRouteResponse {
+            link_token: state.share_links_usecase.route_token(r.share_token?),
             id: r.id,
             name: r.name,
             points_count: r.points_count,
             created_at: r.created_at,
-            share_token: r.share_token.to_string(),
             likes_count: r.likes_count,
             avg_rating: r.avg_rating,
             ratings_count: r.ratings_count,
+            comments_count: r.comments_count,
+            bookmarks_count: r.bookmarks_count,
             category_ids: r.category_ids,
             seasons: r.seasons,
-        })
+            start_location: r.start_location,
+            end_location: r.end_location,
+            cover_url: r.cover_url,
+            cover_thumbnail_url: r.cover_thumbnail_url,
+            stats: route_stats(&r.path),
+        }))
         .collect();
 
     tracing::debug!(user_id = %user.user_id, count = response.len(), "bookmarks listed");
-    Ok((StatusCode::OK, Json(response)))
+    Ok(ListResponse::all(response, request_id))
 }
@@ -6,54 +6,40 @@ use axum::{
     response::IntoResponse,
     Extension, Json,
 };
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use guide_helper_api::list::{ListResponse, RequestId};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::delivery::http::v1::admin::require_admin;
+use crate::delivery::http::v1::locale::RequestLocale;
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::translation::category_key;
 use crate::usecase::error::UsecaseError;
 use crate::AppState;
 
-#[derive(Serialize)]
-pub struct CategoryResponse {
-    pub id: Uuid,
-    pub name: String,
-    pub created_at: DateTime<Utc>,
-}
-
-#[derive(Deserialize, Validate)]
-pub struct CreateCategoryRequest {
-    #[validate(length(min = 1, max = 100))]
-    pub name: String,
-}
-
-#[derive(Deserialize, Validate)]
-pub struct UpdateCategoryRequest {
-    #[validate(length(min = 1, max = 100))]
-    pub name: String,
-}
+pub use guide_helper_api::categories::{CategoryResponse, CreateCategoryRequest, UpdateCategoryRequest};
 
 #[tracing::instrument(skip(state))]
 pub async fn list_categories(
     State(state): State<Arc<AppState>>,
+    RequestLocale(locale): RequestLocale,
+    request_id: RequestId,
 ) -> Result<impl IntoResponse, UsecaseError> {
     tracing::debug!("handling list categories request");
 
     let categories = state.categories_usecase.list_categories().await?;
+    let catalog = state.translations_usecase.catalog(locale).await?;
 
     let response: Vec<CategoryResponse> = categories
         .into_iter()
         .map(|c| CategoryResponse {
             id: c.id,
-            name: c.name,
+            name: catalog.get(&category_key(c.id)).map(str::to_string).unwrap_or(c.name),
             created_at: c.created_at,
         })
         .collect();
 
     tracing::debug!(count = response.len(), "categories listed successfully");
-    Ok((StatusCode::OK, Json(response)))
+    Ok(ListResponse::all(response, request_id))
 }
 
 #[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id))]
@@ -62,7 +48,6 @@ pub async fn create_category(
     Extension(user): Extension<AuthenticatedUser>,
     Json(payload): Json<CreateCategoryRequest>,
 ) -> Result<impl IntoResponse, UsecaseError> {
-    require_admin(&user)?;
     tracing::debug!("handling create category request");
 
     if let Err(validation_errors) = payload.validate() {
@@ -90,7 +75,6 @@ pub async fn update_category(
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateCategoryRequest>,
 ) -> Result<impl IntoResponse, UsecaseError> {
-    require_admin(&user)?;
     tracing::debug!("handling update category request");
 
     if let Err(validation_errors) = payload.validate() {
@@ -110,7 +94,6 @@ pub async fn delete_category(
     Extension(user): Extension<AuthenticatedUser>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, UsecaseError> {
-    require_admin(&user)?;
     tracing::debug!("handling delete category request");
 
     state.categories_usecase.delete_category(id).await?;
@@ -4,16 +4,20 @@ use std::convert::Infallible;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::{IntoResponse, sse::{Event, Sse}},
+    response::{IntoResponse, sse::{Event, KeepAlive, Sse}},
     Extension, Json,
 };
 use chrono::{DateTime, Utc};
 use futures::StreamExt;
+use guide_helper_api::list::{ListResponse, RequestId};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::chat_message::ChatLanguage;
+use crate::domain::spam::{SPAM_CONTENT_CHAT_CONVERSATION, SPAM_REASON_BLOCKED_TERM};
 use crate::usecase::chat::{ChatAction, ChatStreamEvent};
+use crate::usecase::chat_i18n::CannedText;
 use crate::usecase::error::UsecaseError;
 use crate::AppState;
 
@@ -21,12 +25,61 @@ use crate::AppState;
 pub struct SendMessageRequest {
     pub message: String,
     pub conversation_id: Option<Uuid>,
+    /// Route the user has open; the assistant gets its points as context.
+    #[serde(default)]
+    pub route_id: Option<Uuid>,
+    /// Keys from `POST /api/v1/chat/attachments` of images already uploaded.
+    #[serde(default)]
+    pub attachments: Vec<String>,
+}
+
+/// `language: null` goes back to answering in the user's own language.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChatPreferences {
+    pub language: Option<ChatLanguage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAttachmentRequest {
+    pub content_type: String,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ListConversationsQuery {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateConversationRequest {
+    pub archived: Option<bool>,
+    pub pinned: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FlagConversationRequest {
+    pub reason: Option<String>,
+    /// Lets admins read the conversation while reviewing the flag.
+    #[serde(default)]
+    pub allow_review: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FlaggedConversationListParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct FlaggedConversationResponse {
+    pub user_id: Uuid,
+    pub conversation_id: Uuid,
+    pub reason: Option<String>,
+    pub review_consent: bool,
+    pub message_count: i64,
+    pub flagged_at: DateTime<Utc>,
 }
 
 #[derive(Serialize)]
@@ -37,12 +90,8 @@ pub struct ConversationSummaryResponse {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub title: String,
-}
-
-#[derive(Serialize)]
-pub struct ListConversationsResponse {
-    pub conversations: Vec<ConversationSummaryResponse>,
-    pub total: i64,
+    pub archived: bool,
+    pub pinned: bool,
 }
 
 #[derive(Serialize)]
@@ -51,6 +100,21 @@ pub struct ChatMessageResponse {
     pub message: String,
     pub actions: Vec<ChatAction>,
     pub conversation_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notice: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ShareConversationResponse {
+    pub share_token: String,
+}
+
+/// A message as seen through a share link: no ids or actions.
+#[derive(Serialize)]
+pub struct SharedChatMessage {
+    pub role: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Serialize)]
@@ -62,24 +126,28 @@ pub struct ChatHistoryMessage {
     pub created_at: DateTime<Utc>,
 }
 
-async fn enforce_rate_limit(state: &Arc<AppState>, user_id: Uuid) -> Result<(), UsecaseError> {
-    let now = std::time::Instant::now();
-    let mut limits = state.chat_rate_limits.write().await;
-    let entry = limits.entry(user_id).or_insert((now, 0));
-
-    if now.duration_since(entry.0).as_secs() >= state.chat_rate_limit_window_secs {
-        *entry = (now, 1);
-    } else {
-        entry.1 += 1;
-        if entry.1 > state.chat_rate_limit_max {
+async fn enforce_rate_limit(
+    state: &Arc<AppState>,
+    user_id: Uuid,
+    language: Option<ChatLanguage>,
+) -> Result<(), UsecaseError> {
+    let key = format!("chat.{}", user_id);
+    let window = std::time::Duration::from_secs(state.chat_rate_limit_window_secs);
+    match state.rate_limits.hit(&key, state.chat_rate_limit_max, window).await {
+        Ok(true) => Ok(()),
+        Ok(false) => {
             tracing::warn!(%user_id, "chat rate limit exceeded");
             metrics::counter!("chat_rate_limited_total").increment(1);
-            return Err(UsecaseError::RateLimited(
-                "Too many requests. Please try again later.".to_string(),
-            ));
+            Err(UsecaseError::RateLimited(
+                CannedText::RateLimited.localized(language).to_string(),
+            ))
+        }
+        Err(e) => {
+            // An unreachable store should not take the assistant down with it
+            tracing::warn!(%user_id, error = %e, "rate limit store unavailable, allowing chat request");
+            Ok(())
         }
     }
-    Ok(())
 }
 
 fn validate_message(message: &str, max_len: usize) -> Result<(), UsecaseError> {
@@ -92,12 +160,33 @@ fn validate_message(message: &str, max_len: usize) -> Result<(), UsecaseError> {
     Ok(())
 }
 
-fn check_availability(state: &AppState) -> Result<(), UsecaseError> {
+/// Runs the message through the blocked-term filter; a flagged message is
+/// sent as is and its conversation queued for moderation (best-effort).
+async fn filter_message(
+    state: &AppState,
+    user_id: Uuid,
+    conversation_id: Uuid,
+    message: &str,
+) -> Result<String, UsecaseError> {
+    let filtered = state.content_filter.check(message).await?;
+    if filtered.flagged {
+        let result = state
+            .spam_usecase
+            .flag(SPAM_CONTENT_CHAT_CONVERSATION, conversation_id, user_id, &[SPAM_REASON_BLOCKED_TERM])
+            .await;
+        if let Err(e) = result {
+            tracing::error!(%conversation_id, error = %e, "failed to flag chat message with blocked terms");
+        }
+    }
+    Ok(filtered.text)
+}
+
+fn check_availability(state: &AppState, language: Option<ChatLanguage>) -> Result<(), UsecaseError> {
     if !state.chat_usecase.is_available() {
         tracing::warn!("chat request received but Ollama is not available");
         metrics::counter!("chat_unavailable_total").increment(1);
         return Err(UsecaseError::Unavailable(
-            "AI assistant is currently unavailable".to_string(),
+            CannedText::AssistantUnavailable.localized(language).to_string(),
         ));
     }
     Ok(())
@@ -116,8 +205,14 @@ pub async fn send_chat_message(
     });
 
     validate_message(&body.message, state.chat_usecase.max_message_length())?;
-    enforce_rate_limit(&state, user.user_id).await?;
-    check_availability(&state)?;
+    let language = state.chat_usecase.preferred_language(user.user_id).await;
+    enforce_rate_limit(&state, user.user_id, language).await?;
+    check_availability(&state, language)?;
+    let message = filter_message(&state, user.user_id, conversation_id, &body.message).await?;
+    let image_urls = state
+        .chat_attachments_usecase
+        .resolve(user.user_id, &body.attachments)
+        .await?;
 
     tracing::info!(
         %conversation_id,
@@ -131,7 +226,7 @@ pub async fn send_chat_message(
 
     let result = state
         .chat_usecase
-        .send_message(user.user_id, conversation_id, body.message)
+        .send_message(user.user_id, conversation_id, message, body.route_id, image_urls, language)
         .await?;
 
     let elapsed = start.elapsed().as_secs_f64();
@@ -152,6 +247,7 @@ pub async fn send_chat_message(
             message: result.message,
             actions: result.actions,
             conversation_id: result.conversation_id,
+            notice: result.notice,
         }),
     ))
 }
@@ -161,6 +257,7 @@ pub async fn get_chat_history(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
     Path(conversation_id): Path<Uuid>,
+    request_id: RequestId,
 ) -> Result<impl IntoResponse, UsecaseError> {
     tracing::debug!("fetching chat history");
 
@@ -181,7 +278,7 @@ pub async fn get_chat_history(
         .collect();
 
     tracing::debug!(count = response.len(), "chat history fetched");
-    Ok((StatusCode::OK, Json(response)))
+    Ok(ListResponse::all(response, request_id))
 }
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
@@ -189,20 +286,21 @@ pub async fn list_conversations(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
     Query(query): Query<ListConversationsQuery>,
+    request_id: RequestId,
 ) -> Result<impl IntoResponse, UsecaseError> {
     let limit = query.limit.unwrap_or(20).min(100);
     let offset = query.offset.unwrap_or(0);
 
-    tracing::debug!(%limit, %offset, "listing conversations");
+    tracing::debug!(%limit, %offset, include_archived = query.include_archived, "listing conversations");
 
     let conversations = state
         .chat_usecase
-        .list_conversations(user.user_id, limit, offset)
+        .list_conversations(user.user_id, limit, offset, query.include_archived)
         .await?;
 
     let total = state
         .chat_usecase
-        .count_conversations(user.user_id)
+        .count_conversations(user.user_id, query.include_archived)
         .await?;
 
     let items: Vec<ConversationSummaryResponse> = conversations
@@ -214,14 +312,156 @@ pub async fn list_conversations(
             created_at: c.created_at,
             updated_at: c.updated_at,
             title: c.title,
+            archived: c.archived,
+            pinned: c.pinned,
         })
         .collect();
 
     tracing::debug!(count = items.len(), total, "conversations listed");
-    Ok((StatusCode::OK, Json(ListConversationsResponse {
-        conversations: items,
-        total,
-    })))
+    Ok(ListResponse::page(items, limit, offset, total, request_id))
+}
+
+#[tracing::instrument(skip(state, body), fields(user_id = %user.user_id, conversation_id = %conversation_id))]
+pub async fn update_conversation(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(body): Json<UpdateConversationRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::info!(archived = ?body.archived, pinned = ?body.pinned, "updating conversation");
+
+    let flags = state
+        .chat_usecase
+        .update_conversation(user.user_id, conversation_id, body.archived, body.pinned)
+        .await?;
+
+    Ok((StatusCode::OK, Json(flags)))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, conversation_id = %conversation_id))]
+pub async fn share_conversation(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let token = state
+        .chat_usecase
+        .share_conversation(user.user_id, conversation_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ShareConversationResponse {
+            share_token: token.to_string(),
+        }),
+    ))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, conversation_id = %conversation_id))]
+pub async fn unshare_conversation(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    state
+        .chat_usecase
+        .unshare_conversation(user.user_id, conversation_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(skip(state, body), fields(user_id = %user.user_id, conversation_id = %conversation_id))]
+pub async fn flag_conversation(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(conversation_id): Path<Uuid>,
+    Json(body): Json<FlagConversationRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::info!(allow_review = body.allow_review, "flagging conversation");
+
+    state
+        .chat_usecase
+        .flag_conversation(user.user_id, conversation_id, body.reason, body.allow_review)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn list_flagged_conversations(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<FlaggedConversationListParams>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let limit = params.limit.unwrap_or(20).min(100);
+    let offset = params.offset.unwrap_or(0);
+    tracing::debug!(limit, offset, "listing flagged conversations");
+
+    let (conversations, total) = state.chat_usecase.list_flagged_conversations(limit, offset).await?;
+
+    let items: Vec<FlaggedConversationResponse> = conversations
+        .into_iter()
+        .map(|c| FlaggedConversationResponse {
+            user_id: c.user_id,
+            conversation_id: c.conversation_id,
+            reason: c.reason,
+            review_consent: c.review_consent,
+            message_count: c.message_count,
+            flagged_at: c.flagged_at,
+        })
+        .collect();
+
+    Ok(ListResponse::page(items, limit, offset, total, request_id))
+}
+
+/// Messages of a flagged conversation whose owner allowed the review.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, owner_id = %owner_id, conversation_id = %conversation_id))]
+pub async fn review_flagged_conversation(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((owner_id, conversation_id)): Path<(Uuid, Uuid)>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let messages: Vec<ChatHistoryMessage> = state
+        .chat_usecase
+        .review_flagged_conversation(owner_id, conversation_id)
+        .await?
+        .into_iter()
+        .map(|m| ChatHistoryMessage {
+            id: m.id,
+            role: m.role,
+            content: m.content,
+            actions: m.actions,
+            created_at: m.created_at,
+        })
+        .collect();
+
+    Ok(ListResponse::all(messages, request_id))
+}
+
+#[tracing::instrument(skip(state, token))]
+pub async fn get_shared_conversation(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<Uuid>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("fetching shared conversation");
+
+    let messages: Vec<SharedChatMessage> = state
+        .chat_usecase
+        .get_shared_conversation(token)
+        .await?
+        .into_iter()
+        .map(|m| SharedChatMessage {
+            role: m.role,
+            content: m.content,
+            created_at: m.created_at,
+        })
+        .collect();
+
+    Ok(ListResponse::all(messages, request_id))
 }
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id, conversation_id = %conversation_id))]
@@ -249,17 +489,79 @@ pub async fn delete_message(
 ) -> Result<impl IntoResponse, UsecaseError> {
     tracing::info!("deleting message");
 
-    let _ = conversation_id; // included in path for REST convention
-
     state
         .chat_usecase
-        .delete_message(user.user_id, message_id)
+        .delete_message(user.user_id, conversation_id, message_id)
         .await?;
 
     tracing::info!("message deleted");
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Issues a presigned upload link for an image to attach to a chat message.
+#[tracing::instrument(skip(state, body), fields(user_id = %user.user_id))]
+pub async fn create_chat_attachment(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(body): Json<CreateAttachmentRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let upload = state
+        .chat_attachments_usecase
+        .create_upload(user.user_id, &body.content_type)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(upload)))
+}
+
+/// Runs an action the assistant proposed with `confirmation_required`.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, action_id = %action_id))]
+pub async fn confirm_chat_action(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(action_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::info!("confirming chat action");
+
+    let result = state
+        .chat_usecase
+        .confirm_action(user.user_id, action_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ChatMessageResponse {
+            id: result.id,
+            message: result.message,
+            actions: result.actions,
+            conversation_id: result.conversation_id,
+            notice: result.notice,
+        }),
+    ))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_chat_preferences(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let language = state.chat_usecase.preferred_language(user.user_id).await;
+    Ok((StatusCode::OK, Json(ChatPreferences { language })))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn update_chat_preferences(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(body): Json<ChatPreferences>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    state
+        .chat_usecase
+        .set_language(user.user_id, body.language)
+        .await?;
+
+    Ok((StatusCode::OK, Json(body)))
+}
+
 #[tracing::instrument(skip(state, body), fields(user_id = %user.user_id))]
 pub async fn send_chat_message_stream(
     State(state): State<Arc<AppState>>,
@@ -273,39 +575,77 @@ pub async fn send_chat_message_stream(
     });
 
     validate_message(&body.message, state.chat_usecase.max_message_length())?;
-    enforce_rate_limit(&state, user.user_id).await?;
-    check_availability(&state)?;
+    let language = state.chat_usecase.preferred_language(user.user_id).await;
+    enforce_rate_limit(&state, user.user_id, language).await?;
+    check_availability(&state, language)?;
+    let message = filter_message(&state, user.user_id, conversation_id, &body.message).await?;
+    let image_urls = state
+        .chat_attachments_usecase
+        .resolve(user.user_id, &body.attachments)
+        .await?;
 
     metrics::counter!("chat_messages_total", "role" => "user").increment(1);
 
-    let (_response, event_stream) = state
+    let turn = state
         .chat_usecase
-        .send_message_stream(user.user_id, conversation_id, body.message)
+        .prepare_turn(user.user_id, conversation_id, message, body.route_id, image_urls, language)
         .await?;
 
-    let sse_stream = event_stream.map(|result: Result<ChatStreamEvent, _>| {
-        match result {
-            Ok(event) => {
-                let data = serde_json::to_string(&event).unwrap_or_default();
-                let event_type = match &event {
-                    ChatStreamEvent::Token { .. } => "token",
-                    ChatStreamEvent::Actions { .. } => "actions",
-                    ChatStreamEvent::Done { .. } => "done",
-                    ChatStreamEvent::Error { .. } => "error",
-                };
-                Ok(Event::default().event(event_type).data(data))
-            }
-            Err(e) => {
-                tracing::error!(error = %e, "stream error");
-                let error_event = ChatStreamEvent::Error { message: e.to_string() };
-                let data = serde_json::to_string(&error_event).unwrap_or_default();
-                Ok(Event::default().event("error").data(data))
-            }
+    // The stream owns a handle to the state, so the turn keeps running after
+    // the handler returns. When the client disconnects axum drops the stream,
+    // which cancels the turn mid-flight.
+    let sse_stream = async_stream::stream! {
+        let mut guard = DisconnectGuard { conversation_id, finished: false };
+        let events = state.chat_usecase.stream_turn(turn);
+        futures::pin_mut!(events);
+        while let Some(result) = events.next().await {
+            yield Ok::<_, Infallible>(sse_event(result));
         }
-    });
+        guard.finished = true;
+    };
 
     tracing::info!(%conversation_id, "SSE stream started");
-    Ok(Sse::new(sse_stream))
+    // Pings keep proxies from closing the connection during long tool calls.
+    Ok(Sse::new(sse_stream).keep_alive(KeepAlive::default()))
+}
+
+/// Logs streams dropped before their last event.
+struct DisconnectGuard {
+    conversation_id: Uuid,
+    finished: bool,
+}
+
+impl Drop for DisconnectGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            tracing::info!(conversation_id = %self.conversation_id, "client disconnected, chat stream cancelled");
+            metrics::counter!("chat_stream_disconnects_total").increment(1);
+        }
+    }
+}
+
+fn sse_event(result: Result<ChatStreamEvent, UsecaseError>) -> Event {
+    match result {
+        Ok(event) => {
+            let data = serde_json::to_string(&event).unwrap_or_default();
+            let event_type = match &event {
+                ChatStreamEvent::Token { .. } => "token",
+                ChatStreamEvent::Actions { .. } => "actions",
+                ChatStreamEvent::Notice { .. } => "notice",
+                ChatStreamEvent::ToolStarted { .. } => "tool_started",
+                ChatStreamEvent::ToolFinished { .. } => "tool_finished",
+                ChatStreamEvent::Done { .. } => "done",
+                ChatStreamEvent::Error { .. } => "error",
+            };
+            Event::default().event(event_type).data(data)
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "stream error");
+            let error_event = ChatStreamEvent::Error { message: e.to_string() };
+            let data = serde_json::to_string(&error_event).unwrap_or_default();
+            Event::default().event("error").data(data)
+        }
+    }
 }
 
 #[derive(Serialize)]
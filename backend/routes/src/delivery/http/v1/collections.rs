@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use guide_helper_api::list::{ListResponse, RequestId};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::collection::RouteCollection;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct CollectionResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub route_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CollectionNameRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddCollectionRouteRequest {
+    pub route_id: Uuid,
+}
+
+fn collection_to_response(c: RouteCollection) -> CollectionResponse {
+    CollectionResponse {
+        id: c.id,
+        name: c.name,
+        route_count: c.route_count,
+        created_at: c.created_at,
+        updated_at: c.updated_at,
+    }
+}
+
+/// Sorted by name.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn list_collections(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let collections = state.collections_usecase.list_collections(user.user_id).await?;
+
+    let response: Vec<CollectionResponse> = collections.into_iter().map(collection_to_response).collect();
+
+    tracing::debug!(count = response.len(), "collections listed");
+    Ok(ListResponse::all(response, request_id))
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id))]
+pub async fn create_collection(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(payload): Json<CollectionNameRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling create collection request");
+
+    let collection = state.collections_usecase.create_collection(user.user_id, &payload.name).await?;
+
+    Ok((StatusCode::CREATED, Json(collection_to_response(collection))))
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, collection_id = %id))]
+pub async fn rename_collection(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CollectionNameRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling rename collection request");
+
+    let collection = state
+        .collections_usecase
+        .rename_collection(user.user_id, id, &payload.name)
+        .await?;
+
+    Ok(Json(collection_to_response(collection)))
+}
+
+/// Routes in the collection are kept.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, collection_id = %id))]
+pub async fn delete_collection(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    state.collections_usecase.delete_collection(user.user_id, id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Adding a route that is already in the collection succeeds as well.
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, collection_id = %id, route_id = %payload.route_id))]
+pub async fn add_collection_route(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AddCollectionRouteRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    state
+        .collections_usecase
+        .add_route(user.user_id, id, payload.route_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, collection_id = %id, route_id = %route_id))]
+pub async fn remove_collection_route(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((id, route_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    state
+        .collections_usecase
+        .remove_route(user.user_id, id, route_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,37 +1,39 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Extension, Json,
 };
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use guide_helper_api::list::{ListResponse, RequestId};
+use serde::Deserialize;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::delivery::http::v1::activity::record_activity;
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::activity::ACTIVITY_COMMENTED;
+use crate::domain::comment::Comment;
+use crate::domain::spam::{SPAM_CONTENT_COMMENT, SPAM_REASON_BLOCKED_TERM};
 use crate::usecase::contracts::RouteRepository;
 use crate::usecase::error::UsecaseError;
 use crate::AppState;
 
-#[derive(Serialize)]
-pub struct CommentResponse {
-    pub id: Uuid,
-    pub route_id: Uuid,
-    pub user_id: Uuid,
-    pub author_name: String,
-    pub text: String,
-    pub created_at: DateTime<Utc>,
-}
+pub use guide_helper_api::comments::{
+    CommentPolicyResponse, CommentResponse, CreateCommentRequest, SetCommentPolicyRequest,
+};
 
-#[derive(Deserialize, Validate)]
-pub struct CreateCommentRequest {
-    #[validate(length(min = 1, max = 2000))]
-    pub text: String,
-    #[validate(length(min = 1, max = 100))]
-    pub author_name: String,
+fn comment_to_response(c: Comment) -> CommentResponse {
+    CommentResponse {
+        id: c.id,
+        route_id: c.route_id,
+        user_id: c.user_id,
+        author_name: c.author_name,
+        text: c.text,
+        approved: c.approved,
+        created_at: c.created_at,
+    }
 }
 
 #[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, route_id = %route_id))]
@@ -48,24 +50,44 @@ pub async fn create_comment(
         return Err(UsecaseError::Validation(format!("{:?}", validation_errors)));
     }
 
+    let filtered = state.content_filter.check(&payload.text).await?;
     let comment = state
         .comments_usecase
-        .create_comment(route_id, user.user_id, payload.author_name, payload.text)
+        .create_comment(route_id, user.user_id, payload.author_name, filtered.text)
         .await?;
 
     tracing::debug!(comment_id = %comment.id, "comment created successfully");
 
+    // Queue for moderation if it looks like spam or has blocked terms (best-effort)
+    let result = state.spam_usecase.screen_comment(&comment, payload.form_elapsed_ms).await;
+    if let Err(e) = result {
+        tracing::error!(error = %e, "failed to screen comment for spam");
+    }
+    if filtered.flagged {
+        let result = state
+            .spam_usecase
+            .flag(SPAM_CONTENT_COMMENT, comment.id, user.user_id, &[SPAM_REASON_BLOCKED_TERM])
+            .await;
+        if let Err(e) = result {
+            tracing::error!(error = %e, "failed to flag comment with blocked terms");
+        }
+    }
+
+    let details = serde_json::json!({ "comment_id": comment.id, "author_name": comment.author_name });
+    record_activity(&state, route_id, Some(user.user_id), ACTIVITY_COMMENTED, Some(details)).await;
+
     // Emit notification to route owner (best-effort)
     if let Ok(Some(route)) = state.routes_usecase.route_repository().find_by_id(route_id).await {
         if route.user_id != user.user_id {
-            let msg = format!("{} commented on your route \"{}\"", &comment.author_name, &route.name);
-            if let Err(e) = state.notifications_usecase.create_notification(
+            let result = state.notifications_usecase.create_notification(
                 route.user_id,
                 "comment".to_string(),
                 route_id,
                 comment.author_name.clone(),
-                msg,
-            ).await {
+                "notification.comment",
+                serde_json::json!({ "actor": comment.author_name, "route": route.name }),
+            ).await;
+            if let Err(e) = result {
                 tracing::error!(error = %e, "failed to create comment notification");
             }
         }
@@ -73,14 +95,7 @@ pub async fn create_comment(
 
     Ok((
         StatusCode::CREATED,
-        Json(CommentResponse {
-            id: comment.id,
-            route_id: comment.route_id,
-            user_id: comment.user_id,
-            author_name: comment.author_name,
-            text: comment.text,
-            created_at: comment.created_at,
-        }),
+        Json(comment_to_response(comment)),
     ))
 }
 
@@ -88,6 +103,7 @@ pub async fn create_comment(
 pub async fn list_comments(
     State(state): State<Arc<AppState>>,
     Path(route_id): Path<Uuid>,
+    request_id: RequestId,
 ) -> Result<impl IntoResponse, UsecaseError> {
     tracing::debug!("handling list comments request");
 
@@ -95,18 +111,43 @@ pub async fn list_comments(
 
     let response: Vec<CommentResponse> = comments
         .into_iter()
-        .map(|c| CommentResponse {
-            id: c.id,
-            route_id: c.route_id,
-            user_id: c.user_id,
-            author_name: c.author_name,
-            text: c.text,
-            created_at: c.created_at,
-        })
+        .map(comment_to_response)
         .collect();
 
     tracing::debug!(route_id = %route_id, count = response.len(), "comments listed successfully");
-    Ok((StatusCode::OK, Json(response)))
+    Ok(ListResponse::all(response, request_id))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentSearchParams {
+    pub q: String,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[tracing::instrument(skip(state, params), fields(route_id = %route_id))]
+pub async fn search_comments(
+    State(state): State<Arc<AppState>>,
+    Path(route_id): Path<Uuid>,
+    Query(params): Query<CommentSearchParams>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling search comments request");
+
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = params.offset.unwrap_or(0).max(0);
+    let (comments, total) = state
+        .comments_usecase
+        .search_comments(Some(route_id), &params.q, limit, offset)
+        .await?;
+
+    let comments: Vec<CommentResponse> = comments
+        .into_iter()
+        .map(comment_to_response)
+        .collect();
+
+    tracing::debug!(route_id = %route_id, count = comments.len(), total, "comment search completed");
+    Ok(ListResponse::page(comments, limit, offset, total, request_id))
 }
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id, comment_id = %comment_id))]
@@ -119,7 +160,7 @@ pub async fn delete_comment(
 
     state
         .comments_usecase
-        .delete_comment(comment_id, user.user_id, &user.role)
+        .delete_comment(comment_id, user.user_id, user.effective_role())
         .await?;
 
     tracing::debug!(comment_id = %comment_id, "comment deleted successfully");
@@ -138,3 +179,77 @@ pub async fn count_comments(
     tracing::debug!(route_id = %route_id, count, "comment count retrieved");
     Ok((StatusCode::OK, Json(serde_json::json!({ "count": count }))))
 }
+
+/// Comments awaiting approval on the caller's route; the page total is the
+/// pending count.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, route_id = %route_id))]
+pub async fn list_pending_comments(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling list pending comments request");
+
+    let comments = state
+        .comments_usecase
+        .list_pending_comments(route_id, user.user_id)
+        .await?;
+
+    let response: Vec<CommentResponse> = comments.into_iter().map(comment_to_response).collect();
+
+    tracing::debug!(route_id = %route_id, count = response.len(), "pending comments listed successfully");
+    Ok(ListResponse::all(response, request_id))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, comment_id = %comment_id))]
+pub async fn approve_comment(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(comment_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling approve comment request");
+
+    let comment = state
+        .comments_usecase
+        .approve_comment(comment_id, user.user_id, user.effective_role())
+        .await?;
+
+    tracing::debug!(comment_id = %comment_id, "comment approved successfully");
+    Ok((StatusCode::OK, Json(comment_to_response(comment))))
+}
+
+#[tracing::instrument(skip(state), fields(route_id = %route_id))]
+pub async fn get_comment_policy(
+    State(state): State<Arc<AppState>>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling get comment policy request");
+
+    let policy = state.comments_usecase.get_comment_policy(route_id).await?;
+
+    Ok((StatusCode::OK, Json(CommentPolicyResponse { policy })))
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, route_id = %route_id))]
+pub async fn set_comment_policy(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    Json(payload): Json<SetCommentPolicyRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling set comment policy request");
+
+    if let Err(validation_errors) = payload.validate() {
+        tracing::warn!(user_id = %user.user_id, ?validation_errors, "validation failed");
+        return Err(UsecaseError::Validation(format!("{:?}", validation_errors)));
+    }
+
+    state
+        .comments_usecase
+        .set_comment_policy(route_id, user.user_id, &payload.policy)
+        .await?;
+
+    tracing::debug!(route_id = %route_id, policy = %payload.policy, "comment policy updated successfully");
+    Ok((StatusCode::OK, Json(CommentPolicyResponse { policy: payload.policy })))
+}
@@ -6,7 +6,6 @@ use axum::{
     response::IntoResponse,
     Extension, Json,
 };
-use serde::Serialize;
 use uuid::Uuid;
 
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
@@ -14,21 +13,7 @@ use crate::usecase::contracts::RouteRepository;
 use crate::usecase::error::UsecaseError;
 use crate::AppState;
 
-#[derive(Serialize)]
-pub struct LikeCountResponse {
-    pub count: i64,
-}
-
-#[derive(Serialize)]
-pub struct ToggleLikeResponse {
-    pub liked: bool,
-    pub count: i64,
-}
-
-#[derive(Serialize)]
-pub struct UserLikeStatusResponse {
-    pub liked: bool,
-}
+pub use guide_helper_api::likes::{LikeCountResponse, ToggleLikeResponse, UserLikeStatusResponse};
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id, route_id = %route_id))]
 pub async fn toggle_like(
@@ -49,14 +34,15 @@ pub async fn toggle_like(
     if liked {
         if let Ok(Some(route)) = state.routes_usecase.route_repository().find_by_id(route_id).await {
             if route.user_id != user.user_id {
-                let msg = format!("{} liked your route \"{}\"", &user.email, &route.name);
-                if let Err(e) = state.notifications_usecase.create_notification(
+                let result = state.notifications_usecase.create_notification(
                     route.user_id,
                     "like".to_string(),
                     route_id,
                     user.email.clone(),
-                    msg,
-                ).await {
+                    "notification.like",
+                    serde_json::json!({ "actor": user.email, "route": route.name }),
+                ).await;
+                if let Err(e) = result {
                     tracing::error!(error = %e, "failed to create like notification");
                 }
             }
@@ -0,0 +1,18 @@
+use std::convert::Infallible;
+
+use axum::{extract::FromRequestParts, http::header::ACCEPT_LANGUAGE, http::request::Parts};
+
+use crate::usecase::translations::negotiate_locale;
+
+/// Locale negotiated from the request's `Accept-Language` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLocale(pub &'static str);
+
+impl<S: Send + Sync> FromRequestParts<S> for RequestLocale {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header = parts.headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        Ok(Self(negotiate_locale(header)))
+    }
+}
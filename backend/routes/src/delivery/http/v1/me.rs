@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use serde::Serialize;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct DataExportResponse {
+    pub status: &'static str,
+}
+
+/// Starts a data takeout for the current user. The archive is built in the
+/// background; the user receives a notification with a download link.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn request_data_export(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("data export requested");
+
+    state.takeout_usecase.begin_export(user.user_id)?;
+
+    let user_id = user.user_id;
+    let state = state.clone();
+    tokio::spawn(async move {
+        state.takeout_usecase.run_export(user_id).await;
+    });
+
+    tracing::info!(user_id = %user_id, "data export queued");
+    Ok((StatusCode::ACCEPTED, Json(DataExportResponse { status: "processing" })))
+}
+
+/// Object storage held by the current user's processed photos.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_storage_usage(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let usage = state.routes_usecase.get_storage_usage(user.user_id).await?;
+
+    tracing::debug!(?usage, "storage usage retrieved");
+    Ok((StatusCode::OK, Json(usage)))
+}
@@ -1,12 +1,24 @@
+pub mod activity;
 pub mod admin;
+pub mod blocks;
 pub mod bookmarks;
 pub mod categories;
 pub mod chat;
+pub mod collections;
 pub mod comments;
 pub mod likes;
+pub mod locale;
+pub mod me;
 pub mod middleware;
 pub mod notifications;
 pub mod ratings;
+pub mod reports;
 pub mod routes;
+pub mod saved_searches;
+pub mod search;
 pub mod settings;
+pub mod share_links;
+pub mod spam;
+pub mod templates;
+pub mod translations;
 pub mod ws;
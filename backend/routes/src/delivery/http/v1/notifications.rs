@@ -6,48 +6,25 @@ use axum::{
     response::IntoResponse,
     Extension, Json,
 };
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use guide_helper_api::list::{ListResponse, RequestId};
 use uuid::Uuid;
 
+use crate::delivery::http::v1::locale::RequestLocale;
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
 use crate::usecase::error::UsecaseError;
 use crate::AppState;
 
-#[derive(Debug, Deserialize)]
-pub struct NotificationListParams {
-    pub limit: Option<i64>,
-    pub offset: Option<i64>,
-}
-
-#[derive(Serialize)]
-pub struct NotificationResponse {
-    pub id: Uuid,
-    pub user_id: Uuid,
-    pub notification_type: String,
-    pub route_id: Uuid,
-    pub actor_name: String,
-    pub message: String,
-    pub is_read: bool,
-    pub created_at: DateTime<Utc>,
-}
-
-#[derive(Serialize)]
-pub struct NotificationsListResponse {
-    pub notifications: Vec<NotificationResponse>,
-    pub unread_count: i64,
-}
-
-#[derive(Serialize)]
-pub struct UnreadCountResponse {
-    pub unread_count: i64,
-}
+pub use guide_helper_api::notifications::{
+    NotificationListParams, NotificationPreferencesBody, NotificationResponse, NotificationsListMeta, UnreadCountResponse,
+};
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
 pub async fn list_notifications(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
     Query(params): Query<NotificationListParams>,
+    RequestLocale(locale): RequestLocale,
+    request_id: RequestId,
 ) -> Result<impl IntoResponse, UsecaseError> {
     let limit = params.limit.unwrap_or(20).min(100);
     let offset = params.offset.unwrap_or(0);
@@ -58,27 +35,39 @@ pub async fn list_notifications(
         .list_notifications(user.user_id, limit, offset)
         .await?;
 
+    let total = state
+        .notifications_usecase
+        .count_notifications(user.user_id)
+        .await?;
+
     let unread_count = state
         .notifications_usecase
         .count_unread(user.user_id)
         .await?;
 
+    let catalog = state.translations_usecase.catalog(locale).await?;
+
     let response: Vec<NotificationResponse> = notifications
         .into_iter()
         .map(|n| NotificationResponse {
             id: n.id,
             user_id: n.user_id,
+            message: n
+                .template_key
+                .as_deref()
+                .zip(n.params.as_ref())
+                .and_then(|(key, params)| catalog.render(key, params))
+                .unwrap_or(n.message),
             notification_type: n.notification_type,
             route_id: n.route_id,
             actor_name: n.actor_name,
-            message: n.message,
             is_read: n.is_read,
             created_at: n.created_at,
         })
         .collect();
 
-    tracing::debug!(count = response.len(), unread_count, "notifications listed");
-    Ok((StatusCode::OK, Json(NotificationsListResponse { notifications: response, unread_count })))
+    tracing::debug!(count = response.len(), total, unread_count, "notifications listed");
+    Ok(ListResponse::page(response, limit, offset, total, request_id).with_meta(NotificationsListMeta { unread_count }))
 }
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
@@ -129,3 +118,31 @@ pub async fn mark_all_as_read(
     tracing::debug!("all notifications marked as read");
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_notification_preferences(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("getting notification preferences");
+
+    let preferences = state.route_updates_usecase.get_preferences(user.user_id).await?;
+
+    Ok((StatusCode::OK, Json(NotificationPreferencesBody { preferences })))
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id))]
+pub async fn update_notification_preferences(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(payload): Json<NotificationPreferencesBody>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!(?payload.preferences, "updating notification preferences");
+
+    let preferences = state
+        .route_updates_usecase
+        .set_preferences(user.user_id, payload.preferences)
+        .await?;
+
+    Ok((StatusCode::OK, Json(NotificationPreferencesBody { preferences })))
+}
@@ -6,7 +6,6 @@ use axum::{
     response::IntoResponse,
     Extension, Json,
 };
-use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
@@ -15,29 +14,7 @@ use crate::usecase::contracts::RouteRepository;
 use crate::usecase::error::UsecaseError;
 use crate::AppState;
 
-#[derive(Deserialize, Validate)]
-pub struct SetRatingRequest {
-    #[validate(range(min = 1, max = 5))]
-    pub rating: i16,
-}
-
-#[derive(Serialize)]
-pub struct RatingAggregateResponse {
-    pub average: f64,
-    pub count: i64,
-}
-
-#[derive(Serialize)]
-pub struct UserRatingResponse {
-    pub rating: Option<i16>,
-}
-
-#[derive(Serialize)]
-pub struct SetRatingResponse {
-    pub average: f64,
-    pub count: i64,
-    pub user_rating: i16,
-}
+pub use guide_helper_api::ratings::{RatingAggregateResponse, SetRatingRequest, SetRatingResponse, UserRatingResponse};
 
 #[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, route_id = %route_id))]
 pub async fn set_rating(
@@ -66,14 +43,15 @@ pub async fn set_rating(
     // Emit notification to route owner (best-effort)
     if let Ok(Some(route)) = state.routes_usecase.route_repository().find_by_id(route_id).await {
         if route.user_id != user.user_id {
-            let msg = format!("{} rated your route \"{}\" with {}/5", &user.email, &route.name, payload.rating);
-            if let Err(e) = state.notifications_usecase.create_notification(
+            let result = state.notifications_usecase.create_notification(
                 route.user_id,
                 "rating".to_string(),
                 route_id,
                 user.email.clone(),
-                msg,
-            ).await {
+                "notification.rating",
+                serde_json::json!({ "actor": user.email, "route": route.name, "rating": payload.rating }),
+            ).await;
+            if let Err(e) = result {
                 tracing::error!(error = %e, "failed to create rating notification");
             }
         }
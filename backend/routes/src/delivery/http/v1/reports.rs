@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use guide_helper_api::list::{ListResponse, RequestId};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::report::RouteReport;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct ReportResponse {
+    pub id: Uuid,
+    pub route_id: Uuid,
+    pub reporter_id: Uuid,
+    pub category: String,
+    pub details: Option<String>,
+    pub status: String,
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CreateReportRequest {
+    pub category: String,
+    #[validate(length(max = 2000))]
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportListParams {
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ResolveReportRequest {
+    pub status: String,
+    #[validate(length(max = 2000))]
+    pub resolution_note: Option<String>,
+}
+
+fn report_to_response(r: RouteReport) -> ReportResponse {
+    ReportResponse {
+        id: r.id,
+        route_id: r.route_id,
+        reporter_id: r.reporter_id,
+        category: r.category,
+        details: r.details,
+        status: r.status,
+        resolution_note: r.resolution_note,
+        resolved_by: r.resolved_by,
+        resolved_at: r.resolved_at,
+        created_at: r.created_at,
+    }
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, route_id = %route_id))]
+pub async fn report_route(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    Json(payload): Json<CreateReportRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling report route request");
+
+    if let Err(validation_errors) = payload.validate() {
+        tracing::warn!(user_id = %user.user_id, ?validation_errors, "validation failed");
+        return Err(UsecaseError::Validation(format!("{:?}", validation_errors)));
+    }
+
+    let (report, route) = state
+        .reports_usecase
+        .create_report(route_id, user.user_id, payload.category, payload.details)
+        .await?;
+
+    // Notify moderators (best-effort)
+    let params = serde_json::json!({ "actor": user.email, "route": route.name, "category": report.category });
+    for moderator_id in state.reports_usecase.moderator_ids() {
+        if let Err(e) = state.notifications_usecase.create_notification(
+            *moderator_id,
+            "route_report".to_string(),
+            route_id,
+            user.email.clone(),
+            "notification.route_report",
+            params.clone(),
+        ).await {
+            tracing::error!(error = %e, moderator_id = %moderator_id, "failed to create report notification");
+        }
+    }
+
+    Ok((StatusCode::CREATED, Json(report_to_response(report))))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn list_reports(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<ReportListParams>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let limit = params.limit.unwrap_or(20).min(100);
+    let offset = params.offset.unwrap_or(0);
+    tracing::debug!(limit, offset, status = ?params.status, "listing route reports");
+
+    let (reports, total) = state
+        .reports_usecase
+        .list_reports(params.status, limit, offset)
+        .await?;
+
+    let reports: Vec<ReportResponse> = reports.into_iter().map(report_to_response).collect();
+
+    tracing::debug!(count = reports.len(), total, "route reports listed");
+    Ok(ListResponse::page(reports, limit, offset, total, request_id))
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, report_id = %report_id))]
+pub async fn resolve_report(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(report_id): Path<Uuid>,
+    Json(payload): Json<ResolveReportRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    if let Err(validation_errors) = payload.validate() {
+        tracing::warn!(user_id = %user.user_id, ?validation_errors, "validation failed");
+        return Err(UsecaseError::Validation(format!("{:?}", validation_errors)));
+    }
+
+    let report = state
+        .reports_usecase
+        .resolve_report(report_id, user.user_id, payload.status, payload.resolution_note)
+        .await?;
+
+    // Let the reporter know their report was handled (best-effort)
+    if let Err(e) = state.notifications_usecase.create_notification(
+        report.reporter_id,
+        "report_resolved".to_string(),
+        report.route_id,
+        user.email.clone(),
+        "notification.report_resolved",
+        serde_json::json!({ "status": report.status }),
+    ).await {
+        tracing::error!(error = %e, "failed to create report resolution notification");
+    }
+
+    Ok((StatusCode::OK, Json(report_to_response(report))))
+}
@@ -2,65 +2,60 @@ use std::sync::Arc;
 
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Extension, Json,
 };
 use axum_extra::extract::Multipart;
 use chrono::{DateTime, Utc};
+use guide_helper_api::events;
+use guide_helper_api::list::{ListResponse, RequestId};
+use guide_helper_api::photo::PHOTO_PROCESS_SUBJECT;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::delivery::http::etag::{conditional, conditional_json, etag_for};
+use crate::delivery::http::v1::activity::record_activity;
+use crate::delivery::http::v1::locale::RequestLocale;
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
-use crate::domain::route::{Route as DomainRoute, RoutePoint};
+use crate::domain::activity::{
+    ACTIVITY_ARCHIVED, ACTIVITY_CREATED, ACTIVITY_DETAILS_EDITED, ACTIVITY_POINTS_EDITED, ACTIVITY_REVERSED,
+    ACTIVITY_SHARED, ACTIVITY_UNARCHIVED, ACTIVITY_UNSHARED, ACTIVITY_VISIBILITY_CHANGED,
+};
+use crate::domain::route::{Route as DomainRoute, RoutePoint, UserRoutesFilter};
+use crate::domain::route_elevation::ElevationSample;
+use crate::domain::route_geometry::{RouteGeometry, SegmentGeometry};
+use crate::domain::route_stats::RouteStats;
+use crate::domain::spam::{SPAM_CONTENT_ROUTE, SPAM_REASON_BLOCKED_TERM};
 use crate::usecase::error::UsecaseError;
-use crate::usecase::geojson_import::{parse_geojson, ImportError};
-use crate::usecase::photo_tasks::PhotoProcessTask;
+use crate::usecase::geojson_import::{parse_upload, ImportError, ImportFormat};
+use crate::usecase::photo_tasks::{photo_task_for_route, PhotoProcessTask};
+use crate::usecase::route_updates::{RouteUpdatedEvent, ROUTE_UPDATED_SUBJECT};
+use crate::usecase::routes::{route_stats, PointsPatch};
+use crate::usecase::search::{RouteChangedEvent, ROUTE_CHANGED_SUBJECT};
 use crate::AppState;
 
-#[derive(Serialize)]
-pub struct RouteResponse {
-    pub id: Uuid,
-    pub user_id: Uuid,
-    pub name: String,
-    pub points: Vec<RoutePoint>,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub share_token: Option<String>,
-    pub category_ids: Vec<Uuid>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub start_location: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub end_location: Option<String>,
-    pub seasons: Vec<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
-}
-
-#[derive(Deserialize, Validate)]
-pub struct CreateRouteRequest {
-    #[validate(length(min = 1, max = 200))]
-    pub name: String,
-    #[validate(length(min = 1))]
-    pub points: Vec<RoutePoint>,
-    #[serde(default)]
-    pub category_ids: Vec<Uuid>,
-    #[serde(default)]
-    pub seasons: Vec<String>,
-}
-
-#[derive(Deserialize, Validate)]
-pub struct UpdateRouteRequest {
-    #[validate(length(min = 1, max = 200))]
-    pub name: Option<String>,
-    pub points: Option<Vec<RoutePoint>>,
-    pub category_ids: Option<Vec<Uuid>>,
-    pub seasons: Option<Vec<String>>,
-}
-
-fn route_to_response(r: DomainRoute) -> RouteResponse {
+pub use guide_helper_api::routes::{
+    CoverResponse, CreateRouteRequest, DuplicateRouteResponse, ExploreFacets, ExploreMeta, ExploreQuery,
+    ExploreRouteResponse, ImportBatchResponse, ImportFileResult, ImportRouteParams, ImportUrlRequest, ListRoutesQuery,
+    MergeRoutesRequest, PatchPointsRequest, RouteResponse, SetCoverRequest, SetVisibilityRequest, ShareResponse,
+    SplitRouteRequest, SplitRouteResponse, UpdateRouteRequest,
+};
+
+/// Response for the route's owner, carrying a signed link for its share token.
+fn route_to_response(state: &AppState, r: DomainRoute) -> RouteResponse {
+    let share_token = r.share_token.map(|t| state.share_links_usecase.route_token(t));
+    RouteResponse {
+        share_token,
+        ..route_to_public_response(r)
+    }
+}
+
+/// Response for someone who opened the route by a link; they get no token of
+/// their own to pass on.
+fn route_to_public_response(r: DomainRoute) -> RouteResponse {
+    let stats = route_stats(&r.points);
     RouteResponse {
         id: r.id,
         user_id: r.user_id,
@@ -68,12 +63,27 @@ fn route_to_response(r: DomainRoute) -> RouteResponse {
         points: r.points,
         created_at: r.created_at,
         updated_at: r.updated_at,
-        share_token: r.share_token.map(|t| t.to_string()),
+        share_token: None,
+        visibility: r.visibility,
         category_ids: r.category_ids,
         start_location: r.start_location,
         end_location: r.end_location,
         seasons: r.seasons,
         description: r.description,
+        archived_at: r.archived_at,
+        stats,
+    }
+}
+
+/// Filter of the owner's route list from the query; blank searches are ignored.
+pub(crate) fn user_routes_filter(params: &ListRoutesQuery) -> UserRoutesFilter {
+    UserRoutesFilter {
+        include_archived: params.include_archived,
+        search: params.search.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string),
+        category_id: params.category_id,
+        created_after: params.created_after,
+        created_before: params.created_before,
+        collection_id: params.collection_id,
     }
 }
 
@@ -81,14 +91,25 @@ fn route_to_response(r: DomainRoute) -> RouteResponse {
 pub async fn list_routes(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<ListRoutesQuery>,
+    request_id: RequestId,
 ) -> Result<impl IntoResponse, UsecaseError> {
-    tracing::debug!("handling list routes request");
+    let sort = params.sort.as_deref().unwrap_or("newest");
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let filter = user_routes_filter(&params);
 
-    let routes = state.routes_usecase.get_user_routes(user.user_id).await?;
-    let response: Vec<RouteResponse> = routes.into_iter().map(route_to_response).collect();
+    tracing::debug!(?filter, %sort, %limit, %offset, "handling list routes request");
+
+    let (routes, total) = state
+        .routes_usecase
+        .get_user_routes(user.user_id, filter, sort, limit, offset)
+        .await?;
+    let response: Vec<RouteResponse> = routes.into_iter().map(|r| route_to_response(&state, r)).collect();
 
-    tracing::debug!(user_id = %user.user_id, count = response.len(), "routes listed successfully");
-    Ok((StatusCode::OK, Json(response)))
+    tracing::debug!(user_id = %user.user_id, count = response.len(), total, "routes listed successfully");
+    Ok(ListResponse::page(response, limit, offset, total, request_id))
 }
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
@@ -96,13 +117,14 @@ pub async fn get_route(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
     Path(route_id): Path<Uuid>,
-) -> Result<impl IntoResponse, UsecaseError> {
+    headers: HeaderMap,
+) -> Result<Response, UsecaseError> {
     tracing::debug!(user_id = %user.user_id, %route_id, "handling get route request");
 
     let route = state.routes_usecase.get_route(user.user_id, route_id).await?;
 
     tracing::debug!(%route_id, "route retrieved successfully");
-    Ok((StatusCode::OK, Json(route_to_response(route))))
+    Ok(conditional_json(&headers, route_to_response(&state, route)))
 }
 
 #[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id))]
@@ -118,14 +140,23 @@ pub async fn create_route(
         return Err(UsecaseError::Validation(format!("{:?}", validation_errors)));
     }
 
+    let name = state.content_filter.check(&payload.name).await?;
     let route = state
         .routes_usecase
-        .create_route(user.user_id, payload.name, payload.points, payload.category_ids, payload.seasons)
+        .create_route(user.user_id, name.text, payload.points, payload.category_ids, payload.seasons)
         .await?;
 
     tracing::debug!(route_id = %route.id, "route created successfully");
+    if let Err(e) = state.spam_usecase.screen_route(&route, payload.form_elapsed_ms).await {
+        tracing::error!(error = %e, "failed to screen route for spam");
+    }
+    if name.flagged {
+        flag_blocked_terms(&state, route.id, user.user_id).await;
+    }
+    record_activity(&state, route.id, Some(user.user_id), ACTIVITY_CREATED, None).await;
     publish_photo_task(&state.nats_client, &route).await;
-    Ok((StatusCode::CREATED, Json(route_to_response(route))))
+    publish_route_changed(&state.nats_client, route.id).await;
+    Ok((StatusCode::CREATED, Json(route_to_response(&state, route))))
 }
 
 #[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id))]
@@ -142,14 +173,84 @@ pub async fn update_route(
         return Err(UsecaseError::Validation(format!("{:?}", validation_errors)));
     }
 
-    let route = state
+    let name = match payload.name {
+        Some(name) => Some(state.content_filter.check(&name).await?),
+        None => None,
+    };
+    let flagged = name.as_ref().is_some_and(|n| n.flagged);
+    let (route, points_changed) = state
         .routes_usecase
-        .update_route(user.user_id, route_id, payload.name, payload.points, payload.category_ids, payload.seasons)
+        .update_route(user.user_id, route_id, name.map(|n| n.text), payload.points, payload.category_ids, payload.seasons)
         .await?;
 
-    tracing::debug!(%route_id, "route updated successfully");
+    tracing::debug!(%route_id, points_changed, "route updated successfully");
+    if flagged {
+        flag_blocked_terms(&state, route.id, user.user_id).await;
+    }
+    let kind = if points_changed { ACTIVITY_POINTS_EDITED } else { ACTIVITY_DETAILS_EDITED };
+    record_activity(&state, route.id, Some(user.user_id), kind, None).await;
     publish_photo_task(&state.nats_client, &route).await;
-    Ok((StatusCode::OK, Json(route_to_response(route))))
+    publish_route_changed(&state.nats_client, route.id).await;
+    if points_changed && route.share_token.is_some() {
+        publish_route_updated(
+            &state,
+            RouteUpdatedEvent {
+                route_id: route.id,
+                owner_id: route.user_id,
+                route_name: route.name.clone(),
+                actor_name: user.email.clone(),
+            },
+        )
+        .await;
+    }
+    Ok((StatusCode::OK, Json(route_to_response(&state, route))))
+}
+
+/// Edits single points without resending the others, so untouched photos are
+/// left alone. When the route changed since the version the client edited,
+/// nothing is saved and 409 returns the current route.
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, operation_count = payload.operations.len()))]
+pub async fn patch_route_points(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    Json(payload): Json<PatchPointsRequest>,
+) -> Result<Response, UsecaseError> {
+    tracing::debug!(%route_id, "handling patch route points request");
+
+    if let Err(validation_errors) = payload.validate() {
+        tracing::warn!(user_id = %user.user_id, ?validation_errors, "validation failed");
+        return Err(UsecaseError::Validation(format!("{:?}", validation_errors)));
+    }
+
+    let (route, significant) = match state
+        .routes_usecase
+        .patch_points(user.user_id, route_id, payload.expected_updated_at, &payload.operations)
+        .await?
+    {
+        PointsPatch::Applied { route, significant } => (route, significant),
+        PointsPatch::Stale(current) => {
+            tracing::info!(%route_id, "point edit rejected, route changed meanwhile");
+            return Ok((StatusCode::CONFLICT, Json(route_to_response(&state, current))).into_response());
+        }
+    };
+
+    record_activity(&state, route.id, Some(user.user_id), ACTIVITY_POINTS_EDITED, None).await;
+    publish_photo_task(&state.nats_client, &route).await;
+    publish_route_changed(&state.nats_client, route.id).await;
+    if significant && route.share_token.is_some() {
+        publish_route_updated(
+            &state,
+            RouteUpdatedEvent {
+                route_id: route.id,
+                owner_id: route.user_id,
+                route_name: route.name.clone(),
+                actor_name: user.email.clone(),
+            },
+        )
+        .await;
+    }
+    Ok((StatusCode::OK, Json(route_to_response(&state, route))).into_response())
 }
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
@@ -162,22 +263,70 @@ pub async fn delete_route(
 
     state
         .routes_usecase
-        .delete_route(user.user_id, route_id, &user.role)
+        .delete_route(user.user_id, route_id, user.effective_role())
         .await?;
 
     tracing::debug!(%route_id, "route deleted successfully");
+    publish_route_changed(&state.nats_client, route_id).await;
     Ok(StatusCode::NO_CONTENT)
 }
 
-#[tracing::instrument(skip(state, multipart), fields(user_id = %user.user_id))]
+/// One uploaded file after parsing.
+struct ImportUpload {
+    filename: Option<String>,
+    parsed: Result<(ImportFormat, String, Vec<RoutePoint>), ImportError>,
+}
+
+enum ImportOutcome {
+    Created(DomainRoute),
+    Duplicate(DomainRoute),
+}
+
+/// Creates the route for one parsed file. Without `force`, a file matching
+/// an existing route of the user is not imported.
+async fn create_imported_route(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    name: String,
+    points: Vec<RoutePoint>,
+    format: ImportFormat,
+    force: bool,
+) -> Result<ImportOutcome, UsecaseError> {
+    if !force && let Some(existing) = state.routes_usecase.find_duplicate(user.user_id, &points).await? {
+        tracing::info!(existing_route_id = %existing.id, "import looks like a duplicate");
+        return Ok(ImportOutcome::Duplicate(existing));
+    }
+
+    let name = state.content_filter.check(&name).await?;
+    let route = state
+        .routes_usecase
+        .import_route(user.user_id, name.text, points, format.as_str())
+        .await?;
+
+    tracing::info!(route_id = %route.id, format = format.as_str(), "route imported successfully");
+    if name.flagged {
+        flag_blocked_terms(state, route.id, user.user_id).await;
+    }
+    let details = serde_json::json!({ "source": format.as_str() });
+    record_activity(state, route.id, Some(user.user_id), ACTIVITY_CREATED, Some(details)).await;
+    publish_photo_task(&state.nats_client, &route).await;
+    publish_route_changed(&state.nats_client, route.id).await;
+    Ok(ImportOutcome::Created(route))
+}
+
+/// Imports GeoJSON or GPX files sent as `file` fields. A single file answers
+/// with the created route (or 409 for a duplicate); several files create one
+/// route each and answer with a per-file report.
+#[tracing::instrument(skip(state, multipart), fields(user_id = %user.user_id, force = params.force))]
 pub async fn import_route_from_geojson(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<ImportRouteParams>,
     mut multipart: Multipart,
-) -> Result<impl IntoResponse, UsecaseError> {
-    tracing::debug!("handling import route from GeoJSON request");
+) -> Result<Response, UsecaseError> {
+    tracing::debug!("handling import route request");
 
-    let mut file_content: Option<String> = None;
+    let mut uploads = Vec::new();
 
     while let Some(field) = multipart
         .next_field()
@@ -187,57 +336,315 @@ pub async fn import_route_from_geojson(
         let field_name = field.name().unwrap_or("").to_string();
         tracing::debug!(field_name = %field_name, "processing multipart field");
 
-        if field_name == "file" {
-            let bytes = field.bytes().await.map_err(|e| {
-                UsecaseError::Validation(format!("Failed to read file: {}", e))
-            })?;
-
-            file_content = Some(String::from_utf8(bytes.to_vec()).map_err(|_| {
-                UsecaseError::Validation("File must be valid UTF-8".to_string())
-            })?);
+        if field_name != "file" {
+            continue;
+        }
+        if uploads.len() >= state.import_max_files {
+            tracing::warn!(max_files = state.import_max_files, "too many files in import request");
+            return Err(UsecaseError::Validation(format!(
+                "At most {} files can be imported at once",
+                state.import_max_files
+            )));
+        }
 
-            tracing::debug!(content_len = file_content.as_ref().map(|c| c.len()), "read file content");
-            break;
+        let filename = field.file_name().map(|s| s.to_string());
+        let format = ImportFormat::detect(filename.as_deref(), field.content_type());
+        let parsed = parse_upload(format, field, state.import_max_bytes, state.import_max_points).await;
+        if let Err(e) = &parsed {
+            tracing::warn!(filename = ?filename, error = %e, "failed to parse imported file");
         }
+        uploads.push(ImportUpload { filename, parsed });
+    }
+
+    if uploads.len() > 1 {
+        return Ok(import_batch(&state, &user, uploads, params.force).await.into_response());
     }
 
-    let content = file_content.ok_or_else(|| {
+    let upload = uploads.pop().ok_or_else(|| {
         tracing::warn!("no 'file' field in multipart request");
         UsecaseError::Validation("Missing 'file' field in multipart request".to_string())
     })?;
-
-    let (name, points) = parse_geojson(&content).map_err(|e| {
-        tracing::warn!(error = %e, "failed to parse GeoJSON");
-        match &e {
-            ImportError::InvalidGeoJson(_)
-            | ImportError::MissingRouteName
-            | ImportError::EmptyRoute
-            | ImportError::UnsupportedGeometry => {
-                UsecaseError::Validation(e.to_string())
-            }
-        }
-    })?;
+    let (format, name, points) = upload.parsed?;
 
     tracing::info!(
-        user_id = %user.user_id,
         route_name = %name,
         point_count = points.len(),
-        "parsed GeoJSON successfully, creating route"
+        format = format.as_str(),
+        "parsed imported file, creating route"
     );
 
+    import_response(&state, create_imported_route(&state, &user, name, points, format, params.force).await?)
+}
+
+/// Imports the GeoJSON or GPX file behind a link, answering like a single
+/// file upload.
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, force = params.force))]
+pub async fn import_route_from_url(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<ImportRouteParams>,
+    Json(payload): Json<ImportUrlRequest>,
+) -> Result<Response, UsecaseError> {
+    tracing::debug!("handling import route from URL request");
+
+    let (format, name, points) = state.url_importer.fetch(&payload.url).await?;
+    import_response(&state, create_imported_route(&state, &user, name, points, format, params.force).await?)
+}
+
+fn import_response(state: &AppState, outcome: ImportOutcome) -> Result<Response, UsecaseError> {
+    match outcome {
+        ImportOutcome::Created(route) => Ok((StatusCode::CREATED, Json(route_to_response(state, route))).into_response()),
+        ImportOutcome::Duplicate(existing) => Ok((
+            StatusCode::CONFLICT,
+            Json(DuplicateRouteResponse {
+                error: "duplicate_route".to_string(),
+                message: format!("A nearly identical route \"{}\" already exists", existing.name),
+                existing_route_id: existing.id,
+            }),
+        )
+            .into_response()),
+    }
+}
+
+/// Creates a route for every file that parsed. A failing file does not stop
+/// the others; the report says what happened to each.
+async fn import_batch(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    uploads: Vec<ImportUpload>,
+    force: bool,
+) -> Json<ImportBatchResponse> {
+    let mut files = Vec::with_capacity(uploads.len());
+
+    for upload in uploads {
+        let outcome = match upload.parsed {
+            Ok((format, name, points)) => create_imported_route(state, user, name, points, format, force).await,
+            Err(e) => Err(e.into()),
+        };
+        let result = match outcome {
+            Ok(ImportOutcome::Created(route)) => ImportFileResult {
+                filename: upload.filename,
+                status: "created".to_string(),
+                route: Some(route_to_response(state, route)),
+                existing_route_id: None,
+                error: None,
+            },
+            Ok(ImportOutcome::Duplicate(existing)) => ImportFileResult {
+                filename: upload.filename,
+                status: "duplicate".to_string(),
+                route: None,
+                existing_route_id: Some(existing.id),
+                error: Some(format!("A nearly identical route \"{}\" already exists", existing.name)),
+            },
+            Err(e) => {
+                tracing::warn!(filename = ?upload.filename, error = %e, "file of import batch failed");
+                ImportFileResult {
+                    filename: upload.filename,
+                    status: "failed".to_string(),
+                    route: None,
+                    existing_route_id: None,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+        files.push(result);
+    }
+
+    let created = files.iter().filter(|f| f.route.is_some()).count();
+    tracing::info!(files = files.len(), created, "import batch finished");
+    Json(ImportBatchResponse {
+        created,
+        failed: files.len() - created,
+        files,
+    })
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id))]
+pub async fn merge_routes(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(payload): Json<MergeRoutesRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!(route_ids = ?payload.route_ids, "handling merge routes request");
+
+    if let Err(validation_errors) = payload.validate() {
+        tracing::warn!(user_id = %user.user_id, ?validation_errors, "validation failed");
+        return Err(UsecaseError::Validation(format!("{:?}", validation_errors)));
+    }
+    if let Some(id) = payload.reversed_route_ids.iter().find(|id| !payload.route_ids.contains(id)) {
+        return Err(UsecaseError::Validation(format!("Reversed route {} is not among the merged routes", id)));
+    }
+    let parts: Vec<(Uuid, bool)> = payload
+        .route_ids
+        .iter()
+        .map(|id| (*id, payload.reversed_route_ids.contains(id)))
+        .collect();
+
+    let name = match payload.name {
+        Some(name) => Some(state.content_filter.check(&name).await?),
+        None => None,
+    };
+    let flagged = name.as_ref().is_some_and(|n| n.flagged);
     let route = state
         .routes_usecase
-        .create_route(user.user_id, name, points, vec![], vec![])
+        .merge_routes(user.user_id, &parts, name.map(|n| n.text))
         .await?;
 
-    tracing::info!(route_id = %route.id, "route imported successfully from GeoJSON");
+    tracing::info!(route_id = %route.id, "routes merged");
+    if flagged {
+        flag_blocked_terms(&state, route.id, user.user_id).await;
+    }
+    let details = serde_json::json!({ "merged_from": payload.route_ids });
+    record_activity(&state, route.id, Some(user.user_id), ACTIVITY_CREATED, Some(details)).await;
     publish_photo_task(&state.nats_client, &route).await;
-    Ok((StatusCode::CREATED, Json(route_to_response(route))))
+    publish_route_changed(&state.nats_client, route.id).await;
+    Ok((StatusCode::CREATED, Json(route_to_response(&state, route))))
 }
 
-#[derive(Serialize)]
-pub struct ShareResponse {
-    pub share_token: String,
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id))]
+pub async fn split_route(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    Json(payload): Json<SplitRouteRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!(%route_id, point_index = payload.point_index, "handling split route request");
+
+    let (first, second) = state
+        .routes_usecase
+        .split_route(user.user_id, route_id, payload.point_index, payload.truncate_original)
+        .await?;
+
+    tracing::info!(%route_id, first_id = %first.id, second_id = %second.id, "route split");
+    for part in [&first, &second] {
+        // With truncate_original the first part is the original route
+        let (kind, details) = if part.id == route_id {
+            (ACTIVITY_POINTS_EDITED, serde_json::json!({ "split_into": second.id }))
+        } else {
+            (ACTIVITY_CREATED, serde_json::json!({ "split_from": route_id }))
+        };
+        record_activity(&state, part.id, Some(user.user_id), kind, Some(details)).await;
+        publish_photo_task(&state.nats_client, part).await;
+        publish_route_changed(&state.nats_client, part.id).await;
+    }
+    Ok((
+        StatusCode::CREATED,
+        Json(SplitRouteResponse {
+            first: route_to_response(&state, first),
+            second: route_to_response(&state, second),
+        }),
+    ))
+}
+
+/// Copies a template into a new route owned by the caller. The copy goes
+/// through the same checks and quota as a freshly created route.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, template_id = %template_id))]
+pub async fn instantiate_template(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(template_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling instantiate template request");
+
+    let template = state.templates_usecase.template_route(user.user_id, template_id).await?;
+    let route = state
+        .routes_usecase
+        .create_route(user.user_id, template.name, template.points, template.category_ids, template.seasons)
+        .await?;
+
+    tracing::info!(route_id = %route.id, "route created from template");
+    let details = serde_json::json!({ "template_id": template_id });
+    record_activity(&state, route.id, Some(user.user_id), ACTIVITY_CREATED, Some(details)).await;
+    publish_photo_task(&state.nats_client, &route).await;
+    publish_route_changed(&state.nats_client, route.id).await;
+    Ok((StatusCode::CREATED, Json(route_to_response(&state, route))))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn reverse_route(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!(%route_id, "handling reverse route request");
+
+    let (route, previous_updated_at) = state.routes_usecase.reverse_route(user.user_id, route_id).await?;
+
+    // Keep the elevation stats current (best-effort); a failure only means
+    // the profile is recomputed on its next request
+    let result = state.elevation_usecase.reverse_profile(&route, previous_updated_at).await;
+    if let Err(e) = result {
+        tracing::error!(%route_id, error = %e, "failed to reverse elevation profile");
+    }
+    record_activity(&state, route.id, Some(user.user_id), ACTIVITY_REVERSED, None).await;
+    publish_route_changed(&state.nats_client, route.id).await;
+    if route.share_token.is_some() {
+        publish_route_updated(
+            &state,
+            RouteUpdatedEvent {
+                route_id: route.id,
+                owner_id: route.user_id,
+                route_name: route.name.clone(),
+                actor_name: user.email.clone(),
+            },
+        )
+        .await;
+    }
+    Ok((StatusCode::OK, Json(route_to_response(&state, route))))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn archive_route(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!(%route_id, "handling archive route request");
+
+    let route = state.routes_usecase.set_archived(user.user_id, route_id, true).await?;
+
+    record_activity(&state, route.id, Some(user.user_id), ACTIVITY_ARCHIVED, None).await;
+    publish_route_changed(&state.nats_client, route.id).await;
+    Ok((StatusCode::OK, Json(route_to_response(&state, route))))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn unarchive_route(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!(%route_id, "handling unarchive route request");
+
+    let route = state.routes_usecase.set_archived(user.user_id, route_id, false).await?;
+
+    record_activity(&state, route.id, Some(user.user_id), ACTIVITY_UNARCHIVED, None).await;
+    publish_route_changed(&state.nats_client, route.id).await;
+    Ok((StatusCode::OK, Json(route_to_response(&state, route))))
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id))]
+pub async fn set_route_cover(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    Json(payload): Json<SetCoverRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!(%route_id, point_index = ?payload.point_index, "handling set route cover request");
+
+    let cover = state
+        .routes_usecase
+        .set_cover(user.user_id, route_id, payload.point_index)
+        .await?;
+
+    let (cover_url, cover_thumbnail_url) = cover.map(|c| (c.url, c.thumbnail_url)).unzip();
+    Ok((
+        StatusCode::OK,
+        Json(CoverResponse {
+            cover_url,
+            cover_thumbnail_url: cover_thumbnail_url.flatten(),
+        }),
+    ))
 }
 
 #[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
@@ -254,10 +661,12 @@ pub async fn enable_share(
         .await?;
 
     tracing::info!(%route_id, %token, "sharing enabled");
+    record_activity(&state, route_id, Some(user.user_id), ACTIVITY_SHARED, None).await;
+    publish_route_changed(&state.nats_client, route_id).await;
     Ok((
         StatusCode::OK,
         Json(ShareResponse {
-            share_token: token.to_string(),
+            share_token: state.share_links_usecase.route_token(token),
         }),
     ))
 }
@@ -276,57 +685,62 @@ pub async fn disable_share(
         .await?;
 
     tracing::info!(%route_id, "sharing disabled");
+    record_activity(&state, route_id, Some(user.user_id), ACTIVITY_UNSHARED, None).await;
+    publish_route_changed(&state.nats_client, route_id).await;
     Ok(StatusCode::NO_CONTENT)
 }
 
-#[tracing::instrument(skip(state))]
-pub async fn get_shared_route(
+/// Sets the route's privacy level. The share link is kept while the route
+/// stays unlisted or public.
+#[tracing::instrument(skip(state, request), fields(user_id = %user.user_id))]
+pub async fn set_route_visibility(
     State(state): State<Arc<AppState>>,
-    Path(token): Path<Uuid>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    Json(request): Json<SetVisibilityRequest>,
 ) -> Result<impl IntoResponse, UsecaseError> {
-    tracing::debug!(%token, "handling get shared route request");
+    tracing::debug!(%route_id, visibility = request.visibility.as_str(), "handling set visibility request");
 
-    let route = state.routes_usecase.get_shared_route(token).await?;
+    let route = state
+        .routes_usecase
+        .set_visibility(user.user_id, route_id, request.visibility)
+        .await?;
 
-    tracing::debug!(route_id = %route.id, "shared route retrieved");
-    Ok((StatusCode::OK, Json(route_to_response(route))))
+    tracing::info!(%route_id, visibility = route.visibility.as_str(), "route visibility changed");
+    let details = serde_json::json!({ "visibility": route.visibility });
+    record_activity(&state, route_id, Some(user.user_id), ACTIVITY_VISIBILITY_CHANGED, Some(details)).await;
+    publish_route_changed(&state.nats_client, route_id).await;
+    Ok((StatusCode::OK, Json(route_to_response(&state, route))))
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ExploreQuery {
-    pub search: Option<String>,
-    pub category_id: Option<Uuid>,
-    pub season: Option<String>,
-    pub sort: Option<String>,
-    pub limit: Option<i64>,
-    pub offset: Option<i64>,
+/// Route behind a `/shared/{token}` URL. The signed token is resolved to the
+/// route's share token first, so every kind of link goes through the cache.
+pub async fn find_shared_route(state: &AppState, token: &str) -> Result<DomainRoute, UsecaseError> {
+    let share_token = state.share_links_usecase.resolve(token).await?;
+    state.routes_usecase.get_shared_route(share_token).await
 }
 
-#[derive(Serialize)]
-pub struct ExploreRouteResponse {
-    pub id: Uuid,
-    pub name: String,
-    pub points_count: i64,
-    pub created_at: DateTime<Utc>,
-    pub share_token: String,
-    pub likes_count: i64,
-    pub avg_rating: f64,
-    pub ratings_count: i64,
-    pub category_ids: Vec<Uuid>,
-    pub seasons: Vec<String>,
-}
+#[tracing::instrument(skip(state))]
+pub async fn get_shared_route(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, UsecaseError> {
+    tracing::debug!(%token, "handling get shared route request");
 
-#[derive(Serialize)]
-pub struct ExploreResponse {
-    pub routes: Vec<ExploreRouteResponse>,
-    pub total: i64,
+    let route = find_shared_route(&state, &token).await?;
+
+    tracing::debug!(route_id = %route.id, "shared route retrieved");
+    Ok(conditional_json(&headers, route_to_public_response(route)))
 }
 
 #[tracing::instrument(skip(state))]
 pub async fn explore_routes(
     State(state): State<Arc<AppState>>,
     Query(params): Query<ExploreQuery>,
-) -> Result<impl IntoResponse, UsecaseError> {
+    request_id: RequestId,
+    headers: HeaderMap,
+) -> Result<Response, UsecaseError> {
     let search = params.search.filter(|s| !s.is_empty());
     let category_id = params.category_id;
     let season = params.season.filter(|s| !s.is_empty());
@@ -338,27 +752,46 @@ pub async fn explore_routes(
 
     let (rows, total) = state
         .routes_usecase
-        .explore_routes(search, category_id, season, sort, limit, offset)
+        .explore_routes(search.clone(), category_id, season.clone(), sort, limit, offset)
+        .await?;
+    let thresholds = state.settings_usecase.get_difficulty_thresholds().await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    let facets = state
+        .routes_usecase
+        .explore_facets(search, category_id, season, &thresholds)
         .await?;
+    let facets = ExploreFacets {
+        category_ids: facets.categories.into_iter().collect(),
+        difficulty: facets.difficulty.into_iter().collect(),
+    };
 
     let routes: Vec<ExploreRouteResponse> = rows
         .into_iter()
         .map(|r| ExploreRouteResponse {
+            link_token: state.share_links_usecase.public_token(r.id),
             id: r.id,
             name: r.name,
             points_count: r.points_count,
             created_at: r.created_at,
-            share_token: r.share_token.to_string(),
             likes_count: r.likes_count,
             avg_rating: r.avg_rating,
             ratings_count: r.ratings_count,
+            comments_count: r.comments_count,
+            bookmarks_count: r.bookmarks_count,
             category_ids: r.category_ids,
             seasons: r.seasons,
+            start_location: r.start_location,
+            end_location: r.end_location,
+            cover_url: r.cover_url,
+            cover_thumbnail_url: r.cover_thumbnail_url,
+            stats: route_stats(&r.path),
         })
         .collect();
 
     tracing::debug!(count = routes.len(), total, "explore routes listed");
-    Ok((StatusCode::OK, Json(ExploreResponse { routes, total })))
+    let etag = etag_for(&(&routes, limit, offset, total, &facets));
+    let response = ListResponse::page(routes, limit, offset, total, request_id).with_meta(ExploreMeta { facets });
+    Ok(conditional(&headers, &etag, response))
 }
 
 #[derive(Serialize)]
@@ -401,61 +834,389 @@ pub async fn save_description(
         .save_description(user.user_id, route_id, payload.description)
         .await?;
 
-    Ok((StatusCode::OK, Json(route_to_response(route))))
+    publish_route_changed(&state.nats_client, route_id).await;
+    Ok((StatusCode::OK, Json(route_to_response(&state, route))))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %route_id))]
+pub async fn export_route_pdf(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    RequestLocale(locale): RequestLocale,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling export route PDF request");
+
+    let route = state.routes_usecase.get_route(user.user_id, route_id).await?;
+    let thresholds = state.settings_usecase.get_difficulty_thresholds().await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+
+    let elevation_gain_m = match state.elevation_usecase.profile_for_route(&route).await {
+        Ok(profile) => profile.gain_m,
+        Err(e) => {
+            tracing::warn!(%route_id, error = %e, "elevation profile unavailable, classifying by distance only");
+            0.0
+        }
+    };
+
+    let labels = state.translations_usecase.catalog(locale).await?;
+    let pdf = state.route_exporter.export_pdf(&route, &thresholds, elevation_gain_m, &labels).await?;
+
+    tracing::debug!(%route_id, size = pdf.len(), "route PDF exported");
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"route-{}.pdf\"", route_id),
+            ),
+        ],
+        pdf,
+    ))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %route_id))]
+pub async fn export_offline_bundle(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling offline bundle request");
+
+    let route = state.routes_usecase.get_route(user.user_id, route_id).await?;
+    let bundle = state.route_exporter.offline_bundle(&route).await?;
+
+    tracing::debug!(%route_id, size = bundle.len(), "offline bundle exported");
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"route-{}.zip\"", route_id),
+            ),
+        ],
+        bundle,
+    ))
+}
+
+#[derive(Serialize)]
+pub struct RouteSegmentsResponse {
+    pub route_id: Uuid,
+    pub segments: Vec<SegmentGeometry>,
+    pub distance_km: f64,
+    pub computed_at: DateTime<Utc>,
+    /// True when the route was edited after the segments were computed.
+    pub stale: bool,
+}
+
+impl RouteSegmentsResponse {
+    fn new(geometry: RouteGeometry, stale: bool) -> Self {
+        Self {
+            route_id: geometry.route_id,
+            segments: geometry.segments,
+            distance_km: geometry.distance_km,
+            computed_at: geometry.computed_at,
+            stale,
+        }
+    }
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %route_id))]
+pub async fn compute_route_segments(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling compute route segments request");
+
+    let geometry = state
+        .route_segments_usecase
+        .compute_segments(user.user_id, route_id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(RouteSegmentsResponse::new(geometry, false))))
+}
+
+#[derive(Serialize)]
+pub struct RouteStatsResponse {
+    pub route_id: Uuid,
+    pub stats: RouteStats,
+    /// True when distances follow the computed segments rather than
+    /// straight lines between the points.
+    pub from_segments: bool,
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %route_id))]
+pub async fn get_route_stats(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling get route stats request");
+
+    let (stats, from_segments) = state.route_segments_usecase.get_stats(user.user_id, route_id).await?;
+
+    Ok((StatusCode::OK, Json(RouteStatsResponse { route_id, stats, from_segments })))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %route_id))]
+pub async fn get_route_segments(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling get route segments request");
+
+    let (geometry, stale) = state
+        .route_segments_usecase
+        .get_segments(user.user_id, route_id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(RouteSegmentsResponse::new(geometry, stale))))
+}
+
+#[derive(Serialize)]
+pub struct ElevationProfileResponse {
+    pub route_id: Uuid,
+    pub samples: Vec<ElevationSample>,
+    pub gain_m: f64,
+    pub loss_m: f64,
+    pub min_m: f64,
+    pub max_m: f64,
+    pub computed_at: DateTime<Utc>,
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %route_id))]
+pub async fn get_route_elevation(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling get route elevation request");
+
+    let profile = state.elevation_usecase.get_profile(user.user_id, route_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ElevationProfileResponse {
+            route_id: profile.route_id,
+            samples: profile.samples,
+            gain_m: profile.gain_m,
+            loss_m: profile.loss_m,
+            min_m: profile.min_m,
+            max_m: profile.max_m,
+            computed_at: profile.computed_at,
+        }),
+    ))
+}
+
+#[derive(Serialize)]
+pub struct GeocodePointsResponse {
+    pub status: &'static str,
+    pub unnamed_points: usize,
+    pub points_without_address: usize,
+}
+
+/// Queues reverse geocoding of every point missing a name or an address.
+/// Connected WS clients receive a `points_geocoded` message with the updated
+/// points when done.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %route_id))]
+pub async fn geocode_route_points(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling geocode route points request");
+
+    let pending = state
+        .routes_usecase
+        .prepare_point_geocoding(user.user_id, route_id)
+        .await?;
+    let response = |status: &'static str| GeocodePointsResponse {
+        status,
+        unnamed_points: pending.unnamed,
+        points_without_address: pending.without_address,
+    };
+    if pending.is_empty() {
+        return Ok((StatusCode::OK, Json(response("complete"))));
+    }
+
+    let state = state.clone();
+    tokio::spawn(async move {
+        match state.routes_usecase.geocode_points(route_id).await {
+            Ok(Some(route)) => {
+                publish_route_changed(&state.nats_client, route_id).await;
+                broadcast_points_geocoded(&state, &route).await;
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(%route_id, error = %e, "point geocoding failed"),
+        }
+    });
+
+    tracing::info!(%route_id, unnamed = pending.unnamed, without_address = pending.without_address, "point geocoding queued");
+    Ok((StatusCode::ACCEPTED, Json(response("queued"))))
+}
+
+async fn broadcast_points_geocoded(state: &AppState, route: &DomainRoute) {
+    let payload = serde_json::json!({
+        "type": "points_geocoded",
+        "route_id": route.id.to_string(),
+        "points": route.points,
+    });
+    state.realtime.publish(route.id, payload.to_string()).await;
+}
+
+/// Queues a route whose name matched the blocked-term filter (best-effort).
+async fn flag_blocked_terms(state: &AppState, route_id: Uuid, user_id: Uuid) {
+    let result = state
+        .spam_usecase
+        .flag(SPAM_CONTENT_ROUTE, route_id, user_id, &[SPAM_REASON_BLOCKED_TERM])
+        .await;
+    if let Err(e) = result {
+        tracing::error!(%route_id, error = %e, "failed to flag route with blocked terms");
+    }
+}
+
+/// Announces a route change so the search indexer can refresh its entry (best-effort).
+async fn publish_route_changed(nats_client: &Option<async_nats::Client>, route_id: Uuid) {
+    let Some(client) = nats_client else {
+        return;
+    };
+    match events::encode(&RouteChangedEvent { route_id }) {
+        Ok(payload) => {
+            if let Err(e) = client.publish(ROUTE_CHANGED_SUBJECT, payload.into()).await {
+                tracing::warn!(%route_id, error = %e, "failed to publish route changed event");
+            } else {
+                tracing::debug!(%route_id, "published route changed event");
+            }
+        }
+        Err(e) => {
+            tracing::error!(%route_id, error = %e, "failed to serialize route changed event");
+        }
+    }
+}
+
+/// Hands a significant route edit to the event bus so followers get notified.
+/// Without NATS the followers are notified directly in the background.
+async fn publish_route_updated(state: &Arc<AppState>, event: RouteUpdatedEvent) {
+    if let Some(client) = &state.nats_client {
+        match events::encode(&event) {
+            Ok(payload) => match client.publish(ROUTE_UPDATED_SUBJECT, payload.into()).await {
+                Ok(()) => {
+                    tracing::debug!(route_id = %event.route_id, "published route updated event");
+                    return;
+                }
+                Err(e) => tracing::warn!(route_id = %event.route_id, error = %e, "failed to publish route updated event"),
+            },
+            Err(e) => tracing::error!(route_id = %event.route_id, error = %e, "failed to serialize route updated event"),
+        }
+    }
+
+    let state = state.clone();
+    tokio::spawn(async move {
+        if let Err(e) = state.route_updates_usecase.notify_route_updated(&event).await {
+            tracing::warn!(route_id = %event.route_id, error = %e, "failed to notify route followers");
+        }
+    });
+}
+
+#[derive(Serialize)]
+pub struct RetryPhotosResponse {
+    /// Points whose photos were queued again.
+    pub point_indices: Vec<usize>,
+}
+
+/// Queues the route's failed photos for processing again.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %route_id))]
+pub async fn retry_failed_photos(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let Some(client) = state.nats_client.as_ref() else {
+        return Err(UsecaseError::Unavailable("Photo processing is not available".to_string()));
+    };
+
+    let task = state
+        .routes_usecase
+        .prepare_photo_retry(user.user_id, route_id)
+        .await?;
+    if !publish_task(client, &task).await {
+        return Err(UsecaseError::Unavailable("Could not queue photo processing".to_string()));
+    }
+
+    metrics::counter!("photo_retries_total").increment(1);
+    tracing::info!(points = task.point_indices.len(), "failed photos queued for retry");
+    Ok((StatusCode::ACCEPTED, Json(RetryPhotosResponse { point_indices: task.point_indices })))
 }
 
 async fn publish_photo_task(nats_client: &Option<async_nats::Client>, route: &DomainRoute) {
-    if let Some(client) = nats_client {
-        if let Some(task) = PhotoProcessTask::from_route(route) {
-            match serde_json::to_vec(&task) {
-                Ok(payload) => {
-                    let jetstream = async_nats::jetstream::new(client.clone());
-                    match jetstream
-                        .publish("photos.process", payload.into())
-                        .await
-                    {
-                        Ok(ack_future) => {
-                            match ack_future.await {
-                                Ok(_) => {
-                                    tracing::info!(
-                                        route_id = %task.route_id,
-                                        point_count = task.point_indices.len(),
-                                        "published photo processing task to NATS"
-                                    );
-                                }
-                                Err(e) => {
-                                    tracing::error!(
-                                        route_id = %task.route_id,
-                                        error = %e,
-                                        "failed to get NATS publish ack"
-                                    );
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!(
-                                route_id = %task.route_id,
-                                error = %e,
-                                "failed to publish photo task to NATS"
-                            );
-                        }
-                    }
+    if let (Some(client), Some(task)) = (nats_client, photo_task_for_route(route)) {
+        publish_task(client, &task).await;
+    }
+}
+
+/// Publishes the task to the photo worker's stream; `false` if it was not
+/// acknowledged.
+async fn publish_task(client: &async_nats::Client, task: &PhotoProcessTask) -> bool {
+    let payload = match events::encode(task) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::error!(
+                route_id = %task.route_id,
+                error = %e,
+                "failed to serialize photo task"
+            );
+            return false;
+        }
+    };
+
+    let jetstream = async_nats::jetstream::new(client.clone());
+    match jetstream
+        .publish(PHOTO_PROCESS_SUBJECT, payload.into())
+        .await
+    {
+        Ok(ack_future) => {
+            match ack_future.await {
+                Ok(_) => {
+                    metrics::counter!("photo_tasks_published_total", "outcome" => "ok").increment(1);
+                    metrics::histogram!("photo_task_points").record(task.point_indices.len() as f64);
+                    tracing::info!(
+                        route_id = %task.route_id,
+                        point_count = task.point_indices.len(),
+                        "published photo processing task to NATS"
+                    );
+                    true
                 }
                 Err(e) => {
+                    metrics::counter!("photo_tasks_published_total", "outcome" => "error").increment(1);
                     tracing::error!(
                         route_id = %task.route_id,
                         error = %e,
-                        "failed to serialize photo task"
+                        "failed to get NATS publish ack"
                     );
+                    false
                 }
             }
         }
+        Err(e) => {
+            metrics::counter!("photo_tasks_published_total", "outcome" => "error").increment(1);
+            tracing::error!(
+                route_id = %task.route_id,
+                error = %e,
+                "failed to publish photo task to NATS"
+            );
+            false
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::route::RoutePoint;
 
     #[test]
     fn test_create_route_request_validation_valid() {
@@ -467,8 +1228,11 @@ mod tests {
                 name: None,
                 segment_mode: None,
                 photo: None,
+                address: None,
             }],
             category_ids: vec![],
+            seasons: vec![],
+            form_elapsed_ms: None,
         };
 
         assert!(request.validate().is_ok());
@@ -484,8 +1248,11 @@ mod tests {
                 name: None,
                 segment_mode: None,
                 photo: None,
+                address: None,
             }],
             category_ids: vec![],
+            seasons: vec![],
+            form_elapsed_ms: None,
         };
 
         assert!(request.validate().is_err());
@@ -497,11 +1264,36 @@ mod tests {
             name: "Test".to_string(),
             points: vec![],
             category_ids: vec![],
+            seasons: vec![],
+            form_elapsed_ms: None,
         };
 
         assert!(request.validate().is_err());
     }
 
+    #[test]
+    fn test_create_route_request_validation_out_of_range_coordinates() {
+        let point = |lat: f64, lng: f64| RoutePoint {
+            lat,
+            lng,
+            name: None,
+            segment_mode: None,
+            photo: None,
+            address: None,
+        };
+        let request = |points| CreateRouteRequest {
+            name: "Test".to_string(),
+            points,
+            category_ids: vec![],
+            seasons: vec![],
+            form_elapsed_ms: None,
+        };
+
+        assert!(request(vec![point(912.0, 37.0)]).validate().is_err());
+        assert!(request(vec![point(55.0, -181.0)]).validate().is_err());
+        assert!(request(vec![point(-90.0, 180.0)]).validate().is_ok());
+    }
+
     #[test]
     fn test_route_response_serialization() {
         let response = RouteResponse {
@@ -514,15 +1306,19 @@ mod tests {
                 name: Some("Moscow".to_string()),
                 segment_mode: Some("auto".to_string()),
                 photo: None,
+                address: None,
             }],
             created_at: Utc::now(),
             updated_at: Utc::now(),
             share_token: None,
+            visibility: Default::default(),
             category_ids: vec![],
             start_location: None,
             end_location: None,
             seasons: vec![],
             description: None,
+            archived_at: None,
+            stats: Default::default(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use guide_helper_api::list::{ListResponse, RequestId};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::saved_search::{SavedSearch, SearchArea};
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSavedSearchRequest {
+    pub name: String,
+    pub search: Option<String>,
+    pub category_id: Option<Uuid>,
+    pub area: Option<SearchArea>,
+}
+
+#[derive(Serialize)]
+pub struct SavedSearchResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub search: Option<String>,
+    pub category_id: Option<Uuid>,
+    pub area: Option<SearchArea>,
+    pub last_checked_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+fn saved_search_to_response(s: SavedSearch) -> SavedSearchResponse {
+    SavedSearchResponse {
+        id: s.id,
+        area: s.area(),
+        name: s.name,
+        search: s.search,
+        category_id: s.category_id,
+        last_checked_at: s.last_checked_at,
+        created_at: s.created_at,
+    }
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn list_saved_searches(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("listing saved searches");
+
+    let searches = state.saved_searches_usecase.list_searches(user.user_id).await?;
+
+    let response: Vec<SavedSearchResponse> = searches.into_iter().map(saved_search_to_response).collect();
+
+    tracing::debug!(count = response.len(), "saved searches listed");
+    Ok(ListResponse::all(response, request_id))
+}
+
+/// Alerts cover routes shared from now on; earlier ones are on explore.
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id))]
+pub async fn create_saved_search(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(payload): Json<CreateSavedSearchRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!(category_id = ?payload.category_id, has_area = payload.area.is_some(), "handling create saved search request");
+
+    let search = state
+        .saved_searches_usecase
+        .create_search(user.user_id, payload.name, payload.search, payload.category_id, payload.area)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(saved_search_to_response(search))))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, search_id = %id))]
+pub async fn delete_saved_search(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling delete saved search request");
+
+    state.saved_searches_usecase.delete_search(user.user_id, id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
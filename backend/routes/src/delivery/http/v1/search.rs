@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use guide_helper_api::list::{ListResponse, RequestId};
+use guide_helper_api::search::ReindexResponse;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: String,
+    pub category_id: Option<Uuid>,
+    pub season: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct SearchHitResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub start_location: Option<String>,
+    pub end_location: Option<String>,
+    pub category_ids: Vec<Uuid>,
+    pub seasons: Vec<String>,
+    /// Signed token opening the route at `/shared/{token}`.
+    pub link_token: String,
+    pub points_count: i64,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// `meta` of the search results.
+#[derive(Serialize)]
+pub struct SearchMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facets: Option<HashMap<String, HashMap<String, u64>>>,
+    pub engine: &'static str,
+}
+
+#[tracing::instrument(skip(state))]
+pub async fn search_routes(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchQuery>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    if params.q.chars().count() > 200 {
+        return Err(UsecaseError::Validation("Search query must be at most 200 characters".to_string()));
+    }
+    let season = params.season.filter(|s| !s.is_empty());
+    let limit = params.limit.unwrap_or(20).clamp(1, 50);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    tracing::debug!(q = %params.q, ?season, %limit, %offset, "handling search request");
+
+    let result = state
+        .search_usecase
+        .search(&params.q, params.category_id, season, limit, offset)
+        .await?;
+
+    let hits: Vec<SearchHitResponse> = result
+        .hits
+        .into_iter()
+        .map(|d| SearchHitResponse {
+            id: d.id,
+            name: d.name,
+            description: d.description,
+            start_location: d.start_location,
+            end_location: d.end_location,
+            category_ids: d.category_ids,
+            seasons: d.seasons,
+            link_token: state.share_links_usecase.public_token(d.id),
+            points_count: d.points_count,
+            created_at: DateTime::from_timestamp(d.created_at, 0),
+        })
+        .collect();
+
+    tracing::debug!(count = hits.len(), total = result.total, engine = result.engine, "search completed");
+    Ok(ListResponse::page(hits, limit, offset, result.total, request_id).with_meta(SearchMeta {
+        facets: result.facets,
+        engine: result.engine,
+    }))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn reindex_search(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::info!("handling search reindex request");
+    let indexed = state.search_usecase.reindex_all().await?;
+
+    Ok((StatusCode::OK, Json(ReindexResponse { indexed })))
+}
@@ -7,8 +7,14 @@ use axum::{
     Extension, Json,
 };
 
-use crate::delivery::http::v1::admin::require_admin;
+use guide_helper_api::events;
+use guide_helper_api::photo::{PhotoPipelineSettings, PHOTO_SETTINGS_UPDATED_SUBJECT};
+
 use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use serde::{Deserialize, Serialize};
+
+use crate::usecase::chat_prompt::{validate_template, DEFAULT_SYSTEM_PROMPT, PROMPT_VARIABLES};
+use crate::usecase::content_filter::ContentFilterSettings;
 use crate::usecase::error::UsecaseError;
 use crate::usecase::settings::DifficultyThresholds;
 use crate::AppState;
@@ -32,8 +38,6 @@ pub async fn set_difficulty_thresholds(
     Extension(user): Extension<AuthenticatedUser>,
     Json(body): Json<DifficultyThresholds>,
 ) -> Result<impl IntoResponse, UsecaseError> {
-    require_admin(&user)?;
-
     tracing::debug!(?body, "setting difficulty thresholds");
 
     if body.distance_easy_max_km <= 0.0
@@ -80,3 +84,132 @@ pub async fn set_difficulty_thresholds(
     tracing::info!(user_id = %user.user_id, "difficulty thresholds updated by admin");
     Ok((StatusCode::OK, Json(body)))
 }
+
+#[derive(Debug, Deserialize)]
+pub struct ChatPromptRequest {
+    /// `null` restores the built-in prompt.
+    pub template: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ChatPromptResponse {
+    pub template: String,
+    pub is_default: bool,
+    pub variables: &'static [&'static str],
+}
+
+fn chat_prompt_response(template: Option<String>) -> ChatPromptResponse {
+    ChatPromptResponse {
+        is_default: template.is_none(),
+        template: template.unwrap_or_else(|| DEFAULT_SYSTEM_PROMPT.to_string()),
+        variables: PROMPT_VARIABLES,
+    }
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_chat_system_prompt(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let template = state.settings_usecase.get_chat_system_prompt().await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+
+    Ok((StatusCode::OK, Json(chat_prompt_response(template))))
+}
+
+#[tracing::instrument(skip(state, body), fields(user_id = %user.user_id))]
+pub async fn set_chat_system_prompt(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(body): Json<ChatPromptRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    if let Some(template) = body.template.as_deref() {
+        validate_template(template).map_err(|e| {
+            tracing::warn!(error = %e, "invalid chat system prompt");
+            UsecaseError::Validation(e)
+        })?;
+    }
+
+    state.settings_usecase.set_chat_system_prompt(body.template.as_deref()).await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    state.chat_prompt.invalidate();
+
+    tracing::info!(user_id = %user.user_id, reset = body.template.is_none(), "chat system prompt updated by admin");
+    Ok((StatusCode::OK, Json(chat_prompt_response(body.template))))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_photo_pipeline(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let settings = state.settings_usecase.get_photo_pipeline().await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+
+    Ok((StatusCode::OK, Json(settings)))
+}
+
+/// Saves the profile and tells running photo workers to pick it up.
+#[tracing::instrument(skip(state, body), fields(user_id = %user.user_id))]
+pub async fn set_photo_pipeline(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(body): Json<PhotoPipelineSettings>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    body.validate().map_err(|e| {
+        tracing::warn!(error = %e, "invalid photo pipeline settings");
+        UsecaseError::Validation(e)
+    })?;
+
+    state.settings_usecase.set_photo_pipeline(&body).await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+
+    // Workers also read the settings on startup, so a missed event only
+    // delays the change until their next restart.
+    if let Some(client) = &state.nats_client {
+        match events::encode(&body) {
+            Ok(payload) => {
+                if let Err(e) = client.publish(PHOTO_SETTINGS_UPDATED_SUBJECT, payload.into()).await {
+                    tracing::warn!(error = %e, "failed to publish photo settings update");
+                }
+            }
+            Err(e) => tracing::error!(error = %e, "failed to serialize photo settings update"),
+        }
+    }
+
+    tracing::info!(user_id = %user.user_id, "photo pipeline settings updated by admin");
+    Ok((StatusCode::OK, Json(body)))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_content_filter(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let filter = state.settings_usecase.get_content_filter().await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+
+    Ok((StatusCode::OK, Json(filter)))
+}
+
+#[tracing::instrument(skip(state, body), fields(user_id = %user.user_id))]
+pub async fn set_content_filter(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(mut body): Json<ContentFilterSettings>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    body.validate().map_err(|e| {
+        tracing::warn!(error = %e, "invalid content filter");
+        UsecaseError::Validation(e)
+    })?;
+    body.terms = body.terms.iter().map(|t| t.trim().to_string()).collect();
+    body.terms.sort_unstable_by_key(|t| t.to_lowercase());
+    body.terms.dedup_by_key(|t| t.to_lowercase());
+
+    state.settings_usecase.set_content_filter(&body).await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    state.content_filter.invalidate();
+
+    tracing::info!(user_id = %user.user_id, mode = %body.mode, terms = body.terms.len(), "content filter updated by admin");
+    Ok((StatusCode::OK, Json(body)))
+}
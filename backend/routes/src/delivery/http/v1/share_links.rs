@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use guide_helper_api::list::{ListResponse, RequestId};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::share_link::ShareLink;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct ShareLinkResponse {
+    pub id: Uuid,
+    pub label: String,
+    /// Goes in `/shared/{token}` URLs.
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateShareLinkRequest {
+    /// Who the link was given to, e.g. "sent to client X".
+    pub label: String,
+}
+
+fn link_to_response(state: &AppState, link: ShareLink) -> ShareLinkResponse {
+    ShareLinkResponse {
+        token: state.share_links_usecase.token(&link),
+        id: link.id,
+        label: link.label,
+        created_at: link.created_at,
+        revoked_at: link.revoked_at,
+    }
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, route_id = %route_id))]
+pub async fn create_share_link(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    Json(payload): Json<CreateShareLinkRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling create share link request");
+
+    let link = state
+        .share_links_usecase
+        .create_link(user.user_id, route_id, &payload.label)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(link_to_response(&state, link))))
+}
+
+/// Revoked links included, newest first.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, route_id = %route_id))]
+pub async fn list_share_links(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let links = state.share_links_usecase.list_links(user.user_id, route_id).await?;
+
+    let links: Vec<ShareLinkResponse> = links.into_iter().map(|l| link_to_response(&state, l)).collect();
+
+    tracing::debug!(count = links.len(), "share links listed");
+    Ok(ListResponse::all(links, request_id))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, route_id = %route_id, link_id = %link_id))]
+pub async fn revoke_share_link(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((route_id, link_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling revoke share link request");
+
+    state
+        .share_links_usecase
+        .revoke_link(user.user_id, route_id, link_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension,
+};
+use chrono::{DateTime, Utc};
+use guide_helper_api::list::{ListResponse, RequestId};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::spam::SpamFlag;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct SpamFlagResponse {
+    pub id: Uuid,
+    pub content_type: String,
+    pub content_id: Uuid,
+    pub user_id: Uuid,
+    pub reasons: Vec<String>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpamFlagListParams {
+    /// Lists reviewed flags instead of the queue.
+    #[serde(default)]
+    pub reviewed: bool,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+fn spam_flag_to_response(f: SpamFlag) -> SpamFlagResponse {
+    SpamFlagResponse {
+        id: f.id,
+        reasons: f.reason_list(),
+        content_type: f.content_type,
+        content_id: f.content_id,
+        user_id: f.user_id,
+        reviewed_by: f.reviewed_by,
+        reviewed_at: f.reviewed_at,
+        created_at: f.created_at,
+    }
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn list_spam_flags(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<SpamFlagListParams>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let limit = params.limit.unwrap_or(20).min(100);
+    let offset = params.offset.unwrap_or(0);
+    tracing::debug!(limit, offset, reviewed = params.reviewed, "listing spam flags");
+
+    let (flags, total) = state.spam_usecase.list_flags(params.reviewed, limit, offset).await?;
+
+    let flags: Vec<SpamFlagResponse> = flags.into_iter().map(spam_flag_to_response).collect();
+
+    tracing::debug!(count = flags.len(), total, "spam flags listed");
+    Ok(ListResponse::page(flags, limit, offset, total, request_id))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, flag_id = %flag_id))]
+pub async fn review_spam_flag(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(flag_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    state.spam_usecase.review_flag(flag_id, user.user_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use guide_helper_api::list::{ListResponse, RequestId};
+use guide_helper_auth_core::ROLE_ADMIN;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::template::TemplateSummary;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct TemplateResponse {
+    pub route_id: Uuid,
+    pub owner_id: Uuid,
+    pub public: bool,
+    pub name: String,
+    pub description: Option<String>,
+    pub start_location: Option<String>,
+    pub end_location: Option<String>,
+    pub points_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct RouteTemplateResponse {
+    pub route_id: Uuid,
+    pub owner_id: Uuid,
+    pub public: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TemplateListParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetTemplateRequest {
+    /// Lists the template for every user. Admins only.
+    #[serde(default)]
+    pub public: bool,
+}
+
+fn template_to_response(t: TemplateSummary) -> TemplateResponse {
+    TemplateResponse {
+        route_id: t.route_id,
+        owner_id: t.owner_id,
+        public: t.public,
+        name: t.name,
+        description: t.description,
+        start_location: t.start_location,
+        end_location: t.end_location,
+        points_count: t.points_count,
+        created_at: t.created_at,
+    }
+}
+
+/// Public templates first, then the caller's own.
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn list_templates(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<TemplateListParams>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let limit = params.limit.unwrap_or(20).min(100);
+    let offset = params.offset.unwrap_or(0);
+    tracing::debug!(limit, offset, "listing route templates");
+
+    let (templates, total) = state.templates_usecase.list_templates(user.user_id, limit, offset).await?;
+
+    let templates: Vec<TemplateResponse> = templates.into_iter().map(template_to_response).collect();
+
+    tracing::debug!(count = templates.len(), total, "route templates listed");
+    Ok(ListResponse::page(templates, limit, offset, total, request_id))
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id, route_id = %route_id))]
+pub async fn set_route_template(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    Json(payload): Json<SetTemplateRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!(public = payload.public, "handling set route template request");
+
+    let template = state
+        .templates_usecase
+        .set_template(user.user_id, user.effective_role() == ROLE_ADMIN, route_id, payload.public)
+        .await?;
+
+    Ok(Json(RouteTemplateResponse {
+        route_id: template.route_id,
+        owner_id: template.owner_id,
+        public: template.public,
+        created_at: template.created_at,
+    }))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, route_id = %route_id))]
+pub async fn remove_route_template(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    tracing::debug!("handling remove route template request");
+
+    state
+        .templates_usecase
+        .remove_template(user.user_id, user.effective_role() == ROLE_ADMIN, route_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
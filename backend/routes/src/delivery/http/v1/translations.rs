@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use guide_helper_api::list::{ListResponse, RequestId};
+use validator::Validate;
+
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::domain::translation::Translation;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+pub use guide_helper_api::translations::{SetTranslationRequest, TranslationListParams, TranslationResponse};
+
+fn translation_to_response(t: Translation) -> TranslationResponse {
+    TranslationResponse {
+        key: t.key,
+        locale: t.locale,
+        value: t.value,
+        updated_at: t.updated_at,
+    }
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, locale = %params.locale))]
+pub async fn list_translations(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<TranslationListParams>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let translations = state.translations_usecase.list(&params.locale).await?;
+    let response: Vec<TranslationResponse> = translations.into_iter().map(translation_to_response).collect();
+
+    tracing::debug!(count = response.len(), "translations listed");
+    Ok(ListResponse::all(response, request_id))
+}
+
+#[tracing::instrument(skip(state, payload), fields(user_id = %user.user_id))]
+pub async fn set_translation(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Json(payload): Json<SetTranslationRequest>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    if let Err(validation_errors) = payload.validate() {
+        tracing::warn!(?validation_errors, "validation failed");
+        return Err(UsecaseError::Validation(format!("{:?}", validation_errors)));
+    }
+
+    let translation = state
+        .translations_usecase
+        .set(payload.key, payload.locale, payload.value)
+        .await?;
+
+    Ok((StatusCode::OK, Json(translation_to_response(translation))))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id, %locale, %key))]
+pub async fn delete_translation(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path((locale, key)): Path<(String, String)>,
+) -> Result<impl IntoResponse, UsecaseError> {
+    state.translations_usecase.delete(&key, &locale).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
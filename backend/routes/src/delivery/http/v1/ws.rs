@@ -11,7 +11,6 @@ use tokio::sync::broadcast;
 use uuid::Uuid;
 
 use crate::AppState;
-use crate::usecase::jwt::TokenType;
 
 #[derive(Deserialize)]
 pub struct WsQuery {
@@ -25,8 +24,8 @@ pub async fn websocket_handler(
     State(state): State<Arc<AppState>>,
 ) -> Response {
     // Validate JWT from query param
-    let claims = match state.jwt_service.validate_token(&query.token) {
-        Ok(claims) => claims,
+    let user = match state.jwt_service.authenticate(&query.token) {
+        Ok(user) => user,
         Err(e) => {
             tracing::warn!(
                 route_id = %route_id,
@@ -37,37 +36,19 @@ pub async fn websocket_handler(
         }
     };
 
-    if claims.token_type != TokenType::Access {
-        tracing::warn!(
-            route_id = %route_id,
-            "WS connection rejected: not an access token"
-        );
-        return (axum::http::StatusCode::UNAUTHORIZED, "Unauthorized").into_response();
-    }
-
     tracing::info!(
         route_id = %route_id,
-        user_id = %claims.sub,
+        user_id = %user.user_id,
         "WS connection accepted, upgrading"
     );
 
-    ws.on_upgrade(move |socket| handle_socket(socket, route_id, claims.sub, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, route_id, user.user_id.to_string(), state))
 }
 
 async fn handle_socket(socket: WebSocket, route_id: Uuid, user_id: String, state: Arc<AppState>) {
     let (mut ws_sender, mut ws_receiver) = socket.split();
 
-    // Get or create broadcast channel for this route
-    let mut rx = {
-        let mut channels = state.ws_channels.write().await;
-        let tx = channels
-            .entry(route_id)
-            .or_insert_with(|| {
-                tracing::debug!(route_id = %route_id, "creating new broadcast channel");
-                broadcast::channel(64).0
-            });
-        tx.subscribe()
-    };
+    let mut rx = state.realtime.subscribe(route_id);
 
     tracing::info!(
         route_id = %route_id,
@@ -83,7 +64,7 @@ async fn handle_socket(socket: WebSocket, route_id: Uuid, user_id: String, state
                         tracing::debug!(
                             route_id = %route_id,
                             user_id = %user_id,
-                            "sending photo update to WS client"
+                            "sending update to WS client"
                         );
                         if ws_sender.send(Message::Text(payload.into())).await.is_err() {
                             tracing::info!(
@@ -139,14 +120,6 @@ async fn handle_socket(socket: WebSocket, route_id: Uuid, user_id: String, state
     }
 
     // Cleanup: if no more receivers, remove the channel
-    let mut channels = state.ws_channels.write().await;
-    if let Some(tx) = channels.get(&route_id) {
-        if tx.receiver_count() == 0 {
-            channels.remove(&route_id);
-            tracing::debug!(
-                route_id = %route_id,
-                "removed empty broadcast channel"
-            );
-        }
-    }
+    drop(rx);
+    state.realtime.release(route_id);
 }
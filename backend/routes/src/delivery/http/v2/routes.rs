@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use guide_helper_api::list::{ListResponse, RequestId};
+use guide_helper_api::routes::ListRoutesQuery;
+use uuid::Uuid;
+
+use crate::delivery::http::etag::conditional_json;
+use crate::delivery::http::v1::middleware::AuthenticatedUser;
+use crate::delivery::http::v1::routes::{find_shared_route, user_routes_filter};
+use crate::domain::route::Route as DomainRoute;
+use crate::usecase::error::UsecaseError;
+use crate::AppState;
+
+pub use guide_helper_api::v2::routes::{RoutePointResponse, RoutePointsResponse, RouteSummary};
+
+/// Summary for the route's owner, carrying a signed link for its share token.
+fn route_to_summary(state: &AppState, r: DomainRoute) -> RouteSummary {
+    let share_token = r.share_token.map(|t| state.share_links_usecase.route_token(t));
+    RouteSummary {
+        share_token,
+        ..route_to_public_summary(r)
+    }
+}
+
+/// Summary for someone who opened the route by a link.
+fn route_to_public_summary(r: DomainRoute) -> RouteSummary {
+    RouteSummary {
+        id: r.id,
+        user_id: r.user_id,
+        name: r.name,
+        points_count: r.points.len(),
+        photos_count: r.points.iter().filter(|p| p.photo.is_some()).count(),
+        created_at: r.created_at,
+        updated_at: r.updated_at,
+        share_token: None,
+        visibility: r.visibility,
+        category_ids: r.category_ids,
+        start_location: r.start_location,
+        end_location: r.end_location,
+        seasons: r.seasons,
+        description: r.description,
+        archived_at: r.archived_at,
+    }
+}
+
+fn route_to_points(r: &DomainRoute) -> RoutePointsResponse {
+    RoutePointsResponse {
+        route_id: r.id,
+        updated_at: r.updated_at,
+        points: r.points.iter().map(RoutePointResponse::from).collect(),
+    }
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn list_routes(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Query(params): Query<ListRoutesQuery>,
+    request_id: RequestId,
+) -> Result<impl IntoResponse, UsecaseError> {
+    let sort = params.sort.as_deref().unwrap_or("newest");
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let filter = user_routes_filter(&params);
+
+    tracing::debug!(?filter, %sort, %limit, %offset, "handling v2 list routes request");
+
+    let (routes, total) = state
+        .routes_usecase
+        .get_user_routes(user.user_id, filter, sort, limit, offset)
+        .await?;
+    let response: Vec<RouteSummary> = routes.into_iter().map(|r| route_to_summary(&state, r)).collect();
+
+    tracing::debug!(user_id = %user.user_id, count = response.len(), total, "routes listed successfully");
+    Ok(ListResponse::page(response, limit, offset, total, request_id))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_route(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Response, UsecaseError> {
+    tracing::debug!(%route_id, "handling v2 get route request");
+
+    let route = state.routes_usecase.get_route(user.user_id, route_id).await?;
+
+    Ok(conditional_json(&headers, route_to_summary(&state, route)))
+}
+
+#[tracing::instrument(skip(state), fields(user_id = %user.user_id))]
+pub async fn get_route_points(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(route_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Response, UsecaseError> {
+    tracing::debug!(%route_id, "handling v2 get route points request");
+
+    let route = state.routes_usecase.get_route(user.user_id, route_id).await?;
+
+    tracing::debug!(%route_id, points_count = route.points.len(), "route points retrieved");
+    Ok(conditional_json(&headers, route_to_points(&route)))
+}
+
+#[tracing::instrument(skip(state))]
+pub async fn get_shared_route(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, UsecaseError> {
+    tracing::debug!(%token, "handling v2 get shared route request");
+
+    let route = find_shared_route(&state, &token).await?;
+
+    Ok(conditional_json(&headers, route_to_public_summary(route)))
+}
+
+#[tracing::instrument(skip(state))]
+pub async fn get_shared_route_points(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, UsecaseError> {
+    tracing::debug!(%token, "handling v2 get shared route points request");
+
+    let route = find_shared_route(&state, &token).await?;
+
+    Ok(conditional_json(&headers, route_to_points(&route)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::route::{PhotoStatus, RoutePoint};
+    use guide_helper_domain::route::PhotoData;
+
+    #[test]
+    fn test_summary_counts_points_and_photos_without_embedding_them() {
+        let mut route = DomainRoute::new(Uuid::new_v4(), "Loop".to_string(), vec![], vec![], vec![]);
+        route.points = vec![
+            RoutePoint { lat: 1.0, lng: 2.0, name: None, segment_mode: None, photo: None, address: None },
+            RoutePoint {
+                lat: 1.5,
+                lng: 2.5,
+                name: None,
+                segment_mode: None,
+                photo: Some(PhotoData {
+                    original: "data:image/png;base64,AAAA".to_string(),
+                    thumbnail_url: None,
+                    status: PhotoStatus::Pending,
+                }),
+                address: None,
+            },
+        ];
+
+        let json = serde_json::to_value(route_to_public_summary(route)).unwrap();
+
+        assert_eq!(json["points_count"], 2);
+        assert_eq!(json["photos_count"], 1);
+        assert!(json.get("points").is_none());
+    }
+}
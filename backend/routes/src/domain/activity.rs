@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const ACTIVITY_CREATED: &str = "created";
+pub const ACTIVITY_POINTS_EDITED: &str = "points_edited";
+/// Name, categories or seasons changed without touching the points.
+pub const ACTIVITY_DETAILS_EDITED: &str = "details_edited";
+pub const ACTIVITY_REVERSED: &str = "reversed";
+pub const ACTIVITY_SHARED: &str = "shared";
+pub const ACTIVITY_UNSHARED: &str = "unshared";
+/// Privacy level set directly; details hold the new visibility.
+pub const ACTIVITY_VISIBILITY_CHANGED: &str = "visibility_changed";
+pub const ACTIVITY_ARCHIVED: &str = "archived";
+pub const ACTIVITY_UNARCHIVED: &str = "unarchived";
+pub const ACTIVITY_PHOTOS_PROCESSED: &str = "photos_processed";
+pub const ACTIVITY_COMMENTED: &str = "commented";
+
+/// One entry of a route's activity log, shown to the owner.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RouteActivity {
+    pub id: Uuid,
+    pub route_id: Uuid,
+    /// None for system events such as photo processing.
+    pub actor_id: Option<Uuid>,
+    pub kind: String,
+    /// Kind-specific facts, e.g. photo counts or the comment id.
+    pub details: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RouteActivity {
+    pub fn new(route_id: Uuid, actor_id: Option<Uuid>, kind: &str, details: Option<serde_json::Value>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            route_id,
+            actor_id,
+            kind: kind.to_string(),
+            details,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_activity_creation() {
+        let route_id = Uuid::new_v4();
+        let activity = RouteActivity::new(route_id, None, ACTIVITY_PHOTOS_PROCESSED, Some(serde_json::json!({"processed": 2})));
+
+        assert_eq!(activity.route_id, route_id);
+        assert_eq!(activity.kind, ACTIVITY_PHOTOS_PROCESSED);
+        assert!(activity.actor_id.is_none());
+    }
+}
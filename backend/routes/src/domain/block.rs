@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// `blocker_id` no longer wants to hear from `blocked_id`: the blocked user's
+/// comments are hidden on the blocker's routes and they cannot comment on,
+/// like or rate those routes.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserBlock {
+    pub blocker_id: Uuid,
+    pub blocked_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserBlock {
+    pub fn new(blocker_id: Uuid, blocked_id: Uuid) -> Self {
+        Self {
+            blocker_id,
+            blocked_id,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_block_creation() {
+        let blocker_id = Uuid::new_v4();
+        let blocked_id = Uuid::new_v4();
+        let block = UserBlock::new(blocker_id, blocked_id);
+
+        assert_eq!(block.blocker_id, blocker_id);
+        assert_eq!(block.blocked_id, blocked_id);
+    }
+}
@@ -21,6 +21,127 @@ pub struct ConversationSummary {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub title: String,
+    pub archived: bool,
+    pub pinned: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ConversationFlags {
+    pub archived: bool,
+    pub pinned: bool,
+}
+
+/// A conversation its owner flagged for abuse review. Admins may only read
+/// the messages when `review_consent` is set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FlaggedConversation {
+    pub user_id: Uuid,
+    pub conversation_id: Uuid,
+    pub reason: Option<String>,
+    pub review_consent: bool,
+    pub message_count: i64,
+    pub flagged_at: DateTime<Utc>,
+}
+
+/// Outcome of one assistant turn, kept for the admin chat dashboard.
+/// `error` is set when the turn failed; `tool_calls` lists the tools the
+/// assistant ran, in order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatTurnRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub conversation_id: Uuid,
+    pub tool_calls: Vec<String>,
+    pub latency_ms: i64,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ChatTurnRecord {
+    pub fn new(
+        user_id: Uuid,
+        conversation_id: Uuid,
+        tool_calls: Vec<String>,
+        latency_ms: i64,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            conversation_id,
+            tool_calls,
+            latency_ms,
+            error,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Language a user wants the assistant to answer in. Without one the
+/// assistant mirrors the user's language and canned replies are in English.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatLanguage {
+    En,
+    Ru,
+}
+
+impl ChatLanguage {
+    pub fn code(self) -> &'static str {
+        match self {
+            ChatLanguage::En => "en",
+            ChatLanguage::Ru => "ru",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "en" => Some(ChatLanguage::En),
+            "ru" => Some(ChatLanguage::Ru),
+            _ => None,
+        }
+    }
+
+    /// English name, as written into the system prompt.
+    pub fn name(self) -> &'static str {
+        match self {
+            ChatLanguage::En => "English",
+            ChatLanguage::Ru => "Russian",
+        }
+    }
+}
+
+/// A tool call the assistant proposed that only runs once the user confirms it.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PendingChatAction {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub conversation_id: Uuid,
+    pub tool: String,
+    pub arguments: serde_json::Value,
+    /// Human-readable description shown to the user before confirming.
+    pub summary: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PendingChatAction {
+    pub fn new(
+        user_id: Uuid,
+        conversation_id: Uuid,
+        tool: String,
+        arguments: serde_json::Value,
+        summary: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            conversation_id,
+            tool,
+            arguments,
+            summary,
+            created_at: Utc::now(),
+        }
+    }
 }
 
 impl ChatMessage {
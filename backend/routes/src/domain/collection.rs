@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A named folder the owner groups their routes in, e.g. one per city. A
+/// route can sit in several collections.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RouteCollection {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// Routes in the collection, archived ones included.
+    pub route_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RouteCollection {
+    pub fn new(user_id: Uuid, name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            name,
+            route_count: 0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
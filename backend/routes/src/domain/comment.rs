@@ -2,6 +2,14 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub const COMMENT_POLICY_OPEN: &str = "open";
+/// New comments stay hidden until the route owner approves them.
+pub const COMMENT_POLICY_PRE_MODERATED: &str = "pre_moderated";
+/// Only the route owner can comment.
+pub const COMMENT_POLICY_LOCKED: &str = "locked";
+
+pub const COMMENT_POLICIES: &[&str] = &[COMMENT_POLICY_OPEN, COMMENT_POLICY_PRE_MODERATED, COMMENT_POLICY_LOCKED];
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Comment {
     pub id: Uuid,
@@ -9,6 +17,8 @@ pub struct Comment {
     pub user_id: Uuid,
     pub author_name: String,
     pub text: String,
+    /// False while the comment awaits the route owner's approval.
+    pub approved: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -20,9 +30,15 @@ impl Comment {
             user_id,
             author_name,
             text,
+            approved: true,
             created_at: Utc::now(),
         }
     }
+
+    pub fn with_approved(mut self, approved: bool) -> Self {
+        self.approved = approved;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -44,5 +60,7 @@ mod tests {
         assert_eq!(comment.user_id, user_id);
         assert_eq!(comment.author_name, "Test User");
         assert_eq!(comment.text, "Great route!");
+        assert!(comment.approved);
+        assert!(!comment.with_approved(false).approved);
     }
 }
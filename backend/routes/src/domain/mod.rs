@@ -1,8 +1,22 @@
+pub mod activity;
+pub mod block;
 pub mod bookmark;
 pub mod category;
 pub mod chat_message;
+pub mod collection;
 pub mod comment;
 pub mod like;
 pub mod notification;
+pub mod quota;
 pub mod rating;
+pub mod report;
 pub mod route;
+pub mod route_elevation;
+pub mod route_geometry;
+pub mod route_stats;
+pub mod saved_search;
+pub mod share_link;
+pub mod spam;
+pub mod stats;
+pub mod template;
+pub mod translation;
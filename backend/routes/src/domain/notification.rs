@@ -2,6 +2,42 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::translation::fill_placeholders;
+
+/// Sent to users who saved a shared route when its points change.
+pub const NOTIFICATION_TYPE_ROUTE_UPDATED: &str = "route_updated";
+
+/// Sent to the route owner when the photo worker finishes a batch.
+pub const NOTIFICATION_TYPE_PHOTOS_PROCESSED: &str = "photos_processed";
+
+/// Sent to the owner of a saved search when a newly shared route matches it.
+pub const NOTIFICATION_TYPE_SAVED_SEARCH_MATCH: &str = "saved_search_match";
+
+/// Notification types users can opt out of.
+pub const CONFIGURABLE_NOTIFICATION_TYPES: &[&str] = &[NOTIFICATION_TYPE_ROUTE_UPDATED];
+
+/// Texts of the notification templates in the default locale. Other locales
+/// come from the translations table under the same keys.
+pub fn default_template(template_key: &str) -> Option<&'static str> {
+    let template = match template_key {
+        "notification.like" => "{actor} liked your route \"{route}\"",
+        "notification.comment" => "{actor} commented on your route \"{route}\"",
+        "notification.rating" => "{actor} rated your route \"{route}\" with {rating}/5",
+        "notification.route_report" => "{actor} reported route \"{route}\" as {category}",
+        "notification.report_resolved" => "Your report was {status} by a moderator",
+        "notification.route_updated" => "The route \"{route}\" you saved was updated",
+        "notification.saved_search_match" => "A new route \"{route}\" matches your saved search \"{search}\"",
+        "notification.photo_processed" => "{processed} photo processed for route \"{route}\"",
+        "notification.photos_processed" => "{processed} photos processed for route \"{route}\"",
+        "notification.photo_processed_failed" => "{processed} photo processed for route \"{route}\", {failed} failed",
+        "notification.photos_processed_failed" => "{processed} photos processed for route \"{route}\", {failed} failed",
+        "notification.data_export_ready" => "Your data export is ready. The download link expires in {hours} hours: {url}",
+        "notification.data_export_failed" => "Your data export could not be prepared. Please try again later.",
+        _ => return None,
+    };
+    Some(template)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Notification {
     pub id: Uuid,
@@ -9,7 +45,12 @@ pub struct Notification {
     pub notification_type: String,
     pub route_id: Uuid,
     pub actor_name: String,
+    /// Rendered in the default locale when created; shown as is for
+    /// notifications without a template.
     pub message: String,
+    /// Translation key of the message, rendered in the reader's locale.
+    pub template_key: Option<String>,
+    pub params: Option<serde_json::Value>,
     pub is_read: bool,
     pub created_at: DateTime<Utc>,
 }
@@ -29,8 +70,29 @@ impl Notification {
             route_id,
             actor_name,
             message,
+            template_key: None,
+            params: None,
             is_read: false,
             created_at: Utc::now(),
         }
     }
+
+    /// A notification whose message is rendered from a template at read time.
+    pub fn from_template(
+        user_id: Uuid,
+        notification_type: String,
+        route_id: Uuid,
+        actor_name: String,
+        template_key: &str,
+        params: serde_json::Value,
+    ) -> Self {
+        let message = default_template(template_key)
+            .and_then(|template| fill_placeholders(template, &params))
+            .unwrap_or_else(|| template_key.to_string());
+        Self {
+            template_key: Some(template_key.to_string()),
+            params: Some(params),
+            ..Self::new(user_id, notification_type, route_id, actor_name, message)
+        }
+    }
 }
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Per-user storage limits for routes. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RouteQuota {
+    pub max_routes: Option<i64>,
+    /// Limit on the inline photo payloads held in the user's routes, in bytes.
+    pub max_photo_bytes: Option<i64>,
+}
+
+impl RouteQuota {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_routes.is_none() && self.max_photo_bytes.is_none()
+    }
+}
+
+/// What a user currently stores, measured against [`RouteQuota`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RouteUsage {
+    pub routes: i64,
+    pub photo_bytes: i64,
+}
+
+/// A user's effective quota, the admin override it came from (if any) and
+/// current usage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteQuotaStatus {
+    pub quota: RouteQuota,
+    pub override_quota: Option<RouteQuota>,
+    pub usage: RouteUsage,
+}
+
+/// Processed photos a user holds in object storage, as recorded by the
+/// photo worker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StorageUsage {
+    pub objects: i64,
+    pub bytes: i64,
+}
+
+/// Object storage held by one user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserStorageUsage {
+    pub user_id: Uuid,
+    pub objects: i64,
+    pub bytes: i64,
+}
+
+/// Object storage across all users.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StorageTotals {
+    pub users: i64,
+    pub objects: i64,
+    pub bytes: i64,
+}
+
+/// Storage totals with the largest consumers first, for capacity planning.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageOverview {
+    #[serde(flatten)]
+    pub totals: StorageTotals,
+    pub top_users: Vec<UserStorageUsage>,
+}
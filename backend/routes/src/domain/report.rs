@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const REPORT_CATEGORIES: &[&str] = &["dangerous", "wrong_location", "spam", "copyright"];
+
+pub const REPORT_STATUS_OPEN: &str = "open";
+pub const REPORT_STATUS_RESOLVED: &str = "resolved";
+pub const REPORT_STATUS_DISMISSED: &str = "dismissed";
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RouteReport {
+    pub id: Uuid,
+    pub route_id: Uuid,
+    pub reporter_id: Uuid,
+    pub category: String,
+    pub details: Option<String>,
+    pub status: String,
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RouteReport {
+    pub fn new(route_id: Uuid, reporter_id: Uuid, category: String, details: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            route_id,
+            reporter_id,
+            category,
+            details,
+            status: REPORT_STATUS_OPEN.to_string(),
+            resolution_note: None,
+            resolved_by: None,
+            resolved_at: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_report_creation() {
+        let route_id = Uuid::new_v4();
+        let reporter_id = Uuid::new_v4();
+        let report = RouteReport::new(route_id, reporter_id, "spam".to_string(), None);
+
+        assert_eq!(report.route_id, route_id);
+        assert_eq!(report.reporter_id, reporter_id);
+        assert_eq!(report.status, REPORT_STATUS_OPEN);
+        assert!(report.resolved_at.is_none());
+    }
+}
@@ -1,77 +1,7 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum PhotoStatus {
-    Pending,
-    Processing,
-    Done,
-    Failed,
-}
-
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct PhotoData {
-    pub original: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub thumbnail_url: Option<String>,
-    pub status: PhotoStatus,
-}
-
-/// Deserializes photo field with backward compatibility.
-/// Accepts either a plain string (old format) or a PhotoData struct (new format).
-fn deserialize_photo_compat<'de, D>(deserializer: D) -> Result<Option<PhotoData>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
-    match value {
-        None => Ok(None),
-        Some(serde_json::Value::Null) => Ok(None),
-        Some(serde_json::Value::String(s)) => Ok(Some(PhotoData {
-            original: s,
-            thumbnail_url: None,
-            status: PhotoStatus::Pending,
-        })),
-        Some(obj @ serde_json::Value::Object(_)) => {
-            let photo_data: PhotoData =
-                serde_json::from_value(obj).map_err(serde::de::Error::custom)?;
-            Ok(Some(photo_data))
-        }
-        Some(other) => Err(serde::de::Error::custom(format!(
-            "expected string or object for photo, got: {}",
-            other
-        ))),
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct RoutePoint {
-    pub lat: f64,
-    pub lng: f64,
-    pub name: Option<String>,
-    pub segment_mode: Option<String>,
-    #[serde(deserialize_with = "deserialize_photo_compat", default)]
-    pub photo: Option<PhotoData>,
-}
-
-#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
-pub struct Route {
-    pub id: Uuid,
-    pub user_id: Uuid,
-    pub name: String,
-    #[sqlx(json)]
-    pub points: Vec<RoutePoint>,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-    pub share_token: Option<Uuid>,
-    pub category_ids: Vec<Uuid>,
-    pub start_location: Option<String>,
-    pub end_location: Option<String>,
-    pub seasons: Vec<String>,
-    pub description: Option<String>,
-}
+pub use guide_helper_domain::route::{PhotoStatus, PointAddress, Route, RoutePoint, RouteVisibility};
 
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct ExploreRouteRow {
@@ -79,12 +9,52 @@ pub struct ExploreRouteRow {
     pub name: String,
     pub points_count: i64,
     pub created_at: DateTime<Utc>,
-    pub share_token: Uuid,
+    /// None only for unshared routes listed to their owner.
+    pub share_token: Option<Uuid>,
     pub likes_count: i64,
+    pub bookmarks_count: i64,
     pub avg_rating: f64,
     pub ratings_count: i64,
+    /// Approved comments; ones awaiting moderation are not counted.
+    pub comments_count: i64,
     pub category_ids: Vec<Uuid>,
     pub seasons: Vec<String>,
+    pub start_location: Option<String>,
+    pub end_location: Option<String>,
+    pub cover_url: Option<String>,
+    pub cover_thumbnail_url: Option<String>,
+    /// Coordinates and segment modes of the points, for the route stats.
+    #[sqlx(json)]
+    pub path: Vec<RoutePoint>,
+}
+
+/// Explore result counts per filter value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExploreFacets {
+    pub categories: Vec<(Uuid, i64)>,
+    pub difficulty: Vec<(String, i64)>,
+}
+
+/// Narrows the owner's own route list.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserRoutesFilter {
+    pub include_archived: bool,
+    /// Part of the route name.
+    pub search: Option<String>,
+    pub category_id: Option<Uuid>,
+    /// Inclusive lower bound of `created_at`.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Exclusive upper bound of `created_at`.
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only routes in this collection.
+    pub collection_id: Option<Uuid>,
+}
+
+/// Photo chosen by the owner to represent the route on explore cards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteCover {
+    pub url: String,
+    pub thumbnail_url: Option<String>,
 }
 
 #[derive(Debug, Clone, sqlx::FromRow)]
@@ -97,179 +67,3 @@ pub struct AdminRouteRow {
     pub share_token: Option<Uuid>,
     pub category_ids: Vec<Uuid>,
 }
-
-impl Route {
-    pub fn new(user_id: Uuid, name: String, points: Vec<RoutePoint>, category_ids: Vec<Uuid>, seasons: Vec<String>) -> Self {
-        let now = Utc::now();
-        Self {
-            id: Uuid::new_v4(),
-            user_id,
-            name,
-            points,
-            created_at: now,
-            updated_at: now,
-            share_token: None,
-            category_ids,
-            start_location: None,
-            end_location: None,
-            seasons,
-            description: None,
-        }
-    }
-
-    pub fn update(&mut self, name: Option<String>, points: Option<Vec<RoutePoint>>, category_ids: Option<Vec<Uuid>>, seasons: Option<Vec<String>>, description: Option<String>) {
-        if let Some(n) = name {
-            self.name = n;
-        }
-        if let Some(p) = points {
-            self.points = p;
-        }
-        if let Some(c) = category_ids {
-            self.category_ids = c;
-        }
-        if let Some(s) = seasons {
-            self.seasons = s;
-        }
-        if description.is_some() {
-            self.description = description;
-        }
-        self.updated_at = Utc::now();
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_route_creation() {
-        let user_id = Uuid::new_v4();
-        let points = vec![
-            RoutePoint {
-                lat: 55.7558,
-                lng: 37.6173,
-                name: Some("Moscow".to_string()),
-                segment_mode: None,
-                photo: None,
-            },
-            RoutePoint {
-                lat: 59.9343,
-                lng: 30.3351,
-                name: Some("Saint Petersburg".to_string()),
-                segment_mode: Some("auto".to_string()),
-                photo: Some(PhotoData {
-                    original: "data:image/png;base64,test".to_string(),
-                    thumbnail_url: None,
-                    status: PhotoStatus::Pending,
-                }),
-            },
-        ];
-
-        let route = Route::new(user_id, "Test Route".to_string(), points.clone(), vec![], vec![]);
-
-        assert_eq!(route.user_id, user_id);
-        assert_eq!(route.name, "Test Route");
-        assert_eq!(route.points.len(), 2);
-        assert_eq!(route.created_at, route.updated_at);
-        assert!(route.category_ids.is_empty());
-    }
-
-    #[test]
-    fn test_route_update() {
-        let user_id = Uuid::new_v4();
-        let points = vec![RoutePoint {
-            lat: 55.7558,
-            lng: 37.6173,
-            name: None,
-            segment_mode: None,
-            photo: None,
-        }];
-        let mut route = Route::new(user_id, "Original".to_string(), points, vec![], vec![]);
-        let original_updated_at = route.updated_at;
-
-        std::thread::sleep(std::time::Duration::from_millis(10));
-
-        let new_points = vec![
-            RoutePoint {
-                lat: 55.7558,
-                lng: 37.6173,
-                name: None,
-                segment_mode: None,
-                photo: None,
-            },
-            RoutePoint {
-                lat: 59.9343,
-                lng: 30.3351,
-                name: None,
-                segment_mode: Some("auto".to_string()),
-                photo: None,
-            },
-        ];
-        route.update(Some("Updated".to_string()), Some(new_points), None, None, None);
-
-        assert_eq!(route.name, "Updated");
-        assert_eq!(route.points.len(), 2);
-        assert!(route.updated_at > original_updated_at);
-    }
-
-    #[test]
-    fn test_route_point_serialization() {
-        let point = RoutePoint {
-            lat: 55.7558,
-            lng: 37.6173,
-            name: Some("Moscow".to_string()),
-            segment_mode: Some("auto".to_string()),
-            photo: Some(PhotoData {
-                original: "data:image/png;base64,test".to_string(),
-                thumbnail_url: None,
-                status: PhotoStatus::Pending,
-            }),
-        };
-
-        let json = serde_json::to_string(&point).unwrap();
-        let deserialized: RoutePoint = serde_json::from_str(&json).unwrap();
-
-        assert_eq!(point, deserialized);
-    }
-
-    #[test]
-    fn test_backward_compat_plain_string_photo() {
-        let json = r#"{"lat":55.0,"lng":37.0,"name":null,"segment_mode":null,"photo":"data:image/png;base64,abc"}"#;
-        let point: RoutePoint = serde_json::from_str(json).unwrap();
-
-        let photo = point.photo.unwrap();
-        assert_eq!(photo.original, "data:image/png;base64,abc");
-        assert_eq!(photo.status, PhotoStatus::Pending);
-        assert!(photo.thumbnail_url.is_none());
-    }
-
-    #[test]
-    fn test_backward_compat_null_photo() {
-        let json = r#"{"lat":55.0,"lng":37.0,"name":null,"segment_mode":null,"photo":null}"#;
-        let point: RoutePoint = serde_json::from_str(json).unwrap();
-        assert!(point.photo.is_none());
-    }
-
-    #[test]
-    fn test_photo_data_struct_deserialization() {
-        let json = r#"{"lat":55.0,"lng":37.0,"name":null,"segment_mode":null,"photo":{"original":"data:image/png;base64,abc","thumbnail_url":"/photos/thumb.jpg","status":"done"}}"#;
-        let point: RoutePoint = serde_json::from_str(json).unwrap();
-
-        let photo = point.photo.unwrap();
-        assert_eq!(photo.original, "data:image/png;base64,abc");
-        assert_eq!(photo.thumbnail_url, Some("/photos/thumb.jpg".to_string()));
-        assert_eq!(photo.status, PhotoStatus::Done);
-    }
-
-    #[test]
-    fn test_photo_status_serialization() {
-        let photo = PhotoData {
-            original: "test".to_string(),
-            thumbnail_url: Some("/thumb.jpg".to_string()),
-            status: PhotoStatus::Done,
-        };
-        let json = serde_json::to_string(&photo).unwrap();
-        assert!(json.contains("\"status\":\"done\""));
-        assert!(json.contains("\"thumbnail_url\":\"/thumb.jpg\""));
-    }
-}
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Elevation at a position along the route line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElevationSample {
+    /// Distance from the route start, in km.
+    pub distance_km: f64,
+    pub lat: f64,
+    pub lng: f64,
+    pub elevation_m: f64,
+}
+
+/// Cached elevation profile of a route with its summary statistics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RouteElevation {
+    pub route_id: Uuid,
+    #[sqlx(json)]
+    pub samples: Vec<ElevationSample>,
+    pub gain_m: f64,
+    pub loss_m: f64,
+    pub min_m: f64,
+    pub max_m: f64,
+    /// `updated_at` of the route the profile was computed from.
+    pub route_updated_at: DateTime<Utc>,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl RouteElevation {
+    pub fn new(route_id: Uuid, samples: Vec<ElevationSample>, route_updated_at: DateTime<Utc>) -> Self {
+        let (mut gain_m, mut loss_m) = (0.0, 0.0);
+        for pair in samples.windows(2) {
+            let delta = pair[1].elevation_m - pair[0].elevation_m;
+            if delta > 0.0 {
+                gain_m += delta;
+            } else {
+                loss_m -= delta;
+            }
+        }
+        let min_m = samples.iter().map(|s| s.elevation_m).fold(f64::INFINITY, f64::min);
+        let max_m = samples.iter().map(|s| s.elevation_m).fold(f64::NEG_INFINITY, f64::max);
+
+        Self {
+            route_id,
+            samples,
+            gain_m,
+            loss_m,
+            min_m: if min_m.is_finite() { min_m } else { 0.0 },
+            max_m: if max_m.is_finite() { max_m } else { 0.0 },
+            route_updated_at,
+            computed_at: Utc::now(),
+        }
+    }
+
+    /// The profile walked from the end, for a route that was reversed at
+    /// `route_updated_at`. Gain and loss swap.
+    pub fn reversed(&self, route_updated_at: DateTime<Utc>) -> Self {
+        let total_km = self.samples.last().map_or(0.0, |s| s.distance_km);
+        let samples = self
+            .samples
+            .iter()
+            .rev()
+            .map(|s| ElevationSample {
+                distance_km: total_km - s.distance_km,
+                ..s.clone()
+            })
+            .collect();
+        Self::new(self.route_id, samples, route_updated_at)
+    }
+
+    /// True when the route changed after the profile was computed.
+    pub fn is_stale(&self, route_updated_at: DateTime<Utc>) -> bool {
+        self.route_updated_at != route_updated_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(distance_km: f64, elevation_m: f64) -> ElevationSample {
+        ElevationSample {
+            distance_km,
+            lat: 55.0,
+            lng: 37.0,
+            elevation_m,
+        }
+    }
+
+    #[test]
+    fn test_route_elevation_stats() {
+        let samples = vec![sample(0.0, 100.0), sample(1.0, 150.0), sample(2.0, 120.0), sample(3.0, 180.0)];
+        let elevation = RouteElevation::new(Uuid::new_v4(), samples, Utc::now());
+
+        assert!((elevation.gain_m - 110.0).abs() < 1e-9);
+        assert!((elevation.loss_m - 30.0).abs() < 1e-9);
+        assert_eq!(elevation.min_m, 100.0);
+        assert_eq!(elevation.max_m, 180.0);
+    }
+
+    #[test]
+    fn test_reversed_swaps_gain_and_loss() {
+        let samples = vec![sample(0.0, 100.0), sample(1.0, 150.0), sample(3.0, 120.0)];
+        let elevation = RouteElevation::new(Uuid::new_v4(), samples, Utc::now());
+
+        let reversed = elevation.reversed(Utc::now());
+
+        assert_eq!(reversed.gain_m, elevation.loss_m);
+        assert_eq!(reversed.loss_m, elevation.gain_m);
+        let distances: Vec<f64> = reversed.samples.iter().map(|s| s.distance_km).collect();
+        assert_eq!(distances, vec![0.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_route_elevation_empty() {
+        let elevation = RouteElevation::new(Uuid::new_v4(), vec![], Utc::now());
+        assert_eq!(elevation.gain_m, 0.0);
+        assert_eq!(elevation.min_m, 0.0);
+        assert_eq!(elevation.max_m, 0.0);
+    }
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Path between two consecutive route points.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SegmentGeometry {
+    pub from_index: usize,
+    pub to_index: usize,
+    /// Routing profile used ("foot", "bike", "car"), or "straight" for manual segments.
+    pub profile: String,
+    /// True when the path follows the road network, false for straight lines.
+    pub snapped: bool,
+    pub distance_km: f64,
+    /// [lng, lat] pairs, GeoJSON order.
+    pub coordinates: Vec<[f64; 2]>,
+}
+
+/// Computed road/path geometry of a route, stored for rendering and stats.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RouteGeometry {
+    pub route_id: Uuid,
+    #[sqlx(json)]
+    pub segments: Vec<SegmentGeometry>,
+    pub distance_km: f64,
+    /// `updated_at` of the route the geometry was computed from.
+    pub route_updated_at: DateTime<Utc>,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl RouteGeometry {
+    pub fn new(route_id: Uuid, segments: Vec<SegmentGeometry>, route_updated_at: DateTime<Utc>) -> Self {
+        let distance_km = segments.iter().map(|s| s.distance_km).sum();
+        Self {
+            route_id,
+            segments,
+            distance_km,
+            route_updated_at,
+            computed_at: Utc::now(),
+        }
+    }
+
+    /// True when the route changed after the geometry was computed.
+    pub fn is_stale(&self, route_updated_at: DateTime<Utc>) -> bool {
+        self.route_updated_at != route_updated_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(distance_km: f64) -> SegmentGeometry {
+        SegmentGeometry {
+            from_index: 0,
+            to_index: 1,
+            profile: "foot".to_string(),
+            snapped: true,
+            distance_km,
+            coordinates: vec![[37.0, 55.0], [37.1, 55.1]],
+        }
+    }
+
+    #[test]
+    fn test_route_geometry_sums_distance() {
+        let updated_at = Utc::now();
+        let geometry = RouteGeometry::new(Uuid::new_v4(), vec![segment(1.5), segment(2.0)], updated_at);
+
+        assert!((geometry.distance_km - 3.5).abs() < 1e-9);
+        assert!(!geometry.is_stale(updated_at));
+        assert!(geometry.is_stale(updated_at + chrono::Duration::seconds(1)));
+    }
+}
@@ -0,0 +1 @@
+pub use guide_helper_domain::route_stats::*;
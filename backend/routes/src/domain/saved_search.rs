@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::route::Route;
+
+/// Bounding box a route must pass through.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SearchArea {
+    pub min_lat: f64,
+    pub min_lng: f64,
+    pub max_lat: f64,
+    pub max_lng: f64,
+}
+
+impl SearchArea {
+    pub fn contains(&self, lat: f64, lng: f64) -> bool {
+        (self.min_lat..=self.max_lat).contains(&lat) && (self.min_lng..=self.max_lng).contains(&lng)
+    }
+}
+
+/// An explore filter the user gets alerts for. Routes shared after
+/// `last_checked_at` are matched against it by the alert job.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SavedSearch {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub search: Option<String>,
+    pub category_id: Option<Uuid>,
+    pub min_lat: Option<f64>,
+    pub min_lng: Option<f64>,
+    pub max_lat: Option<f64>,
+    pub max_lng: Option<f64>,
+    pub last_checked_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SavedSearch {
+    pub fn new(
+        user_id: Uuid,
+        name: String,
+        search: Option<String>,
+        category_id: Option<Uuid>,
+        area: Option<SearchArea>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            name,
+            search,
+            category_id,
+            min_lat: area.map(|a| a.min_lat),
+            min_lng: area.map(|a| a.min_lng),
+            max_lat: area.map(|a| a.max_lat),
+            max_lng: area.map(|a| a.max_lng),
+            last_checked_at: now,
+            created_at: now,
+        }
+    }
+
+    pub fn area(&self) -> Option<SearchArea> {
+        Some(SearchArea {
+            min_lat: self.min_lat?,
+            min_lng: self.min_lng?,
+            max_lat: self.max_lat?,
+            max_lng: self.max_lng?,
+        })
+    }
+
+    /// Same rules as explore: the text is looked up in the route name,
+    /// case-insensitively. The area matches when any point lies inside it.
+    pub fn matches(&self, route: &Route) -> bool {
+        let text_matches = self
+            .search
+            .as_deref()
+            .is_none_or(|s| route.name.to_lowercase().contains(&s.to_lowercase()));
+        let category_matches = self.category_id.is_none_or(|id| route.category_ids.contains(&id));
+        let area_matches = self
+            .area()
+            .is_none_or(|area| route.points.iter().any(|p| area.contains(p.lat, p.lng)));
+        text_matches && category_matches && area_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::route::RoutePoint;
+
+    fn route(name: &str, lat: f64, lng: f64) -> Route {
+        let point = RoutePoint { lat, lng, name: None, segment_mode: None, photo: None, address: None };
+        Route::new(Uuid::new_v4(), name.to_string(), vec![point], vec![], vec![])
+    }
+
+    #[test]
+    fn test_matches_text_case_insensitively() {
+        let search = SavedSearch::new(Uuid::new_v4(), "Kremlins".to_string(), Some("kremlin".to_string()), None, None);
+
+        assert!(search.matches(&route("Kazan Kremlin walk", 55.8, 49.1)));
+        assert!(!search.matches(&route("Old town", 55.8, 49.1)));
+    }
+
+    #[test]
+    fn test_matches_area_and_category() {
+        let area = SearchArea { min_lat: 55.0, min_lng: 48.0, max_lat: 56.0, max_lng: 50.0 };
+        let category_id = Uuid::new_v4();
+        let search = SavedSearch::new(Uuid::new_v4(), "Kazan".to_string(), None, Some(category_id), Some(area));
+        let mut inside = route("Lake loop", 55.8, 49.1);
+
+        assert!(!search.matches(&inside));
+        inside.category_ids = vec![category_id];
+        assert!(search.matches(&inside));
+
+        let mut outside = route("Lake loop", 59.9, 30.3);
+        outside.category_ids = vec![category_id];
+        assert!(!search.matches(&outside));
+    }
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An extra link to a route, labelled by its owner ("sent to client X") so
+/// it can be revoked on its own. Only `id` is stored; the URL token is signed
+/// from it, see `usecase::share_links::ShareTokenSigner`.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ShareLink {
+    pub id: Uuid,
+    pub route_id: Uuid,
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ShareLink {
+    pub fn new(route_id: Uuid, label: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            route_id,
+            label,
+            created_at: Utc::now(),
+            revoked_at: None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+}
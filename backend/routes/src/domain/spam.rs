@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const SPAM_CONTENT_COMMENT: &str = "comment";
+pub const SPAM_CONTENT_ROUTE: &str = "route";
+/// Chat messages are flagged by conversation.
+pub const SPAM_CONTENT_CHAT_CONVERSATION: &str = "chat_conversation";
+
+pub const SPAM_REASON_TOO_MANY_LINKS: &str = "too_many_links";
+pub const SPAM_REASON_DUPLICATE_TEXT: &str = "duplicate_text";
+pub const SPAM_REASON_SUBMITTED_TOO_FAST: &str = "submitted_too_fast";
+/// Matched the blocked-term filter in `flag` mode.
+pub const SPAM_REASON_BLOCKED_TERM: &str = "blocked_term";
+
+/// Content that looked like spam or contained blocked terms when it was
+/// posted. The content stays published; the flag only puts it in the
+/// moderation queue.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SpamFlag {
+    pub id: Uuid,
+    pub content_type: String,
+    pub content_id: Uuid,
+    pub user_id: Uuid,
+    /// Comma-separated `SPAM_REASON_*` values.
+    pub reasons: String,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SpamFlag {
+    pub fn new(content_type: &str, content_id: Uuid, user_id: Uuid, reasons: &[&str]) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            content_type: content_type.to_string(),
+            content_id,
+            user_id,
+            reasons: reasons.join(","),
+            reviewed_by: None,
+            reviewed_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn reason_list(&self) -> Vec<String> {
+        self.reasons.split(',').filter(|r| !r.is_empty()).map(String::from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spam_flag_creation() {
+        let content_id = Uuid::new_v4();
+        let flag = SpamFlag::new(
+            SPAM_CONTENT_COMMENT,
+            content_id,
+            Uuid::new_v4(),
+            &[SPAM_REASON_TOO_MANY_LINKS, SPAM_REASON_DUPLICATE_TEXT],
+        );
+
+        assert_eq!(flag.content_id, content_id);
+        assert_eq!(flag.reason_list(), vec!["too_many_links", "duplicate_text"]);
+        assert!(flag.reviewed_at.is_none());
+    }
+}
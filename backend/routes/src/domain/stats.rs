@@ -0,0 +1,79 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A count for one UTC calendar day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DailyCount {
+    pub day: NaiveDate,
+    pub count: i64,
+}
+
+/// Assistant chat activity over a period. `messages` counts only what users
+/// sent, not the assistant's replies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ChatUsage {
+    pub messages: i64,
+    pub users: i64,
+    pub conversations: i64,
+}
+
+/// Point photos across all routes, by processing status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PhotoVolume {
+    pub pending: i64,
+    pub processing: i64,
+    pub done: i64,
+    pub failed: i64,
+}
+
+/// Engagement over the last `days` days, for the admin dashboard. Daily
+/// series are zero-filled and oldest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageStats {
+    pub days: i64,
+    pub daily_active_users: i64,
+    pub weekly_active_users: i64,
+    pub active_users_per_day: Vec<DailyCount>,
+    pub new_routes_per_day: Vec<DailyCount>,
+    pub chat: ChatUsage,
+    pub photos: PhotoVolume,
+}
+
+/// How often the assistant ran a tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ToolCallCount {
+    pub tool: String,
+    pub count: i64,
+}
+
+/// Assistant turns over a period: how many, how many failed and how long
+/// they took on average.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ChatTurnSummary {
+    pub turns: i64,
+    pub errors: i64,
+    pub avg_latency_ms: f64,
+}
+
+/// Messages one user sent to the assistant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ChatUserVolume {
+    pub user_id: Uuid,
+    pub messages: i64,
+}
+
+/// Assistant operations over the last `days` days, for the admin chat
+/// dashboard. `messages_per_day` counts user messages, zero-filled and
+/// oldest first; `error_rate` is the share of failed turns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatOpsStats {
+    pub days: i64,
+    pub messages_per_day: Vec<DailyCount>,
+    pub turns: i64,
+    pub errors: i64,
+    pub error_rate: f64,
+    pub avg_latency_ms: f64,
+    pub tool_calls: Vec<ToolCallCount>,
+    pub top_users: Vec<ChatUserVolume>,
+}
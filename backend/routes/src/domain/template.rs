@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A route offered as a starting point for new routes. Templates flagged by
+/// admins are public; the ones users flag on their own routes are only
+/// listed for them.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RouteTemplate {
+    pub route_id: Uuid,
+    pub owner_id: Uuid,
+    pub public: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RouteTemplate {
+    pub fn new(route_id: Uuid, owner_id: Uuid, public: bool) -> Self {
+        Self {
+            route_id,
+            owner_id,
+            public,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// A template with the route fields shown in the template list.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TemplateSummary {
+    pub route_id: Uuid,
+    pub owner_id: Uuid,
+    pub public: bool,
+    pub name: String,
+    pub description: Option<String>,
+    pub start_location: Option<String>,
+    pub end_location: Option<String>,
+    pub points_count: i64,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Locales the server has texts for, in order of preference.
+pub const SUPPORTED_LOCALES: &[&str] = &["en", "ru"];
+
+/// Used when the client accepts none of the supported locales.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Text of a server-generated string or category name in one locale.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Translation {
+    pub key: String,
+    pub locale: String,
+    pub value: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Translation {
+    pub fn new(key: String, locale: String, value: String) -> Self {
+        Self {
+            key,
+            locale,
+            value,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+pub fn category_key(category_id: uuid::Uuid) -> String {
+    format!("category.{}", category_id)
+}
+
+pub fn difficulty_key(level: &str) -> String {
+    format!("difficulty.{}", level)
+}
+
+/// Fills the `{name}` placeholders of a template from a JSON object. None
+/// when a placeholder has no value.
+pub fn fill_placeholders(template: &str, params: &serde_json::Value) -> Option<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        let value = match params.get(&rest[start + 1..end])? {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    rendered.push_str(rest);
+    Some(rendered)
+}
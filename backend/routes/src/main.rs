@@ -7,63 +7,155 @@ mod repository;
 mod telemetry;
 mod usecase;
 
-use std::collections::HashMap;
 use std::sync::Arc;
 
 use axum::{
-    extract::State,
+    extract::{DefaultBodyLimit, State},
     middleware,
-    routing::{delete, get, post, put},
-    Router,
+    routing::{delete, get, patch, post, put},
+    Json, Router,
 };
-use tokio::sync::{broadcast, RwLock};
+use guide_helper_api::events;
+use guide_helper_api::photo::{
+    PhotoCompletedEvent, PHOTO_COMPLETED_CONSUMER_NAME, PHOTO_COMPLETED_STREAM_NAME, PHOTO_COMPLETED_SUBJECT_PREFIX,
+    PHOTO_PROCESS_SUBJECT, PHOTO_STREAM_NAME,
+};
+use guide_helper_auth_core::{require_role, JwtService, RequireRole};
 use uuid::Uuid;
-use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::EnvFilter;
 
-use crate::delivery::http::v1::admin::{get_routes_stats, list_admin_routes, list_admin_comments};
+use crate::delivery::http::cache::{public_cache_control, set_cache_control, shared_cache_control};
+use crate::delivery::http::load_shed::shed_load;
+use crate::delivery::http::throttle::{throttle_by_ip, IpThrottle, TrustedProxies};
+use crate::delivery::http::v1::activity::{list_route_activity, record_activity};
+use crate::delivery::http::v1::admin::{delete_route_quota, get_admin_chat_stats, get_admin_stats, get_route_quota, get_routes_stats, get_storage_overview, list_admin_routes, list_admin_comments, search_admin_comments, set_route_quota};
+use crate::delivery::http::v1::blocks::{block_user, list_blocked_users, unblock_user};
 use crate::delivery::http::v1::bookmarks::{toggle_bookmark, get_user_bookmark_status, list_bookmarks};
 use crate::delivery::http::v1::categories::{list_categories, create_category, update_category, delete_category};
-use crate::delivery::http::v1::chat::{send_chat_message, send_chat_message_stream, get_chat_history, list_conversations, delete_conversation, delete_message, chat_health};
-use crate::delivery::http::v1::notifications::{list_notifications, get_unread_count, mark_as_read, mark_all_as_read};
-use crate::delivery::http::v1::settings::{get_difficulty_thresholds, set_difficulty_thresholds};
-use crate::delivery::http::v1::comments::{count_comments, create_comment, delete_comment, list_comments};
+use crate::delivery::http::v1::chat::{send_chat_message, send_chat_message_stream, confirm_chat_action, create_chat_attachment, get_chat_history, list_conversations, update_conversation, delete_conversation, delete_message, share_conversation, unshare_conversation, flag_conversation, list_flagged_conversations, review_flagged_conversation, get_shared_conversation, get_chat_preferences, update_chat_preferences, chat_health};
+use crate::delivery::http::v1::notifications::{list_notifications, get_unread_count, mark_as_read, mark_all_as_read, get_notification_preferences, update_notification_preferences};
+use crate::delivery::http::v1::search::{reindex_search, search_routes};
+use crate::delivery::http::v1::spam::{list_spam_flags, review_spam_flag};
+use crate::delivery::http::v1::collections::{add_collection_route, create_collection, delete_collection, list_collections, remove_collection_route, rename_collection};
+use crate::delivery::http::v1::saved_searches::{create_saved_search, delete_saved_search, list_saved_searches};
+use crate::delivery::http::v1::share_links::{create_share_link, list_share_links, revoke_share_link};
+use crate::delivery::http::v1::templates::{list_templates, remove_route_template, set_route_template};
+use crate::delivery::http::v1::translations::{delete_translation, list_translations, set_translation};
+use crate::delivery::http::v1::settings::{get_chat_system_prompt, get_content_filter, get_difficulty_thresholds, get_photo_pipeline, set_chat_system_prompt, set_content_filter, set_difficulty_thresholds, set_photo_pipeline};
+use crate::delivery::http::v1::comments::{approve_comment, count_comments, create_comment, delete_comment, get_comment_policy, list_comments, list_pending_comments, search_comments, set_comment_policy};
 use crate::delivery::http::v1::likes::{get_like_count, get_user_like_status, toggle_like};
-use crate::delivery::http::v1::middleware::auth_middleware;
+use crate::delivery::http::v1::me::{get_storage_usage, request_data_export};
+use crate::delivery::http::v1::middleware::{
+    auth_middleware, capture_request_span, describe_request_metrics, make_request_span, record_user_middleware,
+    track_metrics, REQUEST_DURATION_BUCKETS,
+};
+use crate::delivery::http::v1::reports::{list_reports, report_route, resolve_report};
 use crate::delivery::http::v1::ratings::{get_rating_aggregate, get_user_rating, remove_rating, set_rating};
-use crate::delivery::http::v1::routes::{create_route, delete_route, disable_share, enable_share, explore_routes, generate_description, get_route, get_shared_route, import_route_from_geojson, list_routes, save_description, update_route};
+use crate::delivery::http::v1::routes::{archive_route, compute_route_segments, create_route, retry_failed_photos, delete_route, set_route_cover, disable_share, enable_share, explore_routes, export_offline_bundle, export_route_pdf, generate_description, geocode_route_points, get_route_elevation, get_route_segments, get_route_stats, get_route, get_shared_route, import_route_from_geojson, import_route_from_url, instantiate_template, list_routes, merge_routes, patch_route_points, reverse_route, split_route, save_description, set_route_visibility, unarchive_route, update_route};
 use crate::delivery::http::v1::ws::websocket_handler;
-use crate::repository::postgres::{create_pool, PostgresBookmarkRepository, PostgresCategoryRepository, PostgresChatMessageRepository, PostgresCommentRepository, PostgresLikeRepository, PostgresNotificationRepository, PostgresRatingRepository, PostgresRouteRepository, PostgresSettingsRepository};
+use crate::delivery::http::v2::routes as routes_v2;
+use crate::repository::database::{DatabasePool, DbPools, DbBlockRepository, DbBookmarkRepository, DbCategoryRepository, DbChatMessageRepository, DbCommentRepository, DbLikeRepository, DbNotificationPreferenceRepository, DbNotificationRepository, DbRatingRepository, DbReportRepository, DbRouteElevationRepository, DbRouteActivityRepository, DbRouteCollectionRepository, DbRouteGeometryRepository, DbRouteRepository, DbRouteTemplateRepository, DbSavedSearchRepository, DbSettingsRepository, DbShareLinkRepository, DbSpamFlagRepository, DbStatsRepository, DbTranslationRepository, DbUserDataRepository, MigrationStatus};
+use crate::repository::s3::S3ObjectStorage;
+use crate::usecase::activity::ActivityUseCase;
+use crate::usecase::blocks::BlocksUseCase;
+use crate::usecase::collections::CollectionsUseCase;
 use crate::usecase::bookmarks::BookmarksUseCase;
 use crate::usecase::categories::CategoriesUseCase;
 use crate::usecase::chat::ChatUseCase;
+use crate::usecase::chat_attachments::ChatAttachmentsUseCase;
+use crate::usecase::chat_prompt::{PromptVars, SettingsPromptSource};
+use crate::usecase::circuit_breaker::CircuitBreaker;
 use crate::usecase::comments::CommentsUseCase;
+use crate::usecase::content_filter::ContentFilter;
+use crate::usecase::contracts::RouteRepository;
+use crate::usecase::elevation::ElevationClient;
+use crate::usecase::error::UsecaseError;
+use crate::usecase::geocoder::build_geocoder;
 use crate::usecase::notifications::NotificationsUseCase;
-use crate::usecase::jwt::JwtService;
 use crate::usecase::likes::LikesUseCase;
+use crate::usecase::meilisearch::MeilisearchClient;
 use crate::usecase::openai::OpenAIClient;
+use crate::usecase::osrm::OsrmClient;
+use crate::usecase::rate_limit::{build_rate_limit_store, RateLimitStore};
 use crate::usecase::ratings::RatingsUseCase;
-use crate::usecase::routes::RoutesUseCase;
+use crate::usecase::realtime::{build_realtime_hub, RealtimeHub};
+use crate::usecase::reports::ReportsUseCase;
+use crate::usecase::route_elevation::ElevationUseCase;
+use crate::usecase::route_export::RouteExporter;
+use crate::usecase::route_segments::RouteSegmentsUseCase;
+use crate::usecase::route_updates::{RouteUpdatedEvent, RouteUpdatesUseCase, ROUTE_UPDATED_SUBJECT};
+use crate::domain::activity::ACTIVITY_PHOTOS_PROCESSED;
+use crate::domain::quota::RouteQuota;
+use crate::usecase::routes::{RouteLimits, RoutesUseCase};
+use crate::usecase::sanitize::{HtmlPolicy, TextSanitizer};
+use crate::usecase::saved_searches::SavedSearchesUseCase;
+use crate::usecase::search::{RouteChangedEvent, SearchUseCase, ROUTE_CHANGED_SUBJECT};
 use crate::usecase::settings::SettingsUseCase;
+use crate::usecase::share_links::{ShareLinksUseCase, ShareTokenSigner};
+use crate::usecase::spam::SpamUseCase;
+use crate::usecase::stats::StatsUseCase;
+use crate::usecase::takeout::{TakeoutUseCase, MAX_LINK_TTL};
+use crate::usecase::templates::TemplatesUseCase;
+use crate::usecase::translations::TranslationsUseCase;
+use crate::usecase::url_import::{UrlImportPolicy, UrlImporter};
 
 pub struct AppState {
-    pub routes_usecase: RoutesUseCase<PostgresRouteRepository>,
-    pub comments_usecase: CommentsUseCase<PostgresCommentRepository, PostgresRouteRepository>,
-    pub likes_usecase: LikesUseCase<PostgresLikeRepository, PostgresRouteRepository>,
-    pub ratings_usecase: RatingsUseCase<PostgresRatingRepository, PostgresRouteRepository>,
-    pub bookmarks_usecase: BookmarksUseCase<PostgresBookmarkRepository, PostgresRouteRepository>,
-    pub settings_usecase: SettingsUseCase<PostgresSettingsRepository>,
-    pub categories_usecase: CategoriesUseCase<PostgresCategoryRepository>,
-    pub notifications_usecase: NotificationsUseCase<PostgresNotificationRepository>,
-    pub chat_usecase: ChatUseCase<PostgresChatMessageRepository, PostgresRouteRepository>,
+    pub routes_usecase: RoutesUseCase<DbRouteRepository>,
+    pub comments_usecase: CommentsUseCase<DbCommentRepository, DbRouteRepository, DbBlockRepository>,
+    pub likes_usecase: LikesUseCase<DbLikeRepository, DbRouteRepository, DbBlockRepository>,
+    pub ratings_usecase: RatingsUseCase<DbRatingRepository, DbRouteRepository, DbBlockRepository>,
+    pub blocks_usecase: BlocksUseCase<DbBlockRepository>,
+    pub reports_usecase: ReportsUseCase<DbReportRepository, DbRouteRepository>,
+    pub spam_usecase: SpamUseCase<DbSpamFlagRepository, DbCommentRepository>,
+    pub activity_usecase: ActivityUseCase<DbRouteActivityRepository, DbRouteRepository>,
+    pub templates_usecase: TemplatesUseCase<DbRouteTemplateRepository, DbRouteRepository>,
+    pub share_links_usecase: ShareLinksUseCase<DbShareLinkRepository, DbRouteRepository>,
+    pub collections_usecase: CollectionsUseCase<DbRouteCollectionRepository, DbRouteRepository>,
+    pub saved_searches_usecase:
+        SavedSearchesUseCase<DbSavedSearchRepository, DbRouteRepository, DbNotificationRepository>,
+    pub bookmarks_usecase: BookmarksUseCase<DbBookmarkRepository, DbRouteRepository>,
+    pub settings_usecase: SettingsUseCase<DbSettingsRepository>,
+    pub stats_usecase: StatsUseCase<DbStatsRepository>,
+    pub categories_usecase: CategoriesUseCase<DbCategoryRepository>,
+    pub translations_usecase: TranslationsUseCase<DbTranslationRepository>,
+    pub notifications_usecase: NotificationsUseCase<DbNotificationRepository>,
+    pub route_updates_usecase: RouteUpdatesUseCase<
+        DbBookmarkRepository,
+        DbNotificationRepository,
+        DbNotificationPreferenceRepository,
+    >,
+    pub chat_usecase: ChatUseCase<DbChatMessageRepository, DbRouteRepository, DbSettingsRepository>,
+    pub chat_attachments_usecase: ChatAttachmentsUseCase<S3ObjectStorage>,
+    pub chat_prompt: Arc<SettingsPromptSource<DbSettingsRepository>>,
+    pub content_filter: ContentFilter<DbSettingsRepository>,
+    pub search_usecase: SearchUseCase<DbRouteRepository>,
+    pub route_segments_usecase: RouteSegmentsUseCase<DbRouteRepository, DbRouteGeometryRepository>,
+    pub elevation_usecase: ElevationUseCase<DbRouteRepository, DbRouteElevationRepository>,
+    pub route_exporter: RouteExporter,
+    pub takeout_usecase: TakeoutUseCase<
+        DbRouteRepository,
+        DbCommentRepository,
+        DbRatingRepository,
+        DbChatMessageRepository,
+        DbNotificationRepository,
+        DbUserDataRepository,
+        S3ObjectStorage,
+    >,
     pub jwt_service: JwtService,
     pub metrics_handle: PrometheusHandle,
+    pub db_pool: DatabasePool,
     pub nats_client: Option<async_nats::Client>,
-    pub ws_channels: Arc<RwLock<HashMap<Uuid, broadcast::Sender<String>>>>,
-    pub chat_rate_limits: Arc<RwLock<HashMap<Uuid, (std::time::Instant, u32)>>>,
+    pub realtime: Arc<dyn RealtimeHub>,
+    pub rate_limits: Arc<dyn RateLimitStore>,
     pub chat_rate_limit_max: u32,
     pub chat_rate_limit_window_secs: u64,
+    pub import_max_bytes: usize,
+    pub import_max_points: usize,
+    pub import_max_files: usize,
+    pub url_importer: UrlImporter,
 }
 
 #[tokio::main]
@@ -83,46 +175,144 @@ async fn main() -> anyhow::Result<()> {
             otlp_endpoint: config.telemetry_otlp_endpoint.clone(),
         };
 
-        telemetry::init_telemetry_with_subscriber(&telemetry_config, env_filter)
+        telemetry::init_telemetry_with_subscriber(&telemetry_config, env_filter, config.log_format)
             .expect("failed to initialize telemetry");
     } else {
-        telemetry::init_subscriber_without_telemetry(env_filter);
+        telemetry::init_subscriber_without_telemetry(env_filter, config.log_format);
     }
 
     tracing::info!("starting the routes service");
 
     let metrics_handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full("http_request_duration_seconds".to_string()),
+            REQUEST_DURATION_BUCKETS,
+        )
+        .expect("invalid request duration buckets")
         .install_recorder()
         .expect("failed to install Prometheus recorder");
     metrics_process::Collector::default().describe();
+    describe_request_metrics();
+    usecase::metrics::describe();
+    repository::pool::describe();
+    repository::retry::describe();
     tracing::info!("prometheus metrics initialized");
 
     tracing::info!("config loaded, telemetry_enabled={}", config.telemetry_enabled);
 
-    let pool = create_pool(&config.database_url, config.database_max_connections)
-        .await
-        .expect("failed to create database pool");
+    let pools = DbPools::connect(
+        &config.database_url,
+        config.database_read_url.as_deref(),
+        &config.pool_settings(),
+    )
+    .await
+    .expect("failed to create database pool")
+    .with_retry(config.retry_policy());
+    pools.spawn_metrics();
+    let pool = pools.primary();
     tracing::info!("database pool created");
 
-    sqlx::migrate!().run(&pool).await?;
-    tracing::info!("database migrations applied");
-
-    let route_repository = PostgresRouteRepository::new(pool.clone());
-    let comment_repository = PostgresCommentRepository::new(pool.clone());
-    let route_repository_for_comments = PostgresRouteRepository::new(pool.clone());
-    let like_repository = PostgresLikeRepository::new(pool.clone());
-    let route_repository_for_likes = PostgresRouteRepository::new(pool.clone());
-    let rating_repository = PostgresRatingRepository::new(pool.clone());
-    let route_repository_for_ratings = PostgresRouteRepository::new(pool.clone());
-    let bookmark_repository = PostgresBookmarkRepository::new(pool.clone());
-    let route_repository_for_bookmarks = PostgresRouteRepository::new(pool.clone());
-    let settings_repository = PostgresSettingsRepository::new(pool.clone());
-    let category_repository = PostgresCategoryRepository::new(pool.clone());
-    let notification_repository = PostgresNotificationRepository::new(pool.clone());
-    let chat_message_repository = PostgresChatMessageRepository::new(pool.clone());
-    let route_repository_for_chat = PostgresRouteRepository::new(pool);
+    if std::env::args().any(|arg| arg == "--migrate-only") {
+        pool.migrate().await?;
+        tracing::info!("database migrations applied, exiting (--migrate-only)");
+        return Ok(());
+    }
+
+    if config.auto_migrate {
+        pool.migrate().await?;
+        tracing::info!("database migrations applied");
+    } else {
+        match pool.migration_status().await {
+            Ok(status) if status.pending > 0 || status.failed > 0 => tracing::warn!(
+                pending = status.pending,
+                failed = status.failed,
+                "auto-migration disabled and the database is behind; run `routes --migrate-only`"
+            ),
+            Ok(_) => tracing::info!("auto-migration disabled, database is up to date"),
+            Err(e) => tracing::warn!(error = %e, "auto-migration disabled, failed to read migration status"),
+        }
+    }
+
+    let route_repository = DbRouteRepository::new(pools.clone());
+    let comment_repository = DbCommentRepository::new(pools.clone());
+    let route_repository_for_comments = DbRouteRepository::new(pools.clone());
+    let like_repository = DbLikeRepository::new(pools.clone());
+    let route_repository_for_likes = DbRouteRepository::new(pools.clone());
+    let rating_repository = DbRatingRepository::new(pools.clone());
+    let route_repository_for_ratings = DbRouteRepository::new(pools.clone());
+    let block_repository = DbBlockRepository::new(pools.clone());
+    let report_repository = DbReportRepository::new(pools.clone());
+    let route_repository_for_reports = DbRouteRepository::new(pools.clone());
+    let spam_flag_repository = DbSpamFlagRepository::new(pools.clone());
+    let comment_repository_for_spam = DbCommentRepository::new(pools.clone());
+    let activity_repository = DbRouteActivityRepository::new(pools.clone());
+    let route_repository_for_activity = DbRouteRepository::new(pools.clone());
+    let template_repository = DbRouteTemplateRepository::new(pools.clone());
+    let route_repository_for_templates = DbRouteRepository::new(pools.clone());
+    let share_link_repository = DbShareLinkRepository::new(pools.clone());
+    let route_repository_for_share_links = DbRouteRepository::new(pools.clone());
+    let collection_repository = DbRouteCollectionRepository::new(pools.clone());
+    let route_repository_for_collections = DbRouteRepository::new(pools.clone());
+    let saved_search_repository = DbSavedSearchRepository::new(pools.clone());
+    let route_repository_for_saved_searches = DbRouteRepository::new(pools.clone());
+    let notification_repository_for_saved_searches = DbNotificationRepository::new(pools.clone());
+    let bookmark_repository = DbBookmarkRepository::new(pools.clone());
+    let route_repository_for_bookmarks = DbRouteRepository::new(pools.clone());
+    let settings_repository = DbSettingsRepository::new(pools.clone());
+    let settings_repository_for_chat = DbSettingsRepository::new(pools.clone());
+    let settings_repository_for_filter = DbSettingsRepository::new(pools.clone());
+    let stats_repository = DbStatsRepository::new(pools.clone());
+    let category_repository = DbCategoryRepository::new(pools.clone());
+    let translation_repository = DbTranslationRepository::new(pools.clone());
+    let notification_repository = DbNotificationRepository::new(pools.clone());
+    let bookmark_repository_for_updates = DbBookmarkRepository::new(pools.clone());
+    let notification_repository_for_updates = DbNotificationRepository::new(pools.clone());
+    let notification_preference_repository = DbNotificationPreferenceRepository::new(pools.clone());
+    let chat_message_repository = DbChatMessageRepository::new(pools.clone());
+    let route_repository_for_chat = DbRouteRepository::new(pools.clone());
+    let route_repository_for_search = DbRouteRepository::new(pools.clone());
+    let route_repository_for_segments = DbRouteRepository::new(pools.clone());
+    let route_geometry_repository = DbRouteGeometryRepository::new(pools.clone());
+    let route_repository_for_elevation = DbRouteRepository::new(pools.clone());
+    let route_elevation_repository = DbRouteElevationRepository::new(pools.clone());
+    let route_repository_for_takeout = DbRouteRepository::new(pools.clone());
+    let comment_repository_for_takeout = DbCommentRepository::new(pools.clone());
+    let rating_repository_for_takeout = DbRatingRepository::new(pools.clone());
+    let chat_message_repository_for_takeout = DbChatMessageRepository::new(pools.clone());
+    let notification_repository_for_takeout = DbNotificationRepository::new(pools.clone());
+    let user_data_repository_for_takeout = DbUserDataRepository::new(pools.clone());
+
+    let breaker_settings = config.breaker_settings();
+    let spam_settings = config.spam_settings();
+    let share_token_signer = match config.share_link_secret.as_deref().filter(|s| !s.is_empty()) {
+        Some(secret) => ShareTokenSigner::new(secret.as_bytes()),
+        None => {
+            tracing::warn!("SHARE_LINK_SECRET is not set, share links are invalidated whenever JWT_SECRET is rotated");
+            ShareTokenSigner::derived_from(config.jwt_secret.as_bytes())
+        }
+    };
     let jwt_service = JwtService::new(config.jwt_secret);
-    let nominatim_client = crate::usecase::nominatim::NominatimClient::new(config.nominatim_url.clone());
+    let geocoder_url = config
+        .geocoder_url
+        .clone()
+        .filter(|url| !url.trim().is_empty())
+        .or_else(|| (config.geocoder_provider == "nominatim").then(|| config.nominatim_url.clone()));
+    let geocoder = match build_geocoder(
+        &config.geocoder_provider,
+        geocoder_url,
+        config.geocoder_api_key.clone(),
+        config.geocoder_request_interval_ms.map(std::time::Duration::from_millis),
+        breaker_settings,
+    ) {
+        Ok(geocoder) => {
+            tracing::info!(provider = geocoder.name(), "geocoder configured");
+            Some(geocoder)
+        }
+        Err(e) => {
+            tracing::error!(error = %e, provider = %config.geocoder_provider, "invalid geocoder config, geocoding disabled");
+            None
+        }
+    };
     let ollama_client = config.ollama_base_url.as_ref().map(|base_url| {
         tracing::info!(%base_url, model = %config.ollama_vision_model, "Ollama vision client configured");
         OpenAIClient::new(
@@ -130,38 +320,136 @@ async fn main() -> anyhow::Result<()> {
             config.ollama_vision_model.clone(),
             "ollama".to_string(),
         )
+        .with_circuit_breaker(CircuitBreaker::new("ollama", breaker_settings))
+    });
+
+    let sanitizer = TextSanitizer::new(if config.html_escape_user_text {
+        HtmlPolicy::Escape
+    } else {
+        HtmlPolicy::Keep
     });
 
     let routes_usecase = {
-        let uc = RoutesUseCase::new(route_repository).with_nominatim(nominatim_client);
+        let uc = RoutesUseCase::new(route_repository)
+            .with_limits(RouteLimits {
+                max_points: config.route_max_points,
+                max_photo_bytes: config.route_max_photo_bytes,
+                photo_types: config
+                    .route_photo_types
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+                    .collect(),
+            })
+            .with_quota(RouteQuota {
+                max_routes: config.route_quota_max_routes,
+                max_photo_bytes: config.route_quota_max_photo_bytes,
+            })
+            .with_sanitizer(sanitizer)
+            .with_photo_retry_cooldown(std::time::Duration::from_secs(config.photo_retry_cooldown_secs))
+            .with_shared_route_cache(std::time::Duration::from_secs(config.shared_route_cache_secs));
+        let uc = match geocoder {
+            Some(geocoder) => uc.with_geocoder(geocoder),
+            None => uc,
+        };
         if let Some(client) = ollama_client {
             uc.with_ollama(client, config.ollama_vision_model.clone())
         } else {
             uc
         }
     };
-    let comments_usecase = CommentsUseCase::new(comment_repository, route_repository_for_comments);
-    let likes_usecase = LikesUseCase::new(like_repository, route_repository_for_likes);
-    let ratings_usecase = RatingsUseCase::new(rating_repository, route_repository_for_ratings);
+    let comments_usecase = CommentsUseCase::new(
+        comment_repository,
+        route_repository_for_comments,
+        DbBlockRepository::new(pools.clone()),
+    )
+    .with_sanitizer(sanitizer);
+    let likes_usecase = LikesUseCase::new(like_repository, route_repository_for_likes, DbBlockRepository::new(pools.clone()));
+    let ratings_usecase =
+        RatingsUseCase::new(rating_repository, route_repository_for_ratings, DbBlockRepository::new(pools.clone()));
+    let blocks_usecase = BlocksUseCase::new(block_repository);
+    let moderator_ids: Vec<Uuid> = config
+        .moderator_user_ids
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| match s.parse::<Uuid>() {
+            Ok(id) => Some(id),
+            Err(e) => {
+                tracing::warn!(value = %s, error = %e, "ignoring invalid moderator user id");
+                None
+            }
+        })
+        .collect();
+    tracing::info!(moderators = moderator_ids.len(), "report moderators configured");
+    let reports_usecase =
+        ReportsUseCase::new(report_repository, route_repository_for_reports, moderator_ids).with_sanitizer(sanitizer);
+    let spam_usecase =
+        SpamUseCase::new(spam_flag_repository, comment_repository_for_spam).with_settings(spam_settings);
+    let activity_usecase = ActivityUseCase::new(activity_repository, route_repository_for_activity);
+    let templates_usecase = TemplatesUseCase::new(template_repository, route_repository_for_templates);
+    let share_links_usecase = {
+        let uc = ShareLinksUseCase::new(share_link_repository, route_repository_for_share_links, share_token_signer)
+            .with_sanitizer(sanitizer);
+        if let Some(until) = config.legacy_share_tokens_until {
+            tracing::info!(%until, "accepting unsigned share tokens during migration");
+            uc.with_legacy_tokens_until(until)
+        } else {
+            uc
+        }
+    };
+    let collections_usecase =
+        CollectionsUseCase::new(collection_repository, route_repository_for_collections).with_sanitizer(sanitizer);
+    let saved_searches_usecase = SavedSearchesUseCase::new(
+        saved_search_repository,
+        route_repository_for_saved_searches,
+        notification_repository_for_saved_searches,
+    );
     let bookmarks_usecase = BookmarksUseCase::new(bookmark_repository, route_repository_for_bookmarks);
     let settings_usecase = SettingsUseCase::new(settings_repository);
+    let stats_usecase = StatsUseCase::new(stats_repository);
     let categories_usecase = CategoriesUseCase::new(category_repository);
+    let translations_usecase = TranslationsUseCase::new(translation_repository);
     let notifications_usecase = NotificationsUseCase::new(notification_repository);
+    let route_updates_usecase = RouteUpdatesUseCase::new(
+        bookmark_repository_for_updates,
+        notification_repository_for_updates,
+        notification_preference_repository,
+    );
 
     let assistant_client = config.openai_api_key.as_ref().filter(|key| !key.trim().is_empty()).map(|key| {
         let client = OpenAIClient::new(
             config.openai_base_url.clone(),
             config.openai_model.clone(),
             key.clone(),
-        );
+        )
+        .with_circuit_breaker(CircuitBreaker::new("openai", breaker_settings))
+        .with_request_timeout(std::time::Duration::from_secs(config.openai_timeout_secs))
+        .with_max_retries(config.openai_max_retries)
+        .with_vision(config.openai_vision_enabled);
+        let client = match config.openai_fallback_model.as_ref().filter(|m| !m.trim().is_empty()) {
+            Some(model) => client.with_fallback_model(model.clone()),
+            None => client,
+        };
         tracing::info!(
             openai_base_url = %config.openai_base_url,
             openai_model = %config.openai_model,
+            openai_fallback_model = ?config.openai_fallback_model,
+            openai_vision_enabled = config.openai_vision_enabled,
             "OpenAI client configured"
         );
         client
     });
 
+    let content_filter = ContentFilter::new(
+        SettingsUseCase::new(settings_repository_for_filter),
+        std::time::Duration::from_secs(config.content_filter_cache_secs),
+    );
+    let chat_prompt = Arc::new(SettingsPromptSource::new(
+        SettingsUseCase::new(settings_repository_for_chat),
+        std::time::Duration::from_secs(config.chat_prompt_cache_secs),
+    ));
     let chat_usecase = ChatUseCase::new(
         chat_message_repository,
         route_repository_for_chat,
@@ -169,9 +457,117 @@ async fn main() -> anyhow::Result<()> {
         config.nominatim_url.clone(),
         config.chat_max_tool_iterations,
         config.chat_max_message_length,
-    );
+    )
+    .with_max_route_points(config.route_max_points)
+    .with_system_prompt(chat_prompt.clone(), PromptVars { app_name: config.app_name.clone() });
     tracing::info!("ChatUseCase initialized");
 
+    let search_usecase = {
+        let uc = SearchUseCase::new(route_repository_for_search);
+        match config.meilisearch_url.as_ref().filter(|url| !url.trim().is_empty()) {
+            Some(url) => {
+                let client = MeilisearchClient::new(
+                    url.clone(),
+                    config.meilisearch_api_key.clone(),
+                    config.meilisearch_index.clone(),
+                );
+                if let Err(e) = client.configure_index().await {
+                    tracing::warn!(error = %e, "failed to configure meilisearch index");
+                }
+                tracing::info!(meilisearch_url = %url, index = %config.meilisearch_index, "Meilisearch search engine configured");
+                uc.with_meilisearch(client)
+            }
+            None => {
+                tracing::info!("Meilisearch not configured, search uses SQL");
+                uc
+            }
+        }
+    };
+
+    let route_segments_usecase = {
+        let uc = RouteSegmentsUseCase::new(route_repository_for_segments, route_geometry_repository);
+        match config.osrm_url.as_ref().filter(|url| !url.trim().is_empty()) {
+            Some(url) => {
+                tracing::info!(osrm_url = %url, "OSRM segment routing configured");
+                uc.with_osrm(
+                    OsrmClient::new(url.clone())
+                        .with_circuit_breaker(CircuitBreaker::new("osrm", breaker_settings)),
+                )
+            }
+            None => {
+                tracing::info!("OSRM not configured, route segments use straight lines");
+                uc
+            }
+        }
+    };
+
+    let elevation_usecase = {
+        let uc = ElevationUseCase::new(route_repository_for_elevation, route_elevation_repository);
+        match config.elevation_url.as_ref().filter(|url| !url.trim().is_empty()) {
+            Some(url) => {
+                tracing::info!(elevation_url = %url, "elevation service configured");
+                uc.with_client(
+                    ElevationClient::new(url.clone())
+                        .with_circuit_breaker(CircuitBreaker::new("elevation", breaker_settings)),
+                )
+            }
+            None => {
+                tracing::info!("elevation service not configured, elevation profiles unavailable");
+                uc
+            }
+        }
+    };
+
+    let route_exporter = RouteExporter::new(
+        config.photo_fetch_base_url.clone(),
+        Some(config.export_font_path.clone()),
+    );
+
+    let export_storage = S3ObjectStorage::new(
+        &config.minio_endpoint,
+        config.minio_public_endpoint.as_deref(),
+        &config.minio_access_key,
+        &config.minio_secret_key,
+        config.export_bucket.clone(),
+    )
+    .await;
+    if let Err(e) = export_storage.ensure_bucket().await {
+        tracing::warn!(error = %e, bucket = %config.export_bucket, "failed to ensure export bucket, data exports may fail");
+    }
+    // Archives outlive their download link by less than a day
+    let export_expiry_days = config.export_link_ttl_secs.min(MAX_LINK_TTL.as_secs()).div_ceil(86400).max(1);
+    if let Err(e) = export_storage.expire_objects_after(export_expiry_days as i32).await {
+        tracing::warn!(error = %e, bucket = %config.export_bucket, "failed to set export bucket expiry, old data exports are kept");
+    }
+    let attachment_storage = S3ObjectStorage::new(
+        &config.minio_endpoint,
+        config.minio_public_endpoint.as_deref(),
+        &config.minio_access_key,
+        &config.minio_secret_key,
+        config.chat_attachments_bucket.clone(),
+    )
+    .await;
+    if let Err(e) = attachment_storage.ensure_bucket().await {
+        tracing::warn!(error = %e, bucket = %config.chat_attachments_bucket, "failed to ensure chat attachments bucket, image attachments may fail");
+    }
+    let attachment_link_ttl = std::time::Duration::from_secs(config.chat_attachment_link_ttl_secs);
+    let chat_attachments_usecase = ChatAttachmentsUseCase::new(
+        attachment_storage,
+        attachment_link_ttl,
+        attachment_link_ttl,
+    );
+
+    let takeout_usecase = TakeoutUseCase::new(
+        route_repository_for_takeout,
+        comment_repository_for_takeout,
+        rating_repository_for_takeout,
+        chat_message_repository_for_takeout,
+        notification_repository_for_takeout,
+        user_data_repository_for_takeout,
+        export_storage,
+        std::time::Duration::from_secs(config.export_link_ttl_secs),
+    );
+
     // Connect to NATS and setup JetStream
     let nats_client = match async_nats::connect(&config.nats_url).await {
         Ok(client) => {
@@ -181,8 +577,8 @@ async fn main() -> anyhow::Result<()> {
             let jetstream = async_nats::jetstream::new(client.clone());
             match jetstream
                 .get_or_create_stream(async_nats::jetstream::stream::Config {
-                    name: "PHOTOS".to_string(),
-                    subjects: vec!["photos.process".to_string()],
+                    name: PHOTO_STREAM_NAME.to_string(),
+                    subjects: vec![PHOTO_PROCESS_SUBJECT.to_string()],
                     retention: async_nats::jetstream::stream::RetentionPolicy::WorkQueue,
                     ..Default::default()
                 })
@@ -191,6 +587,19 @@ async fn main() -> anyhow::Result<()> {
                 Ok(_) => tracing::info!("NATS JetStream stream 'PHOTOS' ready"),
                 Err(e) => tracing::error!(error = %e, "failed to create NATS JetStream stream"),
             }
+            match jetstream
+                .get_or_create_stream(async_nats::jetstream::stream::Config {
+                    name: PHOTO_COMPLETED_STREAM_NAME.to_string(),
+                    subjects: vec![format!("{}*", PHOTO_COMPLETED_SUBJECT_PREFIX)],
+                    retention: async_nats::jetstream::stream::RetentionPolicy::WorkQueue,
+                    max_age: std::time::Duration::from_secs(24 * 60 * 60),
+                    ..Default::default()
+                })
+                .await
+            {
+                Ok(_) => tracing::info!("NATS JetStream stream 'PHOTOS_COMPLETED' ready"),
+                Err(e) => tracing::error!(error = %e, "failed to create photo completion stream"),
+            }
 
             Some(client)
         }
@@ -200,192 +609,434 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
-    let ws_channels: Arc<RwLock<HashMap<Uuid, broadcast::Sender<String>>>> =
-        Arc::new(RwLock::new(HashMap::new()));
+    // Clustered over NATS when it is available, so WS clients get updates
+    // whichever replica they are connected to
+    let realtime = build_realtime_hub(nats_client.clone());
+    tracing::info!(backend = realtime.name(), "realtime hub ready");
+
+    let rate_limits = build_rate_limit_store(
+        &config.rate_limit_backend,
+        nats_client.clone(),
+        std::time::Duration::from_secs(config.chat_rate_limit_window_secs.max(config.shared_rate_limit_window_secs)),
+    )
+    .await?;
 
-    let chat_rate_limits: Arc<RwLock<HashMap<Uuid, (std::time::Instant, u32)>>> =
-        Arc::new(RwLock::new(HashMap::new()));
+    let url_importer = UrlImporter::new(
+        UrlImportPolicy {
+            allow_http: config.import_url_allow_http,
+            allowed_hosts: config
+                .import_url_allowed_hosts
+                .split(',')
+                .map(|s| s.trim().trim_end_matches('.').to_ascii_lowercase())
+                .filter(|s| !s.is_empty())
+                .collect(),
+        },
+        config.import_max_bytes,
+        std::time::Duration::from_secs(config.import_url_timeout_secs),
+    )
+    .with_max_points(config.route_max_points);
 
     let shared_state = Arc::new(AppState {
         routes_usecase,
         comments_usecase,
         likes_usecase,
         ratings_usecase,
+        blocks_usecase,
+        reports_usecase,
+        spam_usecase,
+        activity_usecase,
+        templates_usecase,
+        share_links_usecase,
+        collections_usecase,
+        saved_searches_usecase,
         bookmarks_usecase,
         settings_usecase,
+        stats_usecase,
         categories_usecase,
+        translations_usecase,
         notifications_usecase,
+        route_updates_usecase,
         chat_usecase,
+        chat_attachments_usecase,
+        chat_prompt,
+        content_filter,
+        search_usecase,
+        route_segments_usecase,
+        elevation_usecase,
+        route_exporter,
+        takeout_usecase,
         jwt_service,
         metrics_handle,
+        db_pool: pool.clone(),
         nats_client,
-        ws_channels: ws_channels.clone(),
-        chat_rate_limits,
+        realtime,
+        rate_limits,
         chat_rate_limit_max: config.chat_rate_limit_max,
         chat_rate_limit_window_secs: config.chat_rate_limit_window_secs,
+        import_max_bytes: config.import_max_bytes,
+        import_max_points: config.route_max_points,
+        import_max_files: config.import_max_files,
+        url_importer,
     });
 
     // Spawn rate limiter cleanup task
     {
-        let rate_limits = shared_state.chat_rate_limits.clone();
-        let window_secs = shared_state.chat_rate_limit_window_secs;
+        let rate_limits = shared_state.rate_limits.clone();
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
             loop {
                 interval.tick().await;
-                let now = std::time::Instant::now();
-                let mut limits = rate_limits.write().await;
-                let before = limits.len();
-                limits.retain(|_, (timestamp, _)| {
-                    now.duration_since(*timestamp).as_secs() < window_secs
-                });
-                let cleaned = before - limits.len();
+                let cleaned = rate_limits.purge_expired();
                 if cleaned > 0 {
-                    tracing::info!(cleaned, remaining = limits.len(), "rate limiter cleanup completed");
+                    tracing::info!(cleaned, "rate limiter cleanup completed");
                 } else {
-                    tracing::debug!(entries = limits.len(), "rate limiter cleanup: nothing to clean");
+                    tracing::debug!("rate limiter cleanup: nothing to clean");
                 }
             }
         });
         tracing::info!("rate limiter cleanup task spawned (every 5 minutes)");
     }
 
-    // Spawn NATS subscriber for photo completion events (core NATS, not JetStream)
-    if let Some(ref client) = shared_state.nats_client {
+    // Spawn saved search alerts for newly shared routes
+    {
+        let state = shared_state.clone();
+        let period = std::time::Duration::from_secs(config.saved_search_alert_interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                match state.saved_searches_usecase.run_alerts(chrono::Utc::now()).await {
+                    Ok(notified) => tracing::debug!(notified, "saved search alerts run completed"),
+                    Err(e) => tracing::warn!(error = %e, "saved search alerts run failed"),
+                }
+            }
+        });
+        tracing::info!(interval_secs = period.as_secs(), "saved search alert task spawned");
+    }
+
+    // Spawn like/bookmark counter reconciliation
+    {
+        let state = shared_state.clone();
+        let period = std::time::Duration::from_secs(config.counter_reconcile_interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                match state.routes_usecase.reconcile_counters().await {
+                    Ok(repaired) => tracing::debug!(repaired, "route counter reconciliation completed"),
+                    Err(e) => tracing::warn!(error = %e, "route counter reconciliation failed"),
+                }
+            }
+        });
+        tracing::info!(interval_secs = period.as_secs(), "route counter reconciliation task spawned");
+    }
+
+    // Spawn search indexer fed by route change events. The replicas share a
+    // queue group, so each change is indexed once.
+    if let (Some(client), Some(_)) = (&shared_state.nats_client, shared_state.search_usecase.engine()) {
         let nats_client = client.clone();
-        let channels = ws_channels.clone();
+        let state = shared_state.clone();
         tokio::spawn(async move {
-            tracing::info!("subscribing to photos.completed.* for WS notifications");
-            match nats_client.subscribe("photos.completed.*").await {
+            match nats_client.queue_subscribe(ROUTE_CHANGED_SUBJECT, "search-indexer".to_string()).await {
                 Ok(mut subscriber) => {
-                    tracing::info!("NATS subscriber for photo completions ready");
+                    tracing::info!(subject = ROUTE_CHANGED_SUBJECT, "search indexer subscribed");
                     use futures::StreamExt;
                     while let Some(msg) = subscriber.next().await {
-                        let subject = msg.subject.as_str();
-                        let route_id_str = match subject.strip_prefix("photos.completed.") {
-                            Some(id) => id,
-                            None => {
-                                tracing::warn!(subject = %subject, "unexpected subject format");
-                                continue;
-                            }
-                        };
-                        let route_id = match route_id_str.parse::<Uuid>() {
-                            Ok(id) => id,
+                        let event: RouteChangedEvent = match events::decode(&msg.payload) {
+                            Ok(event) => event,
                             Err(e) => {
-                                tracing::warn!(
-                                    route_id = %route_id_str,
-                                    error = %e,
-                                    "failed to parse route_id from NATS subject"
-                                );
+                                tracing::warn!(error = %e, "invalid route changed event");
                                 continue;
                             }
                         };
+                        if let Err(e) = state.search_usecase.index_route(event.route_id).await {
+                            tracing::warn!(route_id = %event.route_id, error = %e, "failed to index route");
+                        }
+                    }
+                    tracing::warn!("search indexer subscriber ended");
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "failed to subscribe to route changed events");
+                }
+            }
+        });
+    }
 
-                        let payload = match String::from_utf8(msg.payload.to_vec()) {
-                            Ok(s) => s,
+    // Spawn notifier for significant edits of shared routes. The replicas
+    // share a queue group, so followers are notified once per edit.
+    if let Some(ref client) = shared_state.nats_client {
+        let nats_client = client.clone();
+        let state = shared_state.clone();
+        tokio::spawn(async move {
+            match nats_client.queue_subscribe(ROUTE_UPDATED_SUBJECT, "route-updated-notifier".to_string()).await {
+                Ok(mut subscriber) => {
+                    tracing::info!(subject = ROUTE_UPDATED_SUBJECT, "route update notifier subscribed");
+                    use futures::StreamExt;
+                    while let Some(msg) = subscriber.next().await {
+                        let event: RouteUpdatedEvent = match events::decode(&msg.payload) {
+                            Ok(event) => event,
                             Err(e) => {
-                                tracing::warn!(
-                                    route_id = %route_id,
-                                    error = %e,
-                                    "invalid UTF-8 in NATS message payload"
-                                );
+                                tracing::warn!(error = %e, "invalid route updated event");
                                 continue;
                             }
                         };
-
-                        let channels_read = channels.read().await;
-                        if let Some(tx) = channels_read.get(&route_id) {
-                            let receiver_count = tx.receiver_count();
-                            match tx.send(payload) {
-                                Ok(_) => {
-                                    tracing::info!(
-                                        route_id = %route_id,
-                                        receivers = receiver_count,
-                                        "forwarded photo completion to WS clients"
-                                    );
-                                }
-                                Err(_) => {
-                                    tracing::debug!(
-                                        route_id = %route_id,
-                                        "no active WS receivers for route"
-                                    );
-                                }
-                            }
-                        } else {
-                            tracing::debug!(
-                                route_id = %route_id,
-                                "no WS channel for route, skipping"
-                            );
+                        if let Err(e) = state.route_updates_usecase.notify_route_updated(&event).await {
+                            tracing::warn!(route_id = %event.route_id, error = %e, "failed to notify route followers");
                         }
                     }
-                    tracing::warn!("NATS photo completion subscriber ended");
+                    tracing::warn!("route update notifier subscriber ended");
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "failed to subscribe to route updated events");
                 }
+            }
+        });
+    }
+
+    // Spawn the durable consumer of photo completions. The replicas share it,
+    // so each completion is handled once, and completions published while no
+    // replica was running are delivered after the restart.
+    if let Some(ref client) = shared_state.nats_client {
+        let nats_client = client.clone();
+        let state = shared_state.clone();
+        tokio::spawn(async move {
+            let consumer = match photo_completed_consumer(&nats_client).await {
+                Ok(consumer) => consumer,
                 Err(e) => {
-                    tracing::error!(error = %e, "failed to subscribe to photos.completed.*");
+                    tracing::error!(error = %e, "failed to create photo completion consumer");
+                    return;
+                }
+            };
+            let mut messages = match consumer.messages().await {
+                Ok(messages) => messages,
+                Err(e) => {
+                    tracing::error!(error = %e, "failed to consume photo completions");
+                    return;
+                }
+            };
+            tracing::info!(consumer = PHOTO_COMPLETED_CONSUMER_NAME, "photo completion consumer ready");
+            use futures::StreamExt;
+            while let Some(msg) = messages.next().await {
+                let msg = match msg {
+                    Ok(msg) => msg,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "error receiving photo completion");
+                        continue;
+                    }
+                };
+                let ack = match handle_photo_completed(&state, &msg.payload).await {
+                    Ok(()) => async_nats::jetstream::AckKind::Ack,
+                    Err(e) => {
+                        tracing::warn!(subject = %msg.subject, error = %e, "failed to handle photo completion, will retry");
+                        async_nats::jetstream::AckKind::Nak(Some(std::time::Duration::from_secs(5)))
+                    }
+                };
+                if let Err(e) = msg.ack_with(ack).await {
+                    tracing::warn!(subject = %msg.subject, error = %e, "failed to ack photo completion");
                 }
             }
+            tracing::warn!("photo completion consumer ended");
         });
     }
 
+    let overload_retry_after = std::time::Duration::from_secs(config.overload_retry_after_secs);
+    let chat_limit = shed_load("chat", config.chat_concurrency_limit, overload_retry_after);
+    let import_limit = shed_load("import", config.import_concurrency_limit, overload_retry_after);
+
+    // Staff endpoints: the role is checked here, before any handler runs
+    let admin_api = Router::new()
+        .route("/api/v1/admin/stats", get(get_admin_stats))
+        .route("/api/v1/admin/chat/stats", get(get_admin_chat_stats))
+        .route("/api/v1/admin/chat/flagged", get(list_flagged_conversations))
+        .route(
+            "/api/v1/admin/chat/flagged/{user_id}/{conversation_id}",
+            get(review_flagged_conversation),
+        )
+        .route("/api/v1/admin/routes/stats", get(get_routes_stats))
+        .route("/api/v1/admin/routes", get(list_admin_routes))
+        .route(
+            "/api/v1/admin/users/{user_id}/route-quota",
+            get(get_route_quota).put(set_route_quota).delete(delete_route_quota),
+        )
+        .route("/api/v1/admin/storage", get(get_storage_overview))
+        .route("/internal/migrations", get(migration_status))
+        .route("/api/v1/admin/comments", get(list_admin_comments))
+        .route("/api/v1/admin/search/reindex", post(reindex_search))
+        .route("/api/v1/admin/categories", post(create_category))
+        .route("/api/v1/admin/categories/{id}", put(update_category).delete(delete_category))
+        .route("/api/v1/admin/translations", get(list_translations).put(set_translation))
+        .route("/api/v1/admin/translations/{locale}/{key}", delete(delete_translation))
+        .route("/api/v1/admin/settings/difficulty", put(set_difficulty_thresholds))
+        .route("/api/v1/admin/settings/chat-prompt", get(get_chat_system_prompt).put(set_chat_system_prompt))
+        .route("/api/v1/admin/settings/photo-pipeline", get(get_photo_pipeline).put(set_photo_pipeline))
+        .route("/api/v1/admin/settings/content-filter", get(get_content_filter).put(set_content_filter))
+        .route_layer(middleware::from_fn_with_state(RequireRole::ADMIN, require_role));
+
+    // Moderation queues are open to moderators as well
+    let moderation_api = Router::new()
+        .route("/api/v1/admin/comments/search", get(search_admin_comments))
+        .route("/api/v1/admin/reports", get(list_reports))
+        .route("/api/v1/admin/reports/{id}/resolve", post(resolve_report))
+        .route("/api/v1/admin/spam-flags", get(list_spam_flags))
+        .route("/api/v1/admin/spam-flags/{id}/review", post(review_spam_flag))
+        .route_layer(middleware::from_fn_with_state(RequireRole::MODERATOR, require_role));
+
     // All routes require authentication
     let routes_api = Router::new()
         .route("/api/v1/routes", get(list_routes).post(create_route))
-        .route("/api/v1/routes/import", post(import_route_from_geojson))
+        .route(
+            "/api/v1/routes/import",
+            // Each file is capped while it is parsed; the body limit leaves
+            // room for the multipart framing around them.
+            post(import_route_from_geojson)
+                .layer(DefaultBodyLimit::max(config.import_max_files.max(1) * (config.import_max_bytes + 64 * 1024)))
+                .layer(import_limit.clone()),
+        )
+        .route("/api/v1/routes/import/url", post(import_route_from_url).layer(import_limit))
+        .route("/api/v1/routes/merge", post(merge_routes))
         .route(
             "/api/v1/routes/{id}",
             get(get_route).put(update_route).delete(delete_route),
         )
         .route("/api/v1/routes/{id}/share", post(enable_share).delete(disable_share))
+        .route("/api/v1/routes/{id}/visibility", put(set_route_visibility))
+        .route("/api/v1/routes/{id}/share-links", get(list_share_links).post(create_share_link))
+        .route("/api/v1/routes/{id}/share-links/{link_id}", delete(revoke_share_link))
+        .route("/api/v1/routes/{id}/archive", post(archive_route))
+        .route("/api/v1/routes/{id}/unarchive", post(unarchive_route))
+        .route("/api/v1/routes/{id}/split", post(split_route))
+        .route("/api/v1/routes/{id}/points", patch(patch_route_points))
+        .route("/api/v1/routes/{id}/reverse", post(reverse_route))
+        .route("/api/v1/routes/{id}/activity", get(list_route_activity))
+        .route("/api/v1/routes/{id}/template", put(set_route_template).delete(remove_route_template))
+        .route("/api/v1/templates", get(list_templates))
+        .route("/api/v1/templates/{id}/instantiate", post(instantiate_template))
+        .route("/api/v1/collections", get(list_collections).post(create_collection))
+        .route("/api/v1/collections/{id}", put(rename_collection).delete(delete_collection))
+        .route("/api/v1/collections/{id}/routes", post(add_collection_route))
+        .route("/api/v1/collections/{id}/routes/{route_id}", delete(remove_collection_route))
+        .route("/api/v1/saved-searches", get(list_saved_searches).post(create_saved_search))
+        .route("/api/v1/saved-searches/{id}", delete(delete_saved_search))
+        .route("/api/v1/routes/{id}/cover", put(set_route_cover))
+        .route("/api/v1/routes/{id}/export.pdf", get(export_route_pdf))
+        .route("/api/v1/routes/{id}/offline-bundle", get(export_offline_bundle))
+        .route("/api/v1/routes/{id}/elevation", get(get_route_elevation))
+        .route("/api/v1/routes/{id}/geocode-points", post(geocode_route_points))
+        .route("/api/v1/routes/{id}/photos/retry", post(retry_failed_photos))
+        .route("/api/v1/routes/{id}/route-segments", get(get_route_segments).post(compute_route_segments))
+        .route("/api/v1/routes/{id}/stats", get(get_route_stats))
         .route("/api/v1/routes/{route_id}/comments", post(create_comment))
+        .route("/api/v1/routes/{route_id}/comments/pending", get(list_pending_comments))
+        .route("/api/v1/routes/{route_id}/comment-policy", put(set_comment_policy))
         .route("/api/v1/comments/{comment_id}", delete(delete_comment))
+        .route("/api/v1/comments/{comment_id}/approve", post(approve_comment))
         .route("/api/v1/routes/{route_id}/like", post(toggle_like))
         .route("/api/v1/routes/{route_id}/like/me", get(get_user_like_status))
         .route("/api/v1/routes/{route_id}/rating", put(set_rating).delete(remove_rating))
         .route("/api/v1/routes/{route_id}/rating/me", get(get_user_rating))
+        .route("/api/v1/routes/{route_id}/report", post(report_route))
         .route("/api/v1/routes/{route_id}/description/generate", post(generate_description))
         .route("/api/v1/routes/{route_id}/description", post(save_description))
         .route("/api/v1/routes/{route_id}/bookmark", post(toggle_bookmark))
         .route("/api/v1/routes/{route_id}/bookmark/me", get(get_user_bookmark_status))
         .route("/api/v1/bookmarks", get(list_bookmarks))
-        .route("/api/v1/admin/routes/stats", get(get_routes_stats))
-        .route("/api/v1/admin/routes", get(list_admin_routes))
-        .route("/api/v1/admin/comments", get(list_admin_comments))
-        .route("/api/v1/admin/categories", post(create_category))
-        .route("/api/v1/admin/categories/{id}", put(update_category).delete(delete_category))
         .route("/api/v1/notifications", get(list_notifications))
         .route("/api/v1/notifications/unread-count", get(get_unread_count))
         .route("/api/v1/notifications/{id}/read", post(mark_as_read))
         .route("/api/v1/notifications/read-all", post(mark_all_as_read))
-        .route("/api/v1/admin/settings/difficulty", put(set_difficulty_thresholds))
-        .route("/api/v1/chat", get(list_conversations).post(send_chat_message))
-        .route("/api/v1/chat/{conversation_id}", get(get_chat_history).delete(delete_conversation))
-        .route("/api/v1/chat/stream", post(send_chat_message_stream))
+        .route("/api/v1/notifications/preferences", get(get_notification_preferences).put(update_notification_preferences))
+        .route("/api/v1/me/export", post(request_data_export))
+        .route("/api/v1/me/storage", get(get_storage_usage))
+        .route("/api/v1/me/blocks", get(list_blocked_users))
+        .route("/api/v1/users/{id}/block", post(block_user).delete(unblock_user))
+        .route("/api/v1/chat", get(list_conversations).merge(post(send_chat_message).layer(chat_limit.clone())))
+        .route("/api/v1/chat/{conversation_id}", get(get_chat_history).patch(update_conversation).delete(delete_conversation))
+        .route("/api/v1/chat/stream", post(send_chat_message_stream).layer(chat_limit))
+        .route("/api/v1/chat/preferences", get(get_chat_preferences).put(update_chat_preferences))
+        .route("/api/v1/chat/{conversation_id}/share", post(share_conversation).delete(unshare_conversation))
+        .route("/api/v1/chat/{conversation_id}/flag", post(flag_conversation))
         .route("/api/v1/chat/{conversation_id}/messages/{message_id}", delete(delete_message))
+        .route("/api/v1/chat/actions/{action_id}/confirm", post(confirm_chat_action))
+        .route("/api/v1/chat/attachments", post(create_chat_attachment))
+        .route("/api/v2/routes", get(routes_v2::list_routes))
+        .route("/api/v2/routes/{id}", get(routes_v2::get_route))
+        .route("/api/v2/routes/{id}/points", get(routes_v2::get_route_points))
+        .merge(admin_api)
+        .merge(moderation_api)
+        .layer(middleware::from_fn(record_user_middleware))
         .layer(middleware::from_fn_with_state(
             shared_state.clone(),
             auth_middleware,
         ));
 
+    let explore_cache = shared_cache_control(config.cache_explore_secs);
+    let shared_cache = shared_cache_control(config.cache_shared_secs);
+    let categories_cache = public_cache_control(config.cache_categories_secs);
+    let shared_throttle = middleware::from_fn_with_state(
+        IpThrottle {
+            group: "shared",
+            store: shared_state.rate_limits.clone(),
+            max: config.shared_rate_limit_max,
+            window: std::time::Duration::from_secs(config.shared_rate_limit_window_secs),
+            trusted_proxies: Arc::new(TrustedProxies::from_list(&config.trusted_proxies)),
+        },
+        throttle_by_ip,
+    );
+
     let router = Router::new()
         .route("/healthz", get(healthz))
         .route("/metrics", get(metrics))
-        .route("/api/v1/routes/explore", get(explore_routes))
-        .route("/api/v1/shared/{token}", get(get_shared_route))
+        .route(
+            "/api/v1/routes/explore",
+            get(explore_routes).layer(middleware::map_response_with_state(explore_cache, set_cache_control)),
+        )
+        .route("/api/v1/search", get(search_routes))
+        .route(
+            "/api/v1/shared/{token}",
+            get(get_shared_route)
+                .layer(middleware::map_response_with_state(shared_cache.clone(), set_cache_control))
+                .layer(shared_throttle.clone()),
+        )
         .route("/api/v1/routes/{route_id}/ws", get(websocket_handler))
         .route("/api/v1/routes/{route_id}/comments", get(list_comments))
         .route("/api/v1/routes/{route_id}/comments/count", get(count_comments))
-        .route("/api/v1/routes/{route_id}/like", get(get_like_count))
+        .route("/api/v1/routes/{route_id}/comments/search", get(search_comments))
+        .route("/api/v1/routes/{route_id}/comment-policy", get(get_comment_policy))
+        .route(
+            "/api/v1/routes/{route_id}/like",
+            get(get_like_count).layer(middleware::map_response_with_state(shared_cache.clone(), set_cache_control)),
+        )
         .route("/api/v1/routes/{route_id}/rating", get(get_rating_aggregate))
         .route("/api/v1/settings/difficulty", get(get_difficulty_thresholds))
-        .route("/api/v1/categories", get(list_categories))
+        .route(
+            "/api/v1/categories",
+            get(list_categories).layer(middleware::map_response_with_state(categories_cache, set_cache_control)),
+        )
         .route("/api/v1/chat/health", get(chat_health))
+        .route("/api/v1/chat/shared/{token}", get(get_shared_conversation))
+        .route(
+            "/api/v2/shared/{token}",
+            get(routes_v2::get_shared_route)
+                .layer(middleware::map_response_with_state(shared_cache.clone(), set_cache_control))
+                .layer(shared_throttle.clone()),
+        )
+        .route(
+            "/api/v2/shared/{token}/points",
+            get(routes_v2::get_shared_route_points)
+                .layer(middleware::map_response_with_state(shared_cache, set_cache_control))
+                .layer(shared_throttle),
+        )
         .merge(routes_api)
-        .layer(TraceLayer::new_for_http())
+        .route_layer(middleware::from_fn(track_metrics))
+        .layer(middleware::from_fn(capture_request_span))
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(TraceLayer::new_for_http().make_span_with(make_request_span))
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .with_state(shared_state);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
     tracing::info!("routes service running on 0.0.0.0:8080");
-    axum::serve(listener, router).await?;
+    axum::serve(listener, router.into_make_service_with_connect_info::<std::net::SocketAddr>()).await?;
 
     // Shutdown telemetry on exit
     if config.telemetry_enabled {
@@ -395,6 +1046,61 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn photo_completed_consumer(
+    client: &async_nats::Client,
+) -> anyhow::Result<async_nats::jetstream::consumer::PullConsumer> {
+    let stream = async_nats::jetstream::new(client.clone())
+        .get_stream(PHOTO_COMPLETED_STREAM_NAME)
+        .await?;
+    Ok(stream
+        .get_or_create_consumer(
+            PHOTO_COMPLETED_CONSUMER_NAME,
+            async_nats::jetstream::consumer::pull::Config {
+                durable_name: Some(PHOTO_COMPLETED_CONSUMER_NAME.to_string()),
+                ack_wait: std::time::Duration::from_secs(30),
+                ..Default::default()
+            },
+        )
+        .await?)
+}
+
+/// Re-broadcasts a photo completion to the WS clients of every replica and
+/// writes the owner's notification. The broadcast goes first: repeating it on
+/// a retry only re-applies the same points, while the notification must be
+/// written once.
+async fn handle_photo_completed(state: &AppState, payload: &[u8]) -> anyhow::Result<()> {
+    let update = match events::decode::<PhotoCompletedEvent>(payload) {
+        Ok(PhotoCompletedEvent::PhotoUpdate(update)) => update,
+        Ok(PhotoCompletedEvent::Unknown) => return Ok(()),
+        Err(e) => {
+            // Retrying cannot fix a payload this build does not understand
+            tracing::warn!(error = %e, "invalid photo completion event, dropping");
+            return Ok(());
+        }
+    };
+
+    state.realtime.publish(update.route_id, String::from_utf8(payload.to_vec())?).await;
+
+    let summary = update.summary();
+    let route_name = match state.routes_usecase.route_repository().find_by_id(summary.route_id).await? {
+        Some(route) => route.name,
+        None => {
+            tracing::debug!(route_id = %summary.route_id, "route gone, skipping photo notification");
+            return Ok(());
+        }
+    };
+    state.notifications_usecase.notify_photos_processed(&summary, &route_name).await?;
+    record_activity(
+        state,
+        summary.route_id,
+        None,
+        ACTIVITY_PHOTOS_PROCESSED,
+        Some(serde_json::json!({ "processed": summary.processed, "failed": summary.failed })),
+    )
+    .await;
+    Ok(())
+}
+
 async fn metrics(State(state): State<Arc<AppState>>) -> String {
     metrics_process::Collector::default().collect();
     state.metrics_handle.render()
@@ -404,3 +1110,12 @@ async fn metrics(State(state): State<Arc<AppState>>) -> String {
 async fn healthz() -> &'static str {
     "OK"
 }
+
+/// Embedded migrations and whether each is applied, for operators running
+/// migrations as a separate deploy step. Admins only, as it reveals the schema.
+#[tracing::instrument(skip(state))]
+async fn migration_status(State(state): State<Arc<AppState>>) -> Result<Json<MigrationStatus>, UsecaseError> {
+    let status = state.db_pool.migration_status().await?;
+    tracing::debug!(pending = status.pending, failed = status.failed, "migration status retrieved");
+    Ok(Json(status))
+}
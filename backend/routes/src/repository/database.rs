@@ -0,0 +1,767 @@
+//! Database backend selected from the `DATABASE_URL` scheme. `sqlite:` URLs
+//! require the `sqlite` feature; everything else goes to Postgres.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use guide_helper_domain::user_data::UserKeyedTable;
+use serde::Serialize;
+use sqlx::migrate::Migrator;
+use uuid::Uuid;
+
+use crate::{
+    domain::activity::RouteActivity,
+    domain::block::UserBlock,
+    domain::bookmark::RouteBookmark,
+    domain::category::Category,
+    domain::chat_message::{ChatMessage, ChatTurnRecord, ConversationFlags, ConversationSummary, FlaggedConversation, PendingChatAction},
+    domain::collection::RouteCollection,
+    domain::comment::Comment,
+    domain::like::RouteLike,
+    domain::notification::Notification,
+    domain::quota::{RouteQuota, RouteUsage, StorageTotals, StorageUsage, UserStorageUsage},
+    domain::rating::RouteRating,
+    domain::report::RouteReport,
+    domain::route::{AdminRouteRow, ExploreRouteRow, Route, RouteCover, RoutePoint, RouteVisibility, UserRoutesFilter},
+    domain::route_elevation::RouteElevation,
+    domain::route_geometry::RouteGeometry,
+    domain::saved_search::SavedSearch,
+    domain::share_link::ShareLink,
+    domain::spam::SpamFlag,
+    domain::stats::{ChatTurnSummary, ChatUsage, ChatUserVolume, DailyCount, PhotoVolume, ToolCallCount},
+    domain::template::{RouteTemplate, TemplateSummary},
+    domain::translation::Translation,
+    repository::errors::RepositoryError,
+    repository::pool::{spawn_pool_metrics, PoolSettings},
+    repository::postgres,
+    repository::retry::RetryPolicy,
+    usecase::contracts::{BlockRepository, BookmarkRepository, CategoryRepository, ChatMessageRepository, CommentRepository, LikeRepository, NotificationPreferenceRepository, NotificationRepository, RatingRepository, ReportRepository, RouteActivityRepository, RouteCollectionRepository, RouteElevationRepository, RouteGeometryRepository, RouteRepository, RouteTemplateRepository, SavedSearchRepository, SettingsRepository, ShareLinkRepository, SpamFlagRepository, StatsRepository, TranslationRepository, UserDataRepository},
+    usecase::settings::DifficultyThresholds,
+};
+
+static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!();
+#[cfg(feature = "sqlite")]
+static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations_sqlite");
+
+/// One migration embedded in this build, checked against the database.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MigrationInfo {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_on: Option<DateTime<Utc>>,
+    /// Recorded by sqlx but not completed, e.g. a run that crashed midway.
+    pub failed: bool,
+    /// Applied from a script that differs from the one in this build.
+    pub checksum_mismatch: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MigrationStatus {
+    pub pending: usize,
+    pub failed: usize,
+    pub migrations: Vec<MigrationInfo>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AppliedMigrationRow {
+    version: i64,
+    description: String,
+    installed_on: DateTime<Utc>,
+    success: bool,
+    checksum: Vec<u8>,
+}
+
+const APPLIED_MIGRATIONS_QUERY: &str =
+    "SELECT version, description, installed_on, success, checksum FROM _sqlx_migrations ORDER BY version";
+
+#[derive(Clone)]
+pub enum DatabasePool {
+    Postgres(sqlx::PgPool),
+    #[cfg(feature = "sqlite")]
+    Sqlite(sqlx::SqlitePool),
+}
+
+impl DatabasePool {
+    pub async fn connect(database_url: &str, settings: &PoolSettings) -> anyhow::Result<Self> {
+        if database_url.starts_with("sqlite:") {
+            #[cfg(feature = "sqlite")]
+            {
+                tracing::info!("using sqlite database backend");
+                let pool = super::sqlite::create_pool(database_url, settings).await?;
+                return Ok(Self::Sqlite(pool));
+            }
+            #[cfg(not(feature = "sqlite"))]
+            anyhow::bail!("sqlite DATABASE_URL requires the routes service to be built with the `sqlite` feature");
+        }
+
+        tracing::info!("using postgres database backend");
+        let pool = postgres::create_pool(database_url, settings).await?;
+        Ok(Self::Postgres(pool))
+    }
+
+    /// Publishes pool gauges under the given `pool` label.
+    pub fn spawn_metrics(&self, name: &'static str) {
+        match self {
+            Self::Postgres(pool) => spawn_pool_metrics(name, pool.clone()),
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(pool) => spawn_pool_metrics(name, pool.clone()),
+        }
+    }
+
+    pub async fn migrate(&self) -> Result<(), sqlx::migrate::MigrateError> {
+        match self {
+            Self::Postgres(pool) => POSTGRES_MIGRATOR.run(pool).await,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(pool) => SQLITE_MIGRATOR.run(pool).await,
+        }
+    }
+
+    /// Compares the migrations embedded in this build with those recorded
+    /// in the database. Read-only, so it is safe on a database that was
+    /// never migrated.
+    pub async fn migration_status(&self) -> Result<MigrationStatus, RepositoryError> {
+        let (migrator, applied) = match self {
+            Self::Postgres(pool) => {
+                let (exists,): (bool,) = sqlx::query_as("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+                    .fetch_one(pool)
+                    .await
+                    .map_err(RepositoryError::from)?;
+                let applied = if exists {
+                    sqlx::query_as::<_, AppliedMigrationRow>(APPLIED_MIGRATIONS_QUERY)
+                        .fetch_all(pool)
+                        .await
+                        .map_err(RepositoryError::from)?
+                } else {
+                    vec![]
+                };
+                (&POSTGRES_MIGRATOR, applied)
+            }
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(pool) => {
+                let (exists,): (bool,) = sqlx::query_as(
+                    "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+                )
+                .fetch_one(pool)
+                .await
+                .map_err(RepositoryError::from)?;
+                let applied = if exists {
+                    sqlx::query_as::<_, AppliedMigrationRow>(APPLIED_MIGRATIONS_QUERY)
+                        .fetch_all(pool)
+                        .await
+                        .map_err(RepositoryError::from)?
+                } else {
+                    vec![]
+                };
+                (&SQLITE_MIGRATOR, applied)
+            }
+        };
+
+        Ok(compare_migrations(migrator, applied))
+    }
+}
+
+fn compare_migrations(migrator: &Migrator, applied: Vec<AppliedMigrationRow>) -> MigrationStatus {
+    let mut migrations: Vec<MigrationInfo> = migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| {
+            let row = applied.iter().find(|a| a.version == m.version);
+            MigrationInfo {
+                version: m.version,
+                description: m.description.to_string(),
+                applied: row.is_some_and(|a| a.success),
+                installed_on: row.map(|a| a.installed_on),
+                failed: row.is_some_and(|a| !a.success),
+                checksum_mismatch: row.is_some_and(|a| a.checksum != *m.checksum),
+            }
+        })
+        .collect();
+
+    // Applied by a newer build; sqlx refuses to migrate until it is known.
+    let unknown: Vec<&AppliedMigrationRow> = applied
+        .iter()
+        .filter(|a| !migrations.iter().any(|m| m.version == a.version))
+        .collect();
+    for row in unknown {
+        migrations.push(MigrationInfo {
+            version: row.version,
+            description: row.description.clone(),
+            applied: row.success,
+            installed_on: Some(row.installed_on),
+            failed: !row.success,
+            checksum_mismatch: false,
+        });
+    }
+    migrations.sort_by_key(|m| m.version);
+
+    MigrationStatus {
+        pending: migrations.iter().filter(|m| !m.applied && !m.failed).count(),
+        failed: migrations.iter().filter(|m| m.failed).count(),
+        migrations,
+    }
+}
+
+/// Primary pool for writes plus an optional read replica. Both always use
+/// the same backend.
+#[derive(Clone)]
+pub struct DbPools {
+    primary: DatabasePool,
+    replica: Option<DatabasePool>,
+    retry: RetryPolicy,
+}
+
+impl DbPools {
+    pub async fn connect(database_url: &str, read_url: Option<&str>, settings: &PoolSettings) -> anyhow::Result<Self> {
+        let primary = DatabasePool::connect(database_url, settings).await?;
+        let replica = match read_url {
+            Some(read_url) => {
+                let replica = DatabasePool::connect(read_url, settings).await?;
+                if std::mem::discriminant(&primary) != std::mem::discriminant(&replica) {
+                    anyhow::bail!("DATABASE_READ_URL must use the same database backend as DATABASE_URL");
+                }
+                tracing::info!("read replica pool created");
+                Some(replica)
+            }
+            None => None,
+        };
+        Ok(Self { primary, replica, retry: RetryPolicy::default() })
+    }
+
+    /// Retry policy of the retried calls of repositories built from these pools.
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn primary(&self) -> &DatabasePool {
+        &self.primary
+    }
+
+    pub fn spawn_metrics(&self) {
+        self.primary.spawn_metrics("primary");
+        if let Some(replica) = &self.replica {
+            replica.spawn_metrics("replica");
+        }
+    }
+
+    /// The replica when configured, otherwise the primary.
+    pub fn read(&self) -> &DatabasePool {
+        self.replica.as_ref().unwrap_or(&self.primary)
+    }
+}
+
+/// Declares an enum over the Postgres and SQLite implementations of a
+/// repository trait and forwards every method to the active variant.
+///
+/// Methods marked `#[read]` go to the read replica; only mark queries that
+/// tolerate replication lag, never one that reads back a write made in the
+/// same request. `#[read]` and `#[retry]` methods retry transient failures
+/// with the pools' [`RetryPolicy`]. A transient error can arrive after the
+/// server committed, so only mark `#[retry]` on primary reads and on writes
+/// that leave the same state and result when run twice (upserts, setting a
+/// column to a given value). Inserts, deletes and compare-and-set updates
+/// stay unmarked and fail on the first error.
+macro_rules! backend_repository {
+    (@call [read] $self:ident.$method:ident($($arg:ident),*)) => {
+        match $self {
+            Self::Postgres { replica, .. } => replica.$method($($arg),*).await,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite { replica, .. } => replica.$method($($arg),*).await,
+        }
+    };
+    (@call [retry] $self:ident.$method:ident($($arg:ident),*)) => {
+        backend_repository!(@call [] $self.$method($($arg),*))
+    };
+    (@call [] $self:ident.$method:ident($($arg:ident),*)) => {
+        match $self {
+            Self::Postgres { primary, .. } => primary.$method($($arg),*).await,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite { primary, .. } => primary.$method($($arg),*).await,
+        }
+    };
+    (@run [] $name:ident, $self:ident.$method:ident($($arg:ident),*)) => {
+        backend_repository!(@call [] $self.$method($($arg),*))
+    };
+    (@run [$kind:ident] $name:ident, $self:ident.$method:ident($($arg:ident),*)) => {
+        $self
+            .retry_policy()
+            .run(concat!(stringify!($name), "::", stringify!($method)), || {
+                $(let $arg = Clone::clone(&$arg);)*
+                async move { backend_repository!(@call [$kind] $self.$method($($arg),*)) }
+            })
+            .await
+    };
+    (
+        $name:ident: $trait:ident => $pg:ident, $sqlite:ident {
+            $($(#[$kind:ident])? async fn $method:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*
+        }
+    ) => {
+        pub enum $name {
+            Postgres { primary: postgres::$pg, replica: postgres::$pg, retry: RetryPolicy },
+            #[cfg(feature = "sqlite")]
+            Sqlite { primary: super::sqlite::$sqlite, replica: super::sqlite::$sqlite, retry: RetryPolicy },
+        }
+
+        impl $name {
+            pub fn new(pools: DbPools) -> Self {
+                let replica = pools.read().clone();
+                let retry = pools.retry;
+                match (pools.primary, replica) {
+                    (DatabasePool::Postgres(primary), DatabasePool::Postgres(replica)) => Self::Postgres {
+                        primary: postgres::$pg::new(primary),
+                        replica: postgres::$pg::new(replica),
+                        retry,
+                    },
+                    #[cfg(feature = "sqlite")]
+                    (DatabasePool::Sqlite(primary), DatabasePool::Sqlite(replica)) => Self::Sqlite {
+                        primary: super::sqlite::$sqlite::new(primary),
+                        replica: super::sqlite::$sqlite::new(replica),
+                        retry,
+                    },
+                    #[cfg(feature = "sqlite")]
+                    _ => unreachable!("DbPools never mixes database backends"),
+                }
+            }
+
+            fn retry_policy(&self) -> &RetryPolicy {
+                match self {
+                    Self::Postgres { retry, .. } => retry,
+                    #[cfg(feature = "sqlite")]
+                    Self::Sqlite { retry, .. } => retry,
+                }
+            }
+        }
+
+        impl $trait for $name {
+            $(
+                async fn $method(&self $(, $arg: $ty)*) -> $ret {
+                    backend_repository!(@run [$($kind)?] $name, self.$method($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+backend_repository! {
+    DbRouteRepository: RouteRepository => PostgresRouteRepository, SqliteRouteRepository {
+        async fn create(&self, route: &Route) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Route>, RepositoryError>;
+        #[retry]
+        async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Route>, RepositoryError>;
+        #[retry]
+        async fn find_by_user_id_paginated(&self, user_id: Uuid, filter: &UserRoutesFilter, order_clause: &str, limit: i64, offset: i64) -> Result<Vec<Route>, RepositoryError>;
+        #[retry]
+        async fn count_by_user_id(&self, user_id: Uuid, filter: &UserRoutesFilter) -> Result<i64, RepositoryError>;
+        #[retry]
+        async fn update(&self, route: &Route) -> Result<(), RepositoryError>;
+        async fn replace_points(&self, id: Uuid, points: &[RoutePoint], expected: DateTime<Utc>, updated_at: DateTime<Utc>) -> Result<bool, RepositoryError>;
+        async fn delete(&self, id: Uuid) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn set_visibility(&self, id: Uuid, visibility: RouteVisibility, token: Option<Uuid>) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_share_token(&self, token: Uuid) -> Result<Option<Route>, RepositoryError>;
+        #[retry]
+        async fn find_shared_updated_at(&self, token: Uuid) -> Result<Option<DateTime<Utc>>, RepositoryError>;
+        #[retry]
+        async fn find_share_token(&self, id: Uuid, public_only: bool) -> Result<Option<Uuid>, RepositoryError>;
+        #[read]
+        async fn explore_shared(&self, owner_id: Option<Uuid>, search: Option<String>, category_id: Option<Uuid>, season: Option<String>, order_clause: &str, limit: i64, offset: i64) -> Result<Vec<ExploreRouteRow>, RepositoryError>;
+        #[read]
+        async fn count_explore_shared(&self, search: Option<String>, category_id: Option<Uuid>, season: Option<String>) -> Result<i64, RepositoryError>;
+        #[read]
+        async fn count_explore_by_category(&self, search: Option<String>, season: Option<String>) -> Result<Vec<(Uuid, i64)>, RepositoryError>;
+        #[read]
+        async fn count_explore_by_difficulty(&self, search: Option<String>, category_id: Option<Uuid>, season: Option<String>, thresholds: &DifficultyThresholds) -> Result<Vec<(String, i64)>, RepositoryError>;
+        #[read]
+        async fn count_all(&self) -> Result<i64, RepositoryError>;
+        #[retry]
+        async fn find_shared_between(&self, after: DateTime<Utc>, until: DateTime<Utc>) -> Result<Vec<Route>, RepositoryError>;
+        #[read]
+        async fn find_all_admin(&self, limit: i64, offset: i64) -> Result<Vec<AdminRouteRow>, RepositoryError>;
+        #[retry]
+        async fn update_locations(&self, id: Uuid, start_location: Option<String>, end_location: Option<String>) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn set_archived_at(&self, id: Uuid, archived_at: Option<DateTime<Utc>>) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn set_cover(&self, id: Uuid, cover: Option<RouteCover>) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_comment_policy(&self, id: Uuid) -> Result<Option<String>, RepositoryError>;
+        #[retry]
+        async fn set_comment_policy(&self, id: Uuid, policy: &str) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn usage_by_user_id(&self, user_id: Uuid) -> Result<RouteUsage, RepositoryError>;
+        #[read]
+        async fn storage_by_user_id(&self, user_id: Uuid) -> Result<StorageUsage, RepositoryError>;
+        #[read]
+        async fn storage_totals(&self) -> Result<StorageTotals, RepositoryError>;
+        #[read]
+        async fn top_storage_users(&self, limit: i64) -> Result<Vec<UserStorageUsage>, RepositoryError>;
+        #[retry]
+        async fn find_quota_override(&self, user_id: Uuid) -> Result<Option<RouteQuota>, RepositoryError>;
+        #[retry]
+        async fn set_quota_override(&self, user_id: Uuid, quota: Option<RouteQuota>) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn reconcile_counters(&self) -> Result<u64, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbCommentRepository: CommentRepository => PostgresCommentRepository, SqliteCommentRepository {
+        async fn create(&self, comment: &Comment) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_route_id(&self, route_id: Uuid) -> Result<Vec<Comment>, RepositoryError>;
+        #[retry]
+        async fn find_pending_by_route_id(&self, route_id: Uuid) -> Result<Vec<Comment>, RepositoryError>;
+        #[retry]
+        async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Comment>, RepositoryError>;
+        #[retry]
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Comment>, RepositoryError>;
+        async fn delete(&self, id: Uuid) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn approve(&self, id: Uuid) -> Result<(), RepositoryError>;
+        #[read]
+        async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError>;
+        #[read]
+        async fn count_all(&self) -> Result<i64, RepositoryError>;
+        #[read]
+        async fn find_all_paginated(&self, limit: i64, offset: i64) -> Result<Vec<Comment>, RepositoryError>;
+        #[read]
+        async fn search(&self, route_id: Option<Uuid>, query: &str, limit: i64, offset: i64) -> Result<Vec<Comment>, RepositoryError>;
+        #[read]
+        async fn count_search(&self, route_id: Option<Uuid>, query: &str) -> Result<i64, RepositoryError>;
+        #[retry]
+        async fn count_recent_by_text(&self, text: &str, since: DateTime<Utc>, exclude_id: Uuid) -> Result<i64, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbCategoryRepository: CategoryRepository => PostgresCategoryRepository, SqliteCategoryRepository {
+        async fn create(&self, category: &Category) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_all(&self) -> Result<Vec<Category>, RepositoryError>;
+        #[retry]
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Category>, RepositoryError>;
+        #[retry]
+        async fn update(&self, id: Uuid, name: &str) -> Result<(), RepositoryError>;
+        async fn delete(&self, id: Uuid) -> Result<(), RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbBlockRepository: BlockRepository => PostgresBlockRepository, SqliteBlockRepository {
+        async fn create(&self, block: &UserBlock) -> Result<bool, RepositoryError>;
+        async fn delete(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn exists(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<bool, RepositoryError>;
+        #[read]
+        async fn find_by_blocker(&self, blocker_id: Uuid) -> Result<Vec<UserBlock>, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbSpamFlagRepository: SpamFlagRepository => PostgresSpamFlagRepository, SqliteSpamFlagRepository {
+        async fn create(&self, flag: &SpamFlag) -> Result<(), RepositoryError>;
+        #[read]
+        async fn list(&self, reviewed: bool, limit: i64, offset: i64) -> Result<Vec<SpamFlag>, RepositoryError>;
+        #[read]
+        async fn count(&self, reviewed: bool) -> Result<i64, RepositoryError>;
+        async fn mark_reviewed(&self, id: Uuid, reviewed_by: Uuid) -> Result<(), RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbRouteActivityRepository: RouteActivityRepository => PostgresRouteActivityRepository, SqliteRouteActivityRepository {
+        async fn create(&self, activity: &RouteActivity) -> Result<(), RepositoryError>;
+        #[read]
+        async fn find_by_route_id(&self, route_id: Uuid, limit: i64, offset: i64) -> Result<Vec<RouteActivity>, RepositoryError>;
+        #[read]
+        async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbRouteTemplateRepository: RouteTemplateRepository => PostgresRouteTemplateRepository, SqliteRouteTemplateRepository {
+        #[retry]
+        async fn upsert(&self, template: &RouteTemplate) -> Result<(), RepositoryError>;
+        async fn delete(&self, route_id: Uuid) -> Result<(), RepositoryError>;
+        #[read]
+        async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteTemplate>, RepositoryError>;
+        #[read]
+        async fn list_visible(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<TemplateSummary>, RepositoryError>;
+        #[read]
+        async fn count_visible(&self, user_id: Uuid) -> Result<i64, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbShareLinkRepository: ShareLinkRepository => PostgresShareLinkRepository, SqliteShareLinkRepository {
+        async fn create(&self, link: &ShareLink) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<ShareLink>, RepositoryError>;
+        #[retry]
+        async fn find_by_route_id(&self, route_id: Uuid) -> Result<Vec<ShareLink>, RepositoryError>;
+        async fn revoke(&self, id: Uuid, route_id: Uuid, revoked_at: DateTime<Utc>) -> Result<(), RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbRouteCollectionRepository: RouteCollectionRepository => PostgresRouteCollectionRepository, SqliteRouteCollectionRepository {
+        async fn create(&self, collection: &RouteCollection) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<RouteCollection>, RepositoryError>;
+        #[retry]
+        async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RouteCollection>, RepositoryError>;
+        #[retry]
+        async fn rename(&self, id: Uuid, user_id: Uuid, name: &str, updated_at: DateTime<Utc>) -> Result<(), RepositoryError>;
+        async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError>;
+        async fn add_route(&self, collection_id: Uuid, route_id: Uuid, added_at: DateTime<Utc>) -> Result<(), RepositoryError>;
+        async fn remove_route(&self, collection_id: Uuid, route_id: Uuid) -> Result<(), RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbSavedSearchRepository: SavedSearchRepository => PostgresSavedSearchRepository, SqliteSavedSearchRepository {
+        async fn create(&self, search: &SavedSearch) -> Result<(), RepositoryError>;
+        #[read]
+        async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<SavedSearch>, RepositoryError>;
+        #[read]
+        async fn count_by_user_id(&self, user_id: Uuid) -> Result<i64, RepositoryError>;
+        async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_all(&self) -> Result<Vec<SavedSearch>, RepositoryError>;
+        async fn mark_checked(&self, id: Uuid, previous: DateTime<Utc>, checked_at: DateTime<Utc>) -> Result<bool, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbTranslationRepository: TranslationRepository => PostgresTranslationRepository, SqliteTranslationRepository {
+        #[read]
+        async fn find_by_locale(&self, locale: &str) -> Result<Vec<Translation>, RepositoryError>;
+        #[retry]
+        async fn upsert(&self, translation: &Translation) -> Result<(), RepositoryError>;
+        async fn delete(&self, key: &str, locale: &str) -> Result<(), RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbLikeRepository: LikeRepository => PostgresLikeRepository, SqliteLikeRepository {
+        async fn create(&self, like: &RouteLike) -> Result<(), RepositoryError>;
+        async fn delete_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<Option<RouteLike>, RepositoryError>;
+        #[read]
+        async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbSettingsRepository: SettingsRepository => PostgresSettingsRepository, SqliteSettingsRepository {
+        #[retry]
+        async fn get_value(&self, key: &str) -> Result<Option<serde_json::Value>, RepositoryError>;
+        #[retry]
+        async fn set_value(&self, key: &str, value: &serde_json::Value) -> Result<(), RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbRatingRepository: RatingRepository => PostgresRatingRepository, SqliteRatingRepository {
+        #[retry]
+        async fn upsert(&self, rating: &RouteRating) -> Result<(), RepositoryError>;
+        async fn delete_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<Option<RouteRating>, RepositoryError>;
+        #[read]
+        async fn get_aggregate(&self, route_id: Uuid) -> Result<(f64, i64), RepositoryError>;
+        #[retry]
+        async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RouteRating>, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbChatMessageRepository: ChatMessageRepository => PostgresChatMessageRepository, SqliteChatMessageRepository {
+        async fn create(&self, message: &ChatMessage) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_conversation(&self, user_id: Uuid, conversation_id: Uuid, limit: i64) -> Result<Vec<ChatMessage>, RepositoryError>;
+        #[read]
+        async fn list_conversations(&self, user_id: Uuid, limit: i64, offset: i64, include_archived: bool) -> Result<Vec<ConversationSummary>, RepositoryError>;
+        async fn delete_conversation(&self, user_id: Uuid, conversation_id: Uuid) -> Result<(), RepositoryError>;
+        #[read]
+        async fn count_conversations(&self, user_id: Uuid, include_archived: bool) -> Result<i64, RepositoryError>;
+        #[retry]
+        async fn set_conversation_flags(&self, user_id: Uuid, conversation_id: Uuid, archived: Option<bool>, pinned: Option<bool>) -> Result<ConversationFlags, RepositoryError>;
+        async fn share_conversation(&self, user_id: Uuid, conversation_id: Uuid, token: Uuid) -> Result<Uuid, RepositoryError>;
+        async fn unshare_conversation(&self, user_id: Uuid, conversation_id: Uuid) -> Result<(), RepositoryError>;
+        #[read]
+        async fn find_shared_conversation(&self, token: Uuid) -> Result<Option<(Uuid, Uuid)>, RepositoryError>;
+        #[read]
+        async fn find_all_by_user(&self, user_id: Uuid) -> Result<Vec<ChatMessage>, RepositoryError>;
+        async fn delete_message(&self, user_id: Uuid, conversation_id: Uuid, message_id: Uuid) -> Result<(), RepositoryError>;
+        async fn create_pending_action(&self, action: &PendingChatAction) -> Result<(), RepositoryError>;
+        async fn take_pending_action(&self, user_id: Uuid, action_id: Uuid) -> Result<Option<PendingChatAction>, RepositoryError>;
+        async fn delete_pending_actions_before(&self, before: DateTime<Utc>) -> Result<u64, RepositoryError>;
+        #[read]
+        async fn find_chat_language(&self, user_id: Uuid) -> Result<Option<String>, RepositoryError>;
+        #[retry]
+        async fn set_chat_language(&self, user_id: Uuid, language: Option<String>) -> Result<(), RepositoryError>;
+        async fn record_turn(&self, turn: &ChatTurnRecord) -> Result<(), RepositoryError>;
+        async fn flag_conversation(&self, user_id: Uuid, conversation_id: Uuid, reason: Option<String>, review_consent: bool) -> Result<(), RepositoryError>;
+        #[read]
+        async fn list_flagged_conversations(&self, limit: i64, offset: i64) -> Result<Vec<FlaggedConversation>, RepositoryError>;
+        #[read]
+        async fn count_flagged_conversations(&self) -> Result<i64, RepositoryError>;
+        #[read]
+        async fn find_flagged_conversation(&self, user_id: Uuid, conversation_id: Uuid) -> Result<Option<FlaggedConversation>, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbBookmarkRepository: BookmarkRepository => PostgresBookmarkRepository, SqliteBookmarkRepository {
+        async fn create(&self, bookmark: &RouteBookmark) -> Result<(), RepositoryError>;
+        async fn delete_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<Option<RouteBookmark>, RepositoryError>;
+        #[retry]
+        async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<ExploreRouteRow>, RepositoryError>;
+        #[retry]
+        async fn find_user_ids_by_route(&self, route_id: Uuid) -> Result<Vec<Uuid>, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbNotificationRepository: NotificationRepository => PostgresNotificationRepository, SqliteNotificationRepository {
+        async fn create(&self, notification: &Notification) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_user_id(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<Notification>, RepositoryError>;
+        #[retry]
+        async fn count_unread(&self, user_id: Uuid) -> Result<i64, RepositoryError>;
+        #[retry]
+        async fn count_by_user_id(&self, user_id: Uuid) -> Result<i64, RepositoryError>;
+        #[retry]
+        async fn mark_as_read(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn mark_all_as_read(&self, user_id: Uuid) -> Result<(), RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbNotificationPreferenceRepository: NotificationPreferenceRepository => PostgresNotificationPreferenceRepository, SqliteNotificationPreferenceRepository {
+        #[retry]
+        async fn find_disabled_types(&self, user_id: Uuid) -> Result<Vec<String>, RepositoryError>;
+        #[retry]
+        async fn set_enabled(&self, user_id: Uuid, notification_type: &str, enabled: bool) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_opted_out(&self, notification_type: &str, user_ids: Vec<Uuid>) -> Result<Vec<Uuid>, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbReportRepository: ReportRepository => PostgresReportRepository, SqliteReportRepository {
+        async fn create(&self, report: &RouteReport) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<RouteReport>, RepositoryError>;
+        #[retry]
+        async fn find_open_by_route_and_reporter(&self, route_id: Uuid, reporter_id: Uuid) -> Result<Option<RouteReport>, RepositoryError>;
+        #[read]
+        async fn list(&self, status: Option<String>, limit: i64, offset: i64) -> Result<Vec<RouteReport>, RepositoryError>;
+        #[read]
+        async fn count(&self, status: Option<String>) -> Result<i64, RepositoryError>;
+        async fn resolve(&self, id: Uuid, status: &str, resolved_by: Uuid, resolution_note: Option<String>) -> Result<(), RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbRouteGeometryRepository: RouteGeometryRepository => PostgresRouteGeometryRepository, SqliteRouteGeometryRepository {
+        #[retry]
+        async fn upsert(&self, geometry: &RouteGeometry) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteGeometry>, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbRouteElevationRepository: RouteElevationRepository => PostgresRouteElevationRepository, SqliteRouteElevationRepository {
+        #[retry]
+        async fn upsert(&self, elevation: &RouteElevation) -> Result<(), RepositoryError>;
+        #[retry]
+        async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteElevation>, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbStatsRepository: StatsRepository => PostgresStatsRepository, SqliteStatsRepository {
+        #[retry]
+        async fn record_usage(&self, user_id: Uuid, day: NaiveDate) -> Result<(), RepositoryError>;
+        #[read]
+        async fn count_active_users(&self, since: NaiveDate) -> Result<i64, RepositoryError>;
+        #[read]
+        async fn active_users_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError>;
+        #[read]
+        async fn routes_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError>;
+        #[read]
+        async fn chat_usage(&self, since: NaiveDate) -> Result<ChatUsage, RepositoryError>;
+        #[read]
+        async fn photo_volume(&self) -> Result<PhotoVolume, RepositoryError>;
+        #[read]
+        async fn chat_messages_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError>;
+        #[read]
+        async fn chat_turn_summary(&self, since: NaiveDate) -> Result<ChatTurnSummary, RepositoryError>;
+        #[read]
+        async fn chat_tool_calls(&self, since: NaiveDate) -> Result<Vec<ToolCallCount>, RepositoryError>;
+        #[read]
+        async fn top_chat_users(&self, since: NaiveDate, limit: i64) -> Result<Vec<ChatUserVolume>, RepositoryError>;
+    }
+}
+
+backend_repository! {
+    DbUserDataRepository: UserDataRepository => PostgresUserDataRepository, SqliteUserDataRepository {
+        #[read]
+        async fn find_user_rows(&self, table: UserKeyedTable, user_id: Uuid) -> Result<Vec<serde_json::Value>, RepositoryError>;
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    fn test_settings() -> PoolSettings {
+        PoolSettings { max_connections: 1, ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_migration_status_before_and_after_migrate() {
+        let pool = DatabasePool::connect("sqlite::memory:", &test_settings()).await.unwrap();
+
+        let status = pool.migration_status().await.unwrap();
+        assert_eq!(status.pending, SQLITE_MIGRATOR.iter().count());
+        assert_eq!(status.failed, 0);
+        assert!(status.migrations.iter().all(|m| !m.applied && m.installed_on.is_none()));
+
+        pool.migrate().await.unwrap();
+
+        let status = pool.migration_status().await.unwrap();
+        assert_eq!(status.pending, 0);
+        assert!(status
+            .migrations
+            .iter()
+            .all(|m| m.applied && m.installed_on.is_some() && !m.checksum_mismatch));
+    }
+
+    #[tokio::test]
+    async fn test_read_methods_use_replica() {
+        let pools = DbPools::connect("sqlite::memory:", Some("sqlite::memory:"), &test_settings()).await.unwrap();
+        pools.primary().migrate().await.unwrap();
+        pools.read().migrate().await.unwrap();
+        let repo = DbChatMessageRepository::new(pools);
+
+        let user_id = Uuid::new_v4();
+        let conversation_id = Uuid::new_v4();
+        repo.create(&ChatMessage::new_user_message(user_id, conversation_id, "hi".to_string()))
+            .await
+            .unwrap();
+
+        // Written to the primary only; the replica has not caught up.
+        assert_eq!(repo.find_by_conversation(user_id, conversation_id, 10).await.unwrap().len(), 1);
+        assert_eq!(repo.count_conversations(user_id, false).await.unwrap(), 0);
+    }
+}
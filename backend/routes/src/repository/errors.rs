@@ -4,6 +4,55 @@ use thiserror::Error;
 pub enum RepositoryError {
     #[error("Database error: {0}")]
     DatabaseError(String),
+    /// A failure expected to clear on its own, such as a dropped connection
+    /// or a serialization conflict. Safe to retry.
+    #[error("Transient database error: {0}")]
+    Transient(String),
     #[error("Not found")]
     NotFound,
 }
+
+impl From<sqlx::Error> for RepositoryError {
+    fn from(e: sqlx::Error) -> Self {
+        if is_transient(&e) {
+            Self::Transient(e.to_string())
+        } else {
+            Self::DatabaseError(e.to_string())
+        }
+    }
+}
+
+fn is_transient(e: &sqlx::Error) -> bool {
+    match e {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(db) => {
+            let Some(code) = db.code() else {
+                return false;
+            };
+            // SQLITE_BUSY and SQLITE_LOCKED, including their extended codes.
+            #[cfg(feature = "sqlite")]
+            if db.try_downcast_ref::<sqlx::sqlite::SqliteError>().is_some() {
+                return code.parse::<i32>().is_ok_and(|c| matches!(c & 0xff, 5 | 6));
+            }
+            // Serialization failure, deadlock, server shutdown and the
+            // connection exception class.
+            matches!(code.as_ref(), "40001" | "40P01" | "57P01" | "57P02" | "57P03") || code.starts_with("08")
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_sqlx_error_classifies_transient_failures() {
+        assert!(matches!(RepositoryError::from(sqlx::Error::PoolTimedOut), RepositoryError::Transient(_)));
+        assert!(matches!(
+            RepositoryError::from(sqlx::Error::Io(std::io::ErrorKind::ConnectionReset.into())),
+            RepositoryError::Transient(_)
+        ));
+        assert!(matches!(RepositoryError::from(sqlx::Error::RowNotFound), RepositoryError::DatabaseError(_)));
+    }
+}
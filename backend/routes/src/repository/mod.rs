@@ -1,2 +1,8 @@
+pub mod database;
 pub mod errors;
+pub mod pool;
 pub mod postgres;
+pub mod retry;
+pub mod s3;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
@@ -1,19 +1,54 @@
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres};
+use guide_helper_domain::user_data::UserKeyedTable;
 use uuid::Uuid;
 
 use crate::{
+    domain::activity::RouteActivity,
+    domain::block::UserBlock,
     domain::bookmark::RouteBookmark,
     domain::category::Category,
-    domain::chat_message::{ChatMessage, ConversationSummary},
+    domain::chat_message::{ChatMessage, ChatTurnRecord, ConversationFlags, ConversationSummary, FlaggedConversation, PendingChatAction},
+    domain::collection::RouteCollection,
     domain::comment::Comment,
     domain::like::RouteLike,
     domain::notification::Notification,
+    domain::quota::{RouteQuota, RouteUsage, StorageTotals, StorageUsage, UserStorageUsage},
     domain::rating::RouteRating,
-    domain::route::{AdminRouteRow, ExploreRouteRow, Route},
+    domain::report::RouteReport,
+    domain::route::{AdminRouteRow, ExploreRouteRow, Route, RouteCover, RoutePoint, RouteVisibility, UserRoutesFilter},
+    domain::route_elevation::RouteElevation,
+    domain::route_geometry::RouteGeometry,
+    domain::saved_search::SavedSearch,
+    domain::share_link::ShareLink,
+    domain::spam::SpamFlag,
+    domain::stats::{ChatTurnSummary, ChatUsage, ChatUserVolume, DailyCount, PhotoVolume, ToolCallCount},
+    domain::template::{RouteTemplate, TemplateSummary},
+    domain::translation::Translation,
     repository::errors::RepositoryError,
-    usecase::contracts::{BookmarkRepository, CategoryRepository, ChatMessageRepository, CommentRepository, LikeRepository, NotificationRepository, RatingRepository, RouteRepository, SettingsRepository},
+    repository::pool::PoolSettings,
+    usecase::contracts::{BlockRepository, BookmarkRepository, CategoryRepository, ChatMessageRepository, CommentRepository, LikeRepository, NotificationPreferenceRepository, NotificationRepository, RatingRepository, ReportRepository, RouteActivityRepository, RouteCollectionRepository, RouteElevationRepository, RouteGeometryRepository, RouteRepository, RouteTemplateRepository, SavedSearchRepository, SettingsRepository, ShareLinkRepository, SpamFlagRepository, StatsRepository, TranslationRepository, UserDataRepository},
+    usecase::settings::DifficultyThresholds,
 };
 
+/// Points of `routes r` without names, photos or addresses, see
+/// [`ExploreRouteRow::path`].
+const ROUTE_PATH: &str = "COALESCE((SELECT jsonb_agg(jsonb_build_object('lat', p->'lat', 'lng', p->'lng', \
+     'segment_mode', p->'segment_mode') ORDER BY n) FROM jsonb_array_elements(r.points) WITH ORDINALITY AS e(p, n)), \
+     '[]'::jsonb) AS path";
+
+/// Filters of the owner's route list, shared by `find_by_user_id_paginated`
+/// and `count_by_user_id`.
+const USER_ROUTES_FILTER: &str = r#"
+    r.user_id = $1
+      AND ($2 OR r.archived_at IS NULL)
+      AND ($3::text IS NULL OR r.name ILIKE '%' || $3 || '%')
+      AND ($4::uuid IS NULL OR EXISTS (SELECT 1 FROM route_categories WHERE route_id = r.id AND category_id = $4))
+      AND ($5::timestamptz IS NULL OR r.created_at >= $5)
+      AND ($6::timestamptz IS NULL OR r.created_at < $6)
+      AND ($7::uuid IS NULL OR EXISTS (SELECT 1 FROM route_collection_routes WHERE route_id = r.id AND collection_id = $7))
+"#;
+
 #[derive(Clone)]
 pub struct PostgresRouteRepository {
     pool: PgPool,
@@ -30,7 +65,7 @@ impl RouteRepository for PostgresRouteRepository {
     async fn create(&self, route: &Route) -> Result<(), RepositoryError> {
         tracing::debug!("creating route");
 
-        let mut tx = self.pool.begin().await.map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        let mut tx = self.pool.begin().await.map_err(RepositoryError::from)?;
 
         sqlx::query(
             r#"
@@ -48,7 +83,7 @@ impl RouteRepository for PostgresRouteRepository {
         .bind(&route.description)
         .execute(&mut *tx)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         for category_id in &route.category_ids {
             sqlx::query(
@@ -61,10 +96,10 @@ impl RouteRepository for PostgresRouteRepository {
             .bind(category_id)
             .execute(&mut *tx)
             .await
-            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+            .map_err(RepositoryError::from)?;
         }
 
-        tx.commit().await.map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        tx.commit().await.map_err(RepositoryError::from)?;
 
         tracing::debug!(route_id = %route.id, category_count = route.category_ids.len(), "route created successfully");
         Ok(())
@@ -76,9 +111,9 @@ impl RouteRepository for PostgresRouteRepository {
 
         let route = sqlx::query_as::<_, Route>(
             r#"
-            SELECT r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token,
+            SELECT r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token, r.visibility,
                    COALESCE(ARRAY(SELECT category_id FROM route_categories WHERE route_id = r.id), ARRAY[]::uuid[]) AS category_ids,
-                   r.start_location, r.end_location, r.seasons, r.description
+                   r.start_location, r.end_location, r.seasons, r.description, r.archived_at
             FROM routes r
             WHERE r.id = $1
             "#
@@ -86,7 +121,7 @@ impl RouteRepository for PostgresRouteRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         Ok(route)
     }
@@ -97,9 +132,9 @@ impl RouteRepository for PostgresRouteRepository {
 
         let routes = sqlx::query_as::<_, Route>(
             r#"
-            SELECT r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token,
+            SELECT r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token, r.visibility,
                    COALESCE(ARRAY(SELECT category_id FROM route_categories WHERE route_id = r.id), ARRAY[]::uuid[]) AS category_ids,
-                   r.start_location, r.end_location, r.seasons, r.description
+                   r.start_location, r.end_location, r.seasons, r.description, r.archived_at
             FROM routes r
             WHERE r.user_id = $1
             ORDER BY r.created_at DESC
@@ -108,17 +143,76 @@ impl RouteRepository for PostgresRouteRepository {
         .bind(user_id)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = routes.len(), "found routes");
+        Ok(routes)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id_paginated(
+        &self,
+        user_id: Uuid,
+        filter: &UserRoutesFilter,
+        order_clause: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Route>, RepositoryError> {
+        tracing::debug!("finding page of routes by user_id");
+
+        let query = format!(
+            r#"
+            SELECT r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token, r.visibility,
+                   COALESCE(ARRAY(SELECT category_id FROM route_categories WHERE route_id = r.id), ARRAY[]::uuid[]) AS category_ids,
+                   r.start_location, r.end_location, r.seasons, r.description, r.archived_at
+            FROM routes r
+            WHERE {}
+            ORDER BY {}
+            LIMIT $8 OFFSET $9
+            "#,
+            USER_ROUTES_FILTER, order_clause
+        );
+
+        let routes = sqlx::query_as::<_, Route>(&query)
+            .bind(user_id)
+            .bind(filter.include_archived)
+            .bind(filter.search.as_deref())
+            .bind(filter.category_id)
+            .bind(filter.created_after)
+            .bind(filter.created_before)
+            .bind(filter.collection_id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
 
         tracing::debug!(user_id = %user_id, count = routes.len(), "found routes");
         Ok(routes)
     }
 
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_by_user_id(&self, user_id: Uuid, filter: &UserRoutesFilter) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM routes r WHERE {}", USER_ROUTES_FILTER))
+            .bind(user_id)
+            .bind(filter.include_archived)
+            .bind(filter.search.as_deref())
+            .bind(filter.category_id)
+            .bind(filter.created_after)
+            .bind(filter.created_before)
+            .bind(filter.collection_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
     #[tracing::instrument(skip(self, route), fields(route_id = %route.id))]
     async fn update(&self, route: &Route) -> Result<(), RepositoryError> {
         tracing::debug!("updating route");
 
-        let mut tx = self.pool.begin().await.map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        let mut tx = self.pool.begin().await.map_err(RepositoryError::from)?;
 
         let result = sqlx::query(
             r#"
@@ -135,7 +229,7 @@ impl RouteRepository for PostgresRouteRepository {
         .bind(&route.description)
         .execute(&mut *tx)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
@@ -145,7 +239,7 @@ impl RouteRepository for PostgresRouteRepository {
             .bind(route.id)
             .execute(&mut *tx)
             .await
-            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+            .map_err(RepositoryError::from)?;
 
         for category_id in &route.category_ids {
             sqlx::query(
@@ -158,37 +252,62 @@ impl RouteRepository for PostgresRouteRepository {
             .bind(category_id)
             .execute(&mut *tx)
             .await
-            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+            .map_err(RepositoryError::from)?;
         }
 
-        tx.commit().await.map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        tx.commit().await.map_err(RepositoryError::from)?;
 
         tracing::debug!(route_id = %route.id, category_count = route.category_ids.len(), "route updated successfully");
         Ok(())
     }
 
-    #[tracing::instrument(skip(self), fields(route_id = %id))]
-    async fn set_share_token(&self, id: Uuid, token: Option<Uuid>) -> Result<(), RepositoryError> {
-        tracing::debug!(?token, "setting share token");
+    #[tracing::instrument(skip(self, points), fields(route_id = %id, point_count = points.len()))]
+    async fn replace_points(
+        &self,
+        id: Uuid,
+        points: &[RoutePoint],
+        expected: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("UPDATE routes SET points = $2, updated_at = $4 WHERE id = $1 AND updated_at = $3")
+            .bind(id)
+            .bind(serde_json::to_value(points).unwrap())
+            .bind(expected)
+            .bind(updated_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
 
+        tracing::debug!(replaced = result.rows_affected() > 0, "route points replaced");
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id, visibility = visibility.as_str()))]
+    async fn set_visibility(&self, id: Uuid, visibility: RouteVisibility, token: Option<Uuid>) -> Result<(), RepositoryError> {
+        tracing::debug!(?token, "setting route visibility");
+
+        // `shared_at` records when the route was last published to explore
         let result = sqlx::query(
             r#"
             UPDATE routes
-            SET share_token = $2
+            SET visibility = $2, share_token = $3,
+                shared_at = CASE WHEN $2 <> 'public' THEN NULL WHEN visibility = 'public' THEN shared_at ELSE $4 END
             WHERE id = $1
             "#
         )
         .bind(id)
+        .bind(visibility.as_str())
         .bind(token)
+        .bind(Utc::now())
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
         }
 
-        tracing::debug!(route_id = %id, "share token set successfully");
+        tracing::debug!(route_id = %id, "route visibility set successfully");
         Ok(())
     }
 
@@ -198,21 +317,52 @@ impl RouteRepository for PostgresRouteRepository {
 
         let route = sqlx::query_as::<_, Route>(
             r#"
-            SELECT r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token,
+            SELECT r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token, r.visibility,
                    COALESCE(ARRAY(SELECT category_id FROM route_categories WHERE route_id = r.id), ARRAY[]::uuid[]) AS category_ids,
-                   r.start_location, r.end_location, r.seasons, r.description
+                   r.start_location, r.end_location, r.seasons, r.description, r.archived_at
             FROM routes r
-            WHERE r.share_token = $1
+            WHERE r.share_token = $1 AND r.visibility <> 'private'
             "#
         )
         .bind(token)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         Ok(route)
     }
 
+    #[tracing::instrument(skip(self), fields(share_token = %token))]
+    async fn find_shared_updated_at(&self, token: Uuid) -> Result<Option<DateTime<Utc>>, RepositoryError> {
+        let updated_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            "SELECT updated_at FROM routes WHERE share_token = $1 AND visibility <> 'private'",
+        )
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(updated_at)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id, public_only))]
+    async fn find_share_token(&self, id: Uuid, public_only: bool) -> Result<Option<Uuid>, RepositoryError> {
+        let token = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT share_token FROM routes
+            WHERE id = $1 AND share_token IS NOT NULL
+              AND (visibility = 'public' OR (NOT $2 AND visibility <> 'private'))
+            "#,
+        )
+        .bind(id)
+        .bind(public_only)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(token)
+    }
+
     #[tracing::instrument(skip(self), fields(route_id = %id))]
     async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
         tracing::debug!("deleting route");
@@ -226,7 +376,7 @@ impl RouteRepository for PostgresRouteRepository {
         .bind(id)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
@@ -236,9 +386,10 @@ impl RouteRepository for PostgresRouteRepository {
         Ok(())
     }
 
-    #[tracing::instrument(skip(self), fields(?search, ?category_id, %order_clause, %limit, %offset))]
+    #[tracing::instrument(skip(self), fields(?owner_id, ?search, ?category_id, %order_clause, %limit, %offset))]
     async fn explore_shared(
         &self,
+        owner_id: Option<Uuid>,
         search: Option<String>,
         category_id: Option<Uuid>,
         season: Option<String>,
@@ -253,22 +404,24 @@ impl RouteRepository for PostgresRouteRepository {
             SELECT r.id, r.name,
                    jsonb_array_length(r.points)::bigint AS points_count,
                    r.created_at, r.share_token,
-                   COALESCE(l.likes_count, 0) AS likes_count,
+                   r.likes_count, r.bookmarks_count,
                    COALESCE(rt.avg_rating, 0.0) AS avg_rating,
                    COALESCE(rt.ratings_count, 0) AS ratings_count,
+                   COALESCE(c.comments_count, 0) AS comments_count,
                    COALESCE(ARRAY(SELECT category_id FROM route_categories WHERE route_id = r.id), ARRAY[]::uuid[]) AS category_ids,
-                   r.seasons
+                   r.seasons, r.start_location, r.end_location, r.cover_url, r.cover_thumbnail_url, {}
             FROM routes r
-            LEFT JOIN (SELECT route_id, COUNT(*) AS likes_count FROM route_likes GROUP BY route_id) l ON l.route_id = r.id
             LEFT JOIN (SELECT route_id, AVG(rating::float8) AS avg_rating, COUNT(*) AS ratings_count FROM route_ratings GROUP BY route_id) rt ON rt.route_id = r.id
-            WHERE r.share_token IS NOT NULL
+            LEFT JOIN (SELECT route_id, COUNT(*) AS comments_count FROM comments WHERE approved GROUP BY route_id) c ON c.route_id = r.id
+            WHERE (CASE WHEN $6::uuid IS NULL THEN r.visibility = 'public' ELSE r.user_id = $6 END)
+              AND r.archived_at IS NULL
               AND ($1::text IS NULL OR r.name ILIKE '%' || $1 || '%')
               AND ($2::uuid IS NULL OR EXISTS (SELECT 1 FROM route_categories WHERE route_id = r.id AND category_id = $2))
               AND ($3::text IS NULL OR $3 = ANY(r.seasons))
             ORDER BY {}
             LIMIT $4 OFFSET $5
             "#,
-            order_clause
+            ROUTE_PATH, order_clause
         );
 
         let rows = sqlx::query_as::<_, ExploreRouteRow>(&query)
@@ -277,9 +430,10 @@ impl RouteRepository for PostgresRouteRepository {
             .bind(season.as_deref())
             .bind(limit)
             .bind(offset)
+            .bind(owner_id)
             .fetch_all(&self.pool)
             .await
-            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+            .map_err(RepositoryError::from)?;
 
         tracing::debug!(count = rows.len(), "explored shared routes");
         Ok(rows)
@@ -292,7 +446,8 @@ impl RouteRepository for PostgresRouteRepository {
         let count: (i64,) = sqlx::query_as(
             r#"
             SELECT COUNT(*) FROM routes r
-            WHERE r.share_token IS NOT NULL
+            WHERE r.visibility = 'public'
+              AND r.archived_at IS NULL
               AND ($1::text IS NULL OR r.name ILIKE '%' || $1 || '%')
               AND ($2::uuid IS NULL OR EXISTS (SELECT 1 FROM route_categories WHERE route_id = r.id AND category_id = $2))
               AND ($3::text IS NULL OR $3 = ANY(r.seasons))
@@ -303,18 +458,115 @@ impl RouteRepository for PostgresRouteRepository {
         .bind(season.as_deref())
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(count = count.0, "counted explore shared routes");
         Ok(count.0)
     }
 
+    #[tracing::instrument(skip(self), fields(?search, ?season))]
+    async fn count_explore_by_category(
+        &self,
+        search: Option<String>,
+        season: Option<String>,
+    ) -> Result<Vec<(Uuid, i64)>, RepositoryError> {
+        let rows: Vec<(Uuid, i64)> = sqlx::query_as(
+            r#"
+            SELECT rc.category_id, COUNT(*) AS count
+            FROM routes r
+            JOIN route_categories rc ON rc.route_id = r.id
+            WHERE r.visibility = 'public'
+              AND r.archived_at IS NULL
+              AND ($1::text IS NULL OR r.name ILIKE '%' || $1 || '%')
+              AND ($2::text IS NULL OR $2 = ANY(r.seasons))
+            GROUP BY rc.category_id
+            "#,
+        )
+        .bind(search.as_deref())
+        .bind(season.as_deref())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = rows.len(), "counted explore routes by category");
+        Ok(rows)
+    }
+
+    #[tracing::instrument(skip(self, thresholds), fields(?search, ?category_id, ?season))]
+    async fn count_explore_by_difficulty(
+        &self,
+        search: Option<String>,
+        category_id: Option<Uuid>,
+        season: Option<String>,
+        thresholds: &DifficultyThresholds,
+    ) -> Result<Vec<(String, i64)>, RepositoryError> {
+        // Same scoring as DifficultyThresholds::classify
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT CASE WHEN s.score <= $8 THEN 'easy' WHEN s.score <= $9 THEN 'moderate' ELSE 'hard' END AS level,
+                   COUNT(*) AS count
+            FROM (
+                SELECT (CASE WHEN g.distance_km < $4 THEN 1 WHEN g.distance_km < $5 THEN 2 ELSE 3 END)
+                     + (CASE WHEN COALESCE(e.gain_m, 0) < $6 THEN 1 WHEN COALESCE(e.gain_m, 0) < $7 THEN 2 ELSE 3 END) AS score
+                FROM routes r
+                JOIN route_geometries g ON g.route_id = r.id
+                LEFT JOIN route_elevations e ON e.route_id = r.id
+                WHERE r.visibility = 'public'
+                  AND r.archived_at IS NULL
+                  AND ($1::text IS NULL OR r.name ILIKE '%' || $1 || '%')
+                  AND ($2::uuid IS NULL OR EXISTS (SELECT 1 FROM route_categories WHERE route_id = r.id AND category_id = $2))
+                  AND ($3::text IS NULL OR $3 = ANY(r.seasons))
+            ) s
+            GROUP BY 1
+            "#,
+        )
+        .bind(search.as_deref())
+        .bind(category_id)
+        .bind(season.as_deref())
+        .bind(thresholds.distance_easy_max_km)
+        .bind(thresholds.distance_moderate_max_km)
+        .bind(thresholds.elevation_easy_max_m)
+        .bind(thresholds.elevation_moderate_max_m)
+        .bind(thresholds.score_easy_max)
+        .bind(thresholds.score_moderate_max)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = rows.len(), "counted explore routes by difficulty");
+        Ok(rows)
+    }
+
+    #[tracing::instrument(skip(self), fields(%after, %until))]
+    async fn find_shared_between(&self, after: DateTime<Utc>, until: DateTime<Utc>) -> Result<Vec<Route>, RepositoryError> {
+        let routes = sqlx::query_as::<_, Route>(
+            r#"
+            SELECT r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token, r.visibility,
+                   COALESCE(ARRAY(SELECT category_id FROM route_categories WHERE route_id = r.id), ARRAY[]::uuid[]) AS category_ids,
+                   r.start_location, r.end_location, r.seasons, r.description, r.archived_at
+            FROM routes r
+            WHERE r.visibility = 'public'
+              AND r.archived_at IS NULL
+              AND r.shared_at > $1 AND r.shared_at <= $2
+            ORDER BY r.shared_at
+            "#,
+        )
+        .bind(after)
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = routes.len(), "found newly shared routes");
+        Ok(routes)
+    }
+
     #[tracing::instrument(skip(self))]
     async fn count_all(&self) -> Result<i64, RepositoryError> {
         let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM routes")
             .fetch_one(&self.pool)
             .await
-            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+            .map_err(RepositoryError::from)?;
 
         tracing::debug!(count = count.0, "counted all routes");
         Ok(count.0)
@@ -343,7 +595,7 @@ impl RouteRepository for PostgresRouteRepository {
         .bind(offset)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(count = rows.len(), "admin routes listed");
         Ok(rows)
@@ -368,11 +620,215 @@ impl RouteRepository for PostgresRouteRepository {
         .bind(&end_location)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(route_id = %id, "route locations updated");
         Ok(())
     }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id))]
+    async fn set_archived_at(&self, id: Uuid, archived_at: Option<DateTime<Utc>>) -> Result<(), RepositoryError> {
+        tracing::debug!(?archived_at, "setting route archived_at");
+
+        let result = sqlx::query("UPDATE routes SET archived_at = $2 WHERE id = $1")
+            .bind(id)
+            .bind(archived_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id))]
+    async fn find_comment_policy(&self, id: Uuid) -> Result<Option<String>, RepositoryError> {
+        let policy: Option<(String,)> = sqlx::query_as("SELECT comment_policy FROM routes WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(policy.map(|(p,)| p))
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id))]
+    async fn set_comment_policy(&self, id: Uuid, policy: &str) -> Result<(), RepositoryError> {
+        tracing::debug!(policy, "setting route comment policy");
+
+        let result = sqlx::query("UPDATE routes SET comment_policy = $2 WHERE id = $1")
+            .bind(id)
+            .bind(policy)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, cover), fields(route_id = %id))]
+    async fn set_cover(&self, id: Uuid, cover: Option<RouteCover>) -> Result<(), RepositoryError> {
+        tracing::debug!(has_cover = cover.is_some(), "setting route cover");
+
+        let (url, thumbnail_url) = cover.map(|c| (c.url, c.thumbnail_url)).unzip();
+        let result = sqlx::query("UPDATE routes SET cover_url = $2, cover_thumbnail_url = $3 WHERE id = $1")
+            .bind(id)
+            .bind(url)
+            .bind(thumbnail_url.flatten())
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn usage_by_user_id(&self, user_id: Uuid) -> Result<RouteUsage, RepositoryError> {
+        tracing::debug!("computing route usage");
+
+        let usage = sqlx::query_as::<_, RouteUsage>(
+            r#"
+            SELECT COUNT(DISTINCT r.id) AS routes,
+                   COALESCE(SUM(length(p.value->'photo'->>'original')), 0)::BIGINT AS photo_bytes
+            FROM routes r
+            LEFT JOIN LATERAL jsonb_array_elements(r.points) p
+                ON p.value->'photo'->>'original' LIKE 'data:%'
+            WHERE r.user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(routes = usage.routes, photo_bytes = usage.photo_bytes, "route usage computed");
+        Ok(usage)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn storage_by_user_id(&self, user_id: Uuid) -> Result<StorageUsage, RepositoryError> {
+        tracing::debug!("computing storage usage");
+
+        sqlx::query_as::<_, StorageUsage>(
+            r#"
+            SELECT COUNT(*) AS objects, COALESCE(SUM(size_bytes), 0)::BIGINT AS bytes
+            FROM photo_storage_objects
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn storage_totals(&self) -> Result<StorageTotals, RepositoryError> {
+        tracing::debug!("computing storage totals");
+
+        sqlx::query_as::<_, StorageTotals>(
+            r#"
+            SELECT COUNT(DISTINCT user_id) AS users, COUNT(*) AS objects, COALESCE(SUM(size_bytes), 0)::BIGINT AS bytes
+            FROM photo_storage_objects
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn top_storage_users(&self, limit: i64) -> Result<Vec<UserStorageUsage>, RepositoryError> {
+        tracing::debug!("finding top storage users");
+
+        sqlx::query_as::<_, UserStorageUsage>(
+            r#"
+            SELECT user_id, COUNT(*) AS objects, SUM(size_bytes)::BIGINT AS bytes
+            FROM photo_storage_objects
+            GROUP BY user_id
+            ORDER BY bytes DESC, user_id
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_quota_override(&self, user_id: Uuid) -> Result<Option<RouteQuota>, RepositoryError> {
+        tracing::debug!("finding route quota override");
+
+        sqlx::query_as::<_, RouteQuota>(
+            "SELECT max_routes, max_photo_bytes FROM route_quota_overrides WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn set_quota_override(&self, user_id: Uuid, quota: Option<RouteQuota>) -> Result<(), RepositoryError> {
+        tracing::debug!(?quota, "setting route quota override");
+
+        match quota {
+            Some(quota) => sqlx::query(
+                r#"
+                INSERT INTO route_quota_overrides (user_id, max_routes, max_photo_bytes, updated_at)
+                VALUES ($1, $2, $3, NOW())
+                ON CONFLICT (user_id)
+                DO UPDATE SET max_routes = $2, max_photo_bytes = $3, updated_at = NOW()
+                "#,
+            )
+            .bind(user_id)
+            .bind(quota.max_routes)
+            .bind(quota.max_photo_bytes),
+            None => sqlx::query("DELETE FROM route_quota_overrides WHERE user_id = $1").bind(user_id),
+        }
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn reconcile_counters(&self) -> Result<u64, RepositoryError> {
+        tracing::debug!("reconciling route counters");
+
+        let result = sqlx::query(
+            r#"
+            UPDATE routes r
+            SET likes_count = c.likes_count, bookmarks_count = c.bookmarks_count
+            FROM (
+                SELECT id,
+                       (SELECT COUNT(*) FROM route_likes WHERE route_id = routes.id) AS likes_count,
+                       (SELECT COUNT(*) FROM route_bookmarks WHERE route_id = routes.id) AS bookmarks_count
+                FROM routes
+            ) c
+            WHERE c.id = r.id
+              AND (r.likes_count <> c.likes_count OR r.bookmarks_count <> c.bookmarks_count)
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(result.rows_affected())
+    }
 }
 
 pub struct PostgresCommentRepository {
@@ -392,8 +848,8 @@ impl CommentRepository for PostgresCommentRepository {
 
         sqlx::query(
             r#"
-            INSERT INTO comments (id, route_id, user_id, author_name, text, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO comments (id, route_id, user_id, author_name, text, approved, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             "#,
         )
         .bind(comment.id)
@@ -401,10 +857,11 @@ impl CommentRepository for PostgresCommentRepository {
         .bind(comment.user_id)
         .bind(&comment.author_name)
         .bind(&comment.text)
+        .bind(comment.approved)
         .bind(comment.created_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(comment_id = %comment.id, "comment created successfully");
         Ok(())
@@ -416,28 +873,78 @@ impl CommentRepository for PostgresCommentRepository {
 
         let comments = sqlx::query_as::<_, Comment>(
             r#"
-            SELECT id, route_id, user_id, author_name, text, created_at
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
             FROM comments
-            WHERE route_id = $1
+            WHERE route_id = $1 AND approved
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b JOIN routes r ON r.user_id = b.blocker_id
+                  WHERE r.id = comments.route_id AND b.blocked_id = comments.user_id
+              )
             ORDER BY created_at ASC
             "#,
         )
         .bind(route_id)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(route_id = %route_id, count = comments.len(), "found comments");
         Ok(comments)
     }
 
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_pending_by_route_id(&self, route_id: Uuid) -> Result<Vec<Comment>, RepositoryError> {
+        tracing::debug!("finding pending comments by route_id");
+
+        let comments = sqlx::query_as::<_, Comment>(
+            r#"
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
+            FROM comments
+            WHERE route_id = $1 AND NOT approved
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b JOIN routes r ON r.user_id = b.blocker_id
+                  WHERE r.id = comments.route_id AND b.blocked_id = comments.user_id
+              )
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(route_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(route_id = %route_id, count = comments.len(), "found pending comments");
+        Ok(comments)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Comment>, RepositoryError> {
+        tracing::debug!("finding comments by user_id");
+
+        let comments = sqlx::query_as::<_, Comment>(
+            r#"
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
+            FROM comments
+            WHERE user_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = comments.len(), "found comments");
+        Ok(comments)
+    }
+
     #[tracing::instrument(skip(self), fields(comment_id = %id))]
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Comment>, RepositoryError> {
         tracing::debug!("finding comment by id");
 
         let comment = sqlx::query_as::<_, Comment>(
             r#"
-            SELECT id, route_id, user_id, author_name, text, created_at
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
             FROM comments
             WHERE id = $1
             "#,
@@ -445,7 +952,7 @@ impl CommentRepository for PostgresCommentRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         Ok(comment)
     }
@@ -463,7 +970,7 @@ impl CommentRepository for PostgresCommentRepository {
         .bind(id)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
@@ -473,19 +980,42 @@ impl CommentRepository for PostgresCommentRepository {
         Ok(())
     }
 
-    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
-    async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError> {
-        tracing::debug!("counting comments by route_id");
+    #[tracing::instrument(skip(self), fields(comment_id = %id))]
+    async fn approve(&self, id: Uuid) -> Result<(), RepositoryError> {
+        tracing::debug!("approving comment");
 
-        let count: (i64,) = sqlx::query_as(
-            r#"
-            SELECT COUNT(*) FROM comments WHERE route_id = $1
-            "#,
-        )
-        .bind(route_id)
+        let result = sqlx::query("UPDATE comments SET approved = TRUE WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        tracing::debug!(comment_id = %id, "comment approved");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError> {
+        tracing::debug!("counting comments by route_id");
+
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM comments
+            WHERE route_id = $1 AND approved
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b JOIN routes r ON r.user_id = b.blocker_id
+                  WHERE r.id = comments.route_id AND b.blocked_id = comments.user_id
+              )
+            "#,
+        )
+        .bind(route_id)
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(route_id = %route_id, count = count.0, "counted comments");
         Ok(count.0)
@@ -496,7 +1026,7 @@ impl CommentRepository for PostgresCommentRepository {
         let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM comments")
             .fetch_one(&self.pool)
             .await
-            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+            .map_err(RepositoryError::from)?;
 
         tracing::debug!(count = count.0, "counted all comments");
         Ok(count.0)
@@ -512,7 +1042,7 @@ impl CommentRepository for PostgresCommentRepository {
 
         let comments = sqlx::query_as::<_, Comment>(
             r#"
-            SELECT id, route_id, user_id, author_name, text, created_at
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
             FROM comments
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
@@ -522,11 +1052,101 @@ impl CommentRepository for PostgresCommentRepository {
         .bind(offset)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(count = comments.len(), "admin comments listed");
         Ok(comments)
     }
+
+    #[tracing::instrument(skip(self), fields(?route_id, %query, %limit, %offset))]
+    async fn search(
+        &self,
+        route_id: Option<Uuid>,
+        query: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Comment>, RepositoryError> {
+        tracing::debug!("searching comments");
+
+        let comments = sqlx::query_as::<_, Comment>(
+            r#"
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
+            FROM comments,
+                 websearch_to_tsquery('russian', $2) || websearch_to_tsquery('english', $2) AS q
+            WHERE search_vector @@ q
+              AND approved
+              AND ($1::uuid IS NULL OR route_id = $1)
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b JOIN routes r ON r.user_id = b.blocker_id
+                  WHERE r.id = comments.route_id AND b.blocked_id = comments.user_id
+              )
+            ORDER BY ts_rank(search_vector, q) DESC, created_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(route_id)
+        .bind(query)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = comments.len(), "comments search completed");
+        Ok(comments)
+    }
+
+    #[tracing::instrument(skip(self), fields(?route_id, %query))]
+    async fn count_search(&self, route_id: Option<Uuid>, query: &str) -> Result<i64, RepositoryError> {
+        tracing::debug!("counting comment search results");
+
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM comments,
+                 websearch_to_tsquery('russian', $2) || websearch_to_tsquery('english', $2) AS q
+            WHERE search_vector @@ q
+              AND approved
+              AND ($1::uuid IS NULL OR route_id = $1)
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b JOIN routes r ON r.user_id = b.blocker_id
+                  WHERE r.id = comments.route_id AND b.blocked_id = comments.user_id
+              )
+            "#,
+        )
+        .bind(route_id)
+        .bind(query)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = count.0, "counted comment search results");
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self, text), fields(%since, exclude_id = %exclude_id))]
+    async fn count_recent_by_text(
+        &self,
+        text: &str,
+        since: DateTime<Utc>,
+        exclude_id: Uuid,
+    ) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM comments
+            WHERE md5(text) = md5($1) AND text = $1 AND created_at >= $2 AND id <> $3
+            "#,
+        )
+        .bind(text)
+        .bind(since)
+        .bind(exclude_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = count.0, "counted recent comments with the same text");
+        Ok(count.0)
+    }
 }
 
 pub struct PostgresLikeRepository {
@@ -556,7 +1176,7 @@ impl LikeRepository for PostgresLikeRepository {
         .bind(like.created_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(like_id = %like.id, "route like created successfully");
         Ok(())
@@ -580,7 +1200,7 @@ impl LikeRepository for PostgresLikeRepository {
         .bind(user_id)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
@@ -609,7 +1229,7 @@ impl LikeRepository for PostgresLikeRepository {
         .bind(user_id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         Ok(like)
     }
@@ -618,18 +1238,16 @@ impl LikeRepository for PostgresLikeRepository {
     async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError> {
         tracing::debug!("counting likes by route_id");
 
-        let count: (i64,) = sqlx::query_as(
-            r#"
-            SELECT COUNT(*) FROM route_likes WHERE route_id = $1
-            "#,
-        )
-        .bind(route_id)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        // Maintained by a trigger on route_likes
+        let count: Option<(i64,)> = sqlx::query_as("SELECT likes_count FROM routes WHERE id = $1")
+            .bind(route_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+        let count = count.map_or(0, |c| c.0);
 
-        tracing::debug!(route_id = %route_id, count = count.0, "counted likes");
-        Ok(count.0)
+        tracing::debug!(route_id = %route_id, count, "counted likes");
+        Ok(count)
     }
 }
 
@@ -663,7 +1281,7 @@ impl RatingRepository for PostgresRatingRepository {
         .bind(rating.created_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(rating_id = %rating.id, "route rating upserted successfully");
         Ok(())
@@ -687,7 +1305,7 @@ impl RatingRepository for PostgresRatingRepository {
         .bind(user_id)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
@@ -716,7 +1334,7 @@ impl RatingRepository for PostgresRatingRepository {
         .bind(user_id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         Ok(rating)
     }
@@ -735,12 +1353,33 @@ impl RatingRepository for PostgresRatingRepository {
         .bind(route_id)
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         let average = result.0.unwrap_or(0.0);
         tracing::debug!(route_id = %route_id, average, count = result.1, "rating aggregate retrieved");
         Ok((average, result.1))
     }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RouteRating>, RepositoryError> {
+        tracing::debug!("finding route ratings by user_id");
+
+        let ratings = sqlx::query_as::<_, RouteRating>(
+            r#"
+            SELECT id, route_id, user_id, rating, created_at
+            FROM route_ratings
+            WHERE user_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = ratings.len(), "found route ratings");
+        Ok(ratings)
+    }
 }
 
 pub struct PostgresSettingsRepository {
@@ -764,7 +1403,7 @@ impl SettingsRepository for PostgresSettingsRepository {
         .bind(key)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(key, found = row.is_some(), "setting value retrieved");
         Ok(row.map(|r| r.0))
@@ -785,7 +1424,7 @@ impl SettingsRepository for PostgresSettingsRepository {
         .bind(value)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(key, "setting value saved");
         Ok(())
@@ -818,7 +1457,7 @@ impl CategoryRepository for PostgresCategoryRepository {
         .bind(category.created_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(category_id = %category.id, "category created successfully");
         Ok(())
@@ -837,7 +1476,7 @@ impl CategoryRepository for PostgresCategoryRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         tracing::debug!(count = categories.len(), "found categories");
         Ok(categories)
@@ -857,7 +1496,7 @@ impl CategoryRepository for PostgresCategoryRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         Ok(category)
     }
@@ -877,7 +1516,7 @@ impl CategoryRepository for PostgresCategoryRepository {
         .bind(name)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
@@ -900,7 +1539,7 @@ impl CategoryRepository for PostgresCategoryRepository {
         .bind(id)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
@@ -911,437 +1550,2108 @@ impl CategoryRepository for PostgresCategoryRepository {
     }
 }
 
-pub struct PostgresNotificationRepository {
+pub struct PostgresBlockRepository {
     pool: PgPool,
 }
 
-impl PostgresNotificationRepository {
+impl PostgresBlockRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
 }
 
-impl NotificationRepository for PostgresNotificationRepository {
-    #[tracing::instrument(skip(self, notification), fields(notification_id = %notification.id, user_id = %notification.user_id))]
-    async fn create(&self, notification: &Notification) -> Result<(), RepositoryError> {
-        tracing::debug!("creating notification");
+impl BlockRepository for PostgresBlockRepository {
+    #[tracing::instrument(skip(self, block), fields(blocker_id = %block.blocker_id, blocked_id = %block.blocked_id))]
+    async fn create(&self, block: &UserBlock) -> Result<bool, RepositoryError> {
+        tracing::debug!("creating user block");
 
-        sqlx::query(
+        let result = sqlx::query(
             r#"
-            INSERT INTO notifications (id, user_id, notification_type, route_id, actor_name, message, is_read, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO user_blocks (blocker_id, blocked_id, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (blocker_id, blocked_id) DO NOTHING
             "#,
         )
-        .bind(notification.id)
-        .bind(notification.user_id)
-        .bind(&notification.notification_type)
-        .bind(notification.route_id)
-        .bind(&notification.actor_name)
-        .bind(&notification.message)
-        .bind(notification.is_read)
-        .bind(notification.created_at)
+        .bind(block.blocker_id)
+        .bind(block.blocked_id)
+        .bind(block.created_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip(self), fields(blocker_id = %blocker_id, blocked_id = %blocked_id))]
+    async fn delete(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<(), RepositoryError> {
+        tracing::debug!("deleting user block");
+
+        let result = sqlx::query("DELETE FROM user_blocks WHERE blocker_id = $1 AND blocked_id = $2")
+            .bind(blocker_id)
+            .bind(blocked_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
 
-        tracing::debug!(notification_id = %notification.id, "notification created successfully");
         Ok(())
     }
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id, %limit, %offset))]
-    async fn find_by_user_id(
-        &self,
-        user_id: Uuid,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<Notification>, RepositoryError> {
-        tracing::debug!("finding notifications by user_id");
+    #[tracing::instrument(skip(self), fields(blocker_id = %blocker_id, blocked_id = %blocked_id))]
+    async fn exists(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<bool, RepositoryError> {
+        let exists: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM user_blocks WHERE blocker_id = $1 AND blocked_id = $2)",
+        )
+        .bind(blocker_id)
+        .bind(blocked_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
 
-        let notifications = sqlx::query_as::<_, Notification>(
+        Ok(exists.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(blocker_id = %blocker_id))]
+    async fn find_by_blocker(&self, blocker_id: Uuid) -> Result<Vec<UserBlock>, RepositoryError> {
+        tracing::debug!("finding user blocks");
+
+        let blocks = sqlx::query_as::<_, UserBlock>(
             r#"
-            SELECT id, user_id, notification_type, route_id, actor_name, message, is_read, created_at
-            FROM notifications
-            WHERE user_id = $1
+            SELECT blocker_id, blocked_id, created_at
+            FROM user_blocks
+            WHERE blocker_id = $1
             ORDER BY created_at DESC
-            LIMIT $2 OFFSET $3
             "#,
         )
-        .bind(user_id)
-        .bind(limit)
-        .bind(offset)
+        .bind(blocker_id)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
-        tracing::debug!(user_id = %user_id, count = notifications.len(), "found notifications");
-        Ok(notifications)
+        tracing::debug!(count = blocks.len(), "user blocks found");
+        Ok(blocks)
     }
+}
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
-    async fn count_unread(&self, user_id: Uuid) -> Result<i64, RepositoryError> {
-        tracing::debug!("counting unread notifications");
-
-        let count: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND is_read = FALSE",
-        )
-        .bind(user_id)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+pub struct PostgresRouteActivityRepository {
+    pool: PgPool,
+}
 
-        tracing::debug!(user_id = %user_id, count = count.0, "counted unread notifications");
-        Ok(count.0)
+impl PostgresRouteActivityRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
     }
+}
 
-    #[tracing::instrument(skip(self), fields(notification_id = %id, user_id = %user_id))]
-    async fn mark_as_read(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError> {
-        tracing::debug!("marking notification as read");
+impl RouteActivityRepository for PostgresRouteActivityRepository {
+    #[tracing::instrument(skip(self, activity), fields(activity_id = %activity.id, route_id = %activity.route_id, kind = %activity.kind))]
+    async fn create(&self, activity: &RouteActivity) -> Result<(), RepositoryError> {
+        tracing::debug!("recording route activity");
 
-        let result = sqlx::query(
-            "UPDATE notifications SET is_read = TRUE WHERE id = $1 AND user_id = $2",
+        sqlx::query(
+            r#"
+            INSERT INTO route_activity (id, route_id, actor_id, kind, details, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
         )
-        .bind(id)
-        .bind(user_id)
+        .bind(activity.id)
+        .bind(activity.route_id)
+        .bind(activity.actor_id)
+        .bind(&activity.kind)
+        .bind(&activity.details)
+        .bind(activity.created_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
-
-        if result.rows_affected() == 0 {
-            return Err(RepositoryError::NotFound);
-        }
+        .map_err(RepositoryError::from)?;
 
-        tracing::debug!(notification_id = %id, "notification marked as read");
         Ok(())
     }
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
-    async fn mark_all_as_read(&self, user_id: Uuid) -> Result<(), RepositoryError> {
-        tracing::debug!("marking all notifications as read");
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, %limit, %offset))]
+    async fn find_by_route_id(&self, route_id: Uuid, limit: i64, offset: i64) -> Result<Vec<RouteActivity>, RepositoryError> {
+        tracing::debug!("finding route activity");
 
-        sqlx::query(
-            "UPDATE notifications SET is_read = TRUE WHERE user_id = $1 AND is_read = FALSE",
+        let activity = sqlx::query_as::<_, RouteActivity>(
+            r#"
+            SELECT id, route_id, actor_id, kind, details, created_at
+            FROM route_activity
+            WHERE route_id = $1
+            ORDER BY created_at, id
+            LIMIT $2 OFFSET $3
+            "#,
         )
-        .bind(user_id)
-        .execute(&self.pool)
+        .bind(route_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
-        tracing::debug!(user_id = %user_id, "all notifications marked as read");
-        Ok(())
+        tracing::debug!(count = activity.len(), "route activity found");
+        Ok(activity)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM route_activity WHERE route_id = $1")
+            .bind(route_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
     }
 }
 
-pub struct PostgresChatMessageRepository {
+pub struct PostgresRouteTemplateRepository {
     pool: PgPool,
 }
 
-impl PostgresChatMessageRepository {
+impl PostgresRouteTemplateRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
 }
 
-impl ChatMessageRepository for PostgresChatMessageRepository {
-    #[tracing::instrument(skip(self, message), fields(message_id = %message.id, user_id = %message.user_id, conversation_id = %message.conversation_id))]
-    async fn create(&self, message: &ChatMessage) -> Result<(), RepositoryError> {
-        tracing::debug!(role = %message.role, "creating chat message");
+impl RouteTemplateRepository for PostgresRouteTemplateRepository {
+    #[tracing::instrument(skip(self, template), fields(route_id = %template.route_id, public = template.public))]
+    async fn upsert(&self, template: &RouteTemplate) -> Result<(), RepositoryError> {
+        tracing::debug!("saving route template");
 
         sqlx::query(
             r#"
-            INSERT INTO chat_messages (id, user_id, conversation_id, role, content, actions, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO route_templates (route_id, owner_id, public, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (route_id) DO UPDATE SET public = EXCLUDED.public
             "#,
         )
-        .bind(message.id)
-        .bind(message.user_id)
-        .bind(message.conversation_id)
-        .bind(&message.role)
-        .bind(&message.content)
-        .bind(&message.actions)
-        .bind(message.created_at)
+        .bind(template.route_id)
+        .bind(template.owner_id)
+        .bind(template.public)
+        .bind(template.created_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
-        tracing::debug!(message_id = %message.id, "chat message created successfully");
         Ok(())
     }
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id, %limit))]
-    async fn find_by_conversation(
-        &self,
-        user_id: Uuid,
-        conversation_id: Uuid,
-        limit: i64,
-    ) -> Result<Vec<ChatMessage>, RepositoryError> {
-        tracing::debug!("finding chat messages by conversation");
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn delete(&self, route_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM route_templates WHERE route_id = $1")
+            .bind(route_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
 
-        let messages = sqlx::query_as::<_, ChatMessage>(
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteTemplate>, RepositoryError> {
+        sqlx::query_as::<_, RouteTemplate>(
+            "SELECT route_id, owner_id, public, created_at FROM route_templates WHERE route_id = $1",
+        )
+        .bind(route_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %limit, %offset))]
+    async fn list_visible(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<TemplateSummary>, RepositoryError> {
+        tracing::debug!("listing route templates");
+
+        let templates = sqlx::query_as::<_, TemplateSummary>(
             r#"
-            SELECT * FROM (
-                SELECT id, user_id, conversation_id, role, content, actions, created_at
-                FROM chat_messages
-                WHERE user_id = $1 AND conversation_id = $2
-                ORDER BY created_at DESC
-                LIMIT $3
-            ) sub
-            ORDER BY created_at ASC
+            SELECT t.route_id, t.owner_id, t.public, r.name, r.description, r.start_location, r.end_location,
+                   jsonb_array_length(r.points)::bigint AS points_count, t.created_at
+            FROM route_templates t
+            JOIN routes r ON r.id = t.route_id
+            WHERE t.public OR t.owner_id = $1
+            ORDER BY t.public DESC, r.name, t.route_id
+            LIMIT $2 OFFSET $3
             "#,
         )
         .bind(user_id)
-        .bind(conversation_id)
         .bind(limit)
+        .bind(offset)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
-        tracing::debug!(count = messages.len(), "found chat messages");
-        Ok(messages)
+        tracing::debug!(count = templates.len(), "route templates listed");
+        Ok(templates)
     }
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id, %limit, %offset))]
-    async fn list_conversations(
-        &self,
-        user_id: Uuid,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<ConversationSummary>, RepositoryError> {
-        tracing::debug!("listing conversations");
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_visible(&self, user_id: Uuid) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM route_templates WHERE public OR owner_id = $1")
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+}
+
+pub struct PostgresShareLinkRepository {
+    pool: PgPool,
+}
+
+impl PostgresShareLinkRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ShareLinkRepository for PostgresShareLinkRepository {
+    #[tracing::instrument(skip(self, link), fields(link_id = %link.id, route_id = %link.route_id))]
+    async fn create(&self, link: &ShareLink) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO route_share_links (id, route_id, label, created_at, revoked_at) VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(link.id)
+        .bind(link.route_id)
+        .bind(&link.label)
+        .bind(link.created_at)
+        .bind(link.revoked_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(link_id = %id))]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ShareLink>, RepositoryError> {
+        sqlx::query_as::<_, ShareLink>(
+            "SELECT id, route_id, label, created_at, revoked_at FROM route_share_links WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Vec<ShareLink>, RepositoryError> {
+        sqlx::query_as::<_, ShareLink>(
+            r#"
+            SELECT id, route_id, label, created_at, revoked_at
+            FROM route_share_links
+            WHERE route_id = $1
+            ORDER BY created_at DESC, id
+            "#,
+        )
+        .bind(route_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(link_id = %id, route_id = %route_id))]
+    async fn revoke(&self, id: Uuid, route_id: Uuid, revoked_at: DateTime<Utc>) -> Result<(), RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE route_share_links SET revoked_at = $3 WHERE id = $1 AND route_id = $2 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .bind(route_id)
+        .bind(revoked_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+pub struct PostgresRouteCollectionRepository {
+    pool: PgPool,
+}
+
+impl PostgresRouteCollectionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+const COLLECTION_COLUMNS: &str = "c.id, c.user_id, c.name, \
+    (SELECT COUNT(*) FROM route_collection_routes WHERE collection_id = c.id) AS route_count, \
+    c.created_at, c.updated_at";
+
+impl RouteCollectionRepository for PostgresRouteCollectionRepository {
+    #[tracing::instrument(skip(self, collection), fields(collection_id = %collection.id, user_id = %collection.user_id))]
+    async fn create(&self, collection: &RouteCollection) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO route_collections (id, user_id, name, created_at, updated_at) VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(collection.id)
+        .bind(collection.user_id)
+        .bind(&collection.name)
+        .bind(collection.created_at)
+        .bind(collection.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(collection_id = %id))]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RouteCollection>, RepositoryError> {
+        sqlx::query_as::<_, RouteCollection>(&format!(
+            "SELECT {} FROM route_collections c WHERE c.id = $1",
+            COLLECTION_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RouteCollection>, RepositoryError> {
+        sqlx::query_as::<_, RouteCollection>(&format!(
+            "SELECT {} FROM route_collections c WHERE c.user_id = $1 ORDER BY LOWER(c.name), c.id",
+            COLLECTION_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self, name), fields(collection_id = %id, user_id = %user_id))]
+    async fn rename(&self, id: Uuid, user_id: Uuid, name: &str, updated_at: DateTime<Utc>) -> Result<(), RepositoryError> {
+        let result = sqlx::query("UPDATE route_collections SET name = $3, updated_at = $4 WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .bind(name)
+            .bind(updated_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(collection_id = %id, user_id = %user_id))]
+    async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM route_collections WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(collection_id = %collection_id, route_id = %route_id))]
+    async fn add_route(&self, collection_id: Uuid, route_id: Uuid, added_at: DateTime<Utc>) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO route_collection_routes (collection_id, route_id, added_at) VALUES ($1, $2, $3) \
+             ON CONFLICT (collection_id, route_id) DO NOTHING",
+        )
+        .bind(collection_id)
+        .bind(route_id)
+        .bind(added_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(collection_id = %collection_id, route_id = %route_id))]
+    async fn remove_route(&self, collection_id: Uuid, route_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM route_collection_routes WHERE collection_id = $1 AND route_id = $2")
+            .bind(collection_id)
+            .bind(route_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+pub struct PostgresSavedSearchRepository {
+    pool: PgPool,
+}
+
+impl PostgresSavedSearchRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+const SAVED_SEARCH_COLUMNS: &str =
+    "id, user_id, name, search, category_id, min_lat, min_lng, max_lat, max_lng, last_checked_at, created_at";
+
+impl SavedSearchRepository for PostgresSavedSearchRepository {
+    #[tracing::instrument(skip(self, search), fields(search_id = %search.id, user_id = %search.user_id))]
+    async fn create(&self, search: &SavedSearch) -> Result<(), RepositoryError> {
+        tracing::debug!("creating saved search");
+
+        sqlx::query(
+            r#"
+            INSERT INTO saved_searches (id, user_id, name, search, category_id, min_lat, min_lng, max_lat, max_lng, last_checked_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(search.id)
+        .bind(search.user_id)
+        .bind(&search.name)
+        .bind(&search.search)
+        .bind(search.category_id)
+        .bind(search.min_lat)
+        .bind(search.min_lng)
+        .bind(search.max_lat)
+        .bind(search.max_lng)
+        .bind(search.last_checked_at)
+        .bind(search.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<SavedSearch>, RepositoryError> {
+        sqlx::query_as::<_, SavedSearch>(&format!(
+            "SELECT {} FROM saved_searches WHERE user_id = $1 ORDER BY created_at DESC",
+            SAVED_SEARCH_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_by_user_id(&self, user_id: Uuid) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM saved_searches WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(search_id = %id, user_id = %user_id))]
+    async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM saved_searches WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn find_all(&self) -> Result<Vec<SavedSearch>, RepositoryError> {
+        sqlx::query_as::<_, SavedSearch>(&format!("SELECT {} FROM saved_searches", SAVED_SEARCH_COLUMNS))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(search_id = %id))]
+    async fn mark_checked(
+        &self,
+        id: Uuid,
+        previous: DateTime<Utc>,
+        checked_at: DateTime<Utc>,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("UPDATE saved_searches SET last_checked_at = $3 WHERE id = $1 AND last_checked_at = $2")
+            .bind(id)
+            .bind(previous)
+            .bind(checked_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+pub struct PostgresSpamFlagRepository {
+    pool: PgPool,
+}
+
+impl PostgresSpamFlagRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SpamFlagRepository for PostgresSpamFlagRepository {
+    #[tracing::instrument(skip(self, flag), fields(flag_id = %flag.id, content_type = %flag.content_type, content_id = %flag.content_id))]
+    async fn create(&self, flag: &SpamFlag) -> Result<(), RepositoryError> {
+        tracing::debug!("creating spam flag");
+
+        sqlx::query(
+            r#"
+            INSERT INTO spam_flags (id, content_type, content_id, user_id, reasons, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(flag.id)
+        .bind(&flag.content_type)
+        .bind(flag.content_id)
+        .bind(flag.user_id)
+        .bind(&flag.reasons)
+        .bind(flag.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(%reviewed, %limit, %offset))]
+    async fn list(&self, reviewed: bool, limit: i64, offset: i64) -> Result<Vec<SpamFlag>, RepositoryError> {
+        tracing::debug!("listing spam flags");
+
+        let flags = sqlx::query_as::<_, SpamFlag>(
+            r#"
+            SELECT id, content_type, content_id, user_id, reasons, reviewed_by, reviewed_at, created_at
+            FROM spam_flags
+            WHERE (reviewed_at IS NOT NULL) = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(reviewed)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = flags.len(), "spam flags found");
+        Ok(flags)
+    }
+
+    #[tracing::instrument(skip(self), fields(%reviewed))]
+    async fn count(&self, reviewed: bool) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM spam_flags WHERE (reviewed_at IS NOT NULL) = $1")
+            .bind(reviewed)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(flag_id = %id, reviewed_by = %reviewed_by))]
+    async fn mark_reviewed(&self, id: Uuid, reviewed_by: Uuid) -> Result<(), RepositoryError> {
+        tracing::debug!("marking spam flag reviewed");
+
+        let result = sqlx::query(
+            "UPDATE spam_flags SET reviewed_by = $2, reviewed_at = NOW() WHERE id = $1 AND reviewed_at IS NULL",
+        )
+        .bind(id)
+        .bind(reviewed_by)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+pub struct PostgresTranslationRepository {
+    pool: PgPool,
+}
+
+impl PostgresTranslationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl TranslationRepository for PostgresTranslationRepository {
+    #[tracing::instrument(skip(self), fields(%locale))]
+    async fn find_by_locale(&self, locale: &str) -> Result<Vec<Translation>, RepositoryError> {
+        tracing::debug!("finding translations by locale");
+
+        let translations = sqlx::query_as::<_, Translation>(
+            "SELECT key, locale, value, updated_at FROM translations WHERE locale = $1 ORDER BY key",
+        )
+        .bind(locale)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = translations.len(), "translations found");
+        Ok(translations)
+    }
+
+    #[tracing::instrument(skip(self, translation), fields(key = %translation.key, locale = %translation.locale))]
+    async fn upsert(&self, translation: &Translation) -> Result<(), RepositoryError> {
+        tracing::debug!("saving translation");
+
+        sqlx::query(
+            r#"
+            INSERT INTO translations (key, locale, value, updated_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (key, locale) DO UPDATE SET value = $3, updated_at = $4
+            "#,
+        )
+        .bind(&translation.key)
+        .bind(&translation.locale)
+        .bind(&translation.value)
+        .bind(translation.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!("translation saved");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(%key, %locale))]
+    async fn delete(&self, key: &str, locale: &str) -> Result<(), RepositoryError> {
+        tracing::debug!("deleting translation");
+
+        let result = sqlx::query("DELETE FROM translations WHERE key = $1 AND locale = $2")
+            .bind(key)
+            .bind(locale)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        tracing::debug!("translation deleted");
+        Ok(())
+    }
+}
+
+pub struct PostgresNotificationRepository {
+    pool: PgPool,
+}
+
+impl PostgresNotificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl NotificationRepository for PostgresNotificationRepository {
+    #[tracing::instrument(skip(self, notification), fields(notification_id = %notification.id, user_id = %notification.user_id))]
+    async fn create(&self, notification: &Notification) -> Result<(), RepositoryError> {
+        tracing::debug!("creating notification");
+
+        sqlx::query(
+            r#"
+            INSERT INTO notifications (id, user_id, notification_type, route_id, actor_name, message, template_key, params, is_read, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(notification.id)
+        .bind(notification.user_id)
+        .bind(&notification.notification_type)
+        .bind(notification.route_id)
+        .bind(&notification.actor_name)
+        .bind(&notification.message)
+        .bind(&notification.template_key)
+        .bind(&notification.params)
+        .bind(notification.is_read)
+        .bind(notification.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(notification_id = %notification.id, "notification created successfully");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %limit, %offset))]
+    async fn find_by_user_id(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Notification>, RepositoryError> {
+        tracing::debug!("finding notifications by user_id");
+
+        let notifications = sqlx::query_as::<_, Notification>(
+            r#"
+            SELECT id, user_id, notification_type, route_id, actor_name, message, template_key, params, is_read, created_at
+            FROM notifications
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = notifications.len(), "found notifications");
+        Ok(notifications)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_unread(&self, user_id: Uuid) -> Result<i64, RepositoryError> {
+        tracing::debug!("counting unread notifications");
+
+        let count: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND is_read = FALSE",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = count.0, "counted unread notifications");
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_by_user_id(&self, user_id: Uuid) -> Result<i64, RepositoryError> {
+        tracing::debug!("counting notifications");
+
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM notifications WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = count.0, "counted notifications");
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(notification_id = %id, user_id = %user_id))]
+    async fn mark_as_read(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError> {
+        tracing::debug!("marking notification as read");
+
+        let result = sqlx::query(
+            "UPDATE notifications SET is_read = TRUE WHERE id = $1 AND user_id = $2",
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        tracing::debug!(notification_id = %id, "notification marked as read");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn mark_all_as_read(&self, user_id: Uuid) -> Result<(), RepositoryError> {
+        tracing::debug!("marking all notifications as read");
+
+        sqlx::query(
+            "UPDATE notifications SET is_read = TRUE WHERE user_id = $1 AND is_read = FALSE",
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, "all notifications marked as read");
+        Ok(())
+    }
+}
+
+pub struct PostgresChatMessageRepository {
+    pool: PgPool,
+}
+
+impl PostgresChatMessageRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Conversations exist only through their messages.
+    async fn ensure_conversation_exists(&self, user_id: Uuid, conversation_id: Uuid) -> Result<(), RepositoryError> {
+        let exists: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM chat_messages WHERE user_id = $1 AND conversation_id = $2)",
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if exists.0 { Ok(()) } else { Err(RepositoryError::NotFound) }
+    }
+}
+
+impl ChatMessageRepository for PostgresChatMessageRepository {
+    #[tracing::instrument(skip(self, message), fields(message_id = %message.id, user_id = %message.user_id, conversation_id = %message.conversation_id))]
+    async fn create(&self, message: &ChatMessage) -> Result<(), RepositoryError> {
+        tracing::debug!(role = %message.role, "creating chat message");
+
+        sqlx::query(
+            r#"
+            INSERT INTO chat_messages (id, user_id, conversation_id, role, content, actions, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(message.id)
+        .bind(message.user_id)
+        .bind(message.conversation_id)
+        .bind(&message.role)
+        .bind(&message.content)
+        .bind(&message.actions)
+        .bind(message.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(message_id = %message.id, "chat message created successfully");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id, %limit))]
+    async fn find_by_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<ChatMessage>, RepositoryError> {
+        tracing::debug!("finding chat messages by conversation");
+
+        let messages = sqlx::query_as::<_, ChatMessage>(
+            r#"
+            SELECT * FROM (
+                SELECT id, user_id, conversation_id, role, content, actions, created_at
+                FROM chat_messages
+                WHERE user_id = $1 AND conversation_id = $2
+                ORDER BY created_at DESC
+                LIMIT $3
+            ) sub
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = messages.len(), "found chat messages");
+        Ok(messages)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %limit, %offset))]
+    async fn list_conversations(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+        include_archived: bool,
+    ) -> Result<Vec<ConversationSummary>, RepositoryError> {
+        tracing::debug!(include_archived, "listing conversations");
+
+        let rows = sqlx::query_as::<_, ConversationSummary>(
+            r#"
+            SELECT
+                c.conversation_id,
+                COALESCE(last_msg.content, '') AS last_message,
+                c.message_count,
+                c.created_at,
+                c.updated_at,
+                COALESCE(title_msg.content, '') AS title,
+                c.archived,
+                c.pinned
+            FROM (
+                SELECT
+                    g.*,
+                    COALESCE(cc.archived, FALSE) AS archived,
+                    COALESCE(cc.pinned, FALSE) AS pinned
+                FROM (
+                    SELECT
+                        conversation_id,
+                        COUNT(*)::bigint AS message_count,
+                        MIN(created_at) AS created_at,
+                        MAX(created_at) AS updated_at
+                    FROM chat_messages
+                    WHERE user_id = $1
+                    GROUP BY conversation_id
+                ) g
+                LEFT JOIN chat_conversations cc
+                    ON cc.user_id = $1 AND cc.conversation_id = g.conversation_id
+                WHERE $4 OR NOT COALESCE(cc.archived, FALSE)
+                ORDER BY pinned DESC, g.updated_at DESC
+                LIMIT $2 OFFSET $3
+            ) c
+            LEFT JOIN LATERAL (
+                SELECT content FROM chat_messages
+                WHERE conversation_id = c.conversation_id AND user_id = $1
+                ORDER BY created_at DESC LIMIT 1
+            ) last_msg ON true
+            LEFT JOIN LATERAL (
+                SELECT LEFT(content, 100) AS content FROM chat_messages
+                WHERE conversation_id = c.conversation_id AND user_id = $1 AND role = 'user'
+                ORDER BY created_at ASC LIMIT 1
+            ) title_msg ON true
+            ORDER BY c.pinned DESC, c.updated_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = rows.len(), "listed conversations");
+        Ok(rows)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn delete_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::debug!("deleting conversation");
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM chat_messages
+            WHERE user_id = $1 AND conversation_id = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        sqlx::query("DELETE FROM chat_conversations WHERE user_id = $1 AND conversation_id = $2")
+            .bind(user_id)
+            .bind(conversation_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        tracing::debug!(rows_deleted = result.rows_affected(), "conversation deleted");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, message_id = %message_id))]
+    async fn delete_message(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::debug!("deleting chat message");
+
+        let result = sqlx::query(
+            "DELETE FROM chat_messages WHERE id = $1 AND user_id = $2 AND conversation_id = $3",
+        )
+        .bind(message_id)
+        .bind(user_id)
+        .bind(conversation_id)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        tracing::debug!(message_id = %message_id, "chat message deleted");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_conversations(&self, user_id: Uuid, include_archived: bool) -> Result<i64, RepositoryError> {
+        tracing::debug!(include_archived, "counting conversations");
+
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM (SELECT DISTINCT conversation_id FROM chat_messages WHERE user_id = $1) g
+            LEFT JOIN chat_conversations cc
+                ON cc.user_id = $1 AND cc.conversation_id = g.conversation_id
+            WHERE $2 OR NOT COALESCE(cc.archived, FALSE)
+            "#,
+        )
+        .bind(user_id)
+        .bind(include_archived)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = count.0, "counted conversations");
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn set_conversation_flags(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        archived: Option<bool>,
+        pinned: Option<bool>,
+    ) -> Result<ConversationFlags, RepositoryError> {
+        tracing::debug!(?archived, ?pinned, "updating conversation flags");
+
+        self.ensure_conversation_exists(user_id, conversation_id).await?;
+
+        sqlx::query_as::<_, ConversationFlags>(
+            r#"
+            INSERT INTO chat_conversations (user_id, conversation_id, archived, pinned, updated_at)
+            VALUES ($1, $2, COALESCE($3, FALSE), COALESCE($4, FALSE), NOW())
+            ON CONFLICT (user_id, conversation_id) DO UPDATE SET
+                archived = COALESCE($3, chat_conversations.archived),
+                pinned = COALESCE($4, chat_conversations.pinned),
+                updated_at = NOW()
+            RETURNING archived, pinned
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .bind(archived)
+        .bind(pinned)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self, token), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn share_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        token: Uuid,
+    ) -> Result<Uuid, RepositoryError> {
+        tracing::debug!("sharing conversation");
+
+        self.ensure_conversation_exists(user_id, conversation_id).await?;
+
+        let row: (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO chat_conversations (user_id, conversation_id, share_token, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (user_id, conversation_id) DO UPDATE SET
+                share_token = COALESCE(chat_conversations.share_token, EXCLUDED.share_token),
+                updated_at = NOW()
+            RETURNING share_token
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .bind(token)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(row.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn unshare_conversation(&self, user_id: Uuid, conversation_id: Uuid) -> Result<(), RepositoryError> {
+        tracing::debug!("unsharing conversation");
+
+        sqlx::query(
+            "UPDATE chat_conversations SET share_token = NULL, updated_at = NOW() WHERE user_id = $1 AND conversation_id = $2",
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, token))]
+    async fn find_shared_conversation(&self, token: Uuid) -> Result<Option<(Uuid, Uuid)>, RepositoryError> {
+        tracing::debug!("finding shared conversation");
+
+        sqlx::query_as::<_, (Uuid, Uuid)>(
+            "SELECT user_id, conversation_id FROM chat_conversations WHERE share_token = $1",
+        )
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_all_by_user(&self, user_id: Uuid) -> Result<Vec<ChatMessage>, RepositoryError> {
+        tracing::debug!("finding all chat messages by user");
+
+        let messages = sqlx::query_as::<_, ChatMessage>(
+            r#"
+            SELECT id, user_id, conversation_id, role, content, actions, created_at
+            FROM chat_messages
+            WHERE user_id = $1
+            ORDER BY conversation_id, created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = messages.len(), "found chat messages");
+        Ok(messages)
+    }
+
+    #[tracing::instrument(skip(self, action), fields(action_id = %action.id, user_id = %action.user_id, tool = %action.tool))]
+    async fn create_pending_action(&self, action: &PendingChatAction) -> Result<(), RepositoryError> {
+        tracing::debug!("creating pending chat action");
+
+        sqlx::query(
+            r#"
+            INSERT INTO chat_pending_actions (id, user_id, conversation_id, tool, arguments, summary, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(action.id)
+        .bind(action.user_id)
+        .bind(action.conversation_id)
+        .bind(&action.tool)
+        .bind(&action.arguments)
+        .bind(&action.summary)
+        .bind(action.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, action_id = %action_id))]
+    async fn take_pending_action(
+        &self,
+        user_id: Uuid,
+        action_id: Uuid,
+    ) -> Result<Option<PendingChatAction>, RepositoryError> {
+        tracing::debug!("taking pending chat action");
+
+        sqlx::query_as::<_, PendingChatAction>(
+            r#"
+            DELETE FROM chat_pending_actions
+            WHERE id = $1 AND user_id = $2
+            RETURNING id, user_id, conversation_id, tool, arguments, summary, created_at
+            "#,
+        )
+        .bind(action_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_pending_actions_before(&self, before: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM chat_pending_actions WHERE created_at < $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_chat_language(&self, user_id: Uuid) -> Result<Option<String>, RepositoryError> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT language FROM chat_preferences WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(row.map(|(language,)| language))
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn set_chat_language(&self, user_id: Uuid, language: Option<String>) -> Result<(), RepositoryError> {
+        tracing::debug!(?language, "saving chat language");
+
+        match language {
+            Some(language) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO chat_preferences (user_id, language, updated_at)
+                    VALUES ($1, $2, NOW())
+                    ON CONFLICT (user_id) DO UPDATE SET language = EXCLUDED.language, updated_at = NOW()
+                    "#,
+                )
+                .bind(user_id)
+                .bind(language)
+                .execute(&self.pool)
+                .await
+                .map_err(RepositoryError::from)?;
+            }
+            None => {
+                sqlx::query("DELETE FROM chat_preferences WHERE user_id = $1")
+                    .bind(user_id)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(RepositoryError::from)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, turn), fields(user_id = %turn.user_id, conversation_id = %turn.conversation_id))]
+    async fn record_turn(&self, turn: &ChatTurnRecord) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO chat_turns (id, user_id, conversation_id, tool_calls, latency_ms, error, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(turn.id)
+        .bind(turn.user_id)
+        .bind(turn.conversation_id)
+        .bind(serde_json::to_value(&turn.tool_calls).unwrap())
+        .bind(turn.latency_ms)
+        .bind(&turn.error)
+        .bind(turn.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(latency_ms = turn.latency_ms, failed = turn.error.is_some(), "chat turn recorded");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, reason), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn flag_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        reason: Option<String>,
+        review_consent: bool,
+    ) -> Result<(), RepositoryError> {
+        tracing::debug!(review_consent, "flagging conversation");
+
+        self.ensure_conversation_exists(user_id, conversation_id).await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO chat_conversations (user_id, conversation_id, flagged_at, flag_reason, review_consent, updated_at)
+            VALUES ($1, $2, NOW(), $3, $4, NOW())
+            ON CONFLICT (user_id, conversation_id) DO UPDATE SET
+                flagged_at = NOW(),
+                flag_reason = EXCLUDED.flag_reason,
+                review_consent = EXCLUDED.review_consent,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .bind(reason)
+        .bind(review_consent)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_flagged_conversations(&self, limit: i64, offset: i64) -> Result<Vec<FlaggedConversation>, RepositoryError> {
+        sqlx::query_as::<_, FlaggedConversation>(
+            r#"
+            SELECT c.user_id, c.conversation_id, c.flag_reason AS reason, c.review_consent,
+                   (SELECT COUNT(*) FROM chat_messages m
+                    WHERE m.user_id = c.user_id AND m.conversation_id = c.conversation_id) AS message_count,
+                   c.flagged_at
+            FROM chat_conversations c
+            WHERE c.flagged_at IS NOT NULL
+            ORDER BY c.flagged_at DESC
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn count_flagged_conversations(&self) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM chat_conversations WHERE flagged_at IS NOT NULL")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn find_flagged_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+    ) -> Result<Option<FlaggedConversation>, RepositoryError> {
+        sqlx::query_as::<_, FlaggedConversation>(
+            r#"
+            SELECT c.user_id, c.conversation_id, c.flag_reason AS reason, c.review_consent,
+                   (SELECT COUNT(*) FROM chat_messages m
+                    WHERE m.user_id = c.user_id AND m.conversation_id = c.conversation_id) AS message_count,
+                   c.flagged_at
+            FROM chat_conversations c
+            WHERE c.user_id = $1 AND c.conversation_id = $2 AND c.flagged_at IS NOT NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+}
+
+pub struct PostgresBookmarkRepository {
+    pool: PgPool,
+}
+
+impl PostgresBookmarkRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl BookmarkRepository for PostgresBookmarkRepository {
+    #[tracing::instrument(skip(self, bookmark), fields(bookmark_id = %bookmark.id, route_id = %bookmark.route_id, user_id = %bookmark.user_id))]
+    async fn create(&self, bookmark: &RouteBookmark) -> Result<(), RepositoryError> {
+        tracing::debug!("creating route bookmark");
+
+        sqlx::query(
+            r#"
+            INSERT INTO route_bookmarks (id, route_id, user_id, created_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(bookmark.id)
+        .bind(bookmark.route_id)
+        .bind(bookmark.user_id)
+        .bind(bookmark.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(bookmark_id = %bookmark.id, "route bookmark created successfully");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
+    async fn delete_by_route_and_user(
+        &self,
+        route_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), RepositoryError> {
+        tracing::debug!("deleting route bookmark");
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM route_bookmarks
+            WHERE route_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(route_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        tracing::debug!("route bookmark deleted successfully");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
+    async fn find_by_route_and_user(
+        &self,
+        route_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<RouteBookmark>, RepositoryError> {
+        tracing::debug!("finding route bookmark by route and user");
+
+        let bookmark = sqlx::query_as::<_, RouteBookmark>(
+            r#"
+            SELECT id, route_id, user_id, created_at
+            FROM route_bookmarks
+            WHERE route_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(route_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(bookmark)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<ExploreRouteRow>, RepositoryError> {
+        tracing::debug!("finding bookmarked routes by user_id");
+
+        let rows = sqlx::query_as::<_, ExploreRouteRow>(&format!(
+            r#"
+            SELECT
+                r.id,
+                r.name,
+                jsonb_array_length(r.points)::bigint AS points_count,
+                r.created_at,
+                r.share_token,
+                r.likes_count,
+                r.bookmarks_count,
+                COALESCE((SELECT AVG(rating::float8) FROM route_ratings WHERE route_id = r.id), 0.0) AS avg_rating,
+                COALESCE((SELECT COUNT(*) FROM route_ratings WHERE route_id = r.id), 0) AS ratings_count,
+                COALESCE((SELECT COUNT(*) FROM comments WHERE route_id = r.id AND approved), 0) AS comments_count,
+                COALESCE(ARRAY(SELECT category_id FROM route_categories WHERE route_id = r.id), ARRAY[]::uuid[]) AS category_ids,
+                r.seasons,
+                r.start_location,
+                r.end_location,
+                r.cover_url,
+                r.cover_thumbnail_url,
+                {}
+            FROM route_bookmarks rb
+            JOIN routes r ON r.id = rb.route_id
+            WHERE rb.user_id = $1
+            ORDER BY rb.created_at DESC
+            "#,
+            ROUTE_PATH
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = rows.len(), "found bookmarked routes");
+        Ok(rows)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_user_ids_by_route(&self, route_id: Uuid) -> Result<Vec<Uuid>, RepositoryError> {
+        tracing::debug!("finding bookmarking users by route_id");
+
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            r#"
+            SELECT user_id
+            FROM route_bookmarks
+            WHERE route_id = $1
+            "#,
+        )
+        .bind(route_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(route_id = %route_id, count = rows.len(), "found bookmarking users");
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+}
+
+pub struct PostgresNotificationPreferenceRepository {
+    pool: PgPool,
+}
+
+impl PostgresNotificationPreferenceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl NotificationPreferenceRepository for PostgresNotificationPreferenceRepository {
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_disabled_types(&self, user_id: Uuid) -> Result<Vec<String>, RepositoryError> {
+        tracing::debug!("finding disabled notification types");
+
+        let rows: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT notification_type
+            FROM notification_preferences
+            WHERE user_id = $1 AND enabled = FALSE
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(rows.into_iter().map(|(t,)| t).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %notification_type, %enabled))]
+    async fn set_enabled(&self, user_id: Uuid, notification_type: &str, enabled: bool) -> Result<(), RepositoryError> {
+        tracing::debug!("setting notification preference");
+
+        sqlx::query(
+            r#"
+            INSERT INTO notification_preferences (user_id, notification_type, enabled, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (user_id, notification_type)
+            DO UPDATE SET enabled = $3, updated_at = NOW()
+            "#,
+        )
+        .bind(user_id)
+        .bind(notification_type)
+        .bind(enabled)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, "notification preference saved");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, user_ids), fields(%notification_type, user_count = user_ids.len()))]
+    async fn find_opted_out(&self, notification_type: &str, user_ids: Vec<Uuid>) -> Result<Vec<Uuid>, RepositoryError> {
+        tracing::debug!("finding opted out users");
+
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            r#"
+            SELECT user_id
+            FROM notification_preferences
+            WHERE notification_type = $1 AND enabled = FALSE AND user_id = ANY($2)
+            "#,
+        )
+        .bind(notification_type)
+        .bind(&user_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = rows.len(), "found opted out users");
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+}
+
+pub struct PostgresReportRepository {
+    pool: PgPool,
+}
+
+impl PostgresReportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ReportRepository for PostgresReportRepository {
+    #[tracing::instrument(skip(self, report), fields(report_id = %report.id, route_id = %report.route_id, reporter_id = %report.reporter_id, category = %report.category))]
+    async fn create(&self, report: &RouteReport) -> Result<(), RepositoryError> {
+        tracing::debug!("creating route report");
+
+        sqlx::query(
+            r#"
+            INSERT INTO route_reports (id, route_id, reporter_id, category, details, status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(report.id)
+        .bind(report.route_id)
+        .bind(report.reporter_id)
+        .bind(&report.category)
+        .bind(&report.details)
+        .bind(&report.status)
+        .bind(report.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(report_id = %report.id, "route report created successfully");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(report_id = %id))]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RouteReport>, RepositoryError> {
+        tracing::debug!("finding route report by id");
 
-        let rows = sqlx::query_as::<_, ConversationSummary>(
+        let report = sqlx::query_as::<_, RouteReport>(
             r#"
-            SELECT
-                c.conversation_id,
-                COALESCE(last_msg.content, '') AS last_message,
-                c.message_count,
-                c.created_at,
-                c.updated_at,
-                COALESCE(title_msg.content, '') AS title
-            FROM (
-                SELECT
-                    conversation_id,
-                    COUNT(*)::bigint AS message_count,
-                    MIN(created_at) AS created_at,
-                    MAX(created_at) AS updated_at
-                FROM chat_messages
-                WHERE user_id = $1
-                GROUP BY conversation_id
-                ORDER BY MAX(created_at) DESC
-                LIMIT $2 OFFSET $3
-            ) c
-            LEFT JOIN LATERAL (
-                SELECT content FROM chat_messages
-                WHERE conversation_id = c.conversation_id AND user_id = $1
-                ORDER BY created_at DESC LIMIT 1
-            ) last_msg ON true
-            LEFT JOIN LATERAL (
-                SELECT LEFT(content, 100) AS content FROM chat_messages
-                WHERE conversation_id = c.conversation_id AND user_id = $1 AND role = 'user'
-                ORDER BY created_at ASC LIMIT 1
-            ) title_msg ON true
+            SELECT id, route_id, reporter_id, category, details, status, resolution_note, resolved_by, resolved_at, created_at
+            FROM route_reports
+            WHERE id = $1
             "#,
         )
-        .bind(user_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(report)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, reporter_id = %reporter_id))]
+    async fn find_open_by_route_and_reporter(
+        &self,
+        route_id: Uuid,
+        reporter_id: Uuid,
+    ) -> Result<Option<RouteReport>, RepositoryError> {
+        tracing::debug!("finding open route report by route and reporter");
+
+        let report = sqlx::query_as::<_, RouteReport>(
+            r#"
+            SELECT id, route_id, reporter_id, category, details, status, resolution_note, resolved_by, resolved_at, created_at
+            FROM route_reports
+            WHERE route_id = $1 AND reporter_id = $2 AND status = 'open'
+            "#,
+        )
+        .bind(route_id)
+        .bind(reporter_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(report)
+    }
+
+    #[tracing::instrument(skip(self), fields(?status, %limit, %offset))]
+    async fn list(
+        &self,
+        status: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<RouteReport>, RepositoryError> {
+        tracing::debug!("listing route reports");
+
+        let reports = sqlx::query_as::<_, RouteReport>(
+            r#"
+            SELECT id, route_id, reporter_id, category, details, status, resolution_note, resolved_by, resolved_at, created_at
+            FROM route_reports
+            WHERE ($1::text IS NULL OR status = $1)
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(&status)
         .bind(limit)
         .bind(offset)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
-        tracing::debug!(count = rows.len(), "listed conversations");
-        Ok(rows)
+        tracing::debug!(count = reports.len(), "found route reports");
+        Ok(reports)
     }
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
-    async fn delete_conversation(
+    #[tracing::instrument(skip(self), fields(?status))]
+    async fn count(&self, status: Option<String>) -> Result<i64, RepositoryError> {
+        tracing::debug!("counting route reports");
+
+        let count: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM route_reports WHERE ($1::text IS NULL OR status = $1)",
+        )
+        .bind(&status)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = count.0, "counted route reports");
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self, resolution_note), fields(report_id = %id, %status, resolved_by = %resolved_by))]
+    async fn resolve(
         &self,
-        user_id: Uuid,
-        conversation_id: Uuid,
+        id: Uuid,
+        status: &str,
+        resolved_by: Uuid,
+        resolution_note: Option<String>,
     ) -> Result<(), RepositoryError> {
-        tracing::debug!("deleting conversation");
+        tracing::debug!("resolving route report");
 
         let result = sqlx::query(
             r#"
-            DELETE FROM chat_messages
-            WHERE user_id = $1 AND conversation_id = $2
+            UPDATE route_reports
+            SET status = $2, resolved_by = $3, resolution_note = $4, resolved_at = NOW()
+            WHERE id = $1
             "#,
         )
-        .bind(user_id)
-        .bind(conversation_id)
+        .bind(id)
+        .bind(status)
+        .bind(resolved_by)
+        .bind(&resolution_note)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
         }
 
-        tracing::debug!(rows_deleted = result.rows_affected(), "conversation deleted");
+        tracing::debug!("route report resolved successfully");
         Ok(())
     }
+}
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id, message_id = %message_id))]
-    async fn delete_message(
-        &self,
-        user_id: Uuid,
-        message_id: Uuid,
-    ) -> Result<(), RepositoryError> {
-        tracing::debug!("deleting chat message");
+pub struct PostgresRouteGeometryRepository {
+    pool: PgPool,
+}
 
-        let result = sqlx::query(
-            "DELETE FROM chat_messages WHERE id = $1 AND user_id = $2",
+impl PostgresRouteGeometryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl RouteGeometryRepository for PostgresRouteGeometryRepository {
+    #[tracing::instrument(skip(self, geometry), fields(route_id = %geometry.route_id, segment_count = geometry.segments.len()))]
+    async fn upsert(&self, geometry: &RouteGeometry) -> Result<(), RepositoryError> {
+        tracing::debug!("upserting route geometry");
+
+        sqlx::query(
+            r#"
+            INSERT INTO route_geometries (route_id, segments, distance_km, route_updated_at, computed_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (route_id)
+            DO UPDATE SET segments = $2, distance_km = $3, route_updated_at = $4, computed_at = $5
+            "#,
         )
-        .bind(message_id)
-        .bind(user_id)
+        .bind(geometry.route_id)
+        .bind(serde_json::to_value(&geometry.segments).unwrap())
+        .bind(geometry.distance_km)
+        .bind(geometry.route_updated_at)
+        .bind(geometry.computed_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
-
-        if result.rows_affected() == 0 {
-            return Err(RepositoryError::NotFound);
-        }
+        .map_err(RepositoryError::from)?;
 
-        tracing::debug!(message_id = %message_id, "chat message deleted");
+        tracing::debug!(route_id = %geometry.route_id, "route geometry upserted successfully");
         Ok(())
     }
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
-    async fn count_conversations(&self, user_id: Uuid) -> Result<i64, RepositoryError> {
-        tracing::debug!("counting conversations");
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteGeometry>, RepositoryError> {
+        tracing::debug!("finding route geometry");
 
-        let count: (i64,) = sqlx::query_as(
-            "SELECT COUNT(DISTINCT conversation_id) FROM chat_messages WHERE user_id = $1",
+        let geometry = sqlx::query_as::<_, RouteGeometry>(
+            r#"
+            SELECT route_id, segments, distance_km, route_updated_at, computed_at
+            FROM route_geometries
+            WHERE route_id = $1
+            "#,
         )
-        .bind(user_id)
-        .fetch_one(&self.pool)
+        .bind(route_id)
+        .fetch_optional(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
-        tracing::debug!(user_id = %user_id, count = count.0, "counted conversations");
-        Ok(count.0)
+        Ok(geometry)
     }
 }
 
-pub struct PostgresBookmarkRepository {
+#[derive(Clone)]
+pub struct PostgresRouteElevationRepository {
     pool: PgPool,
 }
 
-impl PostgresBookmarkRepository {
+impl PostgresRouteElevationRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
 }
 
-impl BookmarkRepository for PostgresBookmarkRepository {
-    #[tracing::instrument(skip(self, bookmark), fields(bookmark_id = %bookmark.id, route_id = %bookmark.route_id, user_id = %bookmark.user_id))]
-    async fn create(&self, bookmark: &RouteBookmark) -> Result<(), RepositoryError> {
-        tracing::debug!("creating route bookmark");
+impl RouteElevationRepository for PostgresRouteElevationRepository {
+    #[tracing::instrument(skip(self, elevation), fields(route_id = %elevation.route_id, sample_count = elevation.samples.len()))]
+    async fn upsert(&self, elevation: &RouteElevation) -> Result<(), RepositoryError> {
+        tracing::debug!("upserting route elevation");
 
         sqlx::query(
             r#"
-            INSERT INTO route_bookmarks (id, route_id, user_id, created_at)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO route_elevations (route_id, samples, gain_m, loss_m, min_m, max_m, route_updated_at, computed_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (route_id)
+            DO UPDATE SET samples = $2, gain_m = $3, loss_m = $4, min_m = $5, max_m = $6,
+                          route_updated_at = $7, computed_at = $8
             "#,
         )
-        .bind(bookmark.id)
-        .bind(bookmark.route_id)
-        .bind(bookmark.user_id)
-        .bind(bookmark.created_at)
+        .bind(elevation.route_id)
+        .bind(serde_json::to_value(&elevation.samples).unwrap())
+        .bind(elevation.gain_m)
+        .bind(elevation.loss_m)
+        .bind(elevation.min_m)
+        .bind(elevation.max_m)
+        .bind(elevation.route_updated_at)
+        .bind(elevation.computed_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
-        tracing::debug!(bookmark_id = %bookmark.id, "route bookmark created successfully");
+        tracing::debug!(route_id = %elevation.route_id, "route elevation upserted successfully");
         Ok(())
     }
 
-    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
-    async fn delete_by_route_and_user(
-        &self,
-        route_id: Uuid,
-        user_id: Uuid,
-    ) -> Result<(), RepositoryError> {
-        tracing::debug!("deleting route bookmark");
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteElevation>, RepositoryError> {
+        tracing::debug!("finding route elevation");
 
-        let result = sqlx::query(
+        let elevation = sqlx::query_as::<_, RouteElevation>(
             r#"
-            DELETE FROM route_bookmarks
-            WHERE route_id = $1 AND user_id = $2
+            SELECT route_id, samples, gain_m, loss_m, min_m, max_m, route_updated_at, computed_at
+            FROM route_elevations
+            WHERE route_id = $1
             "#,
         )
         .bind(route_id)
-        .bind(user_id)
-        .execute(&self.pool)
+        .fetch_optional(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)?;
 
-        if result.rows_affected() == 0 {
-            return Err(RepositoryError::NotFound);
-        }
+        Ok(elevation)
+    }
+}
 
-        tracing::debug!("route bookmark deleted successfully");
+pub struct PostgresStatsRepository {
+    pool: PgPool,
+}
+
+impl PostgresStatsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl StatsRepository for PostgresStatsRepository {
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %day))]
+    async fn record_usage(&self, user_id: Uuid, day: NaiveDate) -> Result<(), RepositoryError> {
+        sqlx::query("INSERT INTO usage_events (day, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+            .bind(day)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        tracing::debug!("usage recorded");
         Ok(())
     }
 
-    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
-    async fn find_by_route_and_user(
-        &self,
-        route_id: Uuid,
-        user_id: Uuid,
-    ) -> Result<Option<RouteBookmark>, RepositoryError> {
-        tracing::debug!("finding route bookmark by route and user");
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn count_active_users(&self, since: NaiveDate) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(DISTINCT user_id) FROM usage_events WHERE day >= $1")
+            .bind(since)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
 
-        let bookmark = sqlx::query_as::<_, RouteBookmark>(
+        tracing::debug!(count = count.0, "counted active users");
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn active_users_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError> {
+        sqlx::query_as::<_, DailyCount>(
             r#"
-            SELECT id, route_id, user_id, created_at
-            FROM route_bookmarks
-            WHERE route_id = $1 AND user_id = $2
+            SELECT day, COUNT(*) AS count
+            FROM usage_events
+            WHERE day >= $1
+            GROUP BY day
+            ORDER BY day
             "#,
         )
-        .bind(route_id)
-        .bind(user_id)
-        .fetch_optional(&self.pool)
+        .bind(since)
+        .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)
+    }
 
-        Ok(bookmark)
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn routes_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError> {
+        sqlx::query_as::<_, DailyCount>(
+            r#"
+            SELECT (created_at AT TIME ZONE 'UTC')::date AS day, COUNT(*) AS count
+            FROM routes
+            WHERE created_at >= $1::date AT TIME ZONE 'UTC'
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
     }
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
-    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<ExploreRouteRow>, RepositoryError> {
-        tracing::debug!("finding bookmarked routes by user_id");
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn chat_usage(&self, since: NaiveDate) -> Result<ChatUsage, RepositoryError> {
+        sqlx::query_as::<_, ChatUsage>(
+            r#"
+            SELECT COUNT(*) AS messages,
+                   COUNT(DISTINCT user_id) AS users,
+                   COUNT(DISTINCT conversation_id) AS conversations
+            FROM chat_messages
+            WHERE role = 'user' AND created_at >= $1::date AT TIME ZONE 'UTC'
+            "#,
+        )
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
 
-        let rows = sqlx::query_as::<_, ExploreRouteRow>(
+    #[tracing::instrument(skip(self))]
+    async fn photo_volume(&self) -> Result<PhotoVolume, RepositoryError> {
+        sqlx::query_as::<_, PhotoVolume>(
             r#"
-            SELECT
-                r.id,
-                r.name,
-                jsonb_array_length(r.points)::bigint AS points_count,
-                r.created_at,
-                r.share_token,
-                COALESCE((SELECT COUNT(*) FROM route_likes WHERE route_id = r.id), 0) AS likes_count,
-                COALESCE((SELECT AVG(rating::float8) FROM route_ratings WHERE route_id = r.id), 0.0) AS avg_rating,
-                COALESCE((SELECT COUNT(*) FROM route_ratings WHERE route_id = r.id), 0) AS ratings_count,
-                COALESCE(ARRAY(SELECT category_id FROM route_categories WHERE route_id = r.id), ARRAY[]::uuid[]) AS category_ids
-            FROM route_bookmarks rb
-            JOIN routes r ON r.id = rb.route_id
-            WHERE rb.user_id = $1
-            ORDER BY rb.created_at DESC
+            SELECT COUNT(*) FILTER (WHERE p.value->'photo'->>'status' = 'pending') AS pending,
+                   COUNT(*) FILTER (WHERE p.value->'photo'->>'status' = 'processing') AS processing,
+                   COUNT(*) FILTER (WHERE p.value->'photo'->>'status' = 'done') AS done,
+                   COUNT(*) FILTER (WHERE p.value->'photo'->>'status' = 'failed') AS failed
+            FROM routes r
+            CROSS JOIN LATERAL jsonb_array_elements(r.points) p
+            WHERE jsonb_typeof(p.value->'photo') = 'object'
             "#,
         )
-        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn chat_messages_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError> {
+        sqlx::query_as::<_, DailyCount>(
+            r#"
+            SELECT (created_at AT TIME ZONE 'UTC')::date AS day, COUNT(*) AS count
+            FROM chat_messages
+            WHERE role = 'user' AND created_at >= $1::date AT TIME ZONE 'UTC'
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+        )
+        .bind(since)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        .map_err(RepositoryError::from)
+    }
 
-        tracing::debug!(user_id = %user_id, count = rows.len(), "found bookmarked routes");
-        Ok(rows)
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn chat_turn_summary(&self, since: NaiveDate) -> Result<ChatTurnSummary, RepositoryError> {
+        sqlx::query_as::<_, ChatTurnSummary>(
+            r#"
+            SELECT COUNT(*) AS turns,
+                   COUNT(*) FILTER (WHERE error IS NOT NULL) AS errors,
+                   COALESCE(AVG(latency_ms), 0)::float8 AS avg_latency_ms
+            FROM chat_turns
+            WHERE created_at >= $1::date AT TIME ZONE 'UTC'
+            "#,
+        )
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn chat_tool_calls(&self, since: NaiveDate) -> Result<Vec<ToolCallCount>, RepositoryError> {
+        sqlx::query_as::<_, ToolCallCount>(
+            r#"
+            SELECT tool.value AS tool, COUNT(*) AS count
+            FROM chat_turns t
+            CROSS JOIN LATERAL jsonb_array_elements_text(t.tool_calls) AS tool(value)
+            WHERE t.created_at >= $1::date AT TIME ZONE 'UTC'
+            GROUP BY 1
+            ORDER BY count DESC, tool
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
     }
-}
 
-pub async fn create_pool(database_url: &str, max_connections: u32) -> Result<PgPool, sqlx::Error> {
-    PgPoolOptions::new()
-        .max_connections(max_connections)
-        .connect(database_url)
+    #[tracing::instrument(skip(self), fields(%since, %limit))]
+    async fn top_chat_users(&self, since: NaiveDate, limit: i64) -> Result<Vec<ChatUserVolume>, RepositoryError> {
+        sqlx::query_as::<_, ChatUserVolume>(
+            r#"
+            SELECT user_id, COUNT(*) AS messages
+            FROM chat_messages
+            WHERE role = 'user' AND created_at >= $1::date AT TIME ZONE 'UTC'
+            GROUP BY user_id
+            ORDER BY messages DESC, user_id
+            LIMIT $2
+            "#,
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
         .await
+        .map_err(RepositoryError::from)
+    }
+}
+
+pub struct PostgresUserDataRepository {
+    pool: PgPool,
+}
+
+impl PostgresUserDataRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl UserDataRepository for PostgresUserDataRepository {
+    #[tracing::instrument(skip(self), fields(table = table.table, %user_id))]
+    async fn find_user_rows(&self, table: UserKeyedTable, user_id: Uuid) -> Result<Vec<serde_json::Value>, RepositoryError> {
+        // Table and column names come from `USER_KEYED_TABLES`, never from a request.
+        let query = format!("SELECT to_jsonb(t) FROM {} t WHERE {} = $1", table.table, table.user_column);
+        sqlx::query_scalar::<_, serde_json::Value>(&query)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(RepositoryError::from)
+    }
+}
+
+pub async fn create_pool(database_url: &str, settings: &PoolSettings) -> Result<PgPool, sqlx::Error> {
+    settings.options::<Postgres>().connect(database_url).await
 }
@@ -0,0 +1,151 @@
+//! Retries repository calls that failed with [`RepositoryError::Transient`],
+//! so a connection blip or serialization conflict does not reach the user as
+//! a 500. Only reads and idempotent writes are retried, see
+//! `backend_repository!`.
+
+use std::future::Future;
+use std::time::Duration;
+
+use metrics::describe_counter;
+use rand::Rng;
+
+use crate::repository::errors::RepositoryError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts, the first one included. 1 disables retries.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Runs `operation` until it succeeds, fails with a non-transient error
+    /// or runs out of attempts. `name` labels the retry metrics.
+    pub async fn run<T, F, Fut>(&self, name: &'static str, mut operation: F) -> Result<T, RepositoryError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, RepositoryError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Err(RepositoryError::Transient(e)) if attempt < self.max_attempts => {
+                    let delay = self.delay(attempt);
+                    tracing::warn!(operation = name, attempt, ?delay, error = %e, "transient database error, retrying");
+                    metrics::counter!("db_retries_total", "operation" => name).increment(1);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(RepositoryError::Transient(e)) => {
+                    tracing::error!(operation = name, attempt, error = %e, "transient database error, giving up");
+                    metrics::counter!("db_retries_exhausted_total", "operation" => name).increment(1);
+                    return Err(RepositoryError::Transient(e));
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Exponential backoff with full jitter: a random delay up to
+    /// `base_delay * 2^(attempt - 1)`, capped at `max_delay`.
+    fn delay(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+        let ceiling_nanos = ceiling.as_nanos() as u64;
+        if ceiling_nanos == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos(rand::thread_rng().gen_range(0..=ceiling_nanos))
+    }
+}
+
+pub fn describe() {
+    describe_counter!("db_retries_total", "Repository calls retried after a transient error, by `operation`");
+    describe_counter!("db_retries_exhausted_total", "Repository calls that still failed after the last retry, by `operation`");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy { max_attempts, base_delay: Duration::ZERO, max_delay: Duration::ZERO }
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_transient_errors() {
+        let calls = AtomicU32::new(0);
+
+        let result = policy(3)
+            .run("test", || async {
+                if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(RepositoryError::Transient("connection reset".to_string()))
+                } else {
+                    Ok(7)
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = policy(2)
+            .run("test", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(RepositoryError::Transient("connection reset".to_string()))
+            })
+            .await;
+
+        assert!(matches!(result, Err(RepositoryError::Transient(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_does_not_retry_other_errors() {
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = policy(3)
+            .run("test", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(RepositoryError::DatabaseError("syntax error".to_string()))
+            })
+            .await;
+
+        assert!(matches!(result, Err(RepositoryError::DatabaseError(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_delay_is_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+        };
+
+        for attempt in 1..10 {
+            assert!(policy.delay(attempt) <= Duration::from_millis(300));
+        }
+        assert!(policy.delay(1) <= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{BucketLifecycleConfiguration, ExpirationStatus, LifecycleExpiration, LifecycleRule, LifecycleRuleFilter};
+use aws_sdk_s3::Client as S3Client;
+
+use crate::repository::errors::RepositoryError;
+use crate::usecase::contracts::ObjectStorage;
+
+/// MinIO/S3 bucket for generated files (data exports) and user uploads
+/// (chat attachments).
+///
+/// Objects are private; clients download them through presigned links. Links
+/// are signed against `public_endpoint` when set, because the signature covers
+/// the host and the in-cluster endpoint is not reachable from browsers.
+pub struct S3ObjectStorage {
+    client: S3Client,
+    presign_client: S3Client,
+    bucket: String,
+}
+
+impl S3ObjectStorage {
+    pub async fn new(
+        endpoint: &str,
+        public_endpoint: Option<&str>,
+        access_key: &str,
+        secret_key: &str,
+        bucket: String,
+    ) -> Self {
+        let client = build_client(endpoint, access_key, secret_key).await;
+        let presign_client = match public_endpoint {
+            Some(public) => build_client(public, access_key, secret_key).await,
+            None => client.clone(),
+        };
+        Self {
+            client,
+            presign_client,
+            bucket,
+        }
+    }
+
+    /// Creates the bucket if it does not exist yet.
+    #[tracing::instrument(skip(self), fields(bucket = %self.bucket))]
+    pub async fn ensure_bucket(&self) -> Result<(), RepositoryError> {
+        if self.client.head_bucket().bucket(&self.bucket).send().await.is_ok() {
+            tracing::debug!("bucket already exists");
+            return Ok(());
+        }
+
+        tracing::info!("creating bucket");
+        self.client
+            .create_bucket()
+            .bucket(&self.bucket)
+            .send()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(format!("failed to create bucket: {}", e)))?;
+        tracing::info!("bucket created");
+        Ok(())
+    }
+
+    /// Sets a lifecycle rule deleting every object of the bucket `days` days
+    /// after it was written.
+    #[tracing::instrument(skip(self), fields(bucket = %self.bucket))]
+    pub async fn expire_objects_after(&self, days: i32) -> Result<(), RepositoryError> {
+        let rule = LifecycleRule::builder()
+            .id("expire-objects")
+            .status(ExpirationStatus::Enabled)
+            .filter(LifecycleRuleFilter::builder().prefix("").build())
+            .expiration(LifecycleExpiration::builder().days(days).build())
+            .build()
+            .map_err(|e| RepositoryError::DatabaseError(format!("invalid lifecycle rule: {}", e)))?;
+        let lifecycle = BucketLifecycleConfiguration::builder()
+            .rules(rule)
+            .build()
+            .map_err(|e| RepositoryError::DatabaseError(format!("invalid lifecycle configuration: {}", e)))?;
+
+        self.client
+            .put_bucket_lifecycle_configuration()
+            .bucket(&self.bucket)
+            .lifecycle_configuration(lifecycle)
+            .send()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(format!("failed to set bucket lifecycle: {}", e)))?;
+        tracing::info!(days, "bucket lifecycle set");
+        Ok(())
+    }
+}
+
+async fn build_client(endpoint: &str, access_key: &str, secret_key: &str) -> S3Client {
+    let s3_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .endpoint_url(endpoint)
+        .region(aws_config::Region::new("us-east-1"))
+        .credentials_provider(aws_sdk_s3::config::Credentials::new(
+            access_key,
+            secret_key,
+            None,
+            None,
+            "env",
+        ))
+        .load()
+        .await;
+    S3Client::from_conf(
+        aws_sdk_s3::Config::from(&s3_config)
+            .to_builder()
+            .force_path_style(true)
+            .build(),
+    )
+}
+
+impl ObjectStorage for S3ObjectStorage {
+    #[tracing::instrument(skip(self, body), fields(bucket = %self.bucket, %key, size = body.len()))]
+    async fn put_object(
+        &self,
+        key: &str,
+        body: Vec<u8>,
+        content_type: &str,
+    ) -> Result<(), RepositoryError> {
+        tracing::debug!("uploading object");
+
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from(body))
+            .content_type(content_type)
+            .send()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(format!("failed to upload object: {}", e)))?;
+
+        tracing::debug!("object uploaded");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(bucket = %self.bucket, %key, expires_in_secs = expires_in.as_secs()))]
+    async fn presigned_get_url(
+        &self,
+        key: &str,
+        expires_in: Duration,
+    ) -> Result<String, RepositoryError> {
+        tracing::debug!("presigning download url");
+
+        let presigning = PresigningConfig::expires_in(expires_in)
+            .map_err(|e| RepositoryError::DatabaseError(format!("invalid presign expiry: {}", e)))?;
+        let request = self
+            .presign_client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .presigned(presigning)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(format!("failed to presign url: {}", e)))?;
+
+        Ok(request.uri().to_string())
+    }
+
+    #[tracing::instrument(skip(self), fields(bucket = %self.bucket, %key, expires_in_secs = expires_in.as_secs()))]
+    async fn presigned_put_url(
+        &self,
+        key: &str,
+        content_type: &str,
+        expires_in: Duration,
+    ) -> Result<String, RepositoryError> {
+        tracing::debug!("presigning upload url");
+
+        let presigning = PresigningConfig::expires_in(expires_in)
+            .map_err(|e| RepositoryError::DatabaseError(format!("invalid presign expiry: {}", e)))?;
+        let request = self
+            .presign_client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .presigned(presigning)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(format!("failed to presign url: {}", e)))?;
+
+        Ok(request.uri().to_string())
+    }
+
+    #[tracing::instrument(skip(self), fields(bucket = %self.bucket, %prefix))]
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>, RepositoryError> {
+        let mut keys = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .map_err(|e| RepositoryError::DatabaseError(format!("failed to list objects: {}", e)))?;
+            keys.extend(page.contents().iter().filter_map(|object| object.key().map(String::from)));
+            continuation_token = page.next_continuation_token().map(String::from);
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+
+        tracing::debug!(count = keys.len(), "objects listed");
+        Ok(keys)
+    }
+
+    #[tracing::instrument(skip(self), fields(bucket = %self.bucket, %key))]
+    async fn delete_object(&self, key: &str) -> Result<(), RepositoryError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(format!("failed to delete object: {}", e)))?;
+
+        tracing::debug!("object deleted");
+        Ok(())
+    }
+}
@@ -0,0 +1,4258 @@
+//! SQLite implementation of the repository traits for single-instance
+//! deployments. UUIDs are stored as BLOBs, JSONB columns and arrays as JSON
+//! text; comment search uses `LIKE` instead of Postgres full-text search.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{
+    sqlite::SqliteConnectOptions,
+    types::Json,
+    Sqlite, SqlitePool,
+};
+use guide_helper_domain::user_data::UserKeyedTable;
+use uuid::Uuid;
+
+use crate::{
+    domain::activity::RouteActivity,
+    domain::block::UserBlock,
+    domain::bookmark::RouteBookmark,
+    domain::category::Category,
+    domain::chat_message::{ChatMessage, ChatTurnRecord, ConversationFlags, ConversationSummary, FlaggedConversation, PendingChatAction},
+    domain::collection::RouteCollection,
+    domain::comment::Comment,
+    domain::like::RouteLike,
+    domain::notification::Notification,
+    domain::quota::{RouteQuota, RouteUsage, StorageTotals, StorageUsage, UserStorageUsage},
+    domain::rating::RouteRating,
+    domain::report::RouteReport,
+    domain::route::{AdminRouteRow, ExploreRouteRow, Route, RouteCover, RoutePoint, RouteVisibility, UserRoutesFilter},
+    domain::route_elevation::RouteElevation,
+    domain::route_geometry::RouteGeometry,
+    domain::saved_search::SavedSearch,
+    domain::share_link::ShareLink,
+    domain::spam::SpamFlag,
+    domain::stats::{ChatTurnSummary, ChatUsage, ChatUserVolume, DailyCount, PhotoVolume, ToolCallCount},
+    domain::template::{RouteTemplate, TemplateSummary},
+    domain::translation::Translation,
+    repository::errors::RepositoryError,
+    repository::pool::PoolSettings,
+    usecase::contracts::{BlockRepository, BookmarkRepository, CategoryRepository, ChatMessageRepository, CommentRepository, LikeRepository, NotificationPreferenceRepository, NotificationRepository, RatingRepository, ReportRepository, RouteActivityRepository, RouteCollectionRepository, RouteElevationRepository, RouteGeometryRepository, RouteRepository, RouteTemplateRepository, SavedSearchRepository, SettingsRepository, ShareLinkRepository, SpamFlagRepository, StatsRepository, TranslationRepository, UserDataRepository},
+    usecase::settings::DifficultyThresholds,
+};
+
+/// Comma-separated hex ids of a route's categories, see [`parse_category_ids`].
+const ROUTE_CATEGORY_IDS: &str =
+    "(SELECT group_concat(lower(hex(category_id))) FROM route_categories WHERE route_id = r.id) AS category_ids";
+
+/// Points of `routes r` without names, photos or addresses, see
+/// [`ExploreRouteRow::path`].
+const ROUTE_PATH: &str = "(SELECT json_group_array(json_object('lat', json_extract(value, '$.lat'), \
+     'lng', json_extract(value, '$.lng'), 'segment_mode', json_extract(value, '$.segment_mode'))) \
+     FROM (SELECT value FROM json_each(r.points) ORDER BY key)) AS path";
+
+/// Columns of a full route row, selected from `routes r`.
+const ROUTE_COLUMNS: &str = "r.id, r.user_id, r.name, r.points, r.created_at, r.updated_at, r.share_token, r.visibility, \
+     (SELECT group_concat(lower(hex(category_id))) FROM route_categories WHERE route_id = r.id) AS category_ids, \
+     r.start_location, r.end_location, r.seasons, r.description, r.archived_at";
+
+/// Routes listed on explore.
+const PUBLIC_ROUTES: &str = "r.visibility = 'public'";
+
+/// Explore filters shared by `explore_shared` and `count_explore_shared`,
+/// applied after the visibility condition.
+const EXPLORE_FILTER: &str = r#"
+    r.archived_at IS NULL
+      AND (?1 IS NULL OR r.name LIKE '%' || ?1 || '%')
+      AND (?2 IS NULL OR EXISTS (SELECT 1 FROM route_categories WHERE route_id = r.id AND category_id = ?2))
+      AND (?3 IS NULL OR EXISTS (SELECT 1 FROM json_each(r.seasons) WHERE value = ?3))
+"#;
+
+/// Filters of the owner's route list, shared by `find_by_user_id_paginated`
+/// and `count_by_user_id`.
+const USER_ROUTES_FILTER: &str = r#"
+    r.user_id = ?1
+      AND (?2 OR r.archived_at IS NULL)
+      AND (?3 IS NULL OR r.name LIKE '%' || ?3 || '%')
+      AND (?4 IS NULL OR EXISTS (SELECT 1 FROM route_categories WHERE route_id = r.id AND category_id = ?4))
+      AND (?5 IS NULL OR r.created_at >= ?5)
+      AND (?6 IS NULL OR r.created_at < ?6)
+      AND (?7 IS NULL OR EXISTS (SELECT 1 FROM route_collection_routes WHERE route_id = r.id AND collection_id = ?7))
+"#;
+
+fn parse_category_ids(ids: Option<String>) -> Vec<Uuid> {
+    ids.unwrap_or_default()
+        .split(',')
+        .filter(|id| !id.is_empty())
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect()
+}
+
+#[derive(sqlx::FromRow)]
+struct SqliteRouteRow {
+    id: Uuid,
+    user_id: Uuid,
+    name: String,
+    points: Json<Vec<RoutePoint>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    share_token: Option<Uuid>,
+    #[sqlx(try_from = "String")]
+    visibility: RouteVisibility,
+    category_ids: Option<String>,
+    start_location: Option<String>,
+    end_location: Option<String>,
+    seasons: Json<Vec<String>>,
+    description: Option<String>,
+    archived_at: Option<DateTime<Utc>>,
+}
+
+impl From<SqliteRouteRow> for Route {
+    fn from(row: SqliteRouteRow) -> Self {
+        Route {
+            id: row.id,
+            user_id: row.user_id,
+            name: row.name,
+            points: row.points.0,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            share_token: row.share_token,
+            visibility: row.visibility,
+            category_ids: parse_category_ids(row.category_ids),
+            start_location: row.start_location,
+            end_location: row.end_location,
+            seasons: row.seasons.0,
+            description: row.description,
+            archived_at: row.archived_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SqliteExploreRow {
+    id: Uuid,
+    name: String,
+    points_count: i64,
+    created_at: DateTime<Utc>,
+    share_token: Option<Uuid>,
+    likes_count: i64,
+    bookmarks_count: i64,
+    avg_rating: f64,
+    ratings_count: i64,
+    comments_count: i64,
+    category_ids: Option<String>,
+    seasons: Json<Vec<String>>,
+    start_location: Option<String>,
+    end_location: Option<String>,
+    cover_url: Option<String>,
+    cover_thumbnail_url: Option<String>,
+    path: Json<Vec<RoutePoint>>,
+}
+
+impl From<SqliteExploreRow> for ExploreRouteRow {
+    fn from(row: SqliteExploreRow) -> Self {
+        ExploreRouteRow {
+            id: row.id,
+            name: row.name,
+            points_count: row.points_count,
+            created_at: row.created_at,
+            share_token: row.share_token,
+            likes_count: row.likes_count,
+            bookmarks_count: row.bookmarks_count,
+            avg_rating: row.avg_rating,
+            ratings_count: row.ratings_count,
+            comments_count: row.comments_count,
+            category_ids: parse_category_ids(row.category_ids),
+            seasons: row.seasons.0,
+            start_location: row.start_location,
+            end_location: row.end_location,
+            cover_url: row.cover_url,
+            cover_thumbnail_url: row.cover_thumbnail_url,
+            path: row.path.0,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SqliteAdminRouteRow {
+    id: Uuid,
+    user_id: Uuid,
+    name: String,
+    points_count: i64,
+    created_at: DateTime<Utc>,
+    share_token: Option<Uuid>,
+    category_ids: Option<String>,
+}
+
+impl From<SqliteAdminRouteRow> for AdminRouteRow {
+    fn from(row: SqliteAdminRouteRow) -> Self {
+        AdminRouteRow {
+            id: row.id,
+            user_id: row.user_id,
+            name: row.name,
+            points_count: row.points_count,
+            created_at: row.created_at,
+            share_token: row.share_token,
+            category_ids: parse_category_ids(row.category_ids),
+        }
+    }
+}
+
+pub struct SqliteRouteRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRouteRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn replace_categories(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        route: &Route,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM route_categories WHERE route_id = ?1")
+            .bind(route.id)
+            .execute(&mut **tx)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        for category_id in &route.category_ids {
+            sqlx::query("INSERT INTO route_categories (route_id, category_id) VALUES (?1, ?2)")
+                .bind(route.id)
+                .bind(category_id)
+                .execute(&mut **tx)
+                .await
+                .map_err(RepositoryError::from)?;
+        }
+        Ok(())
+    }
+}
+
+impl RouteRepository for SqliteRouteRepository {
+    #[tracing::instrument(skip(self, route), fields(route_id = %route.id, user_id = %route.user_id))]
+    async fn create(&self, route: &Route) -> Result<(), RepositoryError> {
+        tracing::debug!("creating route");
+
+        let mut tx = self.pool.begin().await.map_err(RepositoryError::from)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO routes (id, user_id, name, points, created_at, updated_at, seasons, description)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+        )
+        .bind(route.id)
+        .bind(route.user_id)
+        .bind(&route.name)
+        .bind(Json(&route.points))
+        .bind(route.created_at)
+        .bind(route.updated_at)
+        .bind(Json(&route.seasons))
+        .bind(&route.description)
+        .execute(&mut *tx)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Self::replace_categories(&mut tx, route).await?;
+
+        tx.commit().await.map_err(RepositoryError::from)?;
+
+        tracing::debug!(route_id = %route.id, category_count = route.category_ids.len(), "route created successfully");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id))]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Route>, RepositoryError> {
+        tracing::debug!("finding route by id");
+
+        let row = sqlx::query_as::<_, SqliteRouteRow>(&format!("SELECT {} FROM routes r WHERE r.id = ?1", ROUTE_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(row.map(Route::from))
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Route>, RepositoryError> {
+        tracing::debug!("finding routes by user_id");
+
+        let rows = sqlx::query_as::<_, SqliteRouteRow>(&format!(
+            "SELECT {} FROM routes r WHERE r.user_id = ?1 ORDER BY r.created_at DESC",
+            ROUTE_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = rows.len(), "found routes");
+        Ok(rows.into_iter().map(Route::from).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id_paginated(
+        &self,
+        user_id: Uuid,
+        filter: &UserRoutesFilter,
+        order_clause: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Route>, RepositoryError> {
+        tracing::debug!("finding page of routes by user_id");
+
+        let rows = sqlx::query_as::<_, SqliteRouteRow>(&format!(
+            "SELECT {} FROM routes r WHERE {} ORDER BY {} LIMIT ?8 OFFSET ?9",
+            ROUTE_COLUMNS, USER_ROUTES_FILTER, order_clause
+        ))
+        .bind(user_id)
+        .bind(filter.include_archived)
+        .bind(filter.search.as_deref())
+        .bind(filter.category_id)
+        .bind(filter.created_after)
+        .bind(filter.created_before)
+        .bind(filter.collection_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tracing::debug!(user_id = %user_id, count = rows.len(), "found routes");
+        Ok(rows.into_iter().map(Route::from).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_by_user_id(&self, user_id: Uuid, filter: &UserRoutesFilter) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM routes r WHERE {}", USER_ROUTES_FILTER))
+            .bind(user_id)
+            .bind(filter.include_archived)
+            .bind(filter.search.as_deref())
+            .bind(filter.category_id)
+            .bind(filter.created_after)
+            .bind(filter.created_before)
+            .bind(filter.collection_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self, route), fields(route_id = %route.id))]
+    async fn update(&self, route: &Route) -> Result<(), RepositoryError> {
+        tracing::debug!("updating route");
+
+        let mut tx = self.pool.begin().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE routes
+            SET name = ?2, points = ?3, updated_at = ?4, seasons = ?5, description = ?6
+            WHERE id = ?1
+            "#,
+        )
+        .bind(route.id)
+        .bind(&route.name)
+        .bind(Json(&route.points))
+        .bind(route.updated_at)
+        .bind(Json(&route.seasons))
+        .bind(&route.description)
+        .execute(&mut *tx)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Self::replace_categories(&mut tx, route).await?;
+
+        tx.commit().await.map_err(RepositoryError::from)?;
+
+        tracing::debug!(route_id = %route.id, "route updated successfully");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, points), fields(route_id = %id, point_count = points.len()))]
+    async fn replace_points(
+        &self,
+        id: Uuid,
+        points: &[RoutePoint],
+        expected: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("UPDATE routes SET points = ?2, updated_at = ?4 WHERE id = ?1 AND updated_at = ?3")
+            .bind(id)
+            .bind(Json(points))
+            .bind(expected)
+            .bind(updated_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        tracing::debug!(replaced = result.rows_affected() > 0, "route points replaced");
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id))]
+    async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        tracing::debug!("deleting route");
+
+        let result = sqlx::query("DELETE FROM routes WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        tracing::debug!(route_id = %id, "route deleted successfully");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id, visibility = visibility.as_str()))]
+    async fn set_visibility(&self, id: Uuid, visibility: RouteVisibility, token: Option<Uuid>) -> Result<(), RepositoryError> {
+        tracing::debug!(?token, "setting route visibility");
+
+        let result = sqlx::query(
+            "UPDATE routes SET visibility = ?2, share_token = ?3, \
+             shared_at = CASE WHEN ?2 <> 'public' THEN NULL WHEN visibility = 'public' THEN shared_at ELSE ?4 END \
+             WHERE id = ?1",
+        )
+        .bind(id)
+        .bind(visibility.as_str())
+        .bind(token)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(share_token = %token))]
+    async fn find_by_share_token(&self, token: Uuid) -> Result<Option<Route>, RepositoryError> {
+        tracing::debug!("finding route by share token");
+
+        let row = sqlx::query_as::<_, SqliteRouteRow>(&format!(
+            "SELECT {} FROM routes r WHERE r.share_token = ?1 AND r.visibility <> 'private'",
+            ROUTE_COLUMNS
+        ))
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(row.map(Route::from))
+    }
+
+    #[tracing::instrument(skip(self), fields(share_token = %token))]
+    async fn find_shared_updated_at(&self, token: Uuid) -> Result<Option<DateTime<Utc>>, RepositoryError> {
+        let updated_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            "SELECT updated_at FROM routes WHERE share_token = ?1 AND visibility <> 'private'",
+        )
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(updated_at)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id, public_only))]
+    async fn find_share_token(&self, id: Uuid, public_only: bool) -> Result<Option<Uuid>, RepositoryError> {
+        let token = sqlx::query_scalar::<_, Uuid>(
+            "SELECT share_token FROM routes WHERE id = ?1 AND share_token IS NOT NULL \
+             AND (visibility = 'public' OR (NOT ?2 AND visibility <> 'private'))",
+        )
+        .bind(id)
+        .bind(public_only)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(token)
+    }
+
+    #[tracing::instrument(skip(self), fields(?owner_id, ?search, ?category_id, %order_clause, %limit, %offset))]
+    async fn explore_shared(
+        &self,
+        owner_id: Option<Uuid>,
+        search: Option<String>,
+        category_id: Option<Uuid>,
+        season: Option<String>,
+        order_clause: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ExploreRouteRow>, RepositoryError> {
+        tracing::debug!("exploring shared routes");
+
+        let query = format!(
+            r#"
+            SELECT r.id, r.name,
+                   json_array_length(r.points) AS points_count,
+                   r.created_at, r.share_token,
+                   r.likes_count, r.bookmarks_count,
+                   COALESCE(rt.avg_rating, 0.0) AS avg_rating,
+                   COALESCE(rt.ratings_count, 0) AS ratings_count,
+                   COALESCE(c.comments_count, 0) AS comments_count,
+                   {},
+                   r.seasons, r.start_location, r.end_location, r.cover_url, r.cover_thumbnail_url, {}
+            FROM routes r
+            LEFT JOIN (SELECT route_id, AVG(rating) AS avg_rating, COUNT(*) AS ratings_count FROM route_ratings GROUP BY route_id) rt ON rt.route_id = r.id
+            LEFT JOIN (SELECT route_id, COUNT(*) AS comments_count FROM comments WHERE approved GROUP BY route_id) c ON c.route_id = r.id
+            WHERE (CASE WHEN ?6 IS NULL THEN r.visibility = 'public' ELSE r.user_id = ?6 END) AND {}
+            ORDER BY {}
+            LIMIT ?4 OFFSET ?5
+            "#,
+            ROUTE_CATEGORY_IDS, ROUTE_PATH, EXPLORE_FILTER, order_clause
+        );
+
+        let rows = sqlx::query_as::<_, SqliteExploreRow>(&query)
+            .bind(search.as_deref())
+            .bind(category_id)
+            .bind(season.as_deref())
+            .bind(limit)
+            .bind(offset)
+            .bind(owner_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        tracing::debug!(count = rows.len(), "explored shared routes");
+        Ok(rows.into_iter().map(ExploreRouteRow::from).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(?search, ?category_id))]
+    async fn count_explore_shared(&self, search: Option<String>, category_id: Option<Uuid>, season: Option<String>) -> Result<i64, RepositoryError> {
+        tracing::debug!("counting explore shared routes");
+
+        let count: (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM routes r WHERE {} AND {}", PUBLIC_ROUTES, EXPLORE_FILTER))
+            .bind(search.as_deref())
+            .bind(category_id)
+            .bind(season.as_deref())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(?search, ?season))]
+    async fn count_explore_by_category(
+        &self,
+        search: Option<String>,
+        season: Option<String>,
+    ) -> Result<Vec<(Uuid, i64)>, RepositoryError> {
+        let rows: Vec<(Uuid, i64)> = sqlx::query_as(&format!(
+            r#"
+            SELECT rc.category_id, COUNT(*) AS count
+            FROM routes r
+            JOIN route_categories rc ON rc.route_id = r.id
+            WHERE {} AND {}
+            GROUP BY rc.category_id
+            "#,
+            PUBLIC_ROUTES, EXPLORE_FILTER
+        ))
+        .bind(search.as_deref())
+        .bind(None::<Uuid>)
+        .bind(season.as_deref())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(rows)
+    }
+
+    #[tracing::instrument(skip(self, thresholds), fields(?search, ?category_id, ?season))]
+    async fn count_explore_by_difficulty(
+        &self,
+        search: Option<String>,
+        category_id: Option<Uuid>,
+        season: Option<String>,
+        thresholds: &DifficultyThresholds,
+    ) -> Result<Vec<(String, i64)>, RepositoryError> {
+        // Same scoring as DifficultyThresholds::classify
+        let rows: Vec<(String, i64)> = sqlx::query_as(&format!(
+            r#"
+            SELECT CASE WHEN s.score <= ?8 THEN 'easy' WHEN s.score <= ?9 THEN 'moderate' ELSE 'hard' END AS level,
+                   COUNT(*) AS count
+            FROM (
+                SELECT (CASE WHEN g.distance_km < ?4 THEN 1 WHEN g.distance_km < ?5 THEN 2 ELSE 3 END)
+                     + (CASE WHEN COALESCE(e.gain_m, 0) < ?6 THEN 1 WHEN COALESCE(e.gain_m, 0) < ?7 THEN 2 ELSE 3 END) AS score
+                FROM routes r
+                JOIN route_geometries g ON g.route_id = r.id
+                LEFT JOIN route_elevations e ON e.route_id = r.id
+                WHERE {} AND {}
+            ) s
+            GROUP BY 1
+            "#,
+            PUBLIC_ROUTES, EXPLORE_FILTER
+        ))
+        .bind(search.as_deref())
+        .bind(category_id)
+        .bind(season.as_deref())
+        .bind(thresholds.distance_easy_max_km)
+        .bind(thresholds.distance_moderate_max_km)
+        .bind(thresholds.elevation_easy_max_m)
+        .bind(thresholds.elevation_moderate_max_m)
+        .bind(thresholds.score_easy_max)
+        .bind(thresholds.score_moderate_max)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(rows)
+    }
+
+    #[tracing::instrument(skip(self), fields(%after, %until))]
+    async fn find_shared_between(&self, after: DateTime<Utc>, until: DateTime<Utc>) -> Result<Vec<Route>, RepositoryError> {
+        let rows = sqlx::query_as::<_, SqliteRouteRow>(&format!(
+            r#"
+            SELECT {} FROM routes r
+            WHERE r.visibility = 'public'
+              AND r.archived_at IS NULL
+              AND r.shared_at > ?1 AND r.shared_at <= ?2
+            ORDER BY r.shared_at
+            "#,
+            ROUTE_COLUMNS
+        ))
+        .bind(after)
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(rows.into_iter().map(Route::from).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn count_all(&self) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM routes")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(%limit, %offset))]
+    async fn find_all_admin(&self, limit: i64, offset: i64) -> Result<Vec<AdminRouteRow>, RepositoryError> {
+        tracing::debug!("listing all routes for admin");
+
+        let rows = sqlx::query_as::<_, SqliteAdminRouteRow>(&format!(
+            r#"
+            SELECT r.id, r.user_id, r.name,
+                   json_array_length(r.points) AS points_count,
+                   r.created_at, r.share_token,
+                   {}
+            FROM routes r
+            ORDER BY r.created_at DESC
+            LIMIT ?1 OFFSET ?2
+            "#,
+            ROUTE_CATEGORY_IDS
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(rows.into_iter().map(AdminRouteRow::from).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id))]
+    async fn update_locations(
+        &self,
+        id: Uuid,
+        start_location: Option<String>,
+        end_location: Option<String>,
+    ) -> Result<(), RepositoryError> {
+        tracing::debug!(?start_location, ?end_location, "updating route locations");
+
+        sqlx::query("UPDATE routes SET start_location = ?2, end_location = ?3 WHERE id = ?1")
+            .bind(id)
+            .bind(&start_location)
+            .bind(&end_location)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id))]
+    async fn set_archived_at(&self, id: Uuid, archived_at: Option<DateTime<Utc>>) -> Result<(), RepositoryError> {
+        let result = sqlx::query("UPDATE routes SET archived_at = ?2 WHERE id = ?1")
+            .bind(id)
+            .bind(archived_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id))]
+    async fn find_comment_policy(&self, id: Uuid) -> Result<Option<String>, RepositoryError> {
+        let policy: Option<(String,)> = sqlx::query_as("SELECT comment_policy FROM routes WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(policy.map(|(p,)| p))
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %id))]
+    async fn set_comment_policy(&self, id: Uuid, policy: &str) -> Result<(), RepositoryError> {
+        let result = sqlx::query("UPDATE routes SET comment_policy = ?2 WHERE id = ?1")
+            .bind(id)
+            .bind(policy)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, cover), fields(route_id = %id))]
+    async fn set_cover(&self, id: Uuid, cover: Option<RouteCover>) -> Result<(), RepositoryError> {
+        let (url, thumbnail_url) = cover.map(|c| (c.url, c.thumbnail_url)).unzip();
+        let result = sqlx::query("UPDATE routes SET cover_url = ?2, cover_thumbnail_url = ?3 WHERE id = ?1")
+            .bind(id)
+            .bind(url)
+            .bind(thumbnail_url.flatten())
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn usage_by_user_id(&self, user_id: Uuid) -> Result<RouteUsage, RepositoryError> {
+        tracing::debug!("computing route usage");
+
+        sqlx::query_as::<_, RouteUsage>(
+            r#"
+            SELECT COUNT(DISTINCT r.id) AS routes,
+                   COALESCE(SUM(length(json_extract(p.value, '$.photo.original'))), 0) AS photo_bytes
+            FROM routes r
+            LEFT JOIN json_each(r.points) p
+                ON json_extract(p.value, '$.photo.original') LIKE 'data:%'
+            WHERE r.user_id = ?1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn storage_by_user_id(&self, user_id: Uuid) -> Result<StorageUsage, RepositoryError> {
+        sqlx::query_as::<_, StorageUsage>(
+            "SELECT COUNT(*) AS objects, COALESCE(SUM(size_bytes), 0) AS bytes FROM photo_storage_objects WHERE user_id = ?1",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn storage_totals(&self) -> Result<StorageTotals, RepositoryError> {
+        sqlx::query_as::<_, StorageTotals>(
+            r#"
+            SELECT COUNT(DISTINCT user_id) AS users, COUNT(*) AS objects, COALESCE(SUM(size_bytes), 0) AS bytes
+            FROM photo_storage_objects
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn top_storage_users(&self, limit: i64) -> Result<Vec<UserStorageUsage>, RepositoryError> {
+        sqlx::query_as::<_, UserStorageUsage>(
+            r#"
+            SELECT user_id, COUNT(*) AS objects, SUM(size_bytes) AS bytes
+            FROM photo_storage_objects
+            GROUP BY user_id
+            ORDER BY bytes DESC, user_id
+            LIMIT ?1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_quota_override(&self, user_id: Uuid) -> Result<Option<RouteQuota>, RepositoryError> {
+        sqlx::query_as::<_, RouteQuota>(
+            "SELECT max_routes, max_photo_bytes FROM route_quota_overrides WHERE user_id = ?1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn set_quota_override(&self, user_id: Uuid, quota: Option<RouteQuota>) -> Result<(), RepositoryError> {
+        tracing::debug!(?quota, "setting route quota override");
+
+        match quota {
+            Some(quota) => sqlx::query(
+                r#"
+                INSERT INTO route_quota_overrides (user_id, max_routes, max_photo_bytes, updated_at)
+                VALUES (?1, ?2, ?3, ?4)
+                ON CONFLICT (user_id)
+                DO UPDATE SET max_routes = excluded.max_routes, max_photo_bytes = excluded.max_photo_bytes,
+                              updated_at = excluded.updated_at
+                "#,
+            )
+            .bind(user_id)
+            .bind(quota.max_routes)
+            .bind(quota.max_photo_bytes)
+            .bind(Utc::now()),
+            None => sqlx::query("DELETE FROM route_quota_overrides WHERE user_id = ?1").bind(user_id),
+        }
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn reconcile_counters(&self) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            WITH c AS (
+                SELECT id,
+                       (SELECT COUNT(*) FROM route_likes WHERE route_id = routes.id) AS likes_count,
+                       (SELECT COUNT(*) FROM route_bookmarks WHERE route_id = routes.id) AS bookmarks_count
+                FROM routes
+            )
+            UPDATE routes
+            SET likes_count = c.likes_count, bookmarks_count = c.bookmarks_count
+            FROM c
+            WHERE c.id = routes.id
+              AND (routes.likes_count <> c.likes_count OR routes.bookmarks_count <> c.bookmarks_count)
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+pub struct SqliteCommentRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteCommentRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl CommentRepository for SqliteCommentRepository {
+    #[tracing::instrument(skip(self, comment), fields(comment_id = %comment.id, route_id = %comment.route_id))]
+    async fn create(&self, comment: &Comment) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO comments (id, route_id, user_id, author_name, text, approved, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(comment.id)
+        .bind(comment.route_id)
+        .bind(comment.user_id)
+        .bind(&comment.author_name)
+        .bind(&comment.text)
+        .bind(comment.approved)
+        .bind(comment.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Vec<Comment>, RepositoryError> {
+        sqlx::query_as::<_, Comment>(
+            r#"
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
+            FROM comments
+            WHERE route_id = ?1 AND approved
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b JOIN routes r ON r.user_id = b.blocker_id
+                  WHERE r.id = comments.route_id AND b.blocked_id = comments.user_id
+              )
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(route_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_pending_by_route_id(&self, route_id: Uuid) -> Result<Vec<Comment>, RepositoryError> {
+        sqlx::query_as::<_, Comment>(
+            r#"
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
+            FROM comments
+            WHERE route_id = ?1 AND NOT approved
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b JOIN routes r ON r.user_id = b.blocker_id
+                  WHERE r.id = comments.route_id AND b.blocked_id = comments.user_id
+              )
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(route_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Comment>, RepositoryError> {
+        sqlx::query_as::<_, Comment>(
+            r#"
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
+            FROM comments
+            WHERE user_id = ?1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(comment_id = %id))]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Comment>, RepositoryError> {
+        sqlx::query_as::<_, Comment>(
+            "SELECT id, route_id, user_id, author_name, text, approved, created_at FROM comments WHERE id = ?1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(comment_id = %id))]
+    async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM comments WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(comment_id = %id))]
+    async fn approve(&self, id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("UPDATE comments SET approved = 1 WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM comments
+            WHERE route_id = ?1 AND approved
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b JOIN routes r ON r.user_id = b.blocker_id
+                  WHERE r.id = comments.route_id AND b.blocked_id = comments.user_id
+              )
+            "#,
+        )
+        .bind(route_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn count_all(&self) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM comments")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(%limit, %offset))]
+    async fn find_all_paginated(&self, limit: i64, offset: i64) -> Result<Vec<Comment>, RepositoryError> {
+        sqlx::query_as::<_, Comment>(
+            r#"
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
+            FROM comments
+            ORDER BY created_at DESC
+            LIMIT ?1 OFFSET ?2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(?route_id, %query, %limit, %offset))]
+    async fn search(
+        &self,
+        route_id: Option<Uuid>,
+        query: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Comment>, RepositoryError> {
+        sqlx::query_as::<_, Comment>(
+            r#"
+            SELECT id, route_id, user_id, author_name, text, approved, created_at
+            FROM comments
+            WHERE text LIKE '%' || ?2 || '%'
+              AND approved
+              AND (?1 IS NULL OR route_id = ?1)
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b JOIN routes r ON r.user_id = b.blocker_id
+                  WHERE r.id = comments.route_id AND b.blocked_id = comments.user_id
+              )
+            ORDER BY created_at DESC
+            LIMIT ?3 OFFSET ?4
+            "#,
+        )
+        .bind(route_id)
+        .bind(query)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(?route_id, %query))]
+    async fn count_search(&self, route_id: Option<Uuid>, query: &str) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM comments
+            WHERE text LIKE '%' || ?2 || '%'
+              AND approved
+              AND (?1 IS NULL OR route_id = ?1)
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_blocks b JOIN routes r ON r.user_id = b.blocker_id
+                  WHERE r.id = comments.route_id AND b.blocked_id = comments.user_id
+              )
+            "#,
+        )
+        .bind(route_id)
+        .bind(query)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self, text), fields(%since, exclude_id = %exclude_id))]
+    async fn count_recent_by_text(
+        &self,
+        text: &str,
+        since: DateTime<Utc>,
+        exclude_id: Uuid,
+    ) -> Result<i64, RepositoryError> {
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM comments WHERE text = ?1 AND created_at >= ?2 AND id <> ?3")
+                .bind(text)
+                .bind(since)
+                .bind(exclude_id)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+}
+
+pub struct SqliteLikeRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteLikeRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl LikeRepository for SqliteLikeRepository {
+    #[tracing::instrument(skip(self, like), fields(like_id = %like.id, route_id = %like.route_id, user_id = %like.user_id))]
+    async fn create(&self, like: &RouteLike) -> Result<(), RepositoryError> {
+        sqlx::query("INSERT INTO route_likes (id, route_id, user_id, created_at) VALUES (?1, ?2, ?3, ?4)")
+            .bind(like.id)
+            .bind(like.route_id)
+            .bind(like.user_id)
+            .bind(like.created_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
+    async fn delete_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM route_likes WHERE route_id = ?1 AND user_id = ?2")
+            .bind(route_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
+    async fn find_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<Option<RouteLike>, RepositoryError> {
+        sqlx::query_as::<_, RouteLike>(
+            "SELECT id, route_id, user_id, created_at FROM route_likes WHERE route_id = ?1 AND user_id = ?2",
+        )
+        .bind(route_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError> {
+        // Maintained by triggers on route_likes
+        let count: Option<(i64,)> = sqlx::query_as("SELECT likes_count FROM routes WHERE id = ?1")
+            .bind(route_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.map_or(0, |c| c.0))
+    }
+}
+
+pub struct SqliteRatingRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRatingRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl RatingRepository for SqliteRatingRepository {
+    #[tracing::instrument(skip(self, rating), fields(rating_id = %rating.id, route_id = %rating.route_id, user_id = %rating.user_id))]
+    async fn upsert(&self, rating: &RouteRating) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO route_ratings (id, route_id, user_id, rating, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (route_id, user_id)
+            DO UPDATE SET rating = excluded.rating, created_at = excluded.created_at
+            "#,
+        )
+        .bind(rating.id)
+        .bind(rating.route_id)
+        .bind(rating.user_id)
+        .bind(rating.rating)
+        .bind(rating.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
+    async fn delete_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM route_ratings WHERE route_id = ?1 AND user_id = ?2")
+            .bind(route_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
+    async fn find_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<Option<RouteRating>, RepositoryError> {
+        sqlx::query_as::<_, RouteRating>(
+            "SELECT id, route_id, user_id, rating, created_at FROM route_ratings WHERE route_id = ?1 AND user_id = ?2",
+        )
+        .bind(route_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn get_aggregate(&self, route_id: Uuid) -> Result<(f64, i64), RepositoryError> {
+        let result: (Option<f64>, i64) = sqlx::query_as(
+            "SELECT AVG(rating), COUNT(*) FROM route_ratings WHERE route_id = ?1",
+        )
+        .bind(route_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok((result.0.unwrap_or(0.0), result.1))
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RouteRating>, RepositoryError> {
+        sqlx::query_as::<_, RouteRating>(
+            "SELECT id, route_id, user_id, rating, created_at FROM route_ratings WHERE user_id = ?1 ORDER BY created_at ASC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+}
+
+pub struct SqliteSettingsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteSettingsRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SettingsRepository for SqliteSettingsRepository {
+    #[tracing::instrument(skip(self), fields(%key))]
+    async fn get_value(&self, key: &str) -> Result<Option<serde_json::Value>, RepositoryError> {
+        let row: Option<(Json<serde_json::Value>,)> = sqlx::query_as("SELECT value FROM settings WHERE key = ?1")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(row.map(|r| r.0 .0))
+    }
+
+    #[tracing::instrument(skip(self, value), fields(%key))]
+    async fn set_value(&self, key: &str, value: &serde_json::Value) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO settings (key, value, updated_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(key)
+        .bind(Json(value))
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+}
+
+pub struct SqliteCategoryRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteCategoryRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl CategoryRepository for SqliteCategoryRepository {
+    #[tracing::instrument(skip(self, category), fields(category_id = %category.id))]
+    async fn create(&self, category: &Category) -> Result<(), RepositoryError> {
+        sqlx::query("INSERT INTO categories (id, name, created_at) VALUES (?1, ?2, ?3)")
+            .bind(category.id)
+            .bind(&category.name)
+            .bind(category.created_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn find_all(&self) -> Result<Vec<Category>, RepositoryError> {
+        sqlx::query_as::<_, Category>("SELECT id, name, created_at FROM categories ORDER BY name ASC")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(category_id = %id))]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Category>, RepositoryError> {
+        sqlx::query_as::<_, Category>("SELECT id, name, created_at FROM categories WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(category_id = %id, %name))]
+    async fn update(&self, id: Uuid, name: &str) -> Result<(), RepositoryError> {
+        let result = sqlx::query("UPDATE categories SET name = ?2 WHERE id = ?1")
+            .bind(id)
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(category_id = %id))]
+    async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM categories WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+pub struct SqliteBlockRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteBlockRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl BlockRepository for SqliteBlockRepository {
+    #[tracing::instrument(skip(self, block), fields(blocker_id = %block.blocker_id, blocked_id = %block.blocked_id))]
+    async fn create(&self, block: &UserBlock) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO user_blocks (blocker_id, blocked_id, created_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (blocker_id, blocked_id) DO NOTHING
+            "#,
+        )
+        .bind(block.blocker_id)
+        .bind(block.blocked_id)
+        .bind(block.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip(self), fields(blocker_id = %blocker_id, blocked_id = %blocked_id))]
+    async fn delete(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM user_blocks WHERE blocker_id = ?1 AND blocked_id = ?2")
+            .bind(blocker_id)
+            .bind(blocked_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(blocker_id = %blocker_id, blocked_id = %blocked_id))]
+    async fn exists(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<bool, RepositoryError> {
+        let exists: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM user_blocks WHERE blocker_id = ?1 AND blocked_id = ?2)",
+        )
+        .bind(blocker_id)
+        .bind(blocked_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(exists.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(blocker_id = %blocker_id))]
+    async fn find_by_blocker(&self, blocker_id: Uuid) -> Result<Vec<UserBlock>, RepositoryError> {
+        sqlx::query_as::<_, UserBlock>(
+            r#"
+            SELECT blocker_id, blocked_id, created_at
+            FROM user_blocks
+            WHERE blocker_id = ?1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(blocker_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+}
+
+pub struct SqliteRouteActivityRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRouteActivityRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl RouteActivityRepository for SqliteRouteActivityRepository {
+    #[tracing::instrument(skip(self, activity), fields(activity_id = %activity.id, route_id = %activity.route_id, kind = %activity.kind))]
+    async fn create(&self, activity: &RouteActivity) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO route_activity (id, route_id, actor_id, kind, details, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(activity.id)
+        .bind(activity.route_id)
+        .bind(activity.actor_id)
+        .bind(&activity.kind)
+        .bind(activity.details.as_ref().map(Json))
+        .bind(activity.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, %limit, %offset))]
+    async fn find_by_route_id(&self, route_id: Uuid, limit: i64, offset: i64) -> Result<Vec<RouteActivity>, RepositoryError> {
+        sqlx::query_as::<_, RouteActivity>(
+            r#"
+            SELECT id, route_id, actor_id, kind, details, created_at
+            FROM route_activity
+            WHERE route_id = ?1
+            ORDER BY created_at, id
+            LIMIT ?2 OFFSET ?3
+            "#,
+        )
+        .bind(route_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM route_activity WHERE route_id = ?1")
+            .bind(route_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+}
+
+pub struct SqliteRouteTemplateRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRouteTemplateRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl RouteTemplateRepository for SqliteRouteTemplateRepository {
+    #[tracing::instrument(skip(self, template), fields(route_id = %template.route_id, public = template.public))]
+    async fn upsert(&self, template: &RouteTemplate) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO route_templates (route_id, owner_id, public, created_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (route_id) DO UPDATE SET public = excluded.public
+            "#,
+        )
+        .bind(template.route_id)
+        .bind(template.owner_id)
+        .bind(template.public)
+        .bind(template.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn delete(&self, route_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM route_templates WHERE route_id = ?1")
+            .bind(route_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteTemplate>, RepositoryError> {
+        sqlx::query_as::<_, RouteTemplate>(
+            "SELECT route_id, owner_id, public, created_at FROM route_templates WHERE route_id = ?1",
+        )
+        .bind(route_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %limit, %offset))]
+    async fn list_visible(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<TemplateSummary>, RepositoryError> {
+        sqlx::query_as::<_, TemplateSummary>(
+            r#"
+            SELECT t.route_id, t.owner_id, t.public, r.name, r.description, r.start_location, r.end_location,
+                   json_array_length(r.points) AS points_count, t.created_at
+            FROM route_templates t
+            JOIN routes r ON r.id = t.route_id
+            WHERE t.public OR t.owner_id = ?1
+            ORDER BY t.public DESC, r.name, t.route_id
+            LIMIT ?2 OFFSET ?3
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_visible(&self, user_id: Uuid) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM route_templates WHERE public OR owner_id = ?1")
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+}
+
+pub struct SqliteShareLinkRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteShareLinkRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ShareLinkRepository for SqliteShareLinkRepository {
+    #[tracing::instrument(skip(self, link), fields(link_id = %link.id, route_id = %link.route_id))]
+    async fn create(&self, link: &ShareLink) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO route_share_links (id, route_id, label, created_at, revoked_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )
+        .bind(link.id)
+        .bind(link.route_id)
+        .bind(&link.label)
+        .bind(link.created_at)
+        .bind(link.revoked_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(link_id = %id))]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ShareLink>, RepositoryError> {
+        sqlx::query_as::<_, ShareLink>(
+            "SELECT id, route_id, label, created_at, revoked_at FROM route_share_links WHERE id = ?1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Vec<ShareLink>, RepositoryError> {
+        sqlx::query_as::<_, ShareLink>(
+            r#"
+            SELECT id, route_id, label, created_at, revoked_at
+            FROM route_share_links
+            WHERE route_id = ?1
+            ORDER BY created_at DESC, id
+            "#,
+        )
+        .bind(route_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(link_id = %id, route_id = %route_id))]
+    async fn revoke(&self, id: Uuid, route_id: Uuid, revoked_at: DateTime<Utc>) -> Result<(), RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE route_share_links SET revoked_at = ?3 WHERE id = ?1 AND route_id = ?2 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .bind(route_id)
+        .bind(revoked_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+pub struct SqliteRouteCollectionRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRouteCollectionRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+const COLLECTION_COLUMNS: &str = "c.id, c.user_id, c.name, \
+    (SELECT COUNT(*) FROM route_collection_routes WHERE collection_id = c.id) AS route_count, \
+    c.created_at, c.updated_at";
+
+impl RouteCollectionRepository for SqliteRouteCollectionRepository {
+    #[tracing::instrument(skip(self, collection), fields(collection_id = %collection.id, user_id = %collection.user_id))]
+    async fn create(&self, collection: &RouteCollection) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO route_collections (id, user_id, name, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )
+        .bind(collection.id)
+        .bind(collection.user_id)
+        .bind(&collection.name)
+        .bind(collection.created_at)
+        .bind(collection.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(collection_id = %id))]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RouteCollection>, RepositoryError> {
+        sqlx::query_as::<_, RouteCollection>(&format!(
+            "SELECT {} FROM route_collections c WHERE c.id = ?1",
+            COLLECTION_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RouteCollection>, RepositoryError> {
+        sqlx::query_as::<_, RouteCollection>(&format!(
+            "SELECT {} FROM route_collections c WHERE c.user_id = ?1 ORDER BY LOWER(c.name), c.id",
+            COLLECTION_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self, name), fields(collection_id = %id, user_id = %user_id))]
+    async fn rename(&self, id: Uuid, user_id: Uuid, name: &str, updated_at: DateTime<Utc>) -> Result<(), RepositoryError> {
+        let result = sqlx::query("UPDATE route_collections SET name = ?3, updated_at = ?4 WHERE id = ?1 AND user_id = ?2")
+            .bind(id)
+            .bind(user_id)
+            .bind(name)
+            .bind(updated_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(collection_id = %id, user_id = %user_id))]
+    async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM route_collections WHERE id = ?1 AND user_id = ?2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(collection_id = %collection_id, route_id = %route_id))]
+    async fn add_route(&self, collection_id: Uuid, route_id: Uuid, added_at: DateTime<Utc>) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO route_collection_routes (collection_id, route_id, added_at) VALUES (?1, ?2, ?3) \
+             ON CONFLICT (collection_id, route_id) DO NOTHING",
+        )
+        .bind(collection_id)
+        .bind(route_id)
+        .bind(added_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(collection_id = %collection_id, route_id = %route_id))]
+    async fn remove_route(&self, collection_id: Uuid, route_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM route_collection_routes WHERE collection_id = ?1 AND route_id = ?2")
+            .bind(collection_id)
+            .bind(route_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+pub struct SqliteSavedSearchRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteSavedSearchRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+const SAVED_SEARCH_COLUMNS: &str =
+    "id, user_id, name, search, category_id, min_lat, min_lng, max_lat, max_lng, last_checked_at, created_at";
+
+impl SavedSearchRepository for SqliteSavedSearchRepository {
+    #[tracing::instrument(skip(self, search), fields(search_id = %search.id, user_id = %search.user_id))]
+    async fn create(&self, search: &SavedSearch) -> Result<(), RepositoryError> {
+        tracing::debug!("creating saved search");
+
+        sqlx::query(
+            r#"
+            INSERT INTO saved_searches (id, user_id, name, search, category_id, min_lat, min_lng, max_lat, max_lng, last_checked_at, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            "#,
+        )
+        .bind(search.id)
+        .bind(search.user_id)
+        .bind(&search.name)
+        .bind(&search.search)
+        .bind(search.category_id)
+        .bind(search.min_lat)
+        .bind(search.min_lng)
+        .bind(search.max_lat)
+        .bind(search.max_lng)
+        .bind(search.last_checked_at)
+        .bind(search.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<SavedSearch>, RepositoryError> {
+        sqlx::query_as::<_, SavedSearch>(&format!(
+            "SELECT {} FROM saved_searches WHERE user_id = ?1 ORDER BY created_at DESC",
+            SAVED_SEARCH_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_by_user_id(&self, user_id: Uuid) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM saved_searches WHERE user_id = ?1")
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(search_id = %id, user_id = %user_id))]
+    async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM saved_searches WHERE id = ?1 AND user_id = ?2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn find_all(&self) -> Result<Vec<SavedSearch>, RepositoryError> {
+        sqlx::query_as::<_, SavedSearch>(&format!("SELECT {} FROM saved_searches", SAVED_SEARCH_COLUMNS))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(search_id = %id))]
+    async fn mark_checked(
+        &self,
+        id: Uuid,
+        previous: DateTime<Utc>,
+        checked_at: DateTime<Utc>,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("UPDATE saved_searches SET last_checked_at = ?3 WHERE id = ?1 AND last_checked_at = ?2")
+            .bind(id)
+            .bind(previous)
+            .bind(checked_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+pub struct SqliteSpamFlagRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteSpamFlagRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SpamFlagRepository for SqliteSpamFlagRepository {
+    #[tracing::instrument(skip(self, flag), fields(flag_id = %flag.id, content_type = %flag.content_type, content_id = %flag.content_id))]
+    async fn create(&self, flag: &SpamFlag) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO spam_flags (id, content_type, content_id, user_id, reasons, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(flag.id)
+        .bind(&flag.content_type)
+        .bind(flag.content_id)
+        .bind(flag.user_id)
+        .bind(&flag.reasons)
+        .bind(flag.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(%reviewed, %limit, %offset))]
+    async fn list(&self, reviewed: bool, limit: i64, offset: i64) -> Result<Vec<SpamFlag>, RepositoryError> {
+        sqlx::query_as::<_, SpamFlag>(
+            r#"
+            SELECT id, content_type, content_id, user_id, reasons, reviewed_by, reviewed_at, created_at
+            FROM spam_flags
+            WHERE (reviewed_at IS NOT NULL) = ?1
+            ORDER BY created_at DESC
+            LIMIT ?2 OFFSET ?3
+            "#,
+        )
+        .bind(reviewed)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(%reviewed))]
+    async fn count(&self, reviewed: bool) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM spam_flags WHERE (reviewed_at IS NOT NULL) = ?1")
+            .bind(reviewed)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(flag_id = %id, reviewed_by = %reviewed_by))]
+    async fn mark_reviewed(&self, id: Uuid, reviewed_by: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE spam_flags SET reviewed_by = ?2, reviewed_at = ?3 WHERE id = ?1 AND reviewed_at IS NULL",
+        )
+        .bind(id)
+        .bind(reviewed_by)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+pub struct SqliteTranslationRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteTranslationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl TranslationRepository for SqliteTranslationRepository {
+    #[tracing::instrument(skip(self), fields(%locale))]
+    async fn find_by_locale(&self, locale: &str) -> Result<Vec<Translation>, RepositoryError> {
+        sqlx::query_as::<_, Translation>(
+            "SELECT key, locale, value, updated_at FROM translations WHERE locale = ?1 ORDER BY key",
+        )
+        .bind(locale)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self, translation), fields(key = %translation.key, locale = %translation.locale))]
+    async fn upsert(&self, translation: &Translation) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO translations (key, locale, value, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (key, locale) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&translation.key)
+        .bind(&translation.locale)
+        .bind(&translation.value)
+        .bind(translation.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(%key, %locale))]
+    async fn delete(&self, key: &str, locale: &str) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM translations WHERE key = ?1 AND locale = ?2")
+            .bind(key)
+            .bind(locale)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+pub struct SqliteNotificationRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteNotificationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl NotificationRepository for SqliteNotificationRepository {
+    #[tracing::instrument(skip(self, notification), fields(notification_id = %notification.id, user_id = %notification.user_id))]
+    async fn create(&self, notification: &Notification) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO notifications (id, user_id, notification_type, route_id, actor_name, message, template_key, params, is_read, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            "#,
+        )
+        .bind(notification.id)
+        .bind(notification.user_id)
+        .bind(&notification.notification_type)
+        .bind(notification.route_id)
+        .bind(&notification.actor_name)
+        .bind(&notification.message)
+        .bind(&notification.template_key)
+        .bind(notification.params.as_ref().map(Json))
+        .bind(notification.is_read)
+        .bind(notification.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %limit, %offset))]
+    async fn find_by_user_id(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<Notification>, RepositoryError> {
+        sqlx::query_as::<_, Notification>(
+            r#"
+            SELECT id, user_id, notification_type, route_id, actor_name, message, template_key, params, is_read, created_at
+            FROM notifications
+            WHERE user_id = ?1
+            ORDER BY created_at DESC
+            LIMIT ?2 OFFSET ?3
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_unread(&self, user_id: Uuid) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM notifications WHERE user_id = ?1 AND is_read = FALSE")
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_by_user_id(&self, user_id: Uuid) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM notifications WHERE user_id = ?1")
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(notification_id = %id, user_id = %user_id))]
+    async fn mark_as_read(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("UPDATE notifications SET is_read = TRUE WHERE id = ?1 AND user_id = ?2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn mark_all_as_read(&self, user_id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE notifications SET is_read = TRUE WHERE user_id = ?1 AND is_read = FALSE")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+}
+
+pub struct SqliteChatMessageRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteChatMessageRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn ensure_conversation_exists(&self, user_id: Uuid, conversation_id: Uuid) -> Result<(), RepositoryError> {
+        let exists: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM chat_messages WHERE user_id = ?1 AND conversation_id = ?2)",
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if exists.0 { Ok(()) } else { Err(RepositoryError::NotFound) }
+    }
+}
+
+impl ChatMessageRepository for SqliteChatMessageRepository {
+    #[tracing::instrument(skip(self, message), fields(message_id = %message.id, user_id = %message.user_id, conversation_id = %message.conversation_id))]
+    async fn create(&self, message: &ChatMessage) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO chat_messages (id, user_id, conversation_id, role, content, actions, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(message.id)
+        .bind(message.user_id)
+        .bind(message.conversation_id)
+        .bind(&message.role)
+        .bind(&message.content)
+        .bind(message.actions.as_ref().map(Json))
+        .bind(message.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id, %limit))]
+    async fn find_by_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<ChatMessage>, RepositoryError> {
+        sqlx::query_as::<_, ChatMessage>(
+            r#"
+            SELECT * FROM (
+                SELECT id, user_id, conversation_id, role, content, actions, created_at
+                FROM chat_messages
+                WHERE user_id = ?1 AND conversation_id = ?2
+                ORDER BY created_at DESC
+                LIMIT ?3
+            ) sub
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %limit, %offset))]
+    async fn list_conversations(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+        include_archived: bool,
+    ) -> Result<Vec<ConversationSummary>, RepositoryError> {
+        sqlx::query_as::<_, ConversationSummary>(
+            r#"
+            SELECT
+                c.conversation_id,
+                COALESCE((
+                    SELECT content FROM chat_messages
+                    WHERE conversation_id = c.conversation_id AND user_id = ?1
+                    ORDER BY created_at DESC LIMIT 1
+                ), '') AS last_message,
+                c.message_count,
+                c.created_at,
+                c.updated_at,
+                COALESCE((
+                    SELECT substr(content, 1, 100) FROM chat_messages
+                    WHERE conversation_id = c.conversation_id AND user_id = ?1 AND role = 'user'
+                    ORDER BY created_at ASC LIMIT 1
+                ), '') AS title,
+                c.archived,
+                c.pinned
+            FROM (
+                SELECT
+                    g.*,
+                    COALESCE(cc.archived, 0) AS archived,
+                    COALESCE(cc.pinned, 0) AS pinned
+                FROM (
+                    SELECT
+                        conversation_id,
+                        COUNT(*) AS message_count,
+                        MIN(created_at) AS created_at,
+                        MAX(created_at) AS updated_at
+                    FROM chat_messages
+                    WHERE user_id = ?1
+                    GROUP BY conversation_id
+                ) g
+                LEFT JOIN chat_conversations cc
+                    ON cc.user_id = ?1 AND cc.conversation_id = g.conversation_id
+                WHERE ?4 OR NOT COALESCE(cc.archived, 0)
+                ORDER BY pinned DESC, g.updated_at DESC
+                LIMIT ?2 OFFSET ?3
+            ) c
+            ORDER BY c.pinned DESC, c.updated_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn delete_conversation(&self, user_id: Uuid, conversation_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM chat_messages WHERE user_id = ?1 AND conversation_id = ?2")
+            .bind(user_id)
+            .bind(conversation_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        sqlx::query("DELETE FROM chat_conversations WHERE user_id = ?1 AND conversation_id = ?2")
+            .bind(user_id)
+            .bind(conversation_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn count_conversations(&self, user_id: Uuid, include_archived: bool) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM (SELECT DISTINCT conversation_id FROM chat_messages WHERE user_id = ?1) g
+            LEFT JOIN chat_conversations cc
+                ON cc.user_id = ?1 AND cc.conversation_id = g.conversation_id
+            WHERE ?2 OR NOT COALESCE(cc.archived, 0)
+            "#,
+        )
+        .bind(user_id)
+        .bind(include_archived)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn set_conversation_flags(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        archived: Option<bool>,
+        pinned: Option<bool>,
+    ) -> Result<ConversationFlags, RepositoryError> {
+        self.ensure_conversation_exists(user_id, conversation_id).await?;
+
+        sqlx::query_as::<_, ConversationFlags>(
+            r#"
+            INSERT INTO chat_conversations (user_id, conversation_id, archived, pinned, updated_at)
+            VALUES (?1, ?2, COALESCE(?3, 0), COALESCE(?4, 0), ?5)
+            ON CONFLICT (user_id, conversation_id) DO UPDATE SET
+                archived = COALESCE(?3, chat_conversations.archived),
+                pinned = COALESCE(?4, chat_conversations.pinned),
+                updated_at = ?5
+            RETURNING archived, pinned
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .bind(archived)
+        .bind(pinned)
+        .bind(Utc::now())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self, token), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn share_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        token: Uuid,
+    ) -> Result<Uuid, RepositoryError> {
+        self.ensure_conversation_exists(user_id, conversation_id).await?;
+
+        let row: (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO chat_conversations (user_id, conversation_id, share_token, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (user_id, conversation_id) DO UPDATE SET
+                share_token = COALESCE(chat_conversations.share_token, excluded.share_token),
+                updated_at = ?4
+            RETURNING share_token
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .bind(token)
+        .bind(Utc::now())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(row.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn unshare_conversation(&self, user_id: Uuid, conversation_id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE chat_conversations SET share_token = NULL, updated_at = ?3 WHERE user_id = ?1 AND conversation_id = ?2",
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, token))]
+    async fn find_shared_conversation(&self, token: Uuid) -> Result<Option<(Uuid, Uuid)>, RepositoryError> {
+        sqlx::query_as::<_, (Uuid, Uuid)>(
+            "SELECT user_id, conversation_id FROM chat_conversations WHERE share_token = ?1",
+        )
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_all_by_user(&self, user_id: Uuid) -> Result<Vec<ChatMessage>, RepositoryError> {
+        sqlx::query_as::<_, ChatMessage>(
+            r#"
+            SELECT id, user_id, conversation_id, role, content, actions, created_at
+            FROM chat_messages
+            WHERE user_id = ?1
+            ORDER BY conversation_id, created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, message_id = %message_id))]
+    async fn delete_message(&self, user_id: Uuid, conversation_id: Uuid, message_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM chat_messages WHERE id = ?1 AND user_id = ?2 AND conversation_id = ?3")
+            .bind(message_id)
+            .bind(user_id)
+            .bind(conversation_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, action), fields(action_id = %action.id, user_id = %action.user_id, tool = %action.tool))]
+    async fn create_pending_action(&self, action: &PendingChatAction) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO chat_pending_actions (id, user_id, conversation_id, tool, arguments, summary, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(action.id)
+        .bind(action.user_id)
+        .bind(action.conversation_id)
+        .bind(&action.tool)
+        .bind(Json(&action.arguments))
+        .bind(&action.summary)
+        .bind(action.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, action_id = %action_id))]
+    async fn take_pending_action(
+        &self,
+        user_id: Uuid,
+        action_id: Uuid,
+    ) -> Result<Option<PendingChatAction>, RepositoryError> {
+        sqlx::query_as::<_, PendingChatAction>(
+            r#"
+            DELETE FROM chat_pending_actions
+            WHERE id = ?1 AND user_id = ?2
+            RETURNING id, user_id, conversation_id, tool, arguments, summary, created_at
+            "#,
+        )
+        .bind(action_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_pending_actions_before(&self, before: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM chat_pending_actions WHERE created_at < ?1")
+            .bind(before)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_chat_language(&self, user_id: Uuid) -> Result<Option<String>, RepositoryError> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT language FROM chat_preferences WHERE user_id = ?1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(row.map(|(language,)| language))
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn set_chat_language(&self, user_id: Uuid, language: Option<String>) -> Result<(), RepositoryError> {
+        match language {
+            Some(language) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO chat_preferences (user_id, language, updated_at)
+                    VALUES (?1, ?2, ?3)
+                    ON CONFLICT (user_id) DO UPDATE SET language = excluded.language, updated_at = excluded.updated_at
+                    "#,
+                )
+                .bind(user_id)
+                .bind(language)
+                .bind(Utc::now())
+                .execute(&self.pool)
+                .await
+                .map_err(RepositoryError::from)?;
+            }
+            None => {
+                sqlx::query("DELETE FROM chat_preferences WHERE user_id = ?1")
+                    .bind(user_id)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(RepositoryError::from)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, turn), fields(user_id = %turn.user_id, conversation_id = %turn.conversation_id))]
+    async fn record_turn(&self, turn: &ChatTurnRecord) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO chat_turns (id, user_id, conversation_id, tool_calls, latency_ms, error, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(turn.id)
+        .bind(turn.user_id)
+        .bind(turn.conversation_id)
+        .bind(Json(&turn.tool_calls))
+        .bind(turn.latency_ms)
+        .bind(&turn.error)
+        .bind(turn.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, reason), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn flag_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        reason: Option<String>,
+        review_consent: bool,
+    ) -> Result<(), RepositoryError> {
+        self.ensure_conversation_exists(user_id, conversation_id).await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO chat_conversations (user_id, conversation_id, flagged_at, flag_reason, review_consent, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?3)
+            ON CONFLICT (user_id, conversation_id) DO UPDATE SET
+                flagged_at = ?3,
+                flag_reason = ?4,
+                review_consent = ?5,
+                updated_at = ?3
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .bind(Utc::now())
+        .bind(reason)
+        .bind(review_consent)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_flagged_conversations(&self, limit: i64, offset: i64) -> Result<Vec<FlaggedConversation>, RepositoryError> {
+        sqlx::query_as::<_, FlaggedConversation>(
+            r#"
+            SELECT c.user_id, c.conversation_id, c.flag_reason AS reason, c.review_consent,
+                   (SELECT COUNT(*) FROM chat_messages m
+                    WHERE m.user_id = c.user_id AND m.conversation_id = c.conversation_id) AS message_count,
+                   c.flagged_at
+            FROM chat_conversations c
+            WHERE c.flagged_at IS NOT NULL
+            ORDER BY c.flagged_at DESC
+            LIMIT ?1 OFFSET ?2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn count_flagged_conversations(&self) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM chat_conversations WHERE flagged_at IS NOT NULL")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    async fn find_flagged_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+    ) -> Result<Option<FlaggedConversation>, RepositoryError> {
+        sqlx::query_as::<_, FlaggedConversation>(
+            r#"
+            SELECT c.user_id, c.conversation_id, c.flag_reason AS reason, c.review_consent,
+                   (SELECT COUNT(*) FROM chat_messages m
+                    WHERE m.user_id = c.user_id AND m.conversation_id = c.conversation_id) AS message_count,
+                   c.flagged_at
+            FROM chat_conversations c
+            WHERE c.user_id = ?1 AND c.conversation_id = ?2 AND c.flagged_at IS NOT NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(conversation_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+}
+
+pub struct SqliteBookmarkRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteBookmarkRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl BookmarkRepository for SqliteBookmarkRepository {
+    #[tracing::instrument(skip(self, bookmark), fields(bookmark_id = %bookmark.id, route_id = %bookmark.route_id, user_id = %bookmark.user_id))]
+    async fn create(&self, bookmark: &RouteBookmark) -> Result<(), RepositoryError> {
+        sqlx::query("INSERT INTO route_bookmarks (id, route_id, user_id, created_at) VALUES (?1, ?2, ?3, ?4)")
+            .bind(bookmark.id)
+            .bind(bookmark.route_id)
+            .bind(bookmark.user_id)
+            .bind(bookmark.created_at)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
+    async fn delete_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<(), RepositoryError> {
+        let result = sqlx::query("DELETE FROM route_bookmarks WHERE route_id = ?1 AND user_id = ?2")
+            .bind(route_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
+    async fn find_by_route_and_user(&self, route_id: Uuid, user_id: Uuid) -> Result<Option<RouteBookmark>, RepositoryError> {
+        sqlx::query_as::<_, RouteBookmark>(
+            "SELECT id, route_id, user_id, created_at FROM route_bookmarks WHERE route_id = ?1 AND user_id = ?2",
+        )
+        .bind(route_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<ExploreRouteRow>, RepositoryError> {
+        let rows = sqlx::query_as::<_, SqliteExploreRow>(&format!(
+            r#"
+            SELECT
+                r.id,
+                r.name,
+                json_array_length(r.points) AS points_count,
+                r.created_at,
+                r.share_token,
+                r.likes_count,
+                r.bookmarks_count,
+                COALESCE((SELECT AVG(rating) FROM route_ratings WHERE route_id = r.id), 0.0) AS avg_rating,
+                (SELECT COUNT(*) FROM route_ratings WHERE route_id = r.id) AS ratings_count,
+                (SELECT COUNT(*) FROM comments WHERE route_id = r.id AND approved) AS comments_count,
+                {},
+                r.seasons,
+                r.start_location,
+                r.end_location,
+                r.cover_url,
+                r.cover_thumbnail_url,
+                {}
+            FROM route_bookmarks rb
+            JOIN routes r ON r.id = rb.route_id
+            WHERE rb.user_id = ?1
+            ORDER BY rb.created_at DESC
+            "#,
+            ROUTE_CATEGORY_IDS, ROUTE_PATH
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(rows.into_iter().map(ExploreRouteRow::from).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_user_ids_by_route(&self, route_id: Uuid) -> Result<Vec<Uuid>, RepositoryError> {
+        let rows: Vec<(Uuid,)> = sqlx::query_as("SELECT user_id FROM route_bookmarks WHERE route_id = ?1")
+            .bind(route_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+}
+
+pub struct SqliteNotificationPreferenceRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteNotificationPreferenceRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl NotificationPreferenceRepository for SqliteNotificationPreferenceRepository {
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_disabled_types(&self, user_id: Uuid) -> Result<Vec<String>, RepositoryError> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT notification_type FROM notification_preferences WHERE user_id = ?1 AND enabled = FALSE",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(rows.into_iter().map(|(t,)| t).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %notification_type, %enabled))]
+    async fn set_enabled(&self, user_id: Uuid, notification_type: &str, enabled: bool) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO notification_preferences (user_id, notification_type, enabled, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (user_id, notification_type)
+            DO UPDATE SET enabled = excluded.enabled, updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(notification_type)
+        .bind(enabled)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, user_ids), fields(%notification_type, user_count = user_ids.len()))]
+    async fn find_opted_out(&self, notification_type: &str, user_ids: Vec<Uuid>) -> Result<Vec<Uuid>, RepositoryError> {
+        // SQLite has no array parameters; filter the (small) opted-out set in Rust.
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT user_id FROM notification_preferences WHERE notification_type = ?1 AND enabled = FALSE",
+        )
+        .bind(notification_type)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id,)| id)
+            .filter(|id| user_ids.contains(id))
+            .collect())
+    }
+}
+
+pub struct SqliteReportRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteReportRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ReportRepository for SqliteReportRepository {
+    #[tracing::instrument(skip(self, report), fields(report_id = %report.id, route_id = %report.route_id, reporter_id = %report.reporter_id))]
+    async fn create(&self, report: &RouteReport) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO route_reports (id, route_id, reporter_id, category, details, status, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(report.id)
+        .bind(report.route_id)
+        .bind(report.reporter_id)
+        .bind(&report.category)
+        .bind(&report.details)
+        .bind(&report.status)
+        .bind(report.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(report_id = %id))]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RouteReport>, RepositoryError> {
+        sqlx::query_as::<_, RouteReport>(
+            r#"
+            SELECT id, route_id, reporter_id, category, details, status, resolution_note, resolved_by, resolved_at, created_at
+            FROM route_reports
+            WHERE id = ?1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, reporter_id = %reporter_id))]
+    async fn find_open_by_route_and_reporter(
+        &self,
+        route_id: Uuid,
+        reporter_id: Uuid,
+    ) -> Result<Option<RouteReport>, RepositoryError> {
+        sqlx::query_as::<_, RouteReport>(
+            r#"
+            SELECT id, route_id, reporter_id, category, details, status, resolution_note, resolved_by, resolved_at, created_at
+            FROM route_reports
+            WHERE route_id = ?1 AND reporter_id = ?2 AND status = 'open'
+            "#,
+        )
+        .bind(route_id)
+        .bind(reporter_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(?status, %limit, %offset))]
+    async fn list(&self, status: Option<String>, limit: i64, offset: i64) -> Result<Vec<RouteReport>, RepositoryError> {
+        sqlx::query_as::<_, RouteReport>(
+            r#"
+            SELECT id, route_id, reporter_id, category, details, status, resolution_note, resolved_by, resolved_at, created_at
+            FROM route_reports
+            WHERE (?1 IS NULL OR status = ?1)
+            ORDER BY created_at DESC
+            LIMIT ?2 OFFSET ?3
+            "#,
+        )
+        .bind(&status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(?status))]
+    async fn count(&self, status: Option<String>) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM route_reports WHERE (?1 IS NULL OR status = ?1)")
+            .bind(&status)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self, resolution_note), fields(report_id = %id, %status, resolved_by = %resolved_by))]
+    async fn resolve(
+        &self,
+        id: Uuid,
+        status: &str,
+        resolved_by: Uuid,
+        resolution_note: Option<String>,
+    ) -> Result<(), RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE route_reports
+            SET status = ?2, resolved_by = ?3, resolution_note = ?4, resolved_at = ?5
+            WHERE id = ?1
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(resolved_by)
+        .bind(&resolution_note)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+pub struct SqliteRouteGeometryRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRouteGeometryRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl RouteGeometryRepository for SqliteRouteGeometryRepository {
+    #[tracing::instrument(skip(self, geometry), fields(route_id = %geometry.route_id))]
+    async fn upsert(&self, geometry: &RouteGeometry) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO route_geometries (route_id, segments, distance_km, route_updated_at, computed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (route_id)
+            DO UPDATE SET segments = excluded.segments, distance_km = excluded.distance_km,
+                          route_updated_at = excluded.route_updated_at, computed_at = excluded.computed_at
+            "#,
+        )
+        .bind(geometry.route_id)
+        .bind(Json(&geometry.segments))
+        .bind(geometry.distance_km)
+        .bind(geometry.route_updated_at)
+        .bind(geometry.computed_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteGeometry>, RepositoryError> {
+        sqlx::query_as::<_, RouteGeometry>(
+            "SELECT route_id, segments, distance_km, route_updated_at, computed_at FROM route_geometries WHERE route_id = ?1",
+        )
+        .bind(route_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+}
+
+pub struct SqliteRouteElevationRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRouteElevationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl RouteElevationRepository for SqliteRouteElevationRepository {
+    #[tracing::instrument(skip(self, elevation), fields(route_id = %elevation.route_id))]
+    async fn upsert(&self, elevation: &RouteElevation) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO route_elevations (route_id, samples, gain_m, loss_m, min_m, max_m, route_updated_at, computed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT (route_id)
+            DO UPDATE SET samples = excluded.samples, gain_m = excluded.gain_m, loss_m = excluded.loss_m,
+                          min_m = excluded.min_m, max_m = excluded.max_m,
+                          route_updated_at = excluded.route_updated_at, computed_at = excluded.computed_at
+            "#,
+        )
+        .bind(elevation.route_id)
+        .bind(Json(&elevation.samples))
+        .bind(elevation.gain_m)
+        .bind(elevation.loss_m)
+        .bind(elevation.min_m)
+        .bind(elevation.max_m)
+        .bind(elevation.route_updated_at)
+        .bind(elevation.computed_at)
+        .execute(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteElevation>, RepositoryError> {
+        sqlx::query_as::<_, RouteElevation>(
+            r#"
+            SELECT route_id, samples, gain_m, loss_m, min_m, max_m, route_updated_at, computed_at
+            FROM route_elevations
+            WHERE route_id = ?1
+            "#,
+        )
+        .bind(route_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+}
+
+pub struct SqliteStatsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteStatsRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl StatsRepository for SqliteStatsRepository {
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %day))]
+    async fn record_usage(&self, user_id: Uuid, day: NaiveDate) -> Result<(), RepositoryError> {
+        sqlx::query("INSERT INTO usage_events (day, user_id, first_seen_at) VALUES (?1, ?2, ?3) ON CONFLICT DO NOTHING")
+            .bind(day)
+            .bind(user_id)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn count_active_users(&self, since: NaiveDate) -> Result<i64, RepositoryError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(DISTINCT user_id) FROM usage_events WHERE day >= ?1")
+            .bind(since)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        Ok(count.0)
+    }
+
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn active_users_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError> {
+        sqlx::query_as::<_, DailyCount>(
+            "SELECT day, COUNT(*) AS count FROM usage_events WHERE day >= ?1 GROUP BY day ORDER BY day",
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn routes_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError> {
+        sqlx::query_as::<_, DailyCount>(
+            r#"
+            SELECT date(created_at) AS day, COUNT(*) AS count
+            FROM routes
+            WHERE date(created_at) >= ?1
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn chat_usage(&self, since: NaiveDate) -> Result<ChatUsage, RepositoryError> {
+        sqlx::query_as::<_, ChatUsage>(
+            r#"
+            SELECT COUNT(*) AS messages,
+                   COUNT(DISTINCT user_id) AS users,
+                   COUNT(DISTINCT conversation_id) AS conversations
+            FROM chat_messages
+            WHERE role = 'user' AND date(created_at) >= ?1
+            "#,
+        )
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn photo_volume(&self) -> Result<PhotoVolume, RepositoryError> {
+        sqlx::query_as::<_, PhotoVolume>(
+            r#"
+            SELECT COALESCE(SUM(json_extract(p.value, '$.photo.status') = 'pending'), 0) AS pending,
+                   COALESCE(SUM(json_extract(p.value, '$.photo.status') = 'processing'), 0) AS processing,
+                   COALESCE(SUM(json_extract(p.value, '$.photo.status') = 'done'), 0) AS done,
+                   COALESCE(SUM(json_extract(p.value, '$.photo.status') = 'failed'), 0) AS failed
+            FROM routes r, json_each(r.points) p
+            WHERE json_type(p.value, '$.photo') = 'object'
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn chat_messages_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError> {
+        sqlx::query_as::<_, DailyCount>(
+            r#"
+            SELECT date(created_at) AS day, COUNT(*) AS count
+            FROM chat_messages
+            WHERE role = 'user' AND date(created_at) >= ?1
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn chat_turn_summary(&self, since: NaiveDate) -> Result<ChatTurnSummary, RepositoryError> {
+        sqlx::query_as::<_, ChatTurnSummary>(
+            r#"
+            SELECT COUNT(*) AS turns,
+                   COALESCE(SUM(error IS NOT NULL), 0) AS errors,
+                   COALESCE(AVG(latency_ms), 0.0) AS avg_latency_ms
+            FROM chat_turns
+            WHERE date(created_at) >= ?1
+            "#,
+        )
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(%since))]
+    async fn chat_tool_calls(&self, since: NaiveDate) -> Result<Vec<ToolCallCount>, RepositoryError> {
+        sqlx::query_as::<_, ToolCallCount>(
+            r#"
+            SELECT tool.value AS tool, COUNT(*) AS count
+            FROM chat_turns t, json_each(t.tool_calls) tool
+            WHERE date(t.created_at) >= ?1
+            GROUP BY 1
+            ORDER BY count DESC, tool
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    #[tracing::instrument(skip(self), fields(%since, %limit))]
+    async fn top_chat_users(&self, since: NaiveDate, limit: i64) -> Result<Vec<ChatUserVolume>, RepositoryError> {
+        sqlx::query_as::<_, ChatUserVolume>(
+            r#"
+            SELECT user_id, COUNT(*) AS messages
+            FROM chat_messages
+            WHERE role = 'user' AND date(created_at) >= ?1
+            GROUP BY user_id
+            ORDER BY messages DESC, user_id
+            LIMIT ?2
+            "#,
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+}
+
+pub struct SqliteUserDataRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteUserDataRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl UserDataRepository for SqliteUserDataRepository {
+    #[tracing::instrument(skip(self), fields(table = table.table, %user_id))]
+    async fn find_user_rows(&self, table: UserKeyedTable, user_id: Uuid) -> Result<Vec<serde_json::Value>, RepositoryError> {
+        // SQLite has no row-to-JSON cast, so the object is spelled out from
+        // the table's columns, with UUID BLOBs written out as text. Names
+        // come from `USER_KEYED_TABLES`, never from a request.
+        let columns: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?1) ORDER BY cid")
+            .bind(table.table)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+        let fields = columns
+            .iter()
+            .map(|column| {
+                let hex = format!("hex(\"{column}\")");
+                format!(
+                    "'{column}', CASE typeof(\"{column}\") WHEN 'blob' THEN lower(substr({hex}, 1, 8) || '-' || substr({hex}, 9, 4) || '-' || substr({hex}, 13, 4) || '-' || substr({hex}, 17, 4) || '-' || substr({hex}, 21)) ELSE \"{column}\" END"
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = format!("SELECT json_object({fields}) FROM {} WHERE {} = ?1", table.table, table.user_column);
+        let rows: Vec<Json<serde_json::Value>> = sqlx::query_scalar(&query)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(RepositoryError::from)?;
+        Ok(rows.into_iter().map(|Json(row)| row).collect())
+    }
+}
+
+pub async fn create_pool(database_url: &str, settings: &PoolSettings) -> Result<SqlitePool, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(database_url)?
+        .create_if_missing(true)
+        .foreign_keys(true);
+    settings.options::<Sqlite>().connect_with(options)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::comment::Comment;
+    use crate::domain::activity::{ACTIVITY_CREATED, ACTIVITY_PHOTOS_PROCESSED};
+    use crate::domain::route_geometry::SegmentGeometry;
+    use crate::domain::spam::{SPAM_CONTENT_COMMENT, SPAM_REASON_DUPLICATE_TEXT};
+
+    async fn test_pool() -> SqlitePool {
+        let pool = create_pool("sqlite::memory:", &PoolSettings { max_connections: 1, ..Default::default() }).await.unwrap();
+        sqlx::migrate!("./migrations_sqlite").run(&pool).await.unwrap();
+        pool
+    }
+
+    fn route(user_id: Uuid) -> Route {
+        Route {
+            id: Uuid::new_v4(),
+            user_id,
+            name: "Lake loop".to_string(),
+            points: vec![
+                RoutePoint { lat: 55.0, lng: 37.0, name: Some("Start".to_string()), segment_mode: None, photo: None, address: None },
+                RoutePoint { lat: 55.1, lng: 37.1, name: None, segment_mode: Some("walk".to_string()), photo: None, address: None },
+            ],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            share_token: None,
+            visibility: RouteVisibility::Private,
+            category_ids: vec![],
+            start_location: None,
+            end_location: None,
+            seasons: vec!["summer".to_string()],
+            description: None,
+            archived_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_route_round_trip_with_categories() {
+        let pool = test_pool().await;
+        let categories = SqliteCategoryRepository::new(pool.clone()).find_all().await.unwrap();
+        let repo = SqliteRouteRepository::new(pool);
+        let mut route = route(Uuid::new_v4());
+        route.category_ids = vec![categories[0].id, categories[1].id];
+
+        repo.create(&route).await.unwrap();
+        let found = repo.find_by_id(route.id).await.unwrap().unwrap();
+
+        assert_eq!(found.points, route.points);
+        assert_eq!(found.seasons, route.seasons);
+        let mut expected = route.category_ids.clone();
+        let mut actual = found.category_ids.clone();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_replace_points_only_over_the_version_read() {
+        let repo = SqliteRouteRepository::new(test_pool().await);
+        let route = route(Uuid::new_v4());
+        repo.create(&route).await.unwrap();
+        let stored = repo.find_by_id(route.id).await.unwrap().unwrap();
+        let points = &stored.points[..1];
+
+        let first = Utc::now();
+        assert!(repo.replace_points(route.id, points, stored.updated_at, first).await.unwrap());
+        assert!(!repo.replace_points(route.id, &stored.points, stored.updated_at, Utc::now()).await.unwrap());
+
+        let found = repo.find_by_id(route.id).await.unwrap().unwrap();
+        assert_eq!(found.points, points);
+        assert_eq!(found.updated_at, first);
+    }
+
+    #[tokio::test]
+    async fn test_user_routes_are_paged_sorted_and_skip_archived() {
+        let pool = test_pool().await;
+        let repo = SqliteRouteRepository::new(pool);
+        let user_id = Uuid::new_v4();
+        let mut names = Vec::new();
+        for (i, name) in ["beach", "Canyon", "alps"].into_iter().enumerate() {
+            let mut route = route(user_id);
+            route.name = name.to_string();
+            route.created_at = Utc::now() - chrono::Duration::minutes(10 - i as i64);
+            repo.create(&route).await.unwrap();
+            names.push(route);
+        }
+        repo.create(&route(Uuid::new_v4())).await.unwrap();
+        repo.set_archived_at(names[1].id, Some(Utc::now())).await.unwrap();
+        let all = UserRoutesFilter { include_archived: true, ..Default::default() };
+        let by_name = "LOWER(r.name) ASC, r.created_at DESC";
+
+        let first = repo.find_by_user_id_paginated(user_id, &all, by_name, 2, 0).await.unwrap();
+        let rest = repo.find_by_user_id_paginated(user_id, &all, by_name, 2, 2).await.unwrap();
+        let active = repo
+            .find_by_user_id_paginated(user_id, &UserRoutesFilter::default(), "r.created_at DESC", 10, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(first.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), ["alps", "beach"]);
+        assert_eq!(rest.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), ["Canyon"]);
+        assert_eq!(active.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), ["alps", "beach"]);
+        assert_eq!(repo.count_by_user_id(user_id, &all).await.unwrap(), 3);
+        assert_eq!(repo.count_by_user_id(user_id, &UserRoutesFilter::default()).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_user_routes_filter_by_name_category_and_creation_time() {
+        let pool = test_pool().await;
+        let categories = SqliteCategoryRepository::new(pool.clone()).find_all().await.unwrap();
+        let repo = SqliteRouteRepository::new(pool);
+        let user_id = Uuid::new_v4();
+        let cutoff = Utc::now() - chrono::Duration::days(30);
+        let mut old = route(user_id);
+        old.name = "Kazan 2023".to_string();
+        old.created_at = cutoff - chrono::Duration::days(300);
+        old.category_ids = vec![categories[0].id];
+        let mut recent = route(user_id);
+        recent.name = "Kazan again".to_string();
+        let mut other = route(user_id);
+        other.name = "Sochi".to_string();
+        other.category_ids = vec![categories[0].id];
+        for r in [&old, &recent, &other] {
+            repo.create(r).await.unwrap();
+        }
+        let mut someone_else = route(Uuid::new_v4());
+        someone_else.name = "Kazan".to_string();
+        repo.create(&someone_else).await.unwrap();
+
+        async fn ids(repo: &SqliteRouteRepository, user_id: Uuid, filter: UserRoutesFilter) -> Vec<Uuid> {
+            let mut ids: Vec<Uuid> = repo
+                .find_by_user_id_paginated(user_id, &filter, "r.created_at DESC", 10, 0)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|r| r.id)
+                .collect();
+            ids.sort();
+            assert_eq!(repo.count_by_user_id(user_id, &filter).await.unwrap(), ids.len() as i64);
+            ids
+        }
+        let sorted = |mut v: Vec<Uuid>| {
+            v.sort();
+            v
+        };
+
+        let kazan = UserRoutesFilter { search: Some("kazan".to_string()), ..Default::default() };
+        assert_eq!(ids(&repo, user_id, kazan.clone()).await, sorted(vec![old.id, recent.id]));
+        let kazan_before = UserRoutesFilter { created_before: Some(cutoff), ..kazan.clone() };
+        assert_eq!(ids(&repo, user_id, kazan_before).await, vec![old.id]);
+        let kazan_after = UserRoutesFilter { created_after: Some(cutoff), ..kazan };
+        assert_eq!(ids(&repo, user_id, kazan_after).await, vec![recent.id]);
+        let in_category = UserRoutesFilter { category_id: Some(categories[0].id), ..Default::default() };
+        assert_eq!(ids(&repo, user_id, in_category).await, sorted(vec![old.id, other.id]));
+    }
+
+    #[tokio::test]
+    async fn test_explore_filters_by_season_and_counts_likes_and_comments() {
+        let pool = test_pool().await;
+        let repo = SqliteRouteRepository::new(pool.clone());
+        let likes = SqliteLikeRepository::new(pool.clone());
+        let comments = SqliteCommentRepository::new(pool);
+        let shared = route(Uuid::new_v4());
+        let mut winter = route(Uuid::new_v4());
+        winter.seasons = vec!["winter".to_string()];
+        for r in [&shared, &winter] {
+            repo.create(r).await.unwrap();
+            repo.set_visibility(r.id, RouteVisibility::Public, Some(Uuid::new_v4())).await.unwrap();
+        }
+        repo.update_locations(shared.id, Some("Kazan".to_string()), Some("Sviyazhsk".to_string()))
+            .await
+            .unwrap();
+        likes
+            .create(&RouteLike { id: Uuid::new_v4(), route_id: shared.id, user_id: Uuid::new_v4(), created_at: Utc::now() })
+            .await
+            .unwrap();
+        let comment = |text: &str| Comment::new(shared.id, Uuid::new_v4(), "Ann".to_string(), text.to_string());
+        comments.create(&comment("Great views")).await.unwrap();
+        comments.create(&comment("Muddy in spring").with_approved(false)).await.unwrap();
+
+        let rows = repo
+            .explore_shared(None, None, None, Some("summer".to_string()), "r.created_at DESC", 10, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].id, shared.id);
+        assert_eq!(rows[0].points_count, 2);
+        assert_eq!(rows[0].likes_count, 1);
+        assert_eq!(rows[0].comments_count, 1);
+        assert_eq!(rows[0].start_location.as_deref(), Some("Kazan"));
+        assert_eq!(rows[0].end_location.as_deref(), Some("Sviyazhsk"));
+        assert_eq!(repo.count_explore_shared(None, None, None).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_like_and_bookmark_counters_follow_triggers_and_reconcile() {
+        let pool = test_pool().await;
+        let repo = SqliteRouteRepository::new(pool.clone());
+        let likes = SqliteLikeRepository::new(pool.clone());
+        let bookmarks = SqliteBookmarkRepository::new(pool.clone());
+        let shared = route(Uuid::new_v4());
+        repo.create(&shared).await.unwrap();
+        repo.set_visibility(shared.id, RouteVisibility::Public, Some(Uuid::new_v4())).await.unwrap();
+
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        for user_id in [alice, bob] {
+            likes
+                .create(&RouteLike { id: Uuid::new_v4(), route_id: shared.id, user_id, created_at: Utc::now() })
+                .await
+                .unwrap();
+        }
+        bookmarks.create(&RouteBookmark::new(shared.id, alice)).await.unwrap();
+        likes.delete_by_route_and_user(shared.id, bob).await.unwrap();
+
+        let rows = repo.explore_shared(None, None, None, None, "r.created_at DESC", 10, 0).await.unwrap();
+        assert_eq!((rows[0].likes_count, rows[0].bookmarks_count), (1, 1));
+        assert_eq!(likes.count_by_route_id(shared.id).await.unwrap(), 1);
+        assert_eq!(repo.reconcile_counters().await.unwrap(), 0);
+
+        sqlx::query("UPDATE routes SET bookmarks_count = 5 WHERE id = ?1")
+            .bind(shared.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(repo.reconcile_counters().await.unwrap(), 1);
+        let rows = repo.explore_shared(None, None, None, None, "r.created_at DESC", 10, 0).await.unwrap();
+        assert_eq!(rows[0].bookmarks_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cover_is_listed_in_explore_until_cleared() {
+        let pool = test_pool().await;
+        let repo = SqliteRouteRepository::new(pool);
+        let shared = route(Uuid::new_v4());
+        repo.create(&shared).await.unwrap();
+        repo.set_visibility(shared.id, RouteVisibility::Public, Some(Uuid::new_v4())).await.unwrap();
+
+        let cover = RouteCover {
+            url: "https://cdn.example/photo.jpg".to_string(),
+            thumbnail_url: Some("https://cdn.example/thumb.jpg".to_string()),
+        };
+        repo.set_cover(shared.id, Some(cover)).await.unwrap();
+        let rows = repo.explore_shared(None, None, None, None, "r.created_at DESC", 10, 0).await.unwrap();
+        assert_eq!(rows[0].cover_url.as_deref(), Some("https://cdn.example/photo.jpg"));
+        assert_eq!(rows[0].cover_thumbnail_url.as_deref(), Some("https://cdn.example/thumb.jpg"));
+
+        repo.set_cover(shared.id, None).await.unwrap();
+        let rows = repo.explore_shared(None, None, None, None, "r.created_at DESC", 10, 0).await.unwrap();
+        assert_eq!(rows[0].cover_url, None);
+        assert_eq!(rows[0].cover_thumbnail_url, None);
+        assert!(matches!(repo.set_cover(Uuid::new_v4(), None).await, Err(RepositoryError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_explore_facets_count_categories_and_difficulty() {
+        let pool = test_pool().await;
+        let repo = SqliteRouteRepository::new(pool.clone());
+        let categories = SqliteCategoryRepository::new(pool.clone());
+        let geometries = SqliteRouteGeometryRepository::new(pool);
+        let (parks, museums) = (Category::new("parks".to_string()), Category::new("museums".to_string()));
+        categories.create(&parks).await.unwrap();
+        categories.create(&museums).await.unwrap();
+
+        let mut short = route(Uuid::new_v4());
+        short.category_ids = vec![parks.id];
+        let mut long = Route { id: Uuid::new_v4(), ..short.clone() };
+        long.category_ids = vec![parks.id, museums.id];
+        let uncomputed = Route { id: Uuid::new_v4(), ..short.clone() };
+        for (r, distance_km) in [(&short, Some(2.0)), (&long, Some(40.0)), (&uncomputed, None)] {
+            repo.create(r).await.unwrap();
+            repo.set_visibility(r.id, RouteVisibility::Public, Some(Uuid::new_v4())).await.unwrap();
+            if let Some(distance_km) = distance_km {
+                let segment = SegmentGeometry {
+                    from_index: 0,
+                    to_index: 1,
+                    profile: "foot".to_string(),
+                    snapped: true,
+                    distance_km,
+                    coordinates: vec![],
+                };
+                geometries.upsert(&RouteGeometry::new(r.id, vec![segment], r.updated_at)).await.unwrap();
+            }
+        }
+
+        let mut by_category = repo.count_explore_by_category(None, None).await.unwrap();
+        by_category.sort_by_key(|(_, count)| *count);
+        assert_eq!(by_category, vec![(museums.id, 1), (parks.id, 3)]);
+
+        let thresholds = DifficultyThresholds::default();
+        let mut by_difficulty = repo.count_explore_by_difficulty(None, None, None, &thresholds).await.unwrap();
+        by_difficulty.sort();
+        assert_eq!(by_difficulty, vec![("easy".to_string(), 1), ("moderate".to_string(), 1)]);
+
+        let filtered = repo
+            .count_explore_by_difficulty(None, Some(museums.id), None, &thresholds)
+            .await
+            .unwrap();
+        assert_eq!(filtered, vec![("moderate".to_string(), 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_deleting_route_cascades_to_comments() {
+        let pool = test_pool().await;
+        let repo = SqliteRouteRepository::new(pool.clone());
+        let comments = SqliteCommentRepository::new(pool);
+        let route = route(Uuid::new_v4());
+        repo.create(&route).await.unwrap();
+        comments
+            .create(&Comment {
+                id: Uuid::new_v4(),
+                route_id: route.id,
+                user_id: Uuid::new_v4(),
+                author_name: "Ann".to_string(),
+                text: "Nice views".to_string(),
+                approved: true,
+                created_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(comments.count_search(Some(route.id), "views").await.unwrap(), 1);
+
+        repo.delete(route.id).await.unwrap();
+
+        assert_eq!(comments.count_by_route_id(route.id).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pending_comments_hidden_until_approved() {
+        let pool = test_pool().await;
+        let routes = SqliteRouteRepository::new(pool.clone());
+        let comments = SqliteCommentRepository::new(pool);
+        let route = route(Uuid::new_v4());
+        routes.create(&route).await.unwrap();
+        assert_eq!(routes.find_comment_policy(route.id).await.unwrap().as_deref(), Some("open"));
+        routes.set_comment_policy(route.id, "pre_moderated").await.unwrap();
+        assert_eq!(routes.find_comment_policy(route.id).await.unwrap().as_deref(), Some("pre_moderated"));
+        assert_eq!(routes.find_comment_policy(Uuid::new_v4()).await.unwrap(), None);
+
+        let pending = Comment::new(route.id, Uuid::new_v4(), "Ann".to_string(), "Muddy trail".to_string())
+            .with_approved(false);
+        comments.create(&pending).await.unwrap();
+
+        assert!(comments.find_by_route_id(route.id).await.unwrap().is_empty());
+        assert_eq!(comments.count_by_route_id(route.id).await.unwrap(), 0);
+        assert_eq!(comments.count_search(Some(route.id), "trail").await.unwrap(), 0);
+        assert_eq!(comments.find_pending_by_route_id(route.id).await.unwrap().len(), 1);
+
+        comments.approve(pending.id).await.unwrap();
+
+        assert!(comments.find_by_id(pending.id).await.unwrap().unwrap().approved);
+        assert_eq!(comments.count_by_route_id(route.id).await.unwrap(), 1);
+        assert!(comments.find_pending_by_route_id(route.id).await.unwrap().is_empty());
+        assert!(matches!(comments.approve(Uuid::new_v4()).await, Err(RepositoryError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_blocked_users_comments_hidden_on_blockers_routes() {
+        let pool = test_pool().await;
+        let routes = SqliteRouteRepository::new(pool.clone());
+        let comments = SqliteCommentRepository::new(pool.clone());
+        let blocks = SqliteBlockRepository::new(pool);
+        let owner_id = Uuid::new_v4();
+        let troll_id = Uuid::new_v4();
+        let route = route(owner_id);
+        routes.create(&route).await.unwrap();
+        comments
+            .create(&Comment::new(route.id, troll_id, "Troll".to_string(), "Boring trail".to_string()))
+            .await
+            .unwrap();
+        comments
+            .create(&Comment::new(route.id, Uuid::new_v4(), "Ann".to_string(), "Lovely trail".to_string()))
+            .await
+            .unwrap();
+
+        assert!(blocks.create(&UserBlock::new(owner_id, troll_id)).await.unwrap());
+        assert!(!blocks.create(&UserBlock::new(owner_id, troll_id)).await.unwrap());
+        assert!(blocks.exists(owner_id, troll_id).await.unwrap());
+        assert!(!blocks.exists(troll_id, owner_id).await.unwrap());
+
+        let visible = comments.find_by_route_id(route.id).await.unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].author_name, "Ann");
+        assert_eq!(comments.count_by_route_id(route.id).await.unwrap(), 1);
+        assert_eq!(comments.count_search(Some(route.id), "trail").await.unwrap(), 1);
+        assert_eq!(comments.find_by_user_id(troll_id).await.unwrap().len(), 1);
+
+        blocks.delete(owner_id, troll_id).await.unwrap();
+        assert_eq!(comments.count_by_route_id(route.id).await.unwrap(), 2);
+        assert!(blocks.find_by_blocker(owner_id).await.unwrap().is_empty());
+        assert!(matches!(blocks.delete(owner_id, troll_id).await, Err(RepositoryError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_spam_flags_queue_and_duplicate_comments() {
+        let pool = test_pool().await;
+        let routes = SqliteRouteRepository::new(pool.clone());
+        let comments = SqliteCommentRepository::new(pool.clone());
+        let flags = SqliteSpamFlagRepository::new(pool);
+        let route = route(Uuid::new_v4());
+        routes.create(&route).await.unwrap();
+        let first = Comment::new(route.id, Uuid::new_v4(), "Bot".to_string(), "Buy now".to_string());
+        let second = Comment::new(route.id, Uuid::new_v4(), "Bot".to_string(), "Buy now".to_string());
+        comments.create(&first).await.unwrap();
+        comments.create(&second).await.unwrap();
+
+        let since = Utc::now() - chrono::Duration::hours(1);
+        assert_eq!(comments.count_recent_by_text("Buy now", since, second.id).await.unwrap(), 1);
+        assert_eq!(comments.count_recent_by_text("Buy later", since, second.id).await.unwrap(), 0);
+        let later = Utc::now() + chrono::Duration::hours(1);
+        assert_eq!(comments.count_recent_by_text("Buy now", later, second.id).await.unwrap(), 0);
+
+        let flag = SpamFlag::new(SPAM_CONTENT_COMMENT, second.id, second.user_id, &[SPAM_REASON_DUPLICATE_TEXT]);
+        flags.create(&flag).await.unwrap();
+        let queued = flags.list(false, 10, 0).await.unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].reason_list(), vec!["duplicate_text"]);
+        assert_eq!(flags.count(true).await.unwrap(), 0);
+
+        let moderator_id = Uuid::new_v4();
+        flags.mark_reviewed(flag.id, moderator_id).await.unwrap();
+        assert_eq!(flags.count(false).await.unwrap(), 0);
+        assert_eq!(flags.list(true, 10, 0).await.unwrap()[0].reviewed_by, Some(moderator_id));
+        assert!(matches!(flags.mark_reviewed(flag.id, moderator_id).await, Err(RepositoryError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_route_activity_oldest_first() {
+        let pool = test_pool().await;
+        let routes = SqliteRouteRepository::new(pool.clone());
+        let activity = SqliteRouteActivityRepository::new(pool);
+        let route = route(Uuid::new_v4());
+        let other = Route { id: Uuid::new_v4(), ..route.clone() };
+        routes.create(&route).await.unwrap();
+        routes.create(&other).await.unwrap();
+
+        let mut created = RouteActivity::new(route.id, Some(route.user_id), ACTIVITY_CREATED, None);
+        created.created_at = Utc::now() - chrono::Duration::minutes(5);
+        let processed = RouteActivity::new(route.id, None, ACTIVITY_PHOTOS_PROCESSED, Some(serde_json::json!({"processed": 2})));
+        activity.create(&processed).await.unwrap();
+        activity.create(&created).await.unwrap();
+        activity.create(&RouteActivity::new(other.id, None, ACTIVITY_CREATED, None)).await.unwrap();
+
+        let log = activity.find_by_route_id(route.id, 10, 0).await.unwrap();
+        assert_eq!(log.iter().map(|a| a.kind.as_str()).collect::<Vec<_>>(), vec!["created", "photos_processed"]);
+        assert_eq!(log[1].details, Some(serde_json::json!({"processed": 2})));
+        assert_eq!(log[1].actor_id, None);
+        assert_eq!(activity.count_by_route_id(route.id).await.unwrap(), 2);
+        assert_eq!(activity.find_by_route_id(route.id, 10, 1).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_route_templates_visibility() {
+        let pool = test_pool().await;
+        let routes = SqliteRouteRepository::new(pool.clone());
+        let templates = SqliteRouteTemplateRepository::new(pool);
+        let (guide_id, other_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let tour = Route { name: "City tour".to_string(), ..route(guide_id) };
+        let own = Route { name: "Back streets".to_string(), ..route(guide_id) };
+        routes.create(&tour).await.unwrap();
+        routes.create(&own).await.unwrap();
+
+        templates.upsert(&RouteTemplate::new(tour.id, guide_id, false)).await.unwrap();
+        templates.upsert(&RouteTemplate::new(own.id, guide_id, false)).await.unwrap();
+        assert_eq!(templates.count_visible(other_id).await.unwrap(), 0);
+
+        templates.upsert(&RouteTemplate::new(tour.id, guide_id, true)).await.unwrap();
+        let listed = templates.list_visible(other_id, 10, 0).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "City tour");
+        assert_eq!(listed[0].points_count, 2);
+        let names: Vec<String> = templates.list_visible(guide_id, 10, 0).await.unwrap().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["City tour", "Back streets"]);
+
+        templates.delete(own.id).await.unwrap();
+        assert!(templates.find_by_route_id(own.id).await.unwrap().is_none());
+        assert!(matches!(templates.delete(own.id).await, Err(RepositoryError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_share_links_are_revoked_one_at_a_time() {
+        let pool = test_pool().await;
+        let routes = SqliteRouteRepository::new(pool.clone());
+        let links = SqliteShareLinkRepository::new(pool);
+        let tour = route(Uuid::new_v4());
+        routes.create(&tour).await.unwrap();
+
+        let client_a = ShareLink::new(tour.id, "Client A".to_string());
+        let client_b = ShareLink { created_at: client_a.created_at + chrono::Duration::seconds(1), ..ShareLink::new(tour.id, "Client B".to_string()) };
+        links.create(&client_a).await.unwrap();
+        links.create(&client_b).await.unwrap();
+
+        assert!(matches!(links.revoke(client_a.id, Uuid::new_v4(), Utc::now()).await, Err(RepositoryError::NotFound)));
+        links.revoke(client_a.id, tour.id, Utc::now()).await.unwrap();
+        assert!(matches!(links.revoke(client_a.id, tour.id, Utc::now()).await, Err(RepositoryError::NotFound)));
+
+        let listed = links.find_by_route_id(tour.id).await.unwrap();
+        let labels: Vec<&str> = listed.iter().map(|l| l.label.as_str()).collect();
+        assert_eq!(labels, vec!["Client B", "Client A"]);
+        assert!(listed[0].is_active());
+        assert!(!links.find_by_id(client_a.id).await.unwrap().unwrap().is_active());
+    }
+
+    #[tokio::test]
+    async fn test_collections_group_routes_and_filter_the_route_list() {
+        let pool = test_pool().await;
+        let routes = SqliteRouteRepository::new(pool.clone());
+        let collections = SqliteRouteCollectionRepository::new(pool);
+        let user_id = Uuid::new_v4();
+        let kremlin = route(user_id);
+        let lake = route(user_id);
+        routes.create(&kremlin).await.unwrap();
+        routes.create(&lake).await.unwrap();
+        let kazan = RouteCollection::new(user_id, "Kazan".to_string());
+        let archive = RouteCollection::new(user_id, "archive".to_string());
+        collections.create(&kazan).await.unwrap();
+        collections.create(&archive).await.unwrap();
+
+        collections.add_route(kazan.id, kremlin.id, Utc::now()).await.unwrap();
+        collections.add_route(kazan.id, kremlin.id, Utc::now()).await.unwrap();
+        collections.add_route(archive.id, kremlin.id, Utc::now()).await.unwrap();
+        collections.add_route(archive.id, lake.id, Utc::now()).await.unwrap();
+
+        let listed = collections.find_by_user_id(user_id).await.unwrap();
+        let summary: Vec<(&str, i64)> = listed.iter().map(|c| (c.name.as_str(), c.route_count)).collect();
+        assert_eq!(summary, vec![("archive", 2), ("Kazan", 1)]);
+        let in_kazan = UserRoutesFilter { collection_id: Some(kazan.id), ..Default::default() };
+        let found = routes.find_by_user_id_paginated(user_id, &in_kazan, "r.created_at DESC", 10, 0).await.unwrap();
+        assert_eq!(found.iter().map(|r| r.id).collect::<Vec<_>>(), vec![kremlin.id]);
+        assert_eq!(routes.count_by_user_id(user_id, &in_kazan).await.unwrap(), 1);
+
+        collections.remove_route(archive.id, lake.id).await.unwrap();
+        assert!(matches!(collections.remove_route(archive.id, lake.id).await, Err(RepositoryError::NotFound)));
+        assert!(matches!(collections.delete(kazan.id, Uuid::new_v4()).await, Err(RepositoryError::NotFound)));
+        collections.delete(kazan.id, user_id).await.unwrap();
+        assert!(collections.find_by_id(kazan.id).await.unwrap().is_none());
+        assert!(routes.find_by_id(kremlin.id).await.unwrap().is_some());
+        assert_eq!(collections.find_by_id(archive.id).await.unwrap().unwrap().route_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_saved_searches_are_claimed_once_per_run() {
+        let searches = SqliteSavedSearchRepository::new(test_pool().await);
+        let user_id = Uuid::new_v4();
+        let saved = SavedSearch::new(user_id, "Kremlins".to_string(), Some("kremlin".to_string()), None, None);
+        searches.create(&saved).await.unwrap();
+        assert_eq!(searches.count_by_user_id(user_id).await.unwrap(), 1);
+
+        let stored = searches.find_all().await.unwrap().remove(0);
+        let now = Utc::now();
+        assert!(searches.mark_checked(stored.id, stored.last_checked_at, now).await.unwrap());
+        assert!(!searches.mark_checked(stored.id, stored.last_checked_at, now).await.unwrap());
+
+        assert!(matches!(searches.delete(saved.id, Uuid::new_v4()).await, Err(RepositoryError::NotFound)));
+        searches.delete(saved.id, user_id).await.unwrap();
+        assert!(searches.find_by_user_id(user_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_user_rows_reads_keyed_rows_as_json() {
+        let pool = test_pool().await;
+        let searches = SqliteSavedSearchRepository::new(pool.clone());
+        let blocks = SqliteBlockRepository::new(pool.clone());
+        let user_data = SqliteUserDataRepository::new(pool);
+        let user_id = Uuid::new_v4();
+        let other_id = Uuid::new_v4();
+        searches
+            .create(&SavedSearch::new(user_id, "Kremlins".to_string(), Some("kremlin".to_string()), None, None))
+            .await
+            .unwrap();
+        searches
+            .create(&SavedSearch::new(other_id, "Lakes".to_string(), None, None, None))
+            .await
+            .unwrap();
+        blocks.create(&UserBlock::new(other_id, user_id)).await.unwrap();
+
+        let table = |table, user_column| UserKeyedTable { table, user_column, exported: true };
+        let rows = user_data.find_user_rows(table("saved_searches", "user_id"), user_id).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["user_id"], user_id.to_string());
+        assert_eq!(rows[0]["name"], "Kremlins");
+        assert_eq!(rows[0]["search"], "kremlin");
+
+        assert!(user_data.find_user_rows(table("user_blocks", "blocker_id"), user_id).await.unwrap().is_empty());
+        let blocked = user_data.find_user_rows(table("user_blocks", "blocked_id"), user_id).await.unwrap();
+        assert_eq!(blocked[0]["blocker_id"], other_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_find_shared_between_uses_share_time() {
+        let repo = SqliteRouteRepository::new(test_pool().await);
+        let shared = route(Uuid::new_v4());
+        let private = route(Uuid::new_v4());
+        repo.create(&shared).await.unwrap();
+        repo.create(&private).await.unwrap();
+
+        let before = Utc::now() - chrono::Duration::seconds(1);
+        repo.set_visibility(shared.id, RouteVisibility::Public, Some(Uuid::new_v4())).await.unwrap();
+        let after = Utc::now() + chrono::Duration::seconds(1);
+
+        let found = repo.find_shared_between(before, after).await.unwrap();
+        assert_eq!(found.iter().map(|r| r.id).collect::<Vec<_>>(), vec![shared.id]);
+        assert!(repo.find_shared_between(after, after + chrono::Duration::minutes(1)).await.unwrap().is_empty());
+
+        repo.set_visibility(shared.id, RouteVisibility::Private, None).await.unwrap();
+        assert!(repo.find_shared_between(before, after).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_route_opens_by_link_but_is_not_explored() {
+        let repo = SqliteRouteRepository::new(test_pool().await);
+        let unlisted = route(Uuid::new_v4());
+        repo.create(&unlisted).await.unwrap();
+        let token = Uuid::new_v4();
+
+        repo.set_visibility(unlisted.id, RouteVisibility::Unlisted, Some(token)).await.unwrap();
+        let found = repo.find_by_share_token(token).await.unwrap().unwrap();
+        assert_eq!(found.visibility, RouteVisibility::Unlisted);
+        assert_eq!(repo.count_explore_shared(None, None, None).await.unwrap(), 0);
+        assert_eq!(repo.find_share_token(unlisted.id, false).await.unwrap(), Some(token));
+        assert_eq!(repo.find_share_token(unlisted.id, true).await.unwrap(), None);
+
+        repo.set_visibility(unlisted.id, RouteVisibility::Public, Some(token)).await.unwrap();
+        assert_eq!(repo.count_explore_shared(None, None, None).await.unwrap(), 1);
+        assert_eq!(repo.find_share_token(unlisted.id, true).await.unwrap(), Some(token));
+
+        // A token left behind on a private route does not open it
+        repo.set_visibility(unlisted.id, RouteVisibility::Private, Some(token)).await.unwrap();
+        assert!(repo.find_by_share_token(token).await.unwrap().is_none());
+        assert_eq!(repo.find_share_token(unlisted.id, false).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_explore_for_owner_includes_private_routes_only_of_that_owner() {
+        let repo = SqliteRouteRepository::new(test_pool().await);
+        let owner_id = Uuid::new_v4();
+        let mine = route(owner_id);
+        let unlisted = route(Uuid::new_v4());
+        repo.create(&mine).await.unwrap();
+        repo.create(&unlisted).await.unwrap();
+        repo.set_visibility(unlisted.id, RouteVisibility::Unlisted, Some(Uuid::new_v4())).await.unwrap();
+
+        let rows = repo
+            .explore_shared(Some(owner_id), None, None, None, "r.created_at DESC", 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].id, mine.id);
+        assert_eq!(rows[0].share_token, None);
+        assert_eq!(rows[0].path.len(), mine.points.len());
+        assert_eq!((rows[0].path[0].lat, rows[0].path[0].lng), (mine.points[0].lat, mine.points[0].lng));
+
+        let public = repo.explore_shared(None, None, None, None, "r.created_at DESC", 10, 0).await.unwrap();
+        assert!(public.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_usage_counts_inline_photo_bytes_and_quota_override() {
+        let repo = SqliteRouteRepository::new(test_pool().await);
+        let user_id = Uuid::new_v4();
+        let mut with_photo = route(user_id);
+        with_photo.points[0].photo = Some(guide_helper_domain::route::PhotoData {
+            original: "data:image/png;base64,AAAA".to_string(),
+            thumbnail_url: None,
+            status: guide_helper_domain::route::PhotoStatus::Pending,
+        });
+        repo.create(&with_photo).await.unwrap();
+        repo.create(&route(user_id)).await.unwrap();
+
+        let usage = repo.usage_by_user_id(user_id).await.unwrap();
+        assert_eq!(usage, RouteUsage { routes: 2, photo_bytes: 26 });
+
+        let quota = RouteQuota { max_routes: Some(5), max_photo_bytes: None };
+        repo.set_quota_override(user_id, Some(quota)).await.unwrap();
+        assert_eq!(repo.find_quota_override(user_id).await.unwrap(), Some(quota));
+        repo.set_quota_override(user_id, None).await.unwrap();
+        assert_eq!(repo.find_quota_override(user_id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_storage_usage_per_user_and_totals() {
+        let pool = test_pool().await;
+        let repo = SqliteRouteRepository::new(pool.clone());
+        let (heavy, light) = (Uuid::new_v4(), Uuid::new_v4());
+        for (key, user_id, size) in [("a/photo_0.jpg", heavy, 700_i64), ("a/thumb_0.jpg", heavy, 100), ("b/photo_0.jpg", light, 50)] {
+            sqlx::query("INSERT INTO photo_storage_objects (object_key, user_id, route_id, size_bytes, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)")
+                .bind(key)
+                .bind(user_id)
+                .bind(Uuid::new_v4())
+                .bind(size)
+                .bind(Utc::now())
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(repo.storage_by_user_id(heavy).await.unwrap(), StorageUsage { objects: 2, bytes: 800 });
+        assert_eq!(repo.storage_by_user_id(Uuid::new_v4()).await.unwrap(), StorageUsage::default());
+        assert_eq!(repo.storage_totals().await.unwrap(), StorageTotals { users: 2, objects: 3, bytes: 850 });
+        let top = repo.top_storage_users(1).await.unwrap();
+        assert_eq!(top, vec![UserStorageUsage { user_id: heavy, objects: 2, bytes: 800 }]);
+    }
+
+    #[tokio::test]
+    async fn test_pending_action_is_taken_once_by_its_owner() {
+        let repo = SqliteChatMessageRepository::new(test_pool().await);
+        let user_id = Uuid::new_v4();
+        let action = PendingChatAction::new(
+            user_id,
+            Uuid::new_v4(),
+            "remove_point_from_route".to_string(),
+            serde_json::json!({ "position": 2 }),
+            "remove point 2".to_string(),
+        );
+        repo.create_pending_action(&action).await.unwrap();
+
+        assert!(repo.take_pending_action(Uuid::new_v4(), action.id).await.unwrap().is_none());
+        let taken = repo.take_pending_action(user_id, action.id).await.unwrap().unwrap();
+        assert_eq!(taken.arguments, serde_json::json!({ "position": 2 }));
+        assert_eq!(taken.summary, "remove point 2");
+        assert!(repo.take_pending_action(user_id, action.id).await.unwrap().is_none());
+
+        repo.create_pending_action(&action).await.unwrap();
+        let purged = repo
+            .delete_pending_actions_before(Utc::now() + chrono::Duration::seconds(1))
+            .await
+            .unwrap();
+        assert_eq!(purged, 1);
+    }
+
+    #[tokio::test]
+    async fn test_archived_conversations_are_hidden_and_pinned_come_first() {
+        let repo = SqliteChatMessageRepository::new(test_pool().await);
+        let user_id = Uuid::new_v4();
+        let (older, newer, archived) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        for conversation_id in [older, newer, archived] {
+            repo.create(&ChatMessage::new_user_message(user_id, conversation_id, "hi".to_string()))
+                .await
+                .unwrap();
+        }
+
+        repo.set_conversation_flags(user_id, older, None, Some(true)).await.unwrap();
+        let flags = repo.set_conversation_flags(user_id, archived, Some(true), None).await.unwrap();
+        assert_eq!(flags, ConversationFlags { archived: true, pinned: false });
+        assert!(matches!(
+            repo.set_conversation_flags(Uuid::new_v4(), older, Some(true), None).await,
+            Err(RepositoryError::NotFound)
+        ));
+
+        let listed = repo.list_conversations(user_id, 10, 0, false).await.unwrap();
+        let ids: Vec<Uuid> = listed.iter().map(|c| c.conversation_id).collect();
+        assert_eq!(ids, vec![older, newer]);
+        assert!(listed[0].pinned);
+        assert_eq!(repo.count_conversations(user_id, false).await.unwrap(), 2);
+
+        let all = repo.list_conversations(user_id, 10, 0, true).await.unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(repo.count_conversations(user_id, true).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_share_token_is_reused_until_revoked() {
+        let repo = SqliteChatMessageRepository::new(test_pool().await);
+        let user_id = Uuid::new_v4();
+        let conversation_id = Uuid::new_v4();
+        repo.create(&ChatMessage::new_user_message(user_id, conversation_id, "hi".to_string()))
+            .await
+            .unwrap();
+
+        let token = repo.share_conversation(user_id, conversation_id, Uuid::new_v4()).await.unwrap();
+        let again = repo.share_conversation(user_id, conversation_id, Uuid::new_v4()).await.unwrap();
+        assert_eq!(token, again);
+        assert_eq!(repo.find_shared_conversation(token).await.unwrap(), Some((user_id, conversation_id)));
+        assert!(matches!(
+            repo.share_conversation(Uuid::new_v4(), conversation_id, Uuid::new_v4()).await,
+            Err(RepositoryError::NotFound)
+        ));
+
+        repo.unshare_conversation(user_id, conversation_id).await.unwrap();
+        assert_eq!(repo.find_shared_conversation(token).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_delete_message_requires_owner_and_conversation() {
+        let repo = SqliteChatMessageRepository::new(test_pool().await);
+        let user_id = Uuid::new_v4();
+        let conversation_id = Uuid::new_v4();
+        let message = ChatMessage::new_user_message(user_id, conversation_id, "hi".to_string());
+        repo.create(&message).await.unwrap();
+
+        assert!(matches!(
+            repo.delete_message(Uuid::new_v4(), conversation_id, message.id).await,
+            Err(RepositoryError::NotFound)
+        ));
+        assert!(matches!(
+            repo.delete_message(user_id, Uuid::new_v4(), message.id).await,
+            Err(RepositoryError::NotFound)
+        ));
+
+        repo.delete_message(user_id, conversation_id, message.id).await.unwrap();
+        assert!(repo.find_by_conversation(user_id, conversation_id, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_chat_language_round_trip() {
+        let repo = SqliteChatMessageRepository::new(test_pool().await);
+        let user_id = Uuid::new_v4();
+        assert_eq!(repo.find_chat_language(user_id).await.unwrap(), None);
+
+        repo.set_chat_language(user_id, Some("ru".to_string())).await.unwrap();
+        repo.set_chat_language(user_id, Some("en".to_string())).await.unwrap();
+        assert_eq!(repo.find_chat_language(user_id).await.unwrap().as_deref(), Some("en"));
+
+        repo.set_chat_language(user_id, None).await.unwrap();
+        assert_eq!(repo.find_chat_language(user_id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_stats_count_usage_routes_chat_and_photos() {
+        let pool = test_pool().await;
+        let stats = SqliteStatsRepository::new(pool.clone());
+        let routes = SqliteRouteRepository::new(pool.clone());
+        let chat = SqliteChatMessageRepository::new(pool);
+        let today = Utc::now().date_naive();
+        let yesterday = today.pred_opt().unwrap();
+        let (ann, bob) = (Uuid::new_v4(), Uuid::new_v4());
+
+        stats.record_usage(ann, yesterday).await.unwrap();
+        stats.record_usage(ann, today).await.unwrap();
+        stats.record_usage(ann, today).await.unwrap();
+        stats.record_usage(bob, today).await.unwrap();
+
+        assert_eq!(stats.count_active_users(today).await.unwrap(), 2);
+        assert_eq!(stats.count_active_users(yesterday).await.unwrap(), 2);
+        assert_eq!(
+            stats.active_users_per_day(yesterday).await.unwrap(),
+            vec![DailyCount { day: yesterday, count: 1 }, DailyCount { day: today, count: 2 }]
+        );
+
+        let mut with_photo = route(ann);
+        with_photo.points[0].photo = Some(guide_helper_domain::route::PhotoData {
+            original: "https://example.com/a.jpg".to_string(),
+            thumbnail_url: None,
+            status: guide_helper_domain::route::PhotoStatus::Done,
+        });
+        routes.create(&with_photo).await.unwrap();
+        routes.create(&route(bob)).await.unwrap();
+
+        assert_eq!(stats.routes_per_day(today).await.unwrap(), vec![DailyCount { day: today, count: 2 }]);
+        assert_eq!(stats.photo_volume().await.unwrap(), PhotoVolume { done: 1, ..Default::default() });
+
+        let conversation_id = Uuid::new_v4();
+        for role in ["user", "assistant", "user"] {
+            chat.create(&ChatMessage {
+                id: Uuid::new_v4(),
+                user_id: ann,
+                conversation_id,
+                role: role.to_string(),
+                content: "hi".to_string(),
+                actions: None,
+                created_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(
+            stats.chat_usage(today).await.unwrap(),
+            ChatUsage { messages: 2, users: 1, conversations: 1 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chat_ops_stats_from_turns_and_messages() {
+        let pool = test_pool().await;
+        let stats = SqliteStatsRepository::new(pool.clone());
+        let chat = SqliteChatMessageRepository::new(pool);
+        let today = Utc::now().date_naive();
+        let (ann, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let conversation_id = Uuid::new_v4();
+
+        for user_id in [ann, ann, bob] {
+            chat.create(&ChatMessage::new_user_message(user_id, conversation_id, "hi".to_string())).await.unwrap();
+        }
+        let tools = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        chat.record_turn(&ChatTurnRecord::new(ann, conversation_id, tools(&["geocode", "search_routes"]), 1000, None))
+            .await
+            .unwrap();
+        chat.record_turn(&ChatTurnRecord::new(ann, conversation_id, tools(&["geocode"]), 3000, Some("timeout".to_string())))
+            .await
+            .unwrap();
+
+        assert_eq!(stats.chat_messages_per_day(today).await.unwrap(), vec![DailyCount { day: today, count: 3 }]);
+        assert_eq!(
+            stats.chat_turn_summary(today).await.unwrap(),
+            ChatTurnSummary { turns: 2, errors: 1, avg_latency_ms: 2000.0 }
+        );
+        assert_eq!(
+            stats.chat_tool_calls(today).await.unwrap(),
+            vec![
+                ToolCallCount { tool: "geocode".to_string(), count: 2 },
+                ToolCallCount { tool: "search_routes".to_string(), count: 1 },
+            ]
+        );
+        assert_eq!(
+            stats.top_chat_users(today, 1).await.unwrap(),
+            vec![ChatUserVolume { user_id: ann, messages: 2 }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flagged_conversations_listed_with_consent() {
+        let chat = SqliteChatMessageRepository::new(test_pool().await);
+        let (user_id, conversation_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let missing = chat.flag_conversation(user_id, conversation_id, None, true).await;
+        assert!(matches!(missing, Err(RepositoryError::NotFound)));
+
+        chat.create(&ChatMessage::new_user_message(user_id, conversation_id, "hi".to_string())).await.unwrap();
+        chat.set_conversation_flags(user_id, conversation_id, None, Some(true)).await.unwrap();
+        chat.flag_conversation(user_id, conversation_id, Some("rude".to_string()), false).await.unwrap();
+        chat.flag_conversation(user_id, conversation_id, Some("abusive".to_string()), true).await.unwrap();
+
+        assert_eq!(chat.count_flagged_conversations().await.unwrap(), 1);
+        let listed = chat.list_flagged_conversations(10, 0).await.unwrap();
+        assert_eq!(listed[0].reason.as_deref(), Some("abusive"));
+        assert!(listed[0].review_consent);
+        assert_eq!(listed[0].message_count, 1);
+        assert!(chat.find_flagged_conversation(user_id, conversation_id).await.unwrap().is_some());
+        assert!(chat.find_flagged_conversation(Uuid::new_v4(), conversation_id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_settings_seeded_and_updatable() {
+        let repo = SqliteSettingsRepository::new(test_pool().await);
+        assert!(repo.get_value("difficulty_thresholds").await.unwrap().is_some());
+
+        repo.set_value("k", &serde_json::json!({"a": 1})).await.unwrap();
+
+        assert_eq!(repo.get_value("k").await.unwrap(), Some(serde_json::json!({"a": 1})));
+    }
+
+    #[tokio::test]
+    async fn test_translations_seeded_upserted_and_deleted() {
+        let repo = SqliteTranslationRepository::new(test_pool().await);
+        let seeded = repo.find_by_locale("ru").await.unwrap();
+        assert!(seeded.iter().any(|t| t.key == "difficulty.easy"));
+
+        let key = format!("category.{}", Uuid::new_v4());
+        repo.upsert(&Translation::new(key.clone(), "ru".to_string(), "Горы".to_string())).await.unwrap();
+        repo.upsert(&Translation::new(key.clone(), "ru".to_string(), "Горные".to_string())).await.unwrap();
+
+        let ru = repo.find_by_locale("ru").await.unwrap();
+        assert_eq!(ru.iter().find(|t| t.key == key).map(|t| t.value.as_str()), Some("Горные"));
+        assert!(repo.find_by_locale("en").await.unwrap().is_empty());
+
+        repo.delete(&key, "ru").await.unwrap();
+        assert!(matches!(repo.delete(&key, "ru").await, Err(RepositoryError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_notification_template_round_trip() {
+        let repo = SqliteNotificationRepository::new(test_pool().await);
+        let user_id = Uuid::new_v4();
+        let params = serde_json::json!({"actor": "ann", "route": "Old town"});
+        repo.create(
+            &Notification::from_template(
+                user_id,
+                "like".to_string(),
+                Uuid::new_v4(),
+                "ann".to_string(),
+                "notification.like",
+                params.clone(),
+            ),
+        )
+        .await
+        .unwrap();
+        repo.create(&Notification::new(user_id, "like".to_string(), Uuid::new_v4(), "bob".to_string(), "m".to_string()))
+            .await
+            .unwrap();
+
+        let mut found: Vec<_> = repo
+            .find_by_user_id(user_id, 10, 0)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|n| (n.template_key, n.params))
+            .collect();
+        found.sort_by_key(|(key, _)| key.is_none());
+        assert_eq!(found, vec![(Some("notification.like".to_string()), Some(params)), (None, None)]);
+    }
+}
@@ -0,0 +1,133 @@
+//! Per-route activity log. Entries are written as things happen to the route
+//! and only its owner can read them.
+
+use uuid::Uuid;
+
+use crate::domain::activity::RouteActivity;
+use crate::usecase::contracts::{RouteActivityRepository, RouteRepository};
+use crate::usecase::error::UsecaseError;
+
+pub struct ActivityUseCase<A, R>
+where
+    A: RouteActivityRepository,
+    R: RouteRepository,
+{
+    activity_repository: A,
+    route_repository: R,
+}
+
+impl<A, R> ActivityUseCase<A, R>
+where
+    A: RouteActivityRepository,
+    R: RouteRepository,
+{
+    pub fn new(activity_repository: A, route_repository: R) -> Self {
+        Self {
+            activity_repository,
+            route_repository,
+        }
+    }
+
+    /// Appends an entry to the route's log. `actor_id` is None for events the
+    /// system caused on its own.
+    #[tracing::instrument(skip(self, details), fields(route_id = %route_id, ?actor_id, %kind))]
+    pub async fn record(
+        &self,
+        route_id: Uuid,
+        actor_id: Option<Uuid>,
+        kind: &str,
+        details: Option<serde_json::Value>,
+    ) -> Result<RouteActivity, UsecaseError> {
+        let activity = RouteActivity::new(route_id, actor_id, kind, details);
+        self.activity_repository.create(&activity).await?;
+
+        tracing::debug!(activity_id = %activity.id, "route activity recorded");
+        Ok(activity)
+    }
+
+    /// Oldest entries first, so the log reads as a timeline.
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id, %limit, %offset))]
+    pub async fn list_activity(
+        &self,
+        route_id: Uuid,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<RouteActivity>, i64), UsecaseError> {
+        let route = self
+            .route_repository
+            .find_by_id(route_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+
+        if route.user_id != user_id {
+            tracing::warn!("route activity requested by non-owner");
+            return Err(UsecaseError::Forbidden("Only the route owner can view its activity".to_string()));
+        }
+
+        let activity = self.activity_repository.find_by_route_id(route_id, limit, offset).await?;
+        let total = self.activity_repository.count_by_route_id(route_id).await?;
+
+        tracing::debug!(count = activity.len(), total, "retrieved route activity");
+        Ok((activity, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::activity::{ACTIVITY_CREATED, ACTIVITY_SHARED};
+    use crate::domain::route::Route;
+    use crate::usecase::contracts::{MockRouteActivityRepository, MockRouteRepository};
+
+    fn route_repository(route: Route) -> MockRouteRepository {
+        let mut routes = MockRouteRepository::new();
+        routes.expect_find_by_id().returning(move |_| Ok(Some(route.clone())));
+        routes
+    }
+
+    #[tokio::test]
+    async fn test_list_activity_for_owner() {
+        let owner_id = Uuid::new_v4();
+        let route = Route::new(owner_id, "Old town".to_string(), vec![], vec![], vec![]);
+        let route_id = route.id;
+        let mut activity = MockRouteActivityRepository::new();
+        activity.expect_find_by_route_id().returning(move |id, _, _| {
+            Ok(vec![
+                RouteActivity::new(id, Some(owner_id), ACTIVITY_CREATED, None),
+                RouteActivity::new(id, Some(owner_id), ACTIVITY_SHARED, None),
+            ])
+        });
+        activity.expect_count_by_route_id().returning(|_| Ok(2));
+        let usecase = ActivityUseCase::new(activity, route_repository(route));
+
+        let (entries, total) = usecase.list_activity(route_id, owner_id, 20, 0).await.unwrap();
+
+        assert_eq!(total, 2);
+        assert_eq!(entries[0].kind, ACTIVITY_CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_list_activity_rejects_other_users() {
+        let route = Route::new(Uuid::new_v4(), "Old town".to_string(), vec![], vec![], vec![]);
+        let route_id = route.id;
+        let mut activity = MockRouteActivityRepository::new();
+        activity.expect_find_by_route_id().never();
+        let usecase = ActivityUseCase::new(activity, route_repository(route));
+
+        let result = usecase.list_activity(route_id, Uuid::new_v4(), 20, 0).await;
+
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_activity_route_not_found() {
+        let mut routes = MockRouteRepository::new();
+        routes.expect_find_by_id().returning(|_| Ok(None));
+        let usecase = ActivityUseCase::new(MockRouteActivityRepository::new(), routes);
+
+        let result = usecase.list_activity(Uuid::new_v4(), Uuid::new_v4(), 20, 0).await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+}
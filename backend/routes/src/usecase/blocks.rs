@@ -0,0 +1,99 @@
+use uuid::Uuid;
+
+use crate::domain::block::UserBlock;
+use crate::usecase::contracts::BlockRepository;
+use crate::usecase::error::UsecaseError;
+
+/// Rejects the action when the route owner has blocked the acting user.
+pub async fn ensure_not_blocked<B>(block_repository: &B, owner_id: Uuid, user_id: Uuid) -> Result<(), UsecaseError>
+where
+    B: BlockRepository,
+{
+    if owner_id != user_id && block_repository.exists(owner_id, user_id).await? {
+        tracing::warn!(owner_id = %owner_id, user_id = %user_id, "blocked user interaction rejected");
+        return Err(UsecaseError::Forbidden("The route owner has blocked you".to_string()));
+    }
+    Ok(())
+}
+
+pub struct BlocksUseCase<B>
+where
+    B: BlockRepository,
+{
+    block_repository: B,
+}
+
+impl<B> BlocksUseCase<B>
+where
+    B: BlockRepository,
+{
+    pub fn new(block_repository: B) -> Self {
+        Self { block_repository }
+    }
+
+    /// Blocks the user; blocking an already blocked user returns the block
+    /// unchanged.
+    #[tracing::instrument(skip(self), fields(blocker_id = %blocker_id, blocked_id = %blocked_id))]
+    pub async fn block_user(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<UserBlock, UsecaseError> {
+        if blocker_id == blocked_id {
+            return Err(UsecaseError::Validation("You cannot block yourself".to_string()));
+        }
+
+        let block = UserBlock::new(blocker_id, blocked_id);
+        let created = self.block_repository.create(&block).await?;
+
+        tracing::info!(created, "user blocked");
+        Ok(block)
+    }
+
+    #[tracing::instrument(skip(self), fields(blocker_id = %blocker_id, blocked_id = %blocked_id))]
+    pub async fn unblock_user(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<(), UsecaseError> {
+        self.block_repository.delete(blocker_id, blocked_id).await?;
+
+        tracing::info!("user unblocked");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(blocker_id = %blocker_id))]
+    pub async fn list_blocked(&self, blocker_id: Uuid) -> Result<Vec<UserBlock>, UsecaseError> {
+        let blocks = self.block_repository.find_by_blocker(blocker_id).await?;
+
+        tracing::debug!(count = blocks.len(), "blocked users listed");
+        Ok(blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecase::contracts::MockBlockRepository;
+
+    #[tokio::test]
+    async fn test_block_user_rejects_self() {
+        let mut mock_block_repo = MockBlockRepository::new();
+        mock_block_repo.expect_create().never();
+        let user_id = Uuid::new_v4();
+
+        let usecase = BlocksUseCase::new(mock_block_repo);
+        let result = usecase.block_user(user_id, user_id).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_not_blocked() {
+        let owner_id = Uuid::new_v4();
+        let blocked_id = Uuid::new_v4();
+        let mut mock_block_repo = MockBlockRepository::new();
+        mock_block_repo
+            .expect_exists()
+            .returning(move |_, user_id| Ok(user_id == blocked_id));
+
+        assert!(matches!(
+            ensure_not_blocked(&mock_block_repo, owner_id, blocked_id).await,
+            Err(UsecaseError::Forbidden(_))
+        ));
+        assert!(ensure_not_blocked(&mock_block_repo, owner_id, Uuid::new_v4()).await.is_ok());
+        assert!(ensure_not_blocked(&mock_block_repo, owner_id, owner_id).await.is_ok());
+    }
+}
@@ -94,7 +94,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::route::Route;
+    use crate::domain::route::{Route, RouteVisibility};
     use crate::usecase::contracts::{MockBookmarkRepository, MockRouteRepository};
 
     fn make_route(route_id: Uuid) -> Route {
@@ -106,9 +106,13 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: None,
+            visibility: RouteVisibility::Private,
             category_ids: vec![],
             start_location: None,
             end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
         }
     }
 
@@ -1,42 +1,34 @@
+use std::sync::Arc;
+
 use futures::Stream;
+use tokio::sync::mpsc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::chat_message::{ChatMessage, ConversationSummary};
-use crate::usecase::contracts::{ChatMessageRepository, RouteRepository};
+use crate::domain::chat_message::{
+    ChatLanguage, ChatMessage, ChatTurnRecord, ConversationFlags, ConversationSummary, FlaggedConversation,
+    PendingChatAction,
+};
+use crate::domain::route::{Route, RoutePoint, RouteVisibility};
+use crate::usecase::chat_i18n::{language_instruction, CannedText};
+use crate::usecase::chat_prompt::{render_prompt, PromptVars, SettingsPromptSource, DEFAULT_SYSTEM_PROMPT};
+use crate::usecase::contracts::{ChatMessageRepository, RouteRepository, SettingsRepository};
 use crate::usecase::error::UsecaseError;
+use crate::usecase::geo::total_distance_km;
+use crate::usecase::routes::RouteLimits;
 use crate::usecase::openai::{
-    OpenAIFunction, OpenAITool, OpenAIChatRequest, OpenAIClient, OpenAIMessage,
+    OpenAIContent, OpenAIFunction, OpenAITool, OpenAIChatRequest, OpenAIClient, OpenAIMessage,
 };
 
-const SYSTEM_PROMPT: &str = r#"You are a helpful route planning assistant for the Guide Helper application.
-You help users find routes, plan trips, search the route catalog, and answer questions about places.
-Always respond in the same language the user writes in.
-
-You have access to these tools:
-- geocode: Look up coordinates for any place name or address. Calling this tool automatically displays the location as a marker on the interactive map.
-- search_routes: Search the route catalog for shared routes by text query, category, or sort order.
-- get_route_details: Get detailed information about a specific route by its ID.
-- navigate: Open a specific page in the application (map, profile/settings, route catalog, admin panel).
-
-Rules for tool usage — follow these strictly:
-1. When the user asks to SHOW, FIND, MARK, or DISPLAY a location — call geocode for that location.
-2. When the user asks to BUILD A ROUTE, PLAN A TRIP, or GO FROM one place TO another — call geocode for EACH location mentioned (start, end, and any waypoints), then describe the route between them. Do NOT give text-only directions; always geocode the places first.
-3. When the user asks to search or browse routes in the catalog — use search_routes.
-4. When the user mentions a specific route ID — use get_route_details.
-5. NEVER say you cannot display maps or show locations on the map. You CAN show locations by calling the geocode tool — it will place markers on the map automatically.
-6. If the user names two or more places, call geocode separately for each one.
-7. When the user says anything that means OPENING or NAVIGATING to a section of this app — ALWAYS call navigate immediately without asking for clarification. Do not ask "what do you want to configure" — just navigate.
+/// Pending actions not confirmed within this many minutes are discarded.
+const PENDING_ACTION_TTL_MINUTES: i64 = 15;
 
-Page mapping (use navigate tool with these paths):
-- /profile → when user says: "открой профиль", "профиль", "настройки", "открой настройки", "open settings", "go to profile", "мои настройки", "мой профиль", "settings", "profile"
-- /map → when user says: "открой карту", "перейди на карту", "на карту", "go to map", "open map", "карта"
-- /explore → when user says: "открой каталог", "каталог маршрутов", "explore", "посмотреть маршруты", "open catalog", "route catalog"
-- /admin → when user says: "открой админку", "панель администратора", "admin", "admin panel", "администрирование"
+/// Longest reason a user can give when flagging a conversation.
+const MAX_FLAG_REASON_LENGTH: usize = 1000;
 
-CRITICAL: For ALL navigation requests, call navigate IMMEDIATELY. Never ask clarifying questions about navigation. If the user wants to open any page or section, use the navigate tool.
-
-Be concise and helpful. After calling tools, summarize the results naturally."#;
+/// Points listed in the route context; longer routes are truncated so the
+/// prompt stays within budget.
+const MAX_CONTEXT_POINTS: usize = 100;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -47,6 +39,13 @@ pub enum ChatAction {
     ShowRoutes { routes: Vec<ChatRouteRef> },
     #[serde(rename = "navigate")]
     Navigate { path: String, label: String },
+    /// The route was edited; the UI should reload it.
+    #[serde(rename = "route_updated")]
+    RouteUpdated { route_id: Uuid },
+    /// A proposed change that runs only after
+    /// `POST /api/v1/chat/actions/{action_id}/confirm`.
+    #[serde(rename = "confirmation_required")]
+    ConfirmationRequired { action_id: Uuid, summary: String },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +62,10 @@ pub struct ChatRouteRef {
     pub category_ids: Vec<Uuid>,
     pub avg_rating: f64,
     pub likes_count: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_location: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -71,6 +74,9 @@ pub struct ChatResponse {
     pub message: String,
     pub actions: Vec<ChatAction>,
     pub conversation_id: Uuid,
+    /// Shown alongside the answer when it was produced in degraded mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notice: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -80,16 +86,38 @@ pub enum ChatStreamEvent {
     Token { content: String },
     #[serde(rename = "actions")]
     Actions { actions: Vec<ChatAction> },
+    #[serde(rename = "notice")]
+    Notice { message: String },
+    /// The assistant started running a tool; lets clients show progress
+    /// during the tool loop.
+    #[serde(rename = "tool_started")]
+    ToolStarted { name: String },
+    #[serde(rename = "tool_finished")]
+    ToolFinished { name: String },
     #[serde(rename = "done")]
     Done { id: Uuid, conversation_id: Uuid },
     #[serde(rename = "error")]
     Error { message: String },
 }
 
-pub struct ChatUseCase<CM, R>
+/// A user message saved and ready to send to the assistant.
+pub struct ChatTurn {
+    user_id: Uuid,
+    conversation_id: Uuid,
+    language: Option<ChatLanguage>,
+    messages: Vec<OpenAIMessage>,
+}
+
+enum TurnStep {
+    Progress(ChatStreamEvent),
+    Finished(Result<ChatResponse, UsecaseError>),
+}
+
+pub struct ChatUseCase<CM, R, S>
 where
     CM: ChatMessageRepository,
     R: RouteRepository,
+    S: SettingsRepository,
 {
     chat_repo: CM,
     route_repo: R,
@@ -98,12 +126,16 @@ where
     nominatim_url: String,
     max_tool_iterations: usize,
     max_message_length: usize,
+    max_route_points: usize,
+    system_prompt: Option<Arc<SettingsPromptSource<S>>>,
+    prompt_vars: PromptVars,
 }
 
-impl<CM, R> ChatUseCase<CM, R>
+impl<CM, R, S> ChatUseCase<CM, R, S>
 where
     CM: ChatMessageRepository,
     R: RouteRepository,
+    S: SettingsRepository,
 {
     pub fn new(
         chat_repo: CM,
@@ -133,9 +165,35 @@ where
             nominatim_url,
             max_tool_iterations,
             max_message_length,
+            max_route_points: RouteLimits::default().max_points,
+            system_prompt: None,
+            prompt_vars: PromptVars::default(),
         }
     }
 
+    /// Loads the prompt template from `source` on every request instead of
+    /// always using [`DEFAULT_SYSTEM_PROMPT`].
+    pub fn with_system_prompt(mut self, source: Arc<SettingsPromptSource<S>>, vars: PromptVars) -> Self {
+        self.system_prompt = Some(source);
+        self.prompt_vars = vars;
+        self
+    }
+
+    async fn system_prompt(&self) -> String {
+        let template = match self.system_prompt.as_ref() {
+            Some(source) => source.template().await,
+            None => None,
+        };
+        render_prompt(template.as_deref().unwrap_or(DEFAULT_SYSTEM_PROMPT), &self.prompt_vars)
+    }
+
+    /// Upper bound for routes edited through chat tools; should match the
+    /// limit enforced by the routes API.
+    pub fn with_max_route_points(mut self, max_route_points: usize) -> Self {
+        self.max_route_points = max_route_points;
+        self
+    }
+
     pub fn max_message_length(&self) -> usize {
         self.max_message_length
     }
@@ -161,19 +219,86 @@ where
         }
     }
 
-    #[tracing::instrument(skip(self, text), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    /// The user's chosen reply language. A failed lookup is logged and treated
+    /// as no preference, so it never blocks a chat message.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn preferred_language(&self, user_id: Uuid) -> Option<ChatLanguage> {
+        match self.chat_repo.find_chat_language(user_id).await {
+            Ok(code) => code.as_deref().and_then(ChatLanguage::from_code),
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to load chat language, using default");
+                None
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn set_language(&self, user_id: Uuid, language: Option<ChatLanguage>) -> Result<(), UsecaseError> {
+        self.chat_repo
+            .set_chat_language(user_id, language.map(|l| l.code().to_string()))
+            .await?;
+
+        tracing::info!(language = ?language, "chat language updated");
+        Ok(())
+    }
+
+    /// `route_id` is the route the user has open; its points are added to the
+    /// system context so the assistant can answer questions about it.
+    /// `image_urls` are attached to this message for vision-capable models.
+    /// `language` comes from [`Self::preferred_language`]; it pins the reply
+    /// language and localizes the texts returned without the model.
+    #[tracing::instrument(skip(self, text, image_urls), fields(user_id = %user_id, conversation_id = %conversation_id))]
     pub async fn send_message(
         &self,
         user_id: Uuid,
         conversation_id: Uuid,
         text: String,
+        route_id: Option<Uuid>,
+        image_urls: Vec<String>,
+        language: Option<ChatLanguage>,
     ) -> Result<ChatResponse, UsecaseError> {
-        let assistant = self
-            .assistant
-            .as_ref()
-            .ok_or_else(|| UsecaseError::Unavailable("AI assistant is not available".to_string()))?;
+        let turn = self
+            .prepare_turn(user_id, conversation_id, text, route_id, image_urls, language)
+            .await?;
+        self.run_turn(turn, None).await
+    }
 
-        tracing::info!(%user_id, %conversation_id, "processing chat message");
+    /// Checks the request, saves the user message and builds the model input.
+    /// Errors surface before anything is streamed.
+    #[tracing::instrument(skip(self, text, image_urls), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    pub async fn prepare_turn(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        text: String,
+        route_id: Option<Uuid>,
+        image_urls: Vec<String>,
+        language: Option<ChatLanguage>,
+    ) -> Result<ChatTurn, UsecaseError> {
+        let assistant = self.assistant.as_ref().ok_or_else(|| {
+            UsecaseError::Unavailable(CannedText::AssistantUnavailable.localized(language).to_string())
+        })?;
+
+        if !image_urls.is_empty() && !assistant.supports_vision() {
+            return Err(UsecaseError::Validation(
+                CannedText::ImagesUnsupported.localized(language).to_string(),
+            ));
+        }
+
+        tracing::info!(%user_id, %conversation_id, ?route_id, images = image_urls.len(), "processing chat message");
+
+        let route_context = match route_id {
+            Some(route_id) => {
+                let route = self
+                    .route_repo
+                    .find_by_id(route_id)
+                    .await?
+                    .filter(|route| route.user_id == user_id)
+                    .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+                Some(format_route_context(&route))
+            }
+            None => None,
+        };
 
         let user_msg = ChatMessage::new_user_message(user_id, conversation_id, text);
         self.chat_repo.create(&user_msg).await?;
@@ -187,21 +312,101 @@ where
 
         let mut messages = vec![OpenAIMessage {
             role: "system".to_string(),
-            content: Some(SYSTEM_PROMPT.to_string()),
+            content: Some(self.system_prompt().await.into()),
             tool_call_id: None,
             tool_calls: None,
         }];
+        if let Some(language) = language {
+            messages.push(OpenAIMessage {
+                role: "system".to_string(),
+                content: Some(language_instruction(language).into()),
+                tool_call_id: None,
+                tool_calls: None,
+            });
+        }
+        if let Some(context) = route_context {
+            messages.push(OpenAIMessage {
+                role: "system".to_string(),
+                content: Some(context.into()),
+                tool_call_id: None,
+                tool_calls: None,
+            });
+        }
         for msg in &history {
+            // Images are only sent with the message they were attached to.
+            let content = if msg.id == user_msg.id && !image_urls.is_empty() {
+                OpenAIContent::with_images(msg.content.clone(), &image_urls)
+            } else {
+                msg.content.clone().into()
+            };
             messages.push(OpenAIMessage {
                 role: msg.role.clone(),
-                content: Some(msg.content.clone()),
+                content: Some(content),
                 tool_call_id: None,
                 tool_calls: None,
             });
         }
 
+        Ok(ChatTurn {
+            user_id,
+            conversation_id,
+            language,
+            messages,
+        })
+    }
+
+    /// Runs the tool loop for a prepared turn and saves the answer. Tool
+    /// progress is reported on `progress` when given. The turn's latency,
+    /// tools and outcome are recorded for the admin dashboard (best-effort).
+    async fn run_turn(
+        &self,
+        turn: ChatTurn,
+        progress: Option<mpsc::UnboundedSender<ChatStreamEvent>>,
+    ) -> Result<ChatResponse, UsecaseError> {
+        let (user_id, conversation_id) = (turn.user_id, turn.conversation_id);
+        let started = std::time::Instant::now();
+        let mut tool_calls = Vec::new();
+
+        let result = self.run_tool_loop(turn, progress, &mut tool_calls).await;
+
+        let record = ChatTurnRecord::new(
+            user_id,
+            conversation_id,
+            tool_calls,
+            started.elapsed().as_millis() as i64,
+            result.as_ref().err().map(ToString::to_string),
+        );
+        if let Err(e) = self.chat_repo.record_turn(&record).await {
+            tracing::warn!(error = %e, "failed to record chat turn");
+        }
+        result
+    }
+
+    async fn run_tool_loop(
+        &self,
+        turn: ChatTurn,
+        progress: Option<mpsc::UnboundedSender<ChatStreamEvent>>,
+        tool_calls: &mut Vec<String>,
+    ) -> Result<ChatResponse, UsecaseError> {
+        let ChatTurn {
+            user_id,
+            conversation_id,
+            language,
+            mut messages,
+        } = turn;
+        let assistant = self.assistant.as_ref().ok_or_else(|| {
+            UsecaseError::Unavailable(CannedText::AssistantUnavailable.localized(language).to_string())
+        })?;
+        let report = |event: ChatStreamEvent| {
+            if let Some(tx) = progress.as_ref() {
+                // The receiver is gone only if the client disconnected.
+                let _ = tx.send(event);
+            }
+        };
+
         let tools = build_tools();
         let mut actions: Vec<ChatAction> = Vec::new();
+        let mut used_fallback = false;
 
         for iteration in 0..self.max_tool_iterations {
             tracing::debug!(iteration, "sending request to OpenAI");
@@ -214,6 +419,7 @@ where
             };
 
             let response = assistant.chat(request).await?;
+            used_fallback |= response.fallback_model.is_some();
             let choice = response
                 .choices
                 .get(0)
@@ -232,7 +438,7 @@ where
                 // Add the assistant message (with tool_calls) to history
                 messages.push(OpenAIMessage {
                     role: resp_message.role.clone(),
-                    content: resp_message.content.clone(),
+                    content: resp_message.content.clone().map(Into::into),
                     tool_call_id: None,
                     tool_calls: Some(resp_message.tool_calls.clone()),
                 });
@@ -247,14 +453,17 @@ where
                     );
 
                     let tool_args = parse_function_arguments(&tool_call.function.arguments);
+                    tool_calls.push(tool_call.function.name.clone());
+                    report(ChatStreamEvent::ToolStarted { name: tool_call.function.name.clone() });
                     let (result_text, new_actions) =
-                        self.execute_tool(&tool_call.function.name, &tool_args).await;
+                        self.execute_tool(user_id, conversation_id, &tool_call.function.name, &tool_args).await;
+                    report(ChatStreamEvent::ToolFinished { name: tool_call.function.name.clone() });
 
                     actions.extend(new_actions);
 
                     messages.push(OpenAIMessage {
                         role: "tool".to_string(),
-                        content: Some(result_text),
+                        content: Some(result_text.into()),
                         tool_call_id: Some(tool_call.id.clone()),
                         tool_calls: None,
                     });
@@ -292,6 +501,7 @@ where
                     message: assistant_text,
                     actions,
                     conversation_id,
+                    notice: used_fallback.then(|| CannedText::FallbackNotice.localized(language).to_string()),
                 });
             }
         }
@@ -305,56 +515,58 @@ where
         ))
     }
 
-    #[tracing::instrument(skip(self, text), fields(user_id = %user_id, conversation_id = %conversation_id))]
-    pub async fn send_message_stream(
-        &self,
-        user_id: Uuid,
-        conversation_id: Uuid,
-        text: String,
-    ) -> Result<(ChatResponse, std::pin::Pin<Box<dyn Stream<Item = Result<ChatStreamEvent, UsecaseError>> + Send>>), UsecaseError> {
-        // Run full non-streaming call first (tool loop + final answer)
-        let response = self.send_message(user_id, conversation_id, text).await?;
+    /// Runs a prepared turn as a stream: tool progress while the assistant
+    /// works, then the answer split into word-level tokens. Dropping the
+    /// stream cancels the turn.
+    pub fn stream_turn(&self, turn: ChatTurn) -> impl Stream<Item = Result<ChatStreamEvent, UsecaseError>> + '_ {
+        let (tx, mut rx) = mpsc::unbounded_channel();
 
-        tracing::info!(
-            response_id = %response.id,
-            response_len = response.message.len(),
-            "streaming buffered response"
-        );
+        async_stream::try_stream! {
+            let run = self.run_turn(turn, Some(tx));
+            tokio::pin!(run);
 
-        let actions = response.actions.clone();
-        let message_id = response.id;
-        let conv_id = response.conversation_id;
+            let response = loop {
+                let step = tokio::select! {
+                    biased;
+                    Some(event) = rx.recv() => TurnStep::Progress(event),
+                    result = &mut run => TurnStep::Finished(result),
+                };
+                match step {
+                    TurnStep::Progress(event) => yield event,
+                    TurnStep::Finished(result) => break result?,
+                }
+            };
+            // Progress sent just before the turn finished.
+            while let Ok(event) = rx.try_recv() {
+                yield event;
+            }
 
-        // Split response text into word-level chunks for progressive rendering
-        let chunks: Vec<String> = response
-            .message
-            .split_inclusive(char::is_whitespace)
-            .map(String::from)
-            .collect();
+            tracing::info!(
+                response_id = %response.id,
+                response_len = response.message.len(),
+                "streaming response"
+            );
 
-        let stream = async_stream::try_stream! {
-            // Emit actions first
-            if !actions.is_empty() {
-                yield ChatStreamEvent::Actions { actions };
+            if let Some(message) = response.notice {
+                yield ChatStreamEvent::Notice { message };
             }
-
-            // Stream tokens progressively
-            for chunk in chunks {
-                yield ChatStreamEvent::Token { content: chunk };
+            if !response.actions.is_empty() {
+                yield ChatStreamEvent::Actions { actions: response.actions };
+            }
+            for chunk in response.message.split_inclusive(char::is_whitespace) {
+                yield ChatStreamEvent::Token { content: chunk.to_string() };
             }
-
-            // Done
             yield ChatStreamEvent::Done {
-                id: message_id,
-                conversation_id: conv_id,
+                id: response.id,
+                conversation_id: response.conversation_id,
             };
-        };
-
-        Ok((response, Box::pin(stream)))
+        }
     }
 
     async fn execute_tool(
         &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
         name: &str,
         args: &std::collections::HashMap<String, serde_json::Value>,
     ) -> (String, Vec<ChatAction>) {
@@ -362,9 +574,12 @@ where
 
         match name {
             "geocode" => self.tool_geocode(args).await,
-            "search_routes" => self.tool_search_routes(args).await,
-            "get_route_details" => self.tool_get_route_details(args).await,
+            "search_routes" => self.tool_search_routes(user_id, args).await,
+            "get_route_details" => self.tool_get_route_details(user_id, args).await,
             "navigate" => self.tool_navigate(args).await,
+            "add_point_to_route" | "remove_point_from_route" => {
+                self.propose_route_edit(user_id, conversation_id, name, args).await
+            }
             _ => {
                 tracing::warn!(%name, "unknown tool called");
                 (format!("Unknown tool: {}", name), vec![])
@@ -438,10 +653,22 @@ where
         }
     }
 
+    /// Searches public routes, or with `scope: "mine"` every route of the
+    /// requesting user, private ones included. Unlisted routes of other
+    /// users are never returned.
     async fn tool_search_routes(
         &self,
+        user_id: Uuid,
         args: &std::collections::HashMap<String, serde_json::Value>,
     ) -> (String, Vec<ChatAction>) {
+        let owner_id = match args.get("scope").and_then(|v| v.as_str()).unwrap_or("public") {
+            "public" => None,
+            "mine" => Some(user_id),
+            scope => {
+                tracing::warn!(%scope, "search_routes called with unknown scope");
+                return (format!("Unknown scope \"{}\", expected \"mine\" or \"public\"", scope), vec![]);
+            }
+        };
         let search = args.get("query").and_then(|v| v.as_str()).map(String::from);
         let category_id = args.get("category_id").and_then(|v| v.as_str()).and_then(|s| Uuid::parse_str(s).ok());
         let sort = args
@@ -454,7 +681,7 @@ where
             .unwrap_or(5)
             .min(10);
 
-        tracing::info!(?search, ?category_id, %sort, %limit, "executing search_routes tool");
+        tracing::info!(?search, ?category_id, %sort, %limit, mine = owner_id.is_some(), "executing search_routes tool");
 
         let order_clause = match sort {
             "oldest" => "r.created_at ASC",
@@ -465,7 +692,7 @@ where
 
         match self
             .route_repo
-            .explore_shared(search, category_id, None, order_clause, limit, 0)
+            .explore_shared(owner_id, search, category_id, None, order_clause, limit, 0)
             .await
         {
             Ok(routes) => {
@@ -479,6 +706,8 @@ where
                         category_ids: r.category_ids.clone(),
                         avg_rating: r.avg_rating,
                         likes_count: r.likes_count,
+                        start_location: r.start_location.clone(),
+                        end_location: r.end_location.clone(),
                     })
                     .collect();
 
@@ -500,8 +729,10 @@ where
         }
     }
 
+    /// Other users' routes are only described when they are public.
     async fn tool_get_route_details(
         &self,
+        user_id: Uuid,
         args: &std::collections::HashMap<String, serde_json::Value>,
     ) -> (String, Vec<ChatAction>) {
         let route_id_str = args
@@ -519,8 +750,9 @@ where
             }
         };
 
+        let visible = |route: &Route| route.user_id == user_id || route.visibility == RouteVisibility::Public;
         match self.route_repo.find_by_id(route_id).await {
-            Ok(Some(route)) => {
+            Ok(Some(route)) if visible(&route) => {
                 tracing::info!(%route_id, name = %route.name, "route details found");
 
                 let result = serde_json::json!({
@@ -529,13 +761,13 @@ where
                     "points_count": route.points.len(),
                     "category_ids": route.category_ids,
                     "created_at": route.created_at.to_rfc3339(),
-                    "is_shared": route.share_token.is_some(),
+                    "visibility": route.visibility,
                 });
 
                 (result.to_string(), vec![])
             }
-            Ok(None) => {
-                tracing::info!(%route_id, "route not found");
+            Ok(_) => {
+                tracing::info!(%route_id, "route not found or not visible");
                 ("Route not found.".to_string(), vec![])
             }
             Err(e) => {
@@ -584,6 +816,149 @@ where
         }
     }
 
+    /// Loads a route for editing, or the text to return to the model when the
+    /// route is missing or belongs to someone else.
+    async fn load_own_route(
+        &self,
+        user_id: Uuid,
+        args: &std::collections::HashMap<String, serde_json::Value>,
+    ) -> Result<Route, String> {
+        let route_id_str = args
+            .get("route_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let route_id = Uuid::parse_str(route_id_str)
+            .map_err(|_| format!("Invalid route ID: {}", route_id_str))?;
+
+        match self.route_repo.find_by_id(route_id).await {
+            Ok(Some(route)) if route.user_id == user_id => Ok(route),
+            Ok(_) => {
+                tracing::warn!(%route_id, "route not found or not owned by user");
+                Err("Route not found.".to_string())
+            }
+            Err(e) => {
+                tracing::error!(%route_id, error = %e, "failed to load route for editing");
+                Err(format!("Failed to get route: {}", e))
+            }
+        }
+    }
+
+    /// Applies a route editing tool to the current state of the route. Returns
+    /// the edited route with a summary of the change, or the text to report
+    /// when the arguments do not fit the route.
+    async fn prepare_route_edit(
+        &self,
+        user_id: Uuid,
+        tool: &str,
+        args: &std::collections::HashMap<String, serde_json::Value>,
+    ) -> Result<(Route, String), String> {
+        let mut route = self.load_own_route(user_id, args).await?;
+        let summary = match tool {
+            "add_point_to_route" => add_point(&mut route, args, self.max_route_points)?,
+            "remove_point_from_route" => remove_point(&mut route, args)?,
+            _ => return Err(format!("Unknown tool: {}", tool)),
+        };
+        Ok((route, summary))
+    }
+
+    /// Validates a route edit and stores it as a pending action. Nothing is
+    /// changed until the user confirms it through [`Self::confirm_action`].
+    async fn propose_route_edit(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        tool: &str,
+        args: &std::collections::HashMap<String, serde_json::Value>,
+    ) -> (String, Vec<ChatAction>) {
+        tracing::info!(%tool, "proposing route edit");
+
+        let summary = match self.prepare_route_edit(user_id, tool, args).await {
+            Ok((_, summary)) => summary,
+            Err(text) => return (text, vec![]),
+        };
+
+        let expired_before = chrono::Utc::now() - chrono::Duration::minutes(PENDING_ACTION_TTL_MINUTES);
+        if let Err(e) = self.chat_repo.delete_pending_actions_before(expired_before).await {
+            tracing::warn!(error = %e, "failed to purge expired pending actions");
+        }
+
+        let action = PendingChatAction::new(
+            user_id,
+            conversation_id,
+            tool.to_string(),
+            serde_json::json!(args),
+            summary.clone(),
+        );
+        if let Err(e) = self.chat_repo.create_pending_action(&action).await {
+            tracing::error!(error = %e, "failed to store pending action");
+            return (format!("Failed to propose the change: {}", e), vec![]);
+        }
+
+        metrics::counter!("chat_pending_actions_total", "tool" => tool.to_string(), "outcome" => "proposed").increment(1);
+        tracing::info!(action_id = %action.id, %tool, "pending action created");
+        (
+            format!(
+                "Awaiting the user's confirmation: {}. The app shows the user a confirm button; do not call {} again for this change.",
+                summary, tool
+            ),
+            vec![ChatAction::ConfirmationRequired {
+                action_id: action.id,
+                summary,
+            }],
+        )
+    }
+
+    /// Runs a pending action the user confirmed and records the result as an
+    /// assistant message in the conversation it was proposed in. Each action
+    /// runs at most once and expires after [`PENDING_ACTION_TTL_MINUTES`].
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, action_id = %action_id))]
+    pub async fn confirm_action(
+        &self,
+        user_id: Uuid,
+        action_id: Uuid,
+    ) -> Result<ChatResponse, UsecaseError> {
+        let action = self
+            .chat_repo
+            .take_pending_action(user_id, action_id)
+            .await?
+            .filter(|a| chrono::Utc::now() - a.created_at < chrono::Duration::minutes(PENDING_ACTION_TTL_MINUTES))
+            .ok_or_else(|| UsecaseError::NotFound("Pending action".to_string()))?;
+
+        let args: std::collections::HashMap<String, serde_json::Value> =
+            serde_json::from_value(action.arguments)
+                .map_err(|e| UsecaseError::Internal(format!("invalid pending action arguments: {}", e)))?;
+
+        // Re-applied to the route as it is now, in case it changed meanwhile.
+        let (route, summary) = self
+            .prepare_route_edit(user_id, &action.tool, &args)
+            .await
+            .map_err(UsecaseError::Validation)?;
+        self.route_repo.update(&route).await?;
+
+        metrics::counter!("chat_pending_actions_total", "tool" => action.tool.clone(), "outcome" => "confirmed").increment(1);
+        tracing::info!(route_id = %route.id, tool = %action.tool, "pending action confirmed");
+
+        let message = format!("Done: {}. The route now has {} points.", summary, route.points.len());
+        let actions = vec![ChatAction::RouteUpdated { route_id: route.id }];
+        let actions_json = serde_json::to_value(&actions)
+            .map_err(|e| UsecaseError::Internal(format!("failed to serialize actions: {}", e)))?;
+        let assistant_msg = ChatMessage::new_assistant_message(
+            user_id,
+            action.conversation_id,
+            message.clone(),
+            Some(actions_json),
+        );
+        self.chat_repo.create(&assistant_msg).await?;
+
+        Ok(ChatResponse {
+            id: assistant_msg.id,
+            message,
+            actions,
+            conversation_id: action.conversation_id,
+            notice: None,
+        })
+    }
+
     #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
     pub async fn get_history(
         &self,
@@ -607,12 +982,13 @@ where
         user_id: Uuid,
         limit: i64,
         offset: i64,
+        include_archived: bool,
     ) -> Result<Vec<ConversationSummary>, UsecaseError> {
-        tracing::debug!(%limit, %offset, "listing conversations");
+        tracing::debug!(%limit, %offset, include_archived, "listing conversations");
 
         let conversations = self
             .chat_repo
-            .list_conversations(user_id, limit, offset)
+            .list_conversations(user_id, limit, offset, include_archived)
             .await?;
 
         tracing::debug!(count = conversations.len(), "conversations listed");
@@ -620,25 +996,168 @@ where
     }
 
     #[tracing::instrument(skip(self), fields(user_id = %user_id))]
-    pub async fn count_conversations(&self, user_id: Uuid) -> Result<i64, UsecaseError> {
+    pub async fn count_conversations(&self, user_id: Uuid, include_archived: bool) -> Result<i64, UsecaseError> {
         tracing::debug!("counting conversations");
 
-        let count = self.chat_repo.count_conversations(user_id).await?;
+        let count = self.chat_repo.count_conversations(user_id, include_archived).await?;
 
         tracing::debug!(count, "conversations counted");
         Ok(count)
     }
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id, message_id = %message_id))]
+    /// Archives/unarchives or pins/unpins a conversation. Flags left as
+    /// `None` keep their current value.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    pub async fn update_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        archived: Option<bool>,
+        pinned: Option<bool>,
+    ) -> Result<ConversationFlags, UsecaseError> {
+        if archived.is_none() && pinned.is_none() {
+            return Err(UsecaseError::Validation(
+                "Nothing to update: set `archived` or `pinned`".to_string(),
+            ));
+        }
+
+        let flags = self
+            .chat_repo
+            .set_conversation_flags(user_id, conversation_id, archived, pinned)
+            .await?;
+
+        tracing::info!(archived = flags.archived, pinned = flags.pinned, "conversation updated");
+        Ok(flags)
+    }
+
+    /// Returns the conversation's share token, issuing one on first use.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    pub async fn share_conversation(&self, user_id: Uuid, conversation_id: Uuid) -> Result<Uuid, UsecaseError> {
+        let token = self
+            .chat_repo
+            .share_conversation(user_id, conversation_id, Uuid::new_v4())
+            .await?;
+
+        metrics::counter!("chat_shares_total", "action" => "enable").increment(1);
+        tracing::info!("conversation shared");
+        Ok(token)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    pub async fn unshare_conversation(&self, user_id: Uuid, conversation_id: Uuid) -> Result<(), UsecaseError> {
+        self.chat_repo
+            .unshare_conversation(user_id, conversation_id)
+            .await?;
+
+        metrics::counter!("chat_shares_total", "action" => "disable").increment(1);
+        tracing::info!("conversation unshared");
+        Ok(())
+    }
+
+    /// History behind a share link, limited to what was said in the chat:
+    /// anything other than user and assistant turns is dropped.
+    #[tracing::instrument(skip(self, token))]
+    pub async fn get_shared_conversation(&self, token: Uuid) -> Result<Vec<ChatMessage>, UsecaseError> {
+        let (user_id, conversation_id) = self
+            .chat_repo
+            .find_shared_conversation(token)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Shared conversation".to_string()))?;
+
+        let messages: Vec<ChatMessage> = self
+            .chat_repo
+            .find_by_conversation(user_id, conversation_id, 100)
+            .await?
+            .into_iter()
+            .filter(|m| m.role == "user" || m.role == "assistant")
+            .collect();
+
+        tracing::debug!(%conversation_id, count = messages.len(), "shared conversation retrieved");
+        Ok(messages)
+    }
+
+    /// Flags the conversation for abuse review. Admins can only read its
+    /// messages when `allow_review` is set; flagging again replaces both.
+    #[tracing::instrument(skip(self, reason), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    pub async fn flag_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        reason: Option<String>,
+        allow_review: bool,
+    ) -> Result<(), UsecaseError> {
+        let reason = reason.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+        if reason.as_ref().is_some_and(|r| r.chars().count() > MAX_FLAG_REASON_LENGTH) {
+            return Err(UsecaseError::Validation(format!(
+                "Reason must be at most {} characters",
+                MAX_FLAG_REASON_LENGTH
+            )));
+        }
+
+        self.chat_repo
+            .flag_conversation(user_id, conversation_id, reason, allow_review)
+            .await?;
+
+        metrics::counter!("chat_flags_total").increment(1);
+        tracing::info!(allow_review, "conversation flagged for review");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn list_flagged_conversations(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<FlaggedConversation>, i64), UsecaseError> {
+        let conversations = self.chat_repo.list_flagged_conversations(limit, offset).await?;
+        let total = self.chat_repo.count_flagged_conversations().await?;
+
+        tracing::debug!(count = conversations.len(), total, "flagged conversations listed");
+        Ok((conversations, total))
+    }
+
+    /// Messages of a flagged conversation, for abuse review. Forbidden
+    /// unless its owner allowed the review when flagging it.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id))]
+    pub async fn review_flagged_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+    ) -> Result<Vec<ChatMessage>, UsecaseError> {
+        let flagged = self
+            .chat_repo
+            .find_flagged_conversation(user_id, conversation_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Flagged conversation".to_string()))?;
+        if !flagged.review_consent {
+            tracing::warn!("review of flagged conversation without consent refused");
+            return Err(UsecaseError::Forbidden(
+                "The user did not allow this conversation to be reviewed".to_string(),
+            ));
+        }
+
+        let messages = self
+            .chat_repo
+            .find_by_conversation(user_id, conversation_id, 100)
+            .await?;
+
+        tracing::info!(count = messages.len(), "flagged conversation reviewed");
+        Ok(messages)
+    }
+
+    /// Deletes one of the user's messages. A message from another user or
+    /// another conversation is reported as not found.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, conversation_id = %conversation_id, message_id = %message_id))]
     pub async fn delete_message(
         &self,
         user_id: Uuid,
+        conversation_id: Uuid,
         message_id: Uuid,
     ) -> Result<(), UsecaseError> {
         tracing::info!("deleting message");
 
         self.chat_repo
-            .delete_message(user_id, message_id)
+            .delete_message(user_id, conversation_id, message_id)
             .await?;
 
         tracing::info!("message deleted");
@@ -669,6 +1188,127 @@ struct NominatimResult {
     display_name: String,
 }
 
+/// Inserts the point described by `add_point_to_route` arguments.
+fn add_point(
+    route: &mut Route,
+    args: &std::collections::HashMap<String, serde_json::Value>,
+    max_points: usize,
+) -> Result<String, String> {
+    let (Some(lat), Some(lng)) = (
+        args.get("lat").and_then(|v| v.as_f64()),
+        args.get("lng").and_then(|v| v.as_f64()),
+    ) else {
+        return Err("Both lat and lng are required.".to_string());
+    };
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) {
+        return Err(format!("Invalid coordinates ({}, {}).", lat, lng));
+    }
+    if route.points.len() >= max_points {
+        return Err(format!("The route already has the maximum of {} points.", max_points));
+    }
+
+    let len = route.points.len();
+    let position = match args.get("position").and_then(|v| v.as_u64()) {
+        None => len + 1,
+        Some(p) if (1..=len as u64 + 1).contains(&p) => p as usize,
+        Some(p) => return Err(format!("Invalid position {}; the route has {} points.", p, len)),
+    };
+    let name = args
+        .get("name")
+        .and_then(|v| v.as_str())
+        .filter(|n| !n.trim().is_empty())
+        .map(|n| n.trim().to_string());
+
+    let summary = format!(
+        "add point {}{} at {:.6}, {:.6} to route \"{}\"",
+        position,
+        name.as_ref().map(|n| format!(" \"{}\"", n)).unwrap_or_default(),
+        lat,
+        lng,
+        route.name
+    );
+    let mut points = route.points.clone();
+    points.insert(
+        position - 1,
+        RoutePoint {
+            lat,
+            lng,
+            name,
+            segment_mode: None,
+            photo: None,
+            address: None,
+        },
+    );
+    route.update(None, Some(points), None, None, None);
+    Ok(summary)
+}
+
+/// Removes the point chosen by `remove_point_from_route` arguments.
+fn remove_point(
+    route: &mut Route,
+    args: &std::collections::HashMap<String, serde_json::Value>,
+) -> Result<String, String> {
+    let len = route.points.len();
+    let position = match args.get("position").and_then(|v| v.as_u64()) {
+        Some(p) if (1..=len as u64).contains(&p) => p as usize,
+        _ => return Err(format!("A position between 1 and {} is required.", len)),
+    };
+
+    let mut points = route.points.clone();
+    let removed = points.remove(position - 1);
+    let summary = format!(
+        "remove point {}{} ({:.6}, {:.6}) from route \"{}\"",
+        position,
+        removed.name.as_ref().map(|n| format!(" \"{}\"", n)).unwrap_or_default(),
+        removed.lat,
+        removed.lng,
+        route.name
+    );
+    route.update(None, Some(points), None, None, None);
+    Ok(summary)
+}
+
+/// Describes the route the user has open, with numbered points so requests
+/// like "what's near point 3" can be resolved.
+fn format_route_context(route: &Route) -> String {
+    let mut context = format!(
+        "The user currently has this route open; questions like \"this route\" or \"point N\" refer to it.\n\
+         Route \"{}\" (id {}): {} points, {:.2} km",
+        route.name,
+        route.id,
+        route.points.len(),
+        total_distance_km(&route.points),
+    );
+    if let (Some(start), Some(end)) = (&route.start_location, &route.end_location) {
+        context.push_str(&format!(", from {} to {}", start, end));
+    }
+    if let Some(description) = &route.description {
+        context.push_str(&format!("\nDescription: {}", description));
+    }
+    context.push_str("\nPoints:");
+
+    for (i, point) in route.points.iter().take(MAX_CONTEXT_POINTS).enumerate() {
+        context.push_str(&format!("\n{}. {:.6}, {:.6}", i + 1, point.lat, point.lng));
+        if let Some(name) = &point.name {
+            context.push_str(&format!(" \"{}\"", name));
+        }
+        if let Some(address) = point.address.as_ref().filter(|a| !a.is_empty()) {
+            context.push_str(&format!(" at {}", address));
+        }
+        if let Some(mode) = &point.segment_mode {
+            context.push_str(&format!(" ({})", mode));
+        }
+    }
+    if route.points.len() > MAX_CONTEXT_POINTS {
+        context.push_str(&format!(
+            "\n... {} more points omitted",
+            route.points.len() - MAX_CONTEXT_POINTS
+        ));
+    }
+
+    context
+}
+
 fn build_tools() -> Vec<OpenAITool> {
     vec![
         OpenAITool {
@@ -692,10 +1332,15 @@ fn build_tools() -> Vec<OpenAITool> {
             tool_type: "function".to_string(),
             function: OpenAIFunction {
                 name: "search_routes".to_string(),
-                description: "Search the route catalog for shared routes. Can filter by text query, category UUID, and sort order.".to_string(),
+                description: "Search the public route catalog, or the user's own routes including private ones. Can filter by text query, category UUID, and sort order.".to_string(),
                 parameters: serde_json::json!({
                     "type": "object",
                     "properties": {
+                        "scope": {
+                            "type": "string",
+                            "enum": ["public", "mine"],
+                            "description": "\"public\" searches routes published to explore (default), \"mine\" searches the user's own routes"
+                        },
                         "query": {
                             "type": "string",
                             "description": "Text search query for route names"
@@ -753,6 +1398,60 @@ fn build_tools() -> Vec<OpenAITool> {
                 }),
             },
         },
+        OpenAITool {
+            tool_type: "function".to_string(),
+            function: OpenAIFunction {
+                name: "add_point_to_route".to_string(),
+                description: "Propose adding a point to one of the user's own routes. The change is applied only after the user confirms it in the app.".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "route_id": {
+                            "type": "string",
+                            "description": "The UUID of the route"
+                        },
+                        "lat": {
+                            "type": "number",
+                            "description": "Latitude of the new point"
+                        },
+                        "lng": {
+                            "type": "number",
+                            "description": "Longitude of the new point"
+                        },
+                        "name": {
+                            "type": "string",
+                            "description": "Optional name of the new point"
+                        },
+                        "position": {
+                            "type": "integer",
+                            "description": "1-based position of the new point; defaults to the end of the route"
+                        }
+                    },
+                    "required": ["route_id", "lat", "lng"]
+                }),
+            },
+        },
+        OpenAITool {
+            tool_type: "function".to_string(),
+            function: OpenAIFunction {
+                name: "remove_point_from_route".to_string(),
+                description: "Propose removing a point from one of the user's own routes. The change is applied only after the user confirms it in the app.".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "route_id": {
+                            "type": "string",
+                            "description": "The UUID of the route"
+                        },
+                        "position": {
+                            "type": "integer",
+                            "description": "1-based position of the point to remove"
+                        }
+                    },
+                    "required": ["route_id", "position"]
+                }),
+            },
+        },
     ]
 }
 
@@ -794,17 +1493,19 @@ mod urlencoding {
 mod tests {
     use super::*;
     use crate::domain::chat_message::ChatMessage;
-    use crate::domain::route::{ExploreRouteRow, Route};
+    use crate::domain::route::{ExploreRouteRow, PointAddress};
     use crate::repository::errors::RepositoryError;
-    use crate::usecase::contracts::{MockChatMessageRepository, MockRouteRepository};
+    use crate::usecase::contracts::{MockChatMessageRepository, MockRouteRepository, MockSettingsRepository};
     use crate::usecase::openai::OpenAIClient;
+    use crate::usecase::settings::SettingsUseCase;
+    use futures::StreamExt;
     use std::collections::HashMap;
 
     fn make_usecase(
         chat_repo: MockChatMessageRepository,
         route_repo: MockRouteRepository,
         with_assistant: bool,
-    ) -> ChatUseCase<MockChatMessageRepository, MockRouteRepository> {
+    ) -> ChatUseCase<MockChatMessageRepository, MockRouteRepository, MockSettingsRepository> {
         let assistant = if with_assistant {
             Some(OpenAIClient::new(
                 "https://api.openai.com/v1".to_string(),
@@ -856,14 +1557,154 @@ mod tests {
             false,
         );
         let result = uc
-            .send_message(Uuid::new_v4(), Uuid::new_v4(), "hi".to_string())
+            .send_message(Uuid::new_v4(), Uuid::new_v4(), "hi".to_string(), None, vec![], None)
             .await;
 
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("not available"));
+            .contains("unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_send_message_no_assistant_error_is_localized() {
+        let uc = make_usecase(
+            MockChatMessageRepository::new(),
+            MockRouteRepository::new(),
+            false,
+        );
+        let result = uc
+            .send_message(Uuid::new_v4(), Uuid::new_v4(), "привет".to_string(), None, vec![], Some(ChatLanguage::Ru))
+            .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            CannedText::AssistantUnavailable.localized(Some(ChatLanguage::Ru))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preferred_language_ignores_lookup_errors() {
+        let mut mock_chat = MockChatMessageRepository::new();
+        mock_chat
+            .expect_find_chat_language()
+            .times(1)
+            .return_once(|_| Err(RepositoryError::DatabaseError("down".to_string())));
+
+        let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
+        assert_eq!(uc.preferred_language(Uuid::new_v4()).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_send_message_with_images_requires_vision() {
+        // No repository expectations: the message is rejected before it is saved.
+        let uc = make_usecase(
+            MockChatMessageRepository::new(),
+            MockRouteRepository::new(),
+            true,
+        );
+        let result = uc
+            .send_message(
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                "where is this?".to_string(),
+                None,
+                vec!["https://minio.local/chat-attachments/photo.jpg".to_string()],
+                None,
+            )
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    // --- route context ---
+
+    fn context_route(user_id: Uuid, points: Vec<RoutePoint>) -> Route {
+        Route {
+            id: Uuid::new_v4(),
+            user_id,
+            name: "Evening walk".to_string(),
+            points,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            share_token: None,
+            visibility: RouteVisibility::Private,
+            category_ids: vec![],
+            start_location: Some("Moscow".to_string()),
+            end_location: Some("Khimki".to_string()),
+            seasons: vec![],
+            description: None,
+            archived_at: None,
+        }
+    }
+
+    fn context_point(lat: f64, lng: f64, name: Option<&str>) -> RoutePoint {
+        RoutePoint {
+            lat,
+            lng,
+            name: name.map(String::from),
+            segment_mode: None,
+            photo: None,
+            address: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_message_with_foreign_route_is_not_found() {
+        let route = context_route(Uuid::new_v4(), vec![]);
+        let route_id = route.id;
+
+        let mut mock_route = MockRouteRepository::new();
+        mock_route
+            .expect_find_by_id()
+            .with(mockall::predicate::eq(route_id))
+            .times(1)
+            .return_once(move |_| Ok(Some(route)));
+
+        // No chat_repo expectations: nothing is saved for a rejected route.
+        let uc = make_usecase(MockChatMessageRepository::new(), mock_route, true);
+        let result = uc
+            .send_message(Uuid::new_v4(), Uuid::new_v4(), "shorten it".to_string(), Some(route_id), vec![], None)
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_format_route_context_numbers_points() {
+        let mut addressed = context_point(55.76, 37.62, None);
+        addressed.address = Some(PointAddress {
+            street: Some("Tverskaya, 1".to_string()),
+            city: Some("Moscow".to_string()),
+            country: None,
+        });
+        let route = context_route(
+            Uuid::new_v4(),
+            vec![context_point(55.75, 37.61, Some("Red Square")), addressed],
+        );
+
+        let context = format_route_context(&route);
+
+        assert!(context.contains("Route \"Evening walk\""));
+        assert!(context.contains("2 points"));
+        assert!(context.contains("from Moscow to Khimki"));
+        assert!(context.contains("1. 55.750000, 37.610000 \"Red Square\""));
+        assert!(context.contains("2. 55.760000, 37.620000 at Tverskaya, 1, Moscow"));
+    }
+
+    #[test]
+    fn test_format_route_context_truncates_long_routes() {
+        let points = (0..MAX_CONTEXT_POINTS + 5)
+            .map(|i| context_point(55.0 + i as f64 * 0.001, 37.0, None))
+            .collect();
+        let route = context_route(Uuid::new_v4(), points);
+
+        let context = format_route_context(&route);
+
+        assert!(context.contains(&format!("{}. ", MAX_CONTEXT_POINTS)));
+        assert!(!context.contains(&format!("{}. ", MAX_CONTEXT_POINTS + 1)));
+        assert!(context.contains("5 more points omitted"));
     }
 
     // --- get_history ---
@@ -939,7 +1780,7 @@ mod tests {
             false,
         );
         let args = HashMap::new();
-        let (text, actions) = uc.execute_tool("nonexistent_tool", &args).await;
+        let (text, actions) = uc.execute_tool(Uuid::new_v4(), Uuid::new_v4(), "nonexistent_tool", &args).await;
 
         assert!(text.contains("Unknown tool"));
         assert!(text.contains("nonexistent_tool"));
@@ -958,17 +1799,25 @@ mod tests {
             name: "Test Route".to_string(),
             points_count: 5,
             created_at: chrono::Utc::now(),
-            share_token: Uuid::new_v4(),
+            share_token: Some(Uuid::new_v4()),
             likes_count: 10,
+            bookmarks_count: 0,
             avg_rating: 4.5,
             ratings_count: 3,
+            comments_count: 0,
             category_ids: vec![],
+            seasons: vec![],
+            start_location: Some("Kazan".to_string()),
+            end_location: Some("Sviyazhsk".to_string()),
+            cover_url: None,
+            cover_thumbnail_url: None,
+            path: vec![],
         }];
 
         mock_route
             .expect_explore_shared()
             .times(1)
-            .return_once(move |_, _, _, _, _| Ok(rows));
+            .return_once(move |_, _, _, _, _, _, _| Ok(rows));
 
         let uc = make_usecase(MockChatMessageRepository::new(), mock_route, false);
 
@@ -978,7 +1827,7 @@ mod tests {
             serde_json::Value::String("test".to_string()),
         );
 
-        let (text, actions) = uc.tool_search_routes(&args).await;
+        let (text, actions) = uc.tool_search_routes(Uuid::new_v4(), &args).await;
 
         assert!(text.contains("Test Route"));
         assert_eq!(actions.len(), 1);
@@ -987,6 +1836,7 @@ mod tests {
                 assert_eq!(routes.len(), 1);
                 assert_eq!(routes[0].name, "Test Route");
                 assert_eq!(routes[0].likes_count, 10);
+                assert_eq!(routes[0].start_location.as_deref(), Some("Kazan"));
             }
             _ => panic!("expected ShowRoutes action"),
         }
@@ -999,12 +1849,12 @@ mod tests {
         mock_route
             .expect_explore_shared()
             .times(1)
-            .return_once(|_, _, _, _, _| Ok(vec![]));
+            .return_once(|_, _, _, _, _, _, _| Ok(vec![]));
 
         let uc = make_usecase(MockChatMessageRepository::new(), mock_route, false);
 
         let args = HashMap::new();
-        let (_, actions) = uc.tool_search_routes(&args).await;
+        let (_, actions) = uc.tool_search_routes(Uuid::new_v4(), &args).await;
 
         assert!(actions.is_empty());
     }
@@ -1015,9 +1865,9 @@ mod tests {
 
         mock_route
             .expect_explore_shared()
-            .withf(|_, _, order, _, _| order == "likes_count DESC, r.created_at DESC")
+            .withf(|_, _, _, _, order, _, _| order == "likes_count DESC, r.created_at DESC")
             .times(1)
-            .return_once(|_, _, _, _, _| Ok(vec![]));
+            .return_once(|_, _, _, _, _, _, _| Ok(vec![]));
 
         let uc = make_usecase(MockChatMessageRepository::new(), mock_route, false);
 
@@ -1027,7 +1877,7 @@ mod tests {
             serde_json::Value::String("popular".to_string()),
         );
 
-        let _ = uc.tool_search_routes(&args).await;
+        let _ = uc.tool_search_routes(Uuid::new_v4(), &args).await;
     }
 
     #[tokio::test]
@@ -1037,17 +1887,58 @@ mod tests {
         mock_route
             .expect_explore_shared()
             .times(1)
-            .return_once(|_, _, _, _, _| Err(RepositoryError::NotFound));
+            .return_once(|_, _, _, _, _, _, _| Err(RepositoryError::NotFound));
 
         let uc = make_usecase(MockChatMessageRepository::new(), mock_route, false);
 
         let args = HashMap::new();
-        let (text, actions) = uc.tool_search_routes(&args).await;
+        let (text, actions) = uc.tool_search_routes(Uuid::new_v4(), &args).await;
 
         assert!(text.contains("Failed to search routes"));
         assert!(actions.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_tool_search_routes_scope_mine_searches_own_routes() {
+        let mut mock_route = MockRouteRepository::new();
+        let user_id = Uuid::new_v4();
+
+        mock_route
+            .expect_explore_shared()
+            .withf(move |owner_id, _, _, _, _, _, _| *owner_id == Some(user_id))
+            .times(1)
+            .return_once(|_, _, _, _, _, _, _| Ok(vec![]));
+
+        let uc = make_usecase(MockChatMessageRepository::new(), mock_route, false);
+
+        let mut args = HashMap::new();
+        args.insert("scope".to_string(), serde_json::Value::String("mine".to_string()));
+
+        let _ = uc.tool_search_routes(user_id, &args).await;
+    }
+
+    #[tokio::test]
+    async fn test_tool_search_routes_defaults_to_public_and_rejects_unknown_scope() {
+        let mut mock_route = MockRouteRepository::new();
+
+        mock_route
+            .expect_explore_shared()
+            .withf(|owner_id, _, _, _, _, _, _| owner_id.is_none())
+            .times(1)
+            .return_once(|_, _, _, _, _, _, _| Ok(vec![]));
+
+        let uc = make_usecase(MockChatMessageRepository::new(), mock_route, false);
+
+        let _ = uc.tool_search_routes(Uuid::new_v4(), &HashMap::new()).await;
+
+        let mut args = HashMap::new();
+        args.insert("scope".to_string(), serde_json::Value::String("all".to_string()));
+        let (text, actions) = uc.tool_search_routes(Uuid::new_v4(), &args).await;
+
+        assert!(text.contains("Unknown scope"));
+        assert!(actions.is_empty());
+    }
+
     // --- tool_get_route_details ---
 
     #[tokio::test]
@@ -1063,9 +1954,13 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: Some(Uuid::new_v4()),
+            visibility: RouteVisibility::Public,
             category_ids: vec![],
             start_location: None,
             end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
         };
         let route_clone = route.clone();
 
@@ -1083,10 +1978,10 @@ mod tests {
             serde_json::Value::String(route_id.to_string()),
         );
 
-        let (text, actions) = uc.tool_get_route_details(&args).await;
+        let (text, actions) = uc.tool_get_route_details(Uuid::new_v4(), &args).await;
 
         assert!(text.contains("My Route"));
-        assert!(text.contains("is_shared"));
+        assert!(text.contains("\"visibility\":\"public\""));
         assert!(actions.is_empty());
     }
 
@@ -1109,12 +2004,35 @@ mod tests {
             serde_json::Value::String(route_id.to_string()),
         );
 
-        let (text, actions) = uc.tool_get_route_details(&args).await;
+        let (text, actions) = uc.tool_get_route_details(Uuid::new_v4(), &args).await;
 
         assert!(text.contains("not found"));
         assert!(actions.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_tool_get_route_details_hides_unlisted_routes_of_others() {
+        let owner_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let mut route = context_route(owner_id, vec![]);
+        route.id = route_id;
+        route.share_token = Some(Uuid::new_v4());
+        route.visibility = RouteVisibility::Unlisted;
+
+        let mut mock_route = MockRouteRepository::new();
+        mock_route
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        let uc = make_usecase(MockChatMessageRepository::new(), mock_route, false);
+        let args = HashMap::from([("route_id".to_string(), serde_json::Value::String(route_id.to_string()))]);
+
+        let (text, _) = uc.tool_get_route_details(Uuid::new_v4(), &args).await;
+        assert!(text.contains("not found"));
+
+        let (text, _) = uc.tool_get_route_details(owner_id, &args).await;
+        assert!(text.contains("unlisted"));
+    }
+
     #[tokio::test]
     async fn test_tool_get_route_details_invalid_uuid() {
         let uc = make_usecase(
@@ -1129,7 +2047,7 @@ mod tests {
             serde_json::Value::String("not-a-uuid".to_string()),
         );
 
-        let (text, actions) = uc.tool_get_route_details(&args).await;
+        let (text, actions) = uc.tool_get_route_details(Uuid::new_v4(), &args).await;
 
         assert!(text.contains("Invalid route ID"));
         assert!(actions.is_empty());
@@ -1144,12 +2062,211 @@ mod tests {
         );
 
         let args = HashMap::new();
-        let (text, actions) = uc.tool_get_route_details(&args).await;
+        let (text, actions) = uc.tool_get_route_details(Uuid::new_v4(), &args).await;
 
         assert!(text.contains("Invalid route ID"));
         assert!(actions.is_empty());
     }
 
+    // --- route editing tools ---
+
+    fn edit_args(route_id: Uuid, extra: serde_json::Value) -> HashMap<String, serde_json::Value> {
+        let mut args: HashMap<String, serde_json::Value> =
+            serde_json::from_value(extra).unwrap();
+        args.insert(
+            "route_id".to_string(),
+            serde_json::Value::String(route_id.to_string()),
+        );
+        args
+    }
+
+    fn route_repo_with(route: Route) -> MockRouteRepository {
+        let mut mock_route = MockRouteRepository::new();
+        mock_route
+            .expect_find_by_id()
+            .with(mockall::predicate::eq(route.id))
+            .times(1)
+            .return_once(move |_| Ok(Some(route)));
+        mock_route
+    }
+
+    #[test]
+    fn test_add_point_inserts_at_position() {
+        let mut route = context_route(
+            Uuid::new_v4(),
+            vec![context_point(55.0, 37.0, None), context_point(56.0, 38.0, None)],
+        );
+        let args = edit_args(
+            route.id,
+            serde_json::json!({ "lat": 55.5, "lng": 37.5, "name": "Cafe", "position": 2 }),
+        );
+
+        let summary = add_point(&mut route, &args, 1000).unwrap();
+
+        assert!(summary.contains("add point 2 \"Cafe\""));
+        assert_eq!(route.points.len(), 3);
+        assert_eq!(route.points[1].name.as_deref(), Some("Cafe"));
+    }
+
+    #[test]
+    fn test_add_point_respects_max_points() {
+        let mut route = context_route(Uuid::new_v4(), vec![context_point(55.0, 37.0, None)]);
+        let args = edit_args(route.id, serde_json::json!({ "lat": 55.5, "lng": 37.5 }));
+
+        let err = add_point(&mut route, &args, 1).unwrap_err();
+
+        assert!(err.contains("maximum of 1 points"));
+        assert_eq!(route.points.len(), 1);
+    }
+
+    #[test]
+    fn test_remove_point_invalid_position() {
+        let mut route = context_route(Uuid::new_v4(), vec![context_point(55.0, 37.0, None)]);
+        let args = edit_args(route.id, serde_json::json!({ "position": 5 }));
+
+        let err = remove_point(&mut route, &args).unwrap_err();
+
+        assert!(err.contains("between 1 and 1"));
+    }
+
+    #[tokio::test]
+    async fn test_propose_route_edit_stores_pending_action() {
+        let user_id = Uuid::new_v4();
+        let conversation_id = Uuid::new_v4();
+        let route = context_route(user_id, vec![context_point(55.0, 37.0, None)]);
+        let args = edit_args(route.id, serde_json::json!({ "lat": 55.1, "lng": 37.1 }));
+
+        let mut mock_chat = MockChatMessageRepository::new();
+        mock_chat
+            .expect_delete_pending_actions_before()
+            .times(1)
+            .returning(|_| Ok(0));
+        mock_chat
+            .expect_create_pending_action()
+            .withf(move |a| {
+                a.user_id == user_id
+                    && a.conversation_id == conversation_id
+                    && a.tool == "add_point_to_route"
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // No expect_update: nothing is saved before confirmation.
+        let uc = make_usecase(mock_chat, route_repo_with(route), false);
+        let (text, actions) = uc
+            .execute_tool(user_id, conversation_id, "add_point_to_route", &args)
+            .await;
+
+        assert!(text.contains("Awaiting the user's confirmation"));
+        assert!(matches!(
+            actions.as_slice(),
+            [ChatAction::ConfirmationRequired { summary, .. }] if summary.contains("add point 2")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_propose_route_edit_foreign_route_not_found() {
+        let route = context_route(Uuid::new_v4(), vec![context_point(55.0, 37.0, None)]);
+        let args = edit_args(route.id, serde_json::json!({ "position": 1 }));
+
+        // No pending action is stored for a rejected edit.
+        let uc = make_usecase(MockChatMessageRepository::new(), route_repo_with(route), false);
+        let (text, actions) = uc
+            .execute_tool(Uuid::new_v4(), Uuid::new_v4(), "remove_point_from_route", &args)
+            .await;
+
+        assert_eq!(text, "Route not found.");
+        assert!(actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_action_applies_edit() {
+        let user_id = Uuid::new_v4();
+        let conversation_id = Uuid::new_v4();
+        let route = context_route(
+            user_id,
+            vec![context_point(55.0, 37.0, Some("Start")), context_point(56.0, 38.0, Some("End"))],
+        );
+        let route_id = route.id;
+        let action = PendingChatAction::new(
+            user_id,
+            conversation_id,
+            "remove_point_from_route".to_string(),
+            serde_json::json!({ "route_id": route_id.to_string(), "position": 1 }),
+            "remove point 1".to_string(),
+        );
+        let action_id = action.id;
+
+        let mut mock_chat = MockChatMessageRepository::new();
+        mock_chat
+            .expect_take_pending_action()
+            .with(mockall::predicate::eq(user_id), mockall::predicate::eq(action_id))
+            .times(1)
+            .return_once(move |_, _| Ok(Some(action)));
+        mock_chat
+            .expect_create()
+            .withf(move |m| m.role == "assistant" && m.conversation_id == conversation_id)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut mock_route = route_repo_with(route);
+        mock_route
+            .expect_update()
+            .withf(|r| r.points.len() == 1 && r.points[0].name.as_deref() == Some("End"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let uc = make_usecase(mock_chat, mock_route, false);
+        let response = uc.confirm_action(user_id, action_id).await.unwrap();
+
+        assert_eq!(response.conversation_id, conversation_id);
+        assert!(response.message.contains("now has 1 points"));
+        assert!(matches!(
+            response.actions.as_slice(),
+            [ChatAction::RouteUpdated { route_id: id }] if *id == route_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_action_unknown_is_not_found() {
+        let mut mock_chat = MockChatMessageRepository::new();
+        mock_chat
+            .expect_take_pending_action()
+            .times(1)
+            .returning(|_, _| Ok(None));
+
+        let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
+        let result = uc.confirm_action(Uuid::new_v4(), Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_action_expired_is_not_found() {
+        let user_id = Uuid::new_v4();
+        let mut action = PendingChatAction::new(
+            user_id,
+            Uuid::new_v4(),
+            "remove_point_from_route".to_string(),
+            serde_json::json!({ "route_id": Uuid::new_v4().to_string(), "position": 1 }),
+            "remove point 1".to_string(),
+        );
+        action.created_at -= chrono::Duration::minutes(PENDING_ACTION_TTL_MINUTES + 1);
+        let action_id = action.id;
+
+        let mut mock_chat = MockChatMessageRepository::new();
+        mock_chat
+            .expect_take_pending_action()
+            .times(1)
+            .return_once(move |_, _| Ok(Some(action)));
+
+        // No route lookups: an expired action never touches the route.
+        let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
+        let result = uc.confirm_action(user_id, action_id).await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
     // --- urlencoding ---
 
     #[test]
@@ -1182,15 +2299,17 @@ mod tests {
     // --- build_tools ---
 
     #[test]
-    fn test_build_tools_returns_four_tools() {
+    fn test_build_tools_returns_six_tools() {
         let tools = build_tools();
-        assert_eq!(tools.len(), 4);
+        assert_eq!(tools.len(), 6);
 
         let names: Vec<&str> = tools.iter().map(|t| t.function.name.as_str()).collect();
         assert!(names.contains(&"geocode"));
         assert!(names.contains(&"search_routes"));
         assert!(names.contains(&"get_route_details"));
         assert!(names.contains(&"navigate"));
+        assert!(names.contains(&"add_point_to_route"));
+        assert!(names.contains(&"remove_point_from_route"));
     }
 
     #[test]
@@ -1251,6 +2370,19 @@ mod tests {
         assert_eq!(json["label"], "Profile & Settings");
     }
 
+    #[test]
+    fn test_chat_action_confirmation_required_serialization() {
+        let action_id = Uuid::new_v4();
+        let action = ChatAction::ConfirmationRequired {
+            action_id,
+            summary: "remove point 1".to_string(),
+        };
+        let json = serde_json::to_value(&action).unwrap();
+        assert_eq!(json["type"], "confirmation_required");
+        assert_eq!(json["action_id"], action_id.to_string());
+        assert_eq!(json["summary"], "remove point 1");
+    }
+
     // --- ChatAction serialization ---
 
     #[test]
@@ -1277,6 +2409,8 @@ mod tests {
                 category_ids: vec![],
                 avg_rating: 4.2,
                 likes_count: 7,
+                start_location: None,
+                end_location: None,
             }],
         };
 
@@ -1295,11 +2429,13 @@ mod tests {
                 points: vec![],
             }],
             conversation_id: Uuid::new_v4(),
+            notice: None,
         };
 
         let json = serde_json::to_value(&resp).unwrap();
         assert_eq!(json["message"], "Here are results");
         assert!(json["actions"].is_array());
+        assert!(json.get("notice").is_none());
     }
 
     // --- list_conversations ---
@@ -1315,12 +2451,13 @@ mod tests {
                 mockall::predicate::eq(user_id),
                 mockall::predicate::eq(20i64),
                 mockall::predicate::eq(0i64),
+                mockall::predicate::eq(false),
             )
             .times(1)
-            .return_once(|_, _, _| Ok(vec![]));
+            .return_once(|_, _, _, _| Ok(vec![]));
 
         let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
-        let result = uc.list_conversations(user_id, 20, 0).await;
+        let result = uc.list_conversations(user_id, 20, 0, false).await;
 
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
@@ -1339,15 +2476,17 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             title: "hello".to_string(),
+            archived: false,
+            pinned: true,
         };
 
         mock_chat
             .expect_list_conversations()
             .times(1)
-            .return_once(move |_, _, _| Ok(vec![summary]));
+            .return_once(move |_, _, _, _| Ok(vec![summary]));
 
         let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
-        let result = uc.list_conversations(user_id, 20, 0).await;
+        let result = uc.list_conversations(user_id, 20, 0, false).await;
 
         assert!(result.is_ok());
         let conversations = result.unwrap();
@@ -1364,14 +2503,104 @@ mod tests {
         mock_chat
             .expect_list_conversations()
             .times(1)
-            .return_once(|_, _, _| Err(RepositoryError::DatabaseError("db error".to_string())));
+            .return_once(|_, _, _, _| Err(RepositoryError::DatabaseError("db error".to_string())));
 
         let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
-        let result = uc.list_conversations(Uuid::new_v4(), 20, 0).await;
+        let result = uc.list_conversations(Uuid::new_v4(), 20, 0, false).await;
 
         assert!(result.is_err());
     }
 
+    // --- update_conversation ---
+
+    #[tokio::test]
+    async fn test_update_conversation_sets_flags() {
+        let mut mock_chat = MockChatMessageRepository::new();
+        let user_id = Uuid::new_v4();
+        let conv_id = Uuid::new_v4();
+
+        mock_chat
+            .expect_set_conversation_flags()
+            .with(
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(conv_id),
+                mockall::predicate::eq(None),
+                mockall::predicate::eq(Some(true)),
+            )
+            .times(1)
+            .return_once(|_, _, _, _| Ok(ConversationFlags { archived: false, pinned: true }));
+
+        let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
+        let flags = uc.update_conversation(user_id, conv_id, None, Some(true)).await.unwrap();
+
+        assert!(flags.pinned);
+        assert!(!flags.archived);
+    }
+
+    #[tokio::test]
+    async fn test_get_shared_conversation_unknown_token() {
+        let mut mock_chat = MockChatMessageRepository::new();
+        mock_chat
+            .expect_find_shared_conversation()
+            .times(1)
+            .return_once(|_| Ok(None));
+
+        let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
+        let result = uc.get_shared_conversation(Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_shared_conversation_loads_owner_history() {
+        let mut mock_chat = MockChatMessageRepository::new();
+        let user_id = Uuid::new_v4();
+        let conv_id = Uuid::new_v4();
+
+        mock_chat
+            .expect_find_shared_conversation()
+            .times(1)
+            .return_once(move |_| Ok(Some((user_id, conv_id))));
+        mock_chat
+            .expect_find_by_conversation()
+            .with(
+                mockall::predicate::eq(user_id),
+                mockall::predicate::eq(conv_id),
+                mockall::predicate::always(),
+            )
+            .times(1)
+            .return_once(move |_, _, _| {
+                // Private route context and tool output must not reach the link
+                let route = context_route(user_id, vec![context_point(55.75, 37.61, Some("Home"))]);
+                let mut context = ChatMessage::new_user_message(user_id, conv_id, format_route_context(&route));
+                context.role = "system".to_string();
+                let mut tool = ChatMessage::new_user_message(user_id, conv_id, "{\"routes\":[]}".to_string());
+                tool.role = "tool".to_string();
+                Ok(vec![
+                    context,
+                    ChatMessage::new_user_message(user_id, conv_id, "plan a hike".to_string()),
+                    tool,
+                    ChatMessage::new_assistant_message(user_id, conv_id, "sure".to_string(), None),
+                ])
+            });
+
+        let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
+        let messages = uc.get_shared_conversation(Uuid::new_v4()).await.unwrap();
+
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["user", "assistant"]);
+        assert_eq!(messages[0].content, "plan a hike");
+        assert!(messages.iter().all(|m| !m.content.contains("Home")));
+    }
+
+    #[tokio::test]
+    async fn test_update_conversation_requires_a_flag() {
+        let uc = make_usecase(MockChatMessageRepository::new(), MockRouteRepository::new(), false);
+        let result = uc.update_conversation(Uuid::new_v4(), Uuid::new_v4(), None, None).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
     // --- delete_conversation ---
 
     #[tokio::test]
@@ -1416,7 +2645,7 @@ mod tests {
         chat_repo: MockChatMessageRepository,
         route_repo: MockRouteRepository,
         nominatim_url: String,
-    ) -> ChatUseCase<MockChatMessageRepository, MockRouteRepository> {
+    ) -> ChatUseCase<MockChatMessageRepository, MockRouteRepository, MockSettingsRepository> {
         ChatUseCase::new(chat_repo, route_repo, None, nominatim_url, 5, 2000)
     }
 
@@ -1532,19 +2761,21 @@ mod tests {
     async fn test_delete_message_success() {
         let mut mock_chat = MockChatMessageRepository::new();
         let user_id = Uuid::new_v4();
+        let conversation_id = Uuid::new_v4();
         let message_id = Uuid::new_v4();
 
         mock_chat
             .expect_delete_message()
             .with(
                 mockall::predicate::eq(user_id),
+                mockall::predicate::eq(conversation_id),
                 mockall::predicate::eq(message_id),
             )
             .times(1)
-            .return_once(|_, _| Ok(()));
+            .return_once(|_, _, _| Ok(()));
 
         let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
-        let result = uc.delete_message(user_id, message_id).await;
+        let result = uc.delete_message(user_id, conversation_id, message_id).await;
 
         assert!(result.is_ok());
     }
@@ -1556,12 +2787,12 @@ mod tests {
         mock_chat
             .expect_delete_message()
             .times(1)
-            .return_once(|_, _| Err(RepositoryError::NotFound));
+            .return_once(|_, _, _| Err(RepositoryError::NotFound));
 
         let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
-        let result = uc.delete_message(Uuid::new_v4(), Uuid::new_v4()).await;
+        let result = uc.delete_message(Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()).await;
 
-        assert!(result.is_err());
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
     }
 
     // --- count_conversations ---
@@ -1573,12 +2804,12 @@ mod tests {
 
         mock_chat
             .expect_count_conversations()
-            .with(mockall::predicate::eq(user_id))
+            .with(mockall::predicate::eq(user_id), mockall::predicate::eq(false))
             .times(1)
-            .return_once(|_| Ok(0));
+            .return_once(|_, _| Ok(0));
 
         let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
-        let result = uc.count_conversations(user_id).await;
+        let result = uc.count_conversations(user_id, false).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 0);
@@ -1591,12 +2822,12 @@ mod tests {
 
         mock_chat
             .expect_count_conversations()
-            .with(mockall::predicate::eq(user_id))
+            .with(mockall::predicate::eq(user_id), mockall::predicate::eq(false))
             .times(1)
-            .return_once(|_| Ok(5));
+            .return_once(|_, _| Ok(5));
 
         let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
-        let result = uc.count_conversations(user_id).await;
+        let result = uc.count_conversations(user_id, false).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 5);
@@ -1606,7 +2837,7 @@ mod tests {
 
     #[test]
     fn test_max_message_length_from_config() {
-        let uc = ChatUseCase::new(
+        let uc: ChatUseCase<_, _, MockSettingsRepository> = ChatUseCase::new(
             MockChatMessageRepository::new(),
             MockRouteRepository::new(),
             None,
@@ -1617,6 +2848,38 @@ mod tests {
         assert_eq!(uc.max_message_length(), 500);
     }
 
+    // --- system prompt ---
+
+    fn prompt_source(template: Option<&'static str>) -> Arc<SettingsPromptSource<MockSettingsRepository>> {
+        let mut settings = MockSettingsRepository::new();
+        settings
+            .expect_get_value()
+            .returning(move |_| Ok(template.map(serde_json::Value::from)));
+        Arc::new(SettingsPromptSource::new(
+            SettingsUseCase::new(settings),
+            std::time::Duration::from_secs(60),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_system_prompt_renders_custom_template() {
+        let uc = make_usecase(MockChatMessageRepository::new(), MockRouteRepository::new(), false)
+            .with_system_prompt(
+                prompt_source(Some("You guide hikers for {app_name}.")),
+                PromptVars { app_name: "Trail Pal".to_string() },
+            );
+
+        assert_eq!(uc.system_prompt().await, "You guide hikers for Trail Pal.");
+    }
+
+    #[tokio::test]
+    async fn test_system_prompt_falls_back_to_default() {
+        let uc = make_usecase(MockChatMessageRepository::new(), MockRouteRepository::new(), false)
+            .with_system_prompt(prompt_source(None), PromptVars::default());
+
+        assert_eq!(uc.system_prompt().await, render_prompt(DEFAULT_SYSTEM_PROMPT, &PromptVars::default()));
+    }
+
     // --- ChatStreamEvent serialization ---
 
     #[test]
@@ -1642,4 +2905,130 @@ mod tests {
         assert_eq!(json["id"], id.to_string());
         assert_eq!(json["conversation_id"], conv_id.to_string());
     }
+
+    #[test]
+    fn test_chat_stream_event_tool_started_serialization() {
+        let event = ChatStreamEvent::ToolStarted {
+            name: "search_routes".to_string(),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "tool_started");
+        assert_eq!(json["name"], "search_routes");
+    }
+
+    // --- flagged conversations ---
+
+    fn flagged(review_consent: bool) -> FlaggedConversation {
+        FlaggedConversation {
+            user_id: Uuid::new_v4(),
+            conversation_id: Uuid::new_v4(),
+            reason: Some("abusive replies".to_string()),
+            review_consent,
+            message_count: 4,
+            flagged_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flag_conversation_drops_blank_reason() {
+        let mut mock_chat = MockChatMessageRepository::new();
+        mock_chat
+            .expect_flag_conversation()
+            .withf(|_, _, reason, consent| reason.is_none() && *consent)
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+
+        let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
+        uc.flag_conversation(Uuid::new_v4(), Uuid::new_v4(), Some("   ".to_string()), true)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_review_flagged_conversation_requires_consent() {
+        let mut mock_chat = MockChatMessageRepository::new();
+        mock_chat
+            .expect_find_flagged_conversation()
+            .times(1)
+            .return_once(|_, _| Ok(Some(flagged(false))));
+        mock_chat.expect_find_by_conversation().never();
+
+        let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
+        let result = uc.review_flagged_conversation(Uuid::new_v4(), Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_review_flagged_conversation_returns_messages() {
+        let mut mock_chat = MockChatMessageRepository::new();
+        mock_chat
+            .expect_find_flagged_conversation()
+            .return_once(|_, _| Ok(Some(flagged(true))));
+        mock_chat
+            .expect_find_by_conversation()
+            .return_once(|user_id, conversation_id, _| {
+                Ok(vec![ChatMessage::new_user_message(user_id, conversation_id, "hi".to_string())])
+            });
+
+        let uc = make_usecase(mock_chat, MockRouteRepository::new(), false);
+        let messages = uc.review_flagged_conversation(Uuid::new_v4(), Uuid::new_v4()).await.unwrap();
+
+        assert_eq!(messages.len(), 1);
+    }
+
+    // --- stream_turn ---
+
+    #[tokio::test]
+    async fn test_stream_turn_reports_tool_progress_before_tokens() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/chat/completions"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{ "message": { "role": "assistant", "content": null, "tool_calls": [{
+                    "id": "call_1",
+                    "type": "function",
+                    "function": { "name": "lookup", "arguments": "{}" }
+                }] } }]
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/chat/completions"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{ "message": { "role": "assistant", "content": "Found it" } }]
+            })))
+            .mount(&server)
+            .await;
+
+        let mut chat_repo = MockChatMessageRepository::new();
+        chat_repo.expect_create().times(2).returning(|_| Ok(()));
+        chat_repo.expect_find_by_conversation().returning(|_, _, _| Ok(vec![]));
+        chat_repo
+            .expect_record_turn()
+            .withf(|turn| turn.tool_calls == ["lookup"] && turn.error.is_none())
+            .times(1)
+            .returning(|_| Ok(()));
+        let uc: ChatUseCase<_, _, MockSettingsRepository> = ChatUseCase::new(
+            chat_repo,
+            MockRouteRepository::new(),
+            Some(OpenAIClient::new(server.uri(), "test-model".to_string(), "key".to_string())),
+            server.uri(),
+            5,
+            2000,
+        );
+
+        let turn = uc
+            .prepare_turn(Uuid::new_v4(), Uuid::new_v4(), "hi".to_string(), None, vec![], None)
+            .await
+            .unwrap();
+        let events: Vec<ChatStreamEvent> = uc.stream_turn(turn).map(Result::unwrap).collect().await;
+        let types: Vec<String> = events
+            .iter()
+            .map(|e| serde_json::to_value(e).unwrap()["type"].as_str().unwrap().to_string())
+            .collect();
+
+        assert_eq!(types, ["tool_started", "tool_finished", "token", "token", "done"]);
+    }
 }
@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::usecase::contracts::ObjectStorage;
+use crate::usecase::error::UsecaseError;
+
+/// Image types vision models accept.
+const ALLOWED_CONTENT_TYPES: &[&str] = &["image/jpeg", "image/png", "image/webp", "image/gif"];
+pub const MAX_ATTACHMENTS_PER_MESSAGE: usize = 3;
+
+#[derive(Debug, Serialize)]
+pub struct AttachmentUpload {
+    /// Sent back in `SendMessageRequest.attachments` once the upload is done.
+    pub key: String,
+    pub upload_url: String,
+    pub expires_in_secs: u64,
+}
+
+/// Images attached to chat messages. Clients upload them straight to object
+/// storage through presigned links; the assistant gets short-lived download
+/// links to the same objects.
+pub struct ChatAttachmentsUseCase<S>
+where
+    S: ObjectStorage,
+{
+    storage: S,
+    upload_ttl: Duration,
+    download_ttl: Duration,
+}
+
+impl<S> ChatAttachmentsUseCase<S>
+where
+    S: ObjectStorage,
+{
+    pub fn new(storage: S, upload_ttl: Duration, download_ttl: Duration) -> Self {
+        Self {
+            storage,
+            upload_ttl,
+            download_ttl,
+        }
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn create_upload(
+        &self,
+        user_id: Uuid,
+        content_type: &str,
+    ) -> Result<AttachmentUpload, UsecaseError> {
+        if !ALLOWED_CONTENT_TYPES.contains(&content_type) {
+            return Err(UsecaseError::Validation(format!(
+                "Unsupported attachment type '{}'; allowed: {}",
+                content_type,
+                ALLOWED_CONTENT_TYPES.join(", ")
+            )));
+        }
+
+        let key = format!("{}/{}", user_id, Uuid::new_v4());
+        let upload_url = self
+            .storage
+            .presigned_put_url(&key, content_type, self.upload_ttl)
+            .await?;
+
+        metrics::counter!("chat_attachment_uploads_total").increment(1);
+        tracing::info!(%key, "chat attachment upload link created");
+        Ok(AttachmentUpload {
+            key,
+            upload_url,
+            expires_in_secs: self.upload_ttl.as_secs(),
+        })
+    }
+
+    /// Turns attachment keys into download links for the model. Keys must
+    /// have been issued to this user by [`Self::create_upload`].
+    #[tracing::instrument(skip(self, keys), fields(user_id = %user_id, count = keys.len()))]
+    pub async fn resolve(&self, user_id: Uuid, keys: &[String]) -> Result<Vec<String>, UsecaseError> {
+        if keys.len() > MAX_ATTACHMENTS_PER_MESSAGE {
+            return Err(UsecaseError::Validation(format!(
+                "At most {} attachments per message",
+                MAX_ATTACHMENTS_PER_MESSAGE
+            )));
+        }
+
+        let mut urls = Vec::with_capacity(keys.len());
+        for key in keys {
+            if !is_owned_key(user_id, key) {
+                tracing::warn!(%key, "attachment key not issued to user");
+                return Err(UsecaseError::NotFound("Attachment".to_string()));
+            }
+            urls.push(self.storage.presigned_get_url(key, self.download_ttl).await?);
+        }
+        Ok(urls)
+    }
+}
+
+/// Keys have the form `{user_id}/{uuid}`; anything else was not issued by us.
+fn is_owned_key(user_id: Uuid, key: &str) -> bool {
+    key.split_once('/')
+        .is_some_and(|(owner, id)| owner == user_id.to_string() && Uuid::parse_str(id).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecase::contracts::MockObjectStorage;
+
+    fn usecase(storage: MockObjectStorage) -> ChatAttachmentsUseCase<MockObjectStorage> {
+        ChatAttachmentsUseCase::new(storage, Duration::from_secs(300), Duration::from_secs(600))
+    }
+
+    #[tokio::test]
+    async fn test_create_upload_scopes_key_to_user() {
+        let user_id = Uuid::new_v4();
+        let mut storage = MockObjectStorage::new();
+        storage
+            .expect_presigned_put_url()
+            .withf(move |key, content_type, _| {
+                key.starts_with(&format!("{}/", user_id)) && content_type == "image/jpeg"
+            })
+            .times(1)
+            .returning(|key, _, _| Ok(format!("https://minio.local/{}?sig", key)));
+
+        let upload = usecase(storage).create_upload(user_id, "image/jpeg").await.unwrap();
+
+        assert!(is_owned_key(user_id, &upload.key));
+        assert!(upload.upload_url.contains(&upload.key));
+        assert_eq!(upload.expires_in_secs, 300);
+    }
+
+    #[tokio::test]
+    async fn test_create_upload_rejects_non_images() {
+        let result = usecase(MockObjectStorage::new())
+            .create_upload(Uuid::new_v4(), "application/pdf")
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_rejects_foreign_keys() {
+        let foreign_key = format!("{}/{}", Uuid::new_v4(), Uuid::new_v4());
+
+        // No presigning: the key is rejected first.
+        let result = usecase(MockObjectStorage::new())
+            .resolve(Uuid::new_v4(), &[foreign_key])
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_presigns_own_keys() {
+        let user_id = Uuid::new_v4();
+        let key = format!("{}/{}", user_id, Uuid::new_v4());
+        let mut storage = MockObjectStorage::new();
+        storage
+            .expect_presigned_get_url()
+            .times(1)
+            .returning(|key, ttl| Ok(format!("https://minio.local/{}?ttl={}", key, ttl.as_secs())));
+
+        let urls = usecase(storage).resolve(user_id, std::slice::from_ref(&key)).await.unwrap();
+
+        assert_eq!(urls, vec![format!("https://minio.local/{}?ttl=600", key)]);
+    }
+}
@@ -0,0 +1,58 @@
+//! Texts the chat returns on its own rather than from the model, in each
+//! [`ChatLanguage`]. English is used when the user has no preference.
+
+use crate::domain::chat_message::ChatLanguage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CannedText {
+    AssistantUnavailable,
+    RateLimited,
+    ImagesUnsupported,
+    /// Attached to answers produced by the fallback model.
+    FallbackNotice,
+}
+
+impl CannedText {
+    pub fn localized(self, language: Option<ChatLanguage>) -> &'static str {
+        match (self, language.unwrap_or(ChatLanguage::En)) {
+            (CannedText::AssistantUnavailable, ChatLanguage::En) => "AI assistant is currently unavailable",
+            (CannedText::AssistantUnavailable, ChatLanguage::Ru) => "ИИ-ассистент сейчас недоступен",
+            (CannedText::RateLimited, ChatLanguage::En) => "Too many requests. Please try again later.",
+            (CannedText::RateLimited, ChatLanguage::Ru) => "Слишком много запросов. Попробуйте позже.",
+            (CannedText::ImagesUnsupported, ChatLanguage::En) => "The assistant cannot read images",
+            (CannedText::ImagesUnsupported, ChatLanguage::Ru) => "Ассистент не умеет распознавать изображения",
+            (CannedText::FallbackNotice, ChatLanguage::En) => {
+                "The assistant is running in degraded mode; this answer comes from a backup model and may be less accurate."
+            }
+            (CannedText::FallbackNotice, ChatLanguage::Ru) => {
+                "Ассистент работает в ограниченном режиме: ответ получен от резервной модели и может быть менее точным."
+            }
+        }
+    }
+}
+
+/// System message pinning the reply language.
+pub fn language_instruction(language: ChatLanguage) -> String {
+    format!(
+        "The user has chosen {} as their language. Always reply in {}, even if they write in another language.",
+        language.name(),
+        language.name()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_is_the_default() {
+        assert_eq!(
+            CannedText::AssistantUnavailable.localized(None),
+            CannedText::AssistantUnavailable.localized(Some(ChatLanguage::En))
+        );
+        assert_ne!(
+            CannedText::RateLimited.localized(Some(ChatLanguage::Ru)),
+            CannedText::RateLimited.localized(None)
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! The chat system prompt. Operators can replace the built-in prompt through
+//! the admin settings; the template is re-read from settings at most once per
+//! cache period, so edits reach every replica without a restart.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::usecase::contracts::SettingsRepository;
+use crate::usecase::settings::SettingsUseCase;
+
+pub const DEFAULT_APP_NAME: &str = "Guide Helper";
+
+/// Placeholders a template may use, written as `{name}`.
+pub const PROMPT_VARIABLES: &[&str] = &["app_name", "date"];
+
+pub const MAX_PROMPT_LENGTH: usize = 16_000;
+
+pub const DEFAULT_SYSTEM_PROMPT: &str = r#"You are a helpful route planning assistant for the {app_name} application.
+You help users find routes, plan trips, search the route catalog, and answer questions about places.
+Always respond in the same language the user writes in.
+
+You have access to these tools:
+- geocode: Look up coordinates for any place name or address. Calling this tool automatically displays the location as a marker on the interactive map.
+- search_routes: Search the route catalog for shared routes by text query, category, or sort order.
+- get_route_details: Get detailed information about a specific route by its ID.
+- navigate: Open a specific page in the application (map, profile/settings, route catalog, admin panel).
+- add_point_to_route: Add a point to one of the user's own routes.
+- remove_point_from_route: Remove a point from one of the user's own routes.
+
+Rules for tool usage — follow these strictly:
+1. When the user asks to SHOW, FIND, MARK, or DISPLAY a location — call geocode for that location.
+2. When the user asks to BUILD A ROUTE, PLAN A TRIP, or GO FROM one place TO another — call geocode for EACH location mentioned (start, end, and any waypoints), then describe the route between them. Do NOT give text-only directions; always geocode the places first.
+3. When the user asks to search or browse routes in the catalog — use search_routes.
+4. When the user mentions a specific route ID — use get_route_details.
+5. NEVER say you cannot display maps or show locations on the map. You CAN show locations by calling the geocode tool — it will place markers on the map automatically.
+6. If the user names two or more places, call geocode separately for each one.
+7. When the user says anything that means OPENING or NAVIGATING to a section of this app — ALWAYS call navigate immediately without asking for clarification. Do not ask "what do you want to configure" — just navigate.
+8. Route edits need the user's confirmation: after calling add_point_to_route or remove_point_from_route, tell the user what will change and that they can confirm it in the app. Never claim the change has already been applied.
+
+Page mapping (use navigate tool with these paths):
+- /profile → when user says: "открой профиль", "профиль", "настройки", "открой настройки", "open settings", "go to profile", "мои настройки", "мой профиль", "settings", "profile"
+- /map → when user says: "открой карту", "перейди на карту", "на карту", "go to map", "open map", "карта"
+- /explore → when user says: "открой каталог", "каталог маршрутов", "explore", "посмотреть маршруты", "open catalog", "route catalog"
+- /admin → when user says: "открой админку", "панель администратора", "admin", "admin panel", "администрирование"
+
+CRITICAL: For ALL navigation requests, call navigate IMMEDIATELY. Never ask clarifying questions about navigation. If the user wants to open any page or section, use the navigate tool.
+
+Be concise and helpful. After calling tools, summarize the results naturally."#;
+
+/// Values substituted into the prompt template.
+#[derive(Debug, Clone)]
+pub struct PromptVars {
+    pub app_name: String,
+}
+
+impl Default for PromptVars {
+    fn default() -> Self {
+        Self {
+            app_name: DEFAULT_APP_NAME.to_string(),
+        }
+    }
+}
+
+/// Substitutes [`PROMPT_VARIABLES`]; `{date}` is today's UTC date.
+pub fn render_prompt(template: &str, vars: &PromptVars) -> String {
+    template
+        .replace("{app_name}", &vars.app_name)
+        .replace("{date}", &chrono::Utc::now().format("%Y-%m-%d").to_string())
+}
+
+/// Rejects empty or oversized templates and unknown `{placeholders}`, so a
+/// typo does not end up verbatim in every conversation.
+pub fn validate_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("Prompt must not be empty".to_string());
+    }
+    if template.chars().count() > MAX_PROMPT_LENGTH {
+        return Err(format!("Prompt must be at most {} characters", MAX_PROMPT_LENGTH));
+    }
+
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let after = &rest[open + 1..];
+        let Some(close) = after.find('}') else { break };
+        let name = &after[..close];
+        if !name.is_empty()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !PROMPT_VARIABLES.contains(&name)
+        {
+            return Err(format!(
+                "Unknown prompt variable '{{{}}}'; available: {}",
+                name,
+                PROMPT_VARIABLES.iter().map(|v| format!("{{{}}}", v)).collect::<Vec<_>>().join(", ")
+            ));
+        }
+        rest = &after[close + 1..];
+    }
+    Ok(())
+}
+
+/// Reads the template from [`SettingsUseCase`] and keeps it for `cache_ttl`.
+/// If settings cannot be read the last known template (or the default) is
+/// used, so a database hiccup never breaks chat.
+pub struct SettingsPromptSource<R: SettingsRepository> {
+    settings: SettingsUseCase<R>,
+    cache_ttl: Duration,
+    cached: Mutex<Option<(Instant, Option<String>)>>,
+}
+
+impl<R: SettingsRepository> SettingsPromptSource<R> {
+    pub fn new(settings: SettingsUseCase<R>, cache_ttl: Duration) -> Self {
+        Self {
+            settings,
+            cache_ttl,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Drops the cached template; call after changing it on this replica.
+    pub fn invalidate(&self) {
+        *self.cached.lock().unwrap() = None;
+    }
+
+    /// The custom template, or `None` to use [`DEFAULT_SYSTEM_PROMPT`].
+    pub async fn template(&self) -> Option<String> {
+        let fresh = self
+            .cached
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.cache_ttl)
+            .map(|(_, template)| template.clone());
+        if let Some(template) = fresh {
+            return template;
+        }
+
+        let template = match self.settings.get_chat_system_prompt().await {
+            Ok(template) => template,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to load chat system prompt, keeping previous one");
+                return self.cached.lock().unwrap().as_ref().and_then(|(_, t)| t.clone());
+            }
+        };
+
+        *self.cached.lock().unwrap() = Some((Instant::now(), template.clone()));
+        template
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::errors::RepositoryError;
+    use crate::usecase::contracts::MockSettingsRepository;
+
+    #[test]
+    fn test_default_prompt_is_a_valid_template() {
+        assert!(validate_template(DEFAULT_SYSTEM_PROMPT).is_ok());
+        assert!(render_prompt(DEFAULT_SYSTEM_PROMPT, &PromptVars::default()).contains("Guide Helper"));
+    }
+
+    #[test]
+    fn test_validate_template_rejects_unknown_variables() {
+        assert!(validate_template("You work for {app_name} on {date}.").is_ok());
+        assert!(validate_template("Reply as JSON: {\"a\": 1}").is_ok());
+        assert!(validate_template("You work for {company}.").is_err());
+        assert!(validate_template("   ").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_settings_source_caches_and_survives_errors() {
+        let mut repo = MockSettingsRepository::new();
+        let mut seq = mockall::Sequence::new();
+        repo.expect_get_value()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(Some(serde_json::json!("Be brief."))));
+        repo.expect_get_value()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Err(RepositoryError::DatabaseError("down".to_string())));
+
+        let source = SettingsPromptSource::new(SettingsUseCase::new(repo), Duration::from_secs(60));
+        assert_eq!(source.template().await.as_deref(), Some("Be brief."));
+        // Served from cache; no second read.
+        assert_eq!(source.template().await.as_deref(), Some("Be brief."));
+
+        source.invalidate();
+        assert_eq!(source.template().await, None);
+    }
+}
@@ -0,0 +1,211 @@
+//! Per-upstream circuit breaker. After `failure_threshold` consecutive
+//! failures the breaker opens and calls fail fast for `open_duration`; then a
+//! single trial call is let through, and its outcome closes or re-opens it.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreakerSettings {
+    /// Consecutive failures that open the breaker.
+    pub failure_threshold: u32,
+    /// How long calls fail fast before a trial call is allowed.
+    pub open_duration: Duration,
+}
+
+impl Default for BreakerSettings {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("{0} is temporarily unavailable (circuit open)")]
+pub struct CircuitOpen(pub &'static str);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    /// A trial call is in flight. If it never reports back (e.g. the caller
+    /// was cancelled), another trial is allowed once `open_duration` passes.
+    HalfOpen { since: Instant },
+}
+
+impl State {
+    /// Exported as the `circuit_breaker_state` gauge.
+    fn gauge_value(&self) -> f64 {
+        match self {
+            State::Closed { .. } => 0.0,
+            State::HalfOpen { .. } => 1.0,
+            State::Open { .. } => 2.0,
+        }
+    }
+}
+
+/// Cheap to clone; clones share state, so every copy of a client trips the
+/// same breaker.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    name: &'static str,
+    settings: BreakerSettings,
+    state: Arc<Mutex<State>>,
+}
+
+impl CircuitBreaker {
+    /// `name` identifies the upstream in logs and the `upstream` metric label.
+    pub fn new(name: &'static str, settings: BreakerSettings) -> Self {
+        let breaker = Self {
+            name,
+            settings,
+            state: Arc::new(Mutex::new(State::Closed { failures: 0 })),
+        };
+        breaker.export(&State::Closed { failures: 0 });
+        breaker
+    }
+
+    /// Checks whether a call may go ahead. Callers must report its outcome
+    /// with [`record_success`](Self::record_success) or
+    /// [`record_failure`](Self::record_failure).
+    pub fn check(&self) -> Result<(), CircuitOpen> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        match *state {
+            State::Closed { .. } => Ok(()),
+            State::Open { until } if now >= until => {
+                tracing::info!(upstream = self.name, "circuit half-open, sending a trial request");
+                *state = State::HalfOpen { since: now };
+                self.export(&state);
+                Ok(())
+            }
+            State::HalfOpen { since } if now.duration_since(since) >= self.settings.open_duration => {
+                *state = State::HalfOpen { since: now };
+                Ok(())
+            }
+            State::Open { .. } | State::HalfOpen { .. } => {
+                metrics::counter!("circuit_breaker_rejected_total", "upstream" => self.name).increment(1);
+                Err(CircuitOpen(self.name))
+            }
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        if !matches!(*state, State::Closed { .. }) {
+            tracing::info!(upstream = self.name, "circuit closed");
+        }
+        *state = State::Closed { failures: 0 };
+        self.export(&state);
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        let failures = match *state {
+            State::Closed { failures } => failures + 1,
+            State::HalfOpen { .. } => self.settings.failure_threshold,
+            // A call admitted before the breaker opened; keep the current window.
+            State::Open { .. } => return,
+        };
+
+        *state = if failures >= self.settings.failure_threshold {
+            tracing::warn!(upstream = self.name, failures, open_for = ?self.settings.open_duration, "circuit opened");
+            metrics::counter!("circuit_breaker_opened_total", "upstream" => self.name).increment(1);
+            State::Open { until: Instant::now() + self.settings.open_duration }
+        } else {
+            State::Closed { failures }
+        };
+        self.export(&state);
+    }
+
+    /// Records a transport error (timeouts included), a 5xx or a 429 as a
+    /// failure and any other response as a success.
+    pub fn record_response(&self, response: &reqwest::Result<reqwest::Response>) {
+        match response {
+            Ok(r) if !r.status().is_server_error() && r.status() != reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                self.record_success()
+            }
+            _ => self.record_failure(),
+        }
+    }
+
+    fn export(&self, state: &State) {
+        metrics::gauge!("circuit_breaker_state", "upstream" => self.name).set(state.gauge_value());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(
+            "test",
+            BreakerSettings { failure_threshold: 2, open_duration: Duration::from_millis(20) },
+        )
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breaker = breaker();
+
+        breaker.record_failure();
+        assert!(breaker.check().is_ok());
+        breaker.record_failure();
+
+        assert!(breaker.check().is_err());
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let breaker = breaker();
+
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn test_half_open_allows_one_trial_and_recovers() {
+        let breaker = breaker();
+        breaker.record_failure();
+        breaker.record_failure();
+
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(breaker.check().is_ok());
+        assert!(breaker.check().is_err());
+
+        breaker.record_success();
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn test_failed_trial_reopens() {
+        let breaker = breaker();
+        breaker.record_failure();
+        breaker.record_failure();
+
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(breaker.check().is_ok());
+        breaker.record_failure();
+
+        assert!(breaker.check().is_err());
+    }
+
+    #[test]
+    fn test_clones_share_state() {
+        let breaker = breaker();
+        let clone = breaker.clone();
+
+        breaker.record_failure();
+        breaker.record_failure();
+
+        assert!(clone.check().is_err());
+    }
+}
@@ -0,0 +1,248 @@
+//! Route collections: named folders the owner groups their own routes in.
+//! The route list can be narrowed to one of them.
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::collection::RouteCollection;
+use crate::repository::errors::RepositoryError;
+use crate::usecase::contracts::{RouteCollectionRepository, RouteRepository};
+use crate::usecase::error::UsecaseError;
+use crate::usecase::sanitize::TextSanitizer;
+
+/// Collections one user can keep.
+pub const MAX_COLLECTIONS: usize = 100;
+const MAX_NAME_CHARS: usize = 100;
+
+fn not_found(e: RepositoryError) -> UsecaseError {
+    match e {
+        RepositoryError::NotFound => UsecaseError::NotFound("Collection".to_string()),
+        e => e.into(),
+    }
+}
+
+pub struct CollectionsUseCase<C, R>
+where
+    C: RouteCollectionRepository,
+    R: RouteRepository,
+{
+    collection_repository: C,
+    route_repository: R,
+    sanitizer: TextSanitizer,
+}
+
+impl<C, R> CollectionsUseCase<C, R>
+where
+    C: RouteCollectionRepository,
+    R: RouteRepository,
+{
+    pub fn new(collection_repository: C, route_repository: R) -> Self {
+        Self {
+            collection_repository,
+            route_repository,
+            sanitizer: TextSanitizer::default(),
+        }
+    }
+
+    pub fn with_sanitizer(mut self, sanitizer: TextSanitizer) -> Self {
+        self.sanitizer = sanitizer;
+        self
+    }
+
+    /// Cleaned name, unique among the user's other collections regardless of
+    /// case.
+    async fn validate_name(&self, user_id: Uuid, name: &str, except: Option<Uuid>) -> Result<String, UsecaseError> {
+        let name = self.sanitizer.single_line(name);
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(UsecaseError::Validation(format!(
+                "Collection name must be between 1 and {} characters",
+                MAX_NAME_CHARS
+            )));
+        }
+
+        let existing = self.collection_repository.find_by_user_id(user_id).await?;
+        let taken = existing
+            .iter()
+            .any(|c| Some(c.id) != except && c.name.to_lowercase() == name.to_lowercase());
+        if taken {
+            return Err(UsecaseError::Validation(format!("A collection named \"{}\" already exists", name)));
+        }
+        if except.is_none() && existing.len() >= MAX_COLLECTIONS {
+            tracing::warn!(count = existing.len(), "collection limit reached");
+            return Err(UsecaseError::Validation(format!(
+                "At most {} collections are allowed",
+                MAX_COLLECTIONS
+            )));
+        }
+        Ok(name)
+    }
+
+    async fn find_owned(&self, user_id: Uuid, id: Uuid) -> Result<RouteCollection, UsecaseError> {
+        self.collection_repository
+            .find_by_id(id)
+            .await?
+            .filter(|c| c.user_id == user_id)
+            .ok_or_else(|| UsecaseError::NotFound("Collection".to_string()))
+    }
+
+    #[tracing::instrument(skip(self, name), fields(user_id = %user_id))]
+    pub async fn create_collection(&self, user_id: Uuid, name: &str) -> Result<RouteCollection, UsecaseError> {
+        let name = self.validate_name(user_id, name, None).await?;
+
+        let collection = RouteCollection::new(user_id, name);
+        self.collection_repository.create(&collection).await?;
+
+        tracing::info!(collection_id = %collection.id, "collection created");
+        Ok(collection)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn list_collections(&self, user_id: Uuid) -> Result<Vec<RouteCollection>, UsecaseError> {
+        let collections = self.collection_repository.find_by_user_id(user_id).await?;
+
+        tracing::debug!(count = collections.len(), "retrieved collections");
+        Ok(collections)
+    }
+
+    #[tracing::instrument(skip(self, name), fields(user_id = %user_id, collection_id = %id))]
+    pub async fn rename_collection(&self, user_id: Uuid, id: Uuid, name: &str) -> Result<RouteCollection, UsecaseError> {
+        let mut collection = self.find_owned(user_id, id).await?;
+        let name = self.validate_name(user_id, name, Some(id)).await?;
+
+        let now = Utc::now();
+        self.collection_repository
+            .rename(id, user_id, &name, now)
+            .await
+            .map_err(not_found)?;
+
+        collection.name = name;
+        collection.updated_at = now;
+        tracing::info!("collection renamed");
+        Ok(collection)
+    }
+
+    /// The routes in the collection are kept.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, collection_id = %id))]
+    pub async fn delete_collection(&self, user_id: Uuid, id: Uuid) -> Result<(), UsecaseError> {
+        self.collection_repository.delete(id, user_id).await.map_err(not_found)?;
+
+        tracing::info!("collection deleted");
+        Ok(())
+    }
+
+    /// Both the collection and the route must belong to the user.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, collection_id = %id, route_id = %route_id))]
+    pub async fn add_route(&self, user_id: Uuid, id: Uuid, route_id: Uuid) -> Result<(), UsecaseError> {
+        self.find_owned(user_id, id).await?;
+        self.route_repository
+            .find_by_id(route_id)
+            .await?
+            .filter(|r| r.user_id == user_id)
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+
+        self.collection_repository.add_route(id, route_id, Utc::now()).await?;
+
+        tracing::info!("route added to collection");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, collection_id = %id, route_id = %route_id))]
+    pub async fn remove_route(&self, user_id: Uuid, id: Uuid, route_id: Uuid) -> Result<(), UsecaseError> {
+        self.find_owned(user_id, id).await?;
+        self.collection_repository.remove_route(id, route_id).await.map_err(|e| match e {
+            RepositoryError::NotFound => UsecaseError::NotFound("Route in collection".to_string()),
+            e => e.into(),
+        })?;
+
+        tracing::info!("route removed from collection");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::route::Route;
+    use crate::usecase::contracts::{MockRouteCollectionRepository, MockRouteRepository};
+
+    fn collection(user_id: Uuid, name: &str) -> RouteCollection {
+        RouteCollection::new(user_id, name.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_create_collection_rejects_blank_and_duplicate_names() {
+        let user_id = Uuid::new_v4();
+        let existing = vec![collection(user_id, "Kazan")];
+        let mut collections = MockRouteCollectionRepository::new();
+        collections.expect_find_by_user_id().returning(move |_| Ok(existing.clone()));
+        collections
+            .expect_create()
+            .withf(|c| c.name == "Saint Petersburg")
+            .times(1)
+            .returning(|_| Ok(()));
+        let usecase = CollectionsUseCase::new(collections, MockRouteRepository::new());
+
+        assert!(matches!(usecase.create_collection(user_id, "  ").await, Err(UsecaseError::Validation(_))));
+        assert!(matches!(usecase.create_collection(user_id, " kazan ").await, Err(UsecaseError::Validation(_))));
+        let created = usecase.create_collection(user_id, " Saint\nPetersburg ").await.unwrap();
+        assert_eq!(created.name, "Saint Petersburg");
+        assert_eq!(created.route_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rename_collection_may_keep_its_own_name() {
+        let user_id = Uuid::new_v4();
+        let kazan = collection(user_id, "Kazan");
+        let stored = vec![kazan.clone(), collection(user_id, "Moscow")];
+        let mut collections = MockRouteCollectionRepository::new();
+        let found = kazan.clone();
+        collections.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+        collections.expect_find_by_user_id().returning(move |_| Ok(stored.clone()));
+        collections
+            .expect_rename()
+            .withf(|_, _, name, _| name == "KAZAN")
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+        let usecase = CollectionsUseCase::new(collections, MockRouteRepository::new());
+
+        assert!(matches!(usecase.rename_collection(user_id, kazan.id, "moscow").await, Err(UsecaseError::Validation(_))));
+        assert!(matches!(
+            usecase.rename_collection(Uuid::new_v4(), kazan.id, "Kazan 2").await,
+            Err(UsecaseError::NotFound(_))
+        ));
+        assert_eq!(usecase.rename_collection(user_id, kazan.id, "KAZAN").await.unwrap().name, "KAZAN");
+    }
+
+    #[tokio::test]
+    async fn test_add_route_requires_owning_both() {
+        let user_id = Uuid::new_v4();
+        let kazan = collection(user_id, "Kazan");
+        let own = Route::new(user_id, "Kremlin walk".to_string(), vec![], vec![], vec![]);
+        let foreign = Route::new(Uuid::new_v4(), "Lake loop".to_string(), vec![], vec![], vec![]);
+        let own_id = own.id;
+        let mut collections = MockRouteCollectionRepository::new();
+        let found = kazan.clone();
+        collections.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+        collections
+            .expect_add_route()
+            .withf(move |_, route_id, _| *route_id == own_id)
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        let mut routes = MockRouteRepository::new();
+        let stored = [own.clone(), foreign.clone()];
+        routes
+            .expect_find_by_id()
+            .returning(move |id| Ok(stored.iter().find(|r| r.id == id).cloned()));
+        let usecase = CollectionsUseCase::new(collections, routes);
+
+        assert!(matches!(
+            usecase.add_route(user_id, kazan.id, foreign.id).await,
+            Err(UsecaseError::NotFound(_))
+        ));
+        assert!(matches!(
+            usecase.add_route(foreign.user_id, kazan.id, foreign.id).await,
+            Err(UsecaseError::NotFound(_))
+        ));
+        usecase.add_route(user_id, kazan.id, own.id).await.unwrap();
+    }
+}
@@ -1,30 +1,47 @@
 use uuid::Uuid;
 
-use crate::domain::comment::Comment;
-use crate::usecase::contracts::{CommentRepository, RouteRepository};
+use crate::domain::comment::{
+    Comment, COMMENT_POLICIES, COMMENT_POLICY_LOCKED, COMMENT_POLICY_PRE_MODERATED,
+};
+use crate::usecase::blocks::ensure_not_blocked;
+use crate::usecase::contracts::{BlockRepository, CommentRepository, RouteRepository};
 use crate::usecase::error::UsecaseError;
+use crate::usecase::sanitize::TextSanitizer;
 
-pub struct CommentsUseCase<C, R>
+const MAX_SEARCH_QUERY_LENGTH: usize = 200;
+
+pub struct CommentsUseCase<C, R, B>
 where
     C: CommentRepository,
     R: RouteRepository,
+    B: BlockRepository,
 {
     comment_repository: C,
     route_repository: R,
+    block_repository: B,
+    sanitizer: TextSanitizer,
 }
 
-impl<C, R> CommentsUseCase<C, R>
+impl<C, R, B> CommentsUseCase<C, R, B>
 where
     C: CommentRepository,
     R: RouteRepository,
+    B: BlockRepository,
 {
-    pub fn new(comment_repository: C, route_repository: R) -> Self {
+    pub fn new(comment_repository: C, route_repository: R, block_repository: B) -> Self {
         Self {
             comment_repository,
             route_repository,
+            block_repository,
+            sanitizer: TextSanitizer::default(),
         }
     }
 
+    pub fn with_sanitizer(mut self, sanitizer: TextSanitizer) -> Self {
+        self.sanitizer = sanitizer;
+        self
+    }
+
     pub fn comment_repository(&self) -> &C {
         &self.comment_repository
     }
@@ -39,19 +56,121 @@ where
     ) -> Result<Comment, UsecaseError> {
         tracing::debug!("creating comment");
 
-        // Verify route exists
-        self.route_repository
+        let author_name = self.sanitizer.single_line(&author_name);
+        let text = self.sanitizer.multi_line(&text);
+        if author_name.is_empty() || text.is_empty() {
+            return Err(UsecaseError::Validation(
+                "Comment text and author name must not be empty".to_string(),
+            ));
+        }
+
+        let route = self
+            .route_repository
             .find_by_id(route_id)
             .await?
             .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+        ensure_not_blocked(&self.block_repository, route.user_id, user_id).await?;
+
+        // The owner's own comments bypass the route's policy
+        let approved = if route.user_id == user_id {
+            true
+        } else {
+            match self.get_comment_policy(route_id).await?.as_str() {
+                COMMENT_POLICY_LOCKED => {
+                    tracing::warn!(route_id = %route_id, "comment on locked route rejected");
+                    return Err(UsecaseError::Forbidden("Comments are locked on this route".to_string()));
+                }
+                COMMENT_POLICY_PRE_MODERATED => false,
+                _ => true,
+            }
+        };
 
-        let comment = Comment::new(route_id, user_id, author_name, text);
+        let comment = Comment::new(route_id, user_id, author_name, text).with_approved(approved);
         self.comment_repository.create(&comment).await?;
+        metrics::counter!("comments_created_total").increment(1);
 
-        tracing::info!(comment_id = %comment.id, route_id = %route_id, "comment created successfully");
+        tracing::info!(comment_id = %comment.id, route_id = %route_id, approved, "comment created successfully");
         Ok(comment)
     }
 
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    pub async fn get_comment_policy(&self, route_id: Uuid) -> Result<String, UsecaseError> {
+        self.route_repository
+            .find_comment_policy(route_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))
+    }
+
+    /// Changes who can comment on the route. Comments already awaiting
+    /// approval stay pending when the route is opened again.
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
+    pub async fn set_comment_policy(&self, route_id: Uuid, user_id: Uuid, policy: &str) -> Result<(), UsecaseError> {
+        if !COMMENT_POLICIES.contains(&policy) {
+            return Err(UsecaseError::Validation(format!(
+                "Invalid comment policy '{}', expected one of: {}",
+                policy,
+                COMMENT_POLICIES.join(", ")
+            )));
+        }
+
+        self.require_route_owner(route_id, user_id).await?;
+        self.route_repository.set_comment_policy(route_id, policy).await?;
+
+        tracing::info!(route_id = %route_id, policy, "comment policy updated");
+        Ok(())
+    }
+
+    /// Comments awaiting approval; only the route owner sees them.
+    #[tracing::instrument(skip(self), fields(route_id = %route_id, user_id = %user_id))]
+    pub async fn list_pending_comments(&self, route_id: Uuid, user_id: Uuid) -> Result<Vec<Comment>, UsecaseError> {
+        self.require_route_owner(route_id, user_id).await?;
+
+        let comments = self.comment_repository.find_pending_by_route_id(route_id).await?;
+
+        tracing::debug!(route_id = %route_id, count = comments.len(), "retrieved pending comments");
+        Ok(comments)
+    }
+
+    /// Publishes a pending comment. Allowed to the route owner and to admins
+    /// and moderators; approving an approved comment is a no-op.
+    #[tracing::instrument(skip(self), fields(comment_id = %comment_id, user_id = %user_id, %role))]
+    pub async fn approve_comment(&self, comment_id: Uuid, user_id: Uuid, role: &str) -> Result<Comment, UsecaseError> {
+        let comment = self
+            .comment_repository
+            .find_by_id(comment_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Comment".to_string()))?;
+
+        let is_privileged = role == "admin" || role == "moderator";
+        if !is_privileged {
+            self.require_route_owner(comment.route_id, user_id).await?;
+        }
+
+        if comment.approved {
+            tracing::debug!("comment already approved");
+            return Ok(comment);
+        }
+
+        self.comment_repository.approve(comment_id).await?;
+
+        tracing::info!(comment_id = %comment_id, "comment approved");
+        Ok(comment.with_approved(true))
+    }
+
+    async fn require_route_owner(&self, route_id: Uuid, user_id: Uuid) -> Result<(), UsecaseError> {
+        let route = self
+            .route_repository
+            .find_by_id(route_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+
+        if route.user_id != user_id {
+            tracing::warn!(route_id = %route_id, user_id = %user_id, "comment moderation by non-owner rejected");
+            return Err(UsecaseError::Forbidden("Only the route owner can moderate comments".to_string()));
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self), fields(route_id = %route_id))]
     pub async fn list_comments(&self, route_id: Uuid) -> Result<Vec<Comment>, UsecaseError> {
         tracing::debug!("listing comments for route");
@@ -112,13 +231,70 @@ where
         tracing::debug!(route_id = %route_id, count, "counted comments");
         Ok(count)
     }
+
+    /// Full-text search over comment text, optionally limited to one route.
+    /// Returns the matching page and the total number of matches.
+    #[tracing::instrument(skip(self), fields(?route_id, %query, %limit, %offset))]
+    pub async fn search_comments(
+        &self,
+        route_id: Option<Uuid>,
+        query: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Comment>, i64), UsecaseError> {
+        tracing::debug!("searching comments");
+
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(UsecaseError::Validation("Search query must not be empty".to_string()));
+        }
+        if query.chars().count() > MAX_SEARCH_QUERY_LENGTH {
+            return Err(UsecaseError::Validation(format!(
+                "Search query must be at most {} characters",
+                MAX_SEARCH_QUERY_LENGTH
+            )));
+        }
+
+        let comments = self.comment_repository.search(route_id, query, limit, offset).await?;
+        let total = self.comment_repository.count_search(route_id, query).await?;
+
+        tracing::debug!(count = comments.len(), total, "comment search completed");
+        Ok((comments, total))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::route::Route;
-    use crate::usecase::contracts::{MockCommentRepository, MockRouteRepository};
+    use crate::domain::comment::COMMENT_POLICY_OPEN;
+    use crate::domain::route::{Route, RouteVisibility};
+    use crate::usecase::contracts::{MockBlockRepository, MockCommentRepository, MockRouteRepository};
+    use crate::usecase::sanitize::HtmlPolicy;
+
+    fn no_blocks() -> MockBlockRepository {
+        let mut mock_block_repo = MockBlockRepository::new();
+        mock_block_repo.expect_exists().returning(|_, _| Ok(false));
+        mock_block_repo
+    }
+
+    fn test_route(id: Uuid) -> Route {
+        Route {
+            id,
+            user_id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            points: vec![],
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            share_token: None,
+            visibility: RouteVisibility::Private,
+            category_ids: vec![],
+            start_location: None,
+            end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
+        }
+    }
 
     #[tokio::test]
     async fn test_create_comment_success() {
@@ -135,9 +311,13 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: None,
+            visibility: RouteVisibility::Private,
             category_ids: vec![],
             start_location: None,
             end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
         };
         let route_clone = route.clone();
 
@@ -147,12 +327,16 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(route_clone.clone())));
 
+        mock_route_repo
+            .expect_find_comment_policy()
+            .returning(|_| Ok(Some(COMMENT_POLICY_OPEN.to_string())));
+
         mock_comment_repo
             .expect_create()
             .times(1)
             .returning(|_| Ok(()));
 
-        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo);
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks());
         let result = usecase
             .create_comment(route_id, user_id, "User".to_string(), "Nice!".to_string())
             .await;
@@ -162,6 +346,140 @@ mod tests {
         assert_eq!(comment.route_id, route_id);
         assert_eq!(comment.user_id, user_id);
         assert_eq!(comment.text, "Nice!");
+        assert!(comment.approved);
+    }
+
+    #[tokio::test]
+    async fn test_create_comment_follows_route_policy() {
+        let owner_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let policy = std::sync::Arc::new(std::sync::Mutex::new(COMMENT_POLICY_PRE_MODERATED));
+
+        let mut mock_route_repo = MockRouteRepository::new();
+        mock_route_repo.expect_find_by_id().returning(move |id| {
+            let mut route = test_route(id);
+            route.user_id = owner_id;
+            Ok(Some(route))
+        });
+        let current = policy.clone();
+        mock_route_repo
+            .expect_find_comment_policy()
+            .returning(move |_| Ok(Some(current.lock().unwrap().to_string())));
+        let mut mock_comment_repo = MockCommentRepository::new();
+        mock_comment_repo.expect_create().times(2).returning(|_| Ok(()));
+
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks());
+        let pending = usecase
+            .create_comment(route_id, Uuid::new_v4(), "Ann".to_string(), "Hi".to_string())
+            .await
+            .unwrap();
+        assert!(!pending.approved);
+
+        *policy.lock().unwrap() = COMMENT_POLICY_LOCKED;
+        let locked = usecase
+            .create_comment(route_id, Uuid::new_v4(), "Ann".to_string(), "Hi".to_string())
+            .await;
+        assert!(matches!(locked, Err(UsecaseError::Forbidden(_))));
+
+        let by_owner = usecase
+            .create_comment(route_id, owner_id, "Owner".to_string(), "Closed for winter".to_string())
+            .await
+            .unwrap();
+        assert!(by_owner.approved);
+    }
+
+    #[tokio::test]
+    async fn test_approve_comment_requires_route_owner() {
+        let owner_id = Uuid::new_v4();
+        let comment = Comment::new(Uuid::new_v4(), Uuid::new_v4(), "Ann".to_string(), "Hi".to_string())
+            .with_approved(false);
+        let comment_id = comment.id;
+
+        let mut mock_comment_repo = MockCommentRepository::new();
+        mock_comment_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(comment.clone())));
+        mock_comment_repo
+            .expect_approve()
+            .with(mockall::predicate::eq(comment_id))
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut mock_route_repo = MockRouteRepository::new();
+        mock_route_repo.expect_find_by_id().returning(move |id| {
+            let mut route = test_route(id);
+            route.user_id = owner_id;
+            Ok(Some(route))
+        });
+
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks());
+        let by_stranger = usecase.approve_comment(comment_id, Uuid::new_v4(), "user").await;
+        assert!(matches!(by_stranger, Err(UsecaseError::Forbidden(_))));
+
+        let approved = usecase.approve_comment(comment_id, owner_id, "user").await.unwrap();
+        assert!(approved.approved);
+    }
+
+    #[tokio::test]
+    async fn test_pending_comments_and_policy_are_owner_only() {
+        let mut mock_route_repo = MockRouteRepository::new();
+        mock_route_repo.expect_find_by_id().returning(|id| Ok(Some(test_route(id))));
+        mock_route_repo.expect_set_comment_policy().never();
+        let mut mock_comment_repo = MockCommentRepository::new();
+        mock_comment_repo.expect_find_pending_by_route_id().never();
+
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks());
+        let route_id = Uuid::new_v4();
+
+        let pending = usecase.list_pending_comments(route_id, Uuid::new_v4()).await;
+        assert!(matches!(pending, Err(UsecaseError::Forbidden(_))));
+        let policy = usecase.set_comment_policy(route_id, Uuid::new_v4(), COMMENT_POLICY_LOCKED).await;
+        assert!(matches!(policy, Err(UsecaseError::Forbidden(_))));
+        let invalid = usecase.set_comment_policy(route_id, Uuid::new_v4(), "friends_only").await;
+        assert!(matches!(invalid, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_comment_escapes_xss_payload() {
+        let mut mock_comment_repo = MockCommentRepository::new();
+        let mut mock_route_repo = MockRouteRepository::new();
+
+        mock_route_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(test_route(id))));
+        mock_route_repo
+            .expect_find_comment_policy()
+            .returning(|_| Ok(Some(COMMENT_POLICY_OPEN.to_string())));
+        mock_comment_repo
+            .expect_create()
+            .withf(|c| c.text == "&lt;script&gt;alert(1)&lt;/script&gt;" && c.author_name == "&lt;b&gt;Eve&lt;/b&gt;")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks())
+            .with_sanitizer(TextSanitizer::new(HtmlPolicy::Escape));
+        let result = usecase
+            .create_comment(
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                "<b>Eve</b>\u{202E}".to_string(),
+                "<script>alert(1)</script>".to_string(),
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_comment_rejects_invisible_only_text() {
+        let mut mock_comment_repo = MockCommentRepository::new();
+        mock_comment_repo.expect_create().never();
+
+        let usecase = CommentsUseCase::new(mock_comment_repo, MockRouteRepository::new(), no_blocks());
+        let result = usecase
+            .create_comment(Uuid::new_v4(), Uuid::new_v4(), "User".to_string(), "\u{200B}\u{0007} ".to_string())
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
     }
 
     #[tokio::test]
@@ -176,7 +494,7 @@ mod tests {
             .times(1)
             .returning(|_| Ok(None));
 
-        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo);
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks());
         let result = usecase
             .create_comment(
                 route_id,
@@ -202,6 +520,7 @@ mod tests {
             user_id,
             author_name: "Author".to_string(),
             text: "My comment".to_string(),
+            approved: true,
             created_at: chrono::Utc::now(),
         };
         let comment_clone = comment.clone();
@@ -218,7 +537,7 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
-        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo);
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks());
         let result = usecase.delete_comment(comment_id, user_id, "user").await;
 
         assert!(result.is_ok());
@@ -239,6 +558,7 @@ mod tests {
             user_id: comment_author_id,
             author_name: "Author".to_string(),
             text: "Comment".to_string(),
+            approved: true,
             created_at: chrono::Utc::now(),
         };
         let comment_clone = comment.clone();
@@ -257,9 +577,13 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: None,
+            visibility: RouteVisibility::Private,
             category_ids: vec![],
             start_location: None,
             end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
         };
         let route_clone = route.clone();
 
@@ -275,7 +599,7 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
-        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo);
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks());
         let result = usecase.delete_comment(comment_id, route_owner_id, "user").await;
 
         assert!(result.is_ok());
@@ -295,6 +619,7 @@ mod tests {
             user_id: Uuid::new_v4(), // different from random_user_id
             author_name: "Author".to_string(),
             text: "Comment".to_string(),
+            approved: true,
             created_at: chrono::Utc::now(),
         };
         let comment_clone = comment.clone();
@@ -313,9 +638,13 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: None,
+            visibility: RouteVisibility::Private,
             category_ids: vec![],
             start_location: None,
             end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
         };
         let route_clone = route.clone();
 
@@ -325,11 +654,10 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(route_clone.clone())));
 
-        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo);
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks());
         let result = usecase.delete_comment(comment_id, random_user_id, "user").await;
 
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Not authorized"));
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
     }
 
     #[tokio::test]
@@ -344,10 +672,58 @@ mod tests {
             .times(1)
             .returning(|_| Ok(vec![]));
 
-        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo);
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks());
         let result = usecase.list_comments(route_id).await;
 
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
     }
+
+    #[tokio::test]
+    async fn test_search_comments_trims_query_and_returns_total() {
+        let mut mock_comment_repo = MockCommentRepository::new();
+        let mock_route_repo = MockRouteRepository::new();
+        let route_id = Uuid::new_v4();
+
+        mock_comment_repo
+            .expect_search()
+            .withf(move |rid, q, limit, offset| *rid == Some(route_id) && q == "river crossing" && *limit == 20 && *offset == 0)
+            .times(1)
+            .returning(|rid, _, _, _| {
+                Ok(vec![Comment::new(rid.unwrap(), Uuid::new_v4(), "A".to_string(), "river crossing was fun".to_string())])
+            });
+        mock_comment_repo
+            .expect_count_search()
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        let usecase = CommentsUseCase::new(mock_comment_repo, mock_route_repo, no_blocks());
+        let (comments, total) = usecase
+            .search_comments(Some(route_id), "  river crossing ", 20, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(comments.len(), 1);
+        assert_eq!(total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_comments_rejects_empty_query() {
+        let mut mock_comment_repo = MockCommentRepository::new();
+        mock_comment_repo.expect_search().never();
+
+        let usecase = CommentsUseCase::new(mock_comment_repo, MockRouteRepository::new(), no_blocks());
+        let result = usecase.search_comments(None, "   ", 20, 0).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_search_comments_rejects_long_query() {
+        let usecase = CommentsUseCase::new(MockCommentRepository::new(), MockRouteRepository::new(), no_blocks());
+        let query = "a".repeat(MAX_SEARCH_QUERY_LENGTH + 1);
+        let result = usecase.search_comments(None, &query, 20, 0).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
 }
@@ -0,0 +1,226 @@
+//! Blocked-term filter for route names, comments and chat messages. The term
+//! list and what happens on a match are set per deployment through the admin
+//! settings and re-read at most once per cache period.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::usecase::contracts::SettingsRepository;
+use crate::usecase::error::UsecaseError;
+use crate::usecase::settings::SettingsUseCase;
+
+/// The text is refused with a validation error.
+pub const FILTER_MODE_REJECT: &str = "reject";
+/// Matched terms are replaced with asterisks.
+pub const FILTER_MODE_MASK: &str = "mask";
+/// The text is kept and the content goes to the moderation queue.
+pub const FILTER_MODE_FLAG: &str = "flag";
+
+pub const FILTER_MODES: &[&str] = &[FILTER_MODE_REJECT, FILTER_MODE_MASK, FILTER_MODE_FLAG];
+
+pub const MAX_FILTER_TERMS: usize = 2000;
+pub const MAX_FILTER_TERM_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentFilterSettings {
+    pub mode: String,
+    /// Matched case-insensitively as whole words; a term may span several
+    /// words.
+    pub terms: Vec<String>,
+}
+
+impl Default for ContentFilterSettings {
+    fn default() -> Self {
+        Self {
+            mode: FILTER_MODE_MASK.to_string(),
+            terms: Vec::new(),
+        }
+    }
+}
+
+/// Text after filtering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filtered {
+    pub text: String,
+    /// Blocked terms were found and the mode is `flag`.
+    pub flagged: bool,
+}
+
+impl ContentFilterSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !FILTER_MODES.contains(&self.mode.as_str()) {
+            return Err(format!("Invalid filter mode '{}', expected one of: {}", self.mode, FILTER_MODES.join(", ")));
+        }
+        if self.terms.len() > MAX_FILTER_TERMS {
+            return Err(format!("At most {} terms are allowed", MAX_FILTER_TERMS));
+        }
+        if self.terms.iter().any(|t| t.trim().is_empty() || t.chars().count() > MAX_FILTER_TERM_LENGTH) {
+            return Err(format!("Terms must be 1 to {} characters long", MAX_FILTER_TERM_LENGTH));
+        }
+        Ok(())
+    }
+
+    /// Char ranges of the blocked terms found in the text.
+    fn find_terms(&self, chars: &[char]) -> Vec<(usize, usize)> {
+        let lower: Vec<char> = chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+        let is_boundary = |i: usize| lower.get(i).is_none_or(|c| !c.is_alphanumeric());
+
+        let mut ranges = Vec::new();
+        for term in &self.terms {
+            let term: Vec<char> = term.trim().chars().map(|c| c.to_lowercase().next().unwrap_or(c)).collect();
+            if term.is_empty() || term.len() > lower.len() {
+                continue;
+            }
+            for start in 0..=lower.len() - term.len() {
+                let end = start + term.len();
+                if lower[start..end] == term[..] && (start == 0 || is_boundary(start - 1)) && is_boundary(end) {
+                    ranges.push((start, end));
+                }
+            }
+        }
+        ranges
+    }
+
+    /// Applies the mode to the text.
+    pub fn apply(&self, text: &str) -> Result<Filtered, UsecaseError> {
+        let mut chars: Vec<char> = text.chars().collect();
+        let ranges = self.find_terms(&chars);
+        if ranges.is_empty() {
+            return Ok(Filtered { text: text.to_string(), flagged: false });
+        }
+
+        tracing::debug!(matches = ranges.len(), mode = %self.mode, "blocked terms found");
+        match self.mode.as_str() {
+            FILTER_MODE_REJECT => Err(UsecaseError::Validation("Text contains blocked terms".to_string())),
+            FILTER_MODE_FLAG => Ok(Filtered { text: text.to_string(), flagged: true }),
+            _ => {
+                for (start, end) in ranges {
+                    chars[start..end].iter_mut().filter(|c| !c.is_whitespace()).for_each(|c| *c = '*');
+                }
+                Ok(Filtered { text: chars.into_iter().collect(), flagged: false })
+            }
+        }
+    }
+}
+
+/// Reads the filter from [`SettingsUseCase`] and keeps it for `cache_ttl`.
+/// If settings cannot be read the last known filter is used, or no filter
+/// at all, so a database hiccup never blocks posting.
+pub struct ContentFilter<R: SettingsRepository> {
+    settings: SettingsUseCase<R>,
+    cache_ttl: Duration,
+    cached: Mutex<Option<(Instant, ContentFilterSettings)>>,
+}
+
+impl<R: SettingsRepository> ContentFilter<R> {
+    pub fn new(settings: SettingsUseCase<R>, cache_ttl: Duration) -> Self {
+        Self {
+            settings,
+            cache_ttl,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Drops the cached filter; call after changing it on this replica.
+    pub fn invalidate(&self) {
+        *self.cached.lock().unwrap() = None;
+    }
+
+    async fn current(&self) -> ContentFilterSettings {
+        let fresh = self
+            .cached
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.cache_ttl)
+            .map(|(_, settings)| settings.clone());
+        if let Some(settings) = fresh {
+            return settings;
+        }
+
+        let settings = match self.settings.get_content_filter().await {
+            Ok(settings) => settings,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to load content filter, keeping previous one");
+                return self.cached.lock().unwrap().as_ref().map(|(_, s)| s.clone()).unwrap_or_default();
+            }
+        };
+
+        *self.cached.lock().unwrap() = Some((Instant::now(), settings.clone()));
+        settings
+    }
+
+    #[tracing::instrument(skip(self, text))]
+    pub async fn check(&self, text: &str) -> Result<Filtered, UsecaseError> {
+        self.current().await.apply(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::errors::RepositoryError;
+    use crate::usecase::contracts::MockSettingsRepository;
+
+    fn filter(mode: &str, terms: &[&str]) -> ContentFilterSettings {
+        ContentFilterSettings {
+            mode: mode.to_string(),
+            terms: terms.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_mask_replaces_whole_words_only() {
+        let filter = filter(FILTER_MODE_MASK, &["darn", "Dang It"]);
+
+        let filtered = filter.apply("Darn! The darning needle, dang  it... dang it").unwrap();
+
+        assert_eq!(filtered.text, "****! The darning needle, dang  it... **** **");
+        assert!(!filtered.flagged);
+        assert_eq!(filter.apply("Чисто").unwrap().text, "Чисто");
+    }
+
+    #[test]
+    fn test_reject_and_flag_modes() {
+        assert!(matches!(
+            filter(FILTER_MODE_REJECT, &["darn"]).apply("oh DARN"),
+            Err(UsecaseError::Validation(_))
+        ));
+        assert!(filter(FILTER_MODE_REJECT, &["darn"]).apply("darning").is_ok());
+
+        let flagged = filter(FILTER_MODE_FLAG, &["чёрт"]).apply("Ну Чёрт возьми").unwrap();
+        assert_eq!(flagged.text, "Ну Чёрт возьми");
+        assert!(flagged.flagged);
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_modes_and_blank_terms() {
+        assert!(filter(FILTER_MODE_FLAG, &["darn"]).validate().is_ok());
+        assert!(filter("shadowban", &[]).validate().is_err());
+        assert!(filter(FILTER_MODE_MASK, &["  "]).validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_filter_caches_and_survives_errors() {
+        let mut repo = MockSettingsRepository::new();
+        let mut seq = mockall::Sequence::new();
+        repo.expect_get_value()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(Some(serde_json::json!({ "mode": "reject", "terms": ["darn"] }))));
+        repo.expect_get_value()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Err(RepositoryError::DatabaseError("down".to_string())));
+
+        let filter = ContentFilter::new(SettingsUseCase::new(repo), Duration::from_secs(60));
+        assert!(filter.check("darn").await.is_err());
+        // Served from cache; no second read.
+        assert!(filter.check("darn").await.is_err());
+
+        filter.invalidate();
+        assert!(filter.check("darn").await.is_ok());
+    }
+}
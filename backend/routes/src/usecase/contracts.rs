@@ -1,15 +1,31 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use guide_helper_domain::user_data::UserKeyedTable;
 use uuid::Uuid;
 
 use crate::{
+    domain::activity::RouteActivity,
+    domain::block::UserBlock,
     domain::bookmark::RouteBookmark,
     domain::category::Category,
-    domain::chat_message::{ChatMessage, ConversationSummary},
+    domain::chat_message::{ChatMessage, ChatTurnRecord, ConversationFlags, ConversationSummary, FlaggedConversation, PendingChatAction},
+    domain::collection::RouteCollection,
     domain::comment::Comment,
     domain::like::RouteLike,
     domain::notification::Notification,
+    domain::quota::{RouteQuota, RouteUsage, StorageTotals, StorageUsage, UserStorageUsage},
     domain::rating::RouteRating,
-    domain::route::{AdminRouteRow, ExploreRouteRow, Route},
+    domain::report::RouteReport,
+    domain::route::{AdminRouteRow, ExploreRouteRow, Route, RouteCover, RoutePoint, RouteVisibility, UserRoutesFilter},
+    domain::route_elevation::RouteElevation,
+    domain::route_geometry::RouteGeometry,
+    domain::saved_search::SavedSearch,
+    domain::share_link::ShareLink,
+    domain::spam::SpamFlag,
+    domain::stats::{ChatTurnSummary, ChatUsage, ChatUserVolume, DailyCount, PhotoVolume, ToolCallCount},
+    domain::template::{RouteTemplate, TemplateSummary},
+    domain::translation::Translation,
     repository::errors::RepositoryError,
+    usecase::settings::DifficultyThresholds,
 };
 
 #[cfg_attr(test, mockall::automock)]
@@ -17,12 +33,39 @@ pub trait RouteRepository: Send + Sync {
     async fn create(&self, route: &Route) -> Result<(), RepositoryError>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Route>, RepositoryError>;
     async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Route>, RepositoryError>;
+    /// One page of the user's routes matching `filter`, ordered by `order_clause`.
+    async fn find_by_user_id_paginated(
+        &self,
+        user_id: Uuid,
+        filter: &UserRoutesFilter,
+        order_clause: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Route>, RepositoryError>;
+    async fn count_by_user_id(&self, user_id: Uuid, filter: &UserRoutesFilter) -> Result<i64, RepositoryError>;
     async fn update(&self, route: &Route) -> Result<(), RepositoryError>;
+    /// Stores new points if the route's `updated_at` is still `expected`.
+    /// `false` when the route changed meanwhile or is gone.
+    async fn replace_points(
+        &self,
+        id: Uuid,
+        points: &[RoutePoint],
+        expected: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<bool, RepositoryError>;
     async fn delete(&self, id: Uuid) -> Result<(), RepositoryError>;
-    async fn set_share_token(&self, id: Uuid, token: Option<Uuid>) -> Result<(), RepositoryError>;
+    async fn set_visibility(&self, id: Uuid, visibility: RouteVisibility, token: Option<Uuid>) -> Result<(), RepositoryError>;
     async fn find_by_share_token(&self, token: Uuid) -> Result<Option<Route>, RepositoryError>;
+    /// `updated_at` of the route shared under `token`, without loading its points.
+    async fn find_shared_updated_at(&self, token: Uuid) -> Result<Option<DateTime<Utc>>, RepositoryError>;
+    /// Share token of a route that can be opened by link, or only of a public
+    /// one with `public_only`.
+    async fn find_share_token(&self, id: Uuid, public_only: bool) -> Result<Option<Uuid>, RepositoryError>;
+    /// Public routes, or every unarchived route of `owner_id` when given.
+    #[allow(clippy::too_many_arguments)]
     async fn explore_shared(
         &self,
+        owner_id: Option<Uuid>,
         search: Option<String>,
         category_id: Option<Uuid>,
         season: Option<String>,
@@ -36,7 +79,29 @@ pub trait RouteRepository: Send + Sync {
         category_id: Option<Uuid>,
         season: Option<String>,
     ) -> Result<i64, RepositoryError>;
+    /// Explore routes per category, ignoring the category filter.
+    async fn count_explore_by_category(
+        &self,
+        search: Option<String>,
+        season: Option<String>,
+    ) -> Result<Vec<(Uuid, i64)>, RepositoryError>;
+    /// Explore routes per difficulty level, classified from the computed
+    /// distance and elevation gain. Routes without a computed distance are
+    /// not counted.
+    async fn count_explore_by_difficulty(
+        &self,
+        search: Option<String>,
+        category_id: Option<Uuid>,
+        season: Option<String>,
+        thresholds: &DifficultyThresholds,
+    ) -> Result<Vec<(String, i64)>, RepositoryError>;
     async fn count_all(&self) -> Result<i64, RepositoryError>;
+    /// Shared, unarchived routes whose share link was created in `(after, until]`.
+    async fn find_shared_between(
+        &self,
+        after: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Route>, RepositoryError>;
     async fn find_all_admin(
         &self,
         limit: i64,
@@ -48,14 +113,36 @@ pub trait RouteRepository: Send + Sync {
         start_location: Option<String>,
         end_location: Option<String>,
     ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
+    async fn set_archived_at(&self, id: Uuid, archived_at: Option<DateTime<Utc>>) -> Result<(), RepositoryError>;
+    /// Stores the route's cover photo; `None` clears it.
+    async fn set_cover(&self, id: Uuid, cover: Option<RouteCover>) -> Result<(), RepositoryError>;
+    /// None when the route does not exist.
+    async fn find_comment_policy(&self, id: Uuid) -> Result<Option<String>, RepositoryError>;
+    async fn set_comment_policy(&self, id: Uuid, policy: &str) -> Result<(), RepositoryError>;
+    async fn usage_by_user_id(&self, user_id: Uuid) -> Result<RouteUsage, RepositoryError>;
+    async fn storage_by_user_id(&self, user_id: Uuid) -> Result<StorageUsage, RepositoryError>;
+    async fn storage_totals(&self) -> Result<StorageTotals, RepositoryError>;
+    /// Users holding the most object storage, largest first.
+    async fn top_storage_users(&self, limit: i64) -> Result<Vec<UserStorageUsage>, RepositoryError>;
+    async fn find_quota_override(&self, user_id: Uuid) -> Result<Option<RouteQuota>, RepositoryError>;
+    /// Stores the admin override for the user; `None` removes it.
+    async fn set_quota_override(&self, user_id: Uuid, quota: Option<RouteQuota>) -> Result<(), RepositoryError>;
+    /// Recounts the like and bookmark counters kept on route rows. Returns
+    /// the number of routes whose counters were wrong.
+    async fn reconcile_counters(&self) -> Result<u64, RepositoryError>;
 }
 
 #[cfg_attr(test, mockall::automock)]
 pub trait CommentRepository: Send + Sync {
     async fn create(&self, comment: &Comment) -> Result<(), RepositoryError>;
+    /// Approved comments only; see `find_pending_by_route_id`. Listings and
+    /// counts by route leave out comments of users the route owner blocked.
     async fn find_by_route_id(&self, route_id: Uuid) -> Result<Vec<Comment>, RepositoryError>;
+    async fn find_pending_by_route_id(&self, route_id: Uuid) -> Result<Vec<Comment>, RepositoryError>;
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Comment>, RepositoryError>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Comment>, RepositoryError>;
     async fn delete(&self, id: Uuid) -> Result<(), RepositoryError>;
+    async fn approve(&self, id: Uuid) -> Result<(), RepositoryError>;
     async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError>;
     async fn count_all(&self) -> Result<i64, RepositoryError>;
     async fn find_all_paginated(
@@ -63,6 +150,22 @@ pub trait CommentRepository: Send + Sync {
         limit: i64,
         offset: i64,
     ) -> Result<Vec<Comment>, RepositoryError>;
+    async fn search(
+        &self,
+        route_id: Option<Uuid>,
+        query: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Comment>, RepositoryError>;
+    async fn count_search(&self, route_id: Option<Uuid>, query: &str) -> Result<i64, RepositoryError>;
+    /// Comments with exactly this text posted since `since`, other than
+    /// `exclude_id`, pending ones included.
+    async fn count_recent_by_text(
+        &self,
+        text: &str,
+        since: DateTime<Utc>,
+        exclude_id: Uuid,
+    ) -> Result<i64, RepositoryError>;
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -74,6 +177,31 @@ pub trait CategoryRepository: Send + Sync {
     async fn delete(&self, id: Uuid) -> Result<(), RepositoryError>;
 }
 
+#[cfg_attr(test, mockall::automock)]
+pub trait BlockRepository: Send + Sync {
+    /// Returns false when the block already existed.
+    async fn create(&self, block: &UserBlock) -> Result<bool, RepositoryError>;
+    async fn delete(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<(), RepositoryError>;
+    async fn exists(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<bool, RepositoryError>;
+    async fn find_by_blocker(&self, blocker_id: Uuid) -> Result<Vec<UserBlock>, RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait SpamFlagRepository: Send + Sync {
+    async fn create(&self, flag: &SpamFlag) -> Result<(), RepositoryError>;
+    async fn list(&self, reviewed: bool, limit: i64, offset: i64) -> Result<Vec<SpamFlag>, RepositoryError>;
+    async fn count(&self, reviewed: bool) -> Result<i64, RepositoryError>;
+    /// NotFound unless the flag exists and is still unreviewed.
+    async fn mark_reviewed(&self, id: Uuid, reviewed_by: Uuid) -> Result<(), RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait TranslationRepository: Send + Sync {
+    async fn find_by_locale(&self, locale: &str) -> Result<Vec<Translation>, RepositoryError>;
+    async fn upsert(&self, translation: &Translation) -> Result<(), RepositoryError>;
+    async fn delete(&self, key: &str, locale: &str) -> Result<(), RepositoryError>;
+}
+
 #[cfg_attr(test, mockall::automock)]
 pub trait LikeRepository: Send + Sync {
     async fn create(&self, like: &RouteLike) -> Result<(), RepositoryError>;
@@ -110,6 +238,7 @@ pub trait RatingRepository: Send + Sync {
         user_id: Uuid,
     ) -> Result<Option<RouteRating>, RepositoryError>;
     async fn get_aggregate(&self, route_id: Uuid) -> Result<(f64, i64), RepositoryError>;
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RouteRating>, RepositoryError>;
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -121,23 +250,81 @@ pub trait ChatMessageRepository: Send + Sync {
         conversation_id: Uuid,
         limit: i64,
     ) -> Result<Vec<ChatMessage>, RepositoryError>;
+    /// Pinned conversations come first; archived ones are skipped unless
+    /// `include_archived` is set.
     async fn list_conversations(
         &self,
         user_id: Uuid,
         limit: i64,
         offset: i64,
+        include_archived: bool,
     ) -> Result<Vec<ConversationSummary>, RepositoryError>;
     async fn delete_conversation(
         &self,
         user_id: Uuid,
         conversation_id: Uuid,
     ) -> Result<(), RepositoryError>;
-    async fn count_conversations(&self, user_id: Uuid) -> Result<i64, RepositoryError>;
+    async fn count_conversations(&self, user_id: Uuid, include_archived: bool) -> Result<i64, RepositoryError>;
+    /// Updates the flags given as `Some`; fails with `NotFound` when the user
+    /// has no messages in the conversation.
+    async fn set_conversation_flags(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        archived: Option<bool>,
+        pinned: Option<bool>,
+    ) -> Result<ConversationFlags, RepositoryError>;
+    /// Stores `token` as the conversation's share token unless it already has
+    /// one, and returns the token in effect. `NotFound` when the user has no
+    /// messages in the conversation.
+    async fn share_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        token: Uuid,
+    ) -> Result<Uuid, RepositoryError>;
+    async fn unshare_conversation(&self, user_id: Uuid, conversation_id: Uuid) -> Result<(), RepositoryError>;
+    /// Owner and conversation id behind a share token.
+    async fn find_shared_conversation(&self, token: Uuid) -> Result<Option<(Uuid, Uuid)>, RepositoryError>;
+    async fn find_all_by_user(&self, user_id: Uuid) -> Result<Vec<ChatMessage>, RepositoryError>;
+    /// Fails with `NotFound` unless the message is the user's and belongs to
+    /// `conversation_id`.
     async fn delete_message(
         &self,
         user_id: Uuid,
+        conversation_id: Uuid,
         message_id: Uuid,
     ) -> Result<(), RepositoryError>;
+    async fn create_pending_action(&self, action: &PendingChatAction) -> Result<(), RepositoryError>;
+    /// Removes and returns the user's pending action, so it can run at most once.
+    async fn take_pending_action(
+        &self,
+        user_id: Uuid,
+        action_id: Uuid,
+    ) -> Result<Option<PendingChatAction>, RepositoryError>;
+    async fn delete_pending_actions_before(&self, before: DateTime<Utc>) -> Result<u64, RepositoryError>;
+    /// Language code the user picked for the assistant, if any.
+    async fn find_chat_language(&self, user_id: Uuid) -> Result<Option<String>, RepositoryError>;
+    /// `None` clears the preference.
+    async fn set_chat_language(&self, user_id: Uuid, language: Option<String>) -> Result<(), RepositoryError>;
+    async fn record_turn(&self, turn: &ChatTurnRecord) -> Result<(), RepositoryError>;
+    /// Flags the conversation for review, replacing an earlier flag. Fails
+    /// with `NotFound` when the user has no messages in the conversation.
+    async fn flag_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+        reason: Option<String>,
+        review_consent: bool,
+    ) -> Result<(), RepositoryError>;
+    /// Most recently flagged first.
+    async fn list_flagged_conversations(&self, limit: i64, offset: i64) -> Result<Vec<FlaggedConversation>, RepositoryError>;
+    async fn count_flagged_conversations(&self) -> Result<i64, RepositoryError>;
+    async fn find_flagged_conversation(
+        &self,
+        user_id: Uuid,
+        conversation_id: Uuid,
+    ) -> Result<Option<FlaggedConversation>, RepositoryError>;
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -154,6 +341,71 @@ pub trait BookmarkRepository: Send + Sync {
         user_id: Uuid,
     ) -> Result<Option<RouteBookmark>, RepositoryError>;
     async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<ExploreRouteRow>, RepositoryError>;
+    async fn find_user_ids_by_route(&self, route_id: Uuid) -> Result<Vec<Uuid>, RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait RouteActivityRepository: Send + Sync {
+    async fn create(&self, activity: &RouteActivity) -> Result<(), RepositoryError>;
+    /// Oldest first.
+    async fn find_by_route_id(&self, route_id: Uuid, limit: i64, offset: i64) -> Result<Vec<RouteActivity>, RepositoryError>;
+    async fn count_by_route_id(&self, route_id: Uuid) -> Result<i64, RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait RouteTemplateRepository: Send + Sync {
+    /// Flags the route as a template, or updates whether it is public.
+    async fn upsert(&self, template: &RouteTemplate) -> Result<(), RepositoryError>;
+    async fn delete(&self, route_id: Uuid) -> Result<(), RepositoryError>;
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteTemplate>, RepositoryError>;
+    /// Public templates and the user's own, public ones first.
+    async fn list_visible(&self, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<TemplateSummary>, RepositoryError>;
+    async fn count_visible(&self, user_id: Uuid) -> Result<i64, RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait ShareLinkRepository: Send + Sync {
+    async fn create(&self, link: &ShareLink) -> Result<(), RepositoryError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ShareLink>, RepositoryError>;
+    /// Revoked links included, newest first.
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Vec<ShareLink>, RepositoryError>;
+    /// Fails with `NotFound` unless an active link `id` of `route_id` exists.
+    async fn revoke(&self, id: Uuid, route_id: Uuid, revoked_at: DateTime<Utc>) -> Result<(), RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait RouteCollectionRepository: Send + Sync {
+    async fn create(&self, collection: &RouteCollection) -> Result<(), RepositoryError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RouteCollection>, RepositoryError>;
+    /// Sorted by name.
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RouteCollection>, RepositoryError>;
+    /// NotFound unless the collection exists and belongs to the user.
+    async fn rename(&self, id: Uuid, user_id: Uuid, name: &str, updated_at: DateTime<Utc>) -> Result<(), RepositoryError>;
+    /// NotFound unless the collection exists and belongs to the user. The
+    /// routes in it are kept.
+    async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError>;
+    /// Adding a route that is already in the collection does nothing.
+    async fn add_route(&self, collection_id: Uuid, route_id: Uuid, added_at: DateTime<Utc>) -> Result<(), RepositoryError>;
+    /// NotFound unless the route is in the collection.
+    async fn remove_route(&self, collection_id: Uuid, route_id: Uuid) -> Result<(), RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait SavedSearchRepository: Send + Sync {
+    async fn create(&self, search: &SavedSearch) -> Result<(), RepositoryError>;
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<SavedSearch>, RepositoryError>;
+    async fn count_by_user_id(&self, user_id: Uuid) -> Result<i64, RepositoryError>;
+    /// NotFound unless the search exists and belongs to the user.
+    async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError>;
+    async fn find_all(&self) -> Result<Vec<SavedSearch>, RepositoryError>;
+    /// Moves `last_checked_at` to `checked_at` if it is still `previous`.
+    /// False when another run got there first.
+    async fn mark_checked(
+        &self,
+        id: Uuid,
+        previous: DateTime<Utc>,
+        checked_at: DateTime<Utc>,
+    ) -> Result<bool, RepositoryError>;
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -166,6 +418,106 @@ pub trait NotificationRepository: Send + Sync {
         offset: i64,
     ) -> Result<Vec<Notification>, RepositoryError>;
     async fn count_unread(&self, user_id: Uuid) -> Result<i64, RepositoryError>;
+    async fn count_by_user_id(&self, user_id: Uuid) -> Result<i64, RepositoryError>;
     async fn mark_as_read(&self, id: Uuid, user_id: Uuid) -> Result<(), RepositoryError>;
     async fn mark_all_as_read(&self, user_id: Uuid) -> Result<(), RepositoryError>;
 }
+
+#[cfg_attr(test, mockall::automock)]
+pub trait NotificationPreferenceRepository: Send + Sync {
+    async fn find_disabled_types(&self, user_id: Uuid) -> Result<Vec<String>, RepositoryError>;
+    async fn set_enabled(&self, user_id: Uuid, notification_type: &str, enabled: bool) -> Result<(), RepositoryError>;
+    /// Subset of `user_ids` that turned the notification type off.
+    async fn find_opted_out(&self, notification_type: &str, user_ids: Vec<Uuid>) -> Result<Vec<Uuid>, RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait ReportRepository: Send + Sync {
+    async fn create(&self, report: &RouteReport) -> Result<(), RepositoryError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RouteReport>, RepositoryError>;
+    async fn find_open_by_route_and_reporter(
+        &self,
+        route_id: Uuid,
+        reporter_id: Uuid,
+    ) -> Result<Option<RouteReport>, RepositoryError>;
+    async fn list(
+        &self,
+        status: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<RouteReport>, RepositoryError>;
+    async fn count(&self, status: Option<String>) -> Result<i64, RepositoryError>;
+    async fn resolve(
+        &self,
+        id: Uuid,
+        status: &str,
+        resolved_by: Uuid,
+        resolution_note: Option<String>,
+    ) -> Result<(), RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait RouteGeometryRepository: Send + Sync {
+    async fn upsert(&self, geometry: &RouteGeometry) -> Result<(), RepositoryError>;
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteGeometry>, RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait RouteElevationRepository: Send + Sync {
+    async fn upsert(&self, elevation: &RouteElevation) -> Result<(), RepositoryError>;
+    async fn find_by_route_id(&self, route_id: Uuid) -> Result<Option<RouteElevation>, RepositoryError>;
+}
+
+/// Activity and volume figures for the admin statistics. `since` is an
+/// inclusive UTC day.
+#[cfg_attr(test, mockall::automock)]
+pub trait StatsRepository: Send + Sync {
+    /// Marks the user active on `day`; repeated calls for the same day are no-ops.
+    async fn record_usage(&self, user_id: Uuid, day: NaiveDate) -> Result<(), RepositoryError>;
+    async fn count_active_users(&self, since: NaiveDate) -> Result<i64, RepositoryError>;
+    async fn active_users_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError>;
+    async fn routes_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError>;
+    async fn chat_usage(&self, since: NaiveDate) -> Result<ChatUsage, RepositoryError>;
+    async fn photo_volume(&self) -> Result<PhotoVolume, RepositoryError>;
+    /// Messages users sent to the assistant, per day.
+    async fn chat_messages_per_day(&self, since: NaiveDate) -> Result<Vec<DailyCount>, RepositoryError>;
+    async fn chat_turn_summary(&self, since: NaiveDate) -> Result<ChatTurnSummary, RepositoryError>;
+    /// Most used first.
+    async fn chat_tool_calls(&self, since: NaiveDate) -> Result<Vec<ToolCallCount>, RepositoryError>;
+    /// Users who sent the most messages, most first.
+    async fn top_chat_users(&self, since: NaiveDate, limit: i64) -> Result<Vec<ChatUserVolume>, RepositoryError>;
+}
+
+/// Raw rows of the tables in [`USER_KEYED_TABLES`](guide_helper_domain::user_data::USER_KEYED_TABLES),
+/// for the data takeout.
+#[cfg_attr(test, mockall::automock)]
+pub trait UserDataRepository: Send + Sync {
+    /// Every row of `table` keyed to the user, one JSON object per row.
+    async fn find_user_rows(&self, table: UserKeyedTable, user_id: Uuid) -> Result<Vec<serde_json::Value>, RepositoryError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait ObjectStorage: Send + Sync {
+    async fn put_object(
+        &self,
+        key: &str,
+        body: Vec<u8>,
+        content_type: &str,
+    ) -> Result<(), RepositoryError>;
+    async fn presigned_get_url(
+        &self,
+        key: &str,
+        expires_in: std::time::Duration,
+    ) -> Result<String, RepositoryError>;
+    /// A link the client can `PUT` the object to directly. The upload must
+    /// send the same `Content-Type`, which is part of the signature.
+    async fn presigned_put_url(
+        &self,
+        key: &str,
+        content_type: &str,
+        expires_in: std::time::Duration,
+    ) -> Result<String, RepositoryError>;
+    /// Keys of every object whose key starts with `prefix`.
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>, RepositoryError>;
+    async fn delete_object(&self, key: &str) -> Result<(), RepositoryError>;
+}
@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::route::RoutePoint;
+use crate::usecase::circuit_breaker::{BreakerSettings, CircuitBreaker};
+use crate::usecase::geo::haversine_km;
+
+/// Locations per lookup request; public Open-Elevation instances reject large bodies.
+const LOOKUP_BATCH_SIZE: usize = 100;
+/// Samples closer than this add noise rather than detail.
+const MIN_SAMPLE_SPACING_KM: f64 = 0.05;
+
+#[derive(Serialize)]
+struct LookupLocation {
+    latitude: f64,
+    longitude: f64,
+}
+
+#[derive(Serialize)]
+struct LookupRequest {
+    locations: Vec<LookupLocation>,
+}
+
+#[derive(Deserialize)]
+struct LookupResult {
+    elevation: f64,
+}
+
+#[derive(Deserialize)]
+struct LookupResponse {
+    results: Vec<LookupResult>,
+}
+
+/// Position along the route line where elevation is sampled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinePosition {
+    pub distance_km: f64,
+    pub lat: f64,
+    pub lng: f64,
+}
+
+/// Spreads up to `max_samples` evenly spaced positions along the polyline
+/// through the points, always including the first and last point.
+pub fn sample_line(points: &[RoutePoint], max_samples: usize) -> Vec<LinePosition> {
+    let Some(first) = points.first() else {
+        return vec![];
+    };
+
+    let mut cumulative = Vec::with_capacity(points.len());
+    cumulative.push(0.0);
+    for pair in points.windows(2) {
+        let last = *cumulative.last().unwrap();
+        cumulative.push(last + haversine_km(pair[0].lat, pair[0].lng, pair[1].lat, pair[1].lng));
+    }
+    let total_km = *cumulative.last().unwrap();
+
+    if total_km == 0.0 || max_samples < 2 {
+        return vec![LinePosition {
+            distance_km: 0.0,
+            lat: first.lat,
+            lng: first.lng,
+        }];
+    }
+
+    let spacing = (total_km / (max_samples - 1) as f64).max(MIN_SAMPLE_SPACING_KM);
+    let steps = (total_km / spacing).ceil() as usize;
+
+    let mut samples = Vec::with_capacity(steps + 1);
+    let mut segment = 0;
+    for step in 0..=steps {
+        let distance_km = (step as f64 * spacing).min(total_km);
+        while segment + 1 < points.len() - 1 && cumulative[segment + 1] < distance_km {
+            segment += 1;
+        }
+
+        let (a, b) = (&points[segment], &points[segment + 1]);
+        let segment_km = cumulative[segment + 1] - cumulative[segment];
+        let t = if segment_km > 0.0 {
+            (distance_km - cumulative[segment]) / segment_km
+        } else {
+            0.0
+        };
+        samples.push(LinePosition {
+            distance_km,
+            lat: a.lat + (b.lat - a.lat) * t,
+            lng: a.lng + (b.lng - a.lng) * t,
+        });
+    }
+    samples
+}
+
+/// Client for the Open-Elevation lookup API.
+#[derive(Clone)]
+pub struct ElevationClient {
+    client: Client,
+    base_url: String,
+    breaker: CircuitBreaker,
+}
+
+impl ElevationClient {
+    pub fn new(base_url: String) -> Self {
+        let client = Client::builder()
+            .user_agent("GuideHelper/1.0")
+            .timeout(Duration::from_secs(15))
+            .build()
+            .expect("failed to build elevation http client");
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            breaker: CircuitBreaker::new("elevation", BreakerSettings::default()),
+        }
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    /// Returns the elevation in meters for each position, in the same order.
+    #[tracing::instrument(skip(self, positions), fields(count = positions.len()))]
+    pub async fn lookup(&self, positions: &[LinePosition]) -> anyhow::Result<Vec<f64>> {
+        let url = format!("{}/api/v1/lookup", self.base_url);
+        let mut elevations = Vec::with_capacity(positions.len());
+
+        for chunk in positions.chunks(LOOKUP_BATCH_SIZE) {
+            let request = LookupRequest {
+                locations: chunk
+                    .iter()
+                    .map(|p| LookupLocation {
+                        latitude: p.lat,
+                        longitude: p.lng,
+                    })
+                    .collect(),
+            };
+
+            self.breaker.check()?;
+            let response = self.client.post(&url).json(&request).send().await;
+            self.breaker.record_response(&response);
+
+            let response: LookupResponse = response
+                .context("elevation lookup request failed")?
+                .error_for_status()
+                .context("elevation service returned error status")?
+                .json()
+                .await
+                .context("failed to parse elevation response")?;
+
+            if response.results.len() != chunk.len() {
+                anyhow::bail!(
+                    "elevation service returned {} results for {} locations",
+                    response.results.len(),
+                    chunk.len()
+                );
+            }
+            elevations.extend(response.results.into_iter().map(|r| r.elevation));
+        }
+
+        tracing::debug!(count = elevations.len(), "elevations resolved");
+        Ok(elevations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(lat: f64, lng: f64) -> RoutePoint {
+        RoutePoint {
+            lat,
+            lng,
+            name: None,
+            segment_mode: None,
+            photo: None,
+            address: None,
+        }
+    }
+
+    #[test]
+    fn test_sample_line_includes_endpoints() {
+        let points = vec![point(55.0, 37.0), point(55.01, 37.0), point(55.03, 37.0)];
+        let samples = sample_line(&points, 10);
+
+        assert!(samples.len() <= 10 && samples.len() >= 2);
+        assert_eq!((samples[0].lat, samples[0].lng), (55.0, 37.0));
+        let last = samples.last().unwrap();
+        assert!((last.lat - 55.03).abs() < 1e-9);
+        assert!(samples.windows(2).all(|w| w[1].distance_km > w[0].distance_km));
+    }
+
+    #[test]
+    fn test_sample_line_respects_min_spacing() {
+        let points = vec![point(55.0, 37.0), point(55.001, 37.0)];
+        let samples = sample_line(&points, 200);
+
+        // ~111 m long, so 50 m spacing gives at most 4 samples.
+        assert!(samples.len() <= 4, "got {}", samples.len());
+    }
+
+    #[test]
+    fn test_sample_line_degenerate_input() {
+        assert!(sample_line(&[], 10).is_empty());
+        assert_eq!(sample_line(&[point(55.0, 37.0)], 10).len(), 1);
+    }
+
+    #[test]
+    fn test_parse_lookup_response() {
+        let json = r#"{"results":[{"latitude":55.0,"longitude":37.0,"elevation":152.0}]}"#;
+        let response: LookupResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.results[0].elevation, 152.0);
+    }
+}
@@ -2,6 +2,7 @@ use axum::{http::StatusCode, response::IntoResponse};
 use thiserror::Error;
 
 use crate::repository::errors::RepositoryError;
+use crate::usecase::geojson_import::ImportError;
 
 #[derive(Debug, Error)]
 pub enum UsecaseError {
@@ -20,6 +21,9 @@ pub enum UsecaseError {
     #[error("{0}")]
     RateLimited(String),
 
+    #[error("{0}")]
+    PayloadTooLarge(String),
+
     #[error("{0}")]
     Internal(String),
 }
@@ -29,6 +33,25 @@ impl From<RepositoryError> for UsecaseError {
         match e {
             RepositoryError::NotFound => UsecaseError::NotFound("Resource".to_string()),
             RepositoryError::DatabaseError(msg) => UsecaseError::Internal(msg),
+            RepositoryError::Transient(msg) => {
+                tracing::warn!(error = %msg, "database still unavailable after retries");
+                UsecaseError::Unavailable("Service temporarily unavailable, please retry".to_string())
+            }
+        }
+    }
+}
+
+impl From<ImportError> for UsecaseError {
+    fn from(e: ImportError) -> Self {
+        match e {
+            ImportError::TooLarge(_) => UsecaseError::PayloadTooLarge(e.to_string()),
+            ImportError::InvalidGeoJson(_)
+            | ImportError::InvalidGpx(_)
+            | ImportError::MissingRouteName
+            | ImportError::EmptyRoute
+            | ImportError::UnsupportedGeometry
+            | ImportError::TooManyPoints(_)
+            | ImportError::Read(_) => UsecaseError::Validation(e.to_string()),
         }
     }
 }
@@ -47,6 +70,7 @@ impl IntoResponse for UsecaseError {
             UsecaseError::Validation(_) => StatusCode::BAD_REQUEST,
             UsecaseError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
             UsecaseError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            UsecaseError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
             UsecaseError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
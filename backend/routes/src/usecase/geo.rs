@@ -0,0 +1,284 @@
+use guide_helper_api::routes::PointOperation;
+
+use crate::domain::route::RoutePoint;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Haversine distance between two coordinates in km.
+pub fn haversine_km(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lng = (lng2 - lng1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lng / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().atan2((1.0 - a).sqrt())
+}
+
+/// Total length of the polyline through the points, in km.
+pub fn total_distance_km(points: &[RoutePoint]) -> f64 {
+    points
+        .windows(2)
+        .map(|w| haversine_km(w[0].lat, w[0].lng, w[1].lat, w[1].lng))
+        .sum()
+}
+
+/// Points moved less than this are treated as map-click jitter.
+const SIGNIFICANT_MOVE_KM: f64 = 0.05;
+
+/// True when points were added or removed, or any point moved noticeably.
+/// Renames and photo changes do not count.
+pub fn points_changed_significantly(old: &[RoutePoint], new: &[RoutePoint]) -> bool {
+    old.len() != new.len()
+        || old
+            .iter()
+            .zip(new)
+            .any(|(a, b)| haversine_km(a.lat, a.lng, b.lat, b.lng) > SIGNIFICANT_MOVE_KM)
+}
+
+/// Start and end points closer than this count as the same place.
+const DUPLICATE_ENDPOINT_TOLERANCE_KM: f64 = 0.1;
+/// Allowed relative difference in length for near-identical routes.
+const DUPLICATE_LENGTH_TOLERANCE: f64 = 0.1;
+
+/// True when both routes start and end at the same places and have a similar length.
+pub fn routes_nearly_identical(a: &[RoutePoint], b: &[RoutePoint]) -> bool {
+    let (Some(a_first), Some(a_last), Some(b_first), Some(b_last)) = (a.first(), a.last(), b.first(), b.last()) else {
+        return false;
+    };
+    if haversine_km(a_first.lat, a_first.lng, b_first.lat, b_first.lng) > DUPLICATE_ENDPOINT_TOLERANCE_KM
+        || haversine_km(a_last.lat, a_last.lng, b_last.lat, b_last.lng) > DUPLICATE_ENDPOINT_TOLERANCE_KM
+    {
+        return false;
+    }
+
+    let (a_km, b_km) = (total_distance_km(a), total_distance_km(b));
+    let longest = a_km.max(b_km);
+    longest <= DUPLICATE_ENDPOINT_TOLERANCE_KM || (a_km - b_km).abs() / longest <= DUPLICATE_LENGTH_TOLERANCE
+}
+
+/// Where one route ends and the next starts closer than this, the two
+/// points are one junction.
+const JUNCTION_TOLERANCE_KM: f64 = 0.02;
+
+/// The points in walking order from the end to the start. A point's
+/// `segment_mode` applies to the segment leading to it, so modes move one
+/// point back along with the segments.
+pub fn reverse_points(points: &[RoutePoint]) -> Vec<RoutePoint> {
+    let mut reversed: Vec<RoutePoint> = points.iter().rev().cloned().collect();
+    let modes: Vec<Option<String>> = points.iter().rev().map(|p| p.segment_mode.clone()).collect();
+    for (i, point) in reversed.iter_mut().enumerate() {
+        point.segment_mode = if i == 0 { None } else { modes[i - 1].clone() };
+    }
+    reversed
+}
+
+/// Appends `second` to `first`. When `second` starts where `first` ends the
+/// junction is kept once, filling its name and photo from either side.
+pub fn join_points(mut first: Vec<RoutePoint>, second: Vec<RoutePoint>) -> Vec<RoutePoint> {
+    let mut rest = second.into_iter().peekable();
+    let junction = match (first.last(), rest.peek()) {
+        (Some(end), Some(start)) => haversine_km(end.lat, end.lng, start.lat, start.lng) <= JUNCTION_TOLERANCE_KM,
+        _ => false,
+    };
+    if junction {
+        let (end, start) = (first.last_mut().unwrap(), rest.next().unwrap());
+        end.name = end.name.take().or(start.name);
+        end.photo = end.photo.take().or(start.photo);
+    }
+    first.extend(rest);
+    first
+}
+
+/// Splits the points at `index` into the part up to the point and the part
+/// from it. The point ends the first part and starts the second; its photo
+/// stays with the first part, and the second part starts without a segment.
+pub fn split_points(points: &[RoutePoint], index: usize) -> (Vec<RoutePoint>, Vec<RoutePoint>) {
+    let head = points[..=index].to_vec();
+    let mut tail = points[index..].to_vec();
+    tail[0].segment_mode = None;
+    tail[0].photo = None;
+    (head, tail)
+}
+
+/// Applies point edits in order, each to the points the previous ones left.
+/// Fails on the first operation naming a point that does not exist.
+pub fn apply_point_operations(points: &mut Vec<RoutePoint>, operations: &[PointOperation]) -> Result<(), String> {
+    for (n, operation) in operations.iter().enumerate() {
+        let len = points.len();
+        let missing = |index: usize| format!("Operation {}: route has no point {}", n, index);
+        match operation {
+            PointOperation::Insert { index, point } => {
+                if *index > len {
+                    return Err(missing(*index));
+                }
+                points.insert(*index, point.clone());
+            }
+            PointOperation::Move { from, to } => {
+                if let Some(index) = [*from, *to].into_iter().find(|i| *i >= len) {
+                    return Err(missing(index));
+                }
+                let point = points.remove(*from);
+                points.insert(*to, point);
+            }
+            PointOperation::Delete { index } => {
+                if *index >= len {
+                    return Err(missing(*index));
+                }
+                points.remove(*index);
+            }
+            PointOperation::Update { index, point } => {
+                let slot = points.get_mut(*index).ok_or_else(|| missing(*index))?;
+                *slot = point.clone();
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(lat: f64, lng: f64) -> RoutePoint {
+        RoutePoint {
+            lat,
+            lng,
+            name: None,
+            segment_mode: None,
+            photo: None,
+            address: None,
+        }
+    }
+
+    #[test]
+    fn test_haversine_moscow_to_saint_petersburg() {
+        let distance = haversine_km(55.7558, 37.6173, 59.9343, 30.3351);
+        assert!((distance - 634.0).abs() < 5.0, "got {}", distance);
+    }
+
+    #[test]
+    fn test_haversine_same_point_is_zero() {
+        assert_eq!(haversine_km(55.0, 37.0, 55.0, 37.0), 0.0);
+    }
+
+    #[test]
+    fn test_total_distance_sums_segments() {
+        let points = vec![point(55.0, 37.0), point(55.01, 37.0), point(55.02, 37.0)];
+        let total = total_distance_km(&points);
+        let expected = 2.0 * haversine_km(55.0, 37.0, 55.01, 37.0);
+        assert!((total - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_total_distance_single_point() {
+        assert_eq!(total_distance_km(&[point(55.0, 37.0)]), 0.0);
+        assert_eq!(total_distance_km(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_reverse_points_moves_segment_modes() {
+        let mut points = vec![point(55.0, 37.0), point(55.01, 37.0), point(55.02, 37.0)];
+        points[1].segment_mode = Some("walk".to_string());
+        points[2].segment_mode = Some("bike".to_string());
+
+        let reversed = reverse_points(&points);
+
+        assert_eq!(reversed[0].lat, 55.02);
+        let modes: Vec<Option<&str>> = reversed.iter().map(|p| p.segment_mode.as_deref()).collect();
+        assert_eq!(modes, vec![None, Some("bike"), Some("walk")]);
+    }
+
+    #[test]
+    fn test_join_points_merges_junction() {
+        let mut second_start = point(55.01005, 37.0);
+        second_start.name = Some("Bridge".to_string());
+        let first = vec![point(55.0, 37.0), point(55.01, 37.0)];
+        let second = vec![second_start, point(55.02, 37.0)];
+
+        let joined = join_points(first.clone(), second);
+        assert_eq!(joined.len(), 3);
+        assert_eq!(joined[1].lat, 55.01);
+        assert_eq!(joined[1].name.as_deref(), Some("Bridge"));
+
+        let apart = join_points(first, vec![point(55.03, 37.0)]);
+        assert_eq!(apart.len(), 3);
+    }
+
+    #[test]
+    fn test_split_points_shares_the_split_point() {
+        let mut points = vec![point(55.0, 37.0), point(55.01, 37.0), point(55.02, 37.0)];
+        points[1].segment_mode = Some("walk".to_string());
+        points[1].photo = Some(guide_helper_domain::route::PhotoData {
+            original: "/photos/bridge.jpg".to_string(),
+            thumbnail_url: None,
+            status: crate::domain::route::PhotoStatus::Done,
+        });
+
+        let (head, tail) = split_points(&points, 1);
+
+        assert_eq!(head, points[..2]);
+        assert_eq!(tail.len(), 2);
+        assert_eq!(tail[0].lat, 55.01);
+        assert!(tail[0].segment_mode.is_none() && tail[0].photo.is_none());
+    }
+
+    #[test]
+    fn test_apply_point_operations_in_order() {
+        let mut points = vec![point(55.0, 37.0), point(55.1, 37.0), point(55.2, 37.0)];
+        let operations = [
+            PointOperation::Insert { index: 3, point: point(55.3, 37.0) },
+            PointOperation::Move { from: 0, to: 3 },
+            PointOperation::Delete { index: 0 },
+            PointOperation::Update { index: 0, point: point(55.25, 37.0) },
+        ];
+
+        apply_point_operations(&mut points, &operations).unwrap();
+
+        let lats: Vec<f64> = points.iter().map(|p| p.lat).collect();
+        assert_eq!(lats, vec![55.25, 55.3, 55.0]);
+    }
+
+    #[test]
+    fn test_apply_point_operations_rejects_missing_points() {
+        let mut points = vec![point(55.0, 37.0)];
+
+        for operation in [
+            PointOperation::Insert { index: 2, point: point(55.1, 37.0) },
+            PointOperation::Move { from: 0, to: 1 },
+            PointOperation::Delete { index: 1 },
+            PointOperation::Update { index: 1, point: point(55.1, 37.0) },
+        ] {
+            assert!(apply_point_operations(&mut points, &[operation]).is_err());
+        }
+        assert_eq!(points.len(), 1);
+    }
+
+    #[test]
+    fn test_points_changed_significantly() {
+        let old = vec![point(55.0, 37.0), point(55.01, 37.0)];
+
+        let jitter = vec![point(55.0001, 37.0), point(55.01, 37.0)];
+        assert!(!points_changed_significantly(&old, &jitter));
+
+        let moved = vec![point(55.0, 37.0), point(55.02, 37.0)];
+        assert!(points_changed_significantly(&old, &moved));
+
+        let added = vec![point(55.0, 37.0), point(55.01, 37.0), point(55.02, 37.0)];
+        assert!(points_changed_significantly(&old, &added));
+    }
+
+    #[test]
+    fn test_routes_nearly_identical() {
+        let existing = vec![point(55.0, 37.0), point(55.01, 37.0), point(55.02, 37.0)];
+
+        let same_ends_fewer_points = vec![point(55.0002, 37.0), point(55.0201, 37.0)];
+        assert!(routes_nearly_identical(&existing, &same_ends_fewer_points));
+
+        let detour = vec![point(55.0, 37.0), point(55.01, 37.03), point(55.02, 37.0)];
+        assert!(!routes_nearly_identical(&existing, &detour));
+
+        let other_end = vec![point(55.0, 37.0), point(55.01, 37.0), point(55.03, 37.0)];
+        assert!(!routes_nearly_identical(&existing, &other_end));
+
+        assert!(!routes_nearly_identical(&existing, &[]));
+    }
+}
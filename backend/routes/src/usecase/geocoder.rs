@@ -0,0 +1,175 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+
+use crate::domain::route::PointAddress;
+use crate::usecase::circuit_breaker::{BreakerSettings, CircuitBreaker};
+use crate::usecase::mapbox::MapboxClient;
+use crate::usecase::nominatim::NominatimClient;
+use crate::usecase::photon::PhotonClient;
+
+pub const DEFAULT_PHOTON_URL: &str = "https://photon.komoot.io";
+pub const DEFAULT_MAPBOX_URL: &str = "https://api.mapbox.com";
+
+/// Reverse geocoding provider used to name route locations and points.
+pub trait Geocoder: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Minimum delay between consecutive requests allowed by the provider.
+    fn request_interval(&self) -> Duration;
+
+    /// Short human-readable name for the coordinate; empty when nothing was found.
+    /// `zoom` follows Nominatim semantics: 14 is suburb level, 16 and above is street level.
+    fn reverse_geocode(&self, lat: f64, lng: f64, zoom: u8) -> BoxFuture<'_, String>;
+
+    /// Street-level postal address of the coordinate; `None` when the
+    /// provider found nothing or the request failed.
+    fn reverse_address(&self, lat: f64, lng: f64) -> BoxFuture<'_, Option<PointAddress>>;
+}
+
+/// Joins a street and house number the way addresses are written locally.
+pub(crate) fn street_with_number(street: Option<String>, number: Option<String>) -> Option<String> {
+    match (street, number) {
+        (Some(street), Some(number)) => Some(format!("{}, {}", street, number)),
+        (street, _) => street,
+    }
+}
+
+/// Drops blank parts and returns `None` when nothing is left.
+pub(crate) fn non_empty_address(address: PointAddress) -> Option<PointAddress> {
+    let keep = |part: Option<String>| part.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    let address = PointAddress {
+        street: keep(address.street),
+        city: keep(address.city),
+        country: keep(address.country),
+    };
+    (!address.is_empty()).then_some(address)
+}
+
+/// Resolves human-readable location names for the start and end of a route.
+/// Tries progressively more specific zoom levels until names differ.
+pub async fn resolve_route_locations(
+    geocoder: &dyn Geocoder,
+    first: (f64, f64),
+    last: (f64, f64),
+) -> (String, String) {
+    let coords_match = (first.0 - last.0).abs() < 1e-9 && (first.1 - last.1).abs() < 1e-9;
+    let interval = geocoder.request_interval();
+
+    for zoom in [14u8, 16, 18] {
+        let from = geocoder.reverse_geocode(first.0, first.1, zoom).await;
+        tokio::time::sleep(interval).await;
+
+        let to = if coords_match {
+            from.clone()
+        } else {
+            let t = geocoder.reverse_geocode(last.0, last.1, zoom).await;
+            tokio::time::sleep(interval).await;
+            t
+        };
+
+        if from != to || coords_match {
+            tracing::debug!(zoom, %from, %to, provider = geocoder.name(), "resolved route locations");
+            return (from, to);
+        }
+
+        tracing::debug!(zoom, %from, "locations matched, trying higher zoom");
+    }
+
+    (String::new(), String::new())
+}
+
+/// Builds the geocoder selected in config. `base_url` falls back to the
+/// provider's public endpoint; Mapbox requires an API key. `breaker` applies
+/// to Nominatim, which is prone to hanging under load.
+pub fn build_geocoder(
+    provider: &str,
+    base_url: Option<String>,
+    api_key: Option<String>,
+    request_interval: Option<Duration>,
+    breaker: BreakerSettings,
+) -> anyhow::Result<Arc<dyn Geocoder>> {
+    let base_url = base_url.filter(|u| !u.trim().is_empty());
+    let api_key = api_key.filter(|k| !k.trim().is_empty());
+
+    let geocoder: Arc<dyn Geocoder> = match provider {
+        "nominatim" => {
+            let url = base_url.ok_or_else(|| anyhow::anyhow!("nominatim geocoder requires a base URL"))?;
+            let client = NominatimClient::new(url)
+                .with_circuit_breaker(CircuitBreaker::new("nominatim", breaker));
+            Arc::new(match request_interval {
+                Some(interval) => client.with_request_interval(interval),
+                None => client,
+            })
+        }
+        "photon" => {
+            let client = PhotonClient::new(base_url.unwrap_or_else(|| DEFAULT_PHOTON_URL.to_string()));
+            Arc::new(match request_interval {
+                Some(interval) => client.with_request_interval(interval),
+                None => client,
+            })
+        }
+        "mapbox" => {
+            let key = api_key.ok_or_else(|| anyhow::anyhow!("mapbox geocoder requires GEOCODER_API_KEY"))?;
+            let client = MapboxClient::new(base_url.unwrap_or_else(|| DEFAULT_MAPBOX_URL.to_string()), key);
+            Arc::new(match request_interval {
+                Some(interval) => client.with_request_interval(interval),
+                None => client,
+            })
+        }
+        other => anyhow::bail!("unknown geocoder provider: {}", other),
+    };
+    Ok(geocoder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_geocoder_selects_provider() {
+        let nominatim = build_geocoder("nominatim", Some("http://localhost".to_string()), None, None, BreakerSettings::default()).unwrap();
+        assert_eq!(nominatim.name(), "nominatim");
+
+        let photon = build_geocoder("photon", None, None, Some(Duration::ZERO), BreakerSettings::default()).unwrap();
+        assert_eq!(photon.name(), "photon");
+        assert_eq!(photon.request_interval(), Duration::ZERO);
+
+        let mapbox = build_geocoder("mapbox", None, Some("pk.test".to_string()), None, BreakerSettings::default()).unwrap();
+        assert_eq!(mapbox.name(), "mapbox");
+    }
+
+    #[test]
+    fn test_build_geocoder_rejects_invalid_config() {
+        assert!(build_geocoder("mapbox", None, Some(" ".to_string()), None, BreakerSettings::default()).is_err());
+        assert!(build_geocoder("google", None, None, None, BreakerSettings::default()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_route_locations_via_provider() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/reverse"))
+            .and(wiremock::matchers::query_param("lat", "55.1"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "features": [{ "properties": { "name": "Start Park" } }]
+            })))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/reverse"))
+            .and(wiremock::matchers::query_param("lat", "55.2"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "features": [{ "properties": { "name": "End Square" } }]
+            })))
+            .mount(&server)
+            .await;
+
+        let geocoder = build_geocoder("photon", Some(server.uri()), None, Some(Duration::ZERO), BreakerSettings::default()).unwrap();
+        let (start, end) = resolve_route_locations(geocoder.as_ref(), (55.1, 37.1), (55.2, 37.2)).await;
+
+        assert_eq!(start, "Start Park");
+        assert_eq!(end, "End Square");
+    }
+}
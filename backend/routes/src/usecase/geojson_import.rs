@@ -1,7 +1,19 @@
-use geojson::{Feature, FeatureCollection, GeoJson, Geometry, Value};
+use std::cell::Cell;
+use std::fmt::{self, Display};
+use std::io::{self, Read};
+
+use futures::{Stream, StreamExt};
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
 use thiserror::Error;
+use tokio::sync::mpsc;
+
+use crate::domain::route::{PointAddress, RoutePoint};
+use crate::usecase::gpx_import::parse_gpx;
 
-use crate::domain::route::RoutePoint;
+/// Upload chunks buffered between the request and the parser.
+const PARSER_QUEUE_CHUNKS: usize = 8;
+/// Upload progress is logged each time another this many bytes arrived.
+const PROGRESS_LOG_BYTES: usize = 1024 * 1024;
 
 #[derive(Debug, Error)]
 pub enum ImportError {
@@ -13,6 +25,107 @@ pub enum ImportError {
     EmptyRoute,
     #[error("unsupported geometry type: expected LineString or FeatureCollection of Points")]
     UnsupportedGeometry,
+    #[error("invalid GPX: {0}")]
+    InvalidGpx(String),
+    #[error("file is larger than {0} bytes")]
+    TooLarge(usize),
+    #[error("route has more than {0} points")]
+    TooManyPoints(usize),
+    #[error("failed to read file: {0}")]
+    Read(String),
+}
+
+/// File formats the import endpoint accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    GeoJson,
+    Gpx,
+}
+
+impl ImportFormat {
+    /// Guesses the format from the file name and MIME type: GPX by its
+    /// extension or MIME type, anything else GeoJSON. [`parse_upload`] still
+    /// checks the content.
+    pub fn detect(filename: Option<&str>, content_type: Option<&str>) -> Self {
+        let gpx_extension = filename
+            .and_then(|name| name.rsplit_once('.'))
+            .is_some_and(|(_, ext)| ext.eq_ignore_ascii_case("gpx"));
+        if gpx_extension || content_type == Some("application/gpx+xml") {
+            ImportFormat::Gpx
+        } else {
+            ImportFormat::GeoJson
+        }
+    }
+
+    /// Value of the `format` label of `routes_imported_total`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFormat::GeoJson => "geojson",
+            ImportFormat::Gpx => "gpx",
+        }
+    }
+}
+
+/// Recognises the format from the first bytes of a file: GPX is XML and
+/// starts with `<`, GeoJSON is a JSON object.
+fn sniff_format(head: &[u8]) -> Option<ImportFormat> {
+    let head = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+    match head.iter().find(|b| !b.is_ascii_whitespace()) {
+        Some(b'<') => Some(ImportFormat::Gpx),
+        Some(b'{') => Some(ImportFormat::GeoJson),
+        _ => None,
+    }
+}
+
+/// Parses an uploaded file. The format is recognised from the content; the
+/// `declared` one, from the file name or MIME type, is used when the content
+/// is not conclusive. GeoJSON is parsed while it arrives, see
+/// [`parse_geojson_stream`]; GPX is read whole first. Routes with more than
+/// `max_points` points are refused.
+pub async fn parse_upload<S, B, E>(
+    declared: ImportFormat,
+    stream: S,
+    max_bytes: usize,
+    max_points: usize,
+) -> Result<(ImportFormat, String, Vec<RoutePoint>), ImportError>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]> + Send + 'static,
+    E: Display,
+{
+    futures::pin_mut!(stream);
+    let first = stream.next().await;
+    let format = match &first {
+        Some(Ok(chunk)) => sniff_format(chunk.as_ref()).unwrap_or(declared),
+        _ => declared,
+    };
+    if format != declared {
+        tracing::debug!(declared = declared.as_str(), detected = format.as_str(), "file content overrides its declared format");
+    }
+    let stream = futures::stream::iter(first).chain(stream);
+
+    if format == ImportFormat::GeoJson {
+        let (name, points) = parse_geojson_stream(stream, max_bytes, max_points).await?;
+        return Ok((format, name, points));
+    }
+
+    futures::pin_mut!(stream);
+    let mut content = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| ImportError::Read(e.to_string()))?;
+        if content.len() + chunk.as_ref().len() > max_bytes {
+            tracing::warn!(max_bytes, "GPX upload exceeds the size limit");
+            return Err(ImportError::TooLarge(max_bytes));
+        }
+        content.extend_from_slice(chunk.as_ref());
+    }
+    let content = String::from_utf8(content).map_err(|_| ImportError::InvalidGpx("file must be valid UTF-8".to_string()))?;
+    let (name, points) = parse_gpx(&content)?;
+    if points.len() > max_points {
+        tracing::warn!(point_count = points.len(), max_points, "GPX route has too many points");
+        return Err(ImportError::TooManyPoints(max_points));
+    }
+    Ok((format, name, points))
 }
 
 /// Parses GeoJSON content and extracts route name and points.
@@ -23,34 +136,477 @@ pub enum ImportError {
 ///
 /// B) FeatureCollection with Point features:
 ///    { "type": "FeatureCollection", "properties": {"name": "..."}, "features": [{"type": "Feature", "geometry": {"type": "Point", "coordinates": [lng, lat]}, "properties": {"name": "point name"}}]}
+///
+/// Fails with [`ImportError::TooManyPoints`] for routes with more than
+/// `max_points` points; coordinates past the limit are not kept.
+#[cfg(test)]
 #[tracing::instrument(skip(content), fields(input_len = content.len()))]
-pub fn parse_geojson(content: &str) -> Result<(String, Vec<RoutePoint>), ImportError> {
+pub fn parse_geojson(content: &str, max_points: usize) -> Result<(String, Vec<RoutePoint>), ImportError> {
+    parse_geojson_reader(content.as_bytes(), max_points)
+}
+
+/// Same as [`parse_geojson`], reading the document from `reader`. Features of
+/// a FeatureCollection are turned into points one at a time, so the parsed
+/// document is never held in memory as a whole.
+pub fn parse_geojson_reader<R: Read>(reader: R, max_points: usize) -> Result<(String, Vec<RoutePoint>), ImportError> {
     tracing::debug!("parsing GeoJSON content");
 
-    let geojson: GeoJson = content
-        .parse()
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let document = GeoJsonDocumentSeed { max_points }
+        .deserialize(&mut deserializer)
+        .and_then(|document| deserializer.end().map(|()| document))
         .map_err(|e| ImportError::InvalidGeoJson(format!("{}", e)))?;
 
-    match geojson {
-        GeoJson::Feature(feature) => parse_feature(feature),
-        GeoJson::FeatureCollection(collection) => parse_feature_collection(collection),
-        GeoJson::Geometry(_) => {
+    match document.kind.as_deref() {
+        Some("Feature") => parse_feature(document.properties, document.geometry, max_points),
+        Some("FeatureCollection") => {
+            let points = document
+                .points
+                .ok_or_else(|| ImportError::InvalidGeoJson("FeatureCollection has no features".to_string()))?;
+            parse_feature_collection(document.name, points, max_points)
+        }
+        Some(
+            "Point" | "MultiPoint" | "LineString" | "MultiLineString" | "Polygon" | "MultiPolygon"
+            | "GeometryCollection",
+        ) => {
             tracing::warn!("received raw Geometry without Feature wrapper");
             Err(ImportError::MissingRouteName)
         }
+        Some(other) => Err(ImportError::InvalidGeoJson(format!("unknown type \"{}\"", other))),
+        None => Err(ImportError::InvalidGeoJson("missing \"type\" member".to_string())),
+    }
+}
+
+/// Parses GeoJSON from an upload as its chunks arrive. The parser runs on a
+/// blocking thread fed through a small queue, so neither the file nor the
+/// document is buffered whole. Fails with [`ImportError::TooLarge`] as soon
+/// as more than `max_bytes` were received.
+pub async fn parse_geojson_stream<S, B, E>(
+    stream: S,
+    max_bytes: usize,
+    max_points: usize,
+) -> Result<(String, Vec<RoutePoint>), ImportError>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]> + Send + 'static,
+    E: Display,
+{
+    let (tx, rx) = mpsc::channel(PARSER_QUEUE_CHUNKS);
+    let parser = tokio::task::spawn_blocking(move || parse_geojson_reader(ChunkReader::new(rx), max_points));
+
+    futures::pin_mut!(stream);
+    let mut received = 0;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| ImportError::Read(e.to_string()))?;
+        let before = received;
+        received += chunk.as_ref().len();
+        if received > max_bytes {
+            tracing::warn!(received, max_bytes, "GeoJSON upload exceeds the size limit");
+            return Err(ImportError::TooLarge(max_bytes));
+        }
+        if before / PROGRESS_LOG_BYTES != received / PROGRESS_LOG_BYTES {
+            tracing::debug!(received, max_bytes, "GeoJSON upload in progress");
+        }
+        if tx.send(chunk).await.is_err() {
+            // The parser already stopped; its result says why
+            break;
+        }
+    }
+    drop(tx);
+
+    tracing::debug!(received, "GeoJSON upload received");
+    parser
+        .await
+        .map_err(|e| ImportError::Read(format!("parser stopped: {}", e)))?
+}
+
+/// Blocking reader over the chunks sent to a channel. Reaches end of file
+/// once the sender is dropped.
+struct ChunkReader<B> {
+    chunks: mpsc::Receiver<B>,
+    current: Option<B>,
+    offset: usize,
+}
+
+impl<B> ChunkReader<B> {
+    fn new(chunks: mpsc::Receiver<B>) -> Self {
+        Self {
+            chunks,
+            current: None,
+            offset: 0,
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> Read for ChunkReader<B> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(chunk) = &self.current {
+                let rest = &chunk.as_ref()[self.offset..];
+                if !rest.is_empty() {
+                    let n = rest.len().min(buf.len());
+                    buf[..n].copy_from_slice(&rest[..n]);
+                    self.offset += n;
+                    return Ok(n);
+                }
+            }
+            match self.chunks.blocking_recv() {
+                Some(chunk) => {
+                    self.current = Some(chunk);
+                    self.offset = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+    }
+}
+/// The top-level members the importer needs. `features` is converted while
+/// it is read, see [`FeaturePoints`].
+#[derive(Default)]
+struct GeoJsonDocument {
+    kind: Option<String>,
+    name: Option<String>,
+    properties: Option<serde_json::Map<String, serde_json::Value>>,
+    geometry: Option<ParsedGeometry>,
+    points: Option<FeaturePointsRead>,
+}
+
+/// Reads a [`GeoJsonDocument`], keeping at most `max_points` points.
+struct GeoJsonDocumentSeed {
+    max_points: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for GeoJsonDocumentSeed {
+    type Value = GeoJsonDocument;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<GeoJsonDocument, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for GeoJsonDocumentSeed {
+    type Value = GeoJsonDocument;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a GeoJSON object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<GeoJsonDocument, A::Error> {
+        let mut document = GeoJsonDocument::default();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "type" => document.kind = Some(map.next_value()?),
+                "name" => {
+                    let name: serde_json::Value = map.next_value()?;
+                    document.name = name.as_str().map(|s| s.to_string());
+                }
+                "properties" => document.properties = map.next_value()?,
+                "geometry" => {
+                    document.geometry = map.next_value_seed(GeometrySeed { max_points: self.max_points })?
+                }
+                "features" => {
+                    document.points = Some(map.next_value_seed(FeaturePoints { max_points: self.max_points })?)
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(document)
+    }
+}
+
+/// Counts what a geometry keeps in memory, so an oversized one is refused
+/// without being held whole.
+struct PointBudget {
+    max: usize,
+    used: Cell<usize>,
+    exceeded: Cell<bool>,
+}
+
+impl PointBudget {
+    fn new(max: usize) -> Self {
+        Self {
+            max,
+            used: Cell::new(0),
+            exceeded: Cell::new(false),
+        }
+    }
+
+    /// Whether one more entry may be kept.
+    fn take(&self) -> bool {
+        if self.used.get() >= self.max {
+            self.exceeded.set(true);
+            return false;
+        }
+        self.used.set(self.used.get() + 1);
+        true
+    }
+}
+
+/// `coordinates` of a geometry. Positions keep only longitude and latitude,
+/// so extra dimensions cost nothing.
+#[derive(Debug)]
+enum Coordinates {
+    /// None for positions with fewer than two numbers.
+    Position(Option<(f64, f64)>),
+    List(Vec<Coordinates>),
+}
+
+/// Reads coordinates of any depth. Past the budget, entries are read and
+/// dropped.
+struct CoordinatesSeed<'a>(&'a PointBudget);
+
+impl<'de> DeserializeSeed<'de> for CoordinatesSeed<'_> {
+    type Value = Coordinates;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Coordinates, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for CoordinatesSeed<'_> {
+    type Value = Coordinates;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("GeoJSON coordinates")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Coordinates, A::Error> {
+        let mut numbers = [0.0; 2];
+        let mut number_count = 0;
+        let mut nested_count = 0;
+        let mut list = Vec::new();
+        while let Some(element) = seq.next_element_seed(CoordinateSeed(self.0))? {
+            match element {
+                Coordinate::Number(n) if nested_count == 0 => {
+                    if let Some(slot) = numbers.get_mut(number_count) {
+                        *slot = n;
+                    }
+                    number_count += 1;
+                }
+                Coordinate::Nested(nested) if number_count == 0 => {
+                    nested_count += 1;
+                    if self.0.take() {
+                        list.push(nested);
+                    }
+                }
+                _ => return Err(serde::de::Error::custom("coordinates mix numbers and arrays")),
+            }
+        }
+
+        if number_count == 0 {
+            return Ok(Coordinates::List(list));
+        }
+        Ok(Coordinates::Position((number_count >= 2).then_some((numbers[0], numbers[1]))))
+    }
+}
+
+enum Coordinate {
+    Number(f64),
+    Nested(Coordinates),
+}
+
+/// One element of a coordinates array: a number or another array.
+struct CoordinateSeed<'a>(&'a PointBudget);
+
+impl<'de> DeserializeSeed<'de> for CoordinateSeed<'_> {
+    type Value = Coordinate;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Coordinate, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for CoordinateSeed<'_> {
+    type Value = Coordinate;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a coordinate number or array")
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Coordinate, E> {
+        if !v.is_finite() {
+            return Err(E::custom("coordinates must be finite numbers"));
+        }
+        Ok(Coordinate::Number(v))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Coordinate, E> {
+        Ok(Coordinate::Number(v as f64))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Coordinate, E> {
+        Ok(Coordinate::Number(v as f64))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Coordinate, A::Error> {
+        CoordinatesSeed(self.0).visit_seq(seq).map(Coordinate::Nested)
+    }
+}
+
+/// The members of a geometry the importer reads.
+struct ParsedGeometry {
+    kind: Option<String>,
+    coordinates: Option<Coordinates>,
+    /// Had more entries than allowed; the rest were dropped.
+    truncated: bool,
+}
+
+/// Reads a geometry or `null`, keeping at most `max_points` entries of its
+/// coordinates.
+struct GeometrySeed {
+    max_points: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for GeometrySeed {
+    type Value = Option<ParsedGeometry>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<ParsedGeometry>, D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de> Visitor<'de> for GeometrySeed {
+    type Value = Option<ParsedGeometry>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a GeoJSON geometry or null")
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<Option<ParsedGeometry>, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Option<ParsedGeometry>, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<ParsedGeometry>, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Option<ParsedGeometry>, A::Error> {
+        let budget = PointBudget::new(self.max_points);
+        let mut kind = None;
+        let mut coordinates = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "type" => kind = Some(map.next_value()?),
+                "coordinates" => coordinates = Some(map.next_value_seed(CoordinatesSeed(&budget))?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(Some(ParsedGeometry {
+            kind,
+            coordinates,
+            truncated: budget.exceeded.get(),
+        }))
+    }
+}
+
+/// A feature of a FeatureCollection.
+#[derive(Default)]
+struct ParsedFeature {
+    properties: Option<serde_json::Map<String, serde_json::Value>>,
+    geometry: Option<ParsedGeometry>,
+}
+
+struct FeatureSeed {
+    max_points: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for FeatureSeed {
+    type Value = ParsedFeature;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<ParsedFeature, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FeatureSeed {
+    type Value = ParsedFeature;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a GeoJSON feature")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<ParsedFeature, A::Error> {
+        let mut feature = ParsedFeature::default();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "properties" => feature.properties = map.next_value()?,
+                "geometry" => feature.geometry = map.next_value_seed(GeometrySeed { max_points: self.max_points })?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(feature)
+    }
+}
+
+/// Points of a `features` array.
+struct FeaturePointsRead {
+    points: Vec<RoutePoint>,
+    /// More than `max_points` Point features; the rest were dropped.
+    truncated: bool,
+}
+
+/// Reads a `features` array, keeping only the points of its Point features.
+struct FeaturePoints {
+    max_points: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for FeaturePoints {
+    type Value = FeaturePointsRead;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<FeaturePointsRead, D::Error> {
+        deserializer.deserialize_seq(self)
     }
 }
 
-fn parse_feature(feature: Feature) -> Result<(String, Vec<RoutePoint>), ImportError> {
+impl<'de> Visitor<'de> for FeaturePoints {
+    type Value = FeaturePointsRead;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of GeoJSON features")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<FeaturePointsRead, A::Error> {
+        let mut points = Vec::new();
+        let mut truncated = false;
+        let mut idx = 0;
+        // A Point has a single position, so one entry is enough
+        while let Some(feature) = seq.next_element_seed(FeatureSeed { max_points: 1 })? {
+            if let Some(point) = point_from_feature(idx, feature) {
+                if points.len() < self.max_points {
+                    points.push(point);
+                } else {
+                    truncated = true;
+                }
+            }
+            idx += 1;
+        }
+        tracing::debug!(feature_count = idx, point_count = points.len(), truncated, "read FeatureCollection features");
+        Ok(FeaturePointsRead { points, truncated })
+    }
+}
+
+fn parse_feature(
+    properties: Option<serde_json::Map<String, serde_json::Value>>,
+    geometry: Option<ParsedGeometry>,
+    max_points: usize,
+) -> Result<(String, Vec<RoutePoint>), ImportError> {
     tracing::debug!("parsing Feature");
 
-    let name = extract_name_from_properties(&feature.properties)?;
+    let name = extract_name_from_properties(&properties)?;
 
-    let geometry = feature
-        .geometry
-        .ok_or_else(|| ImportError::InvalidGeoJson("Feature has no geometry".to_string()))?;
+    let geometry = geometry.ok_or_else(|| ImportError::InvalidGeoJson("Feature has no geometry".to_string()))?;
 
-    let points = parse_linestring_geometry(&geometry)?;
+    let points = parse_linestring_geometry(geometry, max_points)?;
 
     if points.is_empty() {
         tracing::warn!("parsed LineString has no points");
@@ -67,79 +623,23 @@ fn parse_feature(feature: Feature) -> Result<(String, Vec<RoutePoint>), ImportEr
 }
 
 fn parse_feature_collection(
-    collection: FeatureCollection,
+    name: Option<String>,
+    points: FeaturePointsRead,
+    max_points: usize,
 ) -> Result<(String, Vec<RoutePoint>), ImportError> {
-    tracing::debug!(
-        feature_count = collection.features.len(),
-        "parsing FeatureCollection"
-    );
-
-    // Try to get name from FeatureCollection's foreign_members
-    let name = collection
-        .foreign_members
-        .as_ref()
-        .and_then(|fm| fm.get("name"))
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-        .ok_or_else(|| {
-            tracing::warn!("FeatureCollection missing 'name' in foreign_members");
-            ImportError::MissingRouteName
-        })?;
-
-    let mut points = Vec::new();
+    tracing::debug!(point_count = points.points.len(), "parsing FeatureCollection");
 
-    for (idx, feature) in collection.features.into_iter().enumerate() {
-        let geometry = match feature.geometry {
-            Some(g) => g,
-            None => {
-                tracing::debug!(feature_idx = idx, "skipping feature without geometry");
-                continue;
-            }
-        };
-
-        match &geometry.value {
-            Value::Point(coords) => {
-                if coords.len() >= 2 {
-                    let point_name = feature
-                        .properties
-                        .as_ref()
-                        .and_then(|p| p.get("name"))
-                        .and_then(|v| v.as_str())
-                        .map(|s| s.to_string());
-
-                    let point = RoutePoint {
-                        lng: coords[0],
-                        lat: coords[1],
-                        name: point_name,
-                        segment_mode: None,
-                        photo: None,
-                    };
-
-                    tracing::trace!(
-                        feature_idx = idx,
-                        lat = point.lat,
-                        lng = point.lng,
-                        point_name = ?point.name,
-                        "parsed Point feature"
-                    );
-
-                    points.push(point);
-                } else {
-                    tracing::warn!(
-                        feature_idx = idx,
-                        coord_len = coords.len(),
-                        "Point has insufficient coordinates"
-                    );
-                }
-            }
-            _ => {
-                tracing::debug!(
-                    feature_idx = idx,
-                    "skipping non-Point geometry in FeatureCollection"
-                );
-            }
-        }
+    if points.truncated {
+        tracing::warn!(max_points, "FeatureCollection has too many points");
+        return Err(ImportError::TooManyPoints(max_points));
     }
+    let points = points.points;
+
+    // The name is a foreign member of the FeatureCollection
+    let name = name.ok_or_else(|| {
+        tracing::warn!("FeatureCollection missing 'name' in foreign_members");
+        ImportError::MissingRouteName
+    })?;
 
     if points.is_empty() {
         tracing::warn!("FeatureCollection has no valid Point features");
@@ -155,6 +655,65 @@ fn parse_feature_collection(
     Ok((name, points))
 }
 
+fn point_from_feature(idx: usize, feature: ParsedFeature) -> Option<RoutePoint> {
+    let geometry = match feature.geometry {
+        Some(g) => g,
+        None => {
+            tracing::debug!(feature_idx = idx, "skipping feature without geometry");
+            return None;
+        }
+    };
+
+    if geometry.kind.as_deref() != Some("Point") {
+        tracing::debug!(
+            feature_idx = idx,
+            "skipping non-Point geometry in FeatureCollection"
+        );
+        return None;
+    }
+
+    match geometry.coordinates {
+        Some(Coordinates::Position(Some((lng, lat)))) => {
+            let point_name = feature
+                .properties
+                .as_ref()
+                .and_then(|p| p.get("name"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+            // Written by route exports; malformed values are dropped
+            let address = feature
+                .properties
+                .as_ref()
+                .and_then(|p| p.get("address"))
+                .and_then(|v| serde_json::from_value::<PointAddress>(v.clone()).ok())
+                .filter(|a| !a.is_empty());
+
+            let point = RoutePoint {
+                lng,
+                lat,
+                name: point_name,
+                segment_mode: None,
+                photo: None,
+                address,
+            };
+
+            tracing::trace!(
+                feature_idx = idx,
+                lat = point.lat,
+                lng = point.lng,
+                point_name = ?point.name,
+                "parsed Point feature"
+            );
+
+            Some(point)
+        }
+        _ => {
+            tracing::warn!(feature_idx = idx, "Point has invalid coordinates");
+            None
+        }
+    }
+}
+
 fn extract_name_from_properties(
     properties: &Option<serde_json::Map<String, serde_json::Value>>,
 ) -> Result<String, ImportError> {
@@ -169,47 +728,64 @@ fn extract_name_from_properties(
         })
 }
 
-fn parse_linestring_geometry(geometry: &Geometry) -> Result<Vec<RoutePoint>, ImportError> {
-    match &geometry.value {
-        Value::LineString(coords) => {
-            tracing::debug!(coord_count = coords.len(), "parsing LineString coordinates");
-
-            let points: Vec<RoutePoint> = coords
-                .iter()
-                .enumerate()
-                .filter_map(|(idx, coord)| {
-                    if coord.len() >= 2 {
-                        Some(RoutePoint {
-                            lng: coord[0],
-                            lat: coord[1],
-                            name: None,
-                            segment_mode: None,
-                            photo: None,
-                        })
-                    } else {
-                        tracing::warn!(
-                            coord_idx = idx,
-                            coord_len = coord.len(),
-                            "skipping coordinate with insufficient dimensions"
-                        );
-                        None
-                    }
-                })
-                .collect();
+fn parse_linestring_geometry(geometry: ParsedGeometry, max_points: usize) -> Result<Vec<RoutePoint>, ImportError> {
+    match geometry.kind.as_deref() {
+        Some("LineString") => {}
+        Some(
+            "Point" | "MultiPoint" | "MultiLineString" | "Polygon" | "MultiPolygon" | "GeometryCollection",
+        ) => {
+            tracing::warn!("expected LineString geometry, got different type");
+            return Err(ImportError::UnsupportedGeometry);
+        }
+        Some(other) => return Err(ImportError::InvalidGeoJson(format!("unknown geometry type \"{}\"", other))),
+        None => return Err(ImportError::InvalidGeoJson("geometry has no \"type\" member".to_string())),
+    }
+
+    if geometry.truncated {
+        tracing::warn!(max_points, "LineString has too many points");
+        return Err(ImportError::TooManyPoints(max_points));
+    }
 
-            Ok(points)
+    let coords = match geometry.coordinates {
+        Some(Coordinates::List(coords)) => coords,
+        Some(Coordinates::Position(_)) => {
+            return Err(ImportError::InvalidGeoJson("LineString coordinates must be an array of positions".to_string()))
         }
-        _ => {
-            tracing::warn!("expected LineString geometry, got different type");
-            Err(ImportError::UnsupportedGeometry)
+        None => return Err(ImportError::InvalidGeoJson("LineString has no coordinates".to_string())),
+    };
+    tracing::debug!(coord_count = coords.len(), "parsing LineString coordinates");
+
+    let mut points = Vec::with_capacity(coords.len());
+    for (idx, coord) in coords.into_iter().enumerate() {
+        match coord {
+            Coordinates::Position(Some((lng, lat))) => points.push(RoutePoint {
+                lng,
+                lat,
+                name: None,
+                segment_mode: None,
+                photo: None,
+                address: None,
+            }),
+            Coordinates::Position(None) => {
+                tracing::warn!(coord_idx = idx, "skipping coordinate with insufficient dimensions");
+            }
+            Coordinates::List(_) => {
+                return Err(ImportError::InvalidGeoJson("LineString coordinates must be an array of positions".to_string()))
+            }
         }
     }
+
+    Ok(points)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
+    const MAX_POINTS: usize = 1000;
+
     #[test]
     fn test_parse_linestring_feature() {
         let geojson = r#"{
@@ -221,7 +797,7 @@ mod tests {
             }
         }"#;
 
-        let (name, points) = parse_geojson(geojson).unwrap();
+        let (name, points) = parse_geojson(geojson, MAX_POINTS).unwrap();
 
         assert_eq!(name, "Test Route");
         assert_eq!(points.len(), 2);
@@ -250,7 +826,7 @@ mod tests {
             ]
         }"#;
 
-        let (name, points) = parse_geojson(geojson).unwrap();
+        let (name, points) = parse_geojson(geojson, MAX_POINTS).unwrap();
 
         assert_eq!(name, "My Route");
         assert_eq!(points.len(), 2);
@@ -271,7 +847,7 @@ mod tests {
             }
         }"#;
 
-        let result = parse_geojson(geojson);
+        let result = parse_geojson(geojson, MAX_POINTS);
         assert!(matches!(result, Err(ImportError::MissingRouteName)));
     }
 
@@ -286,7 +862,7 @@ mod tests {
             }
         }"#;
 
-        let result = parse_geojson(geojson);
+        let result = parse_geojson(geojson, MAX_POINTS);
         assert!(matches!(result, Err(ImportError::EmptyRoute)));
     }
 
@@ -301,7 +877,7 @@ mod tests {
             }
         }"#;
 
-        let result = parse_geojson(geojson);
+        let result = parse_geojson(geojson, MAX_POINTS);
         assert!(matches!(result, Err(ImportError::UnsupportedGeometry)));
     }
 
@@ -309,7 +885,232 @@ mod tests {
     fn test_invalid_json_returns_error() {
         let geojson = "not valid json";
 
-        let result = parse_geojson(geojson);
+        let result = parse_geojson(geojson, MAX_POINTS);
         assert!(matches!(result, Err(ImportError::InvalidGeoJson(_))));
     }
+
+    fn chunked(content: &str, size: usize) -> impl Stream<Item = Result<Vec<u8>, std::convert::Infallible>> {
+        let chunks: Vec<_> = content.as_bytes().chunks(size).map(|c| Ok(c.to_vec())).collect();
+        futures::stream::iter(chunks)
+    }
+
+    #[tokio::test]
+    async fn test_parse_stream_reads_features_split_across_chunks() {
+        let features: Vec<String> = (0..500)
+            .map(|i| {
+                format!(
+                    r#"{{"type": "Feature", "properties": {{"name": "P{}"}}, "geometry": {{"type": "Point", "coordinates": [{}.5, 55.0]}}}}"#,
+                    i, i % 180
+                )
+            })
+            .collect();
+        let geojson = format!(
+            r#"{{"features": [{}], "type": "FeatureCollection", "name": "Long walk"}}"#,
+            features.join(",")
+        );
+
+        let (name, points) = parse_geojson_stream(chunked(&geojson, 7), geojson.len(), MAX_POINTS).await.unwrap();
+
+        assert_eq!(name, "Long walk");
+        assert_eq!(points.len(), 500);
+        assert_eq!(points[499].name.as_deref(), Some("P499"));
+    }
+
+    #[tokio::test]
+    async fn test_parse_stream_rejects_oversized_upload() {
+        let geojson = r#"{"type": "Feature", "properties": {"name": "Walk"}, "geometry": {"type": "LineString", "coordinates": [[37.6, 55.7], [37.7, 55.8]]}}"#;
+
+        let result = parse_geojson_stream(chunked(geojson, 16), 64, MAX_POINTS).await;
+
+        assert!(matches!(result, Err(ImportError::TooLarge(64))));
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(ImportFormat::detect(Some("walk.GPX"), None), ImportFormat::Gpx);
+        assert_eq!(ImportFormat::detect(Some("walk"), Some("application/gpx+xml")), ImportFormat::Gpx);
+        assert_eq!(ImportFormat::detect(Some("walk.geojson"), Some("application/json")), ImportFormat::GeoJson);
+        assert_eq!(ImportFormat::detect(None, None), ImportFormat::GeoJson);
+    }
+
+    #[tokio::test]
+    async fn test_parse_upload_detects_gpx_from_content() {
+        let gpx = "\u{feff}\n<?xml version=\"1.0\"?><gpx><trk><name>Garmin walk</name><trkseg><trkpt lat=\"55.1\" lon=\"49.1\"/></trkseg></trk></gpx>";
+
+        let (format, name, points) = parse_upload(ImportFormat::GeoJson, chunked(gpx, 16), gpx.len(), MAX_POINTS).await.unwrap();
+
+        assert_eq!(format, ImportFormat::Gpx);
+        assert_eq!(name, "Garmin walk");
+        assert_eq!(points.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_parse_upload_detects_geojson_from_content() {
+        let geojson = r#"{"type": "Feature", "properties": {"name": "Walk"}, "geometry": {"type": "LineString", "coordinates": [[37.6, 55.7]]}}"#;
+
+        let (format, _, points) = parse_upload(ImportFormat::Gpx, chunked(geojson, 16), geojson.len(), MAX_POINTS).await.unwrap();
+
+        assert_eq!(format, ImportFormat::GeoJson);
+        assert_eq!(points.len(), 1);
+    }
+
+    #[test]
+    fn test_trailing_content_returns_error() {
+        let geojson = r#"{"type": "Feature", "properties": {"name": "Walk"}, "geometry": null} {}"#;
+
+        let result = parse_geojson(geojson, MAX_POINTS);
+        assert!(matches!(result, Err(ImportError::InvalidGeoJson(_))));
+    }
+
+    fn linestring(coordinates: &str) -> String {
+        format!(
+            r#"{{"type": "Feature", "properties": {{"name": "Walk"}}, "geometry": {{"type": "LineString", "coordinates": {}}}}}"#,
+            coordinates
+        )
+    }
+
+    #[test]
+    fn test_deep_nesting_returns_error() {
+        let nested = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+
+        let in_coordinates = parse_geojson(&linestring(&nested), MAX_POINTS);
+        let in_properties = parse_geojson(&format!(r#"{{"type": "Feature", "properties": {}}}"#, nested), MAX_POINTS);
+
+        assert!(matches!(in_coordinates, Err(ImportError::InvalidGeoJson(_))));
+        assert!(matches!(in_properties, Err(ImportError::InvalidGeoJson(_))));
+    }
+
+    #[test]
+    fn test_non_finite_coordinates_return_error() {
+        for coordinate in ["1e999", "-1e999", "NaN", "Infinity", "\"37.6\"", "null"] {
+            let geojson = linestring(&format!("[[{}, 55.7]]", coordinate));
+
+            let result = parse_geojson(&geojson, MAX_POINTS);
+            assert!(matches!(result, Err(ImportError::InvalidGeoJson(_))), "{}", coordinate);
+        }
+    }
+
+    #[test]
+    fn test_linestring_over_point_limit_returns_error() {
+        let coordinates = |count| format!("[{}]", vec!["[37.6, 55.7]"; count].join(","));
+
+        let at_limit = parse_geojson(&linestring(&coordinates(MAX_POINTS)), MAX_POINTS).unwrap();
+        let over_limit = parse_geojson(&linestring(&coordinates(MAX_POINTS + 1)), MAX_POINTS);
+
+        assert_eq!(at_limit.1.len(), MAX_POINTS);
+        assert!(matches!(over_limit, Err(ImportError::TooManyPoints(MAX_POINTS))));
+    }
+
+    #[test]
+    fn test_feature_collection_over_point_limit_returns_error() {
+        let feature = r#"{"type": "Feature", "geometry": {"type": "Point", "coordinates": [37.6, 55.7]}}"#;
+        let geojson = format!(
+            r#"{{"type": "FeatureCollection", "name": "Walk", "features": [{}]}}"#,
+            vec![feature; MAX_POINTS + 1].join(",")
+        );
+
+        let result = parse_geojson(&geojson, MAX_POINTS);
+        assert!(matches!(result, Err(ImportError::TooManyPoints(MAX_POINTS))));
+    }
+
+    #[test]
+    fn test_long_position_keeps_longitude_and_latitude() {
+        let position = (0..100_000).map(|i| i.to_string()).collect::<Vec<_>>().join(",");
+        let huge_line = format!(r#"{{"type": "LineString", "coordinates": [{}]}}"#, vec!["[0, 0]"; 100_000].join(","));
+        let geojson = format!(
+            r#"{{"type": "FeatureCollection", "name": "Walk", "features": [
+                {{"type": "Feature", "geometry": {{"type": "Point", "coordinates": [{}]}}}},
+                {{"type": "Feature", "geometry": {}}}
+            ]}}"#,
+            position, huge_line
+        );
+
+        let (_, points) = parse_geojson(&geojson, MAX_POINTS).unwrap();
+
+        assert_eq!(points.len(), 1);
+        assert_eq!((points[0].lng, points[0].lat), (0.0, 1.0));
+    }
+
+    /// Arbitrary JSON, nested up to a few levels.
+    fn json_value() -> impl Strategy<Value = serde_json::Value> {
+        let leaf = prop_oneof![
+            Just(serde_json::Value::Null),
+            any::<bool>().prop_map(serde_json::Value::from),
+            any::<i64>().prop_map(serde_json::Value::from),
+            any::<f64>().prop_map(serde_json::Value::from),
+            ".{0,8}".prop_map(serde_json::Value::from),
+        ];
+        leaf.prop_recursive(4, 64, 8, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..8).prop_map(serde_json::Value::from),
+                prop::collection::hash_map(
+                    prop_oneof![Just("type".to_string()), Just("coordinates".to_string()), ".{0,4}"],
+                    inner,
+                    0..4,
+                )
+                .prop_map(|m| serde_json::Value::Object(m.into_iter().collect())),
+            ]
+        })
+    }
+
+    fn geometry_type() -> impl Strategy<Value = String> {
+        prop_oneof![Just("LineString".to_string()), Just("Point".to_string()), Just("Polygon".to_string()), ".{0,8}"]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_arbitrary_bytes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+            let _ = parse_geojson_reader(bytes.as_slice(), MAX_POINTS);
+        }
+
+        #[test]
+        fn prop_malformed_documents_return_bounded_points(
+            kind in prop_oneof![Just("Feature"), Just("FeatureCollection")],
+            geometry_kind in geometry_type(),
+            coordinates in json_value(),
+            properties in json_value(),
+            features in prop::collection::vec((geometry_type(), json_value(), json_value()), 0..8),
+            max_points in 0..8usize,
+        ) {
+            let features: Vec<_> = features
+                .into_iter()
+                .map(|(kind, coordinates, properties)| serde_json::json!({
+                    "type": "Feature",
+                    "geometry": {"type": kind, "coordinates": coordinates},
+                    "properties": properties,
+                }))
+                .collect();
+            let document = serde_json::json!({
+                "type": kind,
+                "name": "Walk",
+                "properties": properties,
+                "geometry": {"type": geometry_kind, "coordinates": coordinates},
+                "features": features,
+            });
+
+            if let Ok((_, points)) = parse_geojson(&document.to_string(), max_points) {
+                prop_assert!(!points.is_empty() && points.len() <= max_points);
+                prop_assert!(points.iter().all(|p| p.lng.is_finite() && p.lat.is_finite()));
+            }
+        }
+
+        #[test]
+        fn prop_linestring_round_trips(
+            coordinates in prop::collection::vec((-180.0..180.0f64, -90.0..90.0f64), 1..50),
+        ) {
+            let geojson = serde_json::json!({
+                "type": "Feature",
+                "properties": {"name": "Walk"},
+                "geometry": {"type": "LineString", "coordinates": coordinates.iter().map(|&(lng, lat)| [lng, lat]).collect::<Vec<_>>()},
+            });
+
+            let (_, points) = parse_geojson(&geojson.to_string(), MAX_POINTS).unwrap();
+
+            // serde_json may round the last digit of a float
+            prop_assert_eq!(points.len(), coordinates.len());
+            for (point, (lng, lat)) in points.iter().zip(coordinates) {
+                prop_assert!((point.lng - lng).abs() < 1e-9 && (point.lat - lat).abs() < 1e-9);
+            }
+        }
+    }
 }
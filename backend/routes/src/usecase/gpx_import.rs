@@ -0,0 +1,304 @@
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+use crate::domain::route::RoutePoint;
+use crate::usecase::geojson_import::ImportError;
+
+/// Parses GPX content and extracts route name and points.
+///
+/// Points come from the track (`trk/trkseg/trkpt`); files without a track
+/// fall back to the route (`rte/rtept`), then to waypoints (`wpt`). The name
+/// is taken from `metadata`, else from the first track or route.
+#[tracing::instrument(skip(content), fields(input_len = content.len()))]
+pub fn parse_gpx(content: &str) -> Result<(String, Vec<RoutePoint>), ImportError> {
+    tracing::debug!("parsing GPX content");
+
+    // A byte order mark or blank line before the XML declaration is invalid
+    // XML but common in exported files
+    let content = content.trim_start_matches('\u{feff}').trim_start();
+    let mut parser = GpxParser::default();
+    for token in Tokenizer::from(content) {
+        let token = token.map_err(|e| ImportError::InvalidGpx(e.to_string()))?;
+        parser.handle(token)?;
+    }
+    if !parser.stack.is_empty() {
+        return Err(ImportError::InvalidGpx("unexpected end of document".to_string()));
+    }
+
+    let (kind, points) = [("track", parser.track), ("route", parser.route), ("waypoints", parser.waypoints)]
+        .into_iter()
+        .find(|(_, points)| !points.is_empty())
+        .ok_or_else(|| {
+            tracing::warn!("GPX has no track, route or waypoints");
+            ImportError::EmptyRoute
+        })?;
+
+    let name = parser.metadata_name.or(parser.track_name).ok_or_else(|| {
+        tracing::warn!("GPX has no name in metadata, track or route");
+        ImportError::MissingRouteName
+    })?;
+
+    tracing::info!(route_name = %name, point_count = points.len(), source = kind, "successfully parsed GPX");
+
+    Ok((name, points))
+}
+
+#[derive(Default)]
+struct GpxParser<'a> {
+    stack: Vec<&'a str>,
+    opening: Option<&'a str>,
+    lat: Option<f64>,
+    lng: Option<f64>,
+    point: Option<RoutePoint>,
+    text: String,
+    metadata_name: Option<String>,
+    track_name: Option<String>,
+    track: Vec<RoutePoint>,
+    route: Vec<RoutePoint>,
+    waypoints: Vec<RoutePoint>,
+}
+
+fn is_point(element: &str) -> bool {
+    matches!(element, "trkpt" | "rtept" | "wpt")
+}
+
+fn parse_coordinate(value: &str, name: &str) -> Result<f64, ImportError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| ImportError::InvalidGpx(format!("invalid {} \"{}\"", name, value)))
+}
+
+impl<'a> GpxParser<'a> {
+    fn handle(&mut self, token: Token<'a>) -> Result<(), ImportError> {
+        match token {
+            Token::ElementStart { local, .. } => {
+                self.opening = Some(local.as_str());
+                self.lat = None;
+                self.lng = None;
+            }
+            Token::Attribute { local, value, .. } if self.opening.is_some_and(is_point) => match local.as_str() {
+                "lat" => self.lat = Some(parse_coordinate(value.as_str(), "lat")?),
+                "lon" => self.lng = Some(parse_coordinate(value.as_str(), "lon")?),
+                _ => {}
+            },
+            Token::ElementEnd { end: ElementEnd::Open, .. } => {
+                let element = self.opening.take().unwrap_or_default();
+                if is_point(element) {
+                    self.point = Some(self.start_point()?);
+                }
+                if element == "name" {
+                    self.text.clear();
+                }
+                self.stack.push(element);
+            }
+            Token::ElementEnd { end: ElementEnd::Empty, .. } => {
+                let element = self.opening.take().unwrap_or_default();
+                if is_point(element) {
+                    let point = self.start_point()?;
+                    self.finish_point(element, point);
+                }
+            }
+            Token::ElementEnd { end: ElementEnd::Close(_, local), .. } => {
+                let element = self.stack.pop();
+                if element != Some(local.as_str()) {
+                    return Err(ImportError::InvalidGpx(format!("unexpected </{}>", local.as_str())));
+                }
+                match local.as_str() {
+                    "name" => self.finish_name(),
+                    point if is_point(point) => {
+                        if let Some(finished) = self.point.take() {
+                            self.finish_point(point, finished);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Token::Text { text } if self.stack.last() == Some(&"name") => self.text.push_str(&unescape(text.as_str())),
+            Token::Cdata { text, .. } if self.stack.last() == Some(&"name") => self.text.push_str(text.as_str()),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn start_point(&self) -> Result<RoutePoint, ImportError> {
+        let (Some(lat), Some(lng)) = (self.lat, self.lng) else {
+            return Err(ImportError::InvalidGpx("point without lat and lon".to_string()));
+        };
+        Ok(RoutePoint {
+            lat,
+            lng,
+            name: None,
+            segment_mode: None,
+            photo: None,
+            address: None,
+        })
+    }
+
+    fn finish_point(&mut self, element: &str, point: RoutePoint) {
+        match element {
+            "trkpt" => self.track.push(point),
+            "rtept" => self.route.push(point),
+            _ => self.waypoints.push(point),
+        }
+    }
+
+    fn finish_name(&mut self) {
+        let name = self.text.trim();
+        if name.is_empty() {
+            return;
+        }
+        match self.stack.last().copied() {
+            Some("metadata") if self.metadata_name.is_none() => self.metadata_name = Some(name.to_string()),
+            Some("trk" | "rte") if self.track_name.is_none() => self.track_name = Some(name.to_string()),
+            Some(element) if is_point(element) => {
+                if let Some(point) = self.point.as_mut() {
+                    point.name = Some(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Resolves the predefined XML entities and numeric character references.
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else { break };
+        let entity = &rest[1..end];
+        let resolved = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(|dec| dec.parse()))
+                .and_then(|code| code.ok())
+                .and_then(char::from_u32),
+        };
+        match resolved {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_track() {
+        let gpx = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1">
+                <metadata><name>Morning &amp; evening</name></metadata>
+                <trk>
+                    <name>Track name</name>
+                    <trkseg>
+                        <trkpt lat="55.7558" lon="37.6173"><ele>150</ele></trkpt>
+                        <trkpt lat="59.9343" lon="30.3351"/>
+                    </trkseg>
+                </trk>
+                <wpt lat="1.0" lon="2.0"><name>Ignored</name></wpt>
+            </gpx>"#;
+
+        let (name, points) = parse_gpx(gpx).unwrap();
+
+        assert_eq!(name, "Morning & evening");
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].lat, 55.7558);
+        assert_eq!(points[0].lng, 37.6173);
+        assert_eq!(points[1].lng, 30.3351);
+    }
+
+    #[test]
+    fn test_parse_garmin_export_with_segments_and_extensions() {
+        let gpx = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <gpx creator="Garmin Connect" version="1.1"
+                xmlns="http://www.topografix.com/GPX/1/1"
+                xmlns:ns3="http://www.garmin.com/xmlschemas/TrackPointExtension/v1">
+              <metadata><time>2026-05-01T08:00:00.000Z</time></metadata>
+              <wpt lat="55.79" lon="49.10"><name>Start</name></wpt>
+              <trk>
+                <name>Kazan Walking</name>
+                <type>walking</type>
+                <trkseg>
+                  <trkpt lat="55.7961" lon="49.1064">
+                    <ele>60.4</ele><time>2026-05-01T08:00:01.000Z</time>
+                    <extensions><ns3:TrackPointExtension><ns3:hr>92</ns3:hr></ns3:TrackPointExtension></extensions>
+                  </trkpt>
+                  <trkpt lat="55.7965" lon="49.1070"><ele>61.0</ele></trkpt>
+                </trkseg>
+                <trkseg>
+                  <trkpt lat="55.7970" lon="49.1081"/>
+                </trkseg>
+              </trk>
+            </gpx>"#;
+
+        let (name, points) = parse_gpx(gpx).unwrap();
+
+        assert_eq!(name, "Kazan Walking");
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[2].lat, 55.7970);
+        assert_eq!(points[2].lng, 49.1081);
+    }
+
+    #[test]
+    fn test_parse_waypoints_when_no_track() {
+        let gpx = r#"<gpx><metadata><name>Sights</name></metadata>
+            <wpt lat="55.0" lon="49.0"><name>Kremlin</name></wpt>
+            <wpt lat="55.1" lon="49.1"/>
+        </gpx>"#;
+
+        let (name, points) = parse_gpx(gpx).unwrap();
+
+        assert_eq!(name, "Sights");
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].name.as_deref(), Some("Kremlin"));
+    }
+
+    #[test]
+    fn test_parse_route_points_with_names() {
+        let gpx = r#"<gpx><rte><name>City loop</name>
+            <rtept lat="55.0" lon="49.0"><name>Kremlin</name></rtept>
+            <rtept lat="55.1" lon="49.1"><name><![CDATA[Lake]]></name></rtept>
+        </rte></gpx>"#;
+
+        let (name, points) = parse_gpx(gpx).unwrap();
+
+        assert_eq!(name, "City loop");
+        assert_eq!(points[0].name.as_deref(), Some("Kremlin"));
+        assert_eq!(points[1].name.as_deref(), Some("Lake"));
+    }
+
+    #[test]
+    fn test_missing_name_returns_error() {
+        let gpx = r#"<gpx><trk><trkseg><trkpt lat="1" lon="2"/></trkseg></trk></gpx>"#;
+
+        assert!(matches!(parse_gpx(gpx), Err(ImportError::MissingRouteName)));
+    }
+
+    #[test]
+    fn test_invalid_gpx_returns_error() {
+        assert!(matches!(parse_gpx(r#"<gpx><trk><name>A</name></gpx>"#), Err(ImportError::InvalidGpx(_))));
+        assert!(matches!(
+            parse_gpx(r#"<gpx><trk><name>A</name><trkseg><trkpt lat="x" lon="2"/></trkseg></trk></gpx>"#),
+            Err(ImportError::InvalidGpx(_))
+        ));
+        assert!(matches!(parse_gpx(r#"<gpx><trk><name>A</name></trk></gpx>"#), Err(ImportError::EmptyRoute)));
+    }
+}
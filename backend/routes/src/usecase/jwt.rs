@@ -1,79 +0,0 @@
-use serde::{Deserialize, Serialize};
-use thiserror::Error;
-
-#[derive(Debug, Error)]
-pub enum JwtError {
-    #[error("Failed to validate token: {0}")]
-    TokenValidationError(String),
-    #[error("Token expired")]
-    TokenExpired,
-    #[error("Invalid token")]
-    InvalidToken,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct Claims {
-    pub sub: String,      // Subject (user id)
-    pub email: String,    // User email
-    #[serde(default = "default_role")]
-    pub role: String,     // User role (backward compat: defaults to "user")
-    pub exp: i64,         // Expiration time
-    pub iat: i64,         // Issued at
-    pub token_type: TokenType,
-}
-
-fn default_role() -> String {
-    "user".to_string()
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub enum TokenType {
-    Access,
-    Refresh,
-}
-
-#[derive(Clone)]
-pub struct JwtService {
-    secret: String,
-}
-
-impl JwtService {
-    pub fn new(secret: String) -> Self {
-        Self { secret }
-    }
-
-    pub fn validate_token(&self, token: &str) -> Result<Claims, JwtError> {
-        let mut validation = jsonwebtoken::Validation::default();
-        validation.validate_exp = true;
-
-        let token_data = jsonwebtoken::decode::<Claims>(
-            token,
-            &jsonwebtoken::DecodingKey::from_secret(self.secret.as_bytes()),
-            &validation,
-        )
-        .map_err(|e| match e.kind() {
-            jsonwebtoken::errors::ErrorKind::ExpiredSignature => JwtError::TokenExpired,
-            _ => JwtError::TokenValidationError(e.to_string()),
-        })?;
-
-        Ok(token_data.claims)
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_jwt_service_creation() {
-        let service = JwtService::new("secret".to_string());
-        assert_eq!(service.secret, "secret");
-    }
-
-    #[test]
-    fn test_validate_invalid_token() {
-        let service = JwtService::new("secret".to_string());
-        let result = service.validate_token("invalid.token.here");
-        assert!(result.is_err());
-    }
-}
@@ -1,27 +1,32 @@
 use uuid::Uuid;
 
 use crate::domain::like::RouteLike;
-use crate::usecase::contracts::{LikeRepository, RouteRepository};
+use crate::usecase::blocks::ensure_not_blocked;
+use crate::usecase::contracts::{BlockRepository, LikeRepository, RouteRepository};
 use crate::usecase::error::UsecaseError;
 
-pub struct LikesUseCase<L, R>
+pub struct LikesUseCase<L, R, B>
 where
     L: LikeRepository,
     R: RouteRepository,
+    B: BlockRepository,
 {
     like_repository: L,
     route_repository: R,
+    block_repository: B,
 }
 
-impl<L, R> LikesUseCase<L, R>
+impl<L, R, B> LikesUseCase<L, R, B>
 where
     L: LikeRepository,
     R: RouteRepository,
+    B: BlockRepository,
 {
-    pub fn new(like_repository: L, route_repository: R) -> Self {
+    pub fn new(like_repository: L, route_repository: R, block_repository: B) -> Self {
         Self {
             like_repository,
             route_repository,
+            block_repository,
         }
     }
 
@@ -29,8 +34,8 @@ where
     pub async fn toggle_like(&self, route_id: Uuid, user_id: Uuid) -> Result<bool, UsecaseError> {
         tracing::debug!("toggling like");
 
-        // Verify route exists
-        self.route_repository
+        let route = self
+            .route_repository
             .find_by_id(route_id)
             .await?
             .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
@@ -45,11 +50,15 @@ where
             self.like_repository
                 .delete_by_route_and_user(route_id, user_id)
                 .await?;
+            metrics::counter!("likes_total", "action" => "unlike").increment(1);
             tracing::info!(route_id = %route_id, user_id = %user_id, "like removed");
             Ok(false)
         } else {
+            // Blocked users can still take back a like given before the block
+            ensure_not_blocked(&self.block_repository, route.user_id, user_id).await?;
             let like = RouteLike::new(route_id, user_id);
             self.like_repository.create(&like).await?;
+            metrics::counter!("likes_total", "action" => "like").increment(1);
             tracing::info!(route_id = %route_id, user_id = %user_id, "like added");
             Ok(true)
         }
@@ -85,8 +94,14 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::route::Route;
-    use crate::usecase::contracts::{MockLikeRepository, MockRouteRepository};
+    use crate::domain::route::{Route, RouteVisibility};
+    use crate::usecase::contracts::{MockBlockRepository, MockLikeRepository, MockRouteRepository};
+
+    fn no_blocks() -> MockBlockRepository {
+        let mut mock_block_repo = MockBlockRepository::new();
+        mock_block_repo.expect_exists().returning(|_, _| Ok(false));
+        mock_block_repo
+    }
 
     fn make_route(route_id: Uuid) -> Route {
         Route {
@@ -97,9 +112,13 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: None,
+            visibility: RouteVisibility::Private,
             category_ids: vec![],
             start_location: None,
             end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
         }
     }
 
@@ -132,13 +151,41 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
-        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo);
+        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo, no_blocks());
         let result = usecase.toggle_like(route_id, user_id).await;
 
         assert!(result.is_ok());
         assert!(result.unwrap()); // liked = true
     }
 
+    #[tokio::test]
+    async fn test_toggle_like_rejects_blocked_user() {
+        let mut mock_like_repo = MockLikeRepository::new();
+        let mut mock_route_repo = MockRouteRepository::new();
+        let mut mock_block_repo = MockBlockRepository::new();
+        let route = make_route(Uuid::new_v4());
+        let (route_id, owner_id) = (route.id, route.user_id);
+        let user_id = Uuid::new_v4();
+
+        mock_route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        mock_like_repo
+            .expect_find_by_route_and_user()
+            .returning(|_, _| Ok(None));
+        mock_like_repo.expect_create().never();
+        mock_block_repo
+            .expect_exists()
+            .with(mockall::predicate::eq(owner_id), mockall::predicate::eq(user_id))
+            .times(1)
+            .returning(|_, _| Ok(true));
+
+        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo, mock_block_repo);
+        let result = usecase.toggle_like(route_id, user_id).await;
+
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+    }
+
     #[tokio::test]
     async fn test_toggle_like_remove() {
         let mut mock_like_repo = MockLikeRepository::new();
@@ -154,7 +201,6 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(route_clone.clone())));
 
-        let existing_like = RouteLike::new(route_id, user_id);
         mock_like_repo
             .expect_find_by_route_and_user()
             .with(
@@ -173,7 +219,7 @@ mod tests {
             .times(1)
             .returning(|_, _| Ok(()));
 
-        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo);
+        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo, no_blocks());
         let result = usecase.toggle_like(route_id, user_id).await;
 
         assert!(result.is_ok());
@@ -192,11 +238,10 @@ mod tests {
             .times(1)
             .returning(|_| Ok(None));
 
-        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo);
+        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo, no_blocks());
         let result = usecase.toggle_like(route_id, Uuid::new_v4()).await;
 
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("not found"));
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
     }
 
     #[tokio::test]
@@ -211,7 +256,7 @@ mod tests {
             .times(1)
             .returning(|_| Ok(5));
 
-        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo);
+        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo, no_blocks());
         let result = usecase.get_like_count(route_id).await;
 
         assert!(result.is_ok());
@@ -234,7 +279,7 @@ mod tests {
             .times(1)
             .returning(|r, u| Ok(Some(RouteLike::new(r, u))));
 
-        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo);
+        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo, no_blocks());
         let result = usecase.get_user_like_status(route_id, user_id).await;
 
         assert!(result.is_ok());
@@ -257,7 +302,7 @@ mod tests {
             .times(1)
             .returning(|_, _| Ok(None));
 
-        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo);
+        let usecase = LikesUseCase::new(mock_like_repo, mock_route_repo, no_blocks());
         let result = usecase.get_user_like_status(route_id, user_id).await;
 
         assert!(result.is_ok());
@@ -0,0 +1,207 @@
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::domain::route::PointAddress;
+use crate::usecase::geocoder::{non_empty_address, street_with_number, Geocoder};
+
+/// Mapbox allows 600 geocoding requests per minute on the default plan.
+const MAPBOX_REQUEST_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Deserialize)]
+struct MapboxContextEntry {
+    name: Option<String>,
+    address_number: Option<String>,
+    street_name: Option<String>,
+}
+
+/// Hierarchy of places the feature belongs to.
+#[derive(Deserialize, Default)]
+struct MapboxContext {
+    address: Option<MapboxContextEntry>,
+    street: Option<MapboxContextEntry>,
+    locality: Option<MapboxContextEntry>,
+    place: Option<MapboxContextEntry>,
+    country: Option<MapboxContextEntry>,
+}
+
+#[derive(Deserialize)]
+struct MapboxProperties {
+    name: Option<String>,
+    #[serde(default)]
+    context: MapboxContext,
+}
+
+#[derive(Deserialize)]
+struct MapboxFeature {
+    properties: MapboxProperties,
+}
+
+#[derive(Deserialize)]
+struct MapboxResponse {
+    #[serde(default)]
+    features: Vec<MapboxFeature>,
+}
+
+/// Reverse geocoder backed by the Mapbox Geocoding API v6.
+#[derive(Clone)]
+pub struct MapboxClient {
+    client: Client,
+    base_url: String,
+    access_token: String,
+    request_interval: Duration,
+}
+
+impl MapboxClient {
+    pub fn new(base_url: String, access_token: String) -> Self {
+        let client = Client::builder()
+            .user_agent("GuideHelper/1.0")
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("failed to build mapbox http client");
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            access_token,
+            request_interval: MAPBOX_REQUEST_INTERVAL,
+        }
+    }
+
+    pub fn with_request_interval(mut self, interval: Duration) -> Self {
+        self.request_interval = interval;
+        self
+    }
+
+    async fn reverse_features(&self, lat: f64, lng: f64, types: &str) -> Vec<MapboxFeature> {
+        let url = format!("{}/search/geocode/v6/reverse", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[
+                ("longitude", lng.to_string()),
+                ("latitude", lat.to_string()),
+                ("types", types.to_string()),
+                ("language", "ru".to_string()),
+                ("access_token", self.access_token.clone()),
+            ])
+            .send()
+            .await;
+
+        match response {
+            Ok(resp) => match resp.json::<MapboxResponse>().await {
+                Ok(d) => d.features,
+                Err(e) => {
+                    tracing::warn!(error = %e, "mapbox response parse failed");
+                    Vec::new()
+                }
+            },
+            Err(e) => {
+                tracing::warn!(error = %e.without_url(), lat, lng, "mapbox request failed");
+                Vec::new()
+            }
+        }
+    }
+
+    async fn reverse(&self, lat: f64, lng: f64, zoom: u8) -> String {
+        let types = if zoom >= 16 { "street" } else { "neighborhood" };
+        self.reverse_features(lat, lng, &format!("{},locality,place", types))
+            .await
+            .into_iter()
+            .find_map(|f| f.properties.name)
+            .unwrap_or_default()
+    }
+
+    async fn address(&self, lat: f64, lng: f64) -> Option<PointAddress> {
+        let context = self
+            .reverse_features(lat, lng, "address,street")
+            .await
+            .into_iter()
+            .next()?
+            .properties
+            .context;
+        let street = match context.address {
+            Some(address) => street_with_number(address.street_name, address.address_number),
+            None => context.street.and_then(|s| s.name),
+        };
+        non_empty_address(PointAddress {
+            street,
+            city: context.place.or(context.locality).and_then(|p| p.name),
+            country: context.country.and_then(|c| c.name),
+        })
+    }
+}
+
+impl Geocoder for MapboxClient {
+    fn name(&self) -> &'static str {
+        "mapbox"
+    }
+
+    fn request_interval(&self) -> Duration {
+        self.request_interval
+    }
+
+    fn reverse_geocode(&self, lat: f64, lng: f64, zoom: u8) -> BoxFuture<'_, String> {
+        Box::pin(self.reverse(lat, lng, zoom))
+    }
+
+    fn reverse_address(&self, lat: f64, lng: f64) -> BoxFuture<'_, Option<PointAddress>> {
+        Box::pin(self.address(lat, lng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reverse_geocode_sends_token_and_parses_name() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/search/geocode/v6/reverse"))
+            .and(wiremock::matchers::query_param("access_token", "pk.test"))
+            .and(wiremock::matchers::query_param("types", "street,locality,place"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "type": "FeatureCollection",
+                "features": [{ "properties": { "name": "Arbat", "feature_type": "street" } }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = MapboxClient::new(server.uri(), "pk.test".to_string());
+
+        assert_eq!(client.reverse_geocode(55.75, 37.59, 18).await, "Arbat");
+    }
+
+    #[tokio::test]
+    async fn test_reverse_address_reads_feature_context() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/search/geocode/v6/reverse"))
+            .and(wiremock::matchers::query_param("types", "address,street"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "type": "FeatureCollection",
+                "features": [{ "properties": {
+                    "name": "Arbat 10",
+                    "feature_type": "address",
+                    "context": {
+                        "address": { "name": "Arbat 10", "address_number": "10", "street_name": "Arbat" },
+                        "street": { "name": "Arbat" },
+                        "place": { "name": "Moscow" },
+                        "country": { "name": "Russia", "country_code": "RU" }
+                    }
+                } }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = MapboxClient::new(server.uri(), "pk.test".to_string());
+        let address = client.reverse_address(55.75, 37.59).await.unwrap();
+
+        assert_eq!(address.street.as_deref(), Some("Arbat, 10"));
+        assert_eq!(address.city.as_deref(), Some("Moscow"));
+        assert_eq!(address.country.as_deref(), Some("Russia"));
+    }
+}
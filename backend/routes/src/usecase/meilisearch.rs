@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Context;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::route::{Route, RouteVisibility};
+
+/// Facets returned for every search; they are also the filterable attributes.
+pub const FACET_ATTRIBUTES: &[&str] = &["category_ids", "seasons"];
+
+/// Document stored in the search index. Only shared routes are indexed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteDocument {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub start_location: Option<String>,
+    pub end_location: Option<String>,
+    pub category_ids: Vec<Uuid>,
+    pub seasons: Vec<String>,
+    pub points_count: i64,
+    /// Unix timestamp, so the engine can sort on it.
+    pub created_at: i64,
+}
+
+impl RouteDocument {
+    /// Only public, non-archived routes are searchable. The share token stays
+    /// out of the index; hits are opened by a signed public link instead.
+    pub fn from_route(route: &Route) -> Option<Self> {
+        if route.archived_at.is_some() || route.visibility != RouteVisibility::Public || route.share_token.is_none() {
+            return None;
+        }
+        Some(Self {
+            id: route.id,
+            name: route.name.clone(),
+            description: route.description.clone(),
+            start_location: route.start_location.clone(),
+            end_location: route.end_location.clone(),
+            category_ids: route.category_ids.clone(),
+            seasons: route.seasons.clone(),
+            points_count: route.points.len() as i64,
+            created_at: route.created_at.timestamp(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchRequest<'a> {
+    q: &'a str,
+    limit: i64,
+    offset: i64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    filter: Vec<String>,
+    facets: &'a [&'a str],
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResponse {
+    hits: Vec<RouteDocument>,
+    #[serde(default)]
+    estimated_total_hits: Option<i64>,
+    #[serde(default)]
+    facet_distribution: Option<HashMap<String, HashMap<String, u64>>>,
+}
+
+#[derive(Debug)]
+pub struct EngineSearchResult {
+    pub hits: Vec<RouteDocument>,
+    pub total: i64,
+    pub facets: HashMap<String, HashMap<String, u64>>,
+}
+
+#[derive(Clone)]
+pub struct MeilisearchClient {
+    client: Client,
+    base_url: String,
+    api_key: Option<String>,
+    index: String,
+}
+
+impl MeilisearchClient {
+    pub fn new(base_url: String, api_key: Option<String>, index: String) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .expect("failed to build meilisearch http client");
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.filter(|k| !k.is_empty()),
+            index,
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}/indexes/{}{}", self.base_url, self.index, path);
+        let builder = self.client.request(method, url);
+        match &self.api_key {
+            Some(key) => builder.bearer_auth(key),
+            None => builder,
+        }
+    }
+
+    /// Creates or updates index settings. Meilisearch creates the index on first use.
+    #[tracing::instrument(skip(self), fields(index = %self.index))]
+    pub async fn configure_index(&self) -> anyhow::Result<()> {
+        let settings = serde_json::json!({
+            "searchableAttributes": ["name", "description", "start_location", "end_location"],
+            "filterableAttributes": FACET_ATTRIBUTES,
+            "sortableAttributes": ["created_at"],
+            "typoTolerance": { "enabled": true },
+        });
+
+        self.request(reqwest::Method::PATCH, "/settings")
+            .json(&settings)
+            .send()
+            .await
+            .context("meilisearch settings request failed")?
+            .error_for_status()
+            .context("meilisearch rejected index settings")?;
+
+        tracing::info!("meilisearch index configured");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, documents), fields(index = %self.index, count = documents.len()))]
+    pub async fn upsert_documents(&self, documents: &[RouteDocument]) -> anyhow::Result<()> {
+        self.request(reqwest::Method::POST, "/documents?primaryKey=id")
+            .json(documents)
+            .send()
+            .await
+            .context("meilisearch upsert request failed")?
+            .error_for_status()
+            .context("meilisearch rejected documents")?;
+
+        tracing::debug!("documents queued for indexing");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(index = %self.index, %id))]
+    pub async fn delete_document(&self, id: Uuid) -> anyhow::Result<()> {
+        self.request(reqwest::Method::DELETE, &format!("/documents/{}", id))
+            .send()
+            .await
+            .context("meilisearch delete request failed")?
+            .error_for_status()
+            .context("meilisearch rejected document deletion")?;
+
+        tracing::debug!("document deletion queued");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(index = %self.index, %query, %limit, %offset))]
+    pub async fn search(
+        &self,
+        query: &str,
+        category_id: Option<Uuid>,
+        season: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> anyhow::Result<EngineSearchResult> {
+        let request = SearchRequest {
+            q: query,
+            limit,
+            offset,
+            filter: build_filters(category_id, season),
+            facets: FACET_ATTRIBUTES,
+        };
+
+        let response: SearchResponse = self
+            .request(reqwest::Method::POST, "/search")
+            .json(&request)
+            .send()
+            .await
+            .context("meilisearch search request failed")?
+            .error_for_status()
+            .context("meilisearch search failed")?
+            .json()
+            .await
+            .context("failed to parse meilisearch response")?;
+
+        let total = response.estimated_total_hits.unwrap_or(response.hits.len() as i64);
+        tracing::debug!(count = response.hits.len(), total, "meilisearch search completed");
+        Ok(EngineSearchResult {
+            hits: response.hits,
+            total,
+            facets: response.facet_distribution.unwrap_or_default(),
+        })
+    }
+}
+
+fn build_filters(category_id: Option<Uuid>, season: Option<&str>) -> Vec<String> {
+    let mut filters = Vec::new();
+    if let Some(id) = category_id {
+        filters.push(format!("category_ids = \"{}\"", id));
+    }
+    if let Some(season) = season {
+        filters.push(format!("seasons = \"{}\"", season.replace('\\', "\\\\").replace('"', "\\\"")));
+    }
+    filters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn test_route(share_token: Option<Uuid>, visibility: RouteVisibility) -> Route {
+        Route {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "Lake loop".to_string(),
+            points: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            share_token,
+            visibility,
+            category_ids: vec![],
+            start_location: Some("Moscow".to_string()),
+            end_location: None,
+            seasons: vec!["summer".to_string()],
+            description: Some("Easy walk".to_string()),
+            archived_at: None,
+        }
+    }
+
+    #[test]
+    fn test_document_only_for_public_routes() {
+        assert!(RouteDocument::from_route(&test_route(None, RouteVisibility::Private)).is_none());
+        assert!(RouteDocument::from_route(&test_route(Some(Uuid::new_v4()), RouteVisibility::Unlisted)).is_none());
+
+        let doc = RouteDocument::from_route(&test_route(Some(Uuid::new_v4()), RouteVisibility::Public)).unwrap();
+        assert!(!serde_json::to_string(&doc).unwrap().contains("share_token"));
+        assert_eq!(doc.name, "Lake loop");
+        assert_eq!(doc.seasons, vec!["summer".to_string()]);
+    }
+
+    #[test]
+    fn test_build_filters_escapes_quotes() {
+        let id = Uuid::new_v4();
+        let filters = build_filters(Some(id), Some("su\"mmer"));
+        assert_eq!(filters[0], format!("category_ids = \"{}\"", id));
+        assert_eq!(filters[1], "seasons = \"su\\\"mmer\"");
+        assert!(build_filters(None, None).is_empty());
+    }
+}
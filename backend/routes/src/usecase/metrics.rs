@@ -0,0 +1,40 @@
+//! Business event metrics exported on `/metrics`. Counters are recorded where
+//! the event is committed; labels are limited to small fixed sets (`action`,
+//! `outcome`, `format`, `type`, ...) so series stay bounded.
+
+use metrics::{describe_counter, describe_gauge, describe_histogram, Unit};
+
+/// Registers help text for every business metric. Call once after the
+/// Prometheus recorder is installed.
+pub fn describe() {
+    describe_counter!("routes_created_total", "Routes created, including imports and duplicates");
+    describe_histogram!("route_points", Unit::Count, "Number of points in each created route");
+    describe_counter!("routes_imported_total", "Routes created from an uploaded file, by `format`");
+    describe_counter!("route_shares_total", "Share links enabled or disabled, by `action`");
+    describe_counter!("share_links_total", "Labelled share links created or revoked, by `action`");
+    describe_counter!("shared_route_cache_total", "Shared route lookups by cache `outcome`: hit or miss");
+    describe_counter!("comments_created_total", "Comments written on routes");
+    describe_counter!("likes_total", "Route likes added or removed, by `action`");
+    describe_counter!("route_counters_repaired_total", "Routes whose like or bookmark counters were corrected");
+    describe_counter!("ratings_total", "Route ratings set or removed, by `action`");
+    describe_counter!("notifications_created_total", "In-app notifications stored, by `type`");
+    describe_counter!("photo_tasks_published_total", "Photo processing tasks sent to NATS, by `outcome`");
+    describe_counter!("photo_retries_total", "Retries of failed photos requested by users");
+    describe_histogram!("photo_task_points", Unit::Count, "Number of photos in each published processing task");
+    describe_counter!("chat_messages_total", "Chat messages, by `role`");
+    describe_counter!("chat_tool_calls_total", "Tool calls made by the chat assistant, by `tool`");
+    describe_counter!("chat_rate_limited_total", "Chat requests rejected by the per-user rate limit");
+    describe_counter!("chat_unavailable_total", "Chat requests made while no assistant is configured");
+    describe_counter!("chat_stream_disconnects_total", "Chat streams the client closed before the reply finished");
+    describe_histogram!("chat_response_duration_seconds", Unit::Seconds, "Time to produce a chat reply");
+    describe_counter!("chat_fallback_total", "Assistant calls answered by the fallback model");
+    describe_gauge!("circuit_breaker_state", "External service breaker state by `upstream`: 0 closed, 1 half-open, 2 open");
+    describe_counter!("circuit_breaker_opened_total", "Times a breaker opened, by `upstream`");
+    describe_counter!("circuit_breaker_rejected_total", "Calls failed fast by an open breaker, by `upstream`");
+}
+
+/// Counts a stored notification. Shared by every path that writes one so the
+/// `type` label matches `Notification::notification_type`.
+pub fn notification_created(notification_type: &str) {
+    metrics::counter!("notifications_created_total", "type" => notification_type.to_string()).increment(1);
+}
@@ -1,16 +1,49 @@
+pub mod activity;
+pub mod blocks;
 pub mod bookmarks;
 pub mod categories;
 pub mod chat;
+pub mod chat_attachments;
+pub mod chat_i18n;
+pub mod chat_prompt;
+pub mod circuit_breaker;
+pub mod collections;
 pub mod nominatim;
+pub mod meilisearch;
 pub mod comments;
+pub mod content_filter;
 pub mod contracts;
+pub mod elevation;
 pub mod error;
+pub mod geo;
+pub mod geocoder;
 pub mod geojson_import;
-pub mod jwt;
+pub mod gpx_import;
 pub mod likes;
+pub mod mapbox;
+pub mod metrics;
 pub mod notifications;
 pub mod openai;
+pub mod osrm;
+pub mod photon;
 pub mod photo_tasks;
+pub mod rate_limit;
 pub mod ratings;
+pub mod realtime;
+pub mod reports;
+pub mod route_elevation;
+pub mod route_export;
+pub mod route_segments;
+pub mod route_updates;
 pub mod routes;
+pub mod saved_searches;
+pub mod sanitize;
+pub mod search;
+pub mod spam;
 pub mod settings;
+pub mod share_links;
+pub mod stats;
+pub mod takeout;
+pub mod templates;
+pub mod translations;
+pub mod url_import;
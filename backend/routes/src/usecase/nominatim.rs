@@ -1,16 +1,26 @@
 use std::time::Duration;
 
+use futures::future::BoxFuture;
 use reqwest::Client;
 use serde::Deserialize;
 
+use crate::domain::route::PointAddress;
+use crate::usecase::circuit_breaker::{BreakerSettings, CircuitBreaker};
+use crate::usecase::geocoder::{non_empty_address, street_with_number, Geocoder};
+
+/// Nominatim usage policy allows one request per second.
+pub const NOMINATIM_REQUEST_INTERVAL: Duration = Duration::from_millis(1100);
+
 #[derive(Deserialize, Default)]
 struct NominatimAddress {
     road: Option<String>,
+    house_number: Option<String>,
     suburb: Option<String>,
     neighbourhood: Option<String>,
     village: Option<String>,
     town: Option<String>,
     city: Option<String>,
+    country: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -23,6 +33,8 @@ struct NominatimResponse {
 pub struct NominatimClient {
     client: Client,
     base_url: String,
+    request_interval: Duration,
+    breaker: CircuitBreaker,
 }
 
 impl NominatimClient {
@@ -32,29 +44,58 @@ impl NominatimClient {
             .timeout(Duration::from_secs(10))
             .build()
             .expect("failed to build nominatim http client");
-        Self { client, base_url }
+        Self {
+            client,
+            base_url,
+            request_interval: NOMINATIM_REQUEST_INTERVAL,
+            breaker: CircuitBreaker::new("nominatim", BreakerSettings::default()),
+        }
     }
 
-    async fn reverse_geocode_at_zoom(&self, lat: f64, lng: f64, zoom: u8) -> String {
+    /// Overrides the delay between requests, e.g. for a self-hosted instance.
+    pub fn with_request_interval(mut self, interval: Duration) -> Self {
+        self.request_interval = interval;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    async fn reverse(&self, lat: f64, lng: f64, zoom: u8) -> Option<NominatimResponse> {
         let url = format!(
             "{}/reverse?lat={}&lon={}&format=json&accept-language=ru&zoom={}",
             self.base_url, lat, lng, zoom
         );
 
-        let resp = match self.client.get(&url).send().await {
+        if let Err(e) = self.breaker.check() {
+            tracing::debug!(error = %e, lat, lng, zoom, "skipping nominatim request");
+            return None;
+        }
+
+        let resp = self.client.get(&url).send().await;
+        self.breaker.record_response(&resp);
+        let resp = match resp {
             Ok(r) => r,
             Err(e) => {
                 tracing::warn!(error = %e, lat, lng, zoom, "nominatim request failed");
-                return String::new();
+                return None;
             }
         };
 
-        let data: NominatimResponse = match resp.json().await {
-            Ok(d) => d,
+        match resp.json().await {
+            Ok(d) => Some(d),
             Err(e) => {
                 tracing::warn!(error = %e, "nominatim response parse failed");
-                return String::new();
+                None
             }
+        }
+    }
+
+    async fn reverse_geocode_at_zoom(&self, lat: f64, lng: f64, zoom: u8) -> String {
+        let Some(data) = self.reverse(lat, lng, zoom).await else {
+            return String::new();
         };
 
         let addr = data.address.unwrap_or_default();
@@ -78,36 +119,56 @@ impl NominatimClient {
         .unwrap_or_default()
     }
 
-    /// Resolves human-readable location names for the start and end of a route.
-    /// Tries progressively more specific zoom levels until names differ.
-    pub async fn resolve_route_locations(
-        &self,
-        first: (f64, f64),
-        last: (f64, f64),
-    ) -> (String, String) {
-        let coords_match =
-            (first.0 - last.0).abs() < 1e-9 && (first.1 - last.1).abs() < 1e-9;
-
-        for zoom in [14u8, 16, 18] {
-            let from = self.reverse_geocode_at_zoom(first.0, first.1, zoom).await;
-            tokio::time::sleep(Duration::from_millis(1100)).await;
-
-            let to = if coords_match {
-                from.clone()
-            } else {
-                let t = self.reverse_geocode_at_zoom(last.0, last.1, zoom).await;
-                tokio::time::sleep(Duration::from_millis(1100)).await;
-                t
-            };
-
-            if from != to || coords_match {
-                tracing::debug!(zoom, %from, %to, "resolved route locations");
-                return (from, to);
-            }
+    async fn reverse_address_at_street_level(&self, lat: f64, lng: f64) -> Option<PointAddress> {
+        let addr = self.reverse(lat, lng, 18).await?.address?;
+        non_empty_address(PointAddress {
+            street: street_with_number(addr.road, addr.house_number),
+            city: addr.city.or(addr.town).or(addr.village),
+            country: addr.country,
+        })
+    }
+}
 
-            tracing::debug!(zoom, %from, "locations matched, trying higher zoom");
-        }
+impl Geocoder for NominatimClient {
+    fn name(&self) -> &'static str {
+        "nominatim"
+    }
+
+    fn request_interval(&self) -> Duration {
+        self.request_interval
+    }
+
+    fn reverse_geocode(&self, lat: f64, lng: f64, zoom: u8) -> BoxFuture<'_, String> {
+        Box::pin(self.reverse_geocode_at_zoom(lat, lng, zoom))
+    }
+
+    fn reverse_address(&self, lat: f64, lng: f64) -> BoxFuture<'_, Option<PointAddress>> {
+        Box::pin(self.reverse_address_at_street_level(lat, lng))
+    }
+}
 
-        (String::new(), String::new())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reverse_address_builds_street_city_country() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/reverse"))
+            .and(wiremock::matchers::query_param("zoom", "18"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "display_name": "2, Kremlyovskaya, Kazan, Russia",
+                "address": { "road": "Kremlyovskaya", "house_number": "2", "town": "Kazan", "country": "Russia" }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = NominatimClient::new(server.uri());
+        let address = client.reverse_address(55.79, 49.11).await.unwrap();
+
+        assert_eq!(address.street.as_deref(), Some("Kremlyovskaya, 2"));
+        assert_eq!(address.city.as_deref(), Some("Kazan"));
+        assert_eq!(address.country.as_deref(), Some("Russia"));
     }
 }
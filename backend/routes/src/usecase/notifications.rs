@@ -1,8 +1,10 @@
 use uuid::Uuid;
 
-use crate::domain::notification::Notification;
+use crate::domain::notification::{Notification, NOTIFICATION_TYPE_PHOTOS_PROCESSED};
 use crate::usecase::contracts::NotificationRepository;
 use crate::usecase::error::UsecaseError;
+use crate::usecase::metrics;
+use crate::usecase::photo_tasks::PhotoCompletedSummary;
 
 pub struct NotificationsUseCase<N>
 where
@@ -23,24 +25,65 @@ where
         &self.notification_repository
     }
 
-    #[tracing::instrument(skip(self, actor_name, message), fields(%user_id, %notification_type, %route_id))]
+    /// Creates a notification whose message is rendered from `template_key`
+    /// and `params` in the reader's locale.
+    #[tracing::instrument(skip(self, actor_name, params), fields(%user_id, %notification_type, %route_id, %template_key))]
     pub async fn create_notification(
         &self,
         user_id: Uuid,
         notification_type: String,
         route_id: Uuid,
         actor_name: String,
-        message: String,
+        template_key: &str,
+        params: serde_json::Value,
     ) -> Result<Notification, UsecaseError> {
         tracing::debug!("creating notification");
 
-        let notification = Notification::new(user_id, notification_type, route_id, actor_name, message);
+        let notification =
+            Notification::from_template(user_id, notification_type, route_id, actor_name, template_key, params);
         self.notification_repository.create(&notification).await?;
+        metrics::notification_created(&notification.notification_type);
 
         tracing::info!(notification_id = %notification.id, user_id = %user_id, "notification created");
         Ok(notification)
     }
 
+    /// Tells the owner how a photo batch went, so users who closed the tab
+    /// find out later. Batches with nothing to report are skipped.
+    #[tracing::instrument(skip(self, summary, route_name), fields(user_id = %summary.user_id, route_id = %summary.route_id))]
+    pub async fn notify_photos_processed(
+        &self,
+        summary: &PhotoCompletedSummary,
+        route_name: &str,
+    ) -> Result<Option<Notification>, UsecaseError> {
+        if summary.processed == 0 && summary.failed == 0 {
+            tracing::debug!("no photos in batch, skipping notification");
+            return Ok(None);
+        }
+
+        let template_key = match (summary.processed == 1, summary.failed > 0) {
+            (true, false) => "notification.photo_processed",
+            (false, false) => "notification.photos_processed",
+            (true, true) => "notification.photo_processed_failed",
+            (false, true) => "notification.photos_processed_failed",
+        };
+
+        self.create_notification(
+            summary.user_id,
+            NOTIFICATION_TYPE_PHOTOS_PROCESSED.to_string(),
+            summary.route_id,
+            "Photo processing".to_string(),
+            template_key,
+            serde_json::json!({
+                "route": route_name,
+                "processed": summary.processed,
+                "failed": summary.failed,
+            }),
+        )
+        .await
+        .map(Some)
+    }
+
     #[tracing::instrument(skip(self), fields(user_id = %user_id, %limit, %offset))]
     pub async fn list_notifications(
         &self,
@@ -66,6 +109,14 @@ where
         Ok(count)
     }
 
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn count_notifications(&self, user_id: Uuid) -> Result<i64, UsecaseError> {
+        let count = self.notification_repository.count_by_user_id(user_id).await?;
+
+        tracing::debug!(user_id = %user_id, count, "notification count retrieved");
+        Ok(count)
+    }
+
     #[tracing::instrument(skip(self), fields(notification_id = %id, user_id = %user_id))]
     pub async fn mark_as_read(&self, id: Uuid, user_id: Uuid) -> Result<(), UsecaseError> {
         tracing::debug!("marking notification as read");
@@ -113,7 +164,8 @@ mod tests {
                 "like".to_string(),
                 route_id,
                 "actor@test.com".to_string(),
-                "liked your route".to_string(),
+                "notification.like",
+                serde_json::json!({"actor": "actor@test.com", "route": "Old town"}),
             )
             .await;
 
@@ -122,6 +174,8 @@ mod tests {
         assert_eq!(notification.user_id, user_id);
         assert_eq!(notification.route_id, route_id);
         assert_eq!(notification.notification_type, "like");
+        assert_eq!(notification.message, "actor@test.com liked your route \"Old town\"");
+        assert_eq!(notification.template_key.as_deref(), Some("notification.like"));
         assert!(!notification.is_read);
     }
 
@@ -142,13 +196,55 @@ mod tests {
                 "like".to_string(),
                 Uuid::new_v4(),
                 "actor".to_string(),
-                "msg".to_string(),
+                "notification.like",
+                serde_json::json!({"actor": "actor", "route": "Old town"}),
             )
             .await;
 
         assert!(result.is_err());
     }
 
+    fn photo_summary(processed: usize, failed: usize) -> PhotoCompletedSummary {
+        PhotoCompletedSummary {
+            route_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            processed,
+            failed,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_photos_processed_reports_counts() {
+        let mut mock_repo = MockNotificationRepository::new();
+        mock_repo.expect_create().times(1).returning(|_| Ok(()));
+        let usecase = NotificationsUseCase::new(mock_repo);
+        let summary = photo_summary(3, 1);
+
+        let notification = usecase
+            .notify_photos_processed(&summary, "Old town")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(notification.user_id, summary.user_id);
+        assert_eq!(notification.notification_type, NOTIFICATION_TYPE_PHOTOS_PROCESSED);
+        assert_eq!(notification.message, "3 photos processed for route \"Old town\", 1 failed");
+        assert_eq!(
+            notification.params,
+            Some(serde_json::json!({"route": "Old town", "processed": 3, "failed": 1}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_photos_processed_skips_empty_batches() {
+        // No repository calls expected.
+        let usecase = NotificationsUseCase::new(MockNotificationRepository::new());
+
+        let result = usecase.notify_photos_processed(&photo_summary(0, 0), "Old town").await;
+
+        assert!(matches!(result, Ok(None)));
+    }
+
     #[tokio::test]
     async fn test_list_notifications_success() {
         let mut mock_repo = MockNotificationRepository::new();
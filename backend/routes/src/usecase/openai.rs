@@ -2,6 +2,8 @@ use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+use crate::usecase::circuit_breaker::{BreakerSettings, CircuitBreaker};
+
 // ── Request types ──────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize)]
@@ -22,7 +24,7 @@ pub struct OpenAIFunction {
 pub struct OpenAIMessage {
     pub role: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub content: Option<String>,
+    pub content: Option<OpenAIContent>,
     /// Present when role == "tool" — links back to the tool_call that triggered this result
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
@@ -31,6 +33,43 @@ pub struct OpenAIMessage {
     pub tool_calls: Option<Vec<OpenAIToolCall>>,
 }
 
+/// Message content: plain text, or text with images for vision models.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum OpenAIContent {
+    Text(String),
+    Parts(Vec<OpenAIContentPart>),
+}
+
+impl OpenAIContent {
+    /// Text followed by one `image_url` part per image.
+    pub fn with_images(text: String, image_urls: &[String]) -> Self {
+        let mut parts = vec![OpenAIContentPart::Text { text }];
+        parts.extend(image_urls.iter().map(|url| OpenAIContentPart::ImageUrl {
+            image_url: OpenAIImageUrl { url: url.clone() },
+        }));
+        OpenAIContent::Parts(parts)
+    }
+}
+
+impl From<String> for OpenAIContent {
+    fn from(text: String) -> Self {
+        OpenAIContent::Text(text)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OpenAIContentPart {
+    Text { text: String },
+    ImageUrl { image_url: OpenAIImageUrl },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenAIImageUrl {
+    pub url: String,
+}
+
 /// A tool-call record stored inside an assistant message (outbound, serialised)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenAIToolCall {
@@ -61,6 +100,9 @@ pub struct OpenAIChatRequest {
 #[derive(Debug, Deserialize)]
 pub struct OpenAIChatResponse {
     pub choices: Vec<OpenAIChoice>,
+    /// Set by [`OpenAIClient::chat`] when the fallback model answered.
+    #[serde(skip)]
+    pub fallback_model: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -108,17 +150,41 @@ pub struct VisionMessage {
 
 // ── Client ─────────────────────────────────────────────────────────────────────
 
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// A failed chat call and whether repeating it may succeed.
+struct ChatCallError {
+    error: anyhow::Error,
+    retryable: bool,
+}
+
+impl ChatCallError {
+    fn retryable(error: anyhow::Error) -> Self {
+        Self { error, retryable: true }
+    }
+
+    fn fatal(error: anyhow::Error) -> Self {
+        Self { error, retryable: false }
+    }
+}
+
 pub struct OpenAIClient {
     http_client: reqwest::Client,
     base_url: String,
     model: String,
     api_key: String,
+    breaker: CircuitBreaker,
+    request_timeout: Duration,
+    max_retries: u32,
+    fallback_model: Option<String>,
+    vision: bool,
 }
 
 impl OpenAIClient {
     pub fn new(base_url: String, model: String, api_key: String) -> Self {
         let http_client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(120))
+            .timeout(DEFAULT_REQUEST_TIMEOUT)
             .build()
             .expect("failed to create reqwest client");
 
@@ -129,9 +195,50 @@ impl OpenAIClient {
             base_url,
             model,
             api_key,
+            breaker: CircuitBreaker::new("openai", BreakerSettings::default()),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            max_retries: 0,
+            fallback_model: None,
+            vision: false,
         }
     }
 
+    /// Per-attempt timeout for [`chat`](Self::chat).
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Model to answer with when the primary model keeps failing, e.g. a
+    /// smaller, cheaper one from the same provider.
+    pub fn with_fallback_model(mut self, model: String) -> Self {
+        self.fallback_model = Some(model);
+        self
+    }
+
+    /// Replaces the default `openai` breaker, e.g. to label a local model
+    /// server separately.
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    /// Marks the model (and the fallback model, if any) as accepting
+    /// `image_url` content parts.
+    pub fn with_vision(mut self, vision: bool) -> Self {
+        self.vision = vision;
+        self
+    }
+
+    pub fn supports_vision(&self) -> bool {
+        self.vision
+    }
+
     pub fn model(&self) -> &str {
         &self.model
     }
@@ -140,36 +247,81 @@ impl OpenAIClient {
         &self.base_url
     }
 
-    pub async fn chat(&self, request: OpenAIChatRequest) -> anyhow::Result<OpenAIChatResponse> {
+    /// Sends a chat completion, retrying timeouts, 5xx and 429 up to
+    /// `max_retries` times. If the model still fails and a fallback model is
+    /// set, the request is repeated with it and the response is marked via
+    /// [`OpenAIChatResponse::fallback_model`].
+    pub async fn chat(&self, mut request: OpenAIChatRequest) -> anyhow::Result<OpenAIChatResponse> {
+        let primary_error = match self.chat_with_retries(&request).await {
+            Ok(response) => return Ok(response),
+            Err(e) => e,
+        };
+        let Some(fallback) = self.fallback_model.clone().filter(|m| *m != request.model) else {
+            return Err(primary_error);
+        };
+
+        tracing::warn!(primary_model = %request.model, fallback_model = %fallback, error = %primary_error, "primary model failed, using fallback model");
+        metrics::counter!("chat_fallback_total").increment(1);
+        request.model = fallback.clone();
+        let mut response = self.chat_with_retries(&request).await?;
+        response.fallback_model = Some(fallback);
+        Ok(response)
+    }
+
+    async fn chat_with_retries(&self, request: &OpenAIChatRequest) -> anyhow::Result<OpenAIChatResponse> {
+        let mut attempt = 0;
+        loop {
+            match self.chat_once(request).await {
+                Ok(response) => return Ok(response),
+                Err(e) if e.retryable && attempt < self.max_retries => {
+                    attempt += 1;
+                    let delay = RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+                    tracing::warn!(attempt, ?delay, model = %request.model, error = %e.error, "OpenAI request failed, retrying");
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return Err(e.error),
+            }
+        }
+    }
+
+    async fn chat_once(&self, request: &OpenAIChatRequest) -> Result<OpenAIChatResponse, ChatCallError> {
         let url = format!("{}/chat/completions", self.base_url);
         tracing::debug!(%url, model = %request.model, messages_count = request.messages.len(), "sending chat request to OpenAI");
 
+        self.breaker.check().map_err(|e| ChatCallError::fatal(e.into()))?;
         let response = self
             .http_client
             .post(&url)
             .bearer_auth(&self.api_key)
-            .json(&request)
+            .timeout(self.request_timeout)
+            .json(request)
             .send()
-            .await
-            .map_err(|e| {
-                tracing::error!(error = %e, "failed to send request to OpenAI");
-                anyhow!("OpenAI request failed: {}", e)
-            })?;
+            .await;
+        self.breaker.record_response(&response);
+        let response = response.map_err(|e| {
+            tracing::error!(error = %e, "failed to send request to OpenAI");
+            ChatCallError::retryable(anyhow!("OpenAI request failed: {}", e))
+        })?;
 
         let status = response.status();
         let body = response.text().await.map_err(|e| {
             tracing::error!(error = %e, "failed to read OpenAI response");
-            anyhow!("Failed to read OpenAI response: {}", e)
+            ChatCallError::retryable(anyhow!("Failed to read OpenAI response: {}", e))
         })?;
 
         if !status.is_success() {
             tracing::error!(%status, %body, "OpenAI returned error");
-            return Err(anyhow!("OpenAI error ({}): {}", status, body));
+            let error = anyhow!("OpenAI error ({}): {}", status, body);
+            return Err(if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                ChatCallError::retryable(error)
+            } else {
+                ChatCallError::fatal(error)
+            });
         }
 
         serde_json::from_str::<OpenAIChatResponse>(&body).map_err(|e| {
             tracing::error!(error = %e, %body, "failed to parse OpenAI response");
-            anyhow!("Failed to parse OpenAI response: {}", e)
+            ChatCallError::fatal(anyhow!("Failed to parse OpenAI response: {}", e))
         })
     }
 
@@ -177,17 +329,19 @@ impl OpenAIClient {
         let url = format!("{}/chat/completions", self.base_url);
         tracing::debug!(%url, model = %request.model, "sending vision chat request");
 
+        self.breaker.check()?;
         let response = self
             .http_client
             .post(&url)
             .bearer_auth(&self.api_key)
             .json(&request)
             .send()
-            .await
-            .map_err(|e| {
-                tracing::error!(error = %e, "failed to send vision request");
-                anyhow!("Vision request failed: {}", e)
-            })?;
+            .await;
+        self.breaker.record_response(&response);
+        let response = response.map_err(|e| {
+            tracing::error!(error = %e, "failed to send vision request");
+            anyhow!("Vision request failed: {}", e)
+        })?;
 
         let status = response.status();
         let body = response.text().await.map_err(|e| anyhow!("Failed to read vision response: {}", e))?;
@@ -227,3 +381,100 @@ impl OpenAIClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> OpenAIChatRequest {
+        OpenAIChatRequest {
+            model: "primary".to_string(),
+            messages: vec![],
+            tools: None,
+            tool_choice: None,
+        }
+    }
+
+    fn completion() -> serde_json::Value {
+        serde_json::json!({
+            "choices": [{ "message": { "role": "assistant", "content": "hi" } }]
+        })
+    }
+
+    async fn mount_model(server: &wiremock::MockServer, model: &str, response: wiremock::ResponseTemplate) {
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/chat/completions"))
+            .and(wiremock::matchers::body_partial_json(serde_json::json!({ "model": model })))
+            .respond_with(response)
+            .mount(server)
+            .await;
+    }
+
+    #[test]
+    fn test_content_with_images_serializes_as_parts() {
+        let content = OpenAIContent::with_images(
+            "where is this?".to_string(),
+            &["https://example.com/a.jpg".to_string()],
+        );
+
+        assert_eq!(
+            serde_json::to_value(&content).unwrap(),
+            serde_json::json!([
+                { "type": "text", "text": "where is this?" },
+                { "type": "image_url", "image_url": { "url": "https://example.com/a.jpg" } }
+            ])
+        );
+        assert_eq!(
+            serde_json::to_value(OpenAIContent::from("hi".to_string())).unwrap(),
+            serde_json::json!("hi")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chat_retries_server_errors() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(wiremock::ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        mount_model(&server, "primary", wiremock::ResponseTemplate::new(200).set_body_json(completion())).await;
+
+        let client = OpenAIClient::new(server.uri(), "primary".to_string(), "key".to_string()).with_max_retries(1);
+        let response = client.chat(request()).await.unwrap();
+
+        assert_eq!(response.choices[0].message.content.as_deref(), Some("hi"));
+        assert!(response.fallback_model.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_chat_uses_fallback_model_when_primary_fails() {
+        let server = wiremock::MockServer::start().await;
+        mount_model(&server, "primary", wiremock::ResponseTemplate::new(500)).await;
+        mount_model(&server, "small", wiremock::ResponseTemplate::new(200).set_body_json(completion())).await;
+
+        let client = OpenAIClient::new(server.uri(), "primary".to_string(), "key".to_string())
+            .with_fallback_model("small".to_string());
+        let response = client.chat(request()).await.unwrap();
+
+        assert_eq!(response.fallback_model.as_deref(), Some("small"));
+    }
+
+    #[tokio::test]
+    async fn test_chat_times_out_slow_model() {
+        let server = wiremock::MockServer::start().await;
+        mount_model(
+            &server,
+            "primary",
+            wiremock::ResponseTemplate::new(200)
+                .set_body_json(completion())
+                .set_delay(Duration::from_millis(500)),
+        )
+        .await;
+
+        let client = OpenAIClient::new(server.uri(), "primary".to_string(), "key".to_string())
+            .with_request_timeout(Duration::from_millis(50));
+
+        assert!(client.chat(request()).await.is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::usecase::circuit_breaker::{BreakerSettings, CircuitBreaker};
+
+#[derive(Deserialize)]
+struct OsrmGeometry {
+    coordinates: Vec<[f64; 2]>,
+}
+
+#[derive(Deserialize)]
+struct OsrmRoute {
+    distance: f64,
+    geometry: OsrmGeometry,
+}
+
+#[derive(Deserialize)]
+struct OsrmResponse {
+    code: String,
+    #[serde(default)]
+    routes: Vec<OsrmRoute>,
+}
+
+/// Snapped path between two points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutedPath {
+    /// [lng, lat] pairs.
+    pub coordinates: Vec<[f64; 2]>,
+    pub distance_km: f64,
+}
+
+/// Maps a point's `segment_mode` to an OSRM profile.
+/// `None` means the segment is drawn as a straight line.
+pub fn profile_for_mode(mode: Option<&str>) -> Option<&'static str> {
+    match mode {
+        Some("auto") | Some("walk") | Some("foot") => Some("foot"),
+        Some("bike") => Some("bike"),
+        Some("car") => Some("car"),
+        _ => None,
+    }
+}
+
+#[derive(Clone)]
+pub struct OsrmClient {
+    client: Client,
+    base_url: String,
+    breaker: CircuitBreaker,
+}
+
+impl OsrmClient {
+    pub fn new(base_url: String) -> Self {
+        let client = Client::builder()
+            .user_agent("GuideHelper/1.0")
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("failed to build osrm http client");
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            breaker: CircuitBreaker::new("osrm", BreakerSettings::default()),
+        }
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    /// Routes between two (lat, lng) points using the given profile.
+    #[tracing::instrument(skip(self))]
+    pub async fn route(
+        &self,
+        profile: &str,
+        from: (f64, f64),
+        to: (f64, f64),
+    ) -> anyhow::Result<RoutedPath> {
+        let url = format!(
+            "{}/route/v1/{}/{},{};{},{}?overview=full&geometries=geojson",
+            self.base_url, profile, from.1, from.0, to.1, to.0
+        );
+
+        self.breaker.check()?;
+        let response = self.client.get(&url).send().await;
+        self.breaker.record_response(&response);
+
+        let response: OsrmResponse = response
+            .context("osrm request failed")?
+            .error_for_status()
+            .context("osrm returned error status")?
+            .json()
+            .await
+            .context("failed to parse osrm response")?;
+
+        if response.code != "Ok" {
+            anyhow::bail!("osrm returned code {}", response.code);
+        }
+        let route = response
+            .routes
+            .into_iter()
+            .next()
+            .context("osrm returned no routes")?;
+
+        tracing::debug!(points = route.geometry.coordinates.len(), distance_m = route.distance, "osrm route resolved");
+        Ok(RoutedPath {
+            coordinates: route.geometry.coordinates,
+            distance_km: route.distance / 1000.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_for_mode() {
+        assert_eq!(profile_for_mode(Some("auto")), Some("foot"));
+        assert_eq!(profile_for_mode(Some("walk")), Some("foot"));
+        assert_eq!(profile_for_mode(Some("bike")), Some("bike"));
+        assert_eq!(profile_for_mode(Some("car")), Some("car"));
+        assert_eq!(profile_for_mode(Some("manual")), None);
+        assert_eq!(profile_for_mode(None), None);
+    }
+
+    #[test]
+    fn test_parse_osrm_response() {
+        let json = r#"{"code":"Ok","routes":[{"distance":1234.5,"geometry":{"type":"LineString","coordinates":[[37.6,55.7],[37.61,55.71]]}}]}"#;
+        let response: OsrmResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.code, "Ok");
+        assert_eq!(response.routes[0].geometry.coordinates.len(), 2);
+        assert!((response.routes[0].distance - 1234.5).abs() < 1e-9);
+    }
+}
@@ -1,50 +1,51 @@
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+pub use guide_helper_api::photo::{PhotoCompletedSummary, PhotoProcessTask};
+use guide_helper_domain::route::PhotoData;
 
-use crate::domain::route::Route;
+use crate::domain::route::{PhotoStatus, Route};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PhotoProcessTask {
-    pub route_id: Uuid,
-    pub user_id: Uuid,
-    pub point_indices: Vec<usize>,
+/// Builds the processing task for the route's points that still carry inline
+/// base64 photos, or `None` when there is nothing to process.
+pub fn photo_task_for_route(route: &Route) -> Option<PhotoProcessTask> {
+    task_for_photos(route, |photo| photo.original.starts_with("data:"))
 }
 
-impl PhotoProcessTask {
-    pub fn from_route(route: &Route) -> Option<Self> {
-        let indices: Vec<usize> = route
-            .points
-            .iter()
-            .enumerate()
-            .filter_map(|(i, point)| {
-                if let Some(ref photo) = point.photo {
-                    if photo.original.starts_with("data:") {
-                        return Some(i);
-                    }
-                }
-                None
-            })
-            .collect();
-
-        if indices.is_empty() {
-            return None;
-        }
-
-        Some(Self {
-            route_id: route.id,
-            user_id: route.user_id,
-            point_indices: indices,
-        })
+/// Like [`photo_task_for_route`], but only for photos that failed. Photos
+/// whose upload already went through have no inline data left to retry.
+pub fn retry_task_for_route(route: &Route) -> Option<PhotoProcessTask> {
+    task_for_photos(route, |photo| {
+        matches!(photo.status, PhotoStatus::Failed) && photo.original.starts_with("data:")
+    })
+}
+
+fn task_for_photos(route: &Route, include: impl Fn(&PhotoData) -> bool) -> Option<PhotoProcessTask> {
+    let indices: Vec<usize> = route
+        .points
+        .iter()
+        .enumerate()
+        .filter(|(_, point)| point.photo.as_ref().is_some_and(&include))
+        .map(|(i, _)| i)
+        .collect();
+
+    if indices.is_empty() {
+        return None;
     }
+
+    Some(PhotoProcessTask {
+        route_id: route.id,
+        user_id: route.user_id,
+        point_indices: indices,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::route::{PhotoData, PhotoStatus, Route, RoutePoint};
+    use uuid::Uuid;
+
+    use crate::domain::route::{RoutePoint, RouteVisibility};
 
     #[test]
-    fn test_from_route_with_base64_photos() {
+    fn test_task_for_route_with_base64_photos() {
         let route = Route {
             id: Uuid::new_v4(),
             user_id: Uuid::new_v4(),
@@ -60,6 +61,7 @@ mod tests {
                         thumbnail_url: None,
                         status: PhotoStatus::Pending,
                     }),
+                    address: None,
                 },
                 RoutePoint {
                     lat: 56.0,
@@ -67,6 +69,7 @@ mod tests {
                     name: None,
                     segment_mode: None,
                     photo: None,
+                    address: None,
                 },
                 RoutePoint {
                     lat: 57.0,
@@ -78,23 +81,28 @@ mod tests {
                         thumbnail_url: None,
                         status: PhotoStatus::Pending,
                     }),
+                    address: None,
                 },
             ],
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: None,
+            visibility: RouteVisibility::Private,
             category_ids: vec![],
             start_location: None,
             end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
         };
 
-        let task = PhotoProcessTask::from_route(&route).unwrap();
+        let task = photo_task_for_route(&route).unwrap();
         assert_eq!(task.route_id, route.id);
         assert_eq!(task.point_indices, vec![0, 2]);
     }
 
     #[test]
-    fn test_from_route_no_photos() {
+    fn test_task_for_route_no_photos() {
         let route = Route {
             id: Uuid::new_v4(),
             user_id: Uuid::new_v4(),
@@ -105,20 +113,25 @@ mod tests {
                 name: None,
                 segment_mode: None,
                 photo: None,
+                address: None,
             }],
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: None,
+            visibility: RouteVisibility::Private,
             category_ids: vec![],
             start_location: None,
             end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
         };
 
-        assert!(PhotoProcessTask::from_route(&route).is_none());
+        assert!(photo_task_for_route(&route).is_none());
     }
 
     #[test]
-    fn test_from_route_already_processed_url() {
+    fn test_task_for_route_already_processed_url() {
         let route = Route {
             id: Uuid::new_v4(),
             user_id: Uuid::new_v4(),
@@ -133,15 +146,59 @@ mod tests {
                     thumbnail_url: Some("/photos/user/route/thumb_0.jpg".to_string()),
                     status: PhotoStatus::Done,
                 }),
+                address: None,
             }],
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: None,
+            visibility: RouteVisibility::Private,
+            category_ids: vec![],
+            start_location: None,
+            end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
+        };
+
+        assert!(photo_task_for_route(&route).is_none());
+    }
+
+    #[test]
+    fn test_retry_task_only_includes_failed_inline_photos() {
+        let point = |original: &str, status: PhotoStatus| RoutePoint {
+            lat: 55.0,
+            lng: 37.0,
+            name: None,
+            segment_mode: None,
+            photo: Some(PhotoData {
+                original: original.to_string(),
+                thumbnail_url: None,
+                status,
+            }),
+            address: None,
+        };
+        let route = Route {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "test".to_string(),
+            points: vec![
+                point("data:image/png;base64,abc", PhotoStatus::Pending),
+                point("data:image/png;base64,def", PhotoStatus::Failed),
+                point("/photos/user/route/photo_2.jpg", PhotoStatus::Failed),
+            ],
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            share_token: None,
+            visibility: RouteVisibility::Private,
             category_ids: vec![],
             start_location: None,
             end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
         };
 
-        assert!(PhotoProcessTask::from_route(&route).is_none());
+        let task = retry_task_for_route(&route).unwrap();
+        assert_eq!(task.point_indices, vec![1]);
     }
 }
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::domain::route::PointAddress;
+use crate::usecase::geocoder::{non_empty_address, street_with_number, Geocoder};
+
+/// Fair-use spacing for the public komoot instance; self-hosters can lower it.
+const PHOTON_REQUEST_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Deserialize, Default)]
+struct PhotonProperties {
+    name: Option<String>,
+    street: Option<String>,
+    housenumber: Option<String>,
+    district: Option<String>,
+    locality: Option<String>,
+    city: Option<String>,
+    country: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PhotonFeature {
+    #[serde(default)]
+    properties: PhotonProperties,
+}
+
+#[derive(Deserialize)]
+struct PhotonResponse {
+    #[serde(default)]
+    features: Vec<PhotonFeature>,
+}
+
+/// Reverse geocoder backed by Photon (https://github.com/komoot/photon).
+#[derive(Clone)]
+pub struct PhotonClient {
+    client: Client,
+    base_url: String,
+    request_interval: Duration,
+}
+
+impl PhotonClient {
+    pub fn new(base_url: String) -> Self {
+        let client = Client::builder()
+            .user_agent("GuideHelper/1.0")
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("failed to build photon http client");
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            request_interval: PHOTON_REQUEST_INTERVAL,
+        }
+    }
+
+    pub fn with_request_interval(mut self, interval: Duration) -> Self {
+        self.request_interval = interval;
+        self
+    }
+
+    async fn reverse_properties(&self, lat: f64, lng: f64) -> Option<PhotonProperties> {
+        let url = format!("{}/reverse?lat={}&lon={}&limit=1", self.base_url, lat, lng);
+
+        let data: PhotonResponse = match self.client.get(&url).send().await {
+            Ok(resp) => match resp.json().await {
+                Ok(d) => d,
+                Err(e) => {
+                    tracing::warn!(error = %e, "photon response parse failed");
+                    return None;
+                }
+            },
+            Err(e) => {
+                tracing::warn!(error = %e, lat, lng, "photon request failed");
+                return None;
+            }
+        };
+
+        data.features.into_iter().next().map(|f| f.properties)
+    }
+
+    async fn reverse(&self, lat: f64, lng: f64, zoom: u8) -> String {
+        let Some(props) = self.reverse_properties(lat, lng).await else {
+            return String::new();
+        };
+        let name = if zoom >= 16 {
+            props.name.or(props.street).or(props.district).or(props.city)
+        } else {
+            props.district.or(props.locality).or(props.city).or(props.name)
+        };
+        name.unwrap_or_default()
+    }
+
+    async fn address(&self, lat: f64, lng: f64) -> Option<PointAddress> {
+        let props = self.reverse_properties(lat, lng).await?;
+        non_empty_address(PointAddress {
+            street: street_with_number(props.street, props.housenumber),
+            city: props.city.or(props.locality),
+            country: props.country,
+        })
+    }
+}
+
+impl Geocoder for PhotonClient {
+    fn name(&self) -> &'static str {
+        "photon"
+    }
+
+    fn request_interval(&self) -> Duration {
+        self.request_interval
+    }
+
+    fn reverse_geocode(&self, lat: f64, lng: f64, zoom: u8) -> BoxFuture<'_, String> {
+        Box::pin(self.reverse(lat, lng, zoom))
+    }
+
+    fn reverse_address(&self, lat: f64, lng: f64) -> BoxFuture<'_, Option<PointAddress>> {
+        Box::pin(self.address(lat, lng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reverse_geocode_picks_name_by_zoom() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/reverse"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "features": [{ "properties": { "street": "Tverskaya", "district": "Tverskoy", "city": "Moscow" } }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PhotonClient::new(server.uri());
+
+        assert_eq!(client.reverse_geocode(55.76, 37.6, 18).await, "Tverskaya");
+        assert_eq!(client.reverse_geocode(55.76, 37.6, 14).await, "Tverskoy");
+    }
+
+    #[tokio::test]
+    async fn test_reverse_address_includes_house_number() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/reverse"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "features": [{ "properties": {
+                    "street": "Tverskaya", "housenumber": "13", "city": "Moscow", "country": "Russia"
+                } }]
+            })))
+            .mount(&server)
+            .await;
+
+        let address = PhotonClient::new(server.uri()).reverse_address(55.76, 37.6).await.unwrap();
+
+        assert_eq!(address.to_string(), "Tverskaya, 13, Moscow, Russia");
+    }
+
+    #[tokio::test]
+    async fn test_reverse_geocode_server_error_is_empty() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .respond_with(wiremock::ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let client = PhotonClient::new(server.uri());
+        assert_eq!(client.reverse_geocode(55.76, 37.6, 18).await, "");
+        assert_eq!(client.reverse_address(55.76, 37.6).await, None);
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_nats::jetstream::kv::{self, CreateErrorKind, Operation, UpdateErrorKind};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+/// JetStream key-value bucket holding the shared rate limit windows.
+pub const RATE_LIMIT_BUCKET: &str = "rate_limits";
+
+/// Attempts at a counter update that keeps racing other replicas.
+const MAX_UPDATE_ATTEMPTS: usize = 5;
+
+/// Fixed-window request counters.
+pub trait RateLimitStore: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Counts a request under `key`. Returns false once the current window
+    /// holds more than `max` requests.
+    fn hit<'a>(&'a self, key: &'a str, max: u32, window: Duration) -> BoxFuture<'a, anyhow::Result<bool>>;
+
+    /// Forgets windows that have ended and returns how many were dropped.
+    /// Backends that expire entries on their own keep the default.
+    fn purge_expired(&self) -> usize {
+        0
+    }
+}
+
+struct Window {
+    started: Instant,
+    length: Duration,
+    count: u32,
+}
+
+/// Counters of this replica only; each replica enforces its own limit.
+#[derive(Default)]
+pub struct InMemoryRateLimitStore {
+    windows: Mutex<HashMap<String, Window>>,
+}
+
+impl RateLimitStore for InMemoryRateLimitStore {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn hit<'a>(&'a self, key: &'a str, max: u32, window: Duration) -> BoxFuture<'a, anyhow::Result<bool>> {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        let entry = windows.entry(key.to_string()).or_insert(Window { started: now, length: window, count: 0 });
+        if now.duration_since(entry.started) >= window {
+            *entry = Window { started: now, length: window, count: 0 };
+        }
+        entry.count += 1;
+        let allowed = entry.count <= max;
+        Box::pin(async move { Ok(allowed) })
+    }
+
+    fn purge_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        let before = windows.len();
+        windows.retain(|_, w| now.duration_since(w.started) < w.length);
+        before - windows.len()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredWindow {
+    started_ms: i64,
+    count: u32,
+}
+
+/// Counters shared by all replicas in a JetStream key-value bucket. Updates
+/// are compare-and-set on the entry revision, so concurrent hits from
+/// different replicas are all counted.
+pub struct NatsRateLimitStore {
+    kv: kv::Store,
+}
+
+impl NatsRateLimitStore {
+    /// Opens the bucket, creating it if needed. Entries expire after
+    /// `max_window`, which must cover the longest window used with the store.
+    pub async fn new(client: async_nats::Client, max_window: Duration) -> anyhow::Result<Self> {
+        let jetstream = async_nats::jetstream::new(client);
+        let kv = match jetstream.get_key_value(RATE_LIMIT_BUCKET).await {
+            Ok(kv) => kv,
+            Err(_) => {
+                jetstream
+                    .create_key_value(kv::Config {
+                        bucket: RATE_LIMIT_BUCKET.to_string(),
+                        history: 1,
+                        max_age: max_window,
+                        ..Default::default()
+                    })
+                    .await?
+            }
+        };
+        Ok(Self { kv })
+    }
+}
+
+impl RateLimitStore for NatsRateLimitStore {
+    fn name(&self) -> &'static str {
+        "nats"
+    }
+
+    fn hit<'a>(&'a self, key: &'a str, max: u32, window: Duration) -> BoxFuture<'a, anyhow::Result<bool>> {
+        Box::pin(async move {
+            let window_ms = window.as_millis() as i64;
+            for _ in 0..MAX_UPDATE_ATTEMPTS {
+                let now_ms = chrono::Utc::now().timestamp_millis();
+                let entry = self.kv.entry(key).await?;
+                let current = entry
+                    .as_ref()
+                    .filter(|e| e.operation == Operation::Put)
+                    .and_then(|e| serde_json::from_slice::<StoredWindow>(&e.value).ok())
+                    .filter(|w| now_ms - w.started_ms < window_ms);
+                let next = match current {
+                    Some(w) => StoredWindow { started_ms: w.started_ms, count: w.count + 1 },
+                    None => StoredWindow { started_ms: now_ms, count: 1 },
+                };
+                let value = serde_json::to_vec(&next)?;
+
+                let written = match &entry {
+                    Some(entry) => match self.kv.update(key, value.into(), entry.revision).await {
+                        Ok(_) => true,
+                        Err(e) if matches!(e.kind(), UpdateErrorKind::WrongLastRevision) => false,
+                        Err(e) => return Err(e.into()),
+                    },
+                    None => match self.kv.create(key, value.into()).await {
+                        Ok(_) => true,
+                        Err(e) if matches!(e.kind(), CreateErrorKind::AlreadyExists) => false,
+                        Err(e) => return Err(e.into()),
+                    },
+                };
+                if written {
+                    return Ok(next.count <= max);
+                }
+                tracing::debug!(key, "rate limit counter changed concurrently, retrying");
+            }
+            anyhow::bail!("rate limit counter {} kept changing concurrently", key)
+        })
+    }
+}
+
+/// Builds the store selected in config: "memory" or "nats".
+pub async fn build_rate_limit_store(
+    backend: &str,
+    nats_client: Option<async_nats::Client>,
+    max_window: Duration,
+) -> anyhow::Result<Arc<dyn RateLimitStore>> {
+    let store: Arc<dyn RateLimitStore> = match backend {
+        "memory" => Arc::new(InMemoryRateLimitStore::default()),
+        "nats" => {
+            let client = nats_client.ok_or_else(|| anyhow::anyhow!("nats rate limit store requires a NATS connection"))?;
+            Arc::new(NatsRateLimitStore::new(client, max_window).await?)
+        }
+        other => anyhow::bail!("unknown rate limit backend: {}", other),
+    };
+    tracing::info!(backend = store.name(), "rate limit store ready");
+    Ok(store)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_store_limits_each_key() {
+        let store = InMemoryRateLimitStore::default();
+        let window = Duration::from_secs(60);
+
+        for _ in 0..3 {
+            assert!(store.hit("chat.a", 3, window).await.unwrap());
+        }
+        assert!(!store.hit("chat.a", 3, window).await.unwrap());
+        assert!(store.hit("chat.b", 3, window).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_resets_after_window() {
+        let store = InMemoryRateLimitStore::default();
+
+        assert!(store.hit("chat.a", 1, Duration::ZERO).await.unwrap());
+        assert!(store.hit("chat.a", 1, Duration::ZERO).await.unwrap());
+        assert!(store.hit("chat.b", 1, Duration::from_secs(60)).await.unwrap());
+
+        assert_eq!(store.purge_expired(), 1);
+        assert_eq!(store.windows.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_build_rate_limit_store_rejects_invalid_config() {
+        assert!(build_rate_limit_store("memory", None, Duration::from_secs(60)).await.is_ok());
+        assert!(build_rate_limit_store("nats", None, Duration::from_secs(60)).await.is_err());
+        assert!(build_rate_limit_store("redis", None, Duration::from_secs(60)).await.is_err());
+    }
+}
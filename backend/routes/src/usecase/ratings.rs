@@ -1,27 +1,32 @@
 use uuid::Uuid;
 
 use crate::domain::rating::{RatingInfo, RouteRating};
-use crate::usecase::contracts::{RatingRepository, RouteRepository};
+use crate::usecase::blocks::ensure_not_blocked;
+use crate::usecase::contracts::{BlockRepository, RatingRepository, RouteRepository};
 use crate::usecase::error::UsecaseError;
 
-pub struct RatingsUseCase<Ra, R>
+pub struct RatingsUseCase<Ra, R, B>
 where
     Ra: RatingRepository,
     R: RouteRepository,
+    B: BlockRepository,
 {
     rating_repository: Ra,
     route_repository: R,
+    block_repository: B,
 }
 
-impl<Ra, R> RatingsUseCase<Ra, R>
+impl<Ra, R, B> RatingsUseCase<Ra, R, B>
 where
     Ra: RatingRepository,
     R: RouteRepository,
+    B: BlockRepository,
 {
-    pub fn new(rating_repository: Ra, route_repository: R) -> Self {
+    pub fn new(rating_repository: Ra, route_repository: R, block_repository: B) -> Self {
         Self {
             rating_repository,
             route_repository,
+            block_repository,
         }
     }
 
@@ -38,15 +43,17 @@ where
             return Err(UsecaseError::Validation("Rating must be between 1 and 5".to_string()));
         }
 
-        // Verify route exists
-        self.route_repository
+        let route = self
+            .route_repository
             .find_by_id(route_id)
             .await?
             .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+        ensure_not_blocked(&self.block_repository, route.user_id, user_id).await?;
 
         let route_rating = RouteRating::new(route_id, user_id, rating);
         self.rating_repository.upsert(&route_rating).await?;
 
+        metrics::counter!("ratings_total", "action" => "set").increment(1);
         tracing::info!(route_id = %route_id, user_id = %user_id, rating, "rating set successfully");
         Ok(())
     }
@@ -59,6 +66,7 @@ where
             .delete_by_route_and_user(route_id, user_id)
             .await?;
 
+        metrics::counter!("ratings_total", "action" => "remove").increment(1);
         tracing::info!(route_id = %route_id, user_id = %user_id, "rating removed successfully");
         Ok(())
     }
@@ -94,8 +102,14 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::route::Route;
-    use crate::usecase::contracts::{MockRatingRepository, MockRouteRepository};
+    use crate::domain::route::{Route, RouteVisibility};
+    use crate::usecase::contracts::{MockBlockRepository, MockRatingRepository, MockRouteRepository};
+
+    fn no_blocks() -> MockBlockRepository {
+        let mut mock_block_repo = MockBlockRepository::new();
+        mock_block_repo.expect_exists().returning(|_, _| Ok(false));
+        mock_block_repo
+    }
 
     fn make_route(route_id: Uuid) -> Route {
         Route {
@@ -106,9 +120,13 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: None,
+            visibility: RouteVisibility::Private,
             category_ids: vec![],
             start_location: None,
             end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
         }
     }
 
@@ -132,18 +150,38 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
-        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo);
+        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo, no_blocks());
         let result = usecase.set_rating(route_id, user_id, 4).await;
 
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_set_rating_rejects_blocked_user() {
+        let mut mock_rating_repo = MockRatingRepository::new();
+        let mut mock_route_repo = MockRouteRepository::new();
+        let mut mock_block_repo = MockBlockRepository::new();
+        let route = make_route(Uuid::new_v4());
+        let route_id = route.id;
+
+        mock_route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        mock_rating_repo.expect_upsert().never();
+        mock_block_repo.expect_exists().returning(|_, _| Ok(true));
+
+        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo, mock_block_repo);
+        let result = usecase.set_rating(route_id, Uuid::new_v4(), 4).await;
+
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+    }
+
     #[tokio::test]
     async fn test_set_rating_invalid_value() {
         let mock_rating_repo = MockRatingRepository::new();
         let mock_route_repo = MockRouteRepository::new();
 
-        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo);
+        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo, no_blocks());
         let result = usecase
             .set_rating(Uuid::new_v4(), Uuid::new_v4(), 0)
             .await;
@@ -153,7 +191,7 @@ mod tests {
 
         let mock_rating_repo = MockRatingRepository::new();
         let mock_route_repo = MockRouteRepository::new();
-        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo);
+        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo, no_blocks());
         let result = usecase
             .set_rating(Uuid::new_v4(), Uuid::new_v4(), 6)
             .await;
@@ -174,7 +212,7 @@ mod tests {
             .times(1)
             .returning(|_| Ok(None));
 
-        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo);
+        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo, no_blocks());
         let result = usecase.set_rating(route_id, Uuid::new_v4(), 3).await;
 
         assert!(result.is_err());
@@ -197,7 +235,7 @@ mod tests {
             .times(1)
             .returning(|_, _| Ok(()));
 
-        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo);
+        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo, no_blocks());
         let result = usecase.remove_rating(route_id, user_id).await;
 
         assert!(result.is_ok());
@@ -216,7 +254,6 @@ mod tests {
             .times(1)
             .returning(|_| Ok((4.2, 10)));
 
-        let user_id_clone = user_id;
         mock_rating_repo
             .expect_find_by_route_and_user()
             .with(
@@ -226,7 +263,7 @@ mod tests {
             .times(1)
             .returning(move |r, u| Ok(Some(RouteRating::new(r, u, 5))));
 
-        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo);
+        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo, no_blocks());
         let result = usecase.get_rating_info(route_id, Some(user_id)).await;
 
         assert!(result.is_ok());
@@ -248,7 +285,7 @@ mod tests {
             .times(1)
             .returning(|_| Ok((3.5, 7)));
 
-        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo);
+        let usecase = RatingsUseCase::new(mock_rating_repo, mock_route_repo, no_blocks());
         let result = usecase.get_rating_info(route_id, None).await;
 
         assert!(result.is_ok());
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures::future::BoxFuture;
+use futures::StreamExt;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Prefix of the core NATS subjects carrying WS messages for a route.
+pub const WS_FANOUT_SUBJECT_PREFIX: &str = "routes.ws.";
+
+/// Messages buffered per route before slow WS clients start lagging.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Delivers realtime messages to the WS clients of a route.
+pub trait RealtimeHub: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Receives the messages sent for the route.
+    fn subscribe(&self, route_id: Uuid) -> broadcast::Receiver<String>;
+
+    /// Drops the route's channel once no local client listens on it.
+    fn release(&self, route_id: Uuid);
+
+    /// Sends a message to every client of the route.
+    fn publish(&self, route_id: Uuid, payload: String) -> BoxFuture<'_, ()>;
+}
+
+/// Broadcast channels of the clients connected to this replica.
+#[derive(Default)]
+struct LocalChannels {
+    channels: Mutex<HashMap<Uuid, broadcast::Sender<String>>>,
+}
+
+impl LocalChannels {
+    fn subscribe(&self, route_id: Uuid) -> broadcast::Receiver<String> {
+        let mut channels = self.channels.lock().unwrap();
+        channels
+            .entry(route_id)
+            .or_insert_with(|| {
+                tracing::debug!(route_id = %route_id, "creating new broadcast channel");
+                broadcast::channel(CHANNEL_CAPACITY).0
+            })
+            .subscribe()
+    }
+
+    fn release(&self, route_id: Uuid) {
+        let mut channels = self.channels.lock().unwrap();
+        if channels.get(&route_id).is_some_and(|tx| tx.receiver_count() == 0) {
+            channels.remove(&route_id);
+            tracing::debug!(route_id = %route_id, "removed empty broadcast channel");
+        }
+    }
+
+    fn forward(&self, route_id: Uuid, payload: String) {
+        let channels = self.channels.lock().unwrap();
+        match channels.get(&route_id).map(|tx| tx.send(payload)) {
+            Some(Ok(receivers)) => tracing::debug!(route_id = %route_id, receivers, "forwarded message to WS clients"),
+            _ => tracing::debug!(route_id = %route_id, "no active WS receivers for route"),
+        }
+    }
+}
+
+/// Hub for a single replica; messages never leave the process.
+#[derive(Default)]
+pub struct InMemoryRealtimeHub {
+    local: LocalChannels,
+}
+
+impl RealtimeHub for InMemoryRealtimeHub {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn subscribe(&self, route_id: Uuid) -> broadcast::Receiver<String> {
+        self.local.subscribe(route_id)
+    }
+
+    fn release(&self, route_id: Uuid) {
+        self.local.release(route_id);
+    }
+
+    fn publish(&self, route_id: Uuid, payload: String) -> BoxFuture<'_, ()> {
+        self.local.forward(route_id, payload);
+        Box::pin(async {})
+    }
+}
+
+/// Hub shared by all replicas. Messages go out on NATS and every replica
+/// forwards them to its own clients, so a client gets them whichever replica
+/// it is connected to.
+pub struct NatsRealtimeHub {
+    client: async_nats::Client,
+    local: Arc<LocalChannels>,
+}
+
+impl NatsRealtimeHub {
+    /// Starts forwarding the messages published by any replica.
+    pub fn new(client: async_nats::Client) -> Self {
+        let local = Arc::new(LocalChannels::default());
+        spawn_forwarder(client.clone(), local.clone());
+        Self { client, local }
+    }
+}
+
+impl RealtimeHub for NatsRealtimeHub {
+    fn name(&self) -> &'static str {
+        "nats"
+    }
+
+    fn subscribe(&self, route_id: Uuid) -> broadcast::Receiver<String> {
+        self.local.subscribe(route_id)
+    }
+
+    fn release(&self, route_id: Uuid) {
+        self.local.release(route_id);
+    }
+
+    fn publish(&self, route_id: Uuid, payload: String) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            let subject = format!("{}{}", WS_FANOUT_SUBJECT_PREFIX, route_id);
+            match self.client.publish(subject, payload.into()).await {
+                Ok(()) => tracing::debug!(route_id = %route_id, "published WS message"),
+                Err(e) => tracing::warn!(route_id = %route_id, error = %e, "failed to publish WS message"),
+            }
+        })
+    }
+}
+
+fn spawn_forwarder(client: async_nats::Client, local: Arc<LocalChannels>) {
+    tokio::spawn(async move {
+        let mut subscriber = match client.subscribe(format!("{}*", WS_FANOUT_SUBJECT_PREFIX)).await {
+            Ok(subscriber) => subscriber,
+            Err(e) => {
+                tracing::error!(error = %e, "failed to subscribe to WS fan-out");
+                return;
+            }
+        };
+        tracing::info!("WS fan-out subscriber ready");
+
+        while let Some(msg) = subscriber.next().await {
+            let route_id = match msg.subject.strip_prefix(WS_FANOUT_SUBJECT_PREFIX).map(str::parse::<Uuid>) {
+                Some(Ok(route_id)) => route_id,
+                _ => {
+                    tracing::warn!(subject = %msg.subject, "unexpected WS fan-out subject");
+                    continue;
+                }
+            };
+            match String::from_utf8(msg.payload.to_vec()) {
+                Ok(payload) => local.forward(route_id, payload),
+                Err(e) => tracing::warn!(route_id = %route_id, error = %e, "invalid UTF-8 in WS fan-out message"),
+            }
+        }
+        tracing::warn!("WS fan-out subscriber ended");
+    });
+}
+
+/// Clusters the hub over NATS when it is available.
+pub fn build_realtime_hub(nats_client: Option<async_nats::Client>) -> Arc<dyn RealtimeHub> {
+    match nats_client {
+        Some(client) => Arc::new(NatsRealtimeHub::new(client)),
+        None => Arc::new(InMemoryRealtimeHub::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_hub_delivers_to_route_subscribers() {
+        let hub = InMemoryRealtimeHub::default();
+        let route_id = Uuid::new_v4();
+        let mut rx = hub.subscribe(route_id);
+        let mut other = hub.subscribe(Uuid::new_v4());
+
+        hub.publish(route_id, "update".to_string()).await;
+
+        assert_eq!(rx.recv().await.unwrap(), "update");
+        assert!(other.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_release_keeps_channels_with_listeners() {
+        let hub = InMemoryRealtimeHub::default();
+        let route_id = Uuid::new_v4();
+        let first = hub.subscribe(route_id);
+        let second = hub.subscribe(route_id);
+
+        drop(first);
+        hub.release(route_id);
+        assert!(hub.local.channels.lock().unwrap().contains_key(&route_id));
+
+        drop(second);
+        hub.release(route_id);
+        assert!(hub.local.channels.lock().unwrap().is_empty());
+    }
+}
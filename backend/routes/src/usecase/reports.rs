@@ -0,0 +1,350 @@
+use uuid::Uuid;
+
+use crate::domain::report::{
+    RouteReport, REPORT_CATEGORIES, REPORT_STATUS_DISMISSED, REPORT_STATUS_OPEN,
+    REPORT_STATUS_RESOLVED,
+};
+use crate::domain::route::Route;
+use crate::usecase::contracts::{ReportRepository, RouteRepository};
+use crate::usecase::error::UsecaseError;
+use crate::usecase::sanitize::TextSanitizer;
+
+pub struct ReportsUseCase<P, R>
+where
+    P: ReportRepository,
+    R: RouteRepository,
+{
+    report_repository: P,
+    route_repository: R,
+    moderator_ids: Vec<Uuid>,
+    sanitizer: TextSanitizer,
+}
+
+impl<P, R> ReportsUseCase<P, R>
+where
+    P: ReportRepository,
+    R: RouteRepository,
+{
+    pub fn new(report_repository: P, route_repository: R, moderator_ids: Vec<Uuid>) -> Self {
+        Self {
+            report_repository,
+            route_repository,
+            moderator_ids,
+            sanitizer: TextSanitizer::default(),
+        }
+    }
+
+    pub fn with_sanitizer(mut self, sanitizer: TextSanitizer) -> Self {
+        self.sanitizer = sanitizer;
+        self
+    }
+
+    /// Users who get notified about new reports.
+    pub fn moderator_ids(&self) -> &[Uuid] {
+        &self.moderator_ids
+    }
+
+    #[tracing::instrument(skip(self, details), fields(route_id = %route_id, reporter_id = %reporter_id, %category))]
+    pub async fn create_report(
+        &self,
+        route_id: Uuid,
+        reporter_id: Uuid,
+        category: String,
+        details: Option<String>,
+    ) -> Result<(RouteReport, Route), UsecaseError> {
+        tracing::debug!("creating route report");
+
+        if !REPORT_CATEGORIES.contains(&category.as_str()) {
+            return Err(UsecaseError::Validation(format!(
+                "Invalid report category: {}. Must be one of: {}",
+                category,
+                REPORT_CATEGORIES.join(", ")
+            )));
+        }
+
+        // Private routes of others are reported as missing, like on the shared fetch
+        let route = self
+            .route_repository
+            .find_by_id(route_id)
+            .await?
+            .filter(|route| route.user_id == reporter_id || route.visibility.has_link())
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+
+        if self
+            .report_repository
+            .find_open_by_route_and_reporter(route_id, reporter_id)
+            .await?
+            .is_some()
+        {
+            tracing::warn!(route_id = %route_id, reporter_id = %reporter_id, "duplicate open report");
+            return Err(UsecaseError::Validation(
+                "You have already reported this route".to_string(),
+            ));
+        }
+
+        let details = details.map(|d| self.sanitizer.multi_line(&d)).filter(|d| !d.is_empty());
+        let report = RouteReport::new(route_id, reporter_id, category, details);
+        self.report_repository.create(&report).await?;
+
+        tracing::info!(report_id = %report.id, route_id = %route_id, "route report created");
+        Ok((report, route))
+    }
+
+    #[tracing::instrument(skip(self), fields(?status, %limit, %offset))]
+    pub async fn list_reports(
+        &self,
+        status: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<RouteReport>, i64), UsecaseError> {
+        tracing::debug!("listing route reports");
+
+        let valid_statuses = [REPORT_STATUS_OPEN, REPORT_STATUS_RESOLVED, REPORT_STATUS_DISMISSED];
+        if let Some(s) = status.as_deref().filter(|s| !valid_statuses.contains(s)) {
+            return Err(UsecaseError::Validation(format!("Invalid report status: {}", s)));
+        }
+
+        let reports = self.report_repository.list(status.clone(), limit, offset).await?;
+        let total = self.report_repository.count(status).await?;
+
+        tracing::debug!(count = reports.len(), total, "retrieved route reports");
+        Ok((reports, total))
+    }
+
+    /// Closes an open report as `resolved` (action taken) or `dismissed` (no action needed).
+    #[tracing::instrument(skip(self, resolution_note), fields(report_id = %report_id, resolver_id = %resolver_id, %status))]
+    pub async fn resolve_report(
+        &self,
+        report_id: Uuid,
+        resolver_id: Uuid,
+        status: String,
+        resolution_note: Option<String>,
+    ) -> Result<RouteReport, UsecaseError> {
+        tracing::debug!("resolving route report");
+
+        if status != REPORT_STATUS_RESOLVED && status != REPORT_STATUS_DISMISSED {
+            return Err(UsecaseError::Validation(format!(
+                "Invalid resolution status: {}. Must be one of: {}, {}",
+                status, REPORT_STATUS_RESOLVED, REPORT_STATUS_DISMISSED
+            )));
+        }
+
+        let report = self
+            .report_repository
+            .find_by_id(report_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Report".to_string()))?;
+
+        if report.status != REPORT_STATUS_OPEN {
+            return Err(UsecaseError::Validation("Report is already closed".to_string()));
+        }
+
+        let resolution_note = resolution_note
+            .map(|n| self.sanitizer.multi_line(&n))
+            .filter(|n| !n.is_empty());
+        self.report_repository
+            .resolve(report_id, &status, resolver_id, resolution_note.clone())
+            .await?;
+
+        tracing::info!(report_id = %report_id, %status, "route report resolved");
+        Ok(RouteReport {
+            status,
+            resolution_note,
+            resolved_by: Some(resolver_id),
+            resolved_at: Some(chrono::Utc::now()),
+            ..report
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    use crate::domain::route::RouteVisibility;
+    use crate::usecase::contracts::{MockReportRepository, MockRouteRepository};
+
+    fn test_route(id: Uuid) -> Route {
+        Route {
+            id,
+            user_id: Uuid::new_v4(),
+            name: "Test Route".to_string(),
+            points: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            share_token: Some(Uuid::new_v4()),
+            visibility: RouteVisibility::Public,
+            category_ids: vec![],
+            start_location: None,
+            end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_report_success() {
+        let route_id = Uuid::new_v4();
+        let reporter_id = Uuid::new_v4();
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(test_route(id))));
+
+        let mut report_repo = MockReportRepository::new();
+        report_repo
+            .expect_find_open_by_route_and_reporter()
+            .returning(|_, _| Ok(None));
+        report_repo
+            .expect_create()
+            .withf(|r| r.category == "spam" && r.details.as_deref() == Some("ads"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let usecase = ReportsUseCase::new(report_repo, route_repo, vec![]);
+        let (report, route) = usecase
+            .create_report(route_id, reporter_id, "spam".to_string(), Some("  ads ".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(report.route_id, route_id);
+        assert_eq!(report.status, REPORT_STATUS_OPEN);
+        assert_eq!(route.id, route_id);
+    }
+
+    #[tokio::test]
+    async fn test_create_report_invalid_category() {
+        let usecase = ReportsUseCase::new(MockReportRepository::new(), MockRouteRepository::new(), vec![]);
+        let result = usecase
+            .create_report(Uuid::new_v4(), Uuid::new_v4(), "boring".to_string(), None)
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_report_duplicate_open_report() {
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(test_route(id))));
+
+        let mut report_repo = MockReportRepository::new();
+        report_repo
+            .expect_find_open_by_route_and_reporter()
+            .returning(|route_id, reporter_id| {
+                Ok(Some(RouteReport::new(route_id, reporter_id, "spam".to_string(), None)))
+            });
+        report_repo.expect_create().never();
+
+        let usecase = ReportsUseCase::new(report_repo, route_repo, vec![]);
+        let result = usecase
+            .create_report(Uuid::new_v4(), Uuid::new_v4(), "dangerous".to_string(), None)
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_report_route_not_found() {
+        let mut route_repo = MockRouteRepository::new();
+        route_repo.expect_find_by_id().returning(|_| Ok(None));
+
+        let usecase = ReportsUseCase::new(MockReportRepository::new(), route_repo, vec![]);
+        let result = usecase
+            .create_report(Uuid::new_v4(), Uuid::new_v4(), "copyright".to_string(), None)
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_report_hides_private_routes_of_others() {
+        let owner_id = Uuid::new_v4();
+        let mut route_repo = MockRouteRepository::new();
+        route_repo.expect_find_by_id().returning(move |id| {
+            let mut route = test_route(id);
+            route.user_id = owner_id;
+            route.share_token = None;
+            route.visibility = RouteVisibility::Private;
+            Ok(Some(route))
+        });
+
+        let mut report_repo = MockReportRepository::new();
+        report_repo
+            .expect_find_open_by_route_and_reporter()
+            .returning(|_, _| Ok(None));
+        report_repo.expect_create().times(1).returning(|_| Ok(()));
+
+        let usecase = ReportsUseCase::new(report_repo, route_repo, vec![]);
+        let result = usecase
+            .create_report(Uuid::new_v4(), Uuid::new_v4(), "spam".to_string(), None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+
+        let own = usecase
+            .create_report(Uuid::new_v4(), owner_id, "spam".to_string(), None)
+            .await;
+        assert!(own.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_report_success() {
+        let report = RouteReport::new(Uuid::new_v4(), Uuid::new_v4(), "wrong_location".to_string(), None);
+        let report_id = report.id;
+        let resolver_id = Uuid::new_v4();
+
+        let mut report_repo = MockReportRepository::new();
+        report_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(report.clone())));
+        report_repo
+            .expect_resolve()
+            .withf(move |id, status, by, note| {
+                *id == report_id && status == REPORT_STATUS_RESOLVED && *by == resolver_id && note.as_deref() == Some("fixed")
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+
+        let usecase = ReportsUseCase::new(report_repo, MockRouteRepository::new(), vec![]);
+        let resolved = usecase
+            .resolve_report(report_id, resolver_id, "resolved".to_string(), Some("fixed".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(resolved.status, REPORT_STATUS_RESOLVED);
+        assert_eq!(resolved.resolved_by, Some(resolver_id));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_report_already_closed() {
+        let mut report = RouteReport::new(Uuid::new_v4(), Uuid::new_v4(), "spam".to_string(), None);
+        report.status = REPORT_STATUS_DISMISSED.to_string();
+
+        let mut report_repo = MockReportRepository::new();
+        report_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(report.clone())));
+        report_repo.expect_resolve().never();
+
+        let usecase = ReportsUseCase::new(report_repo, MockRouteRepository::new(), vec![]);
+        let result = usecase
+            .resolve_report(Uuid::new_v4(), Uuid::new_v4(), "resolved".to_string(), None)
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_report_invalid_status() {
+        let usecase = ReportsUseCase::new(MockReportRepository::new(), MockRouteRepository::new(), vec![]);
+        let result = usecase
+            .resolve_report(Uuid::new_v4(), Uuid::new_v4(), "open".to_string(), None)
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+}
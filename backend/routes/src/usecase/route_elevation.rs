@@ -0,0 +1,274 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::route::Route;
+use crate::domain::route_elevation::{ElevationSample, RouteElevation};
+use crate::usecase::contracts::{RouteElevationRepository, RouteRepository};
+use crate::usecase::elevation::{sample_line, ElevationClient};
+use crate::usecase::error::UsecaseError;
+
+const MAX_PROFILE_SAMPLES: usize = 200;
+
+pub struct ElevationUseCase<R, E>
+where
+    R: RouteRepository,
+    E: RouteElevationRepository,
+{
+    route_repository: R,
+    elevation_repository: E,
+    client: Option<ElevationClient>,
+}
+
+impl<R, E> ElevationUseCase<R, E>
+where
+    R: RouteRepository,
+    E: RouteElevationRepository,
+{
+    pub fn new(route_repository: R, elevation_repository: E) -> Self {
+        Self {
+            route_repository,
+            elevation_repository,
+            client: None,
+        }
+    }
+
+    pub fn with_client(mut self, client: ElevationClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn get_profile(&self, user_id: Uuid, route_id: Uuid) -> Result<RouteElevation, UsecaseError> {
+        tracing::debug!("getting elevation profile");
+
+        let route = self
+            .route_repository
+            .find_by_id(route_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+
+        if route.user_id != user_id {
+            tracing::warn!(%route_id, %user_id, "unauthorized elevation profile access attempt");
+            return Err(UsecaseError::NotFound("Route".to_string()));
+        }
+
+        self.profile_for_route(&route).await
+    }
+
+    /// Returns the cached profile, recomputing it when the route changed since.
+    #[tracing::instrument(skip(self, route), fields(route_id = %route.id, point_count = route.points.len()))]
+    pub async fn profile_for_route(&self, route: &Route) -> Result<RouteElevation, UsecaseError> {
+        if let Some(cached) = self
+            .elevation_repository
+            .find_by_route_id(route.id)
+            .await?
+            .filter(|e| !e.is_stale(route.updated_at))
+        {
+            tracing::debug!(route_id = %route.id, "elevation profile served from cache");
+            return Ok(cached);
+        }
+
+        if route.points.len() < 2 {
+            return Err(UsecaseError::Validation(
+                "Route needs at least two points for an elevation profile".to_string(),
+            ));
+        }
+        let Some(client) = &self.client else {
+            return Err(UsecaseError::Unavailable("Elevation service is not configured".to_string()));
+        };
+
+        let positions = sample_line(&route.points, MAX_PROFILE_SAMPLES);
+        let elevations = client.lookup(&positions).await.map_err(|e| {
+            tracing::error!(route_id = %route.id, error = %e, "elevation lookup failed");
+            UsecaseError::Unavailable("Elevation service is unavailable".to_string())
+        })?;
+
+        let samples = positions
+            .iter()
+            .zip(elevations)
+            .map(|(p, elevation_m)| ElevationSample {
+                distance_km: p.distance_km,
+                lat: p.lat,
+                lng: p.lng,
+                elevation_m,
+            })
+            .collect();
+        let elevation = RouteElevation::new(route.id, samples, route.updated_at);
+        self.elevation_repository.upsert(&elevation).await?;
+
+        tracing::info!(
+            route_id = %route.id,
+            samples = elevation.samples.len(),
+            gain_m = elevation.gain_m,
+            "elevation profile computed"
+        );
+        Ok(elevation)
+    }
+
+    /// Flips the cached profile of a route that was just reversed, so its
+    /// statistics are current without a new lookup. A profile that did not
+    /// match the route before the reversal is left for the next request to
+    /// recompute.
+    #[tracing::instrument(skip(self, route), fields(route_id = %route.id))]
+    pub async fn reverse_profile(
+        &self,
+        route: &Route,
+        previous_updated_at: DateTime<Utc>,
+    ) -> Result<Option<RouteElevation>, UsecaseError> {
+        let Some(cached) = self
+            .elevation_repository
+            .find_by_route_id(route.id)
+            .await?
+            .filter(|e| !e.is_stale(previous_updated_at))
+        else {
+            tracing::debug!("no current elevation profile to reverse");
+            return Ok(None);
+        };
+
+        let reversed = cached.reversed(route.updated_at);
+        self.elevation_repository.upsert(&reversed).await?;
+
+        tracing::debug!(gain_m = reversed.gain_m, "elevation profile reversed");
+        Ok(Some(reversed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::route::{RoutePoint, RouteVisibility};
+    use crate::usecase::contracts::{MockRouteElevationRepository, MockRouteRepository};
+
+    fn test_route(user_id: Uuid) -> Route {
+        let point = |lat: f64| RoutePoint {
+            lat,
+            lng: 37.0,
+            name: None,
+            segment_mode: None,
+            photo: None,
+            address: None,
+        };
+        Route {
+            id: Uuid::new_v4(),
+            user_id,
+            name: "Test".to_string(),
+            points: vec![point(55.0), point(55.01)],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            share_token: None,
+            visibility: RouteVisibility::Private,
+            category_ids: vec![],
+            start_location: None,
+            end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_served_from_cache() {
+        let user_id = Uuid::new_v4();
+        let route = test_route(user_id);
+        let route_id = route.id;
+        let updated_at = route.updated_at;
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        let mut elevation_repo = MockRouteElevationRepository::new();
+        elevation_repo
+            .expect_find_by_route_id()
+            .returning(move |id| Ok(Some(RouteElevation::new(id, vec![], updated_at))));
+        elevation_repo.expect_upsert().never();
+
+        let usecase = ElevationUseCase::new(route_repo, elevation_repo);
+        let profile = usecase.get_profile(user_id, route_id).await.unwrap();
+
+        assert_eq!(profile.route_id, route_id);
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_stale_without_client_is_unavailable() {
+        let user_id = Uuid::new_v4();
+        let route = test_route(user_id);
+        let route_id = route.id;
+        let old = route.updated_at - chrono::Duration::minutes(1);
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        let mut elevation_repo = MockRouteElevationRepository::new();
+        elevation_repo
+            .expect_find_by_route_id()
+            .returning(move |id| Ok(Some(RouteElevation::new(id, vec![], old))));
+
+        let usecase = ElevationUseCase::new(route_repo, elevation_repo);
+        let result = usecase.get_profile(user_id, route_id).await;
+
+        assert!(matches!(result, Err(UsecaseError::Unavailable(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_service_down_is_unavailable() {
+        let user_id = Uuid::new_v4();
+        let route = test_route(user_id);
+        let route_id = route.id;
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        let mut elevation_repo = MockRouteElevationRepository::new();
+        elevation_repo.expect_find_by_route_id().returning(|_| Ok(None));
+        elevation_repo.expect_upsert().never();
+
+        let usecase = ElevationUseCase::new(route_repo, elevation_repo)
+            .with_client(ElevationClient::new("http://127.0.0.1:9".to_string()));
+        let result = usecase.get_profile(user_id, route_id).await;
+
+        assert!(matches!(result, Err(UsecaseError::Unavailable(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reverse_profile_only_flips_current_profile() {
+        let route = test_route(Uuid::new_v4());
+        let previous = route.updated_at - chrono::Duration::seconds(5);
+        let samples = vec![
+            ElevationSample { distance_km: 0.0, lat: 55.0, lng: 37.0, elevation_m: 100.0 },
+            ElevationSample { distance_km: 1.0, lat: 55.01, lng: 37.0, elevation_m: 160.0 },
+        ];
+
+        let mut elevation_repo = MockRouteElevationRepository::new();
+        let cached = RouteElevation::new(route.id, samples, previous);
+        elevation_repo.expect_find_by_route_id().returning(move |_| Ok(Some(cached.clone())));
+        elevation_repo.expect_upsert().times(1).returning(|_| Ok(()));
+        let usecase = ElevationUseCase::new(MockRouteRepository::new(), elevation_repo);
+
+        let reversed = usecase.reverse_profile(&route, previous).await.unwrap().unwrap();
+        assert_eq!(reversed.loss_m, 60.0);
+        assert!(!reversed.is_stale(route.updated_at));
+
+        let outdated = route.updated_at - chrono::Duration::minutes(5);
+        assert!(usecase.reverse_profile(&route, outdated).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_other_user_not_found() {
+        let route = test_route(Uuid::new_v4());
+        let route_id = route.id;
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+
+        let usecase = ElevationUseCase::new(route_repo, MockRouteElevationRepository::new());
+        let result = usecase.get_profile(Uuid::new_v4(), route_id).await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+}
@@ -0,0 +1,810 @@
+use std::io::{Cursor, Write};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use geojson::{Feature, FeatureCollection, Geometry, JsonObject, Value};
+use image::DynamicImage;
+use printpdf::{
+    BuiltinFont, Color, Image, ImageTransform, IndirectFontRef, Line, Mm, PdfDocument,
+    PdfDocumentReference, PdfLayerReference, Point, Polygon, Rgb,
+};
+
+use serde::Serialize;
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+
+use crate::domain::route::{PhotoStatus, Route};
+use crate::domain::translation::difficulty_key;
+use crate::usecase::error::UsecaseError;
+use crate::usecase::geo::total_distance_km;
+use crate::usecase::settings::DifficultyThresholds;
+use crate::usecase::translations::Catalog;
+
+const PAGE_WIDTH_MM: f64 = 210.0;
+const PAGE_HEIGHT_MM: f64 = 297.0;
+const MARGIN_MM: f64 = 15.0;
+const MAP_HEIGHT_MM: f64 = 100.0;
+const THUMBNAIL_SIZE_MM: f64 = 20.0;
+const DESCRIPTION_LINE_CHARS: usize = 95;
+const DESCRIPTION_MAX_LINES: usize = 8;
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+const BUNDLE_PHOTO_MAX_WIDTH: u32 = 1280;
+const BUNDLE_PHOTO_QUALITY: u8 = 70;
+/// Largest photo body read from storage.
+const MAX_PHOTO_FETCH_BYTES: usize = 10 * 1024 * 1024;
+/// Photos fetched for one export; later points go without.
+const MAX_PHOTO_FETCHES: usize = 100;
+/// Photo bytes one offline bundle may buffer before it is refused.
+const MAX_BUNDLE_PHOTO_BYTES: usize = 100 * 1024 * 1024;
+
+#[derive(Debug, Serialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub route_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub start_location: Option<String>,
+    pub end_location: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub exported_at: DateTime<Utc>,
+    pub point_count: usize,
+    pub distance_km: f64,
+    pub files: Vec<BundleFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleFile {
+    pub path: String,
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub point_index: Option<usize>,
+    pub size: usize,
+}
+
+/// Renders printable handouts for routes.
+pub struct RouteExporter {
+    http: reqwest::Client,
+    photo_fetch_base_url: Option<String>,
+    font_path: Option<String>,
+}
+
+impl RouteExporter {
+    pub fn new(photo_fetch_base_url: Option<String>, font_path: Option<String>) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("failed to build HTTP client");
+
+        Self {
+            http,
+            photo_fetch_base_url: photo_fetch_base_url
+                .filter(|url| !url.trim().is_empty())
+                .map(|url| url.trim_end_matches('/').to_string()),
+            font_path: font_path.filter(|path| !path.trim().is_empty()),
+        }
+    }
+
+    /// Turns a stored photo path into a URL under the photo storage base.
+    /// Points carry client-supplied photo data, so absolute URLs and paths
+    /// that are not plain object keys are refused.
+    fn resolve_photo_url(&self, url: &str) -> Option<String> {
+        let base = self.photo_fetch_base_url.as_ref()?;
+        let key = url.strip_prefix('/')?;
+        let is_object_key = key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        });
+        if !is_object_key {
+            tracing::warn!(%url, "refusing to fetch photo outside of storage");
+            return None;
+        }
+        Some(format!("{}/{}", base, key))
+    }
+
+    async fn fetch_bytes(&self, url: &str) -> Option<Vec<u8>> {
+        let resolved = self.resolve_photo_url(url)?;
+
+        let mut response = match self.http.get(&resolved).send().await {
+            Ok(r) if r.status().is_success() => r,
+            Ok(r) => {
+                tracing::warn!(url = %resolved, status = %r.status(), "photo fetch returned non-success status");
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!(url = %resolved, error = %e, "failed to fetch photo");
+                return None;
+            }
+        };
+        if response.content_length().is_some_and(|len| len > MAX_PHOTO_FETCH_BYTES as u64) {
+            tracing::warn!(url = %resolved, "photo too large to export");
+            return None;
+        }
+
+        let mut bytes = Vec::new();
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) if bytes.len() + chunk.len() <= MAX_PHOTO_FETCH_BYTES => bytes.extend_from_slice(&chunk),
+                Ok(Some(_)) => {
+                    tracing::warn!(url = %resolved, "photo too large to export");
+                    return None;
+                }
+                Ok(None) => return Some(bytes),
+                Err(e) => {
+                    tracing::warn!(url = %resolved, error = %e, "failed to read photo body");
+                    return None;
+                }
+            }
+        }
+    }
+
+    async fn fetch_image(&self, url: &str) -> Option<DynamicImage> {
+        let bytes = self.fetch_bytes(url).await?;
+
+        match image::load_from_memory(&bytes) {
+            Ok(img) => Some(DynamicImage::ImageRgb8(img.to_rgb8())),
+            Err(e) => {
+                tracing::warn!(%url, error = %e, "failed to decode photo");
+                None
+            }
+        }
+    }
+
+    async fn load_font(&self) -> Option<Vec<u8>> {
+        let path = self.font_path.as_ref()?;
+        match tokio::fs::read(path).await {
+            Ok(bytes) => Some(bytes),
+            Err(e) => {
+                tracing::warn!(%path, error = %e, "failed to read export font, falling back to Helvetica");
+                None
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, route, thresholds, labels), fields(route_id = %route.id, point_count = route.points.len(), %elevation_gain_m))]
+    pub async fn export_pdf(
+        &self,
+        route: &Route,
+        thresholds: &DifficultyThresholds,
+        elevation_gain_m: f64,
+        labels: &Catalog,
+    ) -> Result<Vec<u8>, UsecaseError> {
+        tracing::debug!("exporting route to PDF");
+
+        let distance_km = total_distance_km(&route.points);
+        let level = thresholds.classify(distance_km, elevation_gain_m);
+        let difficulty = labels.get(&difficulty_key(level)).unwrap_or(level).to_string();
+
+        let mut thumbnails = Vec::with_capacity(route.points.len());
+        let mut fetches = 0;
+        for point in &route.points {
+            let thumbnail = match point.photo.as_ref() {
+                Some(photo) if photo.status == PhotoStatus::Done && fetches < MAX_PHOTO_FETCHES => {
+                    fetches += 1;
+                    let url = photo.thumbnail_url.as_deref().unwrap_or(&photo.original);
+                    self.fetch_image(url).await
+                }
+                _ => None,
+            };
+            thumbnails.push(thumbnail);
+        }
+        let fetched = thumbnails.iter().filter(|t| t.is_some()).count();
+
+        let font = self.load_font().await;
+        let route = route.clone();
+
+        let bytes = tokio::task::spawn_blocking(move || {
+            render_pdf(&route, distance_km, elevation_gain_m, &difficulty, &thumbnails, font)
+        })
+        .await
+        .map_err(|e| UsecaseError::Internal(format!("PDF rendering task failed: {}", e)))?
+        .map_err(|e| UsecaseError::Internal(format!("failed to render PDF: {}", e)))?;
+
+        tracing::info!(size = bytes.len(), thumbnails = fetched, %distance_km, "route exported to PDF");
+        Ok(bytes)
+    }
+
+    /// Builds a zip with the route GeoJSON, recompressed photos and a manifest,
+    /// so mobile clients can keep a route available without connectivity.
+    #[tracing::instrument(skip(self, route), fields(route_id = %route.id, point_count = route.points.len()))]
+    pub async fn offline_bundle(&self, route: &Route) -> Result<Vec<u8>, UsecaseError> {
+        tracing::debug!("building offline bundle");
+
+        let mut photos: Vec<(usize, Vec<u8>)> = Vec::new();
+        let mut total_bytes = 0;
+        let done = route
+            .points
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, point)| point.photo.as_ref().filter(|p| p.status == PhotoStatus::Done).map(|p| (idx, p)));
+        for (idx, photo) in done.take(MAX_PHOTO_FETCHES) {
+            if let Some(bytes) = self.fetch_bytes(&photo.original).await {
+                total_bytes += bytes.len();
+                if total_bytes > MAX_BUNDLE_PHOTO_BYTES {
+                    tracing::warn!(total_bytes, "offline bundle photos too large");
+                    return Err(UsecaseError::PayloadTooLarge(format!(
+                        "Route photos exceed the offline bundle limit of {} MB",
+                        MAX_BUNDLE_PHOTO_BYTES / (1024 * 1024)
+                    )));
+                }
+                photos.push((idx, bytes));
+            }
+        }
+
+        let route = route.clone();
+        let bytes = tokio::task::spawn_blocking(move || build_bundle(&route, photos))
+            .await
+            .map_err(|e| UsecaseError::Internal(format!("bundle task failed: {}", e)))?
+            .map_err(|e| UsecaseError::Internal(format!("failed to build offline bundle: {}", e)))?;
+
+        tracing::info!(size = bytes.len(), "offline bundle built");
+        Ok(bytes)
+    }
+}
+
+/// Resizes and re-encodes a photo as JPEG for the offline bundle.
+fn compress_photo(data: &[u8], max_width: u32, quality: u8) -> Option<Vec<u8>> {
+    let img = image::load_from_memory(data).ok()?;
+    let img = if img.width() > max_width {
+        let height = (img.height() as f64 * max_width as f64 / img.width() as f64) as u32;
+        img.resize(max_width, height, image::imageops::FilterType::Triangle)
+    } else {
+        img
+    };
+
+    let mut buf = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buf, quality)
+        .encode_image(&DynamicImage::ImageRgb8(img.to_rgb8()))
+        .ok()?;
+    Some(buf)
+}
+
+/// Converts a route into a FeatureCollection that `parse_geojson` can import back:
+/// a LineString for display followed by one Point feature per route point.
+pub fn route_to_geojson(route: &Route, photo_paths: &[Option<String>]) -> FeatureCollection {
+    let mut features = Vec::with_capacity(route.points.len() + 1);
+
+    let mut line_props = JsonObject::new();
+    line_props.insert("name".to_string(), route.name.clone().into());
+    features.push(Feature {
+        bbox: None,
+        geometry: Some(Geometry::new(Value::LineString(
+            route.points.iter().map(|p| vec![p.lng, p.lat]).collect(),
+        ))),
+        id: None,
+        properties: Some(line_props),
+        foreign_members: None,
+    });
+
+    for (idx, point) in route.points.iter().enumerate() {
+        let mut props = JsonObject::new();
+        props.insert("index".to_string(), idx.into());
+        if let Some(name) = &point.name {
+            props.insert("name".to_string(), name.clone().into());
+        }
+        if let Some(mode) = &point.segment_mode {
+            props.insert("segment_mode".to_string(), mode.clone().into());
+        }
+        if let Some(address) = point.address.as_ref().and_then(|a| serde_json::to_value(a).ok()) {
+            props.insert("address".to_string(), address);
+        }
+        if let Some(Some(path)) = photo_paths.get(idx) {
+            props.insert("photo".to_string(), path.clone().into());
+        }
+        features.push(Feature {
+            bbox: None,
+            geometry: Some(Geometry::new(Value::Point(vec![point.lng, point.lat]))),
+            id: None,
+            properties: Some(props),
+            foreign_members: None,
+        });
+    }
+
+    let mut foreign_members = JsonObject::new();
+    foreign_members.insert("name".to_string(), route.name.clone().into());
+    if let Some(description) = &route.description {
+        foreign_members.insert("description".to_string(), description.clone().into());
+    }
+
+    FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: Some(foreign_members),
+    }
+}
+
+fn build_bundle(route: &Route, photos: Vec<(usize, Vec<u8>)>) -> Result<Vec<u8>, zip::result::ZipError> {
+    let mut photo_paths: Vec<Option<String>> = vec![None; route.points.len()];
+    let mut entries: Vec<(String, Vec<u8>, BundleFile)> = Vec::new();
+
+    for (idx, original) in photos {
+        let Some(compressed) = compress_photo(&original, BUNDLE_PHOTO_MAX_WIDTH, BUNDLE_PHOTO_QUALITY) else {
+            tracing::warn!(point_index = idx, "failed to recompress photo, skipping");
+            continue;
+        };
+        let path = format!("photos/{:03}.jpg", idx);
+        photo_paths[idx] = Some(path.clone());
+        let file = BundleFile {
+            path: path.clone(),
+            kind: "photo",
+            point_index: Some(idx),
+            size: compressed.len(),
+        };
+        entries.push((path, compressed, file));
+    }
+
+    let geojson = route_to_geojson(route, &photo_paths).to_string().into_bytes();
+    entries.insert(
+        0,
+        (
+            "route.geojson".to_string(),
+            geojson.clone(),
+            BundleFile {
+                path: "route.geojson".to_string(),
+                kind: "geojson",
+                point_index: None,
+                size: geojson.len(),
+            },
+        ),
+    );
+
+    let (files, contents): (Vec<BundleFile>, Vec<(String, Vec<u8>)>) = entries
+        .into_iter()
+        .map(|(path, bytes, file)| (file, (path, bytes)))
+        .unzip();
+
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        route_id: route.id,
+        name: route.name.clone(),
+        description: route.description.clone(),
+        start_location: route.start_location.clone(),
+        end_location: route.end_location.clone(),
+        created_at: route.created_at,
+        updated_at: route.updated_at,
+        exported_at: Utc::now(),
+        point_count: route.points.len(),
+        distance_km: total_distance_km(&route.points),
+        files,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| zip::result::ZipError::Io(std::io::Error::other(e)))?;
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+    zip.start_file("manifest.json", deflated)?;
+    zip.write_all(&manifest_json)?;
+    for (path, bytes) in contents {
+        // JPEGs don't shrink any further, so store them as-is
+        let options = if path.ends_with(".jpg") { stored } else { deflated };
+        zip.start_file(path, options)?;
+        zip.write_all(&bytes)?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Projects coordinates into a `width` x `height` box (origin bottom-left),
+/// preserving aspect ratio with an equirectangular projection.
+pub fn project_points(coords: &[(f64, f64)], width: f64, height: f64) -> Vec<(f64, f64)> {
+    if coords.is_empty() {
+        return vec![];
+    }
+
+    let (mut min_lat, mut max_lat) = (f64::MAX, f64::MIN);
+    let (mut min_lng, mut max_lng) = (f64::MAX, f64::MIN);
+    for &(lat, lng) in coords {
+        min_lat = min_lat.min(lat);
+        max_lat = max_lat.max(lat);
+        min_lng = min_lng.min(lng);
+        max_lng = max_lng.max(lng);
+    }
+
+    let lng_scale = ((min_lat + max_lat) / 2.0).to_radians().cos().max(0.01);
+    let span_x = ((max_lng - min_lng) * lng_scale).max(1e-9);
+    let span_y = (max_lat - min_lat).max(1e-9);
+    let scale = (width / span_x).min(height / span_y);
+
+    let center_lat = (min_lat + max_lat) / 2.0;
+    let center_lng = (min_lng + max_lng) / 2.0;
+
+    coords
+        .iter()
+        .map(|&(lat, lng)| {
+            (
+                width / 2.0 + (lng - center_lng) * lng_scale * scale,
+                height / 2.0 + (lat - center_lat) * scale,
+            )
+        })
+        .collect()
+}
+
+fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + word.chars().count() + 1 > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn mm(v: f64) -> Mm {
+    Mm(v as f32)
+}
+
+fn rgb(r: f32, g: f32, b: f32) -> Color {
+    Color::Rgb(Rgb::new(r, g, b, None))
+}
+
+/// Tracks the current page/layer and the vertical write position.
+struct PageCursor {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    y: f64,
+}
+
+impl PageCursor {
+    fn ensure_space(&mut self, height: f64) {
+        if self.y - height < MARGIN_MM {
+            let (page, layer) = self.doc.add_page(mm(PAGE_WIDTH_MM), mm(PAGE_HEIGHT_MM), "Layer 1");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT_MM - MARGIN_MM;
+        }
+    }
+
+    fn text(&mut self, text: &str, size: f64, x: f64, font: &IndirectFontRef) {
+        self.layer.use_text(text, size as f32, mm(x), mm(self.y), font);
+    }
+}
+
+fn render_pdf(
+    route: &Route,
+    distance_km: f64,
+    elevation_gain_m: f64,
+    difficulty: &str,
+    thumbnails: &[Option<DynamicImage>],
+    font_bytes: Option<Vec<u8>>,
+) -> Result<Vec<u8>, printpdf::Error> {
+    let (doc, page, layer) = PdfDocument::new(
+        route.name.as_str(),
+        mm(PAGE_WIDTH_MM),
+        mm(PAGE_HEIGHT_MM),
+        "Layer 1",
+    );
+    let font = match font_bytes {
+        Some(bytes) => doc.add_external_font(Cursor::new(bytes))?,
+        None => doc.add_builtin_font(BuiltinFont::Helvetica)?,
+    };
+    let layer = doc.get_page(page).get_layer(layer);
+    let mut cursor = PageCursor {
+        doc,
+        layer,
+        y: PAGE_HEIGHT_MM - MARGIN_MM - 5.0,
+    };
+
+    // Header
+    cursor.text(&route.name, 18.0, MARGIN_MM, &font);
+    cursor.y -= 8.0;
+
+    if let (Some(start), Some(end)) = (&route.start_location, &route.end_location) {
+        cursor.text(&format!("{} → {}", start, end), 11.0, MARGIN_MM, &font);
+        cursor.y -= 6.0;
+    }
+
+    cursor.text(
+        &format!(
+            "Distance: {:.1} km    Elevation gain: {:.0} m    Difficulty: {}    Points: {}",
+            distance_km,
+            elevation_gain_m,
+            difficulty,
+            route.points.len()
+        ),
+        11.0,
+        MARGIN_MM,
+        &font,
+    );
+    cursor.y -= 6.0;
+
+    if let Some(description) = route.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        for line in wrap_text(description, DESCRIPTION_LINE_CHARS)
+            .into_iter()
+            .take(DESCRIPTION_MAX_LINES)
+        {
+            cursor.text(&line, 9.0, MARGIN_MM, &font);
+            cursor.y -= 4.5;
+        }
+    }
+    cursor.y -= 4.0;
+
+    // Map snapshot: route line drawn over a framed box
+    let map_width = PAGE_WIDTH_MM - 2.0 * MARGIN_MM;
+    let map_bottom = cursor.y - MAP_HEIGHT_MM;
+    let frame: Line = [
+        (MARGIN_MM, map_bottom),
+        (MARGIN_MM + map_width, map_bottom),
+        (MARGIN_MM + map_width, cursor.y),
+        (MARGIN_MM, cursor.y),
+    ]
+    .iter()
+    .map(|&(x, y)| (Point::new(mm(x), mm(y)), false))
+    .collect();
+    cursor.layer.set_outline_color(rgb(0.6, 0.6, 0.6));
+    cursor.layer.set_outline_thickness(0.5);
+    cursor.layer.add_line(Line {
+        is_closed: true,
+        ..frame
+    });
+
+    let padding = 6.0;
+    let coords: Vec<(f64, f64)> = route.points.iter().map(|p| (p.lat, p.lng)).collect();
+    let projected: Vec<(f64, f64)> = project_points(&coords, map_width - 2.0 * padding, MAP_HEIGHT_MM - 2.0 * padding)
+        .into_iter()
+        .map(|(x, y)| (MARGIN_MM + padding + x, map_bottom + padding + y))
+        .collect();
+
+    if projected.len() > 1 {
+        cursor.layer.set_outline_color(rgb(0.15, 0.4, 0.85));
+        cursor.layer.set_outline_thickness(1.5);
+        cursor.layer.add_line(
+            projected
+                .iter()
+                .map(|&(x, y)| (Point::new(mm(x), mm(y)), false))
+                .collect(),
+        );
+    }
+
+    for (idx, &(x, y)) in projected.iter().enumerate() {
+        let half = 1.2;
+        cursor.layer.set_fill_color(rgb(0.85, 0.2, 0.2));
+        cursor.layer.add_polygon(
+            [(x - half, y - half), (x + half, y - half), (x + half, y + half), (x - half, y + half)]
+                .iter()
+                .map(|&(px, py)| (Point::new(mm(px), mm(py)), false))
+                .collect::<Polygon>(),
+        );
+        cursor.layer.set_fill_color(rgb(0.0, 0.0, 0.0));
+        cursor.layer.use_text((idx + 1).to_string(), 7.0, mm(x + 1.8), mm(y + 1.0), &font);
+    }
+    cursor.y = map_bottom - 10.0;
+
+    // Ordered point list
+    cursor.ensure_space(8.0);
+    cursor.text("Points", 13.0, MARGIN_MM, &font);
+    cursor.y -= 7.0;
+
+    for (idx, point) in route.points.iter().enumerate() {
+        let thumbnail = thumbnails.get(idx).and_then(|t| t.as_ref());
+        let address = point.address.as_ref().filter(|a| !a.is_empty());
+        let row_height = match (thumbnail, address) {
+            (Some(_), _) => THUMBNAIL_SIZE_MM + 4.0,
+            (None, Some(_)) => 15.0,
+            (None, None) => 11.0,
+        };
+        cursor.ensure_space(row_height);
+
+        let text_x = if let Some(img) = thumbnail {
+            let (w, h) = (img.width() as f64, img.height() as f64);
+            let dpi = (w.max(h) * 25.4 / THUMBNAIL_SIZE_MM) as f32;
+            Image::from_dynamic_image(img).add_to_layer(
+                cursor.layer.clone(),
+                ImageTransform {
+                    translate_x: Some(mm(MARGIN_MM)),
+                    translate_y: Some(mm(cursor.y - THUMBNAIL_SIZE_MM)),
+                    dpi: Some(dpi),
+                    ..Default::default()
+                },
+            );
+            MARGIN_MM + THUMBNAIL_SIZE_MM + 4.0
+        } else {
+            MARGIN_MM
+        };
+
+        let title = match point.name.as_deref().filter(|n| !n.trim().is_empty()) {
+            Some(name) => format!("{}. {}", idx + 1, name),
+            None => format!("{}.", idx + 1),
+        };
+        let row_top = cursor.y;
+        cursor.y -= 4.0;
+        cursor.text(&title, 11.0, text_x, &font);
+        cursor.y -= 5.0;
+
+        if let Some(address) = address {
+            cursor.text(&address.to_string(), 8.0, text_x, &font);
+            cursor.y -= 4.0;
+        }
+        let mut details = format!("{:.5}, {:.5}", point.lat, point.lng);
+        if let Some(mode) = point.segment_mode.as_deref() {
+            details.push_str(&format!("    mode: {}", mode));
+        }
+        cursor.text(&details, 8.0, text_x, &font);
+
+        cursor.y = row_top - row_height;
+    }
+
+    cursor.doc.save_to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::route::{PointAddress, RoutePoint, RouteVisibility};
+    use uuid::Uuid;
+
+    fn make_route(points: Vec<RoutePoint>) -> Route {
+        Route {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "Kazan walk".to_string(),
+            points,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            share_token: None,
+            visibility: RouteVisibility::Private,
+            category_ids: vec![],
+            start_location: Some("Kazan".to_string()),
+            end_location: Some("Kazan".to_string()),
+            seasons: vec![],
+            description: Some("A short walk along the embankment.".to_string()),
+            archived_at: None,
+        }
+    }
+
+    fn point(lat: f64, lng: f64, name: Option<&str>) -> RoutePoint {
+        RoutePoint {
+            lat,
+            lng,
+            name: name.map(|n| n.to_string()),
+            segment_mode: Some("walking".to_string()),
+            photo: None,
+            address: None,
+        }
+    }
+
+    #[test]
+    fn test_project_points_fits_box() {
+        let coords = vec![(55.0, 37.0), (55.1, 37.2), (55.05, 37.1)];
+        let projected = project_points(&coords, 100.0, 50.0);
+
+        assert_eq!(projected.len(), 3);
+        for (x, y) in projected {
+            assert!((0.0..=100.0 + 1e-6).contains(&x), "x out of box: {}", x);
+            assert!((0.0..=50.0 + 1e-6).contains(&y), "y out of box: {}", y);
+        }
+    }
+
+    #[test]
+    fn test_project_points_single_point_centered() {
+        let projected = project_points(&[(55.0, 37.0)], 100.0, 50.0);
+        assert_eq!(projected, vec![(50.0, 25.0)]);
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        let lines = wrap_text("one two three four", 9);
+        assert_eq!(lines, vec!["one two", "three", "four"]);
+    }
+
+    #[test]
+    fn test_resolve_photo_url() {
+        let exporter = RouteExporter::new(Some("http://minio:9000/".to_string()), None);
+        assert_eq!(
+            exporter.resolve_photo_url("/photos/a/b/thumb_0.jpg"),
+            Some("http://minio:9000/photos/a/b/thumb_0.jpg".to_string())
+        );
+        assert_eq!(exporter.resolve_photo_url("https://cdn.example.com/x.jpg"), None);
+        assert_eq!(exporter.resolve_photo_url("http://10.0.0.5/admin"), None);
+        assert_eq!(exporter.resolve_photo_url("//10.0.0.5/admin"), None);
+        assert_eq!(exporter.resolve_photo_url("/photos/../minio/admin"), None);
+        assert_eq!(exporter.resolve_photo_url("/photos/a?x=@10.0.0.5"), None);
+        assert_eq!(exporter.resolve_photo_url("data:image/png;base64,abc"), None);
+
+        let without_base = RouteExporter::new(None, None);
+        assert_eq!(without_base.resolve_photo_url("/photos/x.jpg"), None);
+    }
+
+    #[tokio::test]
+    async fn test_export_pdf_produces_document() {
+        let exporter = RouteExporter::new(None, None);
+        let route = make_route(vec![
+            point(55.7887, 49.1221, Some("Kremlin")),
+            point(55.7903, 49.1147, None),
+            point(55.7963, 49.1088, Some("Embankment")),
+        ]);
+
+        let bytes = exporter
+            .export_pdf(&route, &DifficultyThresholds::default(), 0.0, &Catalog::default())
+            .await
+            .unwrap();
+
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_route_to_geojson_roundtrips_through_import() {
+        let mut route = make_route(vec![
+            point(55.7887, 49.1221, Some("Kremlin")),
+            point(55.7903, 49.1147, None),
+        ]);
+        let address = PointAddress {
+            street: Some("Kremlyovskaya, 2".to_string()),
+            city: Some("Kazan".to_string()),
+            country: Some("Russia".to_string()),
+        };
+        route.points[0].address = Some(address.clone());
+
+        let geojson = route_to_geojson(&route, &[Some("photos/000.jpg".to_string()), None]).to_string();
+        let (name, points) = crate::usecase::geojson_import::parse_geojson(&geojson, usize::MAX).unwrap();
+
+        assert_eq!(name, "Kazan walk");
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].name.as_deref(), Some("Kremlin"));
+        assert_eq!(points[0].address, Some(address));
+        assert_eq!(points[1].lat, 55.7903);
+        assert_eq!(points[1].address, None);
+        assert!(geojson.contains("photos/000.jpg"));
+    }
+
+    #[test]
+    fn test_compress_photo_downscales() {
+        let img = image::RgbImage::from_pixel(2000, 1000, image::Rgb([120, 80, 40]));
+        let mut png = Vec::new();
+        DynamicImage::ImageRgb8(img)
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let jpeg = compress_photo(&png, 1280, 70).unwrap();
+        let decoded = image::load_from_memory(&jpeg).unwrap();
+        assert_eq!(decoded.width(), 1280);
+        assert_eq!(decoded.height(), 640);
+    }
+
+    #[tokio::test]
+    async fn test_offline_bundle_contains_manifest_and_geojson() {
+        let exporter = RouteExporter::new(None, None);
+        let route = make_route(vec![point(55.7887, 49.1221, Some("Kremlin"))]);
+
+        let bytes = exporter.offline_bundle(&route).await.unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+
+        let names: Vec<String> = archive.file_names().map(|n| n.to_string()).collect();
+        assert!(names.contains(&"manifest.json".to_string()));
+        assert!(names.contains(&"route.geojson".to_string()));
+
+        let manifest: serde_json::Value =
+            serde_json::from_reader(archive.by_name("manifest.json").unwrap()).unwrap();
+        assert_eq!(manifest["format_version"], 1);
+        assert_eq!(manifest["point_count"], 1);
+        assert_eq!(manifest["files"][0]["path"], "route.geojson");
+    }
+
+    #[tokio::test]
+    async fn test_export_pdf_paginates_long_routes() {
+        let exporter = RouteExporter::new(None, None);
+        let points = (0..80)
+            .map(|i| point(55.0 + i as f64 * 0.001, 37.0, Some("Stop")))
+            .collect();
+        let route = make_route(points);
+
+        let bytes = exporter
+            .export_pdf(&route, &DifficultyThresholds::default(), 0.0, &Catalog::default())
+            .await
+            .unwrap();
+
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+}
@@ -0,0 +1,354 @@
+use uuid::Uuid;
+
+use crate::domain::route::{Route, RoutePoint};
+use crate::domain::route_geometry::{RouteGeometry, SegmentGeometry};
+use crate::domain::route_stats::{travel_mode, RouteStats};
+use crate::usecase::contracts::{RouteGeometryRepository, RouteRepository};
+use crate::usecase::error::UsecaseError;
+use crate::usecase::geo::haversine_km;
+use crate::usecase::osrm::{profile_for_mode, OsrmClient};
+
+const STRAIGHT_PROFILE: &str = "straight";
+
+pub struct RouteSegmentsUseCase<R, G>
+where
+    R: RouteRepository,
+    G: RouteGeometryRepository,
+{
+    route_repository: R,
+    geometry_repository: G,
+    osrm: Option<OsrmClient>,
+}
+
+impl<R, G> RouteSegmentsUseCase<R, G>
+where
+    R: RouteRepository,
+    G: RouteGeometryRepository,
+{
+    pub fn new(route_repository: R, geometry_repository: G) -> Self {
+        Self {
+            route_repository,
+            geometry_repository,
+            osrm: None,
+        }
+    }
+
+    pub fn with_osrm(mut self, osrm: OsrmClient) -> Self {
+        self.osrm = Some(osrm);
+        self
+    }
+
+    async fn find_owned_route(&self, user_id: Uuid, route_id: Uuid) -> Result<Route, UsecaseError> {
+        let route = self
+            .route_repository
+            .find_by_id(route_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+
+        if route.user_id != user_id {
+            tracing::warn!(%route_id, %user_id, "unauthorized route segments access attempt");
+            return Err(UsecaseError::NotFound("Route".to_string()));
+        }
+        Ok(route)
+    }
+
+    /// Snaps every segment to the road network according to the destination
+    /// point's `segment_mode` and stores the resulting geometry.
+    /// Segments the router cannot resolve fall back to straight lines.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn compute_segments(&self, user_id: Uuid, route_id: Uuid) -> Result<RouteGeometry, UsecaseError> {
+        tracing::debug!("computing route segments");
+
+        let route = self.find_owned_route(user_id, route_id).await?;
+        if route.points.len() < 2 {
+            return Err(UsecaseError::Validation(
+                "Route needs at least two points to compute segments".to_string(),
+            ));
+        }
+
+        let mut segments = Vec::with_capacity(route.points.len() - 1);
+        for (idx, pair) in route.points.windows(2).enumerate() {
+            segments.push(self.compute_segment(idx, &pair[0], &pair[1]).await);
+        }
+
+        let geometry = RouteGeometry::new(route.id, segments, route.updated_at);
+        self.geometry_repository.upsert(&geometry).await?;
+
+        tracing::info!(
+            %route_id,
+            segment_count = geometry.segments.len(),
+            distance_km = geometry.distance_km,
+            "route segments computed"
+        );
+        Ok(geometry)
+    }
+
+    async fn compute_segment(&self, idx: usize, from: &RoutePoint, to: &RoutePoint) -> SegmentGeometry {
+        let straight = |profile: &str| SegmentGeometry {
+            from_index: idx,
+            to_index: idx + 1,
+            profile: profile.to_string(),
+            snapped: false,
+            distance_km: haversine_km(from.lat, from.lng, to.lat, to.lng),
+            coordinates: vec![[from.lng, from.lat], [to.lng, to.lat]],
+        };
+
+        let Some(profile) = profile_for_mode(to.segment_mode.as_deref()) else {
+            return straight(STRAIGHT_PROFILE);
+        };
+        let Some(osrm) = &self.osrm else {
+            return straight(profile);
+        };
+
+        match osrm.route(profile, (from.lat, from.lng), (to.lat, to.lng)).await {
+            Ok(path) => SegmentGeometry {
+                from_index: idx,
+                to_index: idx + 1,
+                profile: profile.to_string(),
+                snapped: true,
+                distance_km: path.distance_km,
+                coordinates: path.coordinates,
+            },
+            Err(e) => {
+                tracing::warn!(segment = idx, %profile, error = %e, "osrm routing failed, using straight line");
+                straight(profile)
+            }
+        }
+    }
+
+    /// Returns the stored geometry and whether the route changed since it was computed.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn get_segments(
+        &self,
+        user_id: Uuid,
+        route_id: Uuid,
+    ) -> Result<(RouteGeometry, bool), UsecaseError> {
+        tracing::debug!("getting route segments");
+
+        let route = self.find_owned_route(user_id, route_id).await?;
+        let geometry = self
+            .geometry_repository
+            .find_by_route_id(route_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Route segments".to_string()))?;
+
+        let stale = geometry.is_stale(route.updated_at);
+        Ok((geometry, stale))
+    }
+
+    /// Distance and travel time per mode. Segment lengths come from the
+    /// stored geometry while it matches the route, straight lines otherwise;
+    /// the flag tells which.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn get_stats(&self, user_id: Uuid, route_id: Uuid) -> Result<(RouteStats, bool), UsecaseError> {
+        let route = self.find_owned_route(user_id, route_id).await?;
+        let geometry = self
+            .geometry_repository
+            .find_by_route_id(route_id)
+            .await?
+            .filter(|g| !g.is_stale(route.updated_at) && g.segments.len() + 1 == route.points.len());
+        let from_geometry = geometry.is_some();
+
+        let segments = route.points.windows(2).enumerate().map(|(idx, pair)| {
+            let distance_km = geometry
+                .as_ref()
+                .map(|g| g.segments[idx].distance_km)
+                .unwrap_or_else(|| haversine_km(pair[0].lat, pair[0].lng, pair[1].lat, pair[1].lng));
+            (travel_mode(pair[1].segment_mode.as_deref()), distance_km)
+        });
+        let stats = RouteStats::from_segments(segments);
+
+        tracing::debug!(distance_km = stats.distance_km, modes = stats.by_mode.len(), from_geometry, "route stats computed");
+        Ok((stats, from_geometry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    use crate::domain::route::RouteVisibility;
+    use crate::usecase::contracts::{MockRouteGeometryRepository, MockRouteRepository};
+
+    fn point(lat: f64, lng: f64, mode: Option<&str>) -> RoutePoint {
+        RoutePoint {
+            lat,
+            lng,
+            name: None,
+            segment_mode: mode.map(String::from),
+            photo: None,
+            address: None,
+        }
+    }
+
+    fn test_route(user_id: Uuid, points: Vec<RoutePoint>) -> Route {
+        Route {
+            id: Uuid::new_v4(),
+            user_id,
+            name: "Test".to_string(),
+            points,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            share_token: None,
+            visibility: RouteVisibility::Private,
+            category_ids: vec![],
+            start_location: None,
+            end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compute_segments_manual_and_unreachable_router() {
+        let user_id = Uuid::new_v4();
+        let route = test_route(
+            user_id,
+            vec![
+                point(55.0, 37.0, None),
+                point(55.01, 37.0, Some("manual")),
+                point(55.02, 37.0, Some("bike")),
+            ],
+        );
+        let route_id = route.id;
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        let mut geometry_repo = MockRouteGeometryRepository::new();
+        geometry_repo
+            .expect_upsert()
+            .withf(move |g| g.route_id == route_id && g.segments.len() == 2)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let usecase = RouteSegmentsUseCase::new(route_repo, geometry_repo)
+            .with_osrm(OsrmClient::new("http://127.0.0.1:9".to_string()));
+        let geometry = usecase.compute_segments(user_id, route_id).await.unwrap();
+
+        assert_eq!(geometry.segments[0].profile, STRAIGHT_PROFILE);
+        assert_eq!(geometry.segments[1].profile, "bike");
+        assert!(geometry.segments.iter().all(|s| !s.snapped));
+        assert_eq!(geometry.segments[1].coordinates, vec![[37.0, 55.01], [37.0, 55.02]]);
+        let expected = 2.0 * haversine_km(55.0, 37.0, 55.01, 37.0);
+        assert!((geometry.distance_km - expected).abs() < 1e-3);
+    }
+
+    #[tokio::test]
+    async fn test_compute_segments_requires_two_points() {
+        let user_id = Uuid::new_v4();
+        let route = test_route(user_id, vec![point(55.0, 37.0, None)]);
+        let route_id = route.id;
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        let mut geometry_repo = MockRouteGeometryRepository::new();
+        geometry_repo.expect_upsert().never();
+
+        let usecase = RouteSegmentsUseCase::new(route_repo, geometry_repo);
+        let result = usecase.compute_segments(user_id, route_id).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_compute_segments_other_user_not_found() {
+        let route = test_route(Uuid::new_v4(), vec![point(55.0, 37.0, None), point(55.1, 37.0, None)]);
+        let route_id = route.id;
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+
+        let usecase = RouteSegmentsUseCase::new(route_repo, MockRouteGeometryRepository::new());
+        let result = usecase.compute_segments(Uuid::new_v4(), route_id).await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_stats_prefers_current_geometry() {
+        let user_id = Uuid::new_v4();
+        let route = test_route(
+            user_id,
+            vec![point(55.0, 37.0, None), point(55.01, 37.0, Some("auto")), point(55.02, 37.0, Some("car"))],
+        );
+        let route_id = route.id;
+        let updated_at = route.updated_at;
+        let segment = |idx: usize, profile: &str, distance_km: f64| SegmentGeometry {
+            from_index: idx,
+            to_index: idx + 1,
+            profile: profile.to_string(),
+            snapped: true,
+            distance_km,
+            coordinates: vec![],
+        };
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        let mut geometry_repo = MockRouteGeometryRepository::new();
+        geometry_repo.expect_find_by_route_id().returning(move |id| {
+            Ok(Some(RouteGeometry::new(id, vec![segment(0, "foot", 2.0), segment(1, "car", 25.0)], updated_at)))
+        });
+
+        let usecase = RouteSegmentsUseCase::new(route_repo, geometry_repo);
+        let (stats, from_geometry) = usecase.get_stats(user_id, route_id).await.unwrap();
+
+        assert!(from_geometry);
+        assert_eq!(stats.distance_km, 27.0);
+        let modes: Vec<&str> = stats.by_mode.iter().map(|m| m.mode.as_str()).collect();
+        assert_eq!(modes, vec!["walking", "driving"]);
+        assert!((stats.duration_min - 54.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_get_stats_without_geometry_uses_straight_lines() {
+        let user_id = Uuid::new_v4();
+        let route = test_route(user_id, vec![point(55.0, 37.0, None), point(55.01, 37.0, Some("bike"))]);
+        let route_id = route.id;
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        let mut geometry_repo = MockRouteGeometryRepository::new();
+        geometry_repo.expect_find_by_route_id().returning(|_| Ok(None));
+
+        let usecase = RouteSegmentsUseCase::new(route_repo, geometry_repo);
+        let (stats, from_geometry) = usecase.get_stats(user_id, route_id).await.unwrap();
+
+        assert!(!from_geometry);
+        assert_eq!(stats.by_mode[0].mode, "cycling");
+        assert!((stats.distance_km - haversine_km(55.0, 37.0, 55.01, 37.0)).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_get_segments_reports_staleness() {
+        let user_id = Uuid::new_v4();
+        let route = test_route(user_id, vec![point(55.0, 37.0, None), point(55.1, 37.0, None)]);
+        let route_id = route.id;
+        let computed_for = route.updated_at - chrono::Duration::minutes(5);
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        let mut geometry_repo = MockRouteGeometryRepository::new();
+        geometry_repo
+            .expect_find_by_route_id()
+            .returning(move |id| Ok(Some(RouteGeometry::new(id, vec![], computed_for))));
+
+        let usecase = RouteSegmentsUseCase::new(route_repo, geometry_repo);
+        let (_, stale) = usecase.get_segments(user_id, route_id).await.unwrap();
+
+        assert!(stale);
+    }
+}
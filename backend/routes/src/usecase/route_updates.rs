@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::domain::notification::{Notification, CONFIGURABLE_NOTIFICATION_TYPES, NOTIFICATION_TYPE_ROUTE_UPDATED};
+use crate::usecase::contracts::{BookmarkRepository, NotificationPreferenceRepository, NotificationRepository};
+use crate::usecase::error::UsecaseError;
+use crate::usecase::metrics;
+
+pub use guide_helper_api::events::{RouteUpdatedEvent, ROUTE_UPDATED_SUBJECT};
+
+/// Fans route update events out to users who saved the route, and manages
+/// the per-user opt-out preferences for such notifications.
+pub struct RouteUpdatesUseCase<B, N, P>
+where
+    B: BookmarkRepository,
+    N: NotificationRepository,
+    P: NotificationPreferenceRepository,
+{
+    bookmark_repository: B,
+    notification_repository: N,
+    preference_repository: P,
+}
+
+impl<B, N, P> RouteUpdatesUseCase<B, N, P>
+where
+    B: BookmarkRepository,
+    N: NotificationRepository,
+    P: NotificationPreferenceRepository,
+{
+    pub fn new(bookmark_repository: B, notification_repository: N, preference_repository: P) -> Self {
+        Self {
+            bookmark_repository,
+            notification_repository,
+            preference_repository,
+        }
+    }
+
+    /// Notifies every bookmarker except the owner and users who opted out.
+    /// Returns the number of notifications created.
+    #[tracing::instrument(skip(self, event), fields(route_id = %event.route_id))]
+    pub async fn notify_route_updated(&self, event: &RouteUpdatedEvent) -> Result<usize, UsecaseError> {
+        tracing::debug!("notifying route followers");
+
+        let followers: Vec<Uuid> = self
+            .bookmark_repository
+            .find_user_ids_by_route(event.route_id)
+            .await?
+            .into_iter()
+            .filter(|id| *id != event.owner_id)
+            .collect();
+        if followers.is_empty() {
+            tracing::debug!("route has no followers");
+            return Ok(0);
+        }
+
+        let opted_out: HashSet<Uuid> = self
+            .preference_repository
+            .find_opted_out(NOTIFICATION_TYPE_ROUTE_UPDATED, followers.clone())
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut notified = 0;
+        for user_id in followers.into_iter().filter(|id| !opted_out.contains(id)) {
+            let notification = Notification::from_template(
+                user_id,
+                NOTIFICATION_TYPE_ROUTE_UPDATED.to_string(),
+                event.route_id,
+                event.actor_name.clone(),
+                "notification.route_updated",
+                serde_json::json!({ "route": event.route_name }),
+            );
+            match self.notification_repository.create(&notification).await {
+                Ok(()) => {
+                    metrics::notification_created(NOTIFICATION_TYPE_ROUTE_UPDATED);
+                    notified += 1;
+                }
+                Err(e) => tracing::warn!(%user_id, error = %e, "failed to create route update notification"),
+            }
+        }
+
+        tracing::info!(route_id = %event.route_id, notified, skipped = opted_out.len(), "route followers notified");
+        Ok(notified)
+    }
+
+    /// Enabled state of every configurable notification type for the user.
+    #[tracing::instrument(skip(self), fields(%user_id))]
+    pub async fn get_preferences(&self, user_id: Uuid) -> Result<HashMap<String, bool>, UsecaseError> {
+        let disabled = self.preference_repository.find_disabled_types(user_id).await?;
+        Ok(CONFIGURABLE_NOTIFICATION_TYPES
+            .iter()
+            .map(|t| (t.to_string(), !disabled.iter().any(|d| d == t)))
+            .collect())
+    }
+
+    #[tracing::instrument(skip(self, preferences), fields(%user_id))]
+    pub async fn set_preferences(
+        &self,
+        user_id: Uuid,
+        preferences: HashMap<String, bool>,
+    ) -> Result<HashMap<String, bool>, UsecaseError> {
+        if let Some(unknown) = preferences
+            .keys()
+            .find(|t| !CONFIGURABLE_NOTIFICATION_TYPES.contains(&t.as_str()))
+        {
+            return Err(UsecaseError::Validation(format!(
+                "Unknown notification type: {}. Must be one of: {}",
+                unknown,
+                CONFIGURABLE_NOTIFICATION_TYPES.join(", ")
+            )));
+        }
+
+        for (notification_type, enabled) in &preferences {
+            self.preference_repository
+                .set_enabled(user_id, notification_type, *enabled)
+                .await?;
+        }
+
+        tracing::info!(%user_id, count = preferences.len(), "notification preferences updated");
+        self.get_preferences(user_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::usecase::contracts::{
+        MockBookmarkRepository, MockNotificationPreferenceRepository, MockNotificationRepository,
+    };
+
+    fn event(owner_id: Uuid) -> RouteUpdatedEvent {
+        RouteUpdatedEvent {
+            route_id: Uuid::new_v4(),
+            owner_id,
+            route_name: "Lake loop".to_string(),
+            actor_name: "owner@example.com".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_skips_owner_and_opted_out_users() {
+        let owner = Uuid::new_v4();
+        let follower = Uuid::new_v4();
+        let muted = Uuid::new_v4();
+
+        let mut bookmark_repo = MockBookmarkRepository::new();
+        bookmark_repo
+            .expect_find_user_ids_by_route()
+            .returning(move |_| Ok(vec![owner, follower, muted]));
+        let mut preference_repo = MockNotificationPreferenceRepository::new();
+        preference_repo
+            .expect_find_opted_out()
+            .withf(move |t, ids| t == NOTIFICATION_TYPE_ROUTE_UPDATED && ids.len() == 2 && !ids.contains(&owner))
+            .returning(move |_, _| Ok(vec![muted]));
+        let mut notification_repo = MockNotificationRepository::new();
+        notification_repo
+            .expect_create()
+            .withf(move |n| n.user_id == follower && n.message.contains("Lake loop"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let usecase = RouteUpdatesUseCase::new(bookmark_repo, notification_repo, preference_repo);
+        let notified = usecase.notify_route_updated(&event(owner)).await.unwrap();
+
+        assert_eq!(notified, 1);
+    }
+
+    #[tokio::test]
+    async fn test_notify_without_followers_does_nothing() {
+        let owner = Uuid::new_v4();
+
+        let mut bookmark_repo = MockBookmarkRepository::new();
+        bookmark_repo
+            .expect_find_user_ids_by_route()
+            .returning(move |_| Ok(vec![owner]));
+        let mut preference_repo = MockNotificationPreferenceRepository::new();
+        preference_repo.expect_find_opted_out().never();
+
+        let usecase = RouteUpdatesUseCase::new(bookmark_repo, MockNotificationRepository::new(), preference_repo);
+        assert_eq!(usecase.notify_route_updated(&event(owner)).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_preferences_defaults_to_enabled() {
+        let mut preference_repo = MockNotificationPreferenceRepository::new();
+        preference_repo.expect_find_disabled_types().returning(|_| Ok(vec![]));
+
+        let usecase = RouteUpdatesUseCase::new(
+            MockBookmarkRepository::new(),
+            MockNotificationRepository::new(),
+            preference_repo,
+        );
+        let preferences = usecase.get_preferences(Uuid::new_v4()).await.unwrap();
+
+        assert_eq!(preferences.get(NOTIFICATION_TYPE_ROUTE_UPDATED), Some(&true));
+    }
+
+    #[tokio::test]
+    async fn test_set_preferences_rejects_unknown_type() {
+        let mut preference_repo = MockNotificationPreferenceRepository::new();
+        preference_repo.expect_set_enabled().never();
+
+        let usecase = RouteUpdatesUseCase::new(
+            MockBookmarkRepository::new(),
+            MockNotificationRepository::new(),
+            preference_repo,
+        );
+        let result = usecase
+            .set_preferences(Uuid::new_v4(), HashMap::from([("like".to_string(), false)]))
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+}
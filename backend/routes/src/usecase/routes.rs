@@ -1,21 +1,110 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use chrono::{DateTime, SubsecRound, Utc};
+use guide_helper_api::routes::PointOperation;
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use crate::domain::route::{ExploreRouteRow, PhotoStatus, Route, RoutePoint};
+use crate::domain::quota::{RouteQuota, RouteQuotaStatus, StorageOverview, StorageUsage};
+use crate::domain::route::{
+    ExploreFacets, ExploreRouteRow, PhotoStatus, PointAddress, Route, RouteCover, RoutePoint, RouteVisibility, UserRoutesFilter,
+};
+use crate::domain::route_stats::{travel_mode, RouteStats};
 use crate::usecase::contracts::RouteRepository;
 use crate::usecase::error::UsecaseError;
-use crate::usecase::nominatim::NominatimClient;
+use crate::usecase::geo::{
+    apply_point_operations, haversine_km, join_points, points_changed_significantly, reverse_points,
+    routes_nearly_identical, split_points,
+};
+use crate::usecase::geocoder::{resolve_route_locations, Geocoder};
 use crate::usecase::openai::{OpenAIClient, VisionChatRequest, VisionContentPart, VisionImageUrl, VisionMessage};
+use crate::usecase::photo_tasks::{retry_task_for_route, PhotoProcessTask};
+use crate::usecase::sanitize::TextSanitizer;
+use crate::usecase::settings::DifficultyThresholds;
 
 pub struct RoutesUseCase<R>
 where
     R: RouteRepository,
 {
     route_repository: Arc<R>,
-    nominatim: Option<Arc<NominatimClient>>,
+    geocoder: Option<Arc<dyn Geocoder>>,
     ollama_client: Option<Arc<OpenAIClient>>,
     ollama_vision_model: String,
+    /// Serializes point geocoding jobs so the provider sees at most one request at a time.
+    point_geocoding_queue: Arc<Mutex<()>>,
+    limits: RouteLimits,
+    /// Applies to users without an admin override.
+    default_quota: RouteQuota,
+    sanitizer: TextSanitizer,
+    /// Minimum time between photo retries of the same route.
+    photo_retry_cooldown: Duration,
+    last_photo_retry: Arc<std::sync::Mutex<HashMap<Uuid, Instant>>>,
+    /// How long a shared route stays cached; zero disables the cache.
+    shared_route_ttl: Duration,
+    shared_routes: Arc<std::sync::Mutex<SharedRouteCache>>,
+}
+
+/// A shared route payload stored under its token and `updated_at`, so edits
+/// miss the cache at once. Changes that leave `updated_at` alone (geocoded
+/// names, categories, cover) show up once the entry expires.
+struct CachedSharedRoute {
+    route: Route,
+    cached_at: Instant,
+}
+
+type SharedRouteCache = HashMap<(Uuid, DateTime<Utc>), CachedSharedRoute>;
+
+/// Routes one merge may join.
+pub const MAX_MERGED_ROUTES: usize = 20;
+
+/// Result of [`RoutesUseCase::patch_points`].
+#[derive(Debug)]
+pub enum PointsPatch {
+    /// The operations were saved; `significant` as for [`RoutesUseCase::update_route`].
+    Applied { route: Route, significant: bool },
+    /// The route changed after the version the operations were written
+    /// against; holds the current route so the client can redo its edit.
+    Stale(Route),
+}
+
+/// Size limits for route payloads stored as JSONB.
+#[derive(Debug, Clone)]
+pub struct RouteLimits {
+    pub max_points: usize,
+    /// Maximum length of a point's inline photo payload, in bytes.
+    pub max_photo_bytes: usize,
+    /// MIME types accepted for inline photo data URLs.
+    pub photo_types: Vec<String>,
+}
+
+impl Default for RouteLimits {
+    fn default() -> Self {
+        Self {
+            max_points: 1000,
+            max_photo_bytes: 2 * 1024 * 1024,
+            photo_types: ["image/jpeg", "image/png", "image/webp"].map(String::from).to_vec(),
+        }
+    }
+}
+
+/// MIME type of a `data:` URL, or `None` for other references (photos the
+/// worker already uploaded).
+fn data_url_mime(original: &str) -> Option<&str> {
+    let rest = original.strip_prefix("data:")?;
+    let end = rest.find([';', ',']).unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+/// Distance and estimated travel time along straight lines between the
+/// points, each segment timed by its `segment_mode`. Road-following lengths
+/// are served by the route stats endpoint once segments are computed.
+pub fn route_stats(points: &[RoutePoint]) -> RouteStats {
+    RouteStats::from_segments(points.windows(2).map(|pair| {
+        let distance_km = haversine_km(pair[0].lat, pair[0].lng, pair[1].lat, pair[1].lng);
+        (travel_mode(pair[1].segment_mode.as_deref()), distance_km)
+    }))
 }
 
 impl<R> RoutesUseCase<R>
@@ -25,14 +114,22 @@ where
     pub fn new(route_repository: R) -> Self {
         Self {
             route_repository: Arc::new(route_repository),
-            nominatim: None,
+            geocoder: None,
             ollama_client: None,
             ollama_vision_model: "llama3.2-vision".to_string(),
+            point_geocoding_queue: Arc::new(Mutex::new(())),
+            limits: RouteLimits::default(),
+            default_quota: RouteQuota::default(),
+            sanitizer: TextSanitizer::default(),
+            photo_retry_cooldown: Duration::from_secs(60),
+            last_photo_retry: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            shared_route_ttl: Duration::ZERO,
+            shared_routes: Arc::new(std::sync::Mutex::new(HashMap::new())),
         }
     }
 
-    pub fn with_nominatim(mut self, nominatim: NominatimClient) -> Self {
-        self.nominatim = Some(Arc::new(nominatim));
+    pub fn with_geocoder(mut self, geocoder: Arc<dyn Geocoder>) -> Self {
+        self.geocoder = Some(geocoder);
         self
     }
 
@@ -42,16 +139,190 @@ where
         self
     }
 
+    pub fn with_limits(mut self, limits: RouteLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_quota(mut self, quota: RouteQuota) -> Self {
+        self.default_quota = quota;
+        self
+    }
+
+    pub fn with_sanitizer(mut self, sanitizer: TextSanitizer) -> Self {
+        self.sanitizer = sanitizer;
+        self
+    }
+
+    pub fn with_photo_retry_cooldown(mut self, cooldown: Duration) -> Self {
+        self.photo_retry_cooldown = cooldown;
+        self
+    }
+
+    pub fn with_shared_route_cache(mut self, ttl: Duration) -> Self {
+        self.shared_route_ttl = ttl;
+        self
+    }
+
     pub fn route_repository(&self) -> &R {
         &self.route_repository
     }
 
+    /// Rejects out-of-range coordinates, oversized point lists, inline photos
+    /// of unsupported types and oversized photos (413), before anything
+    /// reaches the photo worker.
+    fn validate_points(&self, points: &[RoutePoint]) -> Result<(), UsecaseError> {
+        if points.len() > self.limits.max_points {
+            return Err(UsecaseError::Validation(format!(
+                "Route has {} points, maximum is {}",
+                points.len(),
+                self.limits.max_points
+            )));
+        }
+
+        for (idx, point) in points.iter().enumerate() {
+            if !(-90.0..=90.0).contains(&point.lat) || !(-180.0..=180.0).contains(&point.lng) {
+                return Err(UsecaseError::Validation(format!(
+                    "Point {} has invalid coordinates ({}, {})",
+                    idx, point.lat, point.lng
+                )));
+            }
+            let Some(photo) = point.photo.as_ref() else {
+                continue;
+            };
+            if let Some(mime) = data_url_mime(&photo.original)
+                .filter(|mime| !self.limits.photo_types.iter().any(|t| t.eq_ignore_ascii_case(mime)))
+            {
+                return Err(UsecaseError::Validation(format!(
+                    "Photo of point {} has unsupported type '{}'; allowed: {}",
+                    idx,
+                    mime,
+                    self.limits.photo_types.join(", ")
+                )));
+            }
+            if photo.original.len() > self.limits.max_photo_bytes {
+                tracing::warn!(point = idx, bytes = photo.original.len(), "inline photo too large");
+                return Err(UsecaseError::PayloadTooLarge(format!(
+                    "Photo of point {} is {} bytes, maximum is {}",
+                    idx,
+                    photo.original.len(),
+                    self.limits.max_photo_bytes
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    async fn effective_quota(&self, user_id: Uuid) -> Result<RouteQuota, UsecaseError> {
+        let override_quota = self.route_repository.find_quota_override(user_id).await?;
+        Ok(override_quota.unwrap_or(self.default_quota))
+    }
+
+    /// Rejects a new route that would take the user past their route count
+    /// (403) or inline photo storage (413) quota.
+    async fn enforce_quota(&self, user_id: Uuid, points: &[RoutePoint]) -> Result<(), UsecaseError> {
+        let quota = self.effective_quota(user_id).await?;
+        if quota.is_unlimited() {
+            return Ok(());
+        }
+
+        let usage = self.route_repository.usage_by_user_id(user_id).await?;
+        if let Some(max_routes) = quota.max_routes.filter(|max| usage.routes >= *max) {
+            tracing::warn!(%user_id, routes = usage.routes, max_routes, "route quota exceeded");
+            return Err(UsecaseError::Forbidden(format!(
+                "Route quota exceeded: {} of {} routes used",
+                usage.routes, max_routes
+            )));
+        }
+
+        let new_photo_bytes = inline_photo_bytes(points);
+        if let Some(max_bytes) = quota.max_photo_bytes.filter(|max| usage.photo_bytes + new_photo_bytes > *max) {
+            tracing::warn!(%user_id, photo_bytes = usage.photo_bytes, new_photo_bytes, max_bytes, "photo quota exceeded");
+            return Err(UsecaseError::PayloadTooLarge(format!(
+                "Photo storage quota exceeded: {} bytes used, route adds {}, limit is {}",
+                usage.photo_bytes, new_photo_bytes, max_bytes
+            )));
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn get_quota_status(&self, user_id: Uuid) -> Result<RouteQuotaStatus, UsecaseError> {
+        let override_quota = self.route_repository.find_quota_override(user_id).await?;
+        let usage = self.route_repository.usage_by_user_id(user_id).await?;
+
+        Ok(RouteQuotaStatus {
+            quota: override_quota.unwrap_or(self.default_quota),
+            override_quota,
+            usage,
+        })
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn get_storage_usage(&self, user_id: Uuid) -> Result<StorageUsage, UsecaseError> {
+        Ok(self.route_repository.storage_by_user_id(user_id).await?)
+    }
+
+    /// Object storage totals plus the `limit` users holding the most.
+    #[tracing::instrument(skip(self))]
+    pub async fn storage_overview(&self, limit: i64) -> Result<StorageOverview, UsecaseError> {
+        let totals = self.route_repository.storage_totals().await?;
+        let top_users = self.route_repository.top_storage_users(limit).await?;
+
+        tracing::debug!(?totals, top = top_users.len(), "storage overview computed");
+        Ok(StorageOverview { totals, top_users })
+    }
+
+    /// Recomputes the stored like and bookmark counters from their tables and
+    /// returns how many routes had drifted.
+    #[tracing::instrument(skip(self))]
+    pub async fn reconcile_counters(&self) -> Result<u64, UsecaseError> {
+        let repaired = self.route_repository.reconcile_counters().await?;
+        if repaired > 0 {
+            metrics::counter!("route_counters_repaired_total").increment(repaired);
+            tracing::warn!(repaired, "route counters drifted and were repaired");
+        }
+        Ok(repaired)
+    }
+
+    /// Sets (or with `None` removes) the admin override of a user's quota.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn set_quota_override(&self, user_id: Uuid, quota: Option<RouteQuota>) -> Result<(), UsecaseError> {
+        let negative = |limit: Option<i64>| limit.is_some_and(|v| v < 0);
+        if quota.is_some_and(|q| negative(q.max_routes) || negative(q.max_photo_bytes)) {
+            return Err(UsecaseError::Validation("Quota limits must not be negative".to_string()));
+        }
+
+        self.route_repository.set_quota_override(user_id, quota).await?;
+        tracing::info!(%user_id, ?quota, "route quota override updated");
+        Ok(())
+    }
+
     /// Spawns a background task to geocode route start/end and persist to DB.
+    fn sanitize_name(&self, name: &str) -> Result<String, UsecaseError> {
+        let name = self.sanitizer.single_line(name);
+        if name.is_empty() {
+            return Err(UsecaseError::Validation("Route name must not be empty".to_string()));
+        }
+        Ok(name)
+    }
+
+    /// Normalizes user-entered point names; blank names are cleared.
+    fn sanitize_point_names(&self, points: &mut [RoutePoint]) {
+        for point in points.iter_mut() {
+            point.name = point
+                .name
+                .as_deref()
+                .map(|n| self.sanitizer.single_line(n))
+                .filter(|n| !n.is_empty());
+        }
+    }
+
     fn spawn_geocoding(&self, route_id: Uuid, points: Vec<RoutePoint>) {
         if points.is_empty() {
             return;
         }
-        let Some(nominatim) = self.nominatim.clone() else {
+        let Some(geocoder) = self.geocoder.clone() else {
             return;
         };
         let repo = Arc::clone(&self.route_repository);
@@ -60,9 +331,8 @@ where
             let first = &points[0];
             let last = &points[points.len() - 1];
 
-            let (start, end) = nominatim
-                .resolve_route_locations((first.lat, first.lng), (last.lat, last.lng))
-                .await;
+            let (start, end) =
+                resolve_route_locations(geocoder.as_ref(), (first.lat, first.lng), (last.lat, last.lng)).await;
 
             let start = if start.is_empty() { None } else { Some(start) };
             let end = if end.is_empty() { None } else { Some(end) };
@@ -73,19 +343,130 @@ where
         });
     }
 
+    /// Checks that the route can have its points geocoded and counts the
+    /// points still missing a name or an address.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn prepare_point_geocoding(&self, user_id: Uuid, route_id: Uuid) -> Result<PendingPointGeocoding, UsecaseError> {
+        let route = self.get_route(user_id, route_id).await?;
+        if self.geocoder.is_none() {
+            return Err(UsecaseError::Unavailable("Geocoding is not configured".to_string()));
+        }
+
+        let pending = PendingPointGeocoding {
+            unnamed: route.points.iter().filter(|p| is_unnamed(p)).count(),
+            without_address: route.points.iter().filter(|p| p.address.is_none()).count(),
+        };
+        tracing::debug!(%route_id, unnamed = pending.unnamed, without_address = pending.without_address, "point geocoding prepared");
+        Ok(pending)
+    }
+
+    /// Reverse-geocodes the name of every unnamed point and the address of
+    /// every point without one. Jobs are queued one at a time and requests
+    /// are spaced to respect the provider's rate limit. Returns the updated
+    /// route when at least one name or address was filled in.
+    #[tracing::instrument(skip(self), fields(route_id = %route_id))]
+    pub async fn geocode_points(&self, route_id: Uuid) -> Result<Option<Route>, UsecaseError> {
+        let Some(geocoder) = self.geocoder.as_ref() else {
+            return Ok(None);
+        };
+
+        let _queue_slot = self.point_geocoding_queue.lock().await;
+        tracing::debug!("point geocoding job started");
+
+        let route = self
+            .route_repository
+            .find_by_id(route_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+
+        let mut resolved = Vec::new();
+        let pending = route
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| is_unnamed(p) || p.address.is_none());
+        for (idx, point) in pending {
+            let mut found = ResolvedPoint {
+                index: idx,
+                lat: point.lat,
+                lng: point.lng,
+                name: None,
+                address: None,
+            };
+            if is_unnamed(point) {
+                let name = geocoder.reverse_geocode(point.lat, point.lng, 18).await;
+                tokio::time::sleep(geocoder.request_interval()).await;
+                found.name = Some(name).filter(|n| !n.is_empty());
+            }
+            if point.address.is_none() {
+                found.address = geocoder.reverse_address(point.lat, point.lng).await;
+                tokio::time::sleep(geocoder.request_interval()).await;
+            }
+            if found.name.is_some() || found.address.is_some() {
+                resolved.push(found);
+            }
+        }
+        if resolved.is_empty() {
+            tracing::debug!(%route_id, "no point names or addresses resolved");
+            return Ok(None);
+        }
+
+        // Reload so edits made while geocoding are not overwritten.
+        let mut route = self
+            .route_repository
+            .find_by_id(route_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+        let mut points = route.points.clone();
+        let applied = apply_resolved_points(&mut points, &resolved);
+        if applied == 0 {
+            tracing::debug!(%route_id, "route changed during geocoding, nothing applied");
+            return Ok(None);
+        }
+
+        route.update(None, Some(points), None, None, None);
+        self.route_repository.update(&route).await?;
+
+        tracing::info!(%route_id, applied, "route points geocoded");
+        Ok(Some(route))
+    }
+
+    /// Finds an existing route of the user that is nearly identical to the given points.
+    #[tracing::instrument(skip(self, points), fields(user_id = %user_id, point_count = points.len()))]
+    pub async fn find_duplicate(&self, user_id: Uuid, points: &[RoutePoint]) -> Result<Option<Route>, UsecaseError> {
+        let duplicate = self
+            .route_repository
+            .find_by_user_id(user_id)
+            .await?
+            .into_iter()
+            .find(|r| routes_nearly_identical(&r.points, points));
+
+        if let Some(route) = &duplicate {
+            tracing::debug!(existing_route_id = %route.id, "found nearly identical route");
+        }
+        Ok(duplicate)
+    }
+
     #[tracing::instrument(skip(self, points), fields(user_id = %user_id, name = %name, point_count = points.len()))]
     pub async fn create_route(
         &self,
         user_id: Uuid,
         name: String,
-        points: Vec<RoutePoint>,
+        mut points: Vec<RoutePoint>,
         category_ids: Vec<Uuid>,
         seasons: Vec<String>,
     ) -> Result<Route, UsecaseError> {
         tracing::debug!(?category_ids, ?seasons, "creating new route");
 
+        self.validate_points(&points)?;
+        let name = self.sanitize_name(&name)?;
+        self.sanitize_point_names(&mut points);
+        self.enforce_quota(user_id, &points).await?;
+
         let route = Route::new(user_id, name, points.clone(), category_ids, seasons);
         self.route_repository.create(&route).await?;
+        metrics::counter!("routes_created_total").increment(1);
+        metrics::histogram!("route_points").record(route.points.len() as f64);
 
         self.spawn_geocoding(route.id, points);
 
@@ -93,6 +474,146 @@ where
         Ok(route)
     }
 
+    /// Creates a route parsed from an uploaded file; `format` labels the
+    /// import metric (e.g. `geojson`).
+    pub async fn import_route(
+        &self,
+        user_id: Uuid,
+        name: String,
+        points: Vec<RoutePoint>,
+        format: &'static str,
+    ) -> Result<Route, UsecaseError> {
+        let route = self.create_route(user_id, name, points, vec![], vec![]).await?;
+        metrics::counter!("routes_imported_total", "format" => format).increment(1);
+        Ok(route)
+    }
+
+    /// Creates a route walking the routes of `parts` in order, each owned by
+    /// the user and paired with whether to walk it backwards. Where one route
+    /// ends at the start of the next, the junction becomes a single point.
+    /// Points keep their photos, the new route gets the categories and
+    /// seasons of all of them, and the source routes are left as they are.
+    #[tracing::instrument(skip(self, name), fields(user_id = %user_id, route_count = parts.len()))]
+    pub async fn merge_routes(
+        &self,
+        user_id: Uuid,
+        parts: &[(Uuid, bool)],
+        name: Option<String>,
+    ) -> Result<Route, UsecaseError> {
+        if parts.len() < 2 || parts.len() > MAX_MERGED_ROUTES {
+            return Err(UsecaseError::Validation(format!(
+                "Between 2 and {} routes can be merged",
+                MAX_MERGED_ROUTES
+            )));
+        }
+        if parts.iter().enumerate().any(|(i, (id, _))| parts[..i].iter().any(|(other, _)| other == id)) {
+            return Err(UsecaseError::Validation("Cannot merge a route with itself".to_string()));
+        }
+
+        let mut points = Vec::new();
+        let mut names = Vec::with_capacity(parts.len());
+        let mut category_ids: Vec<Uuid> = Vec::new();
+        let mut seasons: Vec<String> = Vec::new();
+        for &(route_id, reverse) in parts {
+            let route = self.get_route(user_id, route_id).await?;
+            let route_points = if reverse {
+                reverse_points(&route.points)
+            } else {
+                route.points
+            };
+            points = join_points(points, route_points);
+            for category_id in route.category_ids {
+                if !category_ids.contains(&category_id) {
+                    category_ids.push(category_id);
+                }
+            }
+            for season in route.seasons {
+                if !seasons.contains(&season) {
+                    seasons.push(season);
+                }
+            }
+            names.push(route.name);
+        }
+        let name = name.unwrap_or_else(|| names.join(" + "));
+
+        let route = self.create_route(user_id, name, points, category_ids, seasons).await?;
+        metrics::counter!("routes_merged_total").increment(1);
+
+        tracing::info!(route_id = %route.id, point_count = route.points.len(), "routes merged");
+        Ok(route)
+    }
+
+    /// Splits a route of the user at the point with `point_index`, which
+    /// ends the first part and starts the second. Both parts become new
+    /// routes, or with `truncate_original` the route itself keeps the first
+    /// part. Returns the first and the second part.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn split_route(
+        &self,
+        user_id: Uuid,
+        route_id: Uuid,
+        point_index: usize,
+        truncate_original: bool,
+    ) -> Result<(Route, Route), UsecaseError> {
+        let route = self.get_route(user_id, route_id).await?;
+        if point_index == 0 || point_index + 1 >= route.points.len() {
+            return Err(UsecaseError::Validation(format!(
+                "Point {} cannot split a route of {} points; each part needs at least two",
+                point_index,
+                route.points.len()
+            )));
+        }
+
+        let (head, tail) = split_points(&route.points, point_index);
+        let part_name = |n: u8| format!("{} ({})", route.name, n);
+        let second = self
+            .create_route(user_id, part_name(2), tail, route.category_ids.clone(), route.seasons.clone())
+            .await?;
+        let first = if truncate_original {
+            self.update_route(user_id, route_id, None, Some(head), None, None).await.map(|(first, ..)| first)
+        } else {
+            self.create_route(user_id, part_name(1), head, route.category_ids.clone(), route.seasons.clone())
+                .await
+        };
+        let first = match first {
+            Ok(first) => first,
+            Err(e) => {
+                // Without the first half the second one is a stray duplicate
+                if let Err(cleanup) = self.route_repository.delete(second.id).await {
+                    tracing::error!(second_id = %second.id, error = %cleanup, "failed to remove half of a failed split");
+                }
+                return Err(e);
+            }
+        };
+        metrics::counter!("routes_split_total").increment(1);
+
+        tracing::info!(first_id = %first.id, second_id = %second.id, "route split");
+        Ok((first, second))
+    }
+
+    /// Reverses the point order of a route of the user, swapping its start
+    /// and end locations. Returns the route and its `updated_at` from before
+    /// the reversal, for refreshing cached data computed from it.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn reverse_route(&self, user_id: Uuid, route_id: Uuid) -> Result<(Route, DateTime<Utc>), UsecaseError> {
+        let mut route = self.get_route(user_id, route_id).await?;
+        if route.points.len() < 2 {
+            return Err(UsecaseError::Validation("Route needs at least two points to be reversed".to_string()));
+        }
+
+        let previous_updated_at = route.updated_at;
+        let points = reverse_points(&route.points);
+        route.update(None, Some(points), None, None, None);
+        std::mem::swap(&mut route.start_location, &mut route.end_location);
+        self.route_repository.update(&route).await?;
+        self.route_repository
+            .update_locations(route_id, route.start_location.clone(), route.end_location.clone())
+            .await?;
+
+        tracing::info!(point_count = route.points.len(), "route reversed");
+        Ok((route, previous_updated_at))
+    }
+
     #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
     pub async fn get_route(&self, user_id: Uuid, route_id: Uuid) -> Result<Route, UsecaseError> {
         tracing::debug!("getting route");
@@ -112,19 +633,118 @@ where
         Ok(route)
     }
 
+    /// Builds a processing task for the route's failed photos. Each route can
+    /// be retried once per cooldown, so a failing upstream isn't hammered.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn prepare_photo_retry(&self, user_id: Uuid, route_id: Uuid) -> Result<PhotoProcessTask, UsecaseError> {
+        let route = self.get_route(user_id, route_id).await?;
+        let task = retry_task_for_route(&route)
+            .ok_or_else(|| UsecaseError::Validation("Route has no failed photos to retry".to_string()))?;
+
+        let now = Instant::now();
+        let mut last_retry = self.last_photo_retry.lock().unwrap();
+        if let Some(wait) = last_retry
+            .get(&route_id)
+            .map(|at| self.photo_retry_cooldown.saturating_sub(now.duration_since(*at)))
+            .filter(|wait| !wait.is_zero())
+        {
+            tracing::warn!(retry_in = ?wait, "photo retry rate limited");
+            return Err(UsecaseError::RateLimited(format!(
+                "Photos of this route were retried recently, try again in {} seconds",
+                wait.as_secs().max(1)
+            )));
+        }
+        last_retry.retain(|_, at| now.duration_since(*at) < self.photo_retry_cooldown);
+        last_retry.insert(route_id, now);
+
+        tracing::info!(points = task.point_indices.len(), "photo retry prepared");
+        Ok(task)
+    }
+
+    /// One page of the user's routes matching `filter` and their total.
+    /// `sort` is `newest` (default), `oldest`, `name` or `updated`.
     #[tracing::instrument(skip(self), fields(user_id = %user_id))]
-    pub async fn get_user_routes(&self, user_id: Uuid) -> Result<Vec<Route>, UsecaseError> {
+    pub async fn get_user_routes(
+        &self,
+        user_id: Uuid,
+        filter: UserRoutesFilter,
+        sort: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Route>, i64), UsecaseError> {
         tracing::debug!("getting user routes");
 
-        let routes = self.route_repository.find_by_user_id(user_id).await?;
+        let inverted = filter.created_after.zip(filter.created_before).is_some_and(|(after, before)| after >= before);
+        if inverted {
+            return Err(UsecaseError::Validation("created_after must be earlier than created_before".to_string()));
+        }
+
+        let order_clause = match sort {
+            "oldest" => "r.created_at ASC",
+            "name" => "LOWER(r.name) ASC, r.created_at DESC",
+            "updated" => "r.updated_at DESC",
+            _ => "r.created_at DESC", // "newest" default
+        };
+
+        let routes = self
+            .route_repository
+            .find_by_user_id_paginated(user_id, &filter, order_clause, limit, offset)
+            .await?;
+        let total = self.route_repository.count_by_user_id(user_id, &filter).await?;
 
         // Backfill locations for any routes missing them
         for route in routes.iter().filter(|r| r.start_location.is_none()) {
             self.spawn_geocoding(route.id, route.points.clone());
         }
 
-        tracing::debug!(%user_id, count = routes.len(), "retrieved user routes");
-        Ok(routes)
+        tracing::debug!(%user_id, count = routes.len(), total, "retrieved user routes");
+        Ok((routes, total))
+    }
+
+    /// Archives (or restores) a route of the user. Archived routes keep their
+    /// share link but drop out of the default list and explore.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn set_archived(&self, user_id: Uuid, route_id: Uuid, archived: bool) -> Result<Route, UsecaseError> {
+        let mut route = self.get_route(user_id, route_id).await?;
+        if route.archived_at.is_some() == archived {
+            return Ok(route);
+        }
+
+        route.archived_at = archived.then(Utc::now);
+        self.route_repository.set_archived_at(route_id, route.archived_at).await?;
+
+        tracing::info!(%route_id, archived, "route archive state changed");
+        Ok(route)
+    }
+
+    /// Makes the processed photo of the point at `point_index` the route
+    /// cover, or clears the cover when no index is given.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn set_cover(&self, user_id: Uuid, route_id: Uuid, point_index: Option<usize>) -> Result<Option<RouteCover>, UsecaseError> {
+        let route = self.get_route(user_id, route_id).await?;
+
+        let cover = point_index
+            .map(|index| {
+                let point = route
+                    .points
+                    .get(index)
+                    .ok_or_else(|| UsecaseError::Validation(format!("Point {} does not exist", index)))?;
+                point
+                    .photo
+                    .as_ref()
+                    .filter(|photo| photo.status == PhotoStatus::Done)
+                    .map(|photo| RouteCover {
+                        url: photo.original.clone(),
+                        thumbnail_url: photo.thumbnail_url.clone(),
+                    })
+                    .ok_or_else(|| UsecaseError::Validation(format!("Point {} has no processed photo", index)))
+            })
+            .transpose()?;
+
+        self.route_repository.set_cover(route_id, cover.clone()).await?;
+
+        tracing::info!(?point_index, "route cover updated");
+        Ok(cover)
     }
 
     #[tracing::instrument(skip(self, points), fields(user_id = %user_id, route_id = %route_id))]
@@ -133,12 +753,18 @@ where
         user_id: Uuid,
         route_id: Uuid,
         name: Option<String>,
-        points: Option<Vec<RoutePoint>>,
+        mut points: Option<Vec<RoutePoint>>,
         category_ids: Option<Vec<Uuid>>,
         seasons: Option<Vec<String>>,
-    ) -> Result<Route, UsecaseError> {
+    ) -> Result<(Route, bool), UsecaseError> {
         tracing::debug!(?category_ids, ?seasons, "updating route");
 
+        if let Some(points) = points.as_mut() {
+            self.validate_points(points)?;
+            self.sanitize_point_names(points);
+        }
+        let name = name.map(|n| self.sanitize_name(&n)).transpose()?;
+
         let mut route = self
             .route_repository
             .find_by_id(route_id)
@@ -152,43 +778,109 @@ where
         }
 
         let points_changed = points.is_some();
+        let old_points = route.points.clone();
         route.update(name, points, category_ids, seasons, None);
         self.route_repository.update(&route).await?;
 
         if points_changed {
             self.spawn_geocoding(route.id, route.points.clone());
         }
+        let significant = points_changed && points_changed_significantly(&old_points, &route.points);
 
-        tracing::debug!(%route_id, "route updated successfully");
-        Ok(route)
+        tracing::debug!(%route_id, significant, "route updated successfully");
+        Ok((route, significant))
     }
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
-    pub async fn enable_sharing(&self, user_id: Uuid, route_id: Uuid) -> Result<Uuid, UsecaseError> {
-        tracing::debug!("enabling sharing for route");
+    /// Applies `operations` to the points of a route of the user; the other
+    /// points stay as stored. Operations address points by index, so they are
+    /// only applied to the version `expected_updated_at` names; a route edited
+    /// since is returned as [`PointsPatch::Stale`] and left untouched.
+    #[tracing::instrument(skip(self, operations), fields(user_id = %user_id, route_id = %route_id, operation_count = operations.len()))]
+    pub async fn patch_points(
+        &self,
+        user_id: Uuid,
+        route_id: Uuid,
+        expected_updated_at: DateTime<Utc>,
+        operations: &[PointOperation],
+    ) -> Result<PointsPatch, UsecaseError> {
+        let mut route = self.get_route(user_id, route_id).await?;
+        // Stored timestamps keep microseconds; a response may have carried more
+        if route.updated_at.trunc_subsecs(6) != expected_updated_at.trunc_subsecs(6) {
+            tracing::debug!(current = %route.updated_at, %expected_updated_at, "points edited against an old version");
+            return Ok(PointsPatch::Stale(route));
+        }
 
-        let route = self
+        let read_at = route.updated_at;
+        let old_points = route.points.clone();
+        apply_point_operations(&mut route.points, operations).map_err(UsecaseError::Validation)?;
+        self.validate_points(&route.points)?;
+        self.sanitize_point_names(&mut route.points);
+
+        let updated_at = Utc::now().trunc_subsecs(6);
+        if !self
             .route_repository
-            .find_by_id(route_id)
+            .replace_points(route_id, &route.points, read_at, updated_at)
             .await?
-            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+        {
+            tracing::debug!("route changed while its points were edited");
+            return Ok(PointsPatch::Stale(self.get_route(user_id, route_id).await?));
+        }
 
-        if route.user_id != user_id {
-            tracing::warn!("unauthorized share enable attempt");
-            return Err(UsecaseError::NotFound("Route".to_string()));
+        route.updated_at = updated_at;
+        self.spawn_geocoding(route.id, route.points.clone());
+        let significant = points_changed_significantly(&old_points, &route.points);
+        tracing::debug!(%route_id, significant, "route points patched");
+        Ok(PointsPatch::Applied { route, significant })
+    }
+
+    /// Changes who can see the route. The share token is kept while the
+    /// route is unlisted or public, so its link survives switching between
+    /// the two; making the route private revokes it.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id, visibility = visibility.as_str()))]
+    pub async fn set_visibility(&self, user_id: Uuid, route_id: Uuid, visibility: RouteVisibility) -> Result<Route, UsecaseError> {
+        let route = self.get_route(user_id, route_id).await?;
+        self.change_visibility(route, visibility).await
+    }
+
+    async fn change_visibility(&self, mut route: Route, visibility: RouteVisibility) -> Result<Route, UsecaseError> {
+        let route_id = route.id;
+        if route.visibility == visibility {
+            tracing::debug!(%route_id, "route visibility unchanged");
+            return Ok(route);
         }
 
-        // Reuse existing token if already shared
-        if let Some(token) = route.share_token {
+        let token = visibility
+            .has_link()
+            .then(|| route.share_token.unwrap_or_else(Uuid::new_v4));
+        self.route_repository
+            .set_visibility(route_id, visibility, token)
+            .await?;
+
+        metrics::counter!("route_visibility_changes_total", "visibility" => visibility.as_str()).increment(1);
+        tracing::info!(%route_id, from = route.visibility.as_str(), "route visibility changed");
+        route.visibility = visibility;
+        route.share_token = token;
+        Ok(route)
+    }
+
+    /// Makes a private route reachable through its link and returns the share
+    /// token. Routes that already have a link keep it and their visibility.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn enable_sharing(&self, user_id: Uuid, route_id: Uuid) -> Result<Uuid, UsecaseError> {
+        tracing::debug!("enabling sharing for route");
+
+        let route = self.get_route(user_id, route_id).await?;
+        if let Some(token) = route.share_token.filter(|_| route.visibility.has_link()) {
             tracing::debug!(%route_id, %token, "route already shared, returning existing token");
             return Ok(token);
         }
 
-        let token = Uuid::new_v4();
-        self.route_repository
-            .set_share_token(route_id, Some(token))
-            .await?;
+        let route = self.change_visibility(route, RouteVisibility::Unlisted).await?;
+        let token = route
+            .share_token
+            .ok_or_else(|| UsecaseError::Internal("shared route has no token".to_string()))?;
 
+        metrics::counter!("route_shares_total", "action" => "enable").increment(1);
         tracing::info!(%route_id, %token, "sharing enabled for route");
         Ok(token)
     }
@@ -197,33 +889,57 @@ where
     pub async fn disable_sharing(&self, user_id: Uuid, route_id: Uuid) -> Result<(), UsecaseError> {
         tracing::debug!("disabling sharing for route");
 
-        let route = self
-            .route_repository
-            .find_by_id(route_id)
-            .await?
-            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
-
-        if route.user_id != user_id {
-            tracing::warn!("unauthorized share disable attempt");
-            return Err(UsecaseError::NotFound("Route".to_string()));
-        }
-
-        self.route_repository
-            .set_share_token(route_id, None)
-            .await?;
+        self.set_visibility(user_id, route_id, RouteVisibility::Private).await?;
 
+        metrics::counter!("route_shares_total", "action" => "disable").increment(1);
         tracing::info!(%route_id, "sharing disabled for route");
         Ok(())
     }
 
+    /// Route shared under `token`. With the cache enabled only the cheap
+    /// `updated_at` lookup reaches the database while the entry is fresh.
     #[tracing::instrument(skip(self), fields(share_token = %token))]
     pub async fn get_shared_route(&self, token: Uuid) -> Result<Route, UsecaseError> {
         tracing::debug!("getting shared route by token");
 
+        if self.shared_route_ttl.is_zero() {
+            return self.load_shared_route(token).await;
+        }
+
+        let updated_at = self
+            .route_repository
+            .find_shared_updated_at(token)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Shared route".to_string()))?;
+        let key = (token, updated_at);
+
+        let cached = self
+            .shared_routes
+            .lock()
+            .unwrap()
+            .get(&key)
+            .filter(|entry| entry.cached_at.elapsed() < self.shared_route_ttl)
+            .map(|entry| entry.route.clone());
+        if let Some(route) = cached {
+            metrics::counter!("shared_route_cache_total", "outcome" => "hit").increment(1);
+            tracing::debug!(route_id = %route.id, "shared route served from cache");
+            return Ok(route);
+        }
+
+        metrics::counter!("shared_route_cache_total", "outcome" => "miss").increment(1);
+        let route = self.load_shared_route(token).await?;
+        let mut cache = self.shared_routes.lock().unwrap();
+        cache.retain(|_, entry| entry.cached_at.elapsed() < self.shared_route_ttl);
+        cache.insert(key, CachedSharedRoute { route: route.clone(), cached_at: Instant::now() });
+        Ok(route)
+    }
+
+    async fn load_shared_route(&self, token: Uuid) -> Result<Route, UsecaseError> {
         let route = self
             .route_repository
             .find_by_share_token(token)
             .await?
+            .filter(|route| route.visibility.has_link())
             .ok_or_else(|| UsecaseError::NotFound("Shared route".to_string()))?;
 
         tracing::debug!(route_id = %route.id, "shared route retrieved successfully");
@@ -251,7 +967,7 @@ where
 
         let routes = self
             .route_repository
-            .explore_shared(search.clone(), category_id, season.clone(), order_clause, limit, offset)
+            .explore_shared(None, search.clone(), category_id, season.clone(), order_clause, limit, offset)
             .await?;
         let total = self
             .route_repository
@@ -262,13 +978,36 @@ where
         Ok((routes, total))
     }
 
-    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id, %role))]
-    pub async fn delete_route(&self, user_id: Uuid, route_id: Uuid, role: &str) -> Result<(), UsecaseError> {
-        tracing::debug!("deleting route");
-
-        let route = self
-            .route_repository
-            .find_by_id(route_id)
+    /// How many explore results each category and difficulty level would
+    /// yield with the current filters.
+    #[tracing::instrument(skip(self, thresholds), fields(?search, ?category_id, ?season))]
+    pub async fn explore_facets(
+        &self,
+        search: Option<String>,
+        category_id: Option<Uuid>,
+        season: Option<String>,
+        thresholds: &DifficultyThresholds,
+    ) -> Result<ExploreFacets, UsecaseError> {
+        let categories = self
+            .route_repository
+            .count_explore_by_category(search.clone(), season.clone())
+            .await?;
+        let difficulty = self
+            .route_repository
+            .count_explore_by_difficulty(search, category_id, season, thresholds)
+            .await?;
+
+        tracing::debug!(categories = categories.len(), "computed explore facets");
+        Ok(ExploreFacets { categories, difficulty })
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id, %role))]
+    pub async fn delete_route(&self, user_id: Uuid, route_id: Uuid, role: &str) -> Result<(), UsecaseError> {
+        tracing::debug!("deleting route");
+
+        let route = self
+            .route_repository
+            .find_by_id(route_id)
             .await?
             .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
 
@@ -374,6 +1113,7 @@ where
             return Err(UsecaseError::NotFound("Route".to_string()));
         }
 
+        let description = self.sanitizer.multi_line(&description);
         route.update(None, None, None, None, Some(description));
         self.route_repository.update(&route).await?;
 
@@ -382,10 +1122,75 @@ where
     }
 }
 
+fn is_unnamed(point: &RoutePoint) -> bool {
+    point.name.as_deref().is_none_or(|n| n.trim().is_empty())
+}
+
+/// Size of the `data:` photo payloads that will be stored with the points,
+/// measured the same way as `RouteRepository::usage_by_user_id`.
+fn inline_photo_bytes(points: &[RoutePoint]) -> i64 {
+    points
+        .iter()
+        .filter_map(|p| p.photo.as_ref())
+        .filter(|photo| photo.original.starts_with("data:"))
+        .map(|photo| photo.original.len() as i64)
+        .sum()
+}
+
+/// Points of a route that reverse geocoding can still fill in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingPointGeocoding {
+    pub unnamed: usize,
+    pub without_address: usize,
+}
+
+impl PendingPointGeocoding {
+    pub fn is_empty(&self) -> bool {
+        self.unnamed == 0 && self.without_address == 0
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ResolvedPoint {
+    index: usize,
+    lat: f64,
+    lng: f64,
+    name: Option<String>,
+    address: Option<PointAddress>,
+}
+
+/// Fills in resolved names and addresses for points that are still at the
+/// same position and still lack them. Returns the number of points changed.
+fn apply_resolved_points(points: &mut [RoutePoint], resolved: &[ResolvedPoint]) -> usize {
+    let mut applied = 0;
+    for r in resolved {
+        let Some(point) = points.get_mut(r.index).filter(|p| p.lat == r.lat && p.lng == r.lng) else {
+            continue;
+        };
+        let mut changed = false;
+        if let Some(name) = r.name.as_ref().filter(|_| is_unnamed(point)) {
+            point.name = Some(name.clone());
+            changed = true;
+        }
+        if let Some(address) = r.address.as_ref().filter(|_| point.address.is_none()) {
+            point.address = Some(address.clone());
+            changed = true;
+        }
+        if changed {
+            applied += 1;
+        }
+    }
+    applied
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::quota::RouteUsage;
+    use crate::repository::errors::RepositoryError;
     use crate::usecase::contracts::MockRouteRepository;
+    use crate::usecase::nominatim::NominatimClient;
+    use crate::usecase::sanitize::HtmlPolicy;
 
     fn make_route(user_id: Uuid, route_id: Uuid) -> Route {
         Route {
@@ -396,18 +1201,43 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             share_token: None,
+            visibility: RouteVisibility::Private,
             category_ids: vec![],
             start_location: None,
             end_location: None,
             seasons: vec![],
             description: None,
+            archived_at: None,
         }
     }
 
+    #[test]
+    fn test_route_stats_times_segments_by_mode() {
+        let point = |lat: f64, mode: Option<&str>| RoutePoint {
+            lat,
+            lng: 49.1,
+            name: None,
+            segment_mode: mode.map(String::from),
+            photo: None,
+            address: None,
+        };
+        // 0.1 degree of latitude is about 11.1 km
+        let stats = route_stats(&[point(55.0, None), point(55.1, Some("bike")), point(55.2, None)]);
+
+        assert_eq!(stats.segment_distances_km.len(), 2);
+        assert!((stats.segment_distances_km[0] - 11.12).abs() < 0.01);
+        assert!((stats.distance_km - 22.24).abs() < 0.02);
+        // 11.12 km cycled at 15 km/h and walked at 5 km/h
+        assert!((stats.duration_min - (11.12 / 15.0 + 11.12 / 5.0) * 60.0).abs() < 0.5);
+        assert_eq!(stats.by_mode.len(), 2);
+        assert_eq!(route_stats(&[point(55.0, None)]), RouteStats::default());
+    }
+
     #[tokio::test]
     async fn test_create_route() {
         let mut mock_repo = MockRouteRepository::new();
 
+        mock_repo.expect_find_quota_override().returning(|_| Ok(None));
         mock_repo.expect_create().times(1).returning(|_| Ok(()));
 
         let usecase = RoutesUseCase::new(mock_repo);
@@ -418,10 +1248,11 @@ mod tests {
             name: None,
             segment_mode: None,
             photo: None,
+            address: None,
         }];
 
         let result = usecase
-            .create_route(user_id, "Test Route".to_string(), points, vec![])
+            .create_route(user_id, "Test Route".to_string(), points, vec![], vec![])
             .await;
 
         assert!(result.is_ok());
@@ -430,6 +1261,204 @@ mod tests {
         assert_eq!(route.name, "Test Route");
     }
 
+    #[tokio::test]
+    async fn test_import_route_applies_create_checks() {
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_create().never();
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let result = usecase
+            .import_route(Uuid::new_v4(), "Imported".to_string(), vec![named_point(95.0, 37.0, None)], "geojson")
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_merge_routes_joins_points_and_categories() {
+        let user_id = Uuid::new_v4();
+        let (category_a, category_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut first = make_route(user_id, Uuid::new_v4());
+        first.name = "Park".to_string();
+        first.points = vec![named_point(55.0, 37.0, Some("Gate")), named_point(55.01, 37.0, None)];
+        first.category_ids = vec![category_a];
+        let mut second = make_route(user_id, Uuid::new_v4());
+        second.name = "River".to_string();
+        second.points = vec![named_point(55.03, 37.0, Some("Pier")), named_point(55.01, 37.0, Some("Bridge"))];
+        second.category_ids = vec![category_a, category_b];
+        let (first_id, second_id) = (first.id, second.id);
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(if id == first.id { first.clone() } else { second.clone() })));
+        mock_repo.expect_find_quota_override().returning(|_| Ok(None));
+        mock_repo.expect_create().times(1).returning(|_| Ok(()));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let route = usecase.merge_routes(user_id, &[(first_id, false), (second_id, true)], None).await.unwrap();
+
+        assert_eq!(route.name, "Park + River");
+        let names: Vec<Option<&str>> = route.points.iter().map(|p| p.name.as_deref()).collect();
+        assert_eq!(names, vec![Some("Gate"), Some("Bridge"), Some("Pier")]);
+        assert_eq!(route.category_ids, vec![category_a, category_b]);
+    }
+
+    #[tokio::test]
+    async fn test_merge_routes_joins_days_in_order() {
+        let user_id = Uuid::new_v4();
+        let days: Vec<Route> = [("Day 1", 55.0, 55.1), ("Day 2", 55.1, 55.2), ("Day 3", 55.2, 55.3)]
+            .into_iter()
+            .map(|(name, start, end)| {
+                let mut day = make_route(user_id, Uuid::new_v4());
+                day.name = name.to_string();
+                day.points = vec![named_point(start, 37.0, None), named_point(end, 37.0, None)];
+                day
+            })
+            .collect();
+        let parts: Vec<(Uuid, bool)> = days.iter().map(|d| (d.id, false)).collect();
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(days.iter().find(|d| d.id == id).cloned()));
+        mock_repo.expect_find_quota_override().returning(|_| Ok(None));
+        mock_repo.expect_create().times(1).returning(|_| Ok(()));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let route = usecase.merge_routes(user_id, &parts, None).await.unwrap();
+
+        assert_eq!(route.name, "Day 1 + Day 2 + Day 3");
+        let lats: Vec<f64> = route.points.iter().map(|p| p.lat).collect();
+        assert_eq!(lats, vec![55.0, 55.1, 55.2, 55.3]);
+    }
+
+    #[tokio::test]
+    async fn test_merge_routes_requires_two_owned_routes() {
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(make_route(Uuid::new_v4(), id))));
+        mock_repo.expect_create().never();
+        let usecase = RoutesUseCase::new(mock_repo);
+
+        let single = usecase.merge_routes(user_id, &[(route_id, false)], None).await;
+        assert!(matches!(single, Err(UsecaseError::Validation(_))));
+
+        let same = usecase.merge_routes(user_id, &[(route_id, false), (Uuid::new_v4(), false), (route_id, true)], None).await;
+        assert!(matches!(same, Err(UsecaseError::Validation(_))));
+
+        let foreign = usecase.merge_routes(user_id, &[(route_id, false), (Uuid::new_v4(), false)], None).await;
+        assert!(matches!(foreign, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_split_route_truncates_original() {
+        let user_id = Uuid::new_v4();
+        let mut route = make_route(user_id, Uuid::new_v4());
+        route.points = vec![
+            named_point(55.0, 37.0, Some("Gate")),
+            named_point(55.01, 37.0, Some("Bridge")),
+            named_point(55.02, 37.0, Some("Pier")),
+        ];
+        let route_id = route.id;
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_find_by_id().returning(move |_| Ok(Some(route.clone())));
+        mock_repo.expect_find_quota_override().returning(|_| Ok(None));
+        mock_repo.expect_create().times(1).returning(|_| Ok(()));
+        mock_repo.expect_update().times(1).returning(|_| Ok(()));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let (first, second) = usecase.split_route(user_id, route_id, 1, true).await.unwrap();
+
+        assert_eq!(first.id, route_id);
+        assert_eq!(first.points.len(), 2);
+        assert_eq!(second.name, "Test (2)");
+        let names: Vec<Option<&str>> = second.points.iter().map(|p| p.name.as_deref()).collect();
+        assert_eq!(names, vec![Some("Bridge"), Some("Pier")]);
+    }
+
+    #[tokio::test]
+    async fn test_split_route_removes_second_half_when_original_update_fails() {
+        let user_id = Uuid::new_v4();
+        let mut route = make_route(user_id, Uuid::new_v4());
+        route.points = vec![named_point(55.0, 37.0, None), named_point(55.01, 37.0, None), named_point(55.02, 37.0, None)];
+        let route_id = route.id;
+        let created = Arc::new(std::sync::Mutex::new(None));
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_find_by_id().returning(move |_| Ok(Some(route.clone())));
+        mock_repo.expect_find_quota_override().returning(|_| Ok(None));
+        let created_id = created.clone();
+        mock_repo.expect_create().times(1).returning(move |r| {
+            *created_id.lock().unwrap() = Some(r.id);
+            Ok(())
+        });
+        mock_repo
+            .expect_update()
+            .times(1)
+            .returning(|_| Err(RepositoryError::DatabaseError("connection lost".to_string())));
+        let deleted_id = created.clone();
+        mock_repo
+            .expect_delete()
+            .withf(move |id| Some(*id) == *deleted_id.lock().unwrap())
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let result = usecase.split_route(user_id, route_id, 1, true).await;
+
+        assert!(result.is_err());
+        assert_ne!(*created.lock().unwrap(), Some(route_id));
+    }
+
+    #[tokio::test]
+    async fn test_split_route_rejects_end_points() {
+        let user_id = Uuid::new_v4();
+        let mut route = make_route(user_id, Uuid::new_v4());
+        route.points = vec![named_point(55.0, 37.0, None), named_point(55.01, 37.0, None), named_point(55.02, 37.0, None)];
+        let route_id = route.id;
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_find_by_id().returning(move |_| Ok(Some(route.clone())));
+        mock_repo.expect_create().never();
+        let usecase = RoutesUseCase::new(mock_repo);
+
+        for index in [0, 2, 7] {
+            let result = usecase.split_route(user_id, route_id, index, false).await;
+            assert!(matches!(result, Err(UsecaseError::Validation(_))), "index {}", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reverse_route_swaps_points_and_locations() {
+        let user_id = Uuid::new_v4();
+        let mut route = make_route(user_id, Uuid::new_v4());
+        route.points = vec![named_point(55.0, 37.0, Some("Gate")), named_point(55.01, 37.0, Some("Pier"))];
+        route.start_location = Some("Park".to_string());
+        route.end_location = Some("River".to_string());
+        let route_id = route.id;
+        let updated_at = route.updated_at;
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_find_by_id().returning(move |_| Ok(Some(route.clone())));
+        mock_repo.expect_update().times(1).returning(|_| Ok(()));
+        mock_repo
+            .expect_update_locations()
+            .withf(|_, start, end| start.as_deref() == Some("River") && end.as_deref() == Some("Park"))
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let (reversed, previous) = usecase.reverse_route(user_id, route_id).await.unwrap();
+
+        assert_eq!(previous, updated_at);
+        assert_eq!(reversed.points[0].name.as_deref(), Some("Pier"));
+        assert_eq!(reversed.start_location.as_deref(), Some("River"));
+    }
+
     #[tokio::test]
     async fn test_get_route_success() {
         let mut mock_repo = MockRouteRepository::new();
@@ -468,6 +1497,16 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_reconcile_counters_reports_repaired_routes() {
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_reconcile_counters().times(1).returning(|| Ok(3));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+
+        assert_eq!(usecase.reconcile_counters().await.unwrap(), 3);
+    }
+
     #[tokio::test]
     async fn test_get_route_wrong_user() {
         let mut mock_repo = MockRouteRepository::new();
@@ -489,6 +1528,82 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_prepare_photo_retry_is_rate_limited_per_route() {
+        let mut mock_repo = MockRouteRepository::new();
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let mut route = make_route(user_id, route_id);
+        let mut failed = named_point(55.0, 37.0, None);
+        failed.photo = Some(guide_helper_domain::route::PhotoData {
+            original: "data:image/jpeg;base64,AAAA".to_string(),
+            thumbnail_url: None,
+            status: PhotoStatus::Failed,
+        });
+        route.points = vec![named_point(55.0, 37.0, None), failed];
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+
+        let usecase = RoutesUseCase::new(mock_repo).with_photo_retry_cooldown(Duration::from_secs(60));
+        let task = usecase.prepare_photo_retry(user_id, route_id).await.unwrap();
+        assert_eq!(task.point_indices, vec![1]);
+
+        let result = usecase.prepare_photo_retry(user_id, route_id).await;
+        assert!(matches!(result, Err(UsecaseError::RateLimited(_))));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_photo_retry_without_failed_photos() {
+        let mut mock_repo = MockRouteRepository::new();
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let route = make_route(user_id, route_id);
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let result = usecase.prepare_photo_retry(user_id, route_id).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_set_cover_requires_processed_photo() {
+        let mut mock_repo = MockRouteRepository::new();
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let mut route = make_route(user_id, route_id);
+        let mut done = named_point(55.0, 37.0, None);
+        done.photo = Some(guide_helper_domain::route::PhotoData {
+            original: "https://cdn.example/photo.jpg".to_string(),
+            thumbnail_url: Some("https://cdn.example/thumb.jpg".to_string()),
+            status: PhotoStatus::Done,
+        });
+        route.points = vec![named_point(55.0, 37.0, None), done];
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        mock_repo
+            .expect_set_cover()
+            .withf(move |id, cover| *id == route_id && cover.as_ref().map(|c| c.url.as_str()) == Some("https://cdn.example/photo.jpg"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let cover = usecase.set_cover(user_id, route_id, Some(1)).await.unwrap().unwrap();
+        assert_eq!(cover.thumbnail_url.as_deref(), Some("https://cdn.example/thumb.jpg"));
+
+        let result = usecase.set_cover(user_id, route_id, Some(0)).await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+        let result = usecase.set_cover(user_id, route_id, Some(5)).await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
     #[tokio::test]
     async fn test_delete_route_success() {
         let mut mock_repo = MockRouteRepository::new();
@@ -530,9 +1645,10 @@ mod tests {
             .returning(move |_| Ok(Some(route_clone.clone())));
 
         mock_repo
-            .expect_set_share_token()
+            .expect_set_visibility()
+            .withf(|_, visibility, token| *visibility == RouteVisibility::Unlisted && token.is_some())
             .times(1)
-            .returning(|_, _| Ok(()));
+            .returning(|_, _, _| Ok(()));
 
         let usecase = RoutesUseCase::new(mock_repo);
         let result = usecase.enable_sharing(user_id, route_id).await;
@@ -548,6 +1664,7 @@ mod tests {
         let existing_token = Uuid::new_v4();
         let mut route = make_route(user_id, route_id);
         route.share_token = Some(existing_token);
+        route.visibility = RouteVisibility::Public;
         let route_clone = route.clone();
 
         mock_repo
@@ -570,6 +1687,7 @@ mod tests {
         let route_id = Uuid::new_v4();
         let mut route = make_route(user_id, route_id);
         route.share_token = Some(Uuid::new_v4());
+        route.visibility = RouteVisibility::Unlisted;
         let route_clone = route.clone();
 
         mock_repo
@@ -579,9 +1697,10 @@ mod tests {
             .returning(move |_| Ok(Some(route_clone.clone())));
 
         mock_repo
-            .expect_set_share_token()
+            .expect_set_visibility()
+            .withf(|_, visibility, token| *visibility == RouteVisibility::Private && token.is_none())
             .times(1)
-            .returning(|_, _| Ok(()));
+            .returning(|_, _, _| Ok(()));
 
         let usecase = RoutesUseCase::new(mock_repo);
         let result = usecase.disable_sharing(user_id, route_id).await;
@@ -589,6 +1708,151 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_publishing_unlisted_route_keeps_its_link() {
+        let mut mock_repo = MockRouteRepository::new();
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let token = Uuid::new_v4();
+        let mut route = make_route(user_id, route_id);
+        route.share_token = Some(token);
+        route.visibility = RouteVisibility::Unlisted;
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        mock_repo
+            .expect_set_visibility()
+            .withf(move |_, visibility, t| *visibility == RouteVisibility::Public && *t == Some(token))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let route = usecase.set_visibility(user_id, route_id, RouteVisibility::Public).await.unwrap();
+
+        assert_eq!(route.visibility, RouteVisibility::Public);
+        assert_eq!(route.share_token, Some(token));
+        assert!(matches!(
+            usecase.set_visibility(Uuid::new_v4(), route_id, RouteVisibility::Private).await,
+            Err(UsecaseError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_set_archived_stores_timestamp_once() {
+        let mut mock_repo = MockRouteRepository::new();
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let route = make_route(user_id, route_id);
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(route.clone())));
+        mock_repo
+            .expect_set_archived_at()
+            .withf(move |id, archived_at| *id == route_id && archived_at.is_some())
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let archived = usecase.set_archived(user_id, route_id, true).await.unwrap();
+        assert!(archived.archived_at.is_some());
+
+        // Archiving an already archived route leaves the timestamp alone.
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(archived.clone())));
+        mock_repo.expect_set_archived_at().never();
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        assert!(usecase.set_archived(user_id, route_id, true).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_set_archived_wrong_user() {
+        let mut mock_repo = MockRouteRepository::new();
+        let route_id = Uuid::new_v4();
+        let route = make_route(Uuid::new_v4(), route_id);
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        mock_repo.expect_set_archived_at().never();
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let result = usecase.set_archived(Uuid::new_v4(), route_id, true).await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_user_routes_pages_with_whitelisted_sort() {
+        let mut mock_repo = MockRouteRepository::new();
+        let user_id = Uuid::new_v4();
+        // Locations are set so listing doesn't spawn geocoding backfills.
+        let mut route = make_route(user_id, Uuid::new_v4());
+        route.start_location = Some("A".to_string());
+        let page = vec![route.clone()];
+
+        mock_repo
+            .expect_find_by_user_id_paginated()
+            .withf(|_, filter, order, limit, offset| {
+                filter.search.as_deref() == Some("Kazan")
+                    && order == "LOWER(r.name) ASC, r.created_at DESC"
+                    && *limit == 1
+                    && *offset == 2
+            })
+            .times(1)
+            .returning(move |_, _, _, _, _| Ok(page.clone()));
+        mock_repo
+            .expect_find_by_user_id_paginated()
+            .withf(|_, filter, order, _, _| filter.include_archived && order == "r.created_at DESC")
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(vec![]));
+        mock_repo
+            .expect_count_by_user_id()
+            .returning(|_, filter| Ok(if filter.include_archived { 4 } else { 3 }));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+
+        let filter = UserRoutesFilter {
+            search: Some("Kazan".to_string()),
+            ..Default::default()
+        };
+        let (routes, total) = usecase.get_user_routes(user_id, filter, "name", 1, 2).await.unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].id, route.id);
+        assert_eq!(total, 3);
+
+        // Unknown sorts (and anything spliced into them) fall back to newest first.
+        let filter = UserRoutesFilter {
+            include_archived: true,
+            ..Default::default()
+        };
+        let (_, total) = usecase.get_user_routes(user_id, filter, "name; DROP TABLE routes", 20, 0).await.unwrap();
+        assert_eq!(total, 4);
+    }
+
+    #[tokio::test]
+    async fn test_get_user_routes_rejects_inverted_date_range() {
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_find_by_user_id_paginated().never();
+        let usecase = RoutesUseCase::new(mock_repo);
+        let now = chrono::Utc::now();
+
+        let filter = UserRoutesFilter {
+            created_after: Some(now),
+            created_before: Some(now - chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        let result = usecase.get_user_routes(Uuid::new_v4(), filter, "newest", 20, 0).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
     #[tokio::test]
     async fn test_get_shared_route() {
         let mut mock_repo = MockRouteRepository::new();
@@ -596,6 +1860,7 @@ mod tests {
         let mut route = make_route(Uuid::new_v4(), Uuid::new_v4());
         route.name = "Shared".to_string();
         route.share_token = Some(token);
+        route.visibility = RouteVisibility::Unlisted;
         let route_clone = route.clone();
 
         mock_repo
@@ -627,4 +1892,431 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_get_shared_route_is_cached_until_updated() {
+        let mut mock_repo = MockRouteRepository::new();
+        let token = Uuid::new_v4();
+        let mut route = make_route(Uuid::new_v4(), Uuid::new_v4());
+        route.share_token = Some(token);
+        route.visibility = RouteVisibility::Public;
+        let (first, edited) = (route.updated_at, route.updated_at + chrono::Duration::seconds(5));
+        let versions = std::sync::Mutex::new(vec![None, Some(edited), Some(first), Some(first)]);
+
+        mock_repo
+            .expect_find_shared_updated_at()
+            .times(4)
+            .returning(move |_| Ok(versions.lock().unwrap().pop().unwrap()));
+        mock_repo
+            .expect_find_by_share_token()
+            .times(2)
+            .returning(move |_| Ok(Some(route.clone())));
+
+        let usecase = RoutesUseCase::new(mock_repo).with_shared_route_cache(Duration::from_secs(60));
+
+        usecase.get_shared_route(token).await.unwrap();
+        usecase.get_shared_route(token).await.unwrap();
+        usecase.get_shared_route(token).await.unwrap();
+        assert!(matches!(usecase.get_shared_route(token).await, Err(UsecaseError::NotFound(_))));
+    }
+
+    fn named_point(lat: f64, lng: f64, name: Option<&str>) -> RoutePoint {
+        RoutePoint {
+            lat,
+            lng,
+            name: name.map(String::from),
+            segment_mode: None,
+            photo: None,
+            address: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_resolved_points_skips_moved_and_filled_points() {
+        let mut points = vec![
+            named_point(55.0, 37.0, None),
+            named_point(55.1, 37.1, Some("Kept")),
+            named_point(55.25, 37.2, None),
+            named_point(55.3, 37.3, Some("  ")),
+        ];
+        points[1].address = Some(PointAddress {
+            city: Some("Kazan".to_string()),
+            ..Default::default()
+        });
+        let resolved = |index: usize, lat: f64, lng: f64| ResolvedPoint {
+            index,
+            lat,
+            lng,
+            name: Some(format!("Street {}", index)),
+            address: None,
+        };
+        let addressed = ResolvedPoint {
+            address: Some(PointAddress {
+                street: Some("Bauman Street, 1".to_string()),
+                ..Default::default()
+            }),
+            ..resolved(1, 55.1, 37.1)
+        };
+
+        let applied = apply_resolved_points(
+            &mut points,
+            &[resolved(0, 55.0, 37.0), addressed, resolved(2, 55.2, 37.2), resolved(3, 55.3, 37.3), resolved(9, 0.0, 0.0)],
+        );
+
+        assert_eq!(applied, 2);
+        assert_eq!(points[0].name.as_deref(), Some("Street 0"));
+        assert_eq!(points[1].name.as_deref(), Some("Kept"));
+        assert_eq!(points[1].address.as_ref().unwrap().city.as_deref(), Some("Kazan"));
+        assert_eq!(points[2].name, None);
+        assert_eq!(points[3].name.as_deref(), Some("Street 3"));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_point_geocoding_counts_unnamed_points() {
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let mut route = make_route(user_id, route_id);
+        route.points = vec![named_point(55.0, 37.0, None), named_point(55.1, 37.1, Some("Park"))];
+        route.points[0].address = Some(PointAddress {
+            country: Some("Russia".to_string()),
+            ..Default::default()
+        });
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+
+        let usecase = RoutesUseCase::new(mock_repo)
+            .with_geocoder(Arc::new(NominatimClient::new("http://127.0.0.1:9".to_string())));
+        let pending = usecase.prepare_point_geocoding(user_id, route_id).await.unwrap();
+
+        assert_eq!(pending, PendingPointGeocoding { unnamed: 1, without_address: 1 });
+    }
+
+    #[tokio::test]
+    async fn test_prepare_point_geocoding_without_geocoder_is_unavailable() {
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(make_route(user_id, route_id))));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let result = usecase.prepare_point_geocoding(user_id, route_id).await;
+
+        assert!(matches!(result, Err(UsecaseError::Unavailable(_))));
+    }
+
+    #[tokio::test]
+    async fn test_geocode_points_all_resolved_makes_no_update() {
+        let route_id = Uuid::new_v4();
+        let mut route = make_route(Uuid::new_v4(), route_id);
+        route.points = vec![named_point(55.0, 37.0, Some("Square"))];
+        route.points[0].address = Some(PointAddress {
+            city: Some("Kazan".to_string()),
+            ..Default::default()
+        });
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(route.clone())));
+        mock_repo.expect_update().never();
+
+        let usecase = RoutesUseCase::new(mock_repo)
+            .with_geocoder(Arc::new(NominatimClient::new("http://127.0.0.1:9".to_string())));
+        let result = usecase.geocode_points(route_id).await.unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_route_reports_significant_point_change() {
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let mut route = make_route(user_id, route_id);
+        route.points = vec![named_point(55.0, 37.0, None), named_point(55.01, 37.0, None)];
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        mock_repo.expect_update().times(2).returning(|_| Ok(()));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+
+        let renamed = vec![named_point(55.0, 37.0, Some("Start")), named_point(55.01, 37.0, None)];
+        let (_, significant) = usecase
+            .update_route(user_id, route_id, None, Some(renamed), None, None)
+            .await
+            .unwrap();
+        assert!(!significant);
+
+        let extended = vec![named_point(55.0, 37.0, None), named_point(55.01, 37.0, None), named_point(55.02, 37.0, None)];
+        let (_, significant) = usecase
+            .update_route(user_id, route_id, None, Some(extended), None, None)
+            .await
+            .unwrap();
+        assert!(significant);
+    }
+
+    #[tokio::test]
+    async fn test_patch_points_applies_operations() {
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let mut route = make_route(user_id, route_id);
+        route.points = vec![named_point(55.0, 37.0, Some("Gate")), named_point(55.01, 37.0, Some("Bridge"))];
+        let version = route.updated_at;
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        mock_repo
+            .expect_replace_points()
+            .withf(move |_, _, expected, _| *expected == version)
+            .times(1)
+            .returning(|_, _, _, _| Ok(true));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let operations = [
+            PointOperation::Insert { index: 2, point: named_point(55.02, 37.0, Some("Pier")) },
+            PointOperation::Delete { index: 0 },
+        ];
+        let patch = usecase.patch_points(user_id, route_id, version, &operations).await.unwrap();
+
+        let PointsPatch::Applied { route, significant } = patch else {
+            panic!("expected the operations to be applied, got {patch:?}");
+        };
+        let names: Vec<Option<&str>> = route.points.iter().map(|p| p.name.as_deref()).collect();
+        assert_eq!(names, vec![Some("Bridge"), Some("Pier")]);
+        assert!(significant);
+
+        let missing = usecase
+            .patch_points(user_id, route_id, version, &[PointOperation::Delete { index: 5 }])
+            .await;
+        assert!(matches!(missing, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_patch_points_rejects_old_version() {
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let route = make_route(user_id, route_id);
+        let stale = route.updated_at - chrono::Duration::seconds(1);
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(route.clone())));
+        mock_repo.expect_replace_points().never();
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let patch = usecase
+            .patch_points(user_id, route_id, stale, &[PointOperation::Delete { index: 0 }])
+            .await
+            .unwrap();
+        assert!(matches!(patch, PointsPatch::Stale(_)));
+    }
+
+    #[tokio::test]
+    async fn test_patch_points_does_not_reapply_after_concurrent_edit() {
+        let user_id = Uuid::new_v4();
+        let route_id = Uuid::new_v4();
+        let mut before = make_route(user_id, route_id);
+        before.points = vec![named_point(55.0, 37.0, Some("Gate")), named_point(55.01, 37.0, None)];
+        let version = before.updated_at;
+        // Another edit inserted a point in front while this one was applied
+        let mut after = before.clone();
+        after.points.insert(0, named_point(54.99, 37.0, Some("Parking")));
+        after.updated_at = version + chrono::Duration::seconds(1);
+
+        let reads = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_find_by_id().returning(move |_| {
+            let read = reads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(Some(if read == 0 { before.clone() } else { after.clone() }))
+        });
+        mock_repo
+            .expect_replace_points()
+            .times(1)
+            .returning(|_, _, _, _| Ok(false));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let patch = usecase
+            .patch_points(user_id, route_id, version, &[PointOperation::Delete { index: 0 }])
+            .await
+            .unwrap();
+
+        let PointsPatch::Stale(current) = patch else {
+            panic!("expected the edit to be rejected, got {patch:?}");
+        };
+        let names: Vec<Option<&str>> = current.points.iter().map(|p| p.name.as_deref()).collect();
+        assert_eq!(names, vec![Some("Parking"), Some("Gate"), None]);
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicate_matches_similar_route() {
+        let user_id = Uuid::new_v4();
+        let mut existing = make_route(user_id, Uuid::new_v4());
+        existing.points = vec![named_point(55.0, 37.0, None), named_point(55.02, 37.0, None)];
+        let existing_id = existing.id;
+        let other = make_route(user_id, Uuid::new_v4());
+
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo
+            .expect_find_by_user_id()
+            .returning(move |_| Ok(vec![other.clone(), existing.clone()]));
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let imported = vec![named_point(55.0001, 37.0, None), named_point(55.01, 37.0, None), named_point(55.02, 37.0, None)];
+        let duplicate = usecase.find_duplicate(user_id, &imported).await.unwrap();
+
+        assert_eq!(duplicate.map(|r| r.id), Some(existing_id));
+    }
+
+    #[tokio::test]
+    async fn test_create_route_rejects_invalid_coordinates() {
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_create().never();
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let result = usecase
+            .create_route(Uuid::new_v4(), "Bad".to_string(), vec![named_point(912.0, 37.0, None)], vec![], vec![])
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_route_enforces_limits() {
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_create().never();
+
+        let usecase = RoutesUseCase::new(mock_repo).with_limits(RouteLimits {
+            max_points: 2,
+            max_photo_bytes: 16,
+            photo_types: vec!["image/jpeg".to_string()],
+        });
+
+        let too_many = vec![named_point(55.0, 37.0, None); 3];
+        let result = usecase
+            .create_route(Uuid::new_v4(), "Long".to_string(), too_many, vec![], vec![])
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+
+        let mut with_photo = named_point(55.0, 37.0, None);
+        with_photo.photo = Some(guide_helper_domain::route::PhotoData {
+            original: "data:image/jpeg;base64,AAAAAAAAAAAA".to_string(),
+            thumbnail_url: None,
+            status: PhotoStatus::Pending,
+        });
+        let result = usecase
+            .create_route(Uuid::new_v4(), "Photo".to_string(), vec![with_photo.clone()], vec![], vec![])
+            .await;
+        assert!(matches!(result, Err(UsecaseError::PayloadTooLarge(_))));
+
+        with_photo.photo.as_mut().unwrap().original = "data:image/tiff;base64,AA".to_string();
+        let result = usecase
+            .create_route(Uuid::new_v4(), "Tiff".to_string(), vec![with_photo], vec![], vec![])
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[test]
+    fn test_data_url_mime() {
+        assert_eq!(data_url_mime("data:image/png;base64,AAAA"), Some("image/png"));
+        assert_eq!(data_url_mime("data:image/webp,AAAA"), Some("image/webp"));
+        assert_eq!(data_url_mime("/photos/user/route/photo_0.jpg"), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_route_sanitizes_names() {
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_find_quota_override().returning(|_| Ok(None));
+        mock_repo
+            .expect_create()
+            .withf(|r| r.name == "&lt;img src=x onerror=alert(1)&gt; loop" && r.points[0].name.is_none())
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let usecase = RoutesUseCase::new(mock_repo).with_sanitizer(TextSanitizer::new(HtmlPolicy::Escape));
+        let points = vec![named_point(55.0, 37.0, Some("\u{200B} "))];
+        let route = usecase
+            .create_route(Uuid::new_v4(), " <img src=x onerror=alert(1)>\n loop\u{202E}".to_string(), points, vec![], vec![])
+            .await
+            .unwrap();
+
+        assert_eq!(route.name, "&lt;img src=x onerror=alert(1)&gt; loop");
+    }
+
+    #[tokio::test]
+    async fn test_create_route_enforces_route_quota() {
+        let user_id = Uuid::new_v4();
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_find_quota_override().returning(|_| Ok(None));
+        mock_repo
+            .expect_usage_by_user_id()
+            .returning(|_| Ok(RouteUsage { routes: 3, photo_bytes: 0 }));
+        mock_repo.expect_create().never();
+
+        let usecase = RoutesUseCase::new(mock_repo).with_quota(RouteQuota {
+            max_routes: Some(3),
+            max_photo_bytes: None,
+        });
+        let result = usecase
+            .create_route(user_id, "Fourth".to_string(), vec![named_point(55.0, 37.0, None)], vec![], vec![])
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_route_enforces_photo_quota_with_override() {
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_find_quota_override().returning(|_| {
+            Ok(Some(RouteQuota {
+                max_routes: None,
+                max_photo_bytes: Some(40),
+            }))
+        });
+        mock_repo
+            .expect_usage_by_user_id()
+            .returning(|_| Ok(RouteUsage { routes: 10, photo_bytes: 20 }));
+        mock_repo.expect_create().never();
+
+        // The default quota would reject the route count; the override lifts it.
+        let usecase = RoutesUseCase::new(mock_repo).with_quota(RouteQuota {
+            max_routes: Some(1),
+            max_photo_bytes: None,
+        });
+        let mut with_photo = named_point(55.0, 37.0, None);
+        with_photo.photo = Some(guide_helper_domain::route::PhotoData {
+            original: "data:image/jpeg;base64,AAAAAAAAAAAA".to_string(),
+            thumbnail_url: None,
+            status: PhotoStatus::Pending,
+        });
+        let result = usecase
+            .create_route(Uuid::new_v4(), "Photo".to_string(), vec![with_photo], vec![], vec![])
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::PayloadTooLarge(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_route_rejects_blank_name() {
+        let mut mock_repo = MockRouteRepository::new();
+        mock_repo.expect_update().never();
+
+        let usecase = RoutesUseCase::new(mock_repo);
+        let result = usecase
+            .update_route(Uuid::new_v4(), Uuid::new_v4(), Some("\u{2066}\t".to_string()), None, None, None)
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
 }
@@ -0,0 +1,180 @@
+/// Whether user text is HTML-escaped before it is stored.
+///
+/// The web client renders text through React, which escapes on output, so the
+/// default keeps `<` and friends as typed. `Escape` is for deployments whose
+/// consumers interpolate stored text into HTML directly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HtmlPolicy {
+    #[default]
+    Keep,
+    Escape,
+}
+
+/// Combining marks allowed in a row after a base character; longer runs
+/// ("zalgo" text) are truncated.
+const MAX_COMBINING_MARKS: usize = 2;
+
+/// Normalizes user-visible strings (route and point names, descriptions,
+/// comments, author names) before they are stored.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextSanitizer {
+    html: HtmlPolicy,
+}
+
+impl TextSanitizer {
+    pub fn new(html: HtmlPolicy) -> Self {
+        Self { html }
+    }
+
+    /// For names: line breaks and tabs become spaces, whitespace runs collapse
+    /// to one space, and the result is trimmed.
+    pub fn single_line(&self, input: &str) -> String {
+        let cleaned = strip_unsafe(input, false);
+        let collapsed = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.apply_html_policy(collapsed)
+    }
+
+    /// For free text: keeps line breaks and tabs, normalizes `\r\n` to `\n`
+    /// and trims surrounding whitespace.
+    pub fn multi_line(&self, input: &str) -> String {
+        let normalized = input.replace("\r\n", "\n");
+        let cleaned = strip_unsafe(&normalized, true);
+        self.apply_html_policy(cleaned.trim().to_string())
+    }
+
+    fn apply_html_policy(&self, text: String) -> String {
+        match self.html {
+            HtmlPolicy::Keep => text,
+            HtmlPolicy::Escape => escape_html(&text),
+        }
+    }
+}
+
+pub fn escape_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#x27;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Drops control characters, bidi overrides and invisible formatting
+/// characters, and caps runs of combining marks.
+fn strip_unsafe(input: &str, keep_line_breaks: bool) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut combining_run = 0;
+    for c in input.chars() {
+        if c == '\n' || c == '\t' {
+            out.push(if keep_line_breaks { c } else { ' ' });
+            combining_run = 0;
+            continue;
+        }
+        if c.is_control() || is_invisible_format(c) {
+            continue;
+        }
+        if is_combining_mark(c) {
+            combining_run += 1;
+            if combining_run > MAX_COMBINING_MARKS {
+                continue;
+            }
+        } else {
+            combining_run = 0;
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn is_invisible_format(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'              // soft hyphen
+            | '\u{061C}'        // arabic letter mark
+            | '\u{180E}'        // mongolian vowel separator
+            | '\u{200B}'..='\u{200F}' // zero-width space/joiners, LRM, RLM
+            | '\u{2028}'..='\u{202E}' // line/paragraph separators, bidi embeddings and overrides
+            | '\u{2060}'..='\u{206F}' // word joiner, invisible operators, bidi isolates
+            | '\u{FEFF}'        // byte order mark
+            | '\u{FFF9}'..='\u{FFFB}' // interlinear annotations
+    )
+}
+
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_line_collapses_whitespace_and_controls() {
+        let s = TextSanitizer::default();
+        assert_eq!(s.single_line("  Lake\n\tloop\u{0007}  trail "), "Lake loop trail");
+    }
+
+    #[test]
+    fn test_multi_line_keeps_line_breaks() {
+        let s = TextSanitizer::default();
+        assert_eq!(s.multi_line("  first\r\nsecond\u{0000}\n\tthird  "), "first\nsecond\n\tthird");
+    }
+
+    #[test]
+    fn test_strips_bidi_overrides_and_zero_width() {
+        let s = TextSanitizer::default();
+        // "evil\u{202E}gnp.exe" renders as "evilexe.png"
+        assert_eq!(s.single_line("evil\u{202E}gnp.exe"), "evilgnp.exe");
+        assert_eq!(s.single_line("ad\u{200B}min\u{FEFF}"), "admin");
+        assert_eq!(s.single_line("\u{2066}x\u{2069}"), "x");
+    }
+
+    #[test]
+    fn test_caps_combining_mark_runs() {
+        let s = TextSanitizer::default();
+        let zalgo = "a\u{0301}\u{0302}\u{0303}\u{0304}b";
+        assert_eq!(s.single_line(zalgo), "a\u{0301}\u{0302}b");
+        // Legitimate accents survive
+        assert_eq!(s.single_line("Cafe\u{0301}"), "Cafe\u{0301}");
+    }
+
+    #[test]
+    fn test_xss_payload_kept_verbatim_by_default() {
+        let s = TextSanitizer::default();
+        let payload = "<script>alert('xss')</script>";
+        assert_eq!(s.single_line(payload), payload);
+    }
+
+    #[test]
+    fn test_xss_payloads_escaped_with_escape_policy() {
+        let s = TextSanitizer::new(HtmlPolicy::Escape);
+        assert_eq!(
+            s.single_line("<script>alert('xss')</script>"),
+            "&lt;script&gt;alert(&#x27;xss&#x27;)&lt;/script&gt;"
+        );
+        assert_eq!(
+            s.multi_line("<img src=x onerror=\"alert(1)\">"),
+            "&lt;img src=x onerror=&quot;alert(1)&quot;&gt;"
+        );
+        assert_eq!(s.single_line("Tom & Jerry"), "Tom &amp; Jerry");
+    }
+
+    #[test]
+    fn test_hidden_characters_cannot_split_escaped_tags() {
+        let s = TextSanitizer::new(HtmlPolicy::Escape);
+        assert_eq!(s.single_line("<scr\u{200B}ipt>"), "&lt;script&gt;");
+    }
+}
@@ -0,0 +1,274 @@
+//! Saved explore filters. A periodic job matches routes shared since each
+//! search was last checked and notifies its owner about new matches.
+
+use std::collections::hash_map::{Entry, HashMap};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::notification::{Notification, NOTIFICATION_TYPE_SAVED_SEARCH_MATCH};
+use crate::domain::route::Route;
+use crate::domain::saved_search::{SavedSearch, SearchArea};
+use crate::repository::errors::RepositoryError;
+use crate::usecase::contracts::{NotificationRepository, RouteRepository, SavedSearchRepository};
+use crate::usecase::error::UsecaseError;
+use crate::usecase::metrics;
+
+/// Saved searches one user can keep; each one is evaluated on every run.
+pub const MAX_SAVED_SEARCHES: i64 = 20;
+const MAX_NAME_CHARS: usize = 100;
+const MAX_SEARCH_CHARS: usize = 200;
+
+fn validate_area(area: &SearchArea) -> Result<(), UsecaseError> {
+    let lat_ok = |lat: f64| (-90.0..=90.0).contains(&lat);
+    let lng_ok = |lng: f64| (-180.0..=180.0).contains(&lng);
+    if !(lat_ok(area.min_lat) && lat_ok(area.max_lat) && lng_ok(area.min_lng) && lng_ok(area.max_lng)) {
+        return Err(UsecaseError::Validation("Area coordinates are out of range".to_string()));
+    }
+    if area.min_lat > area.max_lat || area.min_lng > area.max_lng {
+        return Err(UsecaseError::Validation("Area minimum must not exceed its maximum".to_string()));
+    }
+    Ok(())
+}
+
+pub struct SavedSearchesUseCase<S, R, N>
+where
+    S: SavedSearchRepository,
+    R: RouteRepository,
+    N: NotificationRepository,
+{
+    search_repository: S,
+    route_repository: R,
+    notification_repository: N,
+}
+
+impl<S, R, N> SavedSearchesUseCase<S, R, N>
+where
+    S: SavedSearchRepository,
+    R: RouteRepository,
+    N: NotificationRepository,
+{
+    pub fn new(search_repository: S, route_repository: R, notification_repository: N) -> Self {
+        Self {
+            search_repository,
+            route_repository,
+            notification_repository,
+        }
+    }
+
+    /// Only routes shared after the search is saved are reported.
+    #[tracing::instrument(skip(self, name, search), fields(user_id = %user_id, ?category_id))]
+    pub async fn create_search(
+        &self,
+        user_id: Uuid,
+        name: String,
+        search: Option<String>,
+        category_id: Option<Uuid>,
+        area: Option<SearchArea>,
+    ) -> Result<SavedSearch, UsecaseError> {
+        let name = name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(UsecaseError::Validation(format!(
+                "Name must be between 1 and {} characters",
+                MAX_NAME_CHARS
+            )));
+        }
+        let search = search.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        if search.as_ref().is_some_and(|s| s.chars().count() > MAX_SEARCH_CHARS) {
+            return Err(UsecaseError::Validation(format!(
+                "Search text must be at most {} characters",
+                MAX_SEARCH_CHARS
+            )));
+        }
+        if let Some(area) = &area {
+            validate_area(area)?;
+        }
+        if search.is_none() && category_id.is_none() && area.is_none() {
+            return Err(UsecaseError::Validation(
+                "A saved search needs search text, a category or an area".to_string(),
+            ));
+        }
+
+        let count = self.search_repository.count_by_user_id(user_id).await?;
+        if count >= MAX_SAVED_SEARCHES {
+            tracing::warn!(count, "saved search limit reached");
+            return Err(UsecaseError::Validation(format!(
+                "At most {} saved searches are allowed",
+                MAX_SAVED_SEARCHES
+            )));
+        }
+
+        let saved = SavedSearch::new(user_id, name, search, category_id, area);
+        self.search_repository.create(&saved).await?;
+
+        tracing::info!(search_id = %saved.id, "saved search created");
+        Ok(saved)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn list_searches(&self, user_id: Uuid) -> Result<Vec<SavedSearch>, UsecaseError> {
+        let searches = self.search_repository.find_by_user_id(user_id).await?;
+
+        tracing::debug!(count = searches.len(), "retrieved saved searches");
+        Ok(searches)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, search_id = %id))]
+    pub async fn delete_search(&self, user_id: Uuid, id: Uuid) -> Result<(), UsecaseError> {
+        self.search_repository.delete(id, user_id).await.map_err(|e| match e {
+            RepositoryError::NotFound => UsecaseError::NotFound("Saved search".to_string()),
+            e => e.into(),
+        })?;
+
+        tracing::info!("saved search deleted");
+        Ok(())
+    }
+
+    /// Checks every saved search against the routes shared since its last
+    /// check, up to `now`. A search is claimed before its owner is notified,
+    /// so replicas running the job at the same time do not notify twice.
+    /// Returns the number of notifications created.
+    #[tracing::instrument(skip(self), fields(%now))]
+    pub async fn run_alerts(&self, now: DateTime<Utc>) -> Result<usize, UsecaseError> {
+        let searches = self.search_repository.find_all().await?;
+        // Searches checked by the same run share their window
+        let mut shared: HashMap<DateTime<Utc>, Vec<Route>> = HashMap::new();
+        let mut notified = 0;
+
+        for search in &searches {
+            let routes = match shared.entry(search.last_checked_at) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(
+                    self.route_repository
+                        .find_shared_between(search.last_checked_at, now)
+                        .await?,
+                ),
+            };
+
+            let claimed = self
+                .search_repository
+                .mark_checked(search.id, search.last_checked_at, now)
+                .await;
+            match claimed {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    tracing::warn!(search_id = %search.id, error = %e, "failed to claim saved search");
+                    continue;
+                }
+            }
+
+            let matches = routes
+                .iter()
+                .filter(|r| r.user_id != search.user_id && search.matches(r));
+
+            for route in matches {
+                let notification = Notification::from_template(
+                    search.user_id,
+                    NOTIFICATION_TYPE_SAVED_SEARCH_MATCH.to_string(),
+                    route.id,
+                    "system".to_string(),
+                    "notification.saved_search_match",
+                    serde_json::json!({ "route": route.name, "search": search.name }),
+                );
+                match self.notification_repository.create(&notification).await {
+                    Ok(()) => {
+                        metrics::notification_created(NOTIFICATION_TYPE_SAVED_SEARCH_MATCH);
+                        notified += 1;
+                    }
+                    Err(e) => {
+                        tracing::warn!(search_id = %search.id, route_id = %route.id, error = %e, "failed to create saved search notification")
+                    }
+                }
+            }
+        }
+
+        tracing::info!(searches = searches.len(), notified, "saved search alerts evaluated");
+        Ok(notified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    use crate::usecase::contracts::{MockNotificationRepository, MockRouteRepository, MockSavedSearchRepository};
+
+    fn usecase(
+        searches: MockSavedSearchRepository,
+        routes: MockRouteRepository,
+        notifications: MockNotificationRepository,
+    ) -> SavedSearchesUseCase<MockSavedSearchRepository, MockRouteRepository, MockNotificationRepository> {
+        SavedSearchesUseCase::new(searches, routes, notifications)
+    }
+
+    #[tokio::test]
+    async fn test_create_search_requires_a_filter() {
+        let mut searches = MockSavedSearchRepository::new();
+        searches.expect_create().never();
+        let uc = usecase(searches, MockRouteRepository::new(), MockNotificationRepository::new());
+
+        let result = uc.create_search(Uuid::new_v4(), "Anything".to_string(), Some("  ".to_string()), None, None).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_search_rejects_inverted_area() {
+        let uc = usecase(MockSavedSearchRepository::new(), MockRouteRepository::new(), MockNotificationRepository::new());
+        let area = SearchArea { min_lat: 56.0, min_lng: 48.0, max_lat: 55.0, max_lng: 50.0 };
+
+        let result = uc.create_search(Uuid::new_v4(), "Kazan".to_string(), None, None, Some(area)).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_search_enforces_limit() {
+        let mut searches = MockSavedSearchRepository::new();
+        searches.expect_count_by_user_id().returning(|_| Ok(MAX_SAVED_SEARCHES));
+        searches.expect_create().never();
+        let uc = usecase(searches, MockRouteRepository::new(), MockNotificationRepository::new());
+
+        let result = uc.create_search(Uuid::new_v4(), "Parks".to_string(), Some("park".to_string()), None, None).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_run_alerts_notifies_matches_of_claimed_searches() {
+        let now = Utc::now();
+        let owner_id = Uuid::new_v4();
+        let mut claimed = SavedSearch::new(owner_id, "Kremlins".to_string(), Some("kremlin".to_string()), None, None);
+        claimed.last_checked_at = now - Duration::minutes(10);
+        let mut taken = claimed.clone();
+        taken.id = Uuid::new_v4();
+        let claimed_id = claimed.id;
+
+        let mut searches = MockSavedSearchRepository::new();
+        searches.expect_find_all().returning(move || Ok(vec![claimed.clone(), taken.clone()]));
+        searches
+            .expect_mark_checked()
+            .returning(move |id, _, _| Ok(id == claimed_id));
+        let mut routes = MockRouteRepository::new();
+        routes.expect_find_shared_between().times(1).returning(move |_, _| {
+            Ok(vec![
+                Route::new(Uuid::new_v4(), "Kazan Kremlin".to_string(), vec![], vec![], vec![]),
+                Route::new(Uuid::new_v4(), "Lake loop".to_string(), vec![], vec![], vec![]),
+                Route::new(owner_id, "My kremlin".to_string(), vec![], vec![], vec![]),
+            ])
+        });
+        let mut notifications = MockNotificationRepository::new();
+        notifications
+            .expect_create()
+            .withf(move |n| n.user_id == owner_id && n.message.contains("Kazan Kremlin"))
+            .times(1)
+            .returning(|_| Ok(()));
+        let uc = usecase(searches, routes, notifications);
+
+        let notified = uc.run_alerts(now).await.unwrap();
+
+        assert_eq!(notified, 1);
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::domain::route::ExploreRouteRow;
+use crate::usecase::contracts::RouteRepository;
+use crate::usecase::error::UsecaseError;
+use crate::usecase::meilisearch::{MeilisearchClient, RouteDocument};
+
+pub use guide_helper_api::events::{RouteChangedEvent, ROUTE_CHANGED_SUBJECT};
+
+const REINDEX_PAGE_SIZE: i64 = 100;
+
+#[derive(Debug)]
+pub struct SearchResults {
+    pub hits: Vec<RouteDocument>,
+    pub total: i64,
+    /// Facet counts; only available when the search engine answered.
+    pub facets: Option<HashMap<String, HashMap<String, u64>>>,
+    pub engine: &'static str,
+}
+
+pub struct SearchUseCase<R: RouteRepository> {
+    route_repository: R,
+    engine: Option<MeilisearchClient>,
+}
+
+impl<R: RouteRepository> SearchUseCase<R> {
+    pub fn new(route_repository: R) -> Self {
+        Self {
+            route_repository,
+            engine: None,
+        }
+    }
+
+    pub fn with_meilisearch(mut self, client: MeilisearchClient) -> Self {
+        self.engine = Some(client);
+        self
+    }
+
+    pub fn engine(&self) -> Option<&MeilisearchClient> {
+        self.engine.as_ref()
+    }
+
+    /// Searches shared routes. Uses the search engine when configured and
+    /// falls back to SQL when it is absent or failing.
+    #[tracing::instrument(skip(self), fields(%query, ?category_id, ?season, %limit, %offset))]
+    pub async fn search(
+        &self,
+        query: &str,
+        category_id: Option<Uuid>,
+        season: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<SearchResults, UsecaseError> {
+        let query = query.trim();
+
+        if let Some(engine) = &self.engine {
+            match engine.search(query, category_id, season.as_deref(), limit, offset).await {
+                Ok(result) => {
+                    return Ok(SearchResults {
+                        hits: result.hits,
+                        total: result.total,
+                        facets: Some(result.facets),
+                        engine: "meilisearch",
+                    });
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "search engine failed, falling back to SQL");
+                }
+            }
+        }
+
+        let search = Some(query.to_string()).filter(|q| !q.is_empty());
+        let rows = self
+            .route_repository
+            .explore_shared(None, search.clone(), category_id, season.clone(), "r.created_at DESC", limit, offset)
+            .await?;
+        let total = self
+            .route_repository
+            .count_explore_shared(search, category_id, season)
+            .await?;
+
+        tracing::debug!(count = rows.len(), total, "sql search completed");
+        Ok(SearchResults {
+            hits: rows.into_iter().filter_map(row_to_document).collect(),
+            total,
+            facets: None,
+            engine: "sql",
+        })
+    }
+
+    /// Brings the index entry for a route in line with the database:
+    /// shared routes are upserted, everything else is removed.
+    #[tracing::instrument(skip(self), fields(%route_id))]
+    pub async fn index_route(&self, route_id: Uuid) -> Result<(), UsecaseError> {
+        let Some(engine) = &self.engine else {
+            return Ok(());
+        };
+
+        let document = self
+            .route_repository
+            .find_by_id(route_id)
+            .await?
+            .as_ref()
+            .and_then(RouteDocument::from_route);
+
+        match document {
+            Some(doc) => engine.upsert_documents(&[doc]).await?,
+            None => engine.delete_document(route_id).await?,
+        }
+
+        tracing::debug!(%route_id, "route index entry refreshed");
+        Ok(())
+    }
+
+    /// Pushes every shared route into the index. Returns the number of indexed routes.
+    #[tracing::instrument(skip(self))]
+    pub async fn reindex_all(&self) -> Result<usize, UsecaseError> {
+        let Some(engine) = &self.engine else {
+            return Err(UsecaseError::Unavailable("Search engine is not configured".to_string()));
+        };
+
+        let mut indexed = 0;
+        let mut offset = 0;
+        loop {
+            let rows = self
+                .route_repository
+                .explore_shared(None, None, None, None, "r.created_at ASC", REINDEX_PAGE_SIZE, offset)
+                .await?;
+            let page_len = rows.len() as i64;
+
+            let mut documents = Vec::with_capacity(rows.len());
+            for row in rows {
+                if let Some(doc) = self
+                    .route_repository
+                    .find_by_id(row.id)
+                    .await?
+                    .as_ref()
+                    .and_then(RouteDocument::from_route)
+                {
+                    documents.push(doc);
+                }
+            }
+            if !documents.is_empty() {
+                engine.upsert_documents(&documents).await?;
+                indexed += documents.len();
+            }
+
+            if page_len < REINDEX_PAGE_SIZE {
+                break;
+            }
+            offset += page_len;
+        }
+
+        tracing::info!(indexed, "search index rebuilt");
+        Ok(indexed)
+    }
+}
+
+fn row_to_document(row: ExploreRouteRow) -> Option<RouteDocument> {
+    // Rows without a share token cannot be opened by anyone
+    row.share_token?;
+    Some(RouteDocument {
+        id: row.id,
+        name: row.name,
+        description: None,
+        start_location: row.start_location,
+        end_location: row.end_location,
+        category_ids: row.category_ids,
+        seasons: row.seasons,
+        points_count: row.points_count,
+        created_at: row.created_at.timestamp(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    use crate::usecase::contracts::MockRouteRepository;
+
+    fn explore_row(name: &str) -> ExploreRouteRow {
+        ExploreRouteRow {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            points_count: 3,
+            created_at: Utc::now(),
+            share_token: Some(Uuid::new_v4()),
+            likes_count: 0,
+            bookmarks_count: 0,
+            avg_rating: 0.0,
+            ratings_count: 0,
+            comments_count: 0,
+            category_ids: vec![],
+            seasons: vec![],
+            start_location: None,
+            end_location: None,
+            cover_url: None,
+            cover_thumbnail_url: None,
+            path: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_without_engine_uses_sql() {
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_explore_shared()
+            .withf(|_, search, _, season, _, limit, offset| {
+                search.as_deref() == Some("lake") && season.as_deref() == Some("summer") && *limit == 10 && *offset == 0
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _, _| Ok(vec![explore_row("Lake loop")]));
+        route_repo
+            .expect_count_explore_shared()
+            .times(1)
+            .returning(|_, _, _| Ok(1));
+
+        let usecase = SearchUseCase::new(route_repo);
+        let result = usecase
+            .search(" lake ", None, Some("summer".to_string()), 10, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(result.engine, "sql");
+        assert_eq!(result.total, 1);
+        assert_eq!(result.hits[0].name, "Lake loop");
+        assert!(result.facets.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_falls_back_when_engine_unreachable() {
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_explore_shared()
+            .times(1)
+            .returning(|_, _, _, _, _, _, _| Ok(vec![]));
+        route_repo
+            .expect_count_explore_shared()
+            .times(1)
+            .returning(|_, _, _| Ok(0));
+
+        let client = MeilisearchClient::new("http://127.0.0.1:9".to_string(), None, "routes".to_string());
+        let usecase = SearchUseCase::new(route_repo).with_meilisearch(client);
+        let result = usecase.search("lake", None, None, 10, 0).await.unwrap();
+
+        assert_eq!(result.engine, "sql");
+    }
+
+    #[tokio::test]
+    async fn test_index_route_without_engine_is_noop() {
+        let mut route_repo = MockRouteRepository::new();
+        route_repo.expect_find_by_id().never();
+
+        let usecase = SearchUseCase::new(route_repo);
+        assert!(usecase.index_route(Uuid::new_v4()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reindex_without_engine_is_unavailable() {
+        let usecase = SearchUseCase::new(MockRouteRepository::new());
+        assert!(matches!(usecase.reindex_all().await, Err(UsecaseError::Unavailable(_))));
+    }
+}
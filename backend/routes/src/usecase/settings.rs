@@ -1,9 +1,13 @@
+use guide_helper_api::photo::{PhotoPipelineSettings, PHOTO_PIPELINE_SETTINGS_KEY};
 use serde::{Deserialize, Serialize};
 
 use crate::repository::errors::RepositoryError;
+use crate::usecase::content_filter::ContentFilterSettings;
 use crate::usecase::contracts::SettingsRepository;
 
 const DIFFICULTY_THRESHOLDS_KEY: &str = "difficulty_thresholds";
+const CHAT_SYSTEM_PROMPT_KEY: &str = "chat_system_prompt";
+const CONTENT_FILTER_KEY: &str = "content_filter";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DifficultyThresholds {
@@ -28,6 +32,35 @@ impl Default for DifficultyThresholds {
     }
 }
 
+impl DifficultyThresholds {
+    /// Classifies a route as "easy", "moderate" or "hard".
+    /// Mirrors `classifyDifficulty` in the frontend so both sides agree.
+    pub fn classify(&self, distance_km: f64, elevation_gain_m: f64) -> &'static str {
+        let dist_score = if distance_km < self.distance_easy_max_km {
+            1
+        } else if distance_km < self.distance_moderate_max_km {
+            2
+        } else {
+            3
+        };
+        let elev_score = if elevation_gain_m < self.elevation_easy_max_m {
+            1
+        } else if elevation_gain_m < self.elevation_moderate_max_m {
+            2
+        } else {
+            3
+        };
+        let total = dist_score + elev_score;
+        if total <= self.score_easy_max {
+            "easy"
+        } else if total <= self.score_moderate_max {
+            "moderate"
+        } else {
+            "hard"
+        }
+    }
+}
+
 pub struct SettingsUseCase<R: SettingsRepository> {
     settings_repository: R,
 }
@@ -73,4 +106,99 @@ impl<R: SettingsRepository> SettingsUseCase<R> {
         tracing::info!("difficulty thresholds saved");
         Ok(())
     }
+
+    /// The operator's chat prompt template; `None` means the built-in one.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_chat_system_prompt(&self) -> Result<Option<String>, RepositoryError> {
+        let value = self.settings_repository.get_value(CHAT_SYSTEM_PROMPT_KEY).await?;
+
+        match value {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(serde_json::Value::String(template)) => Ok(Some(template)),
+            Some(other) => Err(RepositoryError::DatabaseError(format!(
+                "chat system prompt must be a string, got {}",
+                other
+            ))),
+        }
+    }
+
+    /// Stores a new template; `None` restores the built-in prompt.
+    #[tracing::instrument(skip(self, template))]
+    pub async fn set_chat_system_prompt(&self, template: Option<&str>) -> Result<(), RepositoryError> {
+        let value = template.map_or(serde_json::Value::Null, |t| serde_json::Value::String(t.to_string()));
+
+        self.settings_repository.set_value(CHAT_SYSTEM_PROMPT_KEY, &value).await?;
+
+        tracing::info!(custom = template.is_some(), "chat system prompt saved");
+        Ok(())
+    }
+
+    /// The photo worker's resize and encoding profile; defaults until an
+    /// admin saves one.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_photo_pipeline(&self) -> Result<PhotoPipelineSettings, RepositoryError> {
+        match self.settings_repository.get_value(PHOTO_PIPELINE_SETTINGS_KEY).await? {
+            Some(v) => serde_json::from_value(v)
+                .map_err(|e| RepositoryError::DatabaseError(format!("failed to deserialize photo pipeline settings: {}", e))),
+            None => Ok(PhotoPipelineSettings::default()),
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn set_photo_pipeline(&self, settings: &PhotoPipelineSettings) -> Result<(), RepositoryError> {
+        let value = serde_json::to_value(settings)
+            .map_err(|e| RepositoryError::DatabaseError(format!("failed to serialize photo pipeline settings: {}", e)))?;
+
+        self.settings_repository.set_value(PHOTO_PIPELINE_SETTINGS_KEY, &value).await?;
+
+        tracing::info!(?settings, "photo pipeline settings saved");
+        Ok(())
+    }
+
+    /// The blocked-term filter; an empty one until an admin saves terms.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_content_filter(&self) -> Result<ContentFilterSettings, RepositoryError> {
+        match self.settings_repository.get_value(CONTENT_FILTER_KEY).await? {
+            Some(v) => serde_json::from_value(v)
+                .map_err(|e| RepositoryError::DatabaseError(format!("failed to deserialize content filter: {}", e))),
+            None => Ok(ContentFilterSettings::default()),
+        }
+    }
+
+    #[tracing::instrument(skip(self, settings), fields(mode = %settings.mode, terms = settings.terms.len()))]
+    pub async fn set_content_filter(&self, settings: &ContentFilterSettings) -> Result<(), RepositoryError> {
+        let value = serde_json::to_value(settings)
+            .map_err(|e| RepositoryError::DatabaseError(format!("failed to serialize content filter: {}", e)))?;
+
+        self.settings_repository.set_value(CONTENT_FILTER_KEY, &value).await?;
+
+        tracing::info!("content filter saved");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_default_thresholds() {
+        let thresholds = DifficultyThresholds::default();
+        assert_eq!(thresholds.classify(3.0, 100.0), "easy");
+        assert_eq!(thresholds.classify(10.0, 100.0), "easy");
+        assert_eq!(thresholds.classify(10.0, 500.0), "moderate");
+        assert_eq!(thresholds.classify(20.0, 1000.0), "hard");
+    }
+
+    #[tokio::test]
+    async fn test_photo_pipeline_defaults_until_saved() {
+        let mut repo = crate::usecase::contracts::MockSettingsRepository::new();
+        repo.expect_get_value()
+            .withf(|key| key == PHOTO_PIPELINE_SETTINGS_KEY)
+            .returning(|_| Ok(None));
+
+        let settings = SettingsUseCase::new(repo).get_photo_pipeline().await.unwrap();
+
+        assert_eq!(settings, PhotoPipelineSettings::default());
+    }
 }
@@ -0,0 +1,390 @@
+//! Labelled share links: besides its own share token a route can have
+//! several links, each revocable on its own. Every token handed out for
+//! `/shared/{token}` is signed, so forged or mistyped ones are turned away
+//! before touching the database, and the stored share token never leaves the
+//! service.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::domain::route::Route;
+use crate::domain::share_link::ShareLink;
+use crate::usecase::contracts::{RouteRepository, ShareLinkRepository};
+use crate::usecase::error::UsecaseError;
+use crate::usecase::sanitize::TextSanitizer;
+
+/// Active links a route may have at once.
+pub const MAX_ACTIVE_LINKS: usize = 20;
+const MAX_LABEL_LEN: usize = 100;
+/// Bytes of the HMAC-SHA256 tag kept in the token.
+const TAG_LEN: usize = 16;
+
+/// What a signed token opens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedToken {
+    /// A labelled share link, by link id.
+    Link(Uuid),
+    /// The route's own link, by its stored share token; handed to the owner.
+    Route(Uuid),
+    /// A route listed on explore or in search, by route id. Only opens the
+    /// route while it stays public.
+    Public(Uuid),
+}
+
+impl SignedToken {
+    fn context(&self) -> &'static [u8] {
+        match self {
+            Self::Link(_) => b"share-link.",
+            Self::Route(_) => b"route-share.",
+            Self::Public(_) => b"public-route.",
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        match *self {
+            Self::Link(id) | Self::Route(id) | Self::Public(id) => id,
+        }
+    }
+}
+
+/// Turns a [`SignedToken`] into `<id>.<tag>` (both base64url) and back. The
+/// tag is a truncated HMAC-SHA256 of the id under a per-kind context, so a
+/// token of one kind never verifies as another.
+#[derive(Clone)]
+pub struct ShareTokenSigner {
+    key: Vec<u8>,
+}
+
+impl ShareTokenSigner {
+    pub fn new(secret: &[u8]) -> Self {
+        Self { key: secret.to_vec() }
+    }
+
+    /// Signer keyed by HMAC-SHA256(`jwt_secret`, "share-link"), for setups
+    /// without a share link secret of their own. The JWT secret itself never
+    /// signs share links, but rotating it still invalidates every link.
+    pub fn derived_from(jwt_secret: &[u8]) -> Self {
+        let mut mac = Hmac::<Sha256>::new_from_slice(jwt_secret).expect("HMAC accepts keys of any length");
+        mac.update(b"share-link");
+        Self::new(&mac.finalize().into_bytes())
+    }
+
+    fn mac(&self, token: SignedToken) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(token.context());
+        mac.update(token.id().as_bytes());
+        mac
+    }
+
+    pub fn sign(&self, token: SignedToken) -> String {
+        let tag = self.mac(token).finalize().into_bytes();
+        format!("{}.{}", URL_SAFE_NO_PAD.encode(token.id().as_bytes()), URL_SAFE_NO_PAD.encode(&tag[..TAG_LEN]))
+    }
+
+    /// The token signed with this key, of whichever kind it was signed as.
+    pub fn verify(&self, token: &str) -> Option<SignedToken> {
+        let (id, tag) = token.split_once('.')?;
+        let id = Uuid::from_slice(&URL_SAFE_NO_PAD.decode(id).ok()?).ok()?;
+        let tag = URL_SAFE_NO_PAD.decode(tag).ok()?;
+        if tag.len() != TAG_LEN {
+            return None;
+        }
+        [SignedToken::Link(id), SignedToken::Route(id), SignedToken::Public(id)]
+            .into_iter()
+            .find(|candidate| self.mac(*candidate).verify_truncated_left(&tag).is_ok())
+    }
+}
+
+pub struct ShareLinksUseCase<L, R>
+where
+    L: ShareLinkRepository,
+    R: RouteRepository,
+{
+    link_repository: L,
+    route_repository: R,
+    signer: ShareTokenSigner,
+    sanitizer: TextSanitizer,
+    legacy_tokens_until: Option<DateTime<Utc>>,
+}
+
+impl<L, R> ShareLinksUseCase<L, R>
+where
+    L: ShareLinkRepository,
+    R: RouteRepository,
+{
+    pub fn new(link_repository: L, route_repository: R, signer: ShareTokenSigner) -> Self {
+        Self {
+            link_repository,
+            route_repository,
+            signer,
+            sanitizer: TextSanitizer::default(),
+            legacy_tokens_until: None,
+        }
+    }
+
+    pub fn with_sanitizer(mut self, sanitizer: TextSanitizer) -> Self {
+        self.sanitizer = sanitizer;
+        self
+    }
+
+    /// Keeps accepting bare share tokens, as handed out before links were
+    /// signed, until `until`.
+    pub fn with_legacy_tokens_until(mut self, until: DateTime<Utc>) -> Self {
+        self.legacy_tokens_until = Some(until);
+        self
+    }
+
+    pub fn token(&self, link: &ShareLink) -> String {
+        self.signer.sign(SignedToken::Link(link.id))
+    }
+
+    /// Token of the route's own link, for its owner.
+    pub fn route_token(&self, share_token: Uuid) -> String {
+        self.signer.sign(SignedToken::Route(share_token))
+    }
+
+    /// Token for opening a route listed on explore or in search.
+    pub fn public_token(&self, route_id: Uuid) -> String {
+        self.signer.sign(SignedToken::Public(route_id))
+    }
+
+    async fn find_owned_route(&self, user_id: Uuid, route_id: Uuid) -> Result<Route, UsecaseError> {
+        self.route_repository
+            .find_by_id(route_id)
+            .await?
+            .filter(|r| r.user_id == user_id)
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))
+    }
+
+    /// Adds a link to an unlisted or public route.
+    #[tracing::instrument(skip(self, label), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn create_link(&self, user_id: Uuid, route_id: Uuid, label: &str) -> Result<ShareLink, UsecaseError> {
+        let label = self.sanitizer.single_line(label);
+        if label.is_empty() || label.chars().count() > MAX_LABEL_LEN {
+            return Err(UsecaseError::Validation(format!(
+                "Link label must be between 1 and {} characters",
+                MAX_LABEL_LEN
+            )));
+        }
+
+        let route = self.find_owned_route(user_id, route_id).await?;
+        if !route.visibility.has_link() {
+            return Err(UsecaseError::Validation("Private routes cannot be shared by link".to_string()));
+        }
+        let active = self
+            .link_repository
+            .find_by_route_id(route_id)
+            .await?
+            .iter()
+            .filter(|l| l.is_active())
+            .count();
+        if active >= MAX_ACTIVE_LINKS {
+            return Err(UsecaseError::Validation(format!(
+                "A route can have at most {} active share links",
+                MAX_ACTIVE_LINKS
+            )));
+        }
+
+        let link = ShareLink::new(route_id, label);
+        self.link_repository.create(&link).await?;
+
+        metrics::counter!("share_links_total", "action" => "create").increment(1);
+        tracing::info!(link_id = %link.id, "share link created");
+        Ok(link)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn list_links(&self, user_id: Uuid, route_id: Uuid) -> Result<Vec<ShareLink>, UsecaseError> {
+        self.find_owned_route(user_id, route_id).await?;
+        Ok(self.link_repository.find_by_route_id(route_id).await?)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id, link_id = %link_id))]
+    pub async fn revoke_link(&self, user_id: Uuid, route_id: Uuid, link_id: Uuid) -> Result<(), UsecaseError> {
+        self.find_owned_route(user_id, route_id).await?;
+        self.link_repository.revoke(link_id, route_id, Utc::now()).await?;
+
+        metrics::counter!("share_links_total", "action" => "revoke").increment(1);
+        tracing::info!("share link revoked");
+        Ok(())
+    }
+
+    /// Stored share token of the route behind a `/shared/{token}` token,
+    /// without loading the route. Unsigned tokens, revoked links, routes made
+    /// private since and listed routes no longer public all read as not found.
+    #[tracing::instrument(skip(self, token))]
+    pub async fn resolve(&self, token: &str) -> Result<Uuid, UsecaseError> {
+        let not_found = || UsecaseError::NotFound("Shared route".to_string());
+        let Some(signed) = self.signer.verify(token) else {
+            return self.resolve_legacy(token).ok_or_else(not_found);
+        };
+
+        let share_token = match signed {
+            SignedToken::Route(share_token) => Some(share_token),
+            SignedToken::Link(link_id) => {
+                let link = self
+                    .link_repository
+                    .find_by_id(link_id)
+                    .await?
+                    .filter(ShareLink::is_active)
+                    .ok_or_else(not_found)?;
+                self.route_repository.find_share_token(link.route_id, false).await?
+            }
+            SignedToken::Public(route_id) => self.route_repository.find_share_token(route_id, true).await?,
+        };
+
+        tracing::debug!(?signed, "shared route token resolved");
+        share_token.ok_or_else(not_found)
+    }
+
+    fn resolve_legacy(&self, token: &str) -> Option<Uuid> {
+        let share_token = Uuid::parse_str(token).ok()?;
+        if self.legacy_tokens_until.is_none_or(|until| Utc::now() >= until) {
+            tracing::debug!("share token has no valid signature");
+            return None;
+        }
+        metrics::counter!("share_links_total", "action" => "legacy_token").increment(1);
+        Some(share_token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::route::RouteVisibility;
+    use crate::usecase::contracts::{MockRouteRepository, MockShareLinkRepository};
+
+    fn signer() -> ShareTokenSigner {
+        ShareTokenSigner::new(b"test-secret")
+    }
+
+    fn route_repository(route: Route) -> MockRouteRepository {
+        let mut routes = MockRouteRepository::new();
+        routes.expect_find_by_id().returning(move |_| Ok(Some(route.clone())));
+        routes
+    }
+
+    #[test]
+    fn test_tokens_round_trip_and_reject_tampering() {
+        let id = Uuid::new_v4();
+        let token = signer().sign(SignedToken::Link(id));
+
+        assert_eq!(signer().verify(&token), Some(SignedToken::Link(id)));
+        assert_eq!(ShareTokenSigner::new(b"other").verify(&token), None);
+        let (encoded_id, tag) = token.split_once('.').unwrap();
+        let other_id = URL_SAFE_NO_PAD.encode(Uuid::new_v4().as_bytes());
+        assert_eq!(signer().verify(&format!("{}.{}", other_id, tag)), None);
+        assert_eq!(signer().verify(encoded_id), None);
+        assert_eq!(signer().verify(&id.to_string()), None);
+    }
+
+    #[test]
+    fn test_token_kinds_do_not_verify_as_each_other() {
+        let id = Uuid::new_v4();
+
+        for kind in [SignedToken::Link(id), SignedToken::Route(id), SignedToken::Public(id)] {
+            assert_eq!(signer().verify(&signer().sign(kind)), Some(kind));
+        }
+        assert_ne!(signer().sign(SignedToken::Route(id)), signer().sign(SignedToken::Public(id)));
+    }
+
+    #[test]
+    fn test_derived_signer_does_not_use_the_jwt_secret_as_key() {
+        let token = SignedToken::Route(Uuid::new_v4());
+        let derived = ShareTokenSigner::derived_from(b"jwt-secret").sign(token);
+
+        assert_eq!(ShareTokenSigner::derived_from(b"jwt-secret").verify(&derived), Some(token));
+        assert_eq!(ShareTokenSigner::new(b"jwt-secret").verify(&derived), None);
+        assert_eq!(ShareTokenSigner::derived_from(b"rotated-secret").verify(&derived), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_link_requires_a_shareable_route() {
+        let owner_id = Uuid::new_v4();
+        let mut route = Route::new(owner_id, "Old town".to_string(), vec![], vec![], vec![]);
+        let route_id = route.id;
+        let mut links = MockShareLinkRepository::new();
+        links.expect_create().never();
+        let private = ShareLinksUseCase::new(links, route_repository(route.clone()), signer());
+
+        let result = private.create_link(owner_id, route_id, "Client A").await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+
+        route.visibility = RouteVisibility::Unlisted;
+        let mut links = MockShareLinkRepository::new();
+        links.expect_find_by_route_id().returning(|_| Ok(vec![]));
+        links.expect_create().withf(|l| l.label == "Client A").times(1).returning(|_| Ok(()));
+        let usecase = ShareLinksUseCase::new(links, route_repository(route), signer());
+
+        assert!(matches!(
+            usecase.create_link(Uuid::new_v4(), route_id, "Client A").await,
+            Err(UsecaseError::NotFound(_))
+        ));
+        let link = usecase.create_link(owner_id, route_id, "  Client\nA ").await.unwrap();
+        assert_eq!(link.label, "Client A");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_ignores_revoked_links_and_bad_signatures() {
+        let route_id = Uuid::new_v4();
+        let share_token = Uuid::new_v4();
+        let active = ShareLink::new(route_id, "Client A".to_string());
+        let mut revoked = ShareLink::new(route_id, "Client B".to_string());
+        revoked.revoked_at = Some(Utc::now());
+        let stored = [active.clone(), revoked.clone()];
+        let mut links = MockShareLinkRepository::new();
+        links
+            .expect_find_by_id()
+            .times(2)
+            .returning(move |id| Ok(stored.iter().find(|l| l.id == id).cloned()));
+        let mut routes = MockRouteRepository::new();
+        routes
+            .expect_find_share_token()
+            .withf(move |id, public_only| *id == route_id && !public_only)
+            .times(1)
+            .returning(move |_, _| Ok(Some(share_token)));
+        let usecase = ShareLinksUseCase::new(links, routes, signer());
+
+        assert_eq!(usecase.resolve(&usecase.token(&active)).await.unwrap(), share_token);
+        assert!(matches!(usecase.resolve(&usecase.token(&revoked)).await, Err(UsecaseError::NotFound(_))));
+        let forged = ShareTokenSigner::new(b"guess").sign(SignedToken::Link(active.id));
+        assert!(matches!(usecase.resolve(&forged).await, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_route_and_public_tokens() {
+        let (route_id, share_token) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut routes = MockRouteRepository::new();
+        routes
+            .expect_find_share_token()
+            .withf(move |id, public_only| *id == route_id && *public_only)
+            .times(1)
+            .returning(move |_, _| Ok(Some(share_token)));
+        let usecase = ShareLinksUseCase::new(MockShareLinkRepository::new(), routes, signer());
+
+        assert_eq!(usecase.resolve(&usecase.route_token(share_token)).await.unwrap(), share_token);
+        assert_eq!(usecase.resolve(&usecase.public_token(route_id)).await.unwrap(), share_token);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_takes_bare_share_tokens_only_during_migration_window() {
+        let share_token = Uuid::new_v4();
+        let bare = share_token.to_string();
+        let usecase = |until| {
+            let usecase = ShareLinksUseCase::new(MockShareLinkRepository::new(), MockRouteRepository::new(), signer());
+            match until {
+                Some(until) => usecase.with_legacy_tokens_until(until),
+                None => usecase,
+            }
+        };
+
+        assert!(matches!(usecase(None).resolve(&bare).await, Err(UsecaseError::NotFound(_))));
+        let expired = Utc::now() - chrono::Duration::days(1);
+        assert!(matches!(usecase(Some(expired)).resolve(&bare).await, Err(UsecaseError::NotFound(_))));
+        let open = Utc::now() + chrono::Duration::days(1);
+        assert_eq!(usecase(Some(open)).resolve(&bare).await.unwrap(), share_token);
+    }
+}
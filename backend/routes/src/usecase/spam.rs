@@ -0,0 +1,240 @@
+//! Spam heuristics for new comments and routes. Suspicious content is still
+//! published; it gets a flag that puts it in the moderators' queue.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::comment::Comment;
+use crate::domain::route::Route;
+use crate::domain::spam::{
+    SpamFlag, SPAM_CONTENT_COMMENT, SPAM_CONTENT_ROUTE, SPAM_REASON_DUPLICATE_TEXT,
+    SPAM_REASON_SUBMITTED_TOO_FAST, SPAM_REASON_TOO_MANY_LINKS,
+};
+use crate::usecase::contracts::{CommentRepository, SpamFlagRepository};
+use crate::usecase::error::UsecaseError;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SpamSettings {
+    /// Links allowed in one comment or route before it is flagged.
+    pub max_links: usize,
+    /// Forms submitted sooner than this after opening are flagged. Clients
+    /// that do not report the time are not checked.
+    pub min_form_time: Duration,
+    /// How far back an identical comment counts as a duplicate.
+    pub duplicate_window: Duration,
+}
+
+impl Default for SpamSettings {
+    fn default() -> Self {
+        Self {
+            max_links: 2,
+            min_form_time: Duration::from_secs(3),
+            duplicate_window: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Counts words that look like links: `http://`, `https://` or `www.` prefixes.
+pub fn count_links(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|word| word.trim_start_matches(|c: char| !c.is_alphanumeric()).to_ascii_lowercase())
+        .filter(|word| word.starts_with("http://") || word.starts_with("https://") || word.starts_with("www."))
+        .count()
+}
+
+pub struct SpamUseCase<F, C>
+where
+    F: SpamFlagRepository,
+    C: CommentRepository,
+{
+    flag_repository: F,
+    comment_repository: C,
+    settings: SpamSettings,
+}
+
+impl<F, C> SpamUseCase<F, C>
+where
+    F: SpamFlagRepository,
+    C: CommentRepository,
+{
+    pub fn new(flag_repository: F, comment_repository: C) -> Self {
+        Self {
+            flag_repository,
+            comment_repository,
+            settings: SpamSettings::default(),
+        }
+    }
+
+    pub fn with_settings(mut self, settings: SpamSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Reasons shared by every kind of content.
+    fn common_reasons<'a>(&self, texts: impl Iterator<Item = &'a str>, form_elapsed_ms: Option<u64>) -> Vec<&'static str> {
+        let mut reasons = Vec::new();
+        if texts.map(count_links).sum::<usize>() > self.settings.max_links {
+            reasons.push(SPAM_REASON_TOO_MANY_LINKS);
+        }
+        if form_elapsed_ms.is_some_and(|ms| Duration::from_millis(ms) < self.settings.min_form_time) {
+            reasons.push(SPAM_REASON_SUBMITTED_TOO_FAST);
+        }
+        reasons
+    }
+
+    /// Puts content in the moderation queue; does nothing without reasons.
+    pub async fn flag(
+        &self,
+        content_type: &str,
+        content_id: Uuid,
+        user_id: Uuid,
+        reasons: &[&str],
+    ) -> Result<Option<SpamFlag>, UsecaseError> {
+        if reasons.is_empty() {
+            return Ok(None);
+        }
+
+        let flag = SpamFlag::new(content_type, content_id, user_id, reasons);
+        self.flag_repository.create(&flag).await?;
+
+        tracing::warn!(flag_id = %flag.id, %content_type, content_id = %content_id, reasons = %flag.reasons, "content flagged for moderation");
+        Ok(Some(flag))
+    }
+
+    /// Flags a just created comment that looks like spam.
+    #[tracing::instrument(skip(self, comment), fields(comment_id = %comment.id, ?form_elapsed_ms))]
+    pub async fn screen_comment(
+        &self,
+        comment: &Comment,
+        form_elapsed_ms: Option<u64>,
+    ) -> Result<Option<SpamFlag>, UsecaseError> {
+        let mut reasons = self.common_reasons(std::iter::once(comment.text.as_str()), form_elapsed_ms);
+
+        let since = Utc::now() - self.settings.duplicate_window;
+        let duplicates = self
+            .comment_repository
+            .count_recent_by_text(&comment.text, since, comment.id)
+            .await?;
+        if duplicates > 0 {
+            reasons.push(SPAM_REASON_DUPLICATE_TEXT);
+        }
+
+        self.flag(SPAM_CONTENT_COMMENT, comment.id, comment.user_id, &reasons).await
+    }
+
+    /// Flags a just created route that looks like spam, judging by its name,
+    /// description and point names.
+    #[tracing::instrument(skip(self, route), fields(route_id = %route.id, ?form_elapsed_ms))]
+    pub async fn screen_route(&self, route: &Route, form_elapsed_ms: Option<u64>) -> Result<Option<SpamFlag>, UsecaseError> {
+        let texts = std::iter::once(route.name.as_str())
+            .chain(route.description.as_deref())
+            .chain(route.points.iter().filter_map(|p| p.name.as_deref()));
+        let reasons = self.common_reasons(texts, form_elapsed_ms);
+
+        self.flag(SPAM_CONTENT_ROUTE, route.id, route.user_id, &reasons).await
+    }
+
+    #[tracing::instrument(skip(self), fields(%reviewed, %limit, %offset))]
+    pub async fn list_flags(
+        &self,
+        reviewed: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<SpamFlag>, i64), UsecaseError> {
+        let flags = self.flag_repository.list(reviewed, limit, offset).await?;
+        let total = self.flag_repository.count(reviewed).await?;
+
+        tracing::debug!(count = flags.len(), total, "retrieved spam flags");
+        Ok((flags, total))
+    }
+
+    /// Takes the flag out of the queue. Removing the content itself goes
+    /// through the regular admin endpoints.
+    #[tracing::instrument(skip(self), fields(flag_id = %id, reviewer_id = %reviewer_id))]
+    pub async fn review_flag(&self, id: Uuid, reviewer_id: Uuid) -> Result<(), UsecaseError> {
+        self.flag_repository.mark_reviewed(id, reviewer_id).await?;
+
+        tracing::info!("spam flag reviewed");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::route::RoutePoint;
+    use crate::usecase::contracts::{MockCommentRepository, MockSpamFlagRepository};
+
+    fn comment(text: &str) -> Comment {
+        Comment::new(Uuid::new_v4(), Uuid::new_v4(), "Ann".to_string(), text.to_string())
+    }
+
+    #[test]
+    fn test_count_links() {
+        assert_eq!(count_links("see https://a.example and (www.b.example) or HTTP://c.example"), 3);
+        assert_eq!(count_links("the path goes past the www-bridge"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_screen_comment_passes_ordinary_comment() {
+        let mut flags = MockSpamFlagRepository::new();
+        flags.expect_create().never();
+        let mut comments = MockCommentRepository::new();
+        comments.expect_count_recent_by_text().returning(|_, _, _| Ok(0));
+        let usecase = SpamUseCase::new(flags, comments);
+
+        let result = usecase.screen_comment(&comment("Lovely trail, see https://a.example"), Some(20_000)).await;
+
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_screen_comment_flags_links_duplicates_and_fast_forms() {
+        let mut flags = MockSpamFlagRepository::new();
+        flags.expect_create().times(1).returning(|_| Ok(()));
+        let mut comments = MockCommentRepository::new();
+        comments.expect_count_recent_by_text().returning(|_, _, _| Ok(3));
+        let usecase = SpamUseCase::new(flags, comments);
+
+        let flag = usecase
+            .screen_comment(&comment("https://a.example https://b.example https://c.example"), Some(500))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(flag.content_type, SPAM_CONTENT_COMMENT);
+        assert_eq!(flag.reason_list(), vec!["too_many_links", "submitted_too_fast", "duplicate_text"]);
+    }
+
+    #[tokio::test]
+    async fn test_screen_route_counts_links_across_names() {
+        let mut flags = MockSpamFlagRepository::new();
+        flags.expect_create().times(1).returning(|_| Ok(()));
+        let usecase = SpamUseCase::new(flags, MockCommentRepository::new()).with_settings(SpamSettings {
+            max_links: 1,
+            ..SpamSettings::default()
+        });
+        let point = |name: &str| RoutePoint {
+            lat: 55.75,
+            lng: 37.61,
+            name: Some(name.to_string()),
+            segment_mode: None,
+            photo: None,
+            address: None,
+        };
+        let route = Route::new(
+            Uuid::new_v4(),
+            "Cheap tours www.a.example".to_string(),
+            vec![point("Start"), point("Book at https://b.example")],
+            vec![],
+            vec![],
+        );
+
+        let flag = usecase.screen_route(&route, None).await.unwrap().unwrap();
+
+        assert_eq!(flag.content_type, SPAM_CONTENT_ROUTE);
+        assert_eq!(flag.reason_list(), vec!["too_many_links"]);
+    }
+}
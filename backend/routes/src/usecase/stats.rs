@@ -0,0 +1,247 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use chrono::{Days, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::domain::stats::{ChatOpsStats, DailyCount, UsageStats};
+use crate::usecase::contracts::StatsRepository;
+use crate::usecase::error::UsecaseError;
+
+pub const MAX_STATS_DAYS: i64 = 365;
+
+/// Users listed by message volume on the chat dashboard.
+const TOP_CHAT_USERS: i64 = 10;
+
+fn validate_days(days: i64) -> Result<(), UsecaseError> {
+    if !(1..=MAX_STATS_DAYS).contains(&days) {
+        return Err(UsecaseError::Validation(format!(
+            "days must be between 1 and {}",
+            MAX_STATS_DAYS
+        )));
+    }
+    Ok(())
+}
+
+pub struct StatsUseCase<S>
+where
+    S: StatsRepository,
+{
+    stats_repository: S,
+    /// Users already recorded for the day, so only their first request of
+    /// the day writes a usage event.
+    recorded: Mutex<(NaiveDate, HashSet<Uuid>)>,
+}
+
+impl<S> StatsUseCase<S>
+where
+    S: StatsRepository,
+{
+    pub fn new(stats_repository: S) -> Self {
+        Self {
+            stats_repository,
+            recorded: Mutex::new((NaiveDate::MIN, HashSet::new())),
+        }
+    }
+
+    /// Marks the user active today. Failures are logged and retried on the
+    /// user's next request; they never fail the request itself.
+    pub async fn record_activity(&self, user_id: Uuid) {
+        let today = Utc::now().date_naive();
+        {
+            let mut recorded = self.recorded.lock().unwrap();
+            if recorded.0 != today {
+                *recorded = (today, HashSet::new());
+            }
+            if !recorded.1.insert(user_id) {
+                return;
+            }
+        }
+
+        if let Err(e) = self.stats_repository.record_usage(user_id, today).await {
+            tracing::warn!(%user_id, error = %e, "failed to record usage event");
+            self.recorded.lock().unwrap().1.remove(&user_id);
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_usage_stats(&self, days: i64) -> Result<UsageStats, UsecaseError> {
+        validate_days(days)?;
+
+        let today = Utc::now().date_naive();
+        let since = today - Days::new(days as u64 - 1);
+        let week_start = today - Days::new(6);
+
+        let daily_active_users = self.stats_repository.count_active_users(today).await?;
+        let weekly_active_users = self.stats_repository.count_active_users(week_start).await?;
+        let active_users_per_day = self.stats_repository.active_users_per_day(since).await?;
+        let new_routes_per_day = self.stats_repository.routes_per_day(since).await?;
+        let chat = self.stats_repository.chat_usage(since).await?;
+        let photos = self.stats_repository.photo_volume().await?;
+
+        tracing::debug!(daily_active_users, weekly_active_users, "usage stats computed");
+        Ok(UsageStats {
+            days,
+            daily_active_users,
+            weekly_active_users,
+            active_users_per_day: fill_days(since, today, &active_users_per_day),
+            new_routes_per_day: fill_days(since, today, &new_routes_per_day),
+            chat,
+            photos,
+        })
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_chat_stats(&self, days: i64) -> Result<ChatOpsStats, UsecaseError> {
+        validate_days(days)?;
+
+        let today = Utc::now().date_naive();
+        let since = today - Days::new(days as u64 - 1);
+
+        let messages_per_day = self.stats_repository.chat_messages_per_day(since).await?;
+        let summary = self.stats_repository.chat_turn_summary(since).await?;
+        let tool_calls = self.stats_repository.chat_tool_calls(since).await?;
+        let top_users = self.stats_repository.top_chat_users(since, TOP_CHAT_USERS).await?;
+        let error_rate = if summary.turns > 0 {
+            summary.errors as f64 / summary.turns as f64
+        } else {
+            0.0
+        };
+
+        tracing::debug!(turns = summary.turns, errors = summary.errors, "chat stats computed");
+        Ok(ChatOpsStats {
+            days,
+            messages_per_day: fill_days(since, today, &messages_per_day),
+            turns: summary.turns,
+            errors: summary.errors,
+            error_rate,
+            avg_latency_ms: summary.avg_latency_ms,
+            tool_calls,
+            top_users,
+        })
+    }
+}
+
+/// One entry per day from `since` to `until`, zero where `counts` has none.
+fn fill_days(since: NaiveDate, until: NaiveDate, counts: &[DailyCount]) -> Vec<DailyCount> {
+    let by_day: HashMap<NaiveDate, i64> = counts.iter().map(|c| (c.day, c.count)).collect();
+    since
+        .iter_days()
+        .take_while(|day| *day <= until)
+        .map(|day| DailyCount { day, count: by_day.get(&day).copied().unwrap_or(0) })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::stats::{ChatTurnSummary, ChatUsage, PhotoVolume, ToolCallCount};
+    use crate::repository::errors::RepositoryError;
+    use crate::usecase::contracts::MockStatsRepository;
+
+    #[tokio::test]
+    async fn test_record_activity_writes_once_per_day() {
+        let mut mock_repo = MockStatsRepository::new();
+        let user_id = Uuid::new_v4();
+
+        mock_repo
+            .expect_record_usage()
+            .withf(move |id, day| *id == user_id && *day == Utc::now().date_naive())
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let usecase = StatsUseCase::new(mock_repo);
+        usecase.record_activity(user_id).await;
+        usecase.record_activity(user_id).await;
+    }
+
+    #[tokio::test]
+    async fn test_record_activity_retries_after_failure() {
+        let mut mock_repo = MockStatsRepository::new();
+        let mut seq = mockall::Sequence::new();
+
+        mock_repo
+            .expect_record_usage()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err(RepositoryError::DatabaseError("down".to_string())));
+        mock_repo
+            .expect_record_usage()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(()));
+
+        let usecase = StatsUseCase::new(mock_repo);
+        let user_id = Uuid::new_v4();
+        usecase.record_activity(user_id).await;
+        usecase.record_activity(user_id).await;
+        usecase.record_activity(user_id).await;
+    }
+
+    #[tokio::test]
+    async fn test_get_usage_stats_fills_missing_days() {
+        let mut mock_repo = MockStatsRepository::new();
+        let today = Utc::now().date_naive();
+
+        mock_repo.expect_count_active_users().returning(|_| Ok(3));
+        mock_repo
+            .expect_active_users_per_day()
+            .returning(move |_| Ok(vec![DailyCount { day: today, count: 3 }]));
+        mock_repo
+            .expect_routes_per_day()
+            .returning(move |since| Ok(vec![DailyCount { day: since, count: 2 }]));
+        mock_repo
+            .expect_chat_usage()
+            .returning(|_| Ok(ChatUsage { messages: 10, users: 2, conversations: 4 }));
+        mock_repo.expect_photo_volume().returning(|| Ok(PhotoVolume::default()));
+
+        let usecase = StatsUseCase::new(mock_repo);
+        let stats = usecase.get_usage_stats(3).await.unwrap();
+
+        let counts = |series: &[DailyCount]| series.iter().map(|c| c.count).collect::<Vec<_>>();
+        assert_eq!(stats.active_users_per_day.len(), 3);
+        assert_eq!(stats.active_users_per_day[2].day, today);
+        assert_eq!(counts(&stats.active_users_per_day), vec![0, 0, 3]);
+        assert_eq!(counts(&stats.new_routes_per_day), vec![2, 0, 0]);
+        assert_eq!(stats.chat.messages, 10);
+    }
+
+    #[tokio::test]
+    async fn test_get_chat_stats_computes_error_rate() {
+        let mut mock_repo = MockStatsRepository::new();
+        let today = Utc::now().date_naive();
+
+        mock_repo
+            .expect_chat_messages_per_day()
+            .returning(move |_| Ok(vec![DailyCount { day: today, count: 5 }]));
+        mock_repo
+            .expect_chat_turn_summary()
+            .returning(|_| Ok(ChatTurnSummary { turns: 8, errors: 2, avg_latency_ms: 1250.0 }));
+        mock_repo
+            .expect_chat_tool_calls()
+            .returning(|_| Ok(vec![ToolCallCount { tool: "geocode".to_string(), count: 3 }]));
+        mock_repo
+            .expect_top_chat_users()
+            .withf(|_, limit| *limit == TOP_CHAT_USERS)
+            .returning(|_, _| Ok(vec![]));
+
+        let usecase = StatsUseCase::new(mock_repo);
+        let stats = usecase.get_chat_stats(7).await.unwrap();
+
+        assert_eq!(stats.messages_per_day.len(), 7);
+        assert_eq!(stats.messages_per_day[6].count, 5);
+        assert_eq!(stats.error_rate, 0.25);
+        assert_eq!(stats.tool_calls[0].tool, "geocode");
+    }
+
+    #[tokio::test]
+    async fn test_get_usage_stats_rejects_invalid_days() {
+        let usecase = StatsUseCase::new(MockStatsRepository::new());
+
+        assert!(matches!(usecase.get_usage_stats(0).await, Err(UsecaseError::Validation(_))));
+        assert!(matches!(
+            usecase.get_usage_stats(MAX_STATS_DAYS + 1).await,
+            Err(UsecaseError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,616 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Cursor, Write};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use guide_helper_domain::user_data::USER_KEYED_TABLES;
+use serde::Serialize;
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+
+use crate::domain::chat_message::ChatMessage;
+use crate::domain::comment::Comment;
+use crate::domain::notification::Notification;
+use crate::domain::rating::RouteRating;
+use crate::domain::route::Route;
+use crate::usecase::contracts::{
+    ChatMessageRepository, CommentRepository, NotificationRepository, ObjectStorage,
+    RatingRepository, RouteRepository, UserDataRepository,
+};
+use crate::usecase::error::UsecaseError;
+use crate::usecase::metrics;
+use crate::usecase::route_export::route_to_geojson;
+
+const TAKEOUT_FORMAT_VERSION: u32 = 2;
+const NOTIFICATION_PAGE_SIZE: i64 = 500;
+pub const EXPORT_READY_NOTIFICATION: &str = "data_export_ready";
+pub const EXPORT_FAILED_NOTIFICATION: &str = "data_export_failed";
+/// Longest validity S3 accepts for a presigned link.
+pub const MAX_LINK_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+#[derive(Debug, Serialize)]
+pub struct TakeoutManifest {
+    pub format_version: u32,
+    pub user_id: Uuid,
+    pub exported_at: DateTime<Utc>,
+    pub routes: usize,
+    pub comments: usize,
+    pub ratings: usize,
+    pub chat_messages: usize,
+    pub notifications: usize,
+    /// Row counts of the `account/{table}.json` files.
+    pub account: BTreeMap<&'static str, usize>,
+}
+
+/// Everything stored about a single user, as written into the takeout archive.
+#[derive(Debug, Default)]
+pub struct UserData {
+    pub routes: Vec<Route>,
+    pub comments: Vec<Comment>,
+    pub ratings: Vec<RouteRating>,
+    pub chat_messages: Vec<ChatMessage>,
+    pub notifications: Vec<Notification>,
+    /// Raw rows of the exported user-keyed tables, by table name.
+    pub account: BTreeMap<&'static str, Vec<serde_json::Value>>,
+}
+
+pub struct TakeoutUseCase<R, C, Ra, M, N, U, S>
+where
+    R: RouteRepository,
+    C: CommentRepository,
+    Ra: RatingRepository,
+    M: ChatMessageRepository,
+    N: NotificationRepository,
+    U: UserDataRepository,
+    S: ObjectStorage,
+{
+    route_repository: R,
+    comment_repository: C,
+    rating_repository: Ra,
+    chat_message_repository: M,
+    notification_repository: N,
+    user_data_repository: U,
+    storage: S,
+    link_ttl: Duration,
+    in_progress: Mutex<HashSet<Uuid>>,
+}
+
+impl<R, C, Ra, M, N, U, S> TakeoutUseCase<R, C, Ra, M, N, U, S>
+where
+    R: RouteRepository,
+    C: CommentRepository,
+    Ra: RatingRepository,
+    M: ChatMessageRepository,
+    N: NotificationRepository,
+    U: UserDataRepository,
+    S: ObjectStorage,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        route_repository: R,
+        comment_repository: C,
+        rating_repository: Ra,
+        chat_message_repository: M,
+        notification_repository: N,
+        user_data_repository: U,
+        storage: S,
+        link_ttl: Duration,
+    ) -> Self {
+        if link_ttl > MAX_LINK_TTL {
+            tracing::warn!(
+                link_ttl_secs = link_ttl.as_secs(),
+                max_secs = MAX_LINK_TTL.as_secs(),
+                "data export link lifetime exceeds what S3 can presign, clamping"
+            );
+        }
+        Self {
+            route_repository,
+            comment_repository,
+            rating_repository,
+            chat_message_repository,
+            notification_repository,
+            user_data_repository,
+            storage,
+            link_ttl: link_ttl.min(MAX_LINK_TTL),
+            in_progress: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Marks an export as started for the user. Only one export per user may
+    /// run at a time; the caller must follow up with `run_export`.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub fn begin_export(&self, user_id: Uuid) -> Result<(), UsecaseError> {
+        let mut in_progress = self.in_progress.lock().unwrap();
+        if !in_progress.insert(user_id) {
+            tracing::warn!(user_id = %user_id, "data export already in progress");
+            return Err(UsecaseError::RateLimited(
+                "A data export is already being prepared".to_string(),
+            ));
+        }
+        tracing::info!(user_id = %user_id, "data export started");
+        Ok(())
+    }
+
+    /// Builds and uploads the archive, then notifies the user with the
+    /// download link (or with a failure notice). Meant to run in the background.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn run_export(&self, user_id: Uuid) {
+        let (notification_type, params) = match self.export_user_data(user_id).await {
+            Ok(url) => {
+                tracing::info!(user_id = %user_id, "data export ready");
+                (
+                    EXPORT_READY_NOTIFICATION,
+                    serde_json::json!({ "hours": self.link_ttl.as_secs().div_ceil(3600), "url": url }),
+                )
+            }
+            Err(e) => {
+                tracing::error!(user_id = %user_id, error = %e, "data export failed");
+                (EXPORT_FAILED_NOTIFICATION, serde_json::json!({}))
+            }
+        };
+
+        let notification = Notification::from_template(
+            user_id,
+            notification_type.to_string(),
+            Uuid::nil(),
+            "system".to_string(),
+            &format!("notification.{}", notification_type),
+            params,
+        );
+        match self.notification_repository.create(&notification).await {
+            Ok(()) => metrics::notification_created(notification_type),
+            Err(e) => tracing::warn!(user_id = %user_id, error = %e, "failed to create data export notification"),
+        }
+
+        self.in_progress.lock().unwrap().remove(&user_id);
+    }
+
+    /// Collects the user's data, stores the zip and returns a presigned download
+    /// URL. Earlier archives of the user are deleted, so only the latest is kept.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn export_user_data(&self, user_id: Uuid) -> Result<String, UsecaseError> {
+        tracing::debug!("collecting user data");
+
+        let data = self.collect_user_data(user_id).await?;
+        let bytes = tokio::task::spawn_blocking(move || build_archive(user_id, &data))
+            .await
+            .map_err(|e| UsecaseError::Internal(format!("takeout task failed: {}", e)))?
+            .map_err(|e| UsecaseError::Internal(format!("failed to build takeout archive: {}", e)))?;
+
+        let key = format!("{}/takeout-{}.zip", user_id, Utc::now().format("%Y%m%dT%H%M%SZ"));
+        let size = bytes.len();
+        self.storage.put_object(&key, bytes, "application/zip").await?;
+        let url = self.storage.presigned_get_url(&key, self.link_ttl).await?;
+
+        tracing::info!(user_id = %user_id, %key, size, "takeout archive uploaded");
+
+        self.remove_previous_archives(user_id, &key).await;
+        Ok(url)
+    }
+
+    /// Best effort: archives left behind expire through the bucket's
+    /// lifecycle rule anyway.
+    async fn remove_previous_archives(&self, user_id: Uuid, latest: &str) {
+        let keys = match self.storage.list_objects(&format!("{}/", user_id)).await {
+            Ok(keys) => keys,
+            Err(e) => {
+                tracing::warn!(user_id = %user_id, error = %e, "failed to list previous takeout archives");
+                return;
+            }
+        };
+        for key in keys.into_iter().filter(|key| key != latest) {
+            match self.storage.delete_object(&key).await {
+                Ok(()) => tracing::info!(user_id = %user_id, %key, "previous takeout archive deleted"),
+                Err(e) => tracing::warn!(user_id = %user_id, %key, error = %e, "failed to delete previous takeout archive"),
+            }
+        }
+    }
+
+    async fn collect_user_data(&self, user_id: Uuid) -> Result<UserData, UsecaseError> {
+        let routes = self.route_repository.find_by_user_id(user_id).await?;
+        let comments = self.comment_repository.find_by_user_id(user_id).await?;
+        let ratings = self.rating_repository.find_by_user_id(user_id).await?;
+        let chat_messages = self.chat_message_repository.find_all_by_user(user_id).await?;
+
+        let mut notifications = Vec::new();
+        loop {
+            let page = self
+                .notification_repository
+                .find_by_user_id(user_id, NOTIFICATION_PAGE_SIZE, notifications.len() as i64)
+                .await?;
+            let done = (page.len() as i64) < NOTIFICATION_PAGE_SIZE;
+            notifications.extend(page);
+            if done {
+                break;
+            }
+        }
+
+        let mut account: BTreeMap<&'static str, Vec<serde_json::Value>> = BTreeMap::new();
+        for table in USER_KEYED_TABLES.iter().filter(|t| t.exported) {
+            let rows = self.user_data_repository.find_user_rows(*table, user_id).await?;
+            account.entry(table.table).or_default().extend(rows);
+        }
+
+        tracing::debug!(
+            routes = routes.len(),
+            comments = comments.len(),
+            ratings = ratings.len(),
+            chat_messages = chat_messages.len(),
+            notifications = notifications.len(),
+            account_rows = account.values().map(Vec::len).sum::<usize>(),
+            "user data collected"
+        );
+
+        Ok(UserData {
+            routes,
+            comments,
+            ratings,
+            chat_messages,
+            notifications,
+            account,
+        })
+    }
+}
+
+fn build_archive(user_id: Uuid, data: &UserData) -> Result<Vec<u8>, zip::result::ZipError> {
+    let manifest = TakeoutManifest {
+        format_version: TAKEOUT_FORMAT_VERSION,
+        user_id,
+        exported_at: Utc::now(),
+        routes: data.routes.len(),
+        comments: data.comments.len(),
+        ratings: data.ratings.len(),
+        chat_messages: data.chat_messages.len(),
+        notifications: data.notifications.len(),
+        account: data.account.iter().map(|(table, rows)| (*table, rows.len())).collect(),
+    };
+
+    let mut entries: Vec<(String, Vec<u8>)> = vec![
+        ("manifest.json".to_string(), to_json(&manifest)?),
+        ("routes.json".to_string(), to_json(&data.routes)?),
+        ("comments.json".to_string(), to_json(&data.comments)?),
+        ("ratings.json".to_string(), to_json(&data.ratings)?),
+        ("chat_messages.json".to_string(), to_json(&data.chat_messages)?),
+        ("notifications.json".to_string(), to_json(&data.notifications)?),
+    ];
+    for (table, rows) in &data.account {
+        entries.push((format!("account/{}.json", table), to_json(rows)?));
+    }
+    for route in &data.routes {
+        entries.push((
+            format!("routes/{}.geojson", route.id),
+            route_to_geojson(route, &[]).to_string().into_bytes(),
+        ));
+    }
+
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (path, bytes) in entries {
+        writer.start_file(path, options)?;
+        writer.write_all(&bytes)?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, zip::result::ZipError> {
+    serde_json::to_vec_pretty(value).map_err(|e| zip::result::ZipError::Io(e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::sync::Arc;
+
+    use crate::domain::route::RouteVisibility;
+    use crate::usecase::contracts::{
+        MockChatMessageRepository, MockCommentRepository, MockNotificationRepository,
+        MockObjectStorage, MockRatingRepository, MockRouteRepository, MockUserDataRepository,
+    };
+
+    type Upload = Option<(String, Vec<u8>)>;
+
+    type TestUseCase = TakeoutUseCase<
+        MockRouteRepository,
+        MockCommentRepository,
+        MockRatingRepository,
+        MockChatMessageRepository,
+        MockNotificationRepository,
+        MockUserDataRepository,
+        MockObjectStorage,
+    >;
+
+    fn test_route(user_id: Uuid) -> Route {
+        Route {
+            id: Uuid::new_v4(),
+            user_id,
+            name: "Test Route".to_string(),
+            points: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            share_token: None,
+            visibility: RouteVisibility::Private,
+            category_ids: vec![],
+            start_location: None,
+            end_location: None,
+            seasons: vec![],
+            description: None,
+            archived_at: None,
+        }
+    }
+
+    fn data_mocks(
+        user_id: Uuid,
+    ) -> (
+        MockRouteRepository,
+        MockCommentRepository,
+        MockRatingRepository,
+        MockChatMessageRepository,
+        MockNotificationRepository,
+        MockUserDataRepository,
+    ) {
+        let route = test_route(user_id);
+        let route_id = route.id;
+
+        let mut route_repo = MockRouteRepository::new();
+        route_repo
+            .expect_find_by_user_id()
+            .returning(move |_| Ok(vec![route.clone()]));
+
+        let mut comment_repo = MockCommentRepository::new();
+        comment_repo.expect_find_by_user_id().returning(move |uid| {
+            Ok(vec![Comment::new(route_id, uid, "me".to_string(), "Nice".to_string())])
+        });
+
+        let mut rating_repo = MockRatingRepository::new();
+        rating_repo
+            .expect_find_by_user_id()
+            .returning(move |uid| Ok(vec![RouteRating::new(route_id, uid, 5)]));
+
+        let mut chat_repo = MockChatMessageRepository::new();
+        chat_repo.expect_find_all_by_user().returning(|_| Ok(vec![]));
+
+        let mut notification_repo = MockNotificationRepository::new();
+        notification_repo
+            .expect_find_by_user_id()
+            .returning(|_, _, _| Ok(vec![]));
+
+        let mut user_data_repo = MockUserDataRepository::new();
+        user_data_repo.expect_find_user_rows().returning(|table, uid| {
+            Ok(match table.table {
+                "saved_searches" => vec![serde_json::json!({ "user_id": uid, "name": "Lakes" })],
+                _ => vec![],
+            })
+        });
+
+        (route_repo, comment_repo, rating_repo, chat_repo, notification_repo, user_data_repo)
+    }
+
+    #[tokio::test]
+    async fn test_export_user_data_uploads_archive() {
+        let user_id = Uuid::new_v4();
+        let (route_repo, comment_repo, rating_repo, chat_repo, notification_repo, user_data_repo) = data_mocks(user_id);
+
+        let uploaded: Arc<Mutex<Upload>> = Arc::new(Mutex::new(None));
+        let uploaded_clone = uploaded.clone();
+        let mut storage = MockObjectStorage::new();
+        storage
+            .expect_put_object()
+            .withf(|_, _, content_type| content_type == "application/zip")
+            .times(1)
+            .returning(move |key, body, _| {
+                *uploaded_clone.lock().unwrap() = Some((key.to_string(), body));
+                Ok(())
+            });
+        storage
+            .expect_presigned_get_url()
+            .withf(|_, ttl| *ttl == Duration::from_secs(3600))
+            .returning(|key, _| Ok(format!("https://files.example.com/{}", key)));
+        storage.expect_list_objects().returning(|_| Ok(vec![]));
+
+        let usecase: TestUseCase = TakeoutUseCase::new(
+            route_repo,
+            comment_repo,
+            rating_repo,
+            chat_repo,
+            notification_repo,
+            user_data_repo,
+            storage,
+            Duration::from_secs(3600),
+        );
+
+        let url = usecase.export_user_data(user_id).await.unwrap();
+
+        let (key, body) = uploaded.lock().unwrap().take().unwrap();
+        assert!(key.starts_with(&user_id.to_string()));
+        assert!(url.ends_with(&key));
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(body)).unwrap();
+        let names: Vec<String> = archive.file_names().map(String::from).collect();
+        for expected in [
+            "manifest.json",
+            "routes.json",
+            "comments.json",
+            "ratings.json",
+            "chat_messages.json",
+            "notifications.json",
+            "account/saved_searches.json",
+            "account/user_blocks.json",
+        ] {
+            assert!(names.contains(&expected.to_string()), "missing {}", expected);
+        }
+        assert!(names.iter().any(|n| n.starts_with("routes/") && n.ends_with(".geojson")));
+
+        let mut manifest = String::new();
+        archive.by_name("manifest.json").unwrap().read_to_string(&mut manifest).unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+        assert_eq!(manifest["routes"], 1);
+        assert_eq!(manifest["comments"], 1);
+        assert_eq!(manifest["ratings"], 1);
+        assert_eq!(manifest["account"]["saved_searches"], 1);
+        assert_eq!(manifest["account"]["user_blocks"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_collect_exports_only_the_users_own_keyed_rows() {
+        let user_id = Uuid::new_v4();
+        let (route_repo, comment_repo, rating_repo, chat_repo, notification_repo, _) = data_mocks(user_id);
+
+        let mut user_data_repo = MockUserDataRepository::new();
+        user_data_repo
+            .expect_find_user_rows()
+            .withf(move |table, uid| *uid == user_id && table.exported)
+            .times(USER_KEYED_TABLES.iter().filter(|t| t.exported).count())
+            .returning(|_, _| Ok(vec![]));
+
+        let usecase: TestUseCase = TakeoutUseCase::new(
+            route_repo,
+            comment_repo,
+            rating_repo,
+            chat_repo,
+            notification_repo,
+            user_data_repo,
+            MockObjectStorage::new(),
+            Duration::from_secs(3600),
+        );
+
+        let data = usecase.collect_user_data(user_id).await.unwrap();
+        assert!(data.account.contains_key("user_blocks"));
+        assert!(data.account.contains_key("chat_conversations"));
+    }
+
+    #[tokio::test]
+    async fn test_export_user_data_deletes_previous_archives() {
+        let user_id = Uuid::new_v4();
+        let (route_repo, comment_repo, rating_repo, chat_repo, notification_repo, user_data_repo) = data_mocks(user_id);
+        let previous = format!("{}/takeout-20260101T000000Z.zip", user_id);
+
+        let uploaded: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+        let mut storage = MockObjectStorage::new();
+        let uploaded_clone = uploaded.clone();
+        storage.expect_put_object().returning(move |key, _, _| {
+            *uploaded_clone.lock().unwrap() = Some(key.to_string());
+            Ok(())
+        });
+        storage
+            .expect_presigned_get_url()
+            .returning(|key, _| Ok(format!("https://files.example.com/{}", key)));
+        let (uploaded_clone, listed) = (uploaded.clone(), previous.clone());
+        storage
+            .expect_list_objects()
+            .withf(move |prefix| prefix == format!("{}/", user_id))
+            .returning(move |_| Ok(vec![listed.clone(), uploaded_clone.lock().unwrap().clone().unwrap()]));
+        let deleted = previous.clone();
+        storage
+            .expect_delete_object()
+            .withf(move |key| key == deleted)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let usecase: TestUseCase = TakeoutUseCase::new(
+            route_repo,
+            comment_repo,
+            rating_repo,
+            chat_repo,
+            notification_repo,
+            user_data_repo,
+            storage,
+            Duration::from_secs(3600),
+        );
+
+        usecase.export_user_data(user_id).await.unwrap();
+    }
+
+    #[test]
+    fn test_link_ttl_is_clamped_to_presign_maximum() {
+        let user_id = Uuid::new_v4();
+        let (route_repo, comment_repo, rating_repo, chat_repo, notification_repo, user_data_repo) = data_mocks(user_id);
+
+        let usecase: TestUseCase = TakeoutUseCase::new(
+            route_repo,
+            comment_repo,
+            rating_repo,
+            chat_repo,
+            notification_repo,
+            user_data_repo,
+            MockObjectStorage::new(),
+            Duration::from_secs(30 * 24 * 3600),
+        );
+
+        assert_eq!(usecase.link_ttl, MAX_LINK_TTL);
+    }
+
+    #[tokio::test]
+    async fn test_collect_pages_through_notifications() {
+        let user_id = Uuid::new_v4();
+        let (route_repo, comment_repo, rating_repo, chat_repo, _, user_data_repo) = data_mocks(user_id);
+
+        let mut notification_repo = MockNotificationRepository::new();
+        notification_repo
+            .expect_find_by_user_id()
+            .returning(|uid, limit, offset| {
+                let total = NOTIFICATION_PAGE_SIZE + 3;
+                let count = (total - offset).clamp(0, limit);
+                Ok((0..count)
+                    .map(|_| Notification::new(uid, "like".to_string(), Uuid::new_v4(), "a".to_string(), "m".to_string()))
+                    .collect())
+            });
+
+        let usecase: TestUseCase = TakeoutUseCase::new(
+            route_repo,
+            comment_repo,
+            rating_repo,
+            chat_repo,
+            notification_repo,
+            user_data_repo,
+            MockObjectStorage::new(),
+            Duration::from_secs(3600),
+        );
+
+        let data = usecase.collect_user_data(user_id).await.unwrap();
+        assert_eq!(data.notifications.len() as i64, NOTIFICATION_PAGE_SIZE + 3);
+    }
+
+    #[tokio::test]
+    async fn test_begin_export_rejects_concurrent_request() {
+        let user_id = Uuid::new_v4();
+        let (route_repo, comment_repo, rating_repo, chat_repo, _, user_data_repo) = data_mocks(user_id);
+
+        let mut notification_repo = MockNotificationRepository::new();
+        notification_repo
+            .expect_find_by_user_id()
+            .returning(|_, _, _| Ok(vec![]));
+        notification_repo
+            .expect_create()
+            .withf(|n| n.notification_type == EXPORT_FAILED_NOTIFICATION && n.route_id.is_nil())
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut storage = MockObjectStorage::new();
+        storage
+            .expect_put_object()
+            .returning(|_, _, _| Err(crate::repository::errors::RepositoryError::DatabaseError("down".to_string())));
+
+        let usecase: TestUseCase = TakeoutUseCase::new(
+            route_repo,
+            comment_repo,
+            rating_repo,
+            chat_repo,
+            notification_repo,
+            user_data_repo,
+            storage,
+            Duration::from_secs(3600),
+        );
+
+        usecase.begin_export(user_id).unwrap();
+        assert!(matches!(
+            usecase.begin_export(user_id),
+            Err(UsecaseError::RateLimited(_))
+        ));
+
+        usecase.run_export(user_id).await;
+        assert!(usecase.begin_export(user_id).is_ok());
+    }
+}
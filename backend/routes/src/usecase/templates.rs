@@ -0,0 +1,165 @@
+//! Route templates: routes guides start new routes from. Users can flag
+//! their own routes for themselves; admins flag public ones for everybody.
+
+use uuid::Uuid;
+
+use crate::domain::route::Route;
+use crate::domain::template::{RouteTemplate, TemplateSummary};
+use crate::usecase::contracts::{RouteRepository, RouteTemplateRepository};
+use crate::usecase::error::UsecaseError;
+
+pub struct TemplatesUseCase<T, R>
+where
+    T: RouteTemplateRepository,
+    R: RouteRepository,
+{
+    template_repository: T,
+    route_repository: R,
+}
+
+impl<T, R> TemplatesUseCase<T, R>
+where
+    T: RouteTemplateRepository,
+    R: RouteRepository,
+{
+    pub fn new(template_repository: T, route_repository: R) -> Self {
+        Self {
+            template_repository,
+            route_repository,
+        }
+    }
+
+    /// Route the user may flag or unflag: their own, or any route for admins.
+    async fn find_manageable_route(&self, user_id: Uuid, is_admin: bool, route_id: Uuid) -> Result<Route, UsecaseError> {
+        let route = self
+            .route_repository
+            .find_by_id(route_id)
+            .await?
+            .filter(|r| is_admin || r.user_id == user_id)
+            .ok_or_else(|| UsecaseError::NotFound("Route".to_string()))?;
+        Ok(route)
+    }
+
+    /// Flags the route as a template. Only admins can make it public.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn set_template(
+        &self,
+        user_id: Uuid,
+        is_admin: bool,
+        route_id: Uuid,
+        public: bool,
+    ) -> Result<RouteTemplate, UsecaseError> {
+        if public && !is_admin {
+            tracing::warn!("public template requested by non-admin");
+            return Err(UsecaseError::Forbidden("Only admins can publish templates".to_string()));
+        }
+        let route = self.find_manageable_route(user_id, is_admin, route_id).await?;
+
+        let template = RouteTemplate::new(route.id, route.user_id, public);
+        self.template_repository.upsert(&template).await?;
+
+        tracing::info!(public, "route flagged as template");
+        Ok(template)
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn remove_template(&self, user_id: Uuid, is_admin: bool, route_id: Uuid) -> Result<(), UsecaseError> {
+        self.find_manageable_route(user_id, is_admin, route_id).await?;
+        self.template_repository.delete(route_id).await?;
+
+        tracing::info!("route template removed");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, %limit, %offset))]
+    pub async fn list_templates(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<TemplateSummary>, i64), UsecaseError> {
+        let templates = self.template_repository.list_visible(user_id, limit, offset).await?;
+        let total = self.template_repository.count_visible(user_id).await?;
+
+        tracing::debug!(count = templates.len(), total, "retrieved route templates");
+        Ok((templates, total))
+    }
+
+    /// The route behind a template the user can see, to copy from.
+    #[tracing::instrument(skip(self), fields(user_id = %user_id, route_id = %route_id))]
+    pub async fn template_route(&self, user_id: Uuid, route_id: Uuid) -> Result<Route, UsecaseError> {
+        self.template_repository
+            .find_by_route_id(route_id)
+            .await?
+            .filter(|t| t.public || t.owner_id == user_id)
+            .ok_or_else(|| UsecaseError::NotFound("Template".to_string()))?;
+
+        let route = self
+            .route_repository
+            .find_by_id(route_id)
+            .await?
+            .ok_or_else(|| UsecaseError::NotFound("Template".to_string()))?;
+        Ok(route)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecase::contracts::{MockRouteRepository, MockRouteTemplateRepository};
+
+    fn route_repository(route: Route) -> MockRouteRepository {
+        let mut routes = MockRouteRepository::new();
+        routes.expect_find_by_id().returning(move |_| Ok(Some(route.clone())));
+        routes
+    }
+
+    #[tokio::test]
+    async fn test_only_admins_publish_templates() {
+        let owner_id = Uuid::new_v4();
+        let route = Route::new(owner_id, "City tour".to_string(), vec![], vec![], vec![]);
+        let route_id = route.id;
+        let mut templates = MockRouteTemplateRepository::new();
+        templates
+            .expect_upsert()
+            .withf(move |t| t.public && t.owner_id == owner_id)
+            .times(1)
+            .returning(|_| Ok(()));
+        let usecase = TemplatesUseCase::new(templates, route_repository(route));
+
+        let by_owner = usecase.set_template(owner_id, false, route_id, true).await;
+        assert!(matches!(by_owner, Err(UsecaseError::Forbidden(_))));
+
+        let by_admin = usecase.set_template(Uuid::new_v4(), true, route_id, true).await.unwrap();
+        assert_eq!(by_admin.owner_id, owner_id);
+    }
+
+    #[tokio::test]
+    async fn test_users_cannot_flag_foreign_routes() {
+        let route = Route::new(Uuid::new_v4(), "City tour".to_string(), vec![], vec![], vec![]);
+        let route_id = route.id;
+        let mut templates = MockRouteTemplateRepository::new();
+        templates.expect_upsert().never();
+        let usecase = TemplatesUseCase::new(templates, route_repository(route));
+
+        let result = usecase.set_template(Uuid::new_v4(), false, route_id, false).await;
+
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_private_templates_are_hidden_from_others() {
+        let owner_id = Uuid::new_v4();
+        let route = Route::new(owner_id, "Back streets".to_string(), vec![], vec![], vec![]);
+        let route_id = route.id;
+        let mut templates = MockRouteTemplateRepository::new();
+        templates
+            .expect_find_by_route_id()
+            .returning(move |id| Ok(Some(RouteTemplate::new(id, owner_id, false))));
+        let usecase = TemplatesUseCase::new(templates, route_repository(route));
+
+        assert!(usecase.template_route(owner_id, route_id).await.is_ok());
+        let result = usecase.template_route(Uuid::new_v4(), route_id).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+}
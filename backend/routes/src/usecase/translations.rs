@@ -0,0 +1,176 @@
+//! Server-generated texts and category names in the reader's locale. Texts
+//! live in the `translations` table; anything without a translation is shown
+//! as stored.
+
+use std::collections::HashMap;
+
+use crate::domain::notification::default_template;
+use crate::domain::translation::{fill_placeholders, Translation, DEFAULT_LOCALE, SUPPORTED_LOCALES};
+use crate::usecase::contracts::TranslationRepository;
+use crate::usecase::error::UsecaseError;
+
+/// Picks the supported locale the client prefers from an `Accept-Language`
+/// header, e.g. `ru-RU,ru;q=0.9,en;q=0.8`. Region subtags are ignored.
+pub fn negotiate_locale(accept_language: Option<&str>) -> &'static str {
+    let Some(header) = accept_language else {
+        return DEFAULT_LOCALE;
+    };
+
+    let mut ranges: Vec<(&str, f32)> = header
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .map(|q| q.parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0);
+            Some((tag, quality))
+        })
+        .filter(|(tag, quality)| !tag.is_empty() && *quality > 0.0)
+        .collect();
+    // Stable, so equally weighted ranges keep the client's order
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges
+        .iter()
+        .filter_map(|(tag, _)| {
+            let language = tag.split('-').next().unwrap_or(tag).to_ascii_lowercase();
+            SUPPORTED_LOCALES.iter().copied().find(|l| *l == language)
+        })
+        .next()
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Translations of one locale.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    entries: HashMap<String, String>,
+}
+
+impl Catalog {
+    pub fn new(translations: Vec<Translation>) -> Self {
+        Self {
+            entries: translations.into_iter().map(|t| (t.key, t.value)).collect(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// Renders a notification template, falling back to its default-locale
+    /// text when the locale has no translation. None when the template is
+    /// unknown or a placeholder has no value, so the caller can fall back to
+    /// its own text.
+    pub fn render(&self, key: &str, params: &serde_json::Value) -> Option<String> {
+        let template = self.get(key).or_else(|| default_template(key))?;
+        fill_placeholders(template, params)
+    }
+}
+
+pub struct TranslationsUseCase<T>
+where
+    T: TranslationRepository,
+{
+    translation_repository: T,
+}
+
+impl<T> TranslationsUseCase<T>
+where
+    T: TranslationRepository,
+{
+    pub fn new(translation_repository: T) -> Self {
+        Self { translation_repository }
+    }
+
+    /// Translations of the locale. The default locale usually has none, since
+    /// stored texts are already in it.
+    #[tracing::instrument(skip(self))]
+    pub async fn catalog(&self, locale: &str) -> Result<Catalog, UsecaseError> {
+        let translations = self.translation_repository.find_by_locale(locale).await?;
+        tracing::debug!(count = translations.len(), "translation catalog loaded");
+        Ok(Catalog::new(translations))
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn list(&self, locale: &str) -> Result<Vec<Translation>, UsecaseError> {
+        Ok(self.translation_repository.find_by_locale(locale).await?)
+    }
+
+    #[tracing::instrument(skip(self, value))]
+    pub async fn set(&self, key: String, locale: String, value: String) -> Result<Translation, UsecaseError> {
+        if !SUPPORTED_LOCALES.contains(&locale.as_str()) {
+            return Err(UsecaseError::Validation(format!(
+                "Unsupported locale '{}', expected one of: {}",
+                locale,
+                SUPPORTED_LOCALES.join(", ")
+            )));
+        }
+
+        let translation = Translation::new(key, locale, value);
+        self.translation_repository.upsert(&translation).await?;
+
+        tracing::info!(key = %translation.key, locale = %translation.locale, "translation saved");
+        Ok(translation)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn delete(&self, key: &str, locale: &str) -> Result<(), UsecaseError> {
+        self.translation_repository.delete(key, locale).await?;
+        tracing::info!("translation deleted");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecase::contracts::MockTranslationRepository;
+
+    fn catalog(entries: &[(&str, &str)]) -> Catalog {
+        Catalog::new(
+            entries
+                .iter()
+                .map(|(k, v)| Translation::new(k.to_string(), "ru".to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_negotiate_locale_follows_quality_and_order() {
+        assert_eq!(negotiate_locale(None), "en");
+        assert_eq!(negotiate_locale(Some("ru-RU,ru;q=0.9,en;q=0.8")), "ru");
+        assert_eq!(negotiate_locale(Some("en;q=0.5, ru;q=0.7")), "ru");
+        assert_eq!(negotiate_locale(Some("de, RU")), "ru");
+        assert_eq!(negotiate_locale(Some("de, fr;q=0.5")), "en");
+        assert_eq!(negotiate_locale(Some("ru;q=0, en")), "en");
+        assert_eq!(negotiate_locale(Some("*")), "en");
+    }
+
+    #[test]
+    fn test_render_fills_placeholders() {
+        let catalog = catalog(&[("notification.rating", "{actor} оценил(а) «{route}» на {rating}/5")]);
+        let params = serde_json::json!({"actor": "ann", "route": "Old town", "rating": 4});
+
+        assert_eq!(
+            catalog.render("notification.rating", &params).as_deref(),
+            Some("ann оценил(а) «Old town» на 4/5")
+        );
+        assert_eq!(
+            catalog.render("notification.like", &params).as_deref(),
+            Some("ann liked your route \"Old town\"")
+        );
+        assert_eq!(catalog.render("notification.unknown", &params), None);
+        assert_eq!(catalog.render("notification.rating", &serde_json::json!({"actor": "ann"})), None);
+    }
+
+    #[tokio::test]
+    async fn test_set_rejects_unsupported_locale() {
+        let usecase = TranslationsUseCase::new(MockTranslationRepository::new());
+
+        let result = usecase.set("difficulty.easy".to_string(), "de".to_string(), "leicht".to_string()).await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+}
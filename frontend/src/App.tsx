@@ -9,6 +9,7 @@ import ProfilePage from './pages/ProfilePage';
 import ExplorePage from './pages/ExplorePage';
 import { SharedMapPage } from './pages/SharedMapPage';
 import { EmbedMapPage } from './pages/EmbedMapPage';
+import { SharedChatPage } from './pages/SharedChatPage';
 import { ProtectedRoute } from './components/ProtectedRoute';
 import { ProtectedAdminRoute } from './components/ProtectedAdminRoute';
 import { ErrorBoundary } from './components/ErrorBoundary';
@@ -59,6 +60,7 @@ function App() {
             }
           />
           <Route path="/shared/:token" element={<SharedMapPage />} />
+          <Route path="/shared/chat/:token" element={<SharedChatPage />} />
           <Route path="/embed/:token" element={<EmbedMapPage />} />
           <Route path="/" element={<Navigate to="/map" replace />} />
         </Routes>
@@ -1,5 +1,6 @@
 import axios from 'axios';
 import { API_BASE_URL } from './config';
+import type { ListResponse } from './types';
 
 const AUTH_URL = `${API_BASE_URL}/api/v1/admin`;
 const ROUTES_URL = `${API_BASE_URL}/api/v1/admin/routes`;
@@ -13,11 +14,6 @@ export interface AdminUser {
   created_at: string;
 }
 
-export interface UsersListResponse {
-  users: AdminUser[];
-  total: number;
-}
-
 export interface AuthStatsResponse {
   total_users: number;
   by_role: { role: string; count: number }[];
@@ -38,11 +34,6 @@ export interface AdminRoute {
   category_ids: string[];
 }
 
-export interface AdminRoutesListResponse {
-  routes: AdminRoute[];
-  total: number;
-}
-
 export interface AdminComment {
   id: string;
   route_id: string;
@@ -52,9 +43,43 @@ export interface AdminComment {
   created_at: string;
 }
 
-export interface AdminCommentsListResponse {
-  comments: AdminComment[];
-  total: number;
+export interface DailyCount {
+  day: string;
+  count: number;
+}
+
+export interface ChatOpsStats {
+  days: number;
+  messages_per_day: DailyCount[];
+  turns: number;
+  errors: number;
+  error_rate: number;
+  avg_latency_ms: number;
+  tool_calls: { tool: string; count: number }[];
+  top_users: { user_id: string; messages: number }[];
+}
+
+export interface FlaggedConversation {
+  user_id: string;
+  conversation_id: string;
+  reason: string | null;
+  review_consent: boolean;
+  message_count: number;
+  flagged_at: string;
+}
+
+export interface ReviewedChatMessage {
+  id: string;
+  role: string;
+  content: string;
+  created_at: string;
+}
+
+export interface ImpersonationResponse {
+  access_token: string;
+  token_type: string;
+  user_id: string;
+  expires_at: string;
 }
 
 const getAuthHeader = () => {
@@ -67,7 +92,7 @@ export const adminApi = {
     limit?: number;
     offset?: number;
     search?: string;
-  }): Promise<UsersListResponse> {
+  }): Promise<ListResponse<AdminUser>> {
     const response = await axios.get(`${AUTH_URL}/users`, {
       headers: getAuthHeader(),
       params,
@@ -83,6 +108,14 @@ export const adminApi = {
     );
   },
 
+  /** Short-lived token to act as the user; every request with it is audited. */
+  async impersonateUser(userId: string): Promise<ImpersonationResponse> {
+    const response = await axios.post(`${AUTH_URL}/impersonate/${userId}`, null, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
   async getAuthStats(): Promise<AuthStatsResponse> {
     const response = await axios.get(`${AUTH_URL}/stats`, {
       headers: getAuthHeader(),
@@ -100,7 +133,7 @@ export const adminApi = {
   async getAdminRoutes(params: {
     limit?: number;
     offset?: number;
-  }): Promise<AdminRoutesListResponse> {
+  }): Promise<ListResponse<AdminRoute>> {
     const response = await axios.get(ROUTES_URL, {
       headers: getAuthHeader(),
       params,
@@ -108,10 +141,38 @@ export const adminApi = {
     return response.data;
   },
 
+  async getChatStats(days = 30): Promise<ChatOpsStats> {
+    const response = await axios.get(`${AUTH_URL}/chat/stats`, {
+      headers: getAuthHeader(),
+      params: { days },
+    });
+    return response.data;
+  },
+
+  async getFlaggedConversations(params: {
+    limit?: number;
+    offset?: number;
+  }): Promise<ListResponse<FlaggedConversation>> {
+    const response = await axios.get(`${AUTH_URL}/chat/flagged`, {
+      headers: getAuthHeader(),
+      params,
+    });
+    return response.data;
+  },
+
+  /** Fails with 403 unless the user allowed the review when flagging. */
+  async getFlaggedConversationMessages(userId: string, conversationId: string): Promise<ReviewedChatMessage[]> {
+    const response = await axios.get<ListResponse<ReviewedChatMessage>>(
+      `${AUTH_URL}/chat/flagged/${userId}/${conversationId}`,
+      { headers: getAuthHeader() }
+    );
+    return response.data.data;
+  },
+
   async getAdminComments(params: {
     limit?: number;
     offset?: number;
-  }): Promise<AdminCommentsListResponse> {
+  }): Promise<ListResponse<AdminComment>> {
     const response = await axios.get(COMMENTS_URL, {
       headers: getAuthHeader(),
       params,
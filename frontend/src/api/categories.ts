@@ -1,5 +1,6 @@
 import axios from 'axios';
 import { API_BASE_URL } from './config';
+import type { ListResponse } from './types';
 
 const CATEGORIES_URL = `${API_BASE_URL}/api/v1/categories`;
 const ADMIN_CATEGORIES_URL = `${API_BASE_URL}/api/v1/admin/categories`;
@@ -17,8 +18,8 @@ const getAuthHeader = () => {
 
 export const categoriesApi = {
   async getCategories(): Promise<Category[]> {
-    const response = await axios.get(CATEGORIES_URL);
-    return response.data;
+    const response = await axios.get<ListResponse<Category>>(CATEGORIES_URL);
+    return response.data.data;
   },
 
   async createCategory(name: string): Promise<Category> {
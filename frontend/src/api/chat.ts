@@ -1,5 +1,6 @@
 import axios from 'axios';
 import { API_BASE_URL } from './config';
+import type { ListResponse } from './types';
 
 const CHAT_URL = `${API_BASE_URL}/api/v1/chat`;
 
@@ -15,14 +16,19 @@ export interface ChatRouteRef {
   category_ids: string[];
   avg_rating: number;
   likes_count: number;
+  start_location?: string;
+  end_location?: string;
 }
 
 export interface ChatAction {
-  type: 'show_points' | 'show_routes' | 'navigate';
+  type: 'show_points' | 'show_routes' | 'navigate' | 'route_updated' | 'confirmation_required';
   points?: ChatPoint[];
   routes?: ChatRouteRef[];
   path?: string;
   label?: string;
+  route_id?: string;
+  action_id?: string;
+  summary?: string;
 }
 
 export interface ChatMessageResponse {
@@ -30,6 +36,7 @@ export interface ChatMessageResponse {
   message: string;
   actions: ChatAction[];
   conversation_id: string;
+  notice?: string;
 }
 
 export interface ChatHistoryMessage {
@@ -47,16 +54,33 @@ export interface ConversationSummary {
   created_at: string;
   updated_at: string;
   title: string;
+  archived: boolean;
+  pinned: boolean;
 }
 
-export interface ListConversationsResponse {
-  conversations: ConversationSummary[];
-  total: number;
+export interface SharedChatMessage {
+  role: 'user' | 'assistant';
+  content: string;
+  created_at: string;
+}
+
+export type ChatLanguage = 'en' | 'ru';
+
+export interface ChatPreferences {
+  /** `null` means the assistant answers in whatever language the user writes. */
+  language: ChatLanguage | null;
+}
+
+export interface ConversationFlags {
+  archived: boolean;
+  pinned: boolean;
 }
 
 export interface ChatStreamEvent {
-  type: 'token' | 'actions' | 'done' | 'error';
+  type: 'token' | 'actions' | 'notice' | 'tool_started' | 'tool_finished' | 'done' | 'error';
   content?: string;
+  /** Tool name for `tool_started` / `tool_finished`. */
+  name?: string;
   actions?: ChatAction[];
   id?: string;
   conversation_id?: string;
@@ -69,12 +93,19 @@ const getAuthHeader = () => {
 };
 
 export const chatApi = {
-  async sendMessage(message: string, conversationId?: string): Promise<ChatMessageResponse> {
+  async sendMessage(
+    message: string,
+    conversationId?: string,
+    routeId?: string,
+    attachments: string[] = [],
+  ): Promise<ChatMessageResponse> {
     const response = await axios.post(
       CHAT_URL,
       {
         message,
         conversation_id: conversationId || undefined,
+        route_id: routeId || undefined,
+        attachments,
       },
       {
         headers: getAuthHeader(),
@@ -91,6 +122,10 @@ export const chatApi = {
     onActions: (actions: ChatAction[]) => void,
     onDone: (id: string, conversationId: string) => void,
     onError: (message: string) => void,
+    onNotice?: (message: string) => void,
+    routeId?: string,
+    attachments: string[] = [],
+    onToolProgress?: (tool: string | null) => void,
   ): Promise<void> {
     const token = localStorage.getItem('access_token');
     const response = await fetch(`${CHAT_URL}/stream`, {
@@ -102,6 +137,8 @@ export const chatApi = {
       body: JSON.stringify({
         message,
         conversation_id: conversationId || undefined,
+        route_id: routeId || undefined,
+        attachments,
       }),
     });
 
@@ -141,6 +178,15 @@ export const chatApi = {
               case 'actions':
                 if (event.actions) onActions(event.actions);
                 break;
+              case 'notice':
+                if (event.message) onNotice?.(event.message);
+                break;
+              case 'tool_started':
+                if (event.name) onToolProgress?.(event.name);
+                break;
+              case 'tool_finished':
+                onToolProgress?.(null);
+                break;
               case 'done':
                 if (event.id && event.conversation_id) onDone(event.id, event.conversation_id);
                 break;
@@ -157,25 +203,101 @@ export const chatApi = {
   },
 
   async getHistory(conversationId: string): Promise<ChatHistoryMessage[]> {
-    const response = await axios.get(`${CHAT_URL}/${conversationId}`, {
+    const response = await axios.get<ListResponse<ChatHistoryMessage>>(`${CHAT_URL}/${conversationId}`, {
       headers: getAuthHeader(),
     });
-    return response.data;
+    return response.data.data;
   },
 
-  async listConversations(): Promise<ListConversationsResponse> {
+  async listConversations(): Promise<ListResponse<ConversationSummary>> {
     const response = await axios.get(CHAT_URL, {
       headers: getAuthHeader(),
     });
     return response.data;
   },
 
+  async updateConversation(
+    conversationId: string,
+    flags: Partial<ConversationFlags>,
+  ): Promise<ConversationFlags> {
+    const response = await axios.patch<ConversationFlags>(`${CHAT_URL}/${conversationId}`, flags, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  /** Returns the share token; the same token is returned until sharing is revoked. */
+  async shareConversation(conversationId: string): Promise<string> {
+    const response = await axios.post<{ share_token: string }>(`${CHAT_URL}/${conversationId}/share`, null, {
+      headers: getAuthHeader(),
+    });
+    return response.data.share_token;
+  },
+
+  async unshareConversation(conversationId: string): Promise<void> {
+    await axios.delete(`${CHAT_URL}/${conversationId}/share`, {
+      headers: getAuthHeader(),
+    });
+  },
+
+  /** `allowReview` lets admins read the conversation while reviewing the flag. */
+  async flagConversation(conversationId: string, reason: string, allowReview: boolean): Promise<void> {
+    await axios.post(`${CHAT_URL}/${conversationId}/flag`, { reason, allow_review: allowReview }, {
+      headers: getAuthHeader(),
+    });
+  },
+
+  async getSharedConversation(token: string): Promise<SharedChatMessage[]> {
+    const response = await axios.get<ListResponse<SharedChatMessage>>(`${CHAT_URL}/shared/${token}`);
+    return response.data.data;
+  },
+
+  async getPreferences(): Promise<ChatPreferences> {
+    const response = await axios.get<ChatPreferences>(`${CHAT_URL}/preferences`, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async updatePreferences(preferences: ChatPreferences): Promise<ChatPreferences> {
+    const response = await axios.put<ChatPreferences>(`${CHAT_URL}/preferences`, preferences, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
   async deleteConversation(conversationId: string): Promise<void> {
     await axios.delete(`${CHAT_URL}/${conversationId}`, {
       headers: getAuthHeader(),
     });
   },
 
+  /** Uploads an image through a presigned link and returns its attachment key. */
+  async uploadAttachment(file: File): Promise<string> {
+    const { data } = await axios.post<{ key: string; upload_url: string }>(
+      `${CHAT_URL}/attachments`,
+      { content_type: file.type },
+      { headers: getAuthHeader() },
+    );
+    await fetch(data.upload_url, {
+      method: 'PUT',
+      headers: { 'Content-Type': file.type },
+      body: file,
+    }).then((res) => {
+      if (!res.ok) throw new Error(`Upload failed: ${res.status}`);
+    });
+    return data.key;
+  },
+
+  async confirmAction(actionId: string): Promise<ChatMessageResponse> {
+    const response = await axios.post(
+      `${CHAT_URL}/actions/${actionId}/confirm`,
+      {},
+      { headers: getAuthHeader() },
+    );
+    return response.data;
+  },
+
   async deleteMessage(conversationId: string, messageId: string): Promise<void> {
     await axios.delete(`${CHAT_URL}/${conversationId}/messages/${messageId}`, {
       headers: getAuthHeader(),
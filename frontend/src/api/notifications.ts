@@ -1,5 +1,6 @@
 import axios from 'axios';
 import { API_BASE_URL } from './config';
+import type { ListResponse } from './types';
 
 const NOTIFICATIONS_URL = `${API_BASE_URL}/api/v1/notifications`;
 
@@ -14,8 +15,7 @@ export interface Notification {
   created_at: string;
 }
 
-export interface NotificationsListResponse {
-  notifications: Notification[];
+export interface NotificationsListMeta {
   unread_count: number;
 }
 
@@ -32,7 +32,7 @@ export const notificationsApi = {
   async list(params?: {
     limit?: number;
     offset?: number;
-  }): Promise<NotificationsListResponse> {
+  }): Promise<ListResponse<Notification, NotificationsListMeta>> {
     const response = await axios.get(NOTIFICATIONS_URL, {
       headers: getAuthHeader(),
       params,
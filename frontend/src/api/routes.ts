@@ -1,7 +1,9 @@
 import axios from 'axios';
 import { API_BASE_URL } from './config';
+import type { ListResponse } from './types';
 
 const ROUTES_URL = `${API_BASE_URL}/api/v1/routes`;
+const COLLECTIONS_URL = `${API_BASE_URL}/api/v1/collections`;
 
 export interface PhotoData {
   original: string;
@@ -9,14 +11,23 @@ export interface PhotoData {
   status: string;
 }
 
+export interface PointAddress {
+  street?: string;
+  city?: string;
+  country?: string;
+}
+
 export interface RoutePoint {
   lat: number;
   lng: number;
   name?: string;
   segment_mode?: 'auto' | 'manual'; // mode for segment TO this point
   photo?: PhotoData;
+  address?: PointAddress;
 }
 
+export type RouteVisibility = 'private' | 'unlisted' | 'public';
+
 export interface Route {
   id: string;
   user_id: string;
@@ -25,11 +36,14 @@ export interface Route {
   created_at: string;
   updated_at: string;
   share_token?: string;
+  visibility: RouteVisibility;
   category_ids: string[];
   start_location?: string;
   end_location?: string;
   seasons: string[];
   description?: string;
+  // Straight-line distance and estimated duration, computed by the server
+  stats: RouteStats;
 }
 
 export interface Comment {
@@ -38,12 +52,91 @@ export interface Comment {
   user_id: string;
   author_name: string;
   text: string;
+  approved: boolean;
+  created_at: string;
+}
+
+export type CommentPolicy = 'open' | 'pre_moderated' | 'locked';
+
+export type RouteActivityKind =
+  | 'created'
+  | 'points_edited'
+  | 'details_edited'
+  | 'reversed'
+  | 'shared'
+  | 'unshared'
+  | 'visibility_changed'
+  | 'archived'
+  | 'unarchived'
+  | 'photos_processed'
+  | 'commented';
+
+export interface RouteActivity {
+  id: string;
+  kind: RouteActivityKind;
+  actor_id: string | null;
+  details: Record<string, unknown> | null;
+  created_at: string;
+}
+
+export interface RouteTemplate {
+  route_id: string;
+  owner_id: string;
+  public: boolean;
+  name: string;
+  description: string | null;
+  start_location: string | null;
+  end_location: string | null;
+  points_count: number;
+  created_at: string;
+}
+
+/** Labelled, individually revocable link; `token` opens `/shared/{token}`. */
+export interface ShareLink {
+  id: string;
+  label: string;
+  token: string;
+  created_at: string;
+  revoked_at: string | null;
+}
+
+/** Named folder of the user's own routes; a route can be in several. */
+export interface RouteCollection {
+  id: string;
+  name: string;
+  route_count: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface SearchArea {
+  min_lat: number;
+  min_lng: number;
+  max_lat: number;
+  max_lng: number;
+}
+
+export interface SavedSearch {
+  id: string;
+  name: string;
+  search: string | null;
+  category_id: string | null;
+  area: SearchArea | null;
+  last_checked_at: string;
   created_at: string;
 }
 
+export interface CreateSavedSearchRequest {
+  name: string;
+  search?: string;
+  category_id?: string;
+  area?: SearchArea;
+}
+
 export interface CreateCommentRequest {
   text: string;
   author_name: string;
+  form_elapsed_ms?: number;
 }
 
 export interface LikeCountResponse {
@@ -79,17 +172,29 @@ export interface ExploreRoute {
   name: string;
   points_count: number;
   created_at: string;
-  share_token: string;
+  link_token: string;
   likes_count: number;
   avg_rating: number;
   ratings_count: number;
+  comments_count: number;
+  bookmarks_count: number;
   category_ids: string[];
   seasons: string[];
+  start_location?: string;
+  end_location?: string;
+  cover_url?: string;
+  cover_thumbnail_url?: string;
+  stats: RouteStats;
 }
 
-export interface ExploreResponse {
-  routes: ExploreRoute[];
-  total: number;
+// How many explore results each filter value would yield
+export interface ExploreFacets {
+  category_ids: Record<string, number>;
+  difficulty: Partial<Record<'easy' | 'moderate' | 'hard', number>>;
+}
+
+export interface ExploreMeta {
+  facets: ExploreFacets;
 }
 
 export interface ExploreParams {
@@ -101,11 +206,24 @@ export interface ExploreParams {
   offset?: number;
 }
 
+export interface ListRoutesParams {
+  include_archived?: boolean;
+  search?: string;
+  category_id?: string;
+  created_after?: string;
+  created_before?: string;
+  collection_id?: string;
+  sort?: 'newest' | 'oldest' | 'name' | 'updated';
+  limit?: number;
+  offset?: number;
+}
+
 export interface CreateRouteRequest {
   name: string;
   points: RoutePoint[];
   category_ids: string[];
   seasons: string[];
+  form_elapsed_ms?: number;
 }
 
 export interface UpdateRouteRequest {
@@ -115,15 +233,69 @@ export interface UpdateRouteRequest {
   seasons?: string[];
 }
 
+export type PointOperation =
+  | { op: 'insert'; index: number; point: RoutePoint }
+  | { op: 'move'; from: number; to: number }
+  | { op: 'delete'; index: number }
+  | { op: 'update'; index: number; point: RoutePoint };
+
+export interface MergeRoutesRequest {
+  route_ids: string[];
+  reversed_route_ids?: string[];
+  name?: string;
+}
+
+export interface ImportFileResult {
+  filename: string | null;
+  status: 'created' | 'duplicate' | 'failed';
+  route: Route | null;
+  existing_route_id: string | null;
+  error: string | null;
+}
+
+export interface ImportBatchResponse {
+  created: number;
+  failed: number;
+  files: ImportFileResult[];
+}
+
+export interface SplitRouteResponse {
+  first: Route;
+  second: Route;
+}
+
+export type TravelMode = 'walking' | 'cycling' | 'driving' | 'transit';
+
+export interface ModeStats {
+  mode: TravelMode;
+  segments: number;
+  distance_km: number;
+  duration_min: number;
+}
+
+export interface RouteStats {
+  distance_km: number;
+  duration_min: number;
+  by_mode: ModeStats[];
+  segment_distances_km: number[];
+}
+
+export interface RouteStatsResponse {
+  route_id: string;
+  stats: RouteStats;
+  from_segments: boolean;
+}
+
 const getAuthHeader = () => {
   const token = localStorage.getItem('access_token');
   return token ? { Authorization: `Bearer ${token}` } : {};
 };
 
 export const routesApi = {
-  async getRoutes(): Promise<Route[]> {
-    const response = await axios.get(ROUTES_URL, {
+  async getRoutes(params: ListRoutesParams = {}): Promise<ListResponse<Route>> {
+    const response = await axios.get<ListResponse<Route>>(ROUTES_URL, {
       headers: getAuthHeader(),
+      params,
     });
     return response.data;
   },
@@ -142,6 +314,36 @@ export const routesApi = {
     return response.data;
   },
 
+  async mergeRoutes(data: MergeRoutesRequest): Promise<Route> {
+    const response = await axios.post(`${ROUTES_URL}/merge`, data, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async splitRoute(id: string, pointIndex: number, truncateOriginal = false): Promise<SplitRouteResponse> {
+    const response = await axios.post<SplitRouteResponse>(
+      `${ROUTES_URL}/${id}/split`,
+      { point_index: pointIndex, truncate_original: truncateOriginal },
+      { headers: getAuthHeader() },
+    );
+    return response.data;
+  },
+
+  async reverseRoute(id: string): Promise<Route> {
+    const response = await axios.post<Route>(`${ROUTES_URL}/${id}/reverse`, null, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async getRouteStats(id: string): Promise<RouteStatsResponse> {
+    const response = await axios.get<RouteStatsResponse>(`${ROUTES_URL}/${id}/stats`, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
   async updateRoute(id: string, data: UpdateRouteRequest): Promise<Route> {
     const response = await axios.put(`${ROUTES_URL}/${id}`, data, {
       headers: getAuthHeader(),
@@ -149,13 +351,20 @@ export const routesApi = {
     return response.data;
   },
 
+  async patchRoutePoints(id: string, expectedUpdatedAt: string, operations: PointOperation[]): Promise<Route> {
+    const response = await axios.patch(`${ROUTES_URL}/${id}/points`, { expected_updated_at: expectedUpdatedAt, operations }, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
   async deleteRoute(id: string): Promise<void> {
     await axios.delete(`${ROUTES_URL}/${id}`, {
       headers: getAuthHeader(),
     });
   },
 
-  async importFromGeoJson(file: File): Promise<Route> {
+  async importFromGeoJson(file: File, force = false): Promise<Route> {
     const formData = new FormData();
     formData.append('file', file);
     const response = await axios.post(`${ROUTES_URL}/import`, formData, {
@@ -163,6 +372,28 @@ export const routesApi = {
         ...getAuthHeader(),
         'Content-Type': 'multipart/form-data',
       },
+      params: force ? { force: true } : undefined,
+    });
+    return response.data;
+  },
+
+  async importFromUrl(url: string, force = false): Promise<Route> {
+    const response = await axios.post(`${ROUTES_URL}/import/url`, { url }, {
+      headers: getAuthHeader(),
+      params: force ? { force: true } : undefined,
+    });
+    return response.data;
+  },
+
+  async importFiles(files: File[], force = false): Promise<ImportBatchResponse> {
+    const formData = new FormData();
+    files.forEach((file) => formData.append('file', file));
+    const response = await axios.post(`${ROUTES_URL}/import`, formData, {
+      headers: {
+        ...getAuthHeader(),
+        'Content-Type': 'multipart/form-data',
+      },
+      params: force ? { force: true } : undefined,
     });
     return response.data;
   },
@@ -180,7 +411,73 @@ export const routesApi = {
     });
   },
 
-  async exploreRoutes(params: ExploreParams = {}): Promise<ExploreResponse> {
+  async setVisibility(id: string, visibility: RouteVisibility): Promise<Route> {
+    const response = await axios.put(`${ROUTES_URL}/${id}/visibility`, { visibility }, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async getShareLinks(routeId: string): Promise<ListResponse<ShareLink>> {
+    const response = await axios.get<ListResponse<ShareLink>>(`${ROUTES_URL}/${routeId}/share-links`, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async createShareLink(routeId: string, label: string): Promise<ShareLink> {
+    const response = await axios.post(`${ROUTES_URL}/${routeId}/share-links`, { label }, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async revokeShareLink(routeId: string, linkId: string): Promise<void> {
+    await axios.delete(`${ROUTES_URL}/${routeId}/share-links/${linkId}`, {
+      headers: getAuthHeader(),
+    });
+  },
+
+  async getCollections(): Promise<ListResponse<RouteCollection>> {
+    const response = await axios.get<ListResponse<RouteCollection>>(COLLECTIONS_URL, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async createCollection(name: string): Promise<RouteCollection> {
+    const response = await axios.post(COLLECTIONS_URL, { name }, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async renameCollection(id: string, name: string): Promise<RouteCollection> {
+    const response = await axios.put(`${COLLECTIONS_URL}/${id}`, { name }, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async deleteCollection(id: string): Promise<void> {
+    await axios.delete(`${COLLECTIONS_URL}/${id}`, {
+      headers: getAuthHeader(),
+    });
+  },
+
+  async addRouteToCollection(collectionId: string, routeId: string): Promise<void> {
+    await axios.post(`${COLLECTIONS_URL}/${collectionId}/routes`, { route_id: routeId }, {
+      headers: getAuthHeader(),
+    });
+  },
+
+  async removeRouteFromCollection(collectionId: string, routeId: string): Promise<void> {
+    await axios.delete(`${COLLECTIONS_URL}/${collectionId}/routes/${routeId}`, {
+      headers: getAuthHeader(),
+    });
+  },
+
+  async exploreRoutes(params: ExploreParams = {}): Promise<ListResponse<ExploreRoute, ExploreMeta>> {
     const response = await axios.get(`${ROUTES_URL}/explore`, { params });
     return response.data;
   },
@@ -190,11 +487,66 @@ export const routesApi = {
     return response.data;
   },
 
-  async getComments(routeId: string): Promise<Comment[]> {
-    const response = await axios.get(`${ROUTES_URL}/${routeId}/comments`);
+  async getRouteActivity(routeId: string, limit = 50, offset = 0): Promise<ListResponse<RouteActivity>> {
+    const response = await axios.get<ListResponse<RouteActivity>>(`${ROUTES_URL}/${routeId}/activity`, {
+      headers: getAuthHeader(),
+      params: { limit, offset },
+    });
+    return response.data;
+  },
+
+  async getTemplates(limit = 20, offset = 0): Promise<ListResponse<RouteTemplate>> {
+    const response = await axios.get<ListResponse<RouteTemplate>>(`${API_BASE_URL}/api/v1/templates`, {
+      headers: getAuthHeader(),
+      params: { limit, offset },
+    });
     return response.data;
   },
 
+  async setRouteTemplate(routeId: string, isPublic = false): Promise<void> {
+    await axios.put(`${ROUTES_URL}/${routeId}/template`, { public: isPublic }, {
+      headers: getAuthHeader(),
+    });
+  },
+
+  async removeRouteTemplate(routeId: string): Promise<void> {
+    await axios.delete(`${ROUTES_URL}/${routeId}/template`, {
+      headers: getAuthHeader(),
+    });
+  },
+
+  async instantiateTemplate(templateId: string): Promise<Route> {
+    const response = await axios.post(`${API_BASE_URL}/api/v1/templates/${templateId}/instantiate`, null, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async getSavedSearches(): Promise<SavedSearch[]> {
+    const response = await axios.get<ListResponse<SavedSearch>>(`${API_BASE_URL}/api/v1/saved-searches`, {
+      headers: getAuthHeader(),
+    });
+    return response.data.data;
+  },
+
+  async createSavedSearch(data: CreateSavedSearchRequest): Promise<SavedSearch> {
+    const response = await axios.post<SavedSearch>(`${API_BASE_URL}/api/v1/saved-searches`, data, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async deleteSavedSearch(id: string): Promise<void> {
+    await axios.delete(`${API_BASE_URL}/api/v1/saved-searches/${id}`, {
+      headers: getAuthHeader(),
+    });
+  },
+
+  async getComments(routeId: string): Promise<Comment[]> {
+    const response = await axios.get<ListResponse<Comment>>(`${ROUTES_URL}/${routeId}/comments`);
+    return response.data.data;
+  },
+
   async createComment(routeId: string, data: CreateCommentRequest): Promise<Comment> {
     const response = await axios.post(`${ROUTES_URL}/${routeId}/comments`, data, {
       headers: getAuthHeader(),
@@ -208,6 +560,37 @@ export const routesApi = {
     });
   },
 
+  async getPendingComments(routeId: string): Promise<Comment[]> {
+    const response = await axios.get<ListResponse<Comment>>(`${ROUTES_URL}/${routeId}/comments/pending`, {
+      headers: getAuthHeader(),
+    });
+    return response.data.data;
+  },
+
+  async approveComment(commentId: string): Promise<Comment> {
+    const response = await axios.post(`${API_BASE_URL}/api/v1/comments/${commentId}/approve`, null, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async getCommentPolicy(routeId: string): Promise<CommentPolicy> {
+    const response = await axios.get(`${ROUTES_URL}/${routeId}/comment-policy`);
+    return response.data.policy;
+  },
+
+  async setCommentPolicy(routeId: string, policy: CommentPolicy): Promise<void> {
+    await axios.put(`${ROUTES_URL}/${routeId}/comment-policy`, { policy }, {
+      headers: getAuthHeader(),
+    });
+  },
+
+  async blockUser(userId: string): Promise<void> {
+    await axios.post(`${API_BASE_URL}/api/v1/users/${userId}/block`, null, {
+      headers: getAuthHeader(),
+    });
+  },
+
   async getCommentCount(routeId: string): Promise<number> {
     const response = await axios.get(`${ROUTES_URL}/${routeId}/comments/count`);
     return response.data.count;
@@ -272,10 +655,10 @@ export const routesApi = {
   },
 
   async getBookmarks(): Promise<ExploreRoute[]> {
-    const response = await axios.get(`${API_BASE_URL}/api/v1/bookmarks`, {
+    const response = await axios.get<ListResponse<ExploreRoute>>(`${API_BASE_URL}/api/v1/bookmarks`, {
       headers: getAuthHeader(),
     });
-    return response.data;
+    return response.data.data;
   },
 
   async generateDescription(routeId: string): Promise<{ description: string }> {
@@ -285,6 +668,21 @@ export const routesApi = {
     return response.data;
   },
 
+  /** Queues failed photos for processing again; returns the point indices queued. */
+  async retryFailedPhotos(routeId: string): Promise<{ point_indices: number[] }> {
+    const response = await axios.post(`${ROUTES_URL}/${routeId}/photos/retry`, {}, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async setCover(routeId: string, pointIndex: number | null): Promise<{ cover_url?: string; cover_thumbnail_url?: string }> {
+    const response = await axios.put(`${ROUTES_URL}/${routeId}/cover`, { point_index: pointIndex }, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
   async saveDescription(routeId: string, description: string): Promise<Route> {
     const response = await axios.post(`${ROUTES_URL}/${routeId}/description`, { description }, {
       headers: getAuthHeader(),
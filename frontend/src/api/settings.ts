@@ -19,6 +19,27 @@ export const DEFAULT_DIFFICULTY_THRESHOLDS: DifficultyThresholds = {
   score_moderate_max: 4,
 };
 
+export interface ChatPromptSettings {
+  template: string;
+  is_default: boolean;
+  variables: string[];
+}
+
+export interface PhotoPipelineSettings {
+  max_width: number;
+  quality: number;
+  thumbnail_width: number;
+  thumbnail_quality: number;
+  format: 'jpeg' | 'webp';
+}
+
+export type ContentFilterMode = 'reject' | 'mask' | 'flag';
+
+export interface ContentFilterSettings {
+  mode: ContentFilterMode;
+  terms: string[];
+}
+
 const getAuthHeader = () => {
   const token = localStorage.getItem('access_token');
   return token ? { Authorization: `Bearer ${token}` } : {};
@@ -38,4 +59,53 @@ export const settingsApi = {
     );
     return response.data;
   },
+
+  async getChatPrompt(): Promise<ChatPromptSettings> {
+    const response = await axios.get(`${API_BASE_URL}/api/v1/admin/settings/chat-prompt`, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  /** Passing `null` restores the built-in prompt. */
+  async updateChatPrompt(template: string | null): Promise<ChatPromptSettings> {
+    const response = await axios.put(
+      `${API_BASE_URL}/api/v1/admin/settings/chat-prompt`,
+      { template },
+      { headers: getAuthHeader() }
+    );
+    return response.data;
+  },
+
+  async getPhotoPipeline(): Promise<PhotoPipelineSettings> {
+    const response = await axios.get(`${API_BASE_URL}/api/v1/admin/settings/photo-pipeline`, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async updatePhotoPipeline(settings: PhotoPipelineSettings): Promise<PhotoPipelineSettings> {
+    const response = await axios.put(
+      `${API_BASE_URL}/api/v1/admin/settings/photo-pipeline`,
+      settings,
+      { headers: getAuthHeader() }
+    );
+    return response.data;
+  },
+
+  async getContentFilter(): Promise<ContentFilterSettings> {
+    const response = await axios.get(`${API_BASE_URL}/api/v1/admin/settings/content-filter`, {
+      headers: getAuthHeader(),
+    });
+    return response.data;
+  },
+
+  async updateContentFilter(settings: ContentFilterSettings): Promise<ContentFilterSettings> {
+    const response = await axios.put(
+      `${API_BASE_URL}/api/v1/admin/settings/content-filter`,
+      settings,
+      { headers: getAuthHeader() }
+    );
+    return response.data;
+  },
 };
@@ -0,0 +1,13 @@
+export interface Pagination {
+  limit: number;
+  offset: number;
+  total: number;
+}
+
+// Envelope returned by every v1 list endpoint
+export interface ListResponse<T, M = undefined> {
+  data: T[];
+  pagination: Pagination;
+  request_id: string;
+  meta?: M;
+}
@@ -1,15 +1,28 @@
 import React, { useState, useRef, useEffect, useCallback, memo } from 'react';
 import ReactMarkdown from 'react-markdown';
 import { useNavigate } from 'react-router-dom';
-import { chatApi, type ChatAction, type ChatPoint, type ChatRouteRef, type ConversationSummary } from '../api/chat';
+import { chatApi, type ChatAction, type ChatLanguage, type ChatPoint, type ChatRouteRef, type ConversationSummary } from '../api/chat';
 import { useLanguage } from '../context/LanguageContext';
+import type { TranslationKey } from '../i18n';
 import './ChatPanel.css';
 
+/** Matches the server-side limit per message. */
+const MAX_ATTACHMENTS = 3;
+
+/** Progress labels for the assistant's tools; others show a generic one. */
+const TOOL_LABELS: Record<string, TranslationKey> = {
+  geocode: 'chat.tool.geocode',
+  search_routes: 'chat.tool.searchRoutes',
+  get_route_details: 'chat.tool.routeDetails',
+  navigate: 'chat.tool.navigate',
+};
+
 interface DisplayMessage {
   id: string;
   role: 'user' | 'assistant';
   content: string;
   actions?: ChatAction[];
+  notice?: string;
 }
 
 interface ChatMessageItemProps {
@@ -20,10 +33,12 @@ interface ChatMessageItemProps {
   onShowPoints: (points: ChatPoint[]) => void;
   onShowRoutes: (routes: ChatRouteRef[]) => void;
   onNavigate: (path: string) => void;
+  onConfirmAction: (actionId: string) => void;
   tCopied: string;
   tDeleteMessage: string;
   tShowOnMap: string;
   tGoTo: string;
+  tConfirm: string;
 }
 
 const ChatMessageItem = memo(function ChatMessageItem({
@@ -34,10 +49,12 @@ const ChatMessageItem = memo(function ChatMessageItem({
   onShowPoints,
   onShowRoutes,
   onNavigate,
+  onConfirmAction,
   tCopied,
   tDeleteMessage,
   tShowOnMap,
   tGoTo,
+  tConfirm,
 }: ChatMessageItemProps) {
   return (
     <div className={`chat-message ${msg.role}`}>
@@ -48,6 +65,7 @@ const ChatMessageItem = memo(function ChatMessageItem({
       ) : (
         <div>{msg.content}</div>
       )}
+      {msg.notice && <div className="chat-message-notice">{msg.notice}</div>}
       {msg.role === 'assistant' && msg.content && (
         <button
           className="chat-message-copy"
@@ -88,6 +106,11 @@ const ChatMessageItem = memo(function ChatMessageItem({
                       onClick={() => onShowRoutes([route])}
                     >
                       <span className="chat-route-card-name">{route.name}</span>
+                      {(route.start_location || route.end_location) && (
+                        <span className="chat-route-card-meta">
+                          {route.start_location} &#8594; {route.end_location}
+                        </span>
+                      )}
                       <span className="chat-route-card-meta">
                         {(route.category_ids?.length ?? 0) > 0 && route.category_ids.join(', ')}
                         {route.avg_rating > 0 && ` \u2022 ${route.avg_rating.toFixed(1)}\u2605`}
@@ -115,6 +138,19 @@ const ChatMessageItem = memo(function ChatMessageItem({
                 </button>
               );
             }
+            if (action.type === 'confirmation_required' && action.action_id) {
+              return (
+                <div key={idx} className="chat-action-confirm">
+                  <span className="chat-action-confirm-summary">{action.summary}</span>
+                  <button
+                    className="chat-action-btn chat-action-navigate"
+                    onClick={() => onConfirmAction(action.action_id!)}
+                  >
+                    {tConfirm}
+                  </button>
+                </div>
+              );
+            }
             return null;
           })}
         </div>
@@ -128,18 +164,27 @@ interface ChatPanelProps {
   onClose: () => void;
   onShowPoints: (points: ChatPoint[]) => void;
   onShowRoutes: (routeIds: string[]) => void;
+  /** Own route currently open on the map, sent as context with each message. */
+  routeId?: string;
+  /** Called when the assistant has edited a route, so the map can reload it. */
+  onRouteUpdated?: (routeId: string) => void;
 }
 
-export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatPanelProps) {
+export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes, routeId, onRouteUpdated }: ChatPanelProps) {
   const [messages, setMessages] = useState<DisplayMessage[]>([]);
   const [input, setInput] = useState('');
   const [loading, setLoading] = useState(false);
+  const [toolProgress, setToolProgress] = useState<string | null>(null);
   const [error, setError] = useState('');
   const [conversationId, setConversationId] = useState<string | undefined>();
   const [copiedId, setCopiedId] = useState<string | null>(null);
   const [showHistory, setShowHistory] = useState(false);
+  const [shareCopied, setShareCopied] = useState(false);
+  const [chatLanguage, setChatLanguage] = useState<ChatLanguage | null>(null);
   const [conversations, setConversations] = useState<ConversationSummary[]>([]);
   const [loadingHistory, setLoadingHistory] = useState(false);
+  const [attachments, setAttachments] = useState<File[]>([]);
+  const fileInputRef = useRef<HTMLInputElement>(null);
   const messagesEndRef = useRef<HTMLDivElement>(null);
   const textareaRef = useRef<HTMLTextAreaElement>(null);
   const { t } = useLanguage();
@@ -149,6 +194,15 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
     messagesEndRef.current?.scrollIntoView({ behavior: 'smooth' });
   }, [messages, loading]);
 
+  useEffect(() => {
+    if (!isOpen) return;
+    chatApi.getPreferences()
+      .then((prefs) => setChatLanguage(prefs.language))
+      .catch(() => {
+        // keep the default
+      });
+  }, [isOpen]);
+
   useEffect(() => {
     if (isOpen && textareaRef.current && !showHistory) {
       textareaRef.current.focus();
@@ -157,12 +211,20 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
 
   if (!isOpen) return null;
 
+  const notifyRouteUpdates = (actions: ChatAction[]) => {
+    actions
+      .filter((a) => a.type === 'route_updated' && a.route_id)
+      .forEach((a) => onRouteUpdated?.(a.route_id!));
+  };
+
   const handleSend = async () => {
     const text = input.trim();
     if (!text || loading) return;
 
     setInput('');
     setError('');
+    const files = attachments;
+    setAttachments([]);
 
     const userMsg: DisplayMessage = {
       id: crypto.randomUUID(),
@@ -172,6 +234,15 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
     setMessages((prev) => [...prev, userMsg]);
     setLoading(true);
 
+    let attachmentKeys: string[] = [];
+    try {
+      attachmentKeys = await Promise.all(files.map((f) => chatApi.uploadAttachment(f)));
+    } catch {
+      setError(t('chat.attachmentFailed'));
+      setLoading(false);
+      return;
+    }
+
     // Try streaming first, fallback to non-streaming
     try {
       const streamingMsgId = crypto.randomUUID();
@@ -197,6 +268,7 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
         },
         (actions) => {
           streamingActions = actions;
+          notifyRouteUpdates(actions);
           setMessages((prev) =>
             prev.map((m) =>
               m.id === streamingMsgId ? { ...m, actions } : m,
@@ -218,6 +290,16 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
           // Remove the placeholder message on error
           setMessages((prev) => prev.filter((m) => m.id !== streamingMsgId));
         },
+        (notice) => {
+          setMessages((prev) =>
+            prev.map((m) =>
+              m.id === streamingMsgId ? { ...m, notice } : m,
+            ),
+          );
+        },
+        routeId,
+        attachmentKeys,
+        setToolProgress,
       );
     } catch (err: any) {
       // Remove streaming placeholder if it exists
@@ -231,13 +313,15 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
       } else {
         // Fallback to non-streaming
         try {
-          const response = await chatApi.sendMessage(text, conversationId);
+          const response = await chatApi.sendMessage(text, conversationId, routeId, attachmentKeys);
           setConversationId(response.conversation_id);
+          notifyRouteUpdates(response.actions);
           const assistantMsg: DisplayMessage = {
             id: response.id,
             role: 'assistant',
             content: response.message,
             actions: response.actions,
+            notice: response.notice,
           };
           setMessages((prev) => [...prev, assistantMsg]);
         } catch (fallbackErr: any) {
@@ -253,6 +337,7 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
       }
     } finally {
       setLoading(false);
+      setToolProgress(null);
     }
   };
 
@@ -288,6 +373,28 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
     }
   }, []);
 
+  const handleChatLanguageChange = async (value: string) => {
+    const language = value ? (value as ChatLanguage) : null;
+    try {
+      const prefs = await chatApi.updatePreferences({ language });
+      setChatLanguage(prefs.language);
+    } catch {
+      setError(t('chat.error'));
+    }
+  };
+
+  const handleShareConversation = async () => {
+    if (!conversationId) return;
+    try {
+      const token = await chatApi.shareConversation(conversationId);
+      await navigator.clipboard.writeText(`${window.location.origin}/shared/chat/${token}`);
+      setShareCopied(true);
+      setTimeout(() => setShareCopied(false), 2000);
+    } catch {
+      setError(t('chat.shareFailed'));
+    }
+  };
+
   const handleDeleteMessage = useCallback(async (msgId: string) => {
     if (!conversationId) return;
     try {
@@ -298,6 +405,26 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
     }
   }, [conversationId]);
 
+  const handleConfirmAction = useCallback(async (actionId: string) => {
+    setError('');
+    try {
+      const response = await chatApi.confirmAction(actionId);
+      // The confirmation is single-use, so drop its button.
+      setMessages((prev) => [
+        ...prev.map((m) => ({
+          ...m,
+          actions: m.actions?.filter((a) => a.action_id !== actionId),
+        })),
+        { id: response.id, role: 'assistant', content: response.message, actions: response.actions },
+      ]);
+      response.actions
+        .filter((a) => a.type === 'route_updated' && a.route_id)
+        .forEach((a) => onRouteUpdated?.(a.route_id!));
+    } catch {
+      setError(t('chat.confirmFailed'));
+    }
+  }, [onRouteUpdated, t]);
+
   const handleNavigate = useCallback((path: string) => {
     navigate(path);
     onClose();
@@ -308,7 +435,7 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
     setLoadingHistory(true);
     try {
       const response = await chatApi.listConversations();
-      setConversations(response.conversations);
+      setConversations(response.data);
     } catch {
       setConversations([]);
     } finally {
@@ -337,6 +464,25 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
     }
   };
 
+  const handleTogglePin = async (conv: ConversationSummary) => {
+    try {
+      await chatApi.updateConversation(conv.conversation_id, { pinned: !conv.pinned });
+      const response = await chatApi.listConversations();
+      setConversations(response.data);
+    } catch {
+      // ignore
+    }
+  };
+
+  const handleArchiveConversation = async (convId: string) => {
+    try {
+      await chatApi.updateConversation(convId, { archived: true });
+      setConversations((prev) => prev.filter((c) => c.conversation_id !== convId));
+    } catch {
+      // ignore
+    }
+  };
+
   const handleDeleteConversation = async (convId: string) => {
     try {
       await chatApi.deleteConversation(convId);
@@ -385,6 +531,27 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
                   {conv.message_count} msg &middot; {formatTimestamp(conv.updated_at)}
                 </div>
               </div>
+              <button
+                className="chat-conversation-delete"
+                aria-label={conv.pinned ? t('chat.unpinConversation') : t('chat.pinConversation')}
+                aria-pressed={conv.pinned}
+                onClick={(e) => {
+                  e.stopPropagation();
+                  handleTogglePin(conv);
+                }}
+              >
+                {conv.pinned ? t('chat.unpinConversation') : t('chat.pinConversation')}
+              </button>
+              <button
+                className="chat-conversation-delete"
+                aria-label={t('chat.archiveConversation')}
+                onClick={(e) => {
+                  e.stopPropagation();
+                  handleArchiveConversation(conv.conversation_id);
+                }}
+              >
+                {t('chat.archiveConversation')}
+              </button>
               <button
                 className="chat-conversation-delete"
                 aria-label={t('chat.deleteConversation')}
@@ -407,7 +574,22 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
       <div className="chat-panel-header">
         <h3>{t('chat.title')}</h3>
         <div className="chat-header-actions">
+          <select
+            className="chat-language-select"
+            aria-label={t('chat.language')}
+            value={chatLanguage ?? ''}
+            onChange={(e) => handleChatLanguageChange(e.target.value)}
+          >
+            <option value="">{t('chat.languageAuto')}</option>
+            <option value="en">English</option>
+            <option value="ru">Русский</option>
+          </select>
           <button aria-label={t('chat.history')} onClick={handleShowHistory}>{t('chat.history')}</button>
+          {conversationId && (
+            <button aria-label={t('chat.share')} onClick={handleShareConversation}>
+              {shareCopied ? t('chat.shareLinkCopied') : t('chat.share')}
+            </button>
+          )}
           <button aria-label={t('chat.newConversation')} onClick={handleNewConversation}>{t('chat.newConversation')}</button>
           <button aria-label="Close" onClick={onClose}>{'\u2715'}</button>
         </div>
@@ -424,10 +606,12 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
             onShowPoints={handleShowPoints}
             onShowRoutes={handleShowRoutes}
             onNavigate={handleNavigate}
+            onConfirmAction={handleConfirmAction}
             tCopied={t('chat.copied')}
             tDeleteMessage={t('chat.deleteMessage')}
             tShowOnMap={t('chat.showOnMap')}
             tGoTo={t('chat.goTo')}
+            tConfirm={t('chat.confirmAction')}
           />
         ))}
         {loading && (
@@ -435,13 +619,53 @@ export function ChatPanel({ isOpen, onClose, onShowPoints, onShowRoutes }: ChatP
             <span className="chat-typing-dot" />
             <span className="chat-typing-dot" />
             <span className="chat-typing-dot" />
+            {toolProgress && (
+              <span className="chat-typing-label">
+                {t(TOOL_LABELS[toolProgress] ?? 'chat.tool.default')}
+              </span>
+            )}
           </div>
         )}
         {error && <div className="chat-error">{error}</div>}
         <div ref={messagesEndRef} />
       </div>
 
+      {attachments.length > 0 && (
+        <div className="chat-attachments">
+          {attachments.map((file, idx) => (
+            <span key={idx} className="chat-attachment-chip">
+              {file.name}
+              <button
+                aria-label={t('chat.removeAttachment')}
+                onClick={() => setAttachments((prev) => prev.filter((_, i) => i !== idx))}
+              >
+                ×
+              </button>
+            </span>
+          ))}
+        </div>
+      )}
       <div className="chat-input-area">
+        <input
+          ref={fileInputRef}
+          type="file"
+          accept="image/jpeg,image/png,image/webp,image/gif"
+          hidden
+          onChange={(e) => {
+            const file = e.target.files?.[0];
+            if (file) setAttachments((prev) => [...prev, file].slice(0, MAX_ATTACHMENTS));
+            e.target.value = '';
+          }}
+        />
+        <button
+          className="chat-attach-btn"
+          aria-label={t('chat.attachImage')}
+          title={t('chat.attachImage')}
+          onClick={() => fileInputRef.current?.click()}
+          disabled={loading || attachments.length >= MAX_ATTACHMENTS}
+        >
+          📎
+        </button>
         <textarea
           ref={textareaRef}
           value={input}
@@ -1,8 +1,8 @@
-import { useState, useEffect } from 'react';
+import { useState, useEffect, useRef } from 'react';
 import { useAuth } from '../context/AuthContext';
 import { useLanguage } from '../context/LanguageContext';
 import { routesApi } from '../api/routes';
-import type { Comment } from '../api/routes';
+import type { Comment, CommentPolicy } from '../api/routes';
 import { ConfirmDialog } from './ConfirmDialog';
 
 interface CommentSectionProps {
@@ -20,16 +20,36 @@ export function CommentSection({ routeId, routeOwnerId }: CommentSectionProps) {
   const [submitting, setSubmitting] = useState(false);
   const [error, setError] = useState('');
   const [confirmDeleteCommentId, setConfirmDeleteCommentId] = useState<string | null>(null);
+  const [policy, setPolicy] = useState<CommentPolicy>('open');
+  const [pending, setPending] = useState<Comment[]>([]);
+  const [awaitingApproval, setAwaitingApproval] = useState(false);
+  const [confirmBlockUserId, setConfirmBlockUserId] = useState<string | null>(null);
+  // When the user started typing; the server uses it to spot bot submissions
+  const typingStartedAt = useRef<number | null>(null);
+
+  const isOwner = !!user && !!routeOwnerId && user.id === routeOwnerId;
 
   useEffect(() => {
     loadComments();
   }, [routeId]);
 
+  useEffect(() => {
+    if (isOwner) {
+      routesApi.getPendingComments(routeId).then(setPending).catch((err) => {
+        console.error('Failed to load pending comments:', err);
+      });
+    }
+  }, [routeId, isOwner]);
+
   const loadComments = async () => {
     setLoading(true);
     try {
-      const data = await routesApi.getComments(routeId);
+      const [data, currentPolicy] = await Promise.all([
+        routesApi.getComments(routeId),
+        routesApi.getCommentPolicy(routeId),
+      ]);
       setComments(data);
+      setPolicy(currentPolicy);
     } catch (err) {
       console.error('Failed to load comments:', err);
     } finally {
@@ -37,6 +57,25 @@ export function CommentSection({ routeId, routeOwnerId }: CommentSectionProps) {
     }
   };
 
+  const handlePolicyChange = async (next: CommentPolicy) => {
+    try {
+      await routesApi.setCommentPolicy(routeId, next);
+      setPolicy(next);
+    } catch (err) {
+      console.error('Failed to update comment policy:', err);
+    }
+  };
+
+  const handleApprove = async (commentId: string) => {
+    try {
+      const comment = await routesApi.approveComment(commentId);
+      setPending((prev) => prev.filter((c) => c.id !== commentId));
+      setComments((prev) => [...prev, comment]);
+    } catch (err) {
+      console.error('Failed to approve comment:', err);
+    }
+  };
+
   const handleSubmit = async () => {
     if (!text.trim() || !user) return;
 
@@ -47,9 +86,14 @@ export function CommentSection({ routeId, routeOwnerId }: CommentSectionProps) {
       const comment = await routesApi.createComment(routeId, {
         text: text.trim(),
         author_name: authorName,
+        form_elapsed_ms: typingStartedAt.current ? Date.now() - typingStartedAt.current : undefined,
       });
-      setComments((prev) => [...prev, comment]);
+      if (comment.approved) {
+        setComments((prev) => [...prev, comment]);
+      }
+      setAwaitingApproval(!comment.approved);
       setText('');
+      typingStartedAt.current = null;
     } catch (err) {
       console.error('Failed to create comment:', err);
       setError(t('comments.submitFailed'));
@@ -69,11 +113,25 @@ export function CommentSection({ routeId, routeOwnerId }: CommentSectionProps) {
     try {
       await routesApi.deleteComment(commentId);
       setComments((prev) => prev.filter((c) => c.id !== commentId));
+      setPending((prev) => prev.filter((c) => c.id !== commentId));
     } catch (err) {
       console.error('Failed to delete comment:', err);
     }
   };
 
+  const handleConfirmBlock = async () => {
+    if (!confirmBlockUserId) return;
+    const userId = confirmBlockUserId;
+    setConfirmBlockUserId(null);
+    try {
+      await routesApi.blockUser(userId);
+      setComments((prev) => prev.filter((c) => c.user_id !== userId));
+      setPending((prev) => prev.filter((c) => c.user_id !== userId));
+    } catch (err) {
+      console.error('Failed to block user:', err);
+    }
+  };
+
   const canDelete = (comment: Comment) => {
     if (!user) return false;
     return comment.user_id === user.id || (routeOwnerId && user.id === routeOwnerId);
@@ -101,8 +159,41 @@ export function CommentSection({ routeId, routeOwnerId }: CommentSectionProps) {
     <div className="comment-section">
       <div className="comment-section-header">
         <h3>{t('comments.title')} ({comments.length})</h3>
+        {isOwner && (
+          <select
+            className="comment-policy-select"
+            value={policy}
+            onChange={(e) => handlePolicyChange(e.target.value as CommentPolicy)}
+            title={t('comments.policy')}
+          >
+            <option value="open">{t('comments.policy.open')}</option>
+            <option value="pre_moderated">{t('comments.policy.pre_moderated')}</option>
+            <option value="locked">{t('comments.policy.locked')}</option>
+          </select>
+        )}
       </div>
 
+      {isOwner && pending.length > 0 && (
+        <div className="comment-pending">
+          <div className="comment-pending-title">{t('comments.pending', { count: pending.length })}</div>
+          {pending.map((comment) => (
+            <div key={comment.id} className="comment-item">
+              <div className="comment-meta">
+                <span className="comment-author">{comment.author_name}</span>
+                <span className="comment-date">{formatDate(comment.created_at)}</span>
+              </div>
+              <div className="comment-text">{comment.text}</div>
+              <button className="comment-approve-btn" onClick={() => handleApprove(comment.id)}>
+                {t('comments.approve')}
+              </button>
+              <button className="comment-delete-btn" onClick={() => handleDelete(comment.id)}>
+                {t('comments.delete')}
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
       <div className="comment-list">
         {loading && <div className="comment-loading">{t('common.loading')}</div>}
 
@@ -125,21 +216,35 @@ export function CommentSection({ routeId, routeOwnerId }: CommentSectionProps) {
                 {t('comments.delete')}
               </button>
             )}
+            {isOwner && comment.user_id !== user?.id && (
+              <button
+                className="comment-delete-btn"
+                onClick={() => setConfirmBlockUserId(comment.user_id)}
+              >
+                {t('comments.blockAuthor')}
+              </button>
+            )}
           </div>
         ))}
       </div>
 
-      {isAuthenticated ? (
+      {policy === 'locked' && !isOwner ? (
+        <div className="comment-login-hint">{t('comments.locked')}</div>
+      ) : isAuthenticated ? (
         <div className="comment-form">
           <textarea
             value={text}
-            onChange={(e) => setText(e.target.value)}
+            onChange={(e) => {
+              typingStartedAt.current ??= Date.now();
+              setText(e.target.value);
+            }}
             onKeyDown={handleKeyDown}
             placeholder={t('comments.placeholder')}
             maxLength={2000}
             rows={2}
           />
           {error && <div className="comment-error">{error}</div>}
+          {awaitingApproval && <div className="comment-login-hint">{t('comments.awaitingApproval')}</div>}
           <button
             onClick={handleSubmit}
             disabled={submitting || !text.trim()}
@@ -162,6 +267,16 @@ export function CommentSection({ routeId, routeOwnerId }: CommentSectionProps) {
         onCancel={() => setConfirmDeleteCommentId(null)}
       />
     )}
+
+    {confirmBlockUserId && (
+      <ConfirmDialog
+        message={t('comments.confirmBlock')}
+        confirmLabel={t('comments.blockAuthor')}
+        cancelLabel={t('map.cancel')}
+        onConfirm={handleConfirmBlock}
+        onCancel={() => setConfirmBlockUserId(null)}
+      />
+    )}
     </>
   );
 }
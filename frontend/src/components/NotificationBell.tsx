@@ -27,8 +27,8 @@ export function NotificationBell() {
     setLoading(true);
     try {
       const data = await notificationsApi.list({ limit: 20 });
-      setNotifications(data.notifications);
-      setUnreadCount(data.unread_count);
+      setNotifications(data.data);
+      setUnreadCount(data.meta?.unread_count ?? 0);
     } catch (err) {
       console.error('Failed to fetch notifications:', err);
     } finally {
@@ -1,4 +1,5 @@
 import { createContext, useContext, useState, useEffect, useCallback } from 'react';
+import axios from 'axios';
 import { t as translate, dateLocales, LANGUAGE_STORAGE_KEY } from '../i18n';
 import type { Locale, TranslationKey } from '../i18n';
 
@@ -22,11 +23,21 @@ function getInitialLocale(): Locale {
   return 'en';
 }
 
+// Server-generated texts (notifications, category names) follow the UI language
+function applyRequestLocale(locale: Locale) {
+  axios.defaults.headers.common['Accept-Language'] = locale;
+}
+
 export function LanguageProvider({ children }: { children: React.ReactNode }) {
-  const [locale, setLocaleState] = useState<Locale>(getInitialLocale);
+  const [locale, setLocaleState] = useState<Locale>(() => {
+    const initial = getInitialLocale();
+    applyRequestLocale(initial);
+    return initial;
+  });
 
   const setLocale = useCallback((newLocale: Locale) => {
     setLocaleState(newLocale);
+    applyRequestLocale(newLocale);
     localStorage.setItem(LANGUAGE_STORAGE_KEY, newLocale);
     console.log(`[i18n] locale changed to: ${newLocale}`);
   }, []);
@@ -58,6 +58,9 @@ export function usePhotoNotifications({ routeId, enabled, onPhotoUpdate }: Photo
           if (data.type === 'photo_update' && data.points) {
             console.log('[ws] photo update received, updating points');
             onPhotoUpdateRef.current(data.points);
+          } else if (data.type === 'points_geocoded' && data.points) {
+            console.log('[ws] point names resolved, updating points');
+            onPhotoUpdateRef.current(data.points);
           }
         } catch (e) {
           console.error('[ws] failed to parse message:', e);
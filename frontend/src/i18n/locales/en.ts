@@ -34,6 +34,12 @@ export const en = {
   "map.selectCategories": "Select categories",
   "map.clearAllPoints": "Clear all points?",
   "map.importPhotos": "Import photos",
+  "map.retryPhotos": "Retry failed photos",
+  "map.retryPhotosQueued": "Failed photos queued for processing",
+  "map.retryPhotosFailed": "Could not retry photos",
+  "map.setCover": "Use as route cover",
+  "map.coverSet": "Route cover updated",
+  "map.coverFailed": "Could not update the route cover",
   "map.photosImported": "{{added}} photos placed on map",
   "map.photosSkipped": "{{skipped}} photos skipped (no GPS data)",
   "map.noGpsPhotos": "None of the selected photos contain GPS data",
@@ -64,10 +70,16 @@ export const en = {
   "profile.passwordChangeFailed": "Failed to change password",
   "profile.mySavedRoutes": "My Saved Routes",
   "profile.importing": "Importing...",
-  "profile.importGeoJson": "Import GeoJSON",
+  "profile.importGeoJson": "Import GeoJSON / GPX",
+  "profile.importBatchSummary": "Imported {{created}} routes, {{failed}} failed",
+  "profile.importUnnamedFile": "Unnamed file",
+  "profile.importFromUrl": "Import from link",
+  "profile.importUrlPlaceholder": "Link to a GeoJSON or GPX file",
   "profile.loadingRoutes": "Loading routes...",
   "profile.loadRoutesFailed": "Failed to load routes",
   "profile.noRoutes": "You haven't saved any routes yet.",
+  "profile.searchRoutes": "Search",
+  "profile.searchRoutesPlaceholder": "Search my routes by name",
   "profile.createRoute": "Create a Route",
   "profile.pointsCount": "{{count}} points",
   "profile.created": "Created:",
@@ -75,7 +87,9 @@ export const en = {
   "profile.delete": "Delete",
   "profile.confirmDelete": "Are you sure you want to delete this route?",
   "profile.deleteFailed": "Failed to delete route",
-  "profile.importFailed": "Failed to import route from GeoJSON",
+  "profile.importFailed": "Failed to import route from file",
+  "profile.importDuplicate": "You already have a nearly identical route. Import it anyway?",
+  "profile.importAnyway": "Import anyway",
   "profile.language": "Language",
   "profile.showSelected": "Show {{count}} on map",
   "profile.share": "Share",
@@ -106,6 +120,16 @@ export const en = {
   "comments.confirmDelete": "Delete this comment?",
   "comments.loginToComment": "Log in to leave a comment",
   "comments.count": "{{count}} comments",
+  "comments.policy": "Who can comment",
+  "comments.policy.open": "Everyone",
+  "comments.policy.pre_moderated": "After my approval",
+  "comments.policy.locked": "Nobody",
+  "comments.locked": "Comments are closed for this route",
+  "comments.awaitingApproval": "Your comment will appear once the route owner approves it",
+  "comments.pending": "Awaiting approval ({{count}})",
+  "comments.approve": "Approve",
+  "comments.blockAuthor": "Block author",
+  "comments.confirmBlock": "Block this user? Their comments will be hidden on your routes and they won't be able to comment, like or rate them.",
   "likes.count": "{{count}} likes",
   "likes.like": "Like",
   "likes.liked": "Liked",
@@ -133,6 +157,9 @@ export const en = {
   "explore.loadFailed": "Failed to load routes",
   "explore.allCategories": "All categories",
   "explore.catalog": "Catalog",
+  "explore.saveSearch": "Notify me about new routes",
+  "explore.searchSaved": "Search saved. You will be notified when new matching routes are shared.",
+  "explore.saveSearchFailed": "Failed to save search",
   "tags.hiking": "Hiking",
   "tags.cycling": "Cycling",
   "tags.historical": "Historical",
@@ -164,6 +191,13 @@ export const en = {
   "admin.stats.totalUsers": "Total Users",
   "admin.stats.totalRoutes": "Total Routes",
   "admin.stats.totalComments": "Total Comments",
+  "admin.chat.title": "Assistant, last {{days}} days",
+  "admin.chat.messages": "Messages",
+  "admin.chat.turns": "Replies",
+  "admin.chat.avgLatency": "Average reply time",
+  "admin.chat.errorRate": "Error rate",
+  "admin.chat.toolCalls": "Tool calls",
+  "admin.chat.topUsers": "Most active users",
   "admin.roles.user": "User",
   "admin.roles.moderator": "Moderator",
   "admin.roles.admin": "Admin",
@@ -186,6 +220,22 @@ export const en = {
   "admin.settings.saving": "Saving...",
   "admin.settings.saved": "Settings saved successfully",
   "admin.settings.saveFailed": "Failed to save settings",
+  "admin.settings.chatPrompt": "Assistant System Prompt",
+  "admin.settings.chatPromptHint": "Available variables:",
+  "admin.settings.chatPromptReset": "Restore default",
+  "admin.settings.photoPipeline": "Photo Processing",
+  "admin.settings.photoMaxWidth": "Max photo width (px)",
+  "admin.settings.photoQuality": "Photo quality (1-100)",
+  "admin.settings.thumbnailWidth": "Thumbnail width (px)",
+  "admin.settings.thumbnailQuality": "Thumbnail quality (1-100)",
+  "admin.settings.photoFormat": "Output format",
+  "admin.settings.photoFormatWebp": "WebP (lossless)",
+  "admin.settings.contentFilter": "Blocked Terms",
+  "admin.settings.contentFilterMode": "When a route name, comment or chat message contains a blocked term",
+  "admin.settings.contentFilterReject": "Reject it",
+  "admin.settings.contentFilterMask": "Replace the term with asterisks",
+  "admin.settings.contentFilterFlag": "Publish it and send it to moderation",
+  "admin.settings.contentFilterTerms": "Blocked terms, one per line (whole words, case-insensitive)",
   "admin.routes": "Routes",
   "admin.routes.name": "Name",
   "admin.routes.points": "Points",
@@ -248,10 +298,30 @@ export const en = {
   "chat.newConversation": "New chat",
   "chat.history": "History",
   "chat.deleteConversation": "Delete",
+  "chat.pinConversation": "Pin",
+  "chat.unpinConversation": "Unpin",
+  "chat.archiveConversation": "Archive",
+  "chat.share": "Share",
+  "chat.language": "Assistant language",
+  "chat.languageAuto": "Auto",
+  "chat.tool.geocode": "Looking up the place…",
+  "chat.tool.searchRoutes": "Searching routes…",
+  "chat.tool.routeDetails": "Loading the route…",
+  "chat.tool.navigate": "Preparing navigation…",
+  "chat.tool.default": "Working on it…",
+  "chat.shareLinkCopied": "Link copied!",
+  "chat.shareFailed": "Could not create a share link",
+  "chat.sharedTitle": "Shared conversation",
+  "chat.sharedNotFound": "This conversation is no longer shared",
   "chat.noConversations": "No conversations yet",
   "chat.copied": "Copied!",
   "chat.backToChat": "Back",
   "chat.rateLimited": "Too many messages. Please wait.",
+  "chat.confirmAction": "Confirm",
+  "chat.confirmFailed": "Could not apply the change. It may have expired.",
+  "chat.attachImage": "Attach photo",
+  "chat.removeAttachment": "Remove attachment",
+  "chat.attachmentFailed": "Failed to upload the photo",
   "chat.deleteMessage": "Delete",
   "chat.goTo": "Go to",
   "bookmarks.title": "Bookmarks",
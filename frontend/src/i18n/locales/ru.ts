@@ -34,6 +34,12 @@ export const ru = {
   "map.selectCategories": "Выберите категории",
   "map.clearAllPoints": "Очистить все точки?",
   "map.importPhotos": "Импорт фото",
+  "map.retryPhotos": "Повторить обработку фото",
+  "map.retryPhotosQueued": "Фото снова поставлены в обработку",
+  "map.retryPhotosFailed": "Не удалось повторить обработку фото",
+  "map.setCover": "Сделать обложкой маршрута",
+  "map.coverSet": "Обложка маршрута обновлена",
+  "map.coverFailed": "Не удалось обновить обложку маршрута",
   "map.photosImported": "{{added}} фото размещено на карте",
   "map.photosSkipped": "{{skipped}} фото пропущено (нет GPS-данных)",
   "map.noGpsPhotos": "Ни одно из выбранных фото не содержит GPS-данных",
@@ -64,10 +70,16 @@ export const ru = {
   "profile.passwordChangeFailed": "Не удалось изменить пароль",
   "profile.mySavedRoutes": "Мои сохранённые маршруты",
   "profile.importing": "Импорт...",
-  "profile.importGeoJson": "Импорт GeoJSON",
+  "profile.importGeoJson": "Импорт GeoJSON / GPX",
+  "profile.importBatchSummary": "Импортировано маршрутов: {{created}}, с ошибками: {{failed}}",
+  "profile.importUnnamedFile": "Файл без имени",
+  "profile.importFromUrl": "Импорт по ссылке",
+  "profile.importUrlPlaceholder": "Ссылка на файл GeoJSON или GPX",
   "profile.loadingRoutes": "Загрузка маршрутов...",
   "profile.loadRoutesFailed": "Не удалось загрузить маршруты",
   "profile.noRoutes": "У вас пока нет сохранённых маршрутов.",
+  "profile.searchRoutes": "Найти",
+  "profile.searchRoutesPlaceholder": "Поиск по названию маршрута",
   "profile.createRoute": "Создать маршрут",
   "profile.pointsCount": "{{count}} точек",
   "profile.created": "Создан:",
@@ -75,7 +87,9 @@ export const ru = {
   "profile.delete": "Удалить",
   "profile.confirmDelete": "Вы уверены, что хотите удалить этот маршрут?",
   "profile.deleteFailed": "Не удалось удалить маршрут",
-  "profile.importFailed": "Не удалось импортировать маршрут из GeoJSON",
+  "profile.importFailed": "Не удалось импортировать маршрут из файла",
+  "profile.importDuplicate": "У вас уже есть почти такой же маршрут. Всё равно импортировать?",
+  "profile.importAnyway": "Импортировать",
   "profile.language": "Язык",
   "profile.showSelected": "Показать {{count}} на карте",
   "profile.share": "Поделиться",
@@ -106,6 +120,16 @@ export const ru = {
   "comments.confirmDelete": "Удалить этот комментарий?",
   "comments.loginToComment": "Войдите, чтобы оставить комментарий",
   "comments.count": "{{count}} комментариев",
+  "comments.policy": "Кто может комментировать",
+  "comments.policy.open": "Все",
+  "comments.policy.pre_moderated": "После моего одобрения",
+  "comments.policy.locked": "Никто",
+  "comments.locked": "Комментарии к этому маршруту закрыты",
+  "comments.awaitingApproval": "Комментарий появится после одобрения автором маршрута",
+  "comments.pending": "Ожидают одобрения ({{count}})",
+  "comments.approve": "Одобрить",
+  "comments.blockAuthor": "Заблокировать автора",
+  "comments.confirmBlock": "Заблокировать пользователя? Его комментарии будут скрыты на ваших маршрутах, и он не сможет комментировать, лайкать и оценивать их.",
   "likes.count": "{{count}} лайков",
   "likes.like": "Нравится",
   "likes.liked": "Нравится",
@@ -131,6 +155,9 @@ export const ru = {
   "explore.noRoutes": "Маршруты не найдены",
   "explore.loadMore": "Загрузить ещё",
   "explore.loadFailed": "Не удалось загрузить маршруты",
+  "explore.saveSearch": "Сообщать о новых маршрутах",
+  "explore.searchSaved": "Поиск сохранён. Мы сообщим, когда появятся новые подходящие маршруты.",
+  "explore.saveSearchFailed": "Не удалось сохранить поиск",
   "explore.allCategories": "Все категории",
   "explore.catalog": "Каталог",
   "tags.hiking": "Пеший",
@@ -164,6 +191,13 @@ export const ru = {
   "admin.stats.totalUsers": "Всего пользователей",
   "admin.stats.totalRoutes": "Всего маршрутов",
   "admin.stats.totalComments": "Всего комментариев",
+  "admin.chat.title": "Ассистент за {{days}} дн.",
+  "admin.chat.messages": "Сообщения",
+  "admin.chat.turns": "Ответы",
+  "admin.chat.avgLatency": "Среднее время ответа",
+  "admin.chat.errorRate": "Доля ошибок",
+  "admin.chat.toolCalls": "Вызовы инструментов",
+  "admin.chat.topUsers": "Самые активные пользователи",
   "admin.roles.user": "Пользователь",
   "admin.roles.moderator": "Модератор",
   "admin.roles.admin": "Администратор",
@@ -186,6 +220,22 @@ export const ru = {
   "admin.settings.saving": "Сохранение...",
   "admin.settings.saved": "Настройки сохранены",
   "admin.settings.saveFailed": "Не удалось сохранить настройки",
+  "admin.settings.chatPrompt": "Системный промпт ассистента",
+  "admin.settings.chatPromptHint": "Доступные переменные:",
+  "admin.settings.chatPromptReset": "Вернуть по умолчанию",
+  "admin.settings.photoPipeline": "Обработка фото",
+  "admin.settings.photoMaxWidth": "Макс. ширина фото (px)",
+  "admin.settings.photoQuality": "Качество фото (1-100)",
+  "admin.settings.thumbnailWidth": "Ширина миниатюры (px)",
+  "admin.settings.thumbnailQuality": "Качество миниатюры (1-100)",
+  "admin.settings.photoFormat": "Формат вывода",
+  "admin.settings.photoFormatWebp": "WebP (без потерь)",
+  "admin.settings.contentFilter": "Запрещённые слова",
+  "admin.settings.contentFilterMode": "Если название маршрута, комментарий или сообщение в чате содержит запрещённое слово",
+  "admin.settings.contentFilterReject": "Отклонить",
+  "admin.settings.contentFilterMask": "Заменить слово звёздочками",
+  "admin.settings.contentFilterFlag": "Опубликовать и отправить на модерацию",
+  "admin.settings.contentFilterTerms": "Запрещённые слова, по одному в строке (целые слова, без учёта регистра)",
   "admin.routes": "Маршруты",
   "admin.routes.name": "Название",
   "admin.routes.points": "Точки",
@@ -248,10 +298,30 @@ export const ru = {
   "chat.newConversation": "Новый чат",
   "chat.history": "История",
   "chat.deleteConversation": "Удалить",
+  "chat.pinConversation": "Закрепить",
+  "chat.unpinConversation": "Открепить",
+  "chat.archiveConversation": "В архив",
+  "chat.share": "Поделиться",
+  "chat.language": "Язык ассистента",
+  "chat.languageAuto": "Авто",
+  "chat.tool.geocode": "Ищу место…",
+  "chat.tool.searchRoutes": "Ищу маршруты…",
+  "chat.tool.routeDetails": "Загружаю маршрут…",
+  "chat.tool.navigate": "Готовлю навигацию…",
+  "chat.tool.default": "Работаю над ответом…",
+  "chat.shareLinkCopied": "Ссылка скопирована!",
+  "chat.shareFailed": "Не удалось создать ссылку",
+  "chat.sharedTitle": "Общий диалог",
+  "chat.sharedNotFound": "Доступ к этому диалогу закрыт",
   "chat.noConversations": "Нет диалогов",
   "chat.copied": "Скопировано!",
   "chat.backToChat": "Назад",
   "chat.rateLimited": "Слишком много сообщений. Подождите.",
+  "chat.confirmAction": "Подтвердить",
+  "chat.confirmFailed": "Не удалось применить изменение. Возможно, оно устарело.",
+  "chat.attachImage": "Прикрепить фото",
+  "chat.removeAttachment": "Убрать вложение",
+  "chat.attachmentFailed": "Не удалось загрузить фото",
   "chat.deleteMessage": "Удалить",
   "chat.goTo": "Перейти",
   "bookmarks.title": "Закладки",
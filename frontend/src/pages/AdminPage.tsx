@@ -5,12 +5,12 @@ import { useNavigate } from 'react-router-dom';
 import { useLanguage } from '../context/LanguageContext';
 import { useTheme } from '../context/ThemeContext';
 import { adminApi } from '../api/admin';
-import type { AdminUser, AuthStatsResponse, RoutesStatsResponse, AdminRoute, AdminComment } from '../api/admin';
+import type { AdminUser, AuthStatsResponse, RoutesStatsResponse, AdminRoute, AdminComment, ChatOpsStats } from '../api/admin';
 import { routesApi } from '../api/routes';
 import { categoriesApi } from '../api/categories';
 import type { Category } from '../api/categories';
 import { settingsApi, DEFAULT_DIFFICULTY_THRESHOLDS } from '../api/settings';
-import type { DifficultyThresholds } from '../api/settings';
+import type { ChatPromptSettings, ContentFilterMode, DifficultyThresholds, PhotoPipelineSettings } from '../api/settings';
 import './AdminPage.css';
 
 type AdminTab = 'dashboard' | 'users' | 'routes' | 'comments' | 'categories' | 'settings';
@@ -26,6 +26,7 @@ export default function AdminPage() {
   // Dashboard state
   const [authStats, setAuthStats] = useState<AuthStatsResponse | null>(null);
   const [routesStats, setRoutesStats] = useState<RoutesStatsResponse | null>(null);
+  const [chatStats, setChatStats] = useState<ChatOpsStats | null>(null);
   const [statsLoading, setStatsLoading] = useState(false);
   const [statsError, setStatsError] = useState('');
 
@@ -67,17 +68,27 @@ export default function AdminPage() {
   const [settingsSaving, setSettingsSaving] = useState(false);
   const [settingsError, setSettingsError] = useState('');
   const [settingsSuccess, setSettingsSuccess] = useState('');
+  const [chatPrompt, setChatPrompt] = useState<ChatPromptSettings | null>(null);
+  const [chatPromptSaving, setChatPromptSaving] = useState(false);
+  const [photoPipeline, setPhotoPipeline] = useState<PhotoPipelineSettings | null>(null);
+  const [photoPipelineSaving, setPhotoPipelineSaving] = useState(false);
+  const [filterMode, setFilterMode] = useState<ContentFilterMode>('mask');
+  // One blocked term per line
+  const [filterTerms, setFilterTerms] = useState('');
+  const [filterSaving, setFilterSaving] = useState(false);
 
   const loadStats = useCallback(async () => {
     setStatsLoading(true);
     setStatsError('');
     try {
-      const [auth, routes] = await Promise.all([
+      const [auth, routes, chat] = await Promise.all([
         adminApi.getAuthStats(),
         adminApi.getRoutesStats(),
+        adminApi.getChatStats(),
       ]);
       setAuthStats(auth);
       setRoutesStats(routes);
+      setChatStats(chat);
     } catch (err: any) {
       console.error('Failed to load admin stats:', err);
       setStatsError(err.response?.data || t('admin.loadFailed'));
@@ -95,8 +106,8 @@ export default function AdminPage() {
         offset: usersPage * PAGE_SIZE,
         search: usersSearch || undefined,
       });
-      setUsers(data.users);
-      setUsersTotal(data.total);
+      setUsers(data.data);
+      setUsersTotal(data.pagination.total);
     } catch (err: any) {
       console.error('Failed to load users:', err);
       setUsersError(err.response?.data || t('admin.loadFailed'));
@@ -113,8 +124,8 @@ export default function AdminPage() {
         limit: PAGE_SIZE,
         offset: routesPage * PAGE_SIZE,
       });
-      setAdminRoutes(data.routes);
-      setRoutesTotal(data.total);
+      setAdminRoutes(data.data);
+      setRoutesTotal(data.pagination.total);
     } catch (err: any) {
       console.error('Failed to load admin routes:', err);
       setRoutesError(err.response?.data || t('admin.loadFailed'));
@@ -131,8 +142,8 @@ export default function AdminPage() {
         limit: PAGE_SIZE,
         offset: commentsPage * PAGE_SIZE,
       });
-      setAdminComments(data.comments);
-      setCommentsTotal(data.total);
+      setAdminComments(data.data);
+      setCommentsTotal(data.pagination.total);
     } catch (err: any) {
       console.error('Failed to load admin comments:', err);
       setCommentsError(err.response?.data || t('admin.loadFailed'));
@@ -160,8 +171,17 @@ export default function AdminPage() {
     setSettingsError('');
     setSettingsSuccess('');
     try {
-      const data = await settingsApi.getDifficultyThresholds();
+      const [data, prompt, pipeline, filter] = await Promise.all([
+        settingsApi.getDifficultyThresholds(),
+        settingsApi.getChatPrompt(),
+        settingsApi.getPhotoPipeline(),
+        settingsApi.getContentFilter(),
+      ]);
       setThresholds(data);
+      setChatPrompt(prompt);
+      setPhotoPipeline(pipeline);
+      setFilterMode(filter.mode);
+      setFilterTerms(filter.terms.join('\n'));
     } catch (err: any) {
       console.error('Failed to load settings:', err);
       setSettingsError(err.response?.data || t('admin.loadFailed'));
@@ -304,6 +324,60 @@ export default function AdminPage() {
     }
   };
 
+  const handleSaveChatPrompt = async (template: string | null) => {
+    setChatPromptSaving(true);
+    setSettingsError('');
+    setSettingsSuccess('');
+    try {
+      setChatPrompt(await settingsApi.updateChatPrompt(template));
+      setSettingsSuccess(t('admin.settings.saved'));
+    } catch (err: any) {
+      console.error('Failed to save chat prompt:', err);
+      setSettingsError(err.response?.data || t('admin.settings.saveFailed'));
+    } finally {
+      setChatPromptSaving(false);
+    }
+  };
+
+  const handleSavePhotoPipeline = async () => {
+    if (!photoPipeline) return;
+    setPhotoPipelineSaving(true);
+    setSettingsError('');
+    setSettingsSuccess('');
+    try {
+      setPhotoPipeline(await settingsApi.updatePhotoPipeline(photoPipeline));
+      setSettingsSuccess(t('admin.settings.saved'));
+    } catch (err: any) {
+      console.error('Failed to save photo pipeline settings:', err);
+      setSettingsError(err.response?.data || t('admin.settings.saveFailed'));
+    } finally {
+      setPhotoPipelineSaving(false);
+    }
+  };
+
+  const handleSaveContentFilter = async () => {
+    setFilterSaving(true);
+    setSettingsError('');
+    setSettingsSuccess('');
+    try {
+      const terms = filterTerms.split('\n').map((term) => term.trim()).filter(Boolean);
+      const saved = await settingsApi.updateContentFilter({ mode: filterMode, terms });
+      setFilterMode(saved.mode);
+      setFilterTerms(saved.terms.join('\n'));
+      setSettingsSuccess(t('admin.settings.saved'));
+    } catch (err: any) {
+      console.error('Failed to save content filter:', err);
+      setSettingsError(err.response?.data || t('admin.settings.saveFailed'));
+    } finally {
+      setFilterSaving(false);
+    }
+  };
+
+  const handlePhotoPipelineChange = (key: Exclude<keyof PhotoPipelineSettings, 'format'>, value: string) => {
+    if (!photoPipeline) return;
+    setPhotoPipeline({ ...photoPipeline, [key]: parseInt(value, 10) || 0 });
+  };
+
   const usersTotalPages = Math.ceil(usersTotal / PAGE_SIZE);
   const routesTotalPages = Math.ceil(routesTotal / PAGE_SIZE);
   const commentsTotalPages = Math.ceil(commentsTotal / PAGE_SIZE);
@@ -386,6 +460,39 @@ export default function AdminPage() {
                   </div>
                 </div>
               )}
+              {!statsLoading && chatStats && (
+                <>
+                  <h3>{t('admin.chat.title', { days: chatStats.days })}</h3>
+                  <div className="stats-grid">
+                    <div className="stat-card">
+                      <p className="stat-value">{chatStats.messages_per_day.reduce((sum, d) => sum + d.count, 0)}</p>
+                      <p className="stat-label">{t('admin.chat.messages')}</p>
+                    </div>
+                    <div className="stat-card">
+                      <p className="stat-value">{chatStats.turns}</p>
+                      <p className="stat-label">{t('admin.chat.turns')}</p>
+                    </div>
+                    <div className="stat-card">
+                      <p className="stat-value">{Math.round(chatStats.avg_latency_ms)} ms</p>
+                      <p className="stat-label">{t('admin.chat.avgLatency')}</p>
+                    </div>
+                    <div className="stat-card">
+                      <p className="stat-value">{(chatStats.error_rate * 100).toFixed(1)}%</p>
+                      <p className="stat-label">{t('admin.chat.errorRate')}</p>
+                    </div>
+                  </div>
+                  {chatStats.tool_calls.length > 0 && (
+                    <p className="admin-chat-list">
+                      {t('admin.chat.toolCalls')}: {chatStats.tool_calls.map((c) => `${c.tool} (${c.count})`).join(', ')}
+                    </p>
+                  )}
+                  {chatStats.top_users.length > 0 && (
+                    <p className="admin-chat-list">
+                      {t('admin.chat.topUsers')}: {chatStats.top_users.map((u) => `${u.user_id.slice(0, 8)} (${u.messages})`).join(', ')}
+                    </p>
+                  )}
+                </>
+              )}
             </div>
           )}
 
@@ -768,6 +875,128 @@ export default function AdminPage() {
                   </button>
                 </div>
               )}
+
+              <h2 className="settings-section-title">{t('admin.settings.chatPrompt')}</h2>
+              {!settingsLoading && chatPrompt && (
+                <div className="settings-form">
+                  <div className="settings-field settings-field-wide">
+                    <label>
+                      {t('admin.settings.chatPromptHint')} {chatPrompt.variables.map((v) => `{${v}}`).join(', ')}
+                    </label>
+                    <textarea
+                      rows={16}
+                      value={chatPrompt.template}
+                      onChange={(e) => setChatPrompt({ ...chatPrompt, template: e.target.value })}
+                    />
+                  </div>
+                  <button
+                    className="btn-primary settings-save-btn"
+                    onClick={() => handleSaveChatPrompt(chatPrompt.template)}
+                    disabled={chatPromptSaving}
+                  >
+                    {chatPromptSaving ? t('admin.settings.saving') : t('admin.settings.save')}
+                  </button>
+                  {!chatPrompt.is_default && (
+                    <button
+                      className="btn-secondary-sm settings-save-btn"
+                      onClick={() => handleSaveChatPrompt(null)}
+                      disabled={chatPromptSaving}
+                    >
+                      {t('admin.settings.chatPromptReset')}
+                    </button>
+                  )}
+                </div>
+              )}
+
+              <h2 className="settings-section-title">{t('admin.settings.photoPipeline')}</h2>
+              {!settingsLoading && photoPipeline && (
+                <div className="settings-form">
+                  <div className="settings-field">
+                    <label>{t('admin.settings.photoMaxWidth')}</label>
+                    <input
+                      type="number"
+                      step="1"
+                      min="16"
+                      value={photoPipeline.max_width}
+                      onChange={(e) => handlePhotoPipelineChange('max_width', e.target.value)}
+                    />
+                  </div>
+                  <div className="settings-field">
+                    <label>{t('admin.settings.photoQuality')}</label>
+                    <input
+                      type="number"
+                      step="1"
+                      min="1"
+                      max="100"
+                      value={photoPipeline.quality}
+                      onChange={(e) => handlePhotoPipelineChange('quality', e.target.value)}
+                    />
+                  </div>
+                  <div className="settings-field">
+                    <label>{t('admin.settings.thumbnailWidth')}</label>
+                    <input
+                      type="number"
+                      step="1"
+                      min="16"
+                      value={photoPipeline.thumbnail_width}
+                      onChange={(e) => handlePhotoPipelineChange('thumbnail_width', e.target.value)}
+                    />
+                  </div>
+                  <div className="settings-field">
+                    <label>{t('admin.settings.thumbnailQuality')}</label>
+                    <input
+                      type="number"
+                      step="1"
+                      min="1"
+                      max="100"
+                      value={photoPipeline.thumbnail_quality}
+                      onChange={(e) => handlePhotoPipelineChange('thumbnail_quality', e.target.value)}
+                    />
+                  </div>
+                  <div className="settings-field">
+                    <label>{t('admin.settings.photoFormat')}</label>
+                    <select
+                      value={photoPipeline.format}
+                      onChange={(e) => setPhotoPipeline({ ...photoPipeline, format: e.target.value as PhotoPipelineSettings['format'] })}
+                    >
+                      <option value="jpeg">JPEG</option>
+                      <option value="webp">{t('admin.settings.photoFormatWebp')}</option>
+                    </select>
+                  </div>
+                  <button
+                    className="btn-primary settings-save-btn"
+                    onClick={handleSavePhotoPipeline}
+                    disabled={photoPipelineSaving}
+                  >
+                    {photoPipelineSaving ? t('admin.settings.saving') : t('admin.settings.save')}
+                  </button>
+                </div>
+              )}
+
+              <h2 className="settings-section-title">{t('admin.settings.contentFilter')}</h2>
+              {!settingsLoading && (
+                <div className="settings-form">
+                  <div className="settings-field">
+                    <label>{t('admin.settings.contentFilterMode')}</label>
+                    <select value={filterMode} onChange={(e) => setFilterMode(e.target.value as ContentFilterMode)}>
+                      <option value="reject">{t('admin.settings.contentFilterReject')}</option>
+                      <option value="mask">{t('admin.settings.contentFilterMask')}</option>
+                      <option value="flag">{t('admin.settings.contentFilterFlag')}</option>
+                    </select>
+                  </div>
+                  <div className="settings-field settings-field-wide">
+                    <label>{t('admin.settings.contentFilterTerms')}</label>
+                    <textarea rows={8} value={filterTerms} onChange={(e) => setFilterTerms(e.target.value)} />
+                  </div>
+                  <button
+                    className="btn-primary settings-save-btn"
+                    onClick={handleSaveContentFilter}
+                    disabled={filterSaving}
+                  >
+                    {filterSaving ? t('admin.settings.saving') : t('admin.settings.save')}
+                  </button>
+                </div>
+              )}
             </div>
           )}
         </div>
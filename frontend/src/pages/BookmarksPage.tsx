@@ -86,9 +86,14 @@ export default function BookmarksPage() {
               <div
                 key={route.id}
                 className="bookmarks-card"
-                onClick={() => navigate(`/shared/${route.share_token}`)}
+                onClick={() => navigate(`/shared/${route.link_token}`)}
               >
                 <h3 className="bookmarks-card-name">{route.name}</h3>
+                {(route.start_location || route.end_location) && (
+                  <div className="bookmarks-card-location">
+                    {route.start_location} &#8594; {route.end_location}
+                  </div>
+                )}
                 <div className="bookmarks-card-meta">
                   <span>{t('bookmarks.pointsCount', { count: route.points_count })}</span>
                   <span className="bookmarks-card-date">{formatDate(route.created_at)}</span>
@@ -100,6 +105,9 @@ export default function BookmarksPage() {
                       &#9733; {route.avg_rating.toFixed(1)} ({route.ratings_count})
                     </span>
                   )}
+                  {route.comments_count > 0 && (
+                    <span className="bookmarks-card-comments">&#128172; {route.comments_count}</span>
+                  )}
                 </div>
                 {route.category_ids.length > 0 && (
                   <div className="route-tags">
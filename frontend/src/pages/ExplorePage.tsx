@@ -3,8 +3,9 @@ import { useNavigate } from 'react-router-dom';
 import { useLanguage } from '../context/LanguageContext';
 import { useTheme } from '../context/ThemeContext';
 import { routesApi } from '../api/routes';
-import type { ExploreRoute } from '../api/routes';
+import type { ExploreFacets, ExploreRoute } from '../api/routes';
 import { categoriesApi, type Category } from '../api/categories';
+import { formatDistance, formatDuration } from '../utils/geo';
 import './ExplorePage.css';
 
 type SortOption = 'newest' | 'oldest' | 'popular' | 'top_rated';
@@ -18,6 +19,7 @@ export default function ExplorePage() {
 
   const [routes, setRoutes] = useState<ExploreRoute[]>([]);
   const [total, setTotal] = useState(0);
+  const [facets, setFacets] = useState<ExploreFacets | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState('');
   const [search, setSearch] = useState('');
@@ -25,6 +27,7 @@ export default function ExplorePage() {
   const [season, setSeason] = useState('');
   const [sort, setSort] = useState<SortOption>('newest');
   const [offset, setOffset] = useState(0);
+  const [saveMessage, setSaveMessage] = useState('');
 
   const getCurrentSeason = (): string => {
     const month = new Date().getMonth() + 1;
@@ -58,11 +61,12 @@ export default function ExplorePage() {
         offset: offsetValue,
       });
       if (append) {
-        setRoutes(prev => [...prev, ...data.routes]);
+        setRoutes(prev => [...prev, ...data.data]);
       } else {
-        setRoutes(data.routes);
+        setRoutes(data.data);
       }
-      setTotal(data.total);
+      setTotal(data.pagination.total);
+      setFacets(data.meta?.facets ?? null);
     } catch (err: any) {
       setError(err.response?.data || t('explore.loadFailed'));
     } finally {
@@ -103,6 +107,22 @@ export default function ExplorePage() {
 
   const hasMore = routes.length < total;
 
+  const handleSaveSearch = async () => {
+    const category = availableCategories.find((c) => c.id === categoryId);
+    const name = search.trim() || (category ? t(`tags.${category.name}` as any) || category.name : '');
+    try {
+      await routesApi.createSavedSearch({
+        name,
+        search: search.trim() || undefined,
+        category_id: categoryId || undefined,
+      });
+      setSaveMessage(t('explore.searchSaved'));
+    } catch (err: any) {
+      console.error('Failed to save search:', err);
+      setSaveMessage(err.response?.data || t('explore.saveSearchFailed'));
+    }
+  };
+
   return (
     <div className="explore-page">
       <header className="explore-header">
@@ -135,6 +155,7 @@ export default function ExplorePage() {
             {availableCategories.map((cat) => (
               <option key={cat.id} value={cat.id}>
                 {t(`tags.${cat.name}` as any) || cat.name}
+                {facets && ` (${facets.category_ids[cat.id] ?? 0})`}
               </option>
             ))}
           </select>
@@ -165,8 +186,25 @@ export default function ExplorePage() {
             <option value="popular">{t('explore.sortPopular')}</option>
             <option value="top_rated">{t('explore.sortTopRated')}</option>
           </select>
+          {(search.trim() || categoryId) && (
+            <button className="btn-secondary" onClick={handleSaveSearch}>
+              {t('explore.saveSearch')}
+            </button>
+          )}
         </div>
 
+        {saveMessage && <div className="explore-save-message">{saveMessage}</div>}
+
+        {facets && Object.keys(facets.difficulty).length > 0 && (
+          <div className="explore-facets">
+            {(['easy', 'moderate', 'hard'] as const).map((level) => (
+              <span key={level} className="explore-facet">
+                {t(`stats.difficulty.${level}`)}: {facets.difficulty[level] ?? 0}
+              </span>
+            ))}
+          </div>
+        )}
+
         {error && <div className="error-message">{error}</div>}
 
         {!initialLoad && routes.length === 0 && !loading && (
@@ -182,11 +220,27 @@ export default function ExplorePage() {
                 <div
                   key={route.id}
                   className="explore-card"
-                  onClick={() => navigate(`/shared/${route.share_token}`)}
+                  onClick={() => navigate(`/shared/${route.link_token}`)}
                 >
+                  {(route.cover_thumbnail_url || route.cover_url) && (
+                    <img
+                      className="explore-card-cover"
+                      src={route.cover_thumbnail_url || route.cover_url}
+                      alt={route.name}
+                      loading="lazy"
+                    />
+                  )}
                   <h3 className="explore-card-name">{route.name}</h3>
+                  {(route.start_location || route.end_location) && (
+                    <div className="explore-card-location">
+                      {route.start_location} &#8594; {route.end_location}
+                    </div>
+                  )}
                   <div className="explore-card-meta">
                     <span>{t('explore.pointsCount', { count: route.points_count })}</span>
+                    {route.stats.distance_km > 0 && (
+                      <span>{formatDistance(route.stats.distance_km)} · {formatDuration(route.stats.duration_min)}</span>
+                    )}
                     <span className="explore-card-date">{formatDate(route.created_at)}</span>
                   </div>
                   <div className="explore-card-stats">
@@ -198,6 +252,9 @@ export default function ExplorePage() {
                         &#9733; {route.avg_rating.toFixed(1)} ({route.ratings_count})
                       </span>
                     )}
+                    {route.comments_count > 0 && (
+                      <span className="explore-card-comments">&#128172; {route.comments_count}</span>
+                    )}
                   </div>
                   {route.category_ids.length > 0 && (
                     <div className="route-tags">
@@ -308,10 +308,12 @@ const PointPopup = React.memo(function PointPopup({
   point,
   index,
   onPhotoChange,
+  onSetCover,
 }: {
   point: RoutePoint;
   index: number;
   onPhotoChange: (pointId: number, photo: PhotoData | undefined) => void;
+  onSetCover?: (index: number) => void;
 }) {
   const fileInputRef = useRef<HTMLInputElement>(null);
   const { t } = useLanguage();
@@ -363,13 +365,22 @@ const PointPopup = React.memo(function PointPopup({
           >
             {t("map.removePhoto")}
           </button>
+          {onSetCover && point.photo?.status === "done" && (
+            <button
+              type="button"
+              onClick={() => onSetCover(index)}
+              className="upload-photo-btn"
+            >
+              {t("map.setCover")}
+            </button>
+          )}
         </div>
       )}
       <div className="point-popup-actions">
         <input
           ref={fileInputRef}
           type="file"
-          accept="image/*"
+          accept="image/jpeg,image/png,image/webp"
           onChange={handleFileSelect}
           style={{ display: "none" }}
           id={`photo-input-${point.id}`}
@@ -388,6 +399,8 @@ export function MapPage() {
   const [routeMode, setRouteMode] = useState<RouteMode>("auto");
   const [tileProvider, setTileProvider] = useState(() => localStorage.getItem("tileProvider") || "yandex");
   const [showSaveModal, setShowSaveModal] = useState(false);
+  // When the save form was opened; the server uses it to spot bot submissions
+  const saveModalOpenedAt = useRef(0);
   const [routeName, setRouteName] = useState("");
   const [selectedCategoryIds, setSelectedCategoryIds] = useState<string[]>([]);
   const [selectedSeasons, setSelectedSeasons] = useState<string[]>([]);
@@ -454,6 +467,8 @@ export function MapPage() {
 
   // Real-time photo processing notifications via WebSocket
   const hasPendingPhotos = routePoints.some(p => p.photo?.status === 'pending');
+  const isRouteOwner = !!loadedRouteInfo && loadedRouteInfo.user_id === user?.id;
+  const canRetryPhotos = isRouteOwner && routePoints.some(p => p.photo?.status === 'failed');
 
   usePhotoNotifications({
     routeId: loadedRouteInfo?.id ?? '',
@@ -555,6 +570,34 @@ export function MapPage() {
     }
   };
 
+  const handleRetryFailedPhotos = async () => {
+    if (!loadedRouteInfo) return;
+    try {
+      const { point_indices } = await routesApi.retryFailedPhotos(loadedRouteInfo.id);
+      // Marking them pending re-subscribes to photo updates.
+      setRoutePoints(prev => prev.map((point, i) =>
+        point_indices.includes(i) && point.photo
+          ? { ...point, photo: { ...point.photo, status: 'pending' } }
+          : point,
+      ));
+      toast.success(t("map.retryPhotosQueued"));
+    } catch (err: any) {
+      toast.error(err.response?.data || t("map.retryPhotosFailed"));
+      console.error("Failed to retry photos:", err);
+    }
+  };
+
+  const handleSetCover = React.useCallback(async (index: number) => {
+    if (!loadedRouteInfo) return;
+    try {
+      await routesApi.setCover(loadedRouteInfo.id, index);
+      toast.success(t("map.coverSet"));
+    } catch (err: any) {
+      toast.error(err.response?.data || t("map.coverFailed"));
+      console.error("Failed to set route cover:", err);
+    }
+  }, [loadedRouteInfo, t]);
+
   const handleSaveAiDescription = async () => {
     if (!loadedRouteInfo) return;
     setAiSaving(true);
@@ -571,6 +614,11 @@ export function MapPage() {
     }
   };
 
+  const openSaveModal = () => {
+    saveModalOpenedAt.current = Date.now();
+    setShowSaveModal(true);
+  };
+
   const handleSaveRoute = async () => {
     if (!routeName.trim()) {
       setSaveError(t("map.pleaseEnterRouteName"));
@@ -604,6 +652,7 @@ export function MapPage() {
         points: pointsToSave,
         category_ids: selectedCategoryIds,
         seasons: selectedSeasons,
+        form_elapsed_ms: Date.now() - saveModalOpenedAt.current,
       });
       setShowSaveModal(false);
       setRouteName("");
@@ -894,12 +943,12 @@ export function MapPage() {
             type="file"
             ref={photoImportRef}
             multiple
-            accept="image/*"
+            accept="image/jpeg,image/png,image/webp"
             onChange={handleImportPhotos}
             style={{ display: "none" }}
           />
           {routePoints.length >= 2 && !loadedRouteInfo && (
-            <button onClick={() => setShowSaveModal(true)} className="save-btn">
+            <button onClick={openSaveModal} className="save-btn">
               {t("map.saveRoute")}
             </button>
           )}
@@ -924,6 +973,11 @@ export function MapPage() {
               >
                 {aiGenerating ? t("ai.generating") : t("ai.generateButton")}
               </button>
+              {canRetryPhotos && (
+                <button onClick={handleRetryFailedPhotos} className="btn-secondary">
+                  {t("map.retryPhotos")}
+                </button>
+              )}
             </>
           )}
           {routePoints.length >= 2 && (
@@ -973,7 +1027,7 @@ export function MapPage() {
             {t("map.importPhotos")}
           </button>
           {routePoints.length >= 2 && !loadedRouteInfo && (
-            <button onClick={() => setShowSaveModal(true)} className="save-btn">
+            <button onClick={openSaveModal} className="save-btn">
               {t("map.saveRoute")}
             </button>
           )}
@@ -998,6 +1052,11 @@ export function MapPage() {
               >
                 {aiGenerating ? t("ai.generating") : t("ai.generateButton")}
               </button>
+              {canRetryPhotos && (
+                <button onClick={handleRetryFailedPhotos} className="btn-secondary">
+                  {t("map.retryPhotos")}
+                </button>
+              )}
             </>
           )}
           {routePoints.length >= 2 && (
@@ -1207,6 +1266,7 @@ export function MapPage() {
                 point={point}
                 index={index}
                 onPhotoChange={handlePhotoChange}
+                onSetCover={isRouteOwner ? handleSetCover : undefined}
               />
             </Popup>
           </Marker>
@@ -1286,6 +1346,8 @@ export function MapPage() {
         onClose={() => setChatOpen(false)}
         onShowPoints={handleChatShowPoints}
         onShowRoutes={handleChatShowRoutes}
+        routeId={loadedRouteInfo && loadedRouteInfo.user_id === user?.id ? loadedRouteInfo.id : undefined}
+        onRouteUpdated={(id) => { if (id === loadedRouteInfo?.id) loadRoute(id); }}
       />
       {showConfirmClear && (
         <ConfirmDialog
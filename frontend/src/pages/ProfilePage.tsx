@@ -8,7 +8,7 @@ import type { Locale } from '../i18n';
 import { profileApi } from '../api/profile';
 import { routesApi } from '../api/routes';
 import type { Route, RoutePoint } from '../api/routes';
-import { totalDistance, formatDistance } from '../utils/geo';
+import { formatDistance } from '../utils/geo';
 import { exportAsGpx, exportAsKml } from '../utils/exportRoute';
 import { NotificationBell } from '../components/NotificationBell';
 import { ConfirmDialog } from '../components/ConfirmDialog';
@@ -21,6 +21,8 @@ import './ProfilePage.css';
 
 type TabType = 'profile' | 'security' | 'routes';
 
+const ROUTES_PAGE_SIZE = 50;
+
 const CATEGORY_COLORS: Record<string, string> = {
   cycling: '#3b82f6',
   hiking: '#22c55e',
@@ -119,6 +121,8 @@ export default function ProfilePage() {
   // Routes state
   const [routes, setRoutes] = useState<Route[]>([]);
   const [routesLoading, setRoutesLoading] = useState(false);
+  const [routesTotal, setRoutesTotal] = useState(0);
+  const [routesSearch, setRoutesSearch] = useState('');
   const [routesError, setRoutesError] = useState('');
   const [importLoading, setImportLoading] = useState(false);
   const [selectedRouteIds, setSelectedRouteIds] = useState<Set<string>>(new Set());
@@ -127,6 +131,10 @@ export default function ProfilePage() {
   const [ratingAggregates, setRatingAggregates] = useState<Record<string, { average: number; count: number }>>({});
   const [categoryMap, setCategoryMap] = useState<Record<string, string>>({});
   const [confirmDeleteRouteId, setConfirmDeleteRouteId] = useState<string | null>(null);
+  const [duplicateImportFile, setDuplicateImportFile] = useState<File | null>(null);
+  const [importSummary, setImportSummary] = useState('');
+  const [importUrl, setImportUrl] = useState('');
+  const [duplicateImportUrl, setDuplicateImportUrl] = useState<string | null>(null);
   const fileInputRef = useRef<HTMLInputElement>(null);
 
   useEffect(() => {
@@ -142,15 +150,17 @@ export default function ProfilePage() {
     }
   }, [activeTab]);
 
-  const loadRoutes = async () => {
+  const loadRoutes = async (offset = 0) => {
     setRoutesLoading(true);
     setRoutesError('');
     try {
-      const [data, categories] = await Promise.all([
-        routesApi.getRoutes(),
+      const [page, categories] = await Promise.all([
+        routesApi.getRoutes({ limit: ROUTES_PAGE_SIZE, offset, search: routesSearch.trim() || undefined }),
         categoriesApi.getCategories().catch(() => [] as Category[]),
       ]);
-      setRoutes(data);
+      const data = page.data;
+      setRoutes((prev) => (offset === 0 ? data : [...prev, ...data]));
+      setRoutesTotal(page.pagination.total);
       const map: Record<string, string> = {};
       categories.forEach((c) => { map[c.id] = c.name; });
       setCategoryMap(map);
@@ -178,9 +188,9 @@ export default function ProfilePage() {
           : { average: 0, count: 0 };
       });
 
-      setCommentCounts(counts);
-      setLikeCounts(likes);
-      setRatingAggregates(ratings);
+      setCommentCounts((prev) => ({ ...prev, ...counts }));
+      setLikeCounts((prev) => ({ ...prev, ...likes }));
+      setRatingAggregates((prev) => ({ ...prev, ...ratings }));
     } catch (err: any) {
       setRoutesError(err.response?.data || t('profile.loadRoutesFailed'));
     } finally {
@@ -289,16 +299,43 @@ export default function ProfilePage() {
     }
   };
 
-  const handleImportGeoJson = async (e: React.ChangeEvent<HTMLInputElement>) => {
-    const file = e.target.files?.[0];
-    if (!file) return;
-
+  const importGeoJson = async (file: File, force: boolean) => {
     setImportLoading(true);
     setRoutesError('');
 
     try {
-      const importedRoute = await routesApi.importFromGeoJson(file);
+      const importedRoute = await routesApi.importFromGeoJson(file, force);
       setRoutes([importedRoute, ...routes]);
+    } catch (err: any) {
+      if (err.response?.status === 409) {
+        setDuplicateImportFile(file);
+      } else {
+        setRoutesError(err.response?.data || t('profile.importFailed'));
+      }
+    } finally {
+      setImportLoading(false);
+      if (fileInputRef.current) {
+        fileInputRef.current.value = '';
+      }
+    }
+  };
+
+  const importBatch = async (files: File[]) => {
+    setImportLoading(true);
+    setRoutesError('');
+    setImportSummary('');
+
+    try {
+      const report = await routesApi.importFiles(files);
+      const created = report.files.flatMap((f) => (f.route ? [f.route] : []));
+      setRoutes([...created, ...routes]);
+      setImportSummary(t('profile.importBatchSummary', { created: report.created, failed: report.failed }));
+      const failures = report.files
+        .filter((f) => f.status !== 'created')
+        .map((f) => `${f.filename ?? t('profile.importUnnamedFile')}: ${f.error ?? ''}`);
+      if (failures.length > 0) {
+        setRoutesError(failures.join('\n'));
+      }
     } catch (err: any) {
       setRoutesError(err.response?.data || t('profile.importFailed'));
     } finally {
@@ -309,6 +346,57 @@ export default function ProfilePage() {
     }
   };
 
+  const handleImportGeoJson = async (e: React.ChangeEvent<HTMLInputElement>) => {
+    const files = Array.from(e.target.files ?? []);
+    if (files.length === 0) return;
+    if (files.length > 1) {
+      await importBatch(files);
+      return;
+    }
+    setImportSummary('');
+    await importGeoJson(files[0], false);
+  };
+
+  const importFromUrl = async (url: string, force: boolean) => {
+    setImportLoading(true);
+    setRoutesError('');
+    setImportSummary('');
+
+    try {
+      const importedRoute = await routesApi.importFromUrl(url, force);
+      setRoutes([importedRoute, ...routes]);
+      setImportUrl('');
+    } catch (err: any) {
+      if (err.response?.status === 409) {
+        setDuplicateImportUrl(url);
+      } else {
+        setRoutesError(err.response?.data || t('profile.importFailed'));
+      }
+    } finally {
+      setImportLoading(false);
+    }
+  };
+
+  const handleImportFromUrl = async (e: React.FormEvent) => {
+    e.preventDefault();
+    const url = importUrl.trim();
+    if (!url) return;
+    await importFromUrl(url, false);
+  };
+
+  const handleConfirmDuplicateImport = async () => {
+    if (duplicateImportUrl) {
+      const url = duplicateImportUrl;
+      setDuplicateImportUrl(null);
+      await importFromUrl(url, true);
+      return;
+    }
+    if (!duplicateImportFile) return;
+    const file = duplicateImportFile;
+    setDuplicateImportFile(null);
+    await importGeoJson(file, true);
+  };
+
   const toggleRouteSelection = (id: string) => {
     setSelectedRouteIds((prev) => {
       const next = new Set(prev);
@@ -511,7 +599,8 @@ export default function ProfilePage() {
                   <input
                     type="file"
                     ref={fileInputRef}
-                    accept=".geojson,.json"
+                    accept=".geojson,.json,.gpx"
+                    multiple
                     onChange={handleImportGeoJson}
                     style={{ display: 'none' }}
                   />
@@ -525,7 +614,38 @@ export default function ProfilePage() {
                 </div>
               </div>
 
+              <form className="import-url-form" onSubmit={handleImportFromUrl}>
+                <input
+                  type="url"
+                  value={importUrl}
+                  onChange={(e) => setImportUrl(e.target.value)}
+                  placeholder={t('profile.importUrlPlaceholder')}
+                />
+                <button type="submit" className="btn-secondary" disabled={importLoading || !importUrl.trim()}>
+                  {t('profile.importFromUrl')}
+                </button>
+              </form>
+
+              <form
+                className="import-url-form"
+                onSubmit={(e) => {
+                  e.preventDefault();
+                  loadRoutes(0);
+                }}
+              >
+                <input
+                  type="search"
+                  value={routesSearch}
+                  onChange={(e) => setRoutesSearch(e.target.value)}
+                  placeholder={t('profile.searchRoutesPlaceholder')}
+                />
+                <button type="submit" className="btn-secondary" disabled={routesLoading}>
+                  {t('profile.searchRoutes')}
+                </button>
+              </form>
+
               {routesLoading && <div className="loading">{t('profile.loadingRoutes')}</div>}
+              {importSummary && <div className="success-message">{importSummary}</div>}
               {routesError && <div className="error-message">{routesError}</div>}
 
               {!routesLoading && routes.length === 0 && (
@@ -568,7 +688,7 @@ export default function ProfilePage() {
                             <div className="route-card-stats">
                               <span className="route-stat"><MapPin size={15} color={accentColor} />{route.points.length}</span>
                               {route.points.length >= 2 && (
-                                <span className="route-stat"><ArrowLeftRight size={15} color="#60a5fa" />{formatDistance(totalDistance(route.points))}</span>
+                                <span className="route-stat"><ArrowLeftRight size={15} color="#60a5fa" />{formatDistance(route.stats.distance_km)}</span>
                               )}
                               {commentCounts[route.id] != null && (
                                 <span className="route-stat"><MessageCircle size={15} color="#a78bfa" />{commentCounts[route.id]}</span>
@@ -639,6 +759,18 @@ export default function ProfilePage() {
                   })}
                 </div>
               )}
+
+              {routes.length < routesTotal && (
+                <div className="routes-load-more">
+                  <button
+                    onClick={() => loadRoutes(routes.length)}
+                    disabled={routesLoading}
+                    className="btn-primary"
+                  >
+                    {routesLoading ? t('common.loading') : t('explore.loadMore')}
+                  </button>
+                </div>
+              )}
             </div>
           )}
         </div>
@@ -654,6 +786,19 @@ export default function ProfilePage() {
         onCancel={() => setConfirmDeleteRouteId(null)}
       />
     )}
+
+    {(duplicateImportFile || duplicateImportUrl) && (
+      <ConfirmDialog
+        message={t('profile.importDuplicate')}
+        confirmLabel={t('profile.importAnyway')}
+        cancelLabel={t('map.cancel')}
+        onConfirm={handleConfirmDuplicateImport}
+        onCancel={() => {
+          setDuplicateImportFile(null);
+          setDuplicateImportUrl(null);
+        }}
+      />
+    )}
     </>
   );
 }
@@ -0,0 +1,46 @@
+import { useEffect, useState } from 'react';
+import ReactMarkdown from 'react-markdown';
+import { useParams } from 'react-router-dom';
+import { chatApi, type SharedChatMessage } from '../api/chat';
+import { useLanguage } from '../context/LanguageContext';
+import '../components/ChatPanel.css';
+
+/** Read-only view of a conversation opened through a share link. */
+export function SharedChatPage() {
+  const { token } = useParams<{ token: string }>();
+  const { t } = useLanguage();
+  const [messages, setMessages] = useState<SharedChatMessage[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState('');
+
+  useEffect(() => {
+    if (!token) return;
+    chatApi.getSharedConversation(token)
+      .then(setMessages)
+      .catch(() => setError(t('chat.sharedNotFound')))
+      .finally(() => setLoading(false));
+  }, [token, t]);
+
+  return (
+    <section className="chat-panel chat-panel-shared" aria-label={t('chat.sharedTitle')}>
+      <div className="chat-panel-header">
+        <h3>{t('chat.sharedTitle')}</h3>
+      </div>
+      <div className="chat-messages" role="log" aria-label="Messages">
+        {loading && <div className="chat-typing"><span className="chat-typing-dot" /><span className="chat-typing-dot" /><span className="chat-typing-dot" /></div>}
+        {error && <div className="chat-empty">{error}</div>}
+        {messages.map((msg, i) => (
+          <div key={i} className={`chat-message ${msg.role}`}>
+            {msg.role === 'assistant' ? (
+              <div className="chat-message-markdown">
+                <ReactMarkdown>{msg.content}</ReactMarkdown>
+              </div>
+            ) : (
+              <div>{msg.content}</div>
+            )}
+          </div>
+        ))}
+      </div>
+    </section>
+  );
+}